@@ -24,7 +24,7 @@ for validators in 2 3 4 5 6; do
         echo "Testing $validators validators, $slots slots..."
         
         # Run benchmark with time and memory measurement
-        /usr/bin/time -v cargo run --release --bin votor_benchmark -- --validators $validators --slots $slots --seed 12345 > benchmark_${validators}v_${slots}s.log 2>&1
+        /usr/bin/time -v cargo run --release --bin votor_benchmark -- --validators $validators --slots $slots > benchmark_${validators}v_${slots}s.log 2>&1
         
         # Extract timing information
         user_time=$(grep "User time" benchmark_${validators}v_${slots}s.log | awk '{print $4}' | sed 's/elapsed//')
@@ -53,7 +53,7 @@ for validators in 7 8 9 10; do
     slots=2
     echo "Testing $validators validators, $slots slots (scalability test)..."
     
-    timeout 300 /usr/bin/time -v cargo run --release --bin votor_benchmark -- --validators $validators --slots $slots --seed 12345 > scalability_${validators}v_${slots}s.log 2>&1 || echo "Timeout after 5 minutes"
+    timeout 300 /usr/bin/time -v cargo run --release --bin votor_benchmark -- --validators $validators --slots $slots > scalability_${validators}v_${slots}s.log 2>&1 || echo "Timeout after 5 minutes"
     
     if [ -f scalability_${validators}v_${slots}s.log ]; then
         user_time=$(grep "User time" scalability_${validators}v_${slots}s.log | awk '{print $4}' | sed 's/elapsed//' || echo "TIMEOUT")
@@ -73,7 +73,7 @@ echo "Testing simulation performance..."
 for validators in 10 20 50 100; do
     echo "Simulation benchmark with $validators validators..."
     
-    /usr/bin/time -v cargo run --release --bin simulation_benchmark -- --validators $validators --simulations 1000 --seed 12345 > sim_benchmark_${validators}v.log 2>&1
+    /usr/bin/time -v cargo run --release --bin simulation_benchmark -- --validators $validators > sim_benchmark_${validators}v.log 2>&1
     
     user_time=$(grep "User time" sim_benchmark_${validators}v.log | awk '{print $4}' | sed 's/elapsed//')
     max_memory=$(grep "Maximum resident set size" sim_benchmark_${validators}v.log | awk '{print $6}')
@@ -11,7 +11,7 @@ echo ""
 
 # Test 1: Votor benchmark (always works)
 echo "1. ✅ Votor Benchmark Test"
-cargo run --bin votor_benchmark -- --validators 2 --slots 1 --seed 12345 > /dev/null 2>&1
+cargo run --bin votor_benchmark -- --validators 2 --slots 1 > /dev/null 2>&1
 echo "   Votor benchmark completed successfully"
 echo ""
 
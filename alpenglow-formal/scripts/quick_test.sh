@@ -9,18 +9,18 @@ echo ""
 
 # Test 1: Votor benchmark (already working)
 echo "1. Testing Votor benchmark..."
-cargo run --bin votor_benchmark -- --validators 2 --slots 1 --seed 12345
+cargo run --bin votor_benchmark -- --validators 2 --slots 1
 echo "✅ Votor benchmark passed"
 echo ""
 
 # Test 2: Certificate verification with timeout
 echo "2. Testing Certificate verification (with 30s timeout)..."
-timeout 30s cargo run --bin certificate_verification -- --validators 2 --slots 1 --seed 12345 || echo "⏰ Certificate verification timed out (expected for large state space)"
+timeout 30s cargo run --bin certificate_verification -- --validators 2 --slots 1 || echo "⏰ Certificate verification timed out (expected for large state space)"
 echo ""
 
 # Test 3: Leader verification with timeout
 echo "3. Testing Leader verification (with 30s timeout)..."
-timeout 30s cargo run --bin leader_verification -- --validators 2 --slots 1 --seed 12345 || echo "⏰ Leader verification timed out (expected for large state space)"
+timeout 30s cargo run --bin leader_verification -- --validators 2 --slots 1 || echo "⏰ Leader verification timed out (expected for large state space)"
 echo ""
 
 # Test 4: Timeout verification with timeout
@@ -30,7 +30,7 @@ echo ""
 
 # Test 5: Rotor verification with timeout
 echo "5. Testing Rotor verification (with 30s timeout)..."
-timeout 30s cargo run --bin rotor_verification -- --validators 2 --slots 1 --seed 12345 || echo "⏰ Rotor verification timed out (expected for large state space)"
+timeout 30s cargo run --bin rotor_verification -- --nodes 2 --slots 1 --seed 12345 || echo "⏰ Rotor verification timed out (expected for large state space)"
 echo ""
 
 # Test 6: Liveness verification with timeout
@@ -23,7 +23,7 @@ for stake_pct in 80 85 90 95 100; do
     echo "Testing fast path with $stake_pct% stake"
     
     for i in $(seq 1 100); do
-        cargo run --release --bin bounded_finalization_test -- --path fast --stake-percent $stake_pct --seed $i > fast_${stake_pct}pct_${i}.log 2>&1
+        cargo run --release --bin bounded_finalization_test -- --path fast --stake-percent $stake_pct --seed $i > fast_${stake_pct}pct_${i}.log 2>&1 || true
         
         if grep -q "Fast path finalization successful" fast_${stake_pct}pct_${i}.log; then
             finalization_time=$(grep "Finalization time" fast_${stake_pct}pct_${i}.log | awk '{print $3}' || echo "1")
@@ -41,7 +41,7 @@ for stake_pct in 60 65 70 75 80; do
     echo "Testing slow path with $stake_pct% stake"
     
     for i in $(seq 1 100); do
-        cargo run --release --bin bounded_finalization_test -- --path slow --stake-percent $stake_pct --seed $i > slow_${stake_pct}pct_${i}.log 2>&1
+        cargo run --release --bin bounded_finalization_test -- --path slow --stake-percent $stake_pct --seed $i > slow_${stake_pct}pct_${i}.log 2>&1 || true
         
         if grep -q "Slow path finalization successful" slow_${stake_pct}pct_${i}.log; then
             finalization_time=$(grep "Finalization time" slow_${stake_pct}pct_${i}.log | awk '{print $3}' || echo "2")
@@ -56,7 +56,7 @@ done
 echo "Test 3: Bounded finalization time verification"
 
 for i in $(seq 1 50); do
-    cargo run --release --bin bounded_finalization_test -- --test-type bounded --seed $i > bounded_${i}.log 2>&1
+    cargo run --release --bin bounded_finalization_test -- --test-type bounded --path both --seed $i > bounded_${i}.log 2>&1 || true
     
     if grep -q "Bounded finalization time verified" bounded_${i}.log; then
         fast_time=$(grep "Fast path time" bounded_${i}.log | awk '{print $4}' || echo "1")
@@ -72,10 +72,10 @@ done
 echo "Test 4: Network delay impact"
 
 for delay_ms in 10 20 30 40 50; do
-    echo "Testing with $delay_ms ms network delay"
+    echo "Testing with $delay_ms ticks of network delay"
     
     for i in $(seq 1 30); do
-        cargo run --release --bin bounded_finalization_test -- --test-type network_delay --delay $delay_ms --seed $i > delay_${delay_ms}ms_${i}.log 2>&1
+        cargo run --release --bin bounded_finalization_test -- --test-type network_delay --delay $delay_ms --seed $i > delay_${delay_ms}ms_${i}.log 2>&1 || true
         
         if grep -q "Network delay handling successful" delay_${delay_ms}ms_${i}.log; then
             finalization_time=$(grep "Finalization time" delay_${delay_ms}ms_${i}.log | awk '{print $3}' || echo "1")
@@ -90,7 +90,7 @@ done
 echo "Test 5: Concurrent finalization"
 
 for i in $(seq 1 30); do
-    cargo run --release --bin bounded_finalization_test -- --test-type concurrent --seed $i > concurrent_${i}.log 2>&1
+    cargo run --release --bin bounded_finalization_test -- --test-type concurrent --seed $i > concurrent_${i}.log 2>&1 || true
     
     if grep -q "Concurrent finalization successful" concurrent_${i}.log; then
         finalization_time=$(grep "Finalization time" concurrent_${i}.log | awk '{print $3}' || echo "1")
@@ -107,7 +107,7 @@ for offline_percent in 10 20 30 40; do
     echo "Testing with $offline_percent% offline nodes"
     
     for i in $(seq 1 30); do
-        cargo run --release --bin bounded_finalization_test -- --test-type partial_network --offline-percent $offline_percent --seed $i > partial_${offline_percent}pct_${i}.log 2>&1
+        cargo run --release --bin bounded_finalization_test -- --test-type partial_network --offline-percent $offline_percent --seed $i > partial_${offline_percent}pct_${i}.log 2>&1 || true
         
         if grep -q "Partial network finalization successful" partial_${offline_percent}pct_${i}.log; then
             finalization_time=$(grep "Finalization time" partial_${offline_percent}pct_${i}.log | awk '{print $3}' || echo "1")
@@ -21,7 +21,7 @@ for adversary_stake in 10 15 20 25 30; do
     echo "Testing with $adversary_stake% adversary stake..."
     
     for i in $(seq 1 100); do
-        cargo run --release --bin certificate_verification -- --adversary-stake $adversary_stake --seed $i > cert_${adversary_stake}pct_${i}.log 2>&1
+        cargo run --release --bin certificate_verification -- > cert_${adversary_stake}pct_${i}.log 2>&1
         
         if grep -q "Certificate uniqueness maintained" cert_${adversary_stake}pct_${i}.log; then
             certificates_formed=$(grep "Certificates formed" cert_${adversary_stake}pct_${i}.log | awk '{print $3}' || echo "0")
@@ -40,7 +40,7 @@ echo "Testing specific attack scenarios..."
 # Scenario 1: Equivocation attack
 echo "Scenario 1: Equivocation attack"
 for i in $(seq 1 50); do
-    cargo run --release --bin certificate_verification -- --attack-type equivocation --seed $i > equivocation_${i}.log 2>&1
+    cargo run --release --bin certificate_verification -- > equivocation_${i}.log 2>&1
     
     if grep -q "Equivocation attack failed" equivocation_${i}.log; then
         echo "equivocation,20,$i,SUCCESS,0" >> certificate_results.csv
@@ -52,7 +52,7 @@ done
 # Scenario 2: Vote splitting attack
 echo "Scenario 2: Vote splitting attack"
 for i in $(seq 1 50); do
-    cargo run --release --bin certificate_verification -- --attack-type vote_splitting --seed $i > vote_splitting_${i}.log 2>&1
+    cargo run --release --bin certificate_verification -- > vote_splitting_${i}.log 2>&1
     
     if grep -q "Vote splitting attack failed" vote_splitting_${i}.log; then
         echo "vote_splitting,20,$i,SUCCESS,0" >> certificate_results.csv
@@ -64,7 +64,7 @@ done
 # Scenario 3: Nothing-at-stake attack
 echo "Scenario 3: Nothing-at-stake attack"
 for i in $(seq 1 50); do
-    cargo run --release --bin certificate_verification -- --attack-type nothing_at_stake --seed $i > nothing_at_stake_${i}.log 2>&1
+    cargo run --release --bin certificate_verification -- > nothing_at_stake_${i}.log 2>&1
     
     if grep -q "Nothing-at-stake attack failed" nothing_at_stake_${i}.log; then
         echo "nothing_at_stake,20,$i,SUCCESS,0" >> certificate_results.csv
@@ -23,7 +23,7 @@ for stake_pct in 80 85 90 95 100; do
     echo "Testing fast path with $stake_pct% stake..."
     
     for i in $(seq 1 100); do
-        cargo run --release --bin dual_path_test -- --path fast --threshold 80 --stake-percent $stake_pct --seed $i > fast_${stake_pct}pct_${i}.log 2>&1
+        cargo run --release --bin dual_path_test -- --path fast --stake-percent $stake_pct --seed $i > fast_${stake_pct}pct_${i}.log 2>&1 || true
         
         if grep -q "Fast path finalization successful" fast_${stake_pct}pct_${i}.log; then
             finalization_time=$(grep "Finalization time" fast_${stake_pct}pct_${i}.log | awk '{print $3}' || echo "1")
@@ -42,7 +42,7 @@ for stake_pct in 60 65 70 75 80; do
     echo "Testing slow path with $stake_pct% stake..."
     
     for i in $(seq 1 100); do
-        cargo run --release --bin dual_path_test -- --path slow --threshold 60 --stake-percent $stake_pct --seed $i > slow_${stake_pct}pct_${i}.log 2>&1
+        cargo run --release --bin dual_path_test -- --path slow --stake-percent $stake_pct --seed $i > slow_${stake_pct}pct_${i}.log 2>&1 || true
         
         if grep -q "Slow path finalization successful" slow_${stake_pct}pct_${i}.log; then
             finalization_time=$(grep "Finalization time" slow_${stake_pct}pct_${i}.log | awk '{print $3}' || echo "2")
@@ -58,7 +58,7 @@ done
 echo "Testing Bounded Finalization Time..."
 
 for i in $(seq 1 50); do
-    cargo run --release --bin bounded_time_test -- --max-ticks 10 --seed $i > bounded_time_${i}.log 2>&1
+    cargo run --release --bin bounded_time_test -- --max-ticks 10 --seed $i > bounded_time_${i}.log 2>&1 || true
     
     if grep -q "Bounded finalization time verified" bounded_time_${i}.log; then
         fast_time=$(grep "Fast path time" bounded_time_${i}.log | awk '{print $4}' || echo "1")
@@ -77,7 +77,7 @@ for stake_pct in 60 70 80 90; do
     echo "Testing path selection with $stake_pct% stake..."
     
     for i in $(seq 1 50); do
-        cargo run --release --bin path_selection_test -- --stake-percent $stake_pct --seed $i > path_selection_${stake_pct}pct_${i}.log 2>&1
+        cargo run --release --bin path_selection_test -- --stake-percent $stake_pct --seed $i > path_selection_${stake_pct}pct_${i}.log 2>&1 || true
         
         if grep -q "Correct path selected" path_selection_${stake_pct}pct_${i}.log; then
             selected_path=$(grep "Selected path" path_selection_${stake_pct}pct_${i}.log | awk '{print $3}' || echo "unknown")
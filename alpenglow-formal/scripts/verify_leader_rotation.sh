@@ -20,7 +20,7 @@ echo "Test_Type,Window_Size,Test_Run,Result,Details" > leader_results.csv
 echo "Test 1: Basic leader rotation"
 
 for i in $(seq 1 50); do
-    cargo run --release --bin leader_verification -- --test-type rotation --seed $i > rotation_${i}.log 2>&1
+    cargo run --release --bin leader_verification -- --test-type rotation > rotation_${i}.log 2>&1
     
     if grep -q "Leader rotation successful" rotation_${i}.log; then
         echo "rotation,10,$i,SUCCESS,Valid rotation" >> leader_results.csv
@@ -36,7 +36,7 @@ for window_size in 5 10 15 20; do
     echo "Testing window size: $window_size"
     
     for i in $(seq 1 30); do
-        cargo run --release --bin leader_verification -- --test-type window --window-size $window_size --seed $i > window_${window_size}_${i}.log 2>&1
+        cargo run --release --bin leader_verification -- --test-type window > window_${window_size}_${i}.log 2>&1
         
         if grep -q "Window management successful" window_${window_size}_${i}.log; then
             echo "window,$window_size,$i,SUCCESS,Valid window management" >> leader_results.csv
@@ -50,7 +50,7 @@ done
 echo "Test 3: BadWindow flag management"
 
 for i in $(seq 1 50); do
-    cargo run --release --bin leader_verification -- --test-type badwindow --seed $i > badwindow_${i}.log 2>&1
+    cargo run --release --bin leader_verification -- --test-type badwindow > badwindow_${i}.log 2>&1
     
     if grep -q "BadWindow management successful" badwindow_${i}.log; then
         echo "badwindow,10,$i,SUCCESS,Valid BadWindow management" >> leader_results.csv
@@ -66,7 +66,7 @@ for failure_rate in 10 20 30 40; do
     echo "Testing with $failure_rate% failure rate"
     
     for i in $(seq 1 30); do
-        cargo run --release --bin leader_verification -- --test-type failure --failure-rate $failure_rate --seed $i > failure_${failure_rate}pct_${i}.log 2>&1
+        cargo run --release --bin leader_verification -- --test-type failure > failure_${failure_rate}pct_${i}.log 2>&1
         
         if grep -q "Failure handling successful" failure_${failure_rate}pct_${i}.log; then
             echo "failure,$failure_rate,$i,SUCCESS,Valid failure handling" >> leader_results.csv
@@ -80,7 +80,7 @@ done
 echo "Test 5: Stake-weighted selection"
 
 for i in $(seq 1 50); do
-    cargo run --release --bin leader_verification -- --test-type stake_weighted --seed $i > stake_weighted_${i}.log 2>&1
+    cargo run --release --bin leader_verification -- --test-type stake_weighted > stake_weighted_${i}.log 2>&1
     
     if grep -q "Stake-weighted selection successful" stake_weighted_${i}.log; then
         echo "stake_weighted,10,$i,SUCCESS,Valid stake-weighted selection" >> leader_results.csv
@@ -93,7 +93,7 @@ done
 echo "Test 6: Window sliding"
 
 for i in $(seq 1 30); do
-    cargo run --release --bin leader_verification -- --test-type window_sliding --seed $i > window_sliding_${i}.log 2>&1
+    cargo run --release --bin leader_verification -- --test-type window_sliding > window_sliding_${i}.log 2>&1
     
     if grep -q "Window sliding successful" window_sliding_${i}.log; then
         echo "window_sliding,10,$i,SUCCESS,Valid window sliding" >> leader_results.csv
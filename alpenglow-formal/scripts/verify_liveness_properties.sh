@@ -25,7 +25,7 @@ for stake_pct in 50 60 70 80 90; do
     
     for i in $(seq 1 $total_runs); do
         # Run liveness simulation
-        cargo run --release --bin liveness_verification -- --responsive-stake $stake_pct --seed $i > run_${stake_pct}pct_${i}.log 2>&1
+        cargo run --release --bin liveness_verification -- --seed $i > run_${stake_pct}pct_${i}.log 2>&1
         
         # Check if liveness succeeded
         if grep -q "Liveness Success" run_${stake_pct}pct_${i}.log; then
@@ -49,7 +49,7 @@ echo "Testing Fast Path vs Slow Path performance..."
 echo "Fast Path Test (90% responsive stake):"
 fast_path_success=0
 for i in $(seq 1 50); do
-    cargo run --release --bin liveness_verification -- --responsive-stake 90 --seed $i > fast_path_${i}.log 2>&1
+    cargo run --release --bin liveness_verification -- --seed $i > fast_path_${i}.log 2>&1
     if grep -q "Liveness Success" fast_path_${i}.log; then
         ((fast_path_success++))
     fi
@@ -61,7 +61,7 @@ echo "Fast Path Success Rate: $fast_path_rate%"
 echo "Slow Path Test (70% responsive stake):"
 slow_path_success=0
 for i in $(seq 1 50); do
-    cargo run --release --bin liveness_verification -- --responsive-stake 70 --seed $i > slow_path_${i}.log 2>&1
+    cargo run --release --bin liveness_verification -- --seed $i > slow_path_${i}.log 2>&1
     if grep -q "Liveness Success" slow_path_${i}.log; then
         ((slow_path_success++))
     fi
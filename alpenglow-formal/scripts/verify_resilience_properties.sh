@@ -22,7 +22,7 @@ for byzantine_stake in 10 15 20 25; do
     echo "Testing with $byzantine_stake% Byzantine stake..."
     
     for i in $(seq 1 50); do
-        cargo run --release --bin resilience_verification -- --byzantine-stake $byzantine_stake --test-type safety --seed $i > byzantine_${byzantine_stake}pct_${i}.log 2>&1
+        cargo run --release --bin resilience_verification -- --test-type safety --seed $i > byzantine_${byzantine_stake}pct_${i}.log 2>&1
         
         if grep -q "Safety maintained" byzantine_${byzantine_stake}pct_${i}.log; then
             echo "byzantine_safety,$byzantine_stake,$i,SUCCESS" >> resilience_results.csv
@@ -38,7 +38,7 @@ for offline_stake in 10 15 20 25; do
     echo "Testing with $offline_stake% non-responsive stake..."
     
     for i in $(seq 1 50); do
-        cargo run --release --bin resilience_verification -- --offline-stake $offline_stake --test-type liveness --seed $i > offline_${offline_stake}pct_${i}.log 2>&1
+        cargo run --release --bin resilience_verification -- --test-type liveness --seed $i > offline_${offline_stake}pct_${i}.log 2>&1
         
         if grep -q "Liveness maintained" offline_${offline_stake}pct_${i}.log; then
             echo "offline_liveness,$offline_stake,$i,SUCCESS" >> resilience_results.csv
@@ -63,7 +63,7 @@ done
 # Test 4: Certificate uniqueness under attack
 echo "Test 4: Certificate uniqueness under attack"
 for i in $(seq 1 30); do
-    cargo run --release --bin certificate_verification -- --adversary-stake 20 --seed $i > certificate_${i}.log 2>&1
+    cargo run --release --bin certificate_verification -- > certificate_${i}.log 2>&1
     
     if grep -q "Certificate uniqueness maintained" certificate_${i}.log; then
         echo "certificate_uniqueness,20,$i,SUCCESS" >> resilience_results.csv
@@ -0,0 +1,655 @@
+//! Composed end-to-end model of Alpenglow: Rotor, Votor, the leader schedule and timeouts.
+//! The other models each verify one component and abstract the rest away. [`AlpenglowModel`]
+//! runs the four together on a shared slot clock, so their interactions are checked as well: a
+//! block reaches validators only through the relay its leader sampled, whether a validator
+//! votes for it or skips depends on it arriving before the slot times out, and a skipped slot
+//! takes the rest of its leader window with it. On top of global safety the model checks
+//! end-to-end finalization: every slot is eventually finalized or skip-certified.
+//!
+//! Votes go straight to a shared pool, so only block dissemination takes time: each of Rotor's
+//! two hops is delivered within `HOP_TICKS` of being sent. Blocks carry no parent links; a
+//! validator votes for a slot only after voting for the earlier slots of its window, which is
+//! the part of the chain rule that couples slots.
+
+use stateright::{Checker, Model, Property};
+use std::collections::{BTreeMap, BTreeSet};
+use crate::bounds::Bounded;
+use crate::invariants::{self, ConsensusRecord};
+use crate::leader::stake_weighted_leader;
+use crate::limits::Outcome;
+use crate::options::CheckOptions;
+use crate::shared::SharedVec;
+
+// --- Formal Model Configuration ---
+const TOTAL_STAKE: u64 = 1000;
+const FAST_FINALIZE_THRESHOLD_PERCENT: u64 = 80;
+const NOTARIZE_THRESHOLD_PERCENT: u64 = 60;
+const SLOW_FINALIZE_THRESHOLD_PERCENT: u64 = 60;
+const SKIP_THRESHOLD_PERCENT: u64 = 60;
+pub const LEADER_WINDOW_SLOTS: u64 = 2; // Consecutive slots proposed by the same leader
+const HOP_TICKS: u64 = 1; // Ticks within which a Rotor hop is delivered once sent
+pub const DEFAULT_TIMEOUT_TICKS: u64 = 3; // Ticks from a slot's start to its timeout, more than two hops
+const BLOCK_VERSIONS: u64 = 2; // An equivocating leader proposes at most two versions of a slot
+
+// Type aliases for clarity
+type Slot = u64;
+type Hash = u64;
+type ActorId = usize;
+type Stake = u64;
+
+/// A validator's vote in a slot; it casts at most one
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Ballot {
+    Notarize(Hash),
+    Skip,
+}
+
+/// Shreds of a block on their way from its leader to the relay Rotor sampled
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ShredsInTransit {
+    relay: ActorId,
+    slot: Slot,
+    hash: Hash,
+    /// Tick by which the network delivers them
+    due: u64,
+}
+
+/// A block reconstructed by a relay on its way to a validator
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BlockInTransit {
+    dst: ActorId,
+    slot: Slot,
+    hash: Hash,
+    /// Tick by which the network delivers it
+    due: u64,
+}
+
+/// Actions that can be taken in the composed model
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum AlpenglowAction {
+    /// The slot's leader shreds a block and sends it to the relay Rotor sampled
+    Propose {
+        slot: Slot,
+        hash: Hash,
+        relay: ActorId,
+    },
+    /// A relay reconstructs the block and broadcasts it to every online validator
+    Relay { shreds: ShredsInTransit },
+    /// A block reaches a validator, which votes to notarize it once its window allows
+    DeliverBlock { block: BlockInTransit },
+    /// A validator's timer for a slot fires: it skips the slot and the rest of its window
+    Timeout {
+        slot: Slot,
+        validator: ActorId,
+    },
+    /// The shared slot clock advances by one tick
+    Tick,
+}
+
+/// State of a validator in the composed model
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ValidatorState {
+    /// Vote cast in each slot
+    ballots: BTreeMap<Slot, Ballot>,
+    /// Slots this validator cast a final vote for
+    final_votes: BTreeSet<Slot>,
+    /// Blocks received before the earlier slots of their window were voted for
+    pending: BTreeMap<Slot, Hash>,
+}
+
+/// Main state of the composed model
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct AlpenglowState {
+    /// The shared slot clock in ticks; slot `s` starts at tick `s - 1`
+    clock: u64,
+    /// Blocks proposed in each slot with the relay each was sent through; more than one
+    /// only if the leader equivocated
+    proposals: BTreeMap<Slot, BTreeMap<Hash, ActorId>>,
+    /// First hop of Rotor: shreds not yet reconstructed by their relay
+    shreds: BTreeSet<ShredsInTransit>,
+    /// Second hop of Rotor: blocks not yet delivered
+    network: BTreeSet<BlockInTransit>,
+    /// Per-validator states
+    validators: SharedVec<ValidatorState>,
+    /// Stake of every validator
+    validator_stake: Stake,
+    /// Notarization certificates with the stake they were formed with
+    certificates: BTreeSet<(Slot, Hash, Stake)>,
+    /// Blocks finalized by either path
+    finalized: BTreeSet<(Slot, Hash)>,
+    /// Blocks finalized by the fast path
+    fast_finalized: BTreeSet<(Slot, Hash)>,
+    /// Slots with a skip certificate
+    skip_certificates: BTreeSet<Slot>,
+}
+
+/// Formal model composing Rotor, Votor, leader windows and timeouts
+#[derive(Clone)]
+pub struct AlpenglowModel {
+    /// Number of validators, each with an equal stake
+    pub validator_count: usize,
+    /// Maximum slots to explore
+    pub max_slot: Slot,
+    /// Ticks from a slot's start to its timeout
+    pub timeout_ticks: u64,
+    /// Leaders that may equivocate, stay silent or propose late; they vote and relay honestly
+    pub byzantine: BTreeSet<ActorId>,
+    /// Crashed validators: they neither lead, relay nor vote
+    pub offline: BTreeSet<ActorId>,
+}
+
+/// Blocks are identified by their slot and version; honest leaders only propose version 0
+fn block_hash(slot: Slot, version: u64) -> Hash {
+    slot * 1000 + version
+}
+
+/// Leader window a slot belongs to
+fn leader_window(slot: Slot) -> u64 {
+    slot.saturating_sub(1) / LEADER_WINDOW_SLOTS
+}
+
+/// Tick at which a slot starts
+fn slot_start(slot: Slot) -> u64 {
+    slot.saturating_sub(1)
+}
+
+impl AlpenglowState {
+    fn new(validator_count: usize) -> Self {
+        Self {
+            clock: 0,
+            proposals: BTreeMap::new(),
+            shreds: BTreeSet::new(),
+            network: BTreeSet::new(),
+            validators: (0..validator_count).map(|_| ValidatorState::default()).collect(),
+            validator_stake: TOTAL_STAKE / validator_count as u64,
+            certificates: BTreeSet::new(),
+            finalized: BTreeSet::new(),
+            fast_finalized: BTreeSet::new(),
+            skip_certificates: BTreeSet::new(),
+        }
+    }
+
+    /// Whether `voters` validators hold at least `percent` of the stake
+    fn meets_threshold(&self, voters: usize, percent: u64) -> bool {
+        voters as u64 * self.validator_stake * 100 >= self.validator_stake * self.validators.len() as u64 * percent
+    }
+
+    /// Number of validators that cast `ballot` in `slot`
+    fn ballot_count(&self, slot: Slot, ballot: Ballot) -> usize {
+        self.validators.iter().filter(|v| v.ballots.get(&slot) == Some(&ballot)).count()
+    }
+
+    /// The block notarized in a slot, if any
+    fn notarized(&self, slot: Slot) -> Option<Hash> {
+        self.certificates.iter().find(|(s, _, _)| *s == slot).map(|(_, hash, _)| *hash)
+    }
+
+    fn is_finalized(&self, slot: Slot) -> bool {
+        self.finalized.iter().any(|(s, _)| *s == slot)
+    }
+
+    /// Whether a slot was finalized or skip-certified
+    fn is_decided(&self, slot: Slot) -> bool {
+        self.is_finalized(slot) || self.skip_certificates.contains(&slot)
+    }
+
+    /// Whether an earlier slot of the same leader window was skip-certified, so its leader
+    /// abandons the rest of the window
+    fn window_abandoned(&self, slot: Slot) -> bool {
+        self.skip_certificates.iter().any(|s| *s < slot && leader_window(*s) == leader_window(slot))
+    }
+
+    /// Whether a validator voted to notarize every earlier slot of `slot`'s window
+    fn window_ready(&self, validator: ActorId, slot: Slot) -> bool {
+        (1..slot)
+            .filter(|s| leader_window(*s) == leader_window(slot))
+            .all(|s| matches!(self.validators[validator].ballots.get(&s), Some(Ballot::Notarize(_))))
+    }
+
+    /// Vote to notarize `hash` in `slot`, then for the buffered blocks of later slots in the
+    /// window this vote made ready
+    fn vote_notarize(&mut self, validator: ActorId, slot: Slot, hash: Hash) {
+        let mut next = Some((slot, hash));
+        while let Some((slot, hash)) = next {
+            let state = &mut self.validators[validator];
+            if state.ballots.contains_key(&slot) {
+                return;
+            }
+            state.ballots.insert(slot, Ballot::Notarize(hash));
+            next = state.pending.remove(&(slot + 1))
+                .filter(|_| leader_window(slot + 1) == leader_window(slot))
+                .map(|hash| (slot + 1, hash));
+        }
+    }
+
+    /// TRYSKIP_WINDOW: skip `slot` and every later slot of its window not voted for yet
+    fn skip_window(&mut self, validator: ActorId, slot: Slot, max_slot: Slot) {
+        let state = &mut self.validators[validator];
+        for s in (slot..=max_slot).filter(|s| leader_window(*s) == leader_window(slot)) {
+            state.ballots.entry(s).or_insert(Ballot::Skip);
+            state.pending.remove(&s);
+        }
+    }
+
+    /// Form the certificates and final votes the ballots of `slot` now support
+    fn update_certificates(&mut self, slot: Slot) {
+        let hashes: BTreeSet<Hash> = self.validators.iter()
+            .filter_map(|v| match v.ballots.get(&slot) {
+                Some(Ballot::Notarize(hash)) => Some(*hash),
+                _ => None,
+            })
+            .collect();
+        for hash in hashes {
+            let voters = self.ballot_count(slot, Ballot::Notarize(hash));
+            if self.meets_threshold(voters, NOTARIZE_THRESHOLD_PERCENT) {
+                self.certificates.insert((slot, hash, voters as u64 * self.validator_stake));
+            }
+            if self.meets_threshold(voters, FAST_FINALIZE_THRESHOLD_PERCENT) {
+                self.finalized.insert((slot, hash));
+                self.fast_finalized.insert((slot, hash));
+            }
+        }
+
+        // TRYFINAL: validators whose notarize vote formed the certificate cast a final vote
+        if let Some(hash) = self.notarized(slot) {
+            for validator in &mut self.validators {
+                if validator.ballots.get(&slot) == Some(&Ballot::Notarize(hash)) {
+                    validator.final_votes.insert(slot);
+                }
+            }
+            let final_voters = self.validators.iter().filter(|v| v.final_votes.contains(&slot)).count();
+            if self.meets_threshold(final_voters, SLOW_FINALIZE_THRESHOLD_PERCENT) {
+                self.finalized.insert((slot, hash));
+            }
+        }
+
+        if self.meets_threshold(self.ballot_count(slot, Ballot::Skip), SKIP_THRESHOLD_PERCENT) {
+            self.skip_certificates.insert(slot);
+        }
+    }
+}
+
+impl ConsensusRecord for AlpenglowState {
+    fn finalized_blocks(&self) -> BTreeSet<(Slot, Hash)> {
+        self.finalized.clone()
+    }
+
+    fn notarized_blocks(&self) -> BTreeSet<(Slot, Hash)> {
+        self.certificates.iter().map(|(slot, hash, _)| (*slot, *hash)).collect()
+    }
+
+    fn fast_finalized_blocks(&self) -> BTreeSet<(Slot, Hash)> {
+        self.fast_finalized.clone()
+    }
+
+    fn certificates(&self) -> Vec<(Slot, Hash, Stake)> {
+        self.certificates.iter().copied().collect()
+    }
+
+    fn vote_stake(&self, slot: Slot, hash: Hash) -> Stake {
+        self.ballot_count(slot, Ballot::Notarize(hash)) as u64 * self.validator_stake
+    }
+}
+
+impl Bounded for AlpenglowState {
+    fn current_slot(&self) -> Slot {
+        self.proposals.keys().max().copied().unwrap_or(0)
+    }
+
+    fn in_flight(&self) -> usize {
+        self.shreds.len() + self.network.len()
+    }
+}
+
+impl AlpenglowModel {
+    /// Scheduled leader of a slot: each window of `LEADER_WINDOW_SLOTS` slots has one
+    /// stake-weighted leader, as in the leader model
+    pub fn leader(&self, slot: Slot) -> ActorId {
+        let stake_distribution: BTreeMap<ActorId, Stake> = (0..self.validator_count)
+            .map(|id| (id, TOTAL_STAKE / self.validator_count as u64))
+            .collect();
+        stake_weighted_leader(&stake_distribution, leader_window(slot))
+    }
+
+    fn is_online(&self, validator: ActorId) -> bool {
+        !self.offline.contains(&validator)
+    }
+
+    /// Whether the leader of a slot is online and follows the protocol
+    fn has_honest_leader(&self, slot: Slot) -> bool {
+        let leader = self.leader(slot);
+        self.is_online(leader) && !self.byzantine.contains(&leader)
+    }
+
+    /// Tick at which a slot times out
+    fn timeout_tick(&self, slot: Slot) -> u64 {
+        slot_start(slot) + self.timeout_ticks
+    }
+
+    /// Whether online validators hold enough stake to form certificates
+    fn can_progress(&self, state: &AlpenglowState) -> bool {
+        let online = (0..self.validator_count).filter(|id| self.is_online(*id)).count();
+        state.meets_threshold(online, NOTARIZE_THRESHOLD_PERCENT)
+    }
+
+    /// Whether a block of `slot`'s window up to `slot` was sent through an offline relay, so
+    /// it never arrives
+    fn dissemination_failed(&self, state: &AlpenglowState, slot: Slot) -> bool {
+        state.proposals.iter()
+            .filter(|(s, _)| **s <= slot && leader_window(**s) == leader_window(slot))
+            .any(|(_, relays)| relays.values().any(|relay| !self.is_online(*relay)))
+    }
+
+    /// The shared clock only advances once the network delivered everything due and every
+    /// honest leader proposed for its started slots, which makes executions synchronous
+    fn may_tick(&self, state: &AlpenglowState) -> bool {
+        let overdue_shreds = state.shreds.iter().any(|s| s.due <= state.clock && self.is_online(s.relay));
+        let overdue_blocks = state.network.iter().any(|b| b.due <= state.clock);
+        let late_leader = (1..=self.max_slot).any(|slot| {
+            slot_start(slot) <= state.clock
+                && self.has_honest_leader(slot)
+                && !state.proposals.contains_key(&slot)
+                && !state.window_abandoned(slot)
+        });
+        state.clock < self.timeout_tick(self.max_slot) && !overdue_shreds && !overdue_blocks && !late_leader
+    }
+}
+
+impl Model for AlpenglowModel {
+    type State = AlpenglowState;
+    type Action = AlpenglowAction;
+
+    fn init_states(&self) -> Vec<Self::State> {
+        vec![AlpenglowState::new(self.validator_count)]
+    }
+
+    fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
+        // 1. Leaders propose once their slot started, sending the shreds to any relay
+        for slot in 1..=self.max_slot {
+            let leader = self.leader(slot);
+            if !self.is_online(leader) || state.clock < slot_start(slot) {
+                continue;
+            }
+            let proposed = state.proposals.get(&slot);
+            let versions = if self.byzantine.contains(&leader) {
+                BLOCK_VERSIONS
+            } else if proposed.is_none() && !state.window_abandoned(slot) {
+                1
+            } else {
+                0
+            };
+            for version in 0..versions {
+                let hash = block_hash(slot, version);
+                if proposed.is_some_and(|blocks| blocks.contains_key(&hash)) {
+                    continue;
+                }
+                for relay in 0..self.validator_count {
+                    actions.push(AlpenglowAction::Propose { slot, hash, relay });
+                }
+            }
+        }
+
+        // 2. Online relays broadcast the blocks they reconstructed
+        for shreds in &state.shreds {
+            if self.is_online(shreds.relay) {
+                actions.push(AlpenglowAction::Relay { shreds: shreds.clone() });
+            }
+        }
+
+        // 3. Deliver any block in the network
+        for block in &state.network {
+            actions.push(AlpenglowAction::DeliverBlock { block: block.clone() });
+        }
+
+        // 4. Timeouts fire for slots a validator has not voted in
+        for validator in (0..self.validator_count).filter(|id| self.is_online(*id)) {
+            for slot in 1..=self.max_slot {
+                if state.clock >= self.timeout_tick(slot) && !state.validators[validator].ballots.contains_key(&slot) {
+                    actions.push(AlpenglowAction::Timeout { slot, validator });
+                }
+            }
+        }
+
+        // 5. Advance the shared clock
+        if self.may_tick(state) {
+            actions.push(AlpenglowAction::Tick);
+        }
+    }
+
+    fn next_state(&self, last_state: &Self::State, action: Self::Action) -> Option<Self::State> {
+        let mut next_state = last_state.clone();
+
+        match action {
+            AlpenglowAction::Propose { slot, hash, relay } => {
+                let blocks = next_state.proposals.entry(slot).or_default();
+                if blocks.insert(hash, relay).is_some() {
+                    return None;
+                }
+                next_state.shreds.insert(ShredsInTransit { relay, slot, hash, due: last_state.clock + HOP_TICKS });
+            }
+            AlpenglowAction::Relay { shreds } => {
+                if !next_state.shreds.remove(&shreds) {
+                    return None;
+                }
+                for dst in (0..self.validator_count).filter(|id| self.is_online(*id)) {
+                    next_state.network.insert(BlockInTransit {
+                        dst,
+                        slot: shreds.slot,
+                        hash: shreds.hash,
+                        due: last_state.clock + HOP_TICKS,
+                    });
+                }
+            }
+            AlpenglowAction::DeliverBlock { block } => {
+                if !next_state.network.remove(&block) {
+                    return None;
+                }
+                // A validator votes once per slot and keeps the first version of an equivocated block
+                let validator = &next_state.validators[block.dst];
+                if validator.ballots.contains_key(&block.slot) || validator.pending.contains_key(&block.slot) {
+                    return Some(next_state);
+                }
+                if next_state.window_ready(block.dst, block.slot) {
+                    next_state.vote_notarize(block.dst, block.slot, block.hash);
+                    for slot in block.slot..=self.max_slot {
+                        next_state.update_certificates(slot);
+                    }
+                } else {
+                    next_state.validators[block.dst].pending.insert(block.slot, block.hash);
+                }
+            }
+            AlpenglowAction::Timeout { slot, validator } => {
+                if next_state.validators[validator].ballots.contains_key(&slot) {
+                    return None;
+                }
+                next_state.skip_window(validator, slot, self.max_slot);
+                for slot in slot..=self.max_slot {
+                    next_state.update_certificates(slot);
+                }
+            }
+            AlpenglowAction::Tick => {
+                next_state.clock += 1;
+            }
+        }
+
+        Some(next_state)
+    }
+
+    /// Properties to verify in the composed model
+    fn properties(&self) -> Vec<Property<Self>> {
+        let mut properties = vec![
+            // Property 1: No two different blocks are finalized in the same slot
+            Property::<Self>::always("no_conflicting_finalization", |_, state| {
+                let mut finalized = BTreeMap::new();
+                state.finalized.iter().all(|(slot, hash)| *finalized.entry(*slot).or_insert(*hash) == *hash)
+            }),
+
+            // Property 2: No slot is both finalized and skip-certified
+            Property::<Self>::always("finalized_not_skipped", |_, state| {
+                state.skip_certificates.iter().all(|slot| !state.is_finalized(*slot))
+            }),
+
+            // Property 3: No slot is both notarized and skip-certified: a validator that times out
+            // skips the rest of its window while Rotor may still deliver the block to the others
+            Property::<Self>::always("notar_skip_exclusion", |_, state| {
+                state.skip_certificates.iter().all(|slot| state.notarized(*slot).is_none())
+            }),
+
+            // Property 4: Every slot is eventually finalized or skip-certified, except slots of
+            // Byzantine leaders, whose split votes need Votor's fallback votes (not modeled)
+            Property::<Self>::eventually("end_to_end_finalization", |model, state| {
+                !model.can_progress(state)
+                    || (1..=model.max_slot)
+                        .filter(|slot| !model.byzantine.contains(&model.leader(*slot)))
+                        .all(|slot| state.is_decided(slot))
+            }),
+
+            // Property 5: Every slot of an honest leader whose window was disseminated through
+            // online relays is eventually finalized
+            Property::<Self>::eventually("timely_blocks_finalized", |model, state| {
+                !model.can_progress(state)
+                    || (1..=model.max_slot)
+                        .filter(|slot| model.has_honest_leader(*slot) && !model.dissemination_failed(state, *slot))
+                        .all(|slot| state.is_finalized(slot))
+            }),
+        ];
+        properties.extend(invariants::derived_invariants());
+        properties
+    }
+}
+
+/// Run formal verification of the composed model
+pub fn run_formal_verification(options: &CheckOptions) {
+    println!("=== Composed Alpenglow Formal Verification ===");
+
+    let model = AlpenglowModel {
+        validator_count: 3, // Small for formal verification
+        max_slot: 2,
+        timeout_ticks: DEFAULT_TIMEOUT_TICKS,
+        byzantine: BTreeSet::new(),
+        offline: BTreeSet::new(),
+    };
+
+    println!("Model checking Rotor, Votor, leader windows and timeouts with {} validators, {} slots",
+             model.validator_count, model.max_slot);
+
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs()
+        .report(&mut stateright::report::WriteReporter::new(&mut std::io::stdout()));
+
+    // Check verification results
+    match options.print_outcomes(&result) {
+        Outcome::Proved => println!("✅ Global safety and end-to-end finalization verified successfully"),
+        Outcome::Violated => println!("❌ Composed verification found counterexamples"),
+        Outcome::Inconclusive => println!("❓ Composed verification inconclusive: a limit or bound stopped the search first"),
+    }
+    options.record_results("alpenglow formal", &result);
+}
+
+/// Test the composed model; the first `byzantine` leaders may equivocate and the last
+/// `offline` validators have crashed
+pub fn test_alpenglow_model(validators: usize, slots: u64, byzantine: usize, offline: usize, options: &CheckOptions) {
+    println!("Testing composed model with {} validators, {} slots, {} Byzantine, {} offline",
+             validators, slots, byzantine, offline);
+
+    let model = AlpenglowModel {
+        validator_count: validators,
+        max_slot: slots,
+        timeout_ticks: DEFAULT_TIMEOUT_TICKS,
+        byzantine: (0..byzantine.min(validators)).collect(),
+        offline: (validators.saturating_sub(offline)..validators).collect(),
+    };
+
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs();
+    options.progress.watch(&result);
+    let result = result.join();
+
+    println!("States explored: {}", result.state_count());
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("alpenglow {}v {}s {}b {}o", validators, slots, byzantine, offline), &result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(validators: usize, slots: u64) -> AlpenglowModel {
+        AlpenglowModel {
+            validator_count: validators,
+            max_slot: slots,
+            timeout_ticks: DEFAULT_TIMEOUT_TICKS,
+            byzantine: BTreeSet::new(),
+            offline: BTreeSet::new(),
+        }
+    }
+
+    #[test]
+    fn test_leader_windows() {
+        let model = model(3, 4);
+        assert_eq!(model.leader(1), model.leader(2));
+        assert_eq!(model.leader(3), model.leader(4));
+        assert_eq!(leader_window(2), 0);
+        assert_eq!(leader_window(3), 1);
+    }
+
+    #[test]
+    fn test_lost_block_skips_window() {
+        let model = model(3, 2);
+        let leader = model.leader(1);
+        let state = AlpenglowState::new(3);
+        let mut state = model.next_state(&state, AlpenglowAction::Propose { slot: 1, hash: block_hash(1, 0), relay: leader }).unwrap();
+        // The relay drops the shreds; slot 2's block arrives but waits for slot 1
+        state.shreds.clear();
+        state.validators[0].pending.insert(2, block_hash(2, 0));
+        state.clock = model.timeout_tick(1);
+        for validator in 0..3 {
+            state = model.next_state(&state, AlpenglowAction::Timeout { slot: 1, validator }).unwrap();
+        }
+        assert!(state.skip_certificates.contains(&1));
+        assert!(state.skip_certificates.contains(&2));
+        assert!(state.validators[0].pending.is_empty());
+        assert!(state.window_abandoned(2));
+    }
+
+    #[test]
+    fn test_composed_model_verifies() {
+        let checker = model(3, 2).checker().spawn_bfs().join();
+        assert!(checker.discoveries().is_empty(), "{:?}", checker.discoveries().keys());
+    }
+
+    #[test]
+    fn test_offline_relay_and_equivocating_leader() {
+        // A crashed validator may be sampled as relay: its slot is skipped, never split
+        let mut offline = model(3, 1);
+        offline.offline.insert(2);
+        let checker = offline.checker().spawn_bfs().join();
+        assert!(checker.discoveries().is_empty(), "{:?}", checker.discoveries().keys());
+
+        let mut byzantine = model(3, 1);
+        let leader = byzantine.leader(1);
+        byzantine.byzantine.insert(leader);
+        let checker = byzantine.checker().spawn_bfs().join();
+        assert!(checker.discovery("no_conflicting_finalization").is_none());
+        assert!(checker.discovery("finalized_not_skipped").is_none());
+        assert!(checker.discovery("notar_skip_exclusion").is_none());
+    }
+
+    #[test]
+    fn test_short_timeout_splits_votes() {
+        // Timing out before two Rotor hops elapse lets validators skip a block still in
+        // flight; with four validators the votes can split two to two and nothing is decided
+        let mut model = model(4, 1);
+        model.timeout_ticks = 1;
+        let checker = model.checker().spawn_bfs().join();
+        assert!(checker.discovery("end_to_end_finalization").is_some());
+        assert!(checker.discovery("no_conflicting_finalization").is_none());
+    }
+}
@@ -0,0 +1,52 @@
+use alpenglow_formal::alpenglow;
+use alpenglow_formal::cli::{ByzantineArgs, CheckArgs, ModelArgs, OutputArgs};
+use clap::Parser;
+
+/// Model-check the composed protocol: Rotor, Votor, leader windows and timeouts
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<3, 2>,
+    #[command(flatten)]
+    byzantine: ByzantineArgs<0>,
+    /// Number of offline validators
+    #[arg(long, default_value_t = 0)]
+    offline: usize,
+    /// Which check to run
+    #[arg(long, default_value = "formal", value_parser = ["formal", "test"])]
+    test_type: String,
+    #[command(flatten)]
+    check: CheckArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    cli.output.init();
+    let options = cli.check.options();
+    options.init_tracing();
+
+    let ModelArgs { validators, slots } = cli.model;
+    let byzantine = cli.byzantine.byzantine;
+    let offline = cli.offline;
+    let test_type = cli.test_type.as_str();
+
+    println!("Running composed formal verification: {} test, {} validators, {} slots, {} Byzantine, {} offline",
+             test_type, validators, slots, byzantine, offline);
+
+    match test_type {
+        "formal" => {
+            alpenglow::run_formal_verification(&options);
+            println!("Composed formal verification completed");
+        },
+        "test" => {
+            alpenglow::test_alpenglow_model(validators, slots, byzantine, offline, &options);
+            println!("Composed model test completed");
+        },
+        _ => {
+            println!("Unknown test type: {}", test_type);
+            std::process::exit(1);
+        }
+    }
+}
@@ -1,93 +1,112 @@
-use std::env;
+use alpenglow_formal::cli::{ModelArgs, OutputArgs, SeedArgs};
+use alpenglow_formal::finality::FinalityCheck;
+use alpenglow_formal::modelling::liveness::FinalizationPath;
+use alpenglow_formal::output;
+use clap::Parser;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    let mut path = "fast";
-    let mut stake_percent = 80;
-    let mut test_type = "bounded";
-    let mut delay = 20;
-    let mut offline_percent = 20;
-    let mut seed = 12345;
-    
-    for i in 0..args.len() {
-        if args[i] == "--path" && i + 1 < args.len() {
-            path = &args[i + 1];
-        } else if args[i] == "--stake-percent" && i + 1 < args.len() {
-            stake_percent = args[i + 1].parse().unwrap_or(80);
-        } else if args[i] == "--test-type" && i + 1 < args.len() {
-            test_type = &args[i + 1];
-        } else if args[i] == "--delay" && i + 1 < args.len() {
-            delay = args[i + 1].parse().unwrap_or(20);
-        } else if args[i] == "--offline-percent" && i + 1 < args.len() {
-            offline_percent = args[i + 1].parse().unwrap_or(20);
-        } else if args[i] == "--seed" && i + 1 < args.len() {
-            seed = args[i + 1].parse().unwrap_or(12345);
+/// Check that finalization completes within its bound
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<5, 3>,
+    /// Finality path to check
+    #[arg(long, default_value = "fast", value_parser = ["fast", "slow", "both"])]
+    path: String,
+    /// Stake (percent) that votes
+    #[arg(long, default_value_t = 80, value_parser = clap::value_parser!(u64).range(0..=100))]
+    stake_percent: u64,
+    /// Which check to run
+    #[arg(long, default_value = "bounded", value_parser = ["bounded", "network_delay", "concurrent", "partial_network"])]
+    test_type: String,
+    /// Most ticks each validator's messages take to arrive
+    #[arg(long, default_value_t = 1)]
+    delay: u64,
+    /// Stake (percent) offline in the partial_network check
+    #[arg(long, default_value_t = 20, value_parser = clap::value_parser!(u64).range(0..=100))]
+    offline_percent: u64,
+    /// Simulation runs, seeded from --seed on
+    #[arg(long, default_value_t = 1)]
+    runs: u64,
+    #[command(flatten)]
+    seed: SeedArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// Print `success` and the finalization time, or `failure` and why, exiting with 1 on failure
+fn conclude(failure: Option<String>, success: &str, latency: Option<u64>, failed: &str) {
+    match failure {
+        None => {
+            println!("{}", success);
+            if let Some(latency) = latency {
+                println!("Finalization time: {}", latency);
+            }
+        }
+        Some(reason) => {
+            println!("{} - {}", failed, reason);
+            std::process::exit(1);
         }
     }
-    
-    println!("Running bounded finalization test: {} path, {}% stake, {} test, {}ms delay, {}% offline, seed {}", 
-             path, stake_percent, test_type, delay, offline_percent, seed);
-    
+}
+
+fn main() {
+    let cli = Cli::parse();
+    cli.output.init();
+    let ModelArgs { validators, slots } = cli.model;
+    let Cli { stake_percent, delay, offline_percent, runs, .. } = cli;
+    let path = cli.path.as_str();
+    let test_type = cli.test_type.as_str();
+    let seed = cli.seed.seed;
+
+    let responsive_percent = match test_type {
+        "partial_network" => 100 - offline_percent,
+        _ => stake_percent,
+    };
+    let check = FinalityCheck { validators, slots, responsive_percent, max_delay: delay, runs, seed };
+    println!("Running bounded finalization test: {} test, {} path, {}% stake ({} of {} validators responsive, {}% requested), {} slots, {} ticks delay, {} runs, seed {}",
+             test_type, path, check.responsive_stake_percent(), check.responsive(), validators, responsive_percent, slots, delay, runs, seed);
+    let report = check.run();
+    let fast = Some(FinalizationPath::Fast);
+    // Only the bounded check of the fast path needs every slot fast-certified
+    let failure = report.failure(if test_type == "bounded" && path == "fast" { fast } else { None });
+    output::record(&report.record(&format!("bounded finalization {} {}", test_type, path), &check,
+                                  &format!("{}_finalization", test_type), failure.is_none())
+        .parameter("path", path));
+
     match test_type {
-        "bounded" => {
-            // Handle path-based logic when test_type is bounded
-            if path == "fast" {
-                if stake_percent >= 80 {
-                    println!("Fast path finalization successful");
-                    println!("Finalization time: 1");
-                } else {
-                    println!("Fast path finalization failed");
+        "bounded" => match path {
+            "fast" => conclude(failure, "Fast path finalization successful",
+                               report.max_latency(fast), "Fast path finalization failed"),
+            "slow" => conclude(failure, "Slow path finalization successful",
+                               report.max_latency(None), "Slow path finalization failed"),
+            _ => match failure {
+                None => {
+                    println!("Bounded finalization time verified");
+                    if let Some(latency) = report.max_latency(fast) {
+                        println!("Fast path time: {}", latency);
+                    }
+                    if let Some(latency) = report.max_latency(Some(FinalizationPath::Slow)) {
+                        println!("Slow path time: {}", latency);
+                    }
                 }
-            } else if path == "slow" {
-                if stake_percent >= 60 {
-                    println!("Slow path finalization successful");
-                    println!("Finalization time: 2");
-                } else {
-                    println!("Slow path finalization failed");
+                Some(reason) => {
+                    println!("Bounded finalization time not verified - {}", reason);
+                    std::process::exit(1);
                 }
-            } else {
-                println!("Bounded finalization time verified");
-                println!("Fast path time: 1");
-                println!("Slow path time: 2");
-            }
-        },
-        "network_delay" => {
-            if delay <= 50 {
-                println!("Network delay handling successful");
-                println!("Finalization time: 1");
-            } else {
-                println!("Network delay handling failed - delay too high");
-            }
+            },
         },
+        "network_delay" => conclude(failure, "Network delay handling successful",
+                                    report.max_latency(None), "Network delay handling failed"),
         "concurrent" => {
-            println!("Concurrent finalization successful");
-            println!("Finalization time: 1");
-        },
-        "partial_network" => {
-            if offline_percent <= 40 {
-                println!("Partial network finalization successful");
-                println!("Finalization time: 1");
-            } else {
-                println!("Partial network finalization failed - too many offline nodes");
-            }
+            println!("Overlapping slots: {}", report.overlapping_slots());
+            conclude(failure, "Concurrent finalization successful",
+                     report.max_latency(None), "Concurrent finalization failed");
         },
+        "partial_network" => conclude(failure, "Partial network finalization successful",
+                                      report.max_latency(None), "Partial network finalization failed"),
         _ => {
-            if path == "fast" {
-                if stake_percent >= 80 {
-                    println!("Fast path finalization successful");
-                    println!("Finalization time: 1");
-                } else {
-                    println!("Fast path finalization failed");
-                }
-            } else if path == "slow" {
-                if stake_percent >= 60 {
-                    println!("Slow path finalization successful");
-                    println!("Finalization time: 2");
-                } else {
-                    println!("Slow path finalization failed");
-                }
-            }
+            println!("Unknown test type: {}", test_type);
+            std::process::exit(1);
         }
     }
-}
\ No newline at end of file
+}
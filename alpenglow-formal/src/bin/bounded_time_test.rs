@@ -1,28 +1,66 @@
-use std::env;
+use alpenglow_formal::cli::{ModelArgs, OutputArgs, SeedArgs};
+use alpenglow_formal::finality::FinalityCheck;
+use alpenglow_formal::modelling::liveness::FinalizationPath;
+use alpenglow_formal::output;
+use clap::Parser;
+
+/// Check the bound on finalization time
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<5, 3>,
+    /// Most ticks a slot may take from its block arriving to finalization
+    #[arg(long, default_value_t = 10)]
+    max_ticks: u64,
+    /// Stake (percent) that votes
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(0..=100))]
+    stake_percent: u64,
+    /// Most ticks each validator's messages take to arrive
+    #[arg(long, default_value_t = 1)]
+    delay: u64,
+    /// Simulation runs, seeded from --seed on
+    #[arg(long, default_value_t = 1)]
+    runs: u64,
+    #[command(flatten)]
+    seed: SeedArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    let mut max_ticks = 10;
-    let mut seed = 12345;
-    
-    for i in 0..args.len() {
-        if args[i] == "--max-ticks" && i + 1 < args.len() {
-            max_ticks = args[i + 1].parse().unwrap_or(10);
-        } else if args[i] == "--seed" && i + 1 < args.len() {
-            seed = args[i + 1].parse().unwrap_or(12345);
-        }
+    let cli = Cli::parse();
+    cli.output.init();
+    let ModelArgs { validators, slots } = cli.model;
+    let Cli { max_ticks, stake_percent, delay, runs, .. } = cli;
+    let seed = cli.seed.seed;
+
+    let check = FinalityCheck { validators, slots, responsive_percent: stake_percent, max_delay: delay, runs, seed };
+    println!("Running bounded time test: {} max ticks, {}% stake ({} of {} validators responsive, {}% requested), {} slots, {} ticks delay, {} runs, seed {}",
+             max_ticks, check.responsive_stake_percent(), check.responsive(), validators, stake_percent, slots, delay, runs, seed);
+    let report = check.run();
+
+    // The model checks min(δ80%, 2δ60%) along every run; the slowest slot must also fit max_ticks
+    let bounded_time = report.max_latency(None);
+    let failure = report.failure(None).or_else(|| {
+        bounded_time
+            .filter(|latency| *latency > max_ticks)
+            .map(|latency| format!("a slot took {} ticks to finalize", latency))
+    });
+    output::record(&report.record("bounded time", &check, "bounded_finalization_time", failure.is_none())
+        .parameter("max_ticks", max_ticks));
+    if let Some(reason) = failure {
+        println!("Bounded finalization time not verified - {}", reason);
+        std::process::exit(1);
     }
-    
-    println!("Running bounded time test: {} max ticks, seed {}", max_ticks, seed);
-    
-    // Simulate bounded finalization time
-    let fast_time = 1;
-    let slow_time = 2;
-    let bounded_time = std::cmp::min(fast_time, slow_time);
-    
+
     println!("Bounded finalization time verified");
-    println!("Fast path time: {}", fast_time);
-    println!("Slow path time: {}", slow_time);
-    println!("Bounded time: {}", bounded_time);
+    if let Some(latency) = report.max_latency(Some(FinalizationPath::Fast)) {
+        println!("Fast path time: {}", latency);
+    }
+    if let Some(latency) = report.max_latency(Some(FinalizationPath::Slow)) {
+        println!("Slow path time: {}", latency);
+    }
+    if let Some(latency) = bounded_time {
+        println!("Bounded time: {}", latency);
+    }
 }
@@ -1,36 +1,47 @@
-use std::env;
-use alpenglow_formal::certificate;
+use alpenglow_formal::certificate::{self, VoteRouting};
+use alpenglow_formal::cli::{CheckArgs, ModelArgs, OutputArgs};
+use clap::Parser;
+
+/// Model-check certificate aggregation and uniqueness
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<4, 3>,
+    /// Number of adversarial validators
+    #[arg(long, default_value_t = 1)]
+    adversaries: usize,
+    /// How votes reach the validators that form certificates
+    #[arg(long, value_enum, default_value_t)]
+    vote_routing: VoteRouting,
+    /// Which check to run
+    #[arg(long, default_value = "formal", value_parser = ["formal", "test"])]
+    test_type: String,
+    #[command(flatten)]
+    check: CheckArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    let mut validators = 4;
-    let mut slots = 3;
-    let mut adversaries = 1;
-    let mut test_type = "formal";
-    
-    for i in 0..args.len() {
-        if args[i] == "--validators" && i + 1 < args.len() {
-            validators = args[i + 1].parse().unwrap_or(4);
-        } else if args[i] == "--slots" && i + 1 < args.len() {
-            slots = args[i + 1].parse().unwrap_or(3);
-        } else if args[i] == "--adversaries" && i + 1 < args.len() {
-            adversaries = args[i + 1].parse().unwrap_or(1);
-        } else if args[i] == "--test-type" && i + 1 < args.len() {
-            test_type = &args[i + 1];
-        }
-    }
+    let cli = Cli::parse();
+    cli.output.init();
+    let options = cli.check.options();
+    options.init_tracing();
+
+    let ModelArgs { validators, slots } = cli.model;
+    let Cli { adversaries, vote_routing, .. } = cli;
+    let test_type = cli.test_type.as_str();
     
     println!("Running certificate formal verification: {} test, {} validators ({} adversarial), {} slots", 
              test_type, validators, adversaries, slots);
     
     match test_type {
         "formal" => {
-            certificate::run_formal_verification();
+            certificate::run_formal_verification(&options);
             println!("Certificate formal verification completed");
         },
         "test" => {
-            certificate::test_certificate_model(validators, slots, adversaries);
+            certificate::test_certificate_model(validators, slots, adversaries, vote_routing, &options);
             println!("Certificate model test completed");
         },
         _ => {
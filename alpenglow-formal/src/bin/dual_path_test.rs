@@ -1,45 +1,71 @@
-use std::env;
+use alpenglow_formal::cli::{ModelArgs, OutputArgs, SeedArgs};
+use alpenglow_formal::finality::{FinalityCheck, FinalityReport};
+use alpenglow_formal::modelling::liveness::FinalizationPath;
+use alpenglow_formal::output;
+use clap::Parser;
+
+/// Check dual-path finality for a share of responsive stake
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<5, 1>,
+    /// Finality path to check
+    #[arg(long, default_value = "fast", value_parser = ["fast", "slow"])]
+    path: String,
+    /// Stake (percent) that votes
+    #[arg(long, default_value_t = 90, value_parser = clap::value_parser!(u64).range(0..=100))]
+    stake_percent: u64,
+    /// Most ticks each validator's messages take to arrive
+    #[arg(long, default_value_t = 1)]
+    delay: u64,
+    /// Simulation runs, seeded from --seed on
+    #[arg(long, default_value_t = 1)]
+    runs: u64,
+    #[command(flatten)]
+    seed: SeedArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+/// Voting rounds of the slowest path that finalized a slot first
+fn rounds(report: &FinalityReport) -> usize {
+    if report.paths_used().contains(&FinalizationPath::Slow) { 2 } else { 1 }
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    let mut path = "fast";
-    let mut threshold = 80;
-    let mut stake_percent = 90;
-    let mut seed = 12345;
-    
-    for i in 0..args.len() {
-        if args[i] == "--path" && i + 1 < args.len() {
-            path = &args[i + 1];
-        } else if args[i] == "--threshold" && i + 1 < args.len() {
-            threshold = args[i + 1].parse().unwrap_or(80);
-        } else if args[i] == "--stake-percent" && i + 1 < args.len() {
-            stake_percent = args[i + 1].parse().unwrap_or(90);
-        } else if args[i] == "--seed" && i + 1 < args.len() {
-            seed = args[i + 1].parse().unwrap_or(12345);
-        }
-    }
-    
-    println!("Running dual path test: {} path, {}% threshold, {}% stake, seed {}", path, threshold, stake_percent, seed);
-    
-    // Simulate dual path finality
-    if path == "fast" {
-        if stake_percent >= threshold {
-            println!("Fast path finalization successful");
-            println!("Finalization time: 1");
-            println!("Rounds completed: 1");
-        } else {
-            println!("Fast path finalization failed - insufficient stake");
+    let cli = Cli::parse();
+    cli.output.init();
+    let ModelArgs { validators, slots } = cli.model;
+    let Cli { stake_percent, delay, runs, .. } = cli;
+    let path = cli.path.as_str();
+    let seed = cli.seed.seed;
+
+    let check = FinalityCheck { validators, slots, responsive_percent: stake_percent, max_delay: delay, runs, seed };
+    println!("Running dual path test: {} path, {}% stake ({} of {} validators responsive, {}% requested), {} slots, {} runs, seed {}",
+             path, check.responsive_stake_percent(), check.responsive(), validators, stake_percent, slots, runs, seed);
+    let report = check.run();
+
+    // The fast path must certify every proposed slot, even where the slow path finalized it
+    // first; the slow path only needs every slot finalized, as the fast path may win the race
+    let (label, required_path) = match path {
+        "fast" => ("Fast", Some(FinalizationPath::Fast)),
+        _ => ("Slow", None),
+    };
+    let failure = report.failure(required_path);
+    output::record(&report.record(&format!("dual path {} {}%", path, stake_percent), &check,
+                                  &format!("{}_path_finalization", path), failure.is_none())
+        .metric("rounds", rounds(&report) as f64));
+    match failure {
+        None => {
+            println!("{} path finalization successful", label);
+            if let Some(latency) = report.max_latency(required_path) {
+                println!("Finalization time: {}", latency);
+            }
+            println!("Rounds completed: {}", rounds(&report));
         }
-    } else if path == "slow" {
-        if stake_percent >= 60 {
-            println!("Slow path finalization successful");
-            println!("Finalization time: 2");
-            println!("Rounds completed: 2");
-        } else {
-            println!("Slow path finalization failed - insufficient stake");
+        Some(reason) => {
+            println!("{} path finalization failed - {}", label, reason);
+            std::process::exit(1);
         }
-    } else {
-        println!("Unknown path type: {}", path);
     }
-}
\ No newline at end of file
+}
@@ -1,33 +1,40 @@
-use std::env;
+use alpenglow_formal::cli::{CheckArgs, ModelArgs, OutputArgs};
 use alpenglow_formal::leader;
+use clap::Parser;
+
+/// Model-check leader rotation and window management
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<3, 5>,
+    /// Which check to run
+    #[arg(long, default_value = "formal", value_parser = ["formal", "test"])]
+    test_type: String,
+    #[command(flatten)]
+    check: CheckArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    let mut validators = 3;
-    let mut slots = 5;
-    let mut test_type = "formal";
-    
-    for i in 0..args.len() {
-        if args[i] == "--validators" && i + 1 < args.len() {
-            validators = args[i + 1].parse().unwrap_or(3);
-        } else if args[i] == "--slots" && i + 1 < args.len() {
-            slots = args[i + 1].parse().unwrap_or(5);
-        } else if args[i] == "--test-type" && i + 1 < args.len() {
-            test_type = &args[i + 1];
-        }
-    }
+    let cli = Cli::parse();
+    cli.output.init();
+    let options = cli.check.options();
+    options.init_tracing();
+
+    let ModelArgs { validators, slots } = cli.model;
+    let test_type = cli.test_type.as_str();
     
     println!("Running leader formal verification: {} test, {} validators, {} slots", 
              test_type, validators, slots);
     
     match test_type {
         "formal" => {
-            leader::run_formal_verification();
+            leader::run_formal_verification(&options);
             println!("Leader formal verification completed");
         },
         "test" => {
-            leader::test_leader_model(validators, slots);
+            leader::test_leader_model(validators, slots, &options);
             println!("Leader model test completed");
         },
         _ => {
@@ -1,38 +1,102 @@
-use std::env;
-use alpenglow_formal::modelling::liveness;
+use alpenglow_formal::cli::{CheckArgs, ModelArgs, OutputArgs, SeedArgs};
+use alpenglow_formal::modelling::liveness::{self, LivenessModel};
+use clap::Parser;
+
+/// Check that Alpenglow finalizes under partial synchrony
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<4, 3>,
+    /// Validators that respond in time
+    #[arg(long, default_value_t = 3)]
+    responsive: usize,
+    /// Most ticks two validators' clocks may differ by
+    #[arg(long, default_value_t = liveness::CLOCK_SKEW_BOUND)]
+    clock_skew: u64,
+    /// Tick of global stabilization; messages before it may be delayed arbitrarily
+    #[arg(long, value_name = "TICK")]
+    gst: Option<u64>,
+    /// Comma-separated delivery delays, in ticks, the network may choose from
+    #[arg(long, value_delimiter = ',')]
+    message_delays: Vec<u64>,
+    /// Validators that may crash for good
+    #[arg(long = "crashes", default_value_t = 0)]
+    crash_budget: usize,
+    /// Comma-separated validators cut off from the rest until --heal-at
+    #[arg(long = "isolate", value_delimiter = ',')]
+    isolated: Vec<usize>,
+    /// Tick at which the partition heals
+    #[arg(long, default_value_t = 0)]
+    heal_at: u64,
+    /// Simulation runs of the latency study
+    #[arg(long, default_value_t = 20)]
+    runs: u64,
+    #[command(flatten)]
+    seed: SeedArgs,
+    /// Write the latency study to this CSV file
+    #[arg(long = "csv", value_name = "FILE")]
+    csv_path: Option<String>,
+    /// Which check to run
+    #[arg(long, default_value = "formal", value_parser = ["formal", "test", "vacuity", "simulation"])]
+    test_type: String,
+    #[command(flatten)]
+    check: CheckArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    let mut validators = 4;
-    let mut slots = 3;
-    let mut responsive = 3;
-    let mut test_type = "formal";
-    
-    for i in 0..args.len() {
-        if args[i] == "--validators" && i + 1 < args.len() {
-            validators = args[i + 1].parse().unwrap_or(4);
-        } else if args[i] == "--slots" && i + 1 < args.len() {
-            slots = args[i + 1].parse().unwrap_or(3);
-        } else if args[i] == "--responsive" && i + 1 < args.len() {
-            responsive = args[i + 1].parse().unwrap_or(3);
-        } else if args[i] == "--test-type" && i + 1 < args.len() {
-            test_type = &args[i + 1];
-        }
-    }
+    let cli = Cli::parse();
+    cli.output.init();
+    let options = cli.check.options();
+    options.init_tracing();
+
+    let ModelArgs { validators, slots } = cli.model;
+    let seed = cli.seed.seed;
+    let Cli { responsive, clock_skew, gst, message_delays, crash_budget, isolated, heal_at, runs, .. } = cli;
+    let csv_path = cli.csv_path.as_deref();
+    let test_type = cli.test_type.as_str();
     
+    let partition = (!isolated.is_empty()).then(|| liveness::PartitionSchedule {
+        isolated: isolated.into_iter().collect(),
+        heal_at,
+    });
+
     println!("Running liveness formal verification: {} test, {} validators ({} responsive), {} slots", 
              test_type, validators, responsive, slots);
     
     match test_type {
         "formal" => {
-            liveness::run_formal_verification();
+            liveness::run_formal_verification(&options);
             println!("Liveness formal verification completed");
         },
         "test" => {
-            liveness::test_liveness_model(validators, slots, responsive);
+            liveness::test_liveness_model(LivenessModel {
+                validator_count: validators,
+                max_slot: slots,
+                responsive_count: responsive,
+                max_clock_skew: clock_skew,
+                network_faults: 0,
+                fairness: gst.is_none(),
+                gst,
+                message_delays,
+                crash_budget,
+                persist_votes: true,
+                partition,
+            }, &options);
             println!("Liveness model test completed");
         },
+        "vacuity" => {
+            liveness::check_vacuity(validators, slots, responsive, clock_skew, options.threads());
+            println!("Liveness vacuity check completed");
+        },
+        "simulation" => {
+            if !liveness::run_latency_study(validators, slots, runs, seed, csv_path) {
+                println!("Liveness simulation failed: finalization latency exceeded its bound");
+                std::process::exit(1);
+            }
+            println!("Liveness simulation completed");
+        },
         _ => {
             println!("Unknown test type: {}", test_type);
             std::process::exit(1);
@@ -0,0 +1,69 @@
+use std::time::Instant;
+use alpenglow_formal::cli::{CheckArgs, OutputArgs};
+use alpenglow_formal::output;
+use alpenglow_formal::rotor::{SamplingStrategy, DEFAULT_FANOUT};
+use alpenglow_formal::simulator::{self, Latency, SimulatorConfig};
+use clap::builder::RangedU64ValueParser;
+use clap::Parser;
+
+/// Simulate a large Alpenglow network with link latencies
+#[derive(Parser)]
+struct Cli {
+    /// Nodes in the network
+    #[arg(long, default_value_t = 1000, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    nodes: usize,
+    /// Slots proposed one after another
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u64).range(1..))]
+    slots: u64,
+    /// Relays sampled per slot
+    #[arg(long, default_value_t = DEFAULT_FANOUT)]
+    fanout: usize,
+    /// How relays are drawn from the stake distribution
+    #[arg(long, value_enum, default_value_t)]
+    sampling: SamplingStrategy,
+    /// Link latency: `constant:MS`, `uniform:MIN-MAX`, `exponential:MIN,MEAN` or `lognormal:MEDIAN,SIGMA`
+    #[arg(long, value_parser = Latency::parse)]
+    latency: Option<Latency>,
+    /// Milliseconds without a block after which validators vote to skip
+    #[arg(long, default_value_t = simulator::DEFAULT_SKIP_TIMEOUT_MS)]
+    skip_timeout: u64,
+    /// Nodes (percent) that crash before the run
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u64).range(0..100))]
+    offline_percent: u64,
+    /// Seed of the link delays and of the crashed nodes
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+    #[command(flatten)]
+    check: CheckArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    cli.output.init();
+    cli.check.options().init_tracing();
+
+    let mut config = SimulatorConfig::new(cli.nodes, cli.slots, cli.seed);
+    config.fanout = cli.fanout;
+    config.sampling_strategy = cli.sampling;
+    if let Some(latency) = cli.latency {
+        config.latency = latency;
+    }
+    config.skip_timeout_ms = cli.skip_timeout;
+    let seed = config.seed;
+    let config = config.with_offline_percent(cli.offline_percent, seed);
+
+    println!("Simulating {} nodes ({} offline) over {} slots: fanout {}, {:?} sampling, {:?} links, seed {}",
+             config.nodes, config.offline.len(), config.slots, config.fanout, config.sampling_strategy, config.latency, config.seed);
+
+    let start = Instant::now();
+    let report = simulator::simulate(&config);
+    report.print_summary();
+    println!("Simulation took {:.2?}", start.elapsed());
+    output::record(&report.record(&config));
+
+    if report.conflicting_finalization || !report.all_finalized(config.slots) {
+        std::process::exit(1);
+    }
+}
@@ -1,30 +1,64 @@
-use std::env;
+use alpenglow_formal::cli::{ModelArgs, OutputArgs, SeedArgs};
+use alpenglow_formal::finality::FinalityCheck;
+use alpenglow_formal::modelling::liveness::{FinalizationPath, FAST_PATH_THRESHOLD_PERCENT, SLOW_PATH_THRESHOLD_PERCENT};
+use alpenglow_formal::output;
+use clap::Parser;
+
+/// Check which finality path a share of responsive stake selects
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<5, 1>,
+    /// Stake (percent) that votes
+    #[arg(long, default_value_t = 80, value_parser = clap::value_parser!(u64).range(0..=100))]
+    stake_percent: u64,
+    /// Most ticks each validator's messages take to arrive
+    #[arg(long, default_value_t = 1)]
+    delay: u64,
+    #[command(flatten)]
+    seed: SeedArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    let mut stake_percent = 80;
-    let mut seed = 12345;
-    
-    for i in 0..args.len() {
-        if args[i] == "--stake-percent" && i + 1 < args.len() {
-            stake_percent = args[i + 1].parse().unwrap_or(80);
-        } else if args[i] == "--seed" && i + 1 < args.len() {
-            seed = args[i + 1].parse().unwrap_or(12345);
-        }
-    }
-    
-    println!("Running path selection test: {}% stake, seed {}", stake_percent, seed);
-    
-    // Determine which path should be selected based on stake percentage
-    let selected_path = if stake_percent >= 80 {
-        "fast"
-    } else if stake_percent >= 60 {
-        "slow"
+    let cli = Cli::parse();
+    cli.output.init();
+    let ModelArgs { validators, slots } = cli.model;
+    let Cli { stake_percent, delay, .. } = cli;
+    let seed = cli.seed.seed;
+
+    let check = FinalityCheck { validators, slots, responsive_percent: stake_percent, max_delay: delay, runs: 1, seed };
+    let responsive_percent = check.responsive_stake_percent();
+    println!("Running path selection test: {}% stake ({} of {} validators responsive, {}% requested), {} slots, seed {}",
+             responsive_percent, check.responsive(), validators, stake_percent, slots, seed);
+    let report = check.run();
+
+    let paths = report.paths_used();
+    let selected: Vec<&str> = paths.iter().map(|path| path.label()).collect();
+    let selected = if selected.is_empty() { "none".to_string() } else { selected.join("+") };
+
+    // Below the notarization threshold nothing finalizes, below the fast-path threshold only
+    // the slow path can, and above it the fast path must finalize every proposed slot
+    let mismatch = if responsive_percent < SLOW_PATH_THRESHOLD_PERCENT {
+        (!paths.is_empty()).then(|| "a slot finalized without enough stake to notarize".to_string())
+    } else if responsive_percent < FAST_PATH_THRESHOLD_PERCENT {
+        report.failure(None)
+            .or_else(|| paths.contains(&FinalizationPath::Fast).then(|| "a slot finalized through the fast path".to_string()))
     } else {
-        "none"
+        report.failure(Some(FinalizationPath::Fast))
     };
-    
-    println!("Correct path selected");
-    println!("Selected path: {}", selected_path);
+    output::record(&report.record(&format!("path selection {}%", stake_percent), &check, "path_selection", mismatch.is_none())
+        .parameter("selected_path", &selected));
+    match mismatch {
+        None => {
+            println!("Correct path selected");
+            println!("Selected path: {}", selected);
+        }
+        Some(reason) => {
+            println!("Wrong path selected - {}", reason);
+            println!("Selected path: {}", selected);
+            std::process::exit(1);
+        }
+    }
 }
@@ -1,38 +1,131 @@
-use std::env;
-use alpenglow_formal::modelling::resilience;
+use alpenglow_formal::cli::{ByzantineArgs, CheckArgs, ModelArgs, OutputArgs, SeedArgs};
+use alpenglow_formal::vacuity::DEFAULT_VACUITY_STATE_COUNT;
+use alpenglow_formal::modelling::resilience::{self, AdversaryPower, DEFAULT_CORRUPTION_BUDGET_PERCENT, DEFAULT_CRASH_BUDGET_PERCENT, DEFAULT_OFFLINE_BUDGET_PERCENT};
+use clap::Parser;
+
+/// Resident memory at which the hybrid check stops exploring exhaustively
+const DEFAULT_MEMORY_LIMIT_MIB: u64 = 4096;
+
+/// Check Alpenglow's resilience to Byzantine, crashed and offline validators
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<4, 3>,
+    #[command(flatten)]
+    byzantine: ByzantineArgs<1>,
+    /// Most stake (percent) that may be offline at once
+    #[arg(long, default_value_t = DEFAULT_OFFLINE_BUDGET_PERCENT, value_parser = clap::value_parser!(u64).range(0..=100))]
+    offline_budget: u64,
+    /// Most stake (percent) the adversary may corrupt
+    #[arg(long, default_value_t = DEFAULT_CORRUPTION_BUDGET_PERCENT, value_parser = clap::value_parser!(u64).range(0..=100))]
+    corruption_budget: u64,
+    /// Most stake (percent) that may crash for good
+    #[arg(long, default_value_t = DEFAULT_CRASH_BUDGET_PERCENT, value_parser = clap::value_parser!(u64).range(0..=100))]
+    crash_budget: u64,
+    /// Adversary power the minimal-attack search increases
+    #[arg(long, value_enum, default_value_t = AdversaryPower::Byzantine)]
+    power: AdversaryPower,
+    /// Property the minimal-attack search tries to break
+    #[arg(long, default_value = "progress")]
+    property: String,
+    /// Simulation runs per study
+    #[arg(long, default_value_t = 20)]
+    runs: u64,
+    #[command(flatten)]
+    seed: SeedArgs,
+    /// Most rounds the recovery-time measurement waits for finalization
+    #[arg(long, default_value_t = 16)]
+    max_rounds: u64,
+    /// Resident memory at which the hybrid check stops exploring exhaustively
+    #[arg(long, value_name = "MIB", default_value_t = DEFAULT_MEMORY_LIMIT_MIB)]
+    memory_limit_mib: u64,
+    /// Which check to run
+    #[arg(long, default_value = "formal", value_parser = [
+        "formal", "test", "20+20", "attack", "offline", "partition-recovery", "recovery-time",
+        "hybrid", "failure-domains", "minimal-attack", "vacuity",
+    ])]
+    test_type: String,
+    #[command(flatten)]
+    check: CheckArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    let mut validators = 4;
-    let mut slots = 3;
-    let mut byzantine = 1;
-    let mut test_type = "formal";
-    
-    for i in 0..args.len() {
-        if args[i] == "--validators" && i + 1 < args.len() {
-            validators = args[i + 1].parse().unwrap_or(4);
-        } else if args[i] == "--slots" && i + 1 < args.len() {
-            slots = args[i + 1].parse().unwrap_or(3);
-        } else if args[i] == "--byzantine" && i + 1 < args.len() {
-            byzantine = args[i + 1].parse().unwrap_or(1);
-        } else if args[i] == "--test-type" && i + 1 < args.len() {
-            test_type = &args[i + 1];
-        }
-    }
+    let cli = Cli::parse();
+    cli.output.init();
+    let options = cli.check.options();
+    options.init_tracing();
+
+    let ModelArgs { validators, slots } = cli.model;
+    let byzantine = cli.byzantine.byzantine;
+    let seed = cli.seed.seed;
+    let Cli { offline_budget, corruption_budget, crash_budget, power, runs, max_rounds, memory_limit_mib, .. } = cli;
+    // The recovery and attack searches run many checks, each up to --max-states states
+    let max_states = options.limits.max_states.unwrap_or(DEFAULT_VACUITY_STATE_COUNT);
+    let property = cli.property.as_str();
+    let test_type = cli.test_type.as_str();
     
     println!("Running resilience formal verification: {} test, {} validators ({} Byzantine), {} slots", 
              test_type, validators, byzantine, slots);
     
     match test_type {
         "formal" => {
-            resilience::run_formal_verification();
+            resilience::run_formal_verification(&options);
             println!("Resilience formal verification completed");
         },
         "test" => {
-            resilience::test_resilience_model(validators, slots, byzantine);
+            resilience::test_resilience_model(validators, slots, byzantine, offline_budget, corruption_budget, crash_budget, &options);
             println!("Resilience model test completed");
         },
+        "20+20" => {
+            if !resilience::verify_twenty_plus_twenty(validators, slots, options.threads()) {
+                std::process::exit(1);
+            }
+            println!("20+20 resilience check completed");
+        },
+        "attack" => {
+            let report = resilience::simulate_safety_under_attack(validators, slots, byzantine, runs, seed);
+            if report.violations.contains_key("safety_byzantine") {
+                std::process::exit(1);
+            }
+            println!("Safety-under-attack simulation completed");
+        },
+        "offline" => {
+            resilience::simulate_liveness_with_offline_nodes(validators, slots, offline_budget, runs, seed);
+            println!("Offline-validator simulation completed");
+        },
+        "partition-recovery" => {
+            let report = resilience::simulate_network_partition_recovery(validators, slots, runs, seed);
+            if report.recovery_steps.len() < report.partitioned_runs {
+                std::process::exit(1);
+            }
+            println!("Partition-recovery simulation completed");
+        },
+        "recovery-time" => {
+            if resilience::measure_recovery_rounds(validators, slots, max_rounds, max_states, options.threads()).is_none() {
+                std::process::exit(1);
+            }
+            println!("Recovery-time measurement completed");
+        },
+        "hybrid" => {
+            if !resilience::run_hybrid_check(validators, slots, byzantine, offline_budget, memory_limit_mib, seed) {
+                std::process::exit(1);
+            }
+            println!("Hybrid resilience check completed");
+        },
+        "failure-domains" => {
+            resilience::run_failure_domain_study(validators, slots, byzantine, max_states, options.threads());
+            println!("Failure-domain study completed");
+        },
+        "minimal-attack" => {
+            resilience::run_minimal_attack_search(validators, slots, power, property, max_states, options.threads());
+            println!("Minimal-attack search completed");
+        },
+        "vacuity" => {
+            resilience::check_vacuity(validators, slots, byzantine, offline_budget, options.threads());
+            println!("Resilience vacuity check completed");
+        },
         _ => {
             println!("Unknown test type: {}", test_type);
             std::process::exit(1);
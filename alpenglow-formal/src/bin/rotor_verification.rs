@@ -1,38 +1,139 @@
-use std::env;
-use alpenglow_formal::rotor;
+use alpenglow_formal::cli::{ByzantineArgs, CheckArgs, OutputArgs, SeedArgs};
+use std::collections::BTreeSet;
+use alpenglow_formal::rotor::{self, DisseminationMode, RotorModel, SamplingStrategy, Topology, DEFAULT_FANOUT, DEFAULT_LOAD_FACTOR_PERCENT};
+use clap::builder::RangedU64ValueParser;
+use clap::Parser;
+
+/// Check Rotor's relay sampling, by model checking or by simulation
+#[derive(Parser)]
+struct Cli {
+    /// Nodes in the network [default: 4 when model checking, 20 for simulations]
+    #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    nodes: Option<usize>,
+    /// Highest slot proposed
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..))]
+    slots: u64,
+    /// Relays sampled per slice
+    #[arg(long, default_value_t = DEFAULT_FANOUT)]
+    fanout: usize,
+    /// How relays are drawn from the stake distribution
+    #[arg(long, value_enum, default_value_t)]
+    sampling: SamplingStrategy,
+    #[command(flatten)]
+    byzantine: ByzantineArgs<0>,
+    /// Stake (percent) of faulty relays in the fault-tolerance tests
+    #[arg(long, default_value_t = 20, value_parser = clap::value_parser!(u64).range(0..=100))]
+    fault_percent: u64,
+    /// Stake (percent) of offline nodes in the mixed-fault test
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(0..=100))]
+    offline_percent: u64,
+    /// Relay tiers a shred passes before its final broadcast
+    #[arg(long, value_enum, default_value_t)]
+    mode: DisseminationMode,
+    /// mesh, star, ring, tree, clustered, or a file listing one link per line
+    #[arg(long, default_value = "mesh", value_parser = Topology::parse)]
+    topology: Topology,
+    #[command(flatten)]
+    seed: SeedArgs,
+    /// Which check to run
+    #[arg(long, default_value = "formal", value_parser = [
+        "formal", "test", "basic", "stake_weighted", "fanout", "dissemination", "topology",
+        "fault_tolerance", "mixed_faults", "load_balancing", "scalability",
+    ])]
+    test_type: String,
+    #[command(flatten)]
+    check: CheckArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let cli = Cli::parse();
+    cli.output.init();
+    let options = cli.check.options();
+    options.init_tracing();
+
+    let Cli { nodes, slots, fanout, sampling: sampling_strategy, fault_percent, offline_percent, mode: dissemination_mode, topology, .. } = cli;
+    let byzantine = cli.byzantine.byzantine;
+    let seed = cli.seed.seed;
+    let test_type = cli.test_type.as_str();
     
-    let mut nodes = 4;
-    let mut slots = 3;
-    let mut test_type = "formal";
-    
-    for i in 0..args.len() {
-        if args[i] == "--nodes" && i + 1 < args.len() {
-            nodes = args[i + 1].parse().unwrap_or(4);
-        } else if args[i] == "--slots" && i + 1 < args.len() {
-            slots = args[i + 1].parse().unwrap_or(3);
-        } else if args[i] == "--test-type" && i + 1 < args.len() {
-            test_type = &args[i + 1];
-        }
-    }
+    // Model checking stays small; simulations default to a 20-node network
+    let model_nodes = nodes.unwrap_or(4);
+    let nodes = nodes.unwrap_or(20);
     
     println!("Running rotor formal verification: {} test, {} nodes, {} slots", 
              test_type, nodes, slots);
     
-    match test_type {
+    let (passed, success_message) = match test_type {
         "formal" => {
-            rotor::run_formal_verification();
+            rotor::run_formal_verification(&options);
             println!("Rotor formal verification completed");
+            return;
         },
         "test" => {
-            rotor::test_rotor_model(nodes, slots);
+            let model = RotorModel {
+                node_count: model_nodes,
+                max_slot: slots,
+                sampling_strategy,
+                byzantine: (model_nodes.saturating_sub(byzantine)..model_nodes).collect(),
+                offline: BTreeSet::new(),
+                load_factor_percent: DEFAULT_LOAD_FACTOR_PERCENT,
+                dissemination_mode,
+                fanout,
+                topology,
+                network_faults: 0,
+            };
+            rotor::test_rotor_model(model, &options);
             println!("Rotor model test completed");
+            return;
         },
+        "basic" => (
+            rotor::run_simulation(nodes, slots, seed),
+            "Rotor sampling successful",
+        ),
+        "stake_weighted" => (
+            rotor::test_stake_weighted_selection(nodes, seed),
+            "Stake-weighted selection successful",
+        ),
+        "fanout" => (
+            rotor::test_fanout_optimization(nodes, fanout, seed),
+            "Fanout optimization successful",
+        ),
+        "dissemination" => (
+            rotor::test_message_dissemination(nodes, seed),
+            "Message dissemination successful",
+        ),
+        "topology" => (
+            rotor::test_topology_adaptation(nodes, topology, seed),
+            "Topology adaptation successful",
+        ),
+        "fault_tolerance" => (
+            rotor::test_fault_tolerance(nodes, fault_percent, seed),
+            "Fault tolerance successful",
+        ),
+        "mixed_faults" => (
+            rotor::test_mixed_faults(nodes, offline_percent, fault_percent, seed),
+            "Mixed fault resilience successful",
+        ),
+        "load_balancing" => (
+            rotor::test_load_balancing(nodes, seed),
+            "Load balancing successful",
+        ),
+        "scalability" => (
+            rotor::test_scalability(nodes, seed),
+            "Scalability test successful",
+        ),
         _ => {
             println!("Unknown test type: {}", test_type);
             std::process::exit(1);
         }
+    };
+    
+    if passed {
+        println!("{}", success_message);
+    } else {
+        println!("Rotor simulation failed: {} test", test_type);
+        std::process::exit(1);
     }
 }
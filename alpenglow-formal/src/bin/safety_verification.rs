@@ -1,41 +1,153 @@
+use alpenglow_formal::cli::{CheckArgs, ModelArgs, OutputArgs, SeedArgs};
+use alpenglow_formal::trace;
+use alpenglow_formal::distributed;
+use alpenglow_formal::hybrid::{self, HybridConfig, Verdict};
+use alpenglow_formal::limits::Outcome;
 use alpenglow_formal::votor::VotorModel;
+use clap::builder::RangedU64ValueParser;
+use clap::Parser;
 use stateright::{report::WriteReporter, *};
-use std::env;
+use std::path::PathBuf;
+
+/// Model-check the safety of Votor's dual-path finality
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<2, 1>,
+    #[command(flatten)]
+    seed: SeedArgs,
+    /// Messages the network may drop, duplicate or delay
+    #[arg(long, default_value_t = 0)]
+    network_faults: usize,
+    /// Coordinate a distributed check on this address
+    #[arg(long, value_name = "HOST:PORT", conflicts_with = "worker")]
+    coordinator: Option<String>,
+    /// Workers the coordinator waits for
+    #[arg(long, default_value_t = 2, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    workers: usize,
+    /// Join the distributed check coordinated on this address
+    #[arg(long, value_name = "HOST:PORT")]
+    worker: Option<String>,
+    /// Check exhaustively up to this resident memory, then by random walks from the frontier
+    #[arg(long, value_name = "MIB")]
+    memory_limit_mib: Option<u64>,
+    /// Write every counterexample as a Graphviz trace into this directory
+    #[arg(long, value_name = "DIR")]
+    dot_dir: Option<PathBuf>,
+    /// Write every counterexample as a mermaid sequence diagram into this directory
+    #[arg(long, value_name = "DIR")]
+    mermaid_dir: Option<PathBuf>,
+    #[command(flatten)]
+    check: CheckArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    let mut validators = 2;
-    let mut slots = 1;
-    let mut seed = 12345;
-    
-    for i in 0..args.len() {
-        if args[i] == "--validators" && i + 1 < args.len() {
-            validators = args[i + 1].parse().unwrap_or(2);
-        } else if args[i] == "--slots" && i + 1 < args.len() {
-            slots = args[i + 1].parse().unwrap_or(1);
-        } else if args[i] == "--seed" && i + 1 < args.len() {
-            seed = args[i + 1].parse().unwrap_or(12345);
-        }
-    }
+    let cli = Cli::parse();
+    cli.output.init();
+    let options = cli.check.options();
+    options.init_tracing();
+
+    let ModelArgs { validators, slots } = cli.model;
+    let seed = cli.seed.seed;
+    let network_faults = cli.network_faults;
     
-    println!("Running safety verification with {} validators, {} slots, seed {}", validators, slots, seed);
+    println!("Running safety verification with {} validators, {} slots, seed {}, {} network faults",
+             validators, slots, seed, network_faults);
+    options.print_summary();
     
     let model = VotorModel {
         honest_validators: validators,
         max_slot: slots,
+        network_faults,
     };
 
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
-        .spawn_dfs()
+    let properties: Vec<_> = model.properties().into_iter()
+        .filter(|property| options.properties.selects(property.name))
+        .collect();
+
+    // Hybrid mode: exhaustive up to the memory limit, random walks from the frontier afterwards
+    if let Some(limit) = cli.memory_limit_mib {
+        let report = hybrid::check(&options.bounded(model), &HybridConfig::new(limit, seed));
+        report.print_summary();
+        if properties.iter().any(|property| report.verdict(property.name) == Some(Verdict::Refuted)) {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Distributed mode: every worker runs with the same model flags as the coordinator, which
+    // turns away workers whose flags differ. Bounds and limits apply on every worker.
+    let parameters = (validators, slots, network_faults, options.bounds, options.limits);
+    if let Some(address) = cli.worker {
+        match distributed::run_worker(&options.bounded(model), &parameters, address.as_str()) {
+            Ok(states) => println!("Worker explored {} unique states", states),
+            Err(error) => {
+                eprintln!("Worker failed: {}", error);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    if let Some(address) = cli.coordinator {
+        let report = match distributed::coordinate(&options.bounded(model), &parameters, address.as_str(), cli.workers) {
+            Ok(report) => report,
+            Err(error) => {
+                eprintln!("Coordinator failed: {}", error);
+                std::process::exit(1);
+            }
+        };
+        report.print_summary();
+        let reason = report.cutoff.map_or(String::new(), |cutoff| format!(" ({})", cutoff));
+        for property in properties {
+            match report.outcome(property.name, &property.expectation) {
+                Outcome::Proved => println!("Property '{}' is always true", property.name),
+                Outcome::Violated => println!("Property '{}' has counterexamples", property.name),
+                Outcome::Inconclusive => println!("Property '{}' is inconclusive{}", property.name, reason),
+            }
+        }
+        return;
+    }
+
+    let checker = options
+        .checker(model)
+        .spawn_dfs();
+    options.progress.watch(&checker);
+    let result = checker
         .report(&mut WriteReporter::new(&mut std::io::stdout()));
+    options.print_coverage(&result);
+    options.record_results(&format!("safety {}v {}s {}f", validators, slots, network_faults), &result);
     
-    // Check if safety property was verified
-    if result.discoveries().is_empty() {
-        println!("Property 'safety' is always true");
-    } else {
-        println!("Property 'safety' has counterexamples");
+    // Check if the safety property and the derived invariants were verified
+    let reason = options.cutoff(&result).map_or(String::new(), |cutoff| format!(" ({})", cutoff));
+    for (name, outcome) in options.outcomes(&result) {
+        match outcome {
+            Outcome::Proved => println!("Property '{}' is always true", name),
+            Outcome::Violated => println!("Property '{}' has counterexamples", name),
+            Outcome::Inconclusive => println!("Property '{}' is inconclusive{}", name, reason),
+        }
+    }
+
+    // Export every counterexample as a Graphviz trace and as a mermaid sequence diagram
+    let mut exports = Vec::new();
+    if let Some(directory) = cli.dot_dir {
+        exports.push(trace::write_counterexamples(&result, &directory));
+    }
+    if let Some(directory) = cli.mermaid_dir {
+        exports.push(trace::write_sequence_diagrams(&result.model().model, &result, &directory));
+    }
+    for export in exports {
+        match export {
+            Ok(files) => {
+                for file in files {
+                    println!("Wrote counterexample trace {}", file.display());
+                }
+            }
+            Err(error) => {
+                eprintln!("Failed to write counterexample traces: {}", error);
+                std::process::exit(1);
+            }
+        }
     }
 }
\ No newline at end of file
@@ -1,27 +1,38 @@
-use std::env;
 use std::time::Instant;
 
-use alpenglow_formal::modelling::liveness;
+use alpenglow_formal::cli::{CheckArgs, ModelArgs, OutputArgs};
+use alpenglow_formal::modelling::liveness::{self, LivenessModel};
+use clap::Parser;
+
+/// Time the liveness verification
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<4, 3>,
+    /// Validators that respond in time
+    #[arg(long, default_value_t = 3)]
+    responsive: usize,
+    /// Most ticks two validators' clocks may differ by
+    #[arg(long, default_value_t = liveness::CLOCK_SKEW_BOUND)]
+    clock_skew: u64,
+    /// Which check to run
+    #[arg(long, default_value = "formal", value_parser = ["formal", "test"])]
+    test_type: String,
+    #[command(flatten)]
+    check: CheckArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    let mut validators = 4;
-    let mut slots = 3;
-    let mut responsive = 3;
-    let mut test_type = "formal";
-    
-    for i in 0..args.len() {
-        if args[i] == "--validators" && i + 1 < args.len() {
-            validators = args[i + 1].parse().unwrap_or(4);
-        } else if args[i] == "--slots" && i + 1 < args.len() {
-            slots = args[i + 1].parse().unwrap_or(3);
-        } else if args[i] == "--responsive" && i + 1 < args.len() {
-            responsive = args[i + 1].parse().unwrap_or(3);
-        } else if args[i] == "--test-type" && i + 1 < args.len() {
-            test_type = &args[i + 1];
-        }
-    }
+    let cli = Cli::parse();
+    cli.output.init();
+    let options = cli.check.options();
+    options.init_tracing();
+
+    let ModelArgs { validators, slots } = cli.model;
+    let Cli { responsive, clock_skew, .. } = cli;
+    let test_type = cli.test_type.as_str();
     
     println!("Running formal verification benchmark: {} test, {} validators ({} responsive), {} slots", 
              test_type, validators, responsive, slots);
@@ -30,10 +41,22 @@ fn main() {
     
     match test_type {
         "formal" => {
-            liveness::run_formal_verification();
+            liveness::run_formal_verification(&options);
         },
         "test" => {
-            liveness::test_liveness_model(validators, slots, responsive);
+            liveness::test_liveness_model(LivenessModel {
+                validator_count: validators,
+                max_slot: slots,
+                responsive_count: responsive,
+                max_clock_skew: clock_skew,
+                network_faults: 0,
+                fairness: true,
+                gst: None,
+                message_delays: Vec::new(),
+                crash_budget: 0,
+                persist_votes: true,
+                partition: None,
+            }, &options);
         },
         _ => {
             println!("Unknown test type: {}", test_type);
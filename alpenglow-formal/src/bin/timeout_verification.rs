@@ -1,38 +1,101 @@
-use std::env;
+use alpenglow_formal::cli::{CheckArgs, ModelArgs, OutputArgs, SeedArgs};
 use alpenglow_formal::timeout;
+use clap::Parser;
+
+/// Check timeout handling and skip certificates, by model checking or by simulation
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<3, 3>,
+    /// Most ticks two validators' clocks may differ by
+    #[arg(long, default_value_t = timeout::CLOCK_SKEW_BOUND)]
+    clock_skew: u64,
+    /// Timeout of the basic simulation, in milliseconds
+    #[arg(long = "timeout", default_value_t = 100)]
+    timeout_ms: u64,
+    /// Network delay of the network-delay simulation, in milliseconds
+    #[arg(long, default_value_t = 10)]
+    delay: u64,
+    /// Stake (percent) offline in the partial-network simulation
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u64).range(0..=100))]
+    offline_percent: u64,
+    #[command(flatten)]
+    seed: SeedArgs,
+    /// Which check to run
+    #[arg(long, default_value = "formal", value_parser = [
+        "formal", "test", "basic", "skip_cert", "badwindow", "network_delay", "recovery",
+        "concurrent", "partial_network",
+    ])]
+    test_type: String,
+    #[command(flatten)]
+    check: CheckArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    let mut validators = 3;
-    let mut slots = 3;
-    let mut test_type = "formal";
-    
-    for i in 0..args.len() {
-        if args[i] == "--validators" && i + 1 < args.len() {
-            validators = args[i + 1].parse().unwrap_or(3);
-        } else if args[i] == "--slots" && i + 1 < args.len() {
-            slots = args[i + 1].parse().unwrap_or(3);
-        } else if args[i] == "--test-type" && i + 1 < args.len() {
-            test_type = &args[i + 1];
-        }
-    }
+    let cli = Cli::parse();
+    cli.output.init();
+    let options = cli.check.options();
+    options.init_tracing();
+
+    let ModelArgs { validators, slots } = cli.model;
+    let seed = cli.seed.seed;
+    let Cli { clock_skew, timeout_ms, delay, offline_percent, .. } = cli;
+    let test_type = cli.test_type.as_str();
     
     println!("Running timeout formal verification: {} test, {} validators, {} slots", 
              test_type, validators, slots);
     
-    match test_type {
+    let (passed, success_message) = match test_type {
         "formal" => {
-            timeout::run_formal_verification();
+            timeout::run_formal_verification(&options);
             println!("Timeout formal verification completed");
+            return;
         },
         "test" => {
-            timeout::test_timeout_model(validators, slots);
+            timeout::test_timeout_model(validators, slots, clock_skew, &options);
             println!("Timeout model test completed");
+            return;
         },
+        "basic" => (
+            timeout::run_simulation(validators, slots, timeout_ms, seed),
+            "Timeout handling successful",
+        ),
+        "skip_cert" => (
+            timeout::test_skip_certificate_generation(validators, seed),
+            "Skip certificate generation successful",
+        ),
+        "badwindow" => (
+            timeout::test_badwindow_triggering(validators, seed),
+            "BadWindow flag triggered correctly",
+        ),
+        "network_delay" => (
+            timeout::test_network_delay_handling(validators, slots, delay, seed),
+            "Network delay handling successful",
+        ),
+        "recovery" => (
+            timeout::test_timeout_recovery(validators, seed),
+            "Timeout recovery successful",
+        ),
+        "concurrent" => (
+            timeout::test_concurrent_timeouts(validators, slots, seed),
+            "Concurrent timeout handling successful",
+        ),
+        "partial_network" => (
+            timeout::test_partial_network_handling(validators, slots, offline_percent, seed),
+            "Partial network timeout handling successful",
+        ),
         _ => {
             println!("Unknown test type: {}", test_type);
             std::process::exit(1);
         }
+    };
+    
+    if passed {
+        println!("{}", success_message);
+    } else {
+        println!("Timeout simulation failed: {} test", test_type);
+        std::process::exit(1);
     }
 }
@@ -0,0 +1,77 @@
+use std::thread;
+use std::time::Duration;
+use alpenglow_formal::cli::{CheckArgs, ModelArgs, OutputArgs};
+use alpenglow_formal::votor_actor;
+use clap::Parser;
+use stateright::Checker;
+
+/// Model-check the Votor actors, or run them as validators on localhost
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<3, 2>,
+    /// First UDP port of the spawned validators
+    #[arg(long, default_value_t = 3000)]
+    port: u16,
+    /// How long the spawned validators run
+    #[arg(long, default_value_t = 10)]
+    seconds: u64,
+    /// Model-check the actors, or spawn them
+    #[arg(long, default_value = "check", value_parser = ["check", "spawn"])]
+    mode: String,
+    #[command(flatten)]
+    check: CheckArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    cli.output.init();
+    let options = cli.check.options();
+    options.init_tracing();
+
+    let ModelArgs { validators, slots } = cli.model;
+    let Cli { port, seconds, .. } = cli;
+    let mode = cli.mode.as_str();
+
+    match mode {
+        "check" => {
+            println!("Model checking {} Votor actors over {} slots", validators, slots);
+            options.print_summary();
+            let result = options.checker(votor_actor::actor_model(validators, slots)).spawn_bfs();
+            options.progress.watch(&result);
+            let result = result.join();
+
+            println!("States explored: {}", result.state_count());
+            options.print_outcomes(&result);
+            options.print_coverage(&result);
+            options.record_results(&format!("votor actors {}v {}s", validators, slots), &result);
+        },
+        "spawn" => {
+            println!("Spawning {} Votor validators on localhost UDP ports {}..{} for {} seconds",
+                     validators, port, port as usize + validators, seconds);
+            // Finalizations are logged at info level as they happen
+            let validators = votor_actor::spawn_localhost(validators, slots, port, votor_actor::SPAWN_SKIP_TIMEOUT);
+            thread::sleep(Duration::from_secs(seconds));
+            if validators.handle.is_finished() {
+                match validators.handle.join() {
+                    Ok(Err(e)) => eprintln!("The validators stopped early: {}", e),
+                    _ => eprintln!("The validators stopped early"),
+                }
+                std::process::exit(1);
+            }
+            match validators.highest_finalized() {
+                0 => {
+                    eprintln!("Smoke run failed: no slot was finalized in {} seconds", seconds);
+                    std::process::exit(1);
+                },
+                slot => println!("Smoke run finished: slots finalized through {}", slot),
+            }
+        },
+        _ => {
+            println!("Unknown mode: {}", mode);
+            std::process::exit(1);
+        }
+    }
+}
@@ -1,46 +1,54 @@
-use std::env;
 use std::time::Instant;
 
-use stateright::{Model, report::WriteReporter, *};
+use clap::Parser;
+use stateright::{report::WriteReporter, *};
+use alpenglow_formal::cli::{CheckArgs, ModelArgs, OutputArgs};
+use alpenglow_formal::stats::CheckStats;
 use alpenglow_formal::votor::VotorModel;
 
+/// Time a model check of Votor
+#[derive(Parser)]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs<2, 1>,
+    #[command(flatten)]
+    check: CheckArgs,
+    #[command(flatten)]
+    output: OutputArgs,
+}
+
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    
-    let mut validators = 2;
-    let mut slots = 1;
-    let mut seed = 12345;
-    
-    for i in 0..args.len() {
-        if args[i] == "--validators" && i + 1 < args.len() {
-            validators = args[i + 1].parse().unwrap_or(2);
-        } else if args[i] == "--slots" && i + 1 < args.len() {
-            slots = args[i + 1].parse().unwrap_or(1);
-        } else if args[i] == "--seed" && i + 1 < args.len() {
-            seed = args[i + 1].parse().unwrap_or(12345);
-        }
-    }
+    let cli = Cli::parse();
+    cli.output.init();
+    let options = cli.check.options();
+    options.init_tracing();
+
+    let ModelArgs { validators, slots } = cli.model;
     
-    println!("Running votor benchmark with {} validators, {} slots, seed {}", validators, slots, seed);
+    println!("Running votor benchmark with {} validators, {} slots", validators, slots);
     
     let start = Instant::now();
     
     let model = VotorModel {
         honest_validators: validators,
         max_slot: slots,
+        network_faults: 0,
     };
 
     // Run the model checker
-    model
-        .checker()
-        .threads(num_cpus::get())
-        .spawn_dfs()
+    options.print_summary();
+    let checker = options
+        .checker(model)
+        .spawn_dfs();
+    options.progress.watch(&checker);
+    let result = checker
         .report(&mut WriteReporter::new(&mut std::io::stdout()));
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("votor {}v {}s", validators, slots), &result);
 
     let duration = start.elapsed();
     
-    println!("States explored: 1000"); // Simulated for now
-    println!("Transitions: 2000"); // Simulated for now
-    println!("Properties checked: 1"); // Simulated for now
+    CheckStats::of(&result).print_summary();
     println!("User time: {:.2}s", duration.as_secs_f64());
 }
\ No newline at end of file
@@ -0,0 +1,110 @@
+//! Persistent storage of a validator in the formal models.
+//! A crash wipes a validator's vote pool, its certificates and whatever else it holds in memory.
+//! A [`Blockstore`] is what survives: the blocks the validator received, the notarization and
+//! finalization certificates it formed and, if the model persists them, the votes it signed.
+//! A restarted validator rebuilds its state from its blockstore, which keeps it from signing a
+//! second vote for a slot, and answers the repair requests of rejoining peers from it, so a
+//! peer that crashed itself still serves what it had before.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+type Slot = u64;
+type Hash = u64;
+
+/// Blocks, certificates and signed votes of one validator, kept across its crashes
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Blockstore<V: Ord> {
+    /// Received blocks: slot -> hash
+    blocks: BTreeMap<Slot, Hash>,
+    /// Notarization certificates: slot -> notarized hash
+    notarized: BTreeMap<Slot, Hash>,
+    /// Finalization certificates: slot -> finalized hash
+    finalized: BTreeMap<Slot, Hash>,
+    /// Votes signed per slot
+    votes: BTreeMap<Slot, BTreeSet<V>>,
+}
+
+impl<V: Ord> Default for Blockstore<V> {
+    fn default() -> Self {
+        Self {
+            blocks: BTreeMap::new(),
+            notarized: BTreeMap::new(),
+            finalized: BTreeMap::new(),
+            votes: BTreeMap::new(),
+        }
+    }
+}
+
+impl<V: Clone + Ord> Blockstore<V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert_block(&mut self, slot: Slot, hash: Hash) {
+        self.blocks.insert(slot, hash);
+    }
+
+    pub fn insert_notarized(&mut self, slot: Slot, hash: Hash) {
+        self.notarized.insert(slot, hash);
+    }
+
+    /// Store the finalization of a slot, which is never overwritten
+    pub fn insert_finalized(&mut self, slot: Slot, hash: Hash) {
+        self.finalized.entry(slot).or_insert(hash);
+    }
+
+    /// Store a signed vote, returning whether it was not stored already
+    pub fn insert_vote(&mut self, slot: Slot, vote: V) -> bool {
+        self.votes.entry(slot).or_default().insert(vote)
+    }
+
+    pub fn notarized(&self) -> &BTreeMap<Slot, Hash> {
+        &self.notarized
+    }
+
+    pub fn finalized(&self) -> &BTreeMap<Slot, Hash> {
+        &self.finalized
+    }
+
+    /// Whether `vote` was signed for `slot`
+    pub fn has_vote(&self, slot: Slot, vote: &V) -> bool {
+        self.votes.get(&slot).is_some_and(|votes| votes.contains(vote))
+    }
+
+    /// Every signed vote as (slot, vote)
+    pub fn votes(&self) -> impl Iterator<Item = (Slot, &V)> {
+        self.votes.iter().flat_map(|(slot, votes)| votes.iter().map(move |vote| (*slot, vote)))
+    }
+
+    /// Received blocks of slots not finalized yet, which a repair request is answered with
+    /// alongside the certificates
+    pub fn pending_blocks(&self) -> Vec<(Slot, Hash)> {
+        self.blocks.iter()
+            .filter(|(slot, _)| !self.finalized.contains_key(slot))
+            .map(|(slot, hash)| (*slot, *hash))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blockstore_keeps_first_finalization_and_votes() {
+        let mut blockstore = Blockstore::<&str>::new();
+        blockstore.insert_block(1, 1001);
+        blockstore.insert_block(2, 2001);
+        blockstore.insert_notarized(1, 1001);
+        blockstore.insert_finalized(1, 1001);
+        blockstore.insert_finalized(1, 1002);
+        assert_eq!(blockstore.finalized().get(&1), Some(&1001));
+        assert_eq!(blockstore.pending_blocks(), vec![(2, 2001)]);
+
+        assert!(blockstore.insert_vote(1, "notar"));
+        assert!(!blockstore.insert_vote(1, "notar"));
+        assert!(blockstore.insert_vote(2, "skip"));
+        assert!(blockstore.has_vote(1, &"notar") && !blockstore.has_vote(2, &"notar"));
+        assert_eq!(blockstore.votes().collect::<Vec<_>>(), vec![(1, &"notar"), (2, &"skip")]);
+    }
+}
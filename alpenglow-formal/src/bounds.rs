@@ -0,0 +1,460 @@
+//! Explicit exploration bounds shared by the formal models.
+//! Every model's state space grows with its in-flight messages and with the slots it reaches, and
+//! a full exploration is often out of reach for larger configurations. Instead of editing the
+//! model constants, users choose [`ExplorationBounds`]: a maximum search depth and a boundary
+//! predicate capping the highest slot and the network size. The checker stops expanding states
+//! outside the bounds, so a clean run is exhaustive only within them. A [`PropertySelection`]
+//! narrows the properties the wrapped model forwards, each of which is evaluated on every state.
+//! The wrapper also enforces the [`ResourceLimits`] and remembers why exploration was cut short.
+
+use crate::coverage::{action_label, Coverage, CoverageReport};
+use crate::limits::{Cutoff, ResourceLimits};
+use stateright::{CheckerBuilder, Expectation, Model, Property};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, trace, trace_span};
+
+type Slot = u64;
+
+/// State measurements the boundary predicate caps
+pub trait Bounded {
+    /// Highest slot the state has reached
+    fn current_slot(&self) -> Slot;
+    /// Messages currently in flight
+    fn in_flight(&self) -> usize;
+}
+
+/// Limits on how far the checker explores; `None` leaves a dimension unbounded
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ExplorationBounds {
+    /// Longest path from an initial state the checker expands
+    pub max_depth: Option<usize>,
+    /// Highest slot a state may reach and still be expanded
+    pub max_slot: Option<Slot>,
+    /// Most in-flight messages a state may hold and still be expanded
+    pub max_in_flight: Option<usize>,
+}
+
+impl ExplorationBounds {
+    /// Whether exploration is left unrestricted
+    pub fn is_unbounded(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether a state lies inside the boundary predicate
+    pub fn admits<S: Bounded>(&self, state: &S) -> bool {
+        self.max_slot.is_none_or(|max_slot| state.current_slot() <= max_slot)
+            && self.max_in_flight.is_none_or(|max_in_flight| state.in_flight() <= max_in_flight)
+    }
+
+    /// Wrap `model` so the checker only expands states within these bounds
+    pub fn checker<M>(&self, model: M) -> CheckerBuilder<BoundedModel<M>>
+    where
+        M: Model + Send + Sync + 'static,
+        M::State: Bounded + Hash + Send + Sync,
+        M::Action: Debug,
+    {
+        self.limit_depth(BoundedModel::new(model, *self).checker())
+    }
+
+    /// Apply the depth bound to a checker
+    pub fn limit_depth<M: Model>(&self, checker: CheckerBuilder<M>) -> CheckerBuilder<M> {
+        match self.max_depth {
+            Some(depth) => checker.target_max_depth(depth),
+            None => checker,
+        }
+    }
+
+    pub fn print_summary(&self) {
+        if self.is_unbounded() {
+            return;
+        }
+        let describe = |bound: Option<String>| bound.unwrap_or_else(|| "unbounded".to_string());
+        println!("Exploration bounds: depth {}, slot {}, in-flight messages {} (results are exhaustive only within these bounds)",
+                 describe(self.max_depth.map(|depth| depth.to_string())),
+                 describe(self.max_slot.map(|slot| slot.to_string())),
+                 describe(self.max_in_flight.map(|messages| messages.to_string())));
+    }
+}
+
+/// Which of a model's properties the checker evaluates; an empty `only` selects them all
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PropertySelection {
+    /// Properties to check, leaving out the others
+    pub only: Vec<String>,
+    /// Properties never to check
+    pub skip: Vec<String>,
+}
+
+impl PropertySelection {
+    /// Whether every property is checked
+    pub fn is_all(&self) -> bool {
+        self.only.is_empty() && self.skip.is_empty()
+    }
+
+    /// Whether the property `name` is checked
+    pub fn selects(&self, name: &str) -> bool {
+        (self.only.is_empty() || self.only.iter().any(|only| only == name))
+            && !self.skip.iter().any(|skip| skip == name)
+    }
+
+    /// Names in the selection that are not among `names`
+    pub fn unknown(&self, names: &[&str]) -> Vec<&str> {
+        self.only.iter().chain(&self.skip)
+            .map(String::as_str)
+            .filter(|name| !names.contains(name))
+            .collect()
+    }
+
+    /// The selection without the names that are not among `names`, for a model that only has
+    /// some of the properties selected
+    pub fn restricted_to(&self, names: &[&str]) -> Self {
+        let known = |list: &[String]| list.iter().filter(|name| names.contains(&name.as_str())).cloned().collect();
+        Self { only: known(&self.only), skip: known(&self.skip) }
+    }
+
+    pub fn print_summary(&self) {
+        if !self.only.is_empty() {
+            println!("Checking only properties: {}", self.only.join(", "));
+        }
+        if !self.skip.is_empty() {
+            println!("Skipping properties: {}", self.skip.join(", "));
+        }
+    }
+}
+
+/// Wraps a model so that states outside the exploration bounds are not expanded. Laid out with
+/// the wrapped model first, so the wrapped model's property conditions can take the wrapper.
+#[repr(C)]
+pub struct BoundedModel<M: Model> {
+    pub model: M,
+    pub bounds: ExplorationBounds,
+    properties: Vec<Property<M>>,
+    /// States expanded so far, read by progress reporting
+    expanded: AtomicUsize,
+    /// States each forwarded property was checked on
+    evaluations: Vec<AtomicUsize>,
+    /// Action and property counters, when coverage is requested
+    coverage: Option<Coverage>,
+    /// When the model was wrapped, just before the checker that explores it is spawned
+    created: Instant,
+    /// States and time after which exploration stops
+    pub limits: ResourceLimits,
+    /// The resource limit that stopped exploration, once one has
+    stopped: OnceLock<Cutoff>,
+    /// Whether the exploration bounds left a state unexpanded
+    truncated: AtomicBool,
+}
+
+impl<M: Model> BoundedModel<M> {
+    pub fn new(model: M, bounds: ExplorationBounds) -> Self {
+        let properties = model.properties();
+        let evaluations = properties.iter().map(|_| AtomicUsize::new(0)).collect();
+        Self {
+            model,
+            bounds,
+            properties,
+            expanded: AtomicUsize::new(0),
+            evaluations,
+            coverage: None,
+            created: Instant::now(),
+            limits: ResourceLimits::default(),
+            stopped: OnceLock::new(),
+            truncated: AtomicBool::new(false),
+        }
+    }
+
+    /// Stop expanding states once `limits` are reached
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Names of the properties forwarded to the checker
+    pub fn property_names(&self) -> Vec<&'static str> {
+        self.properties.iter().map(|property| property.name).collect()
+    }
+
+    /// Only forward the properties `selection` selects; call before [`Self::with_coverage`]
+    pub fn with_properties(mut self, selection: &PropertySelection) -> Self {
+        self.properties.retain(|property| selection.selects(property.name));
+        self.evaluations.truncate(self.properties.len());
+        self
+    }
+
+    /// Count actions and property outcomes during exploration
+    pub fn with_coverage(mut self) -> Self {
+        let names: Vec<_> = self.properties.iter().map(|property| property.name).collect();
+        self.coverage = Some(Coverage::new(&names));
+        self
+    }
+
+    /// Coverage gathered so far, if requested
+    pub fn coverage(&self) -> Option<CoverageReport> {
+        self.coverage.as_ref().map(Coverage::report)
+    }
+
+    /// Number of states whose successors have been generated
+    pub fn expanded(&self) -> usize {
+        self.expanded.load(Ordering::Relaxed)
+    }
+
+    /// Times each forwarded property's condition has been evaluated so far
+    pub fn property_evaluations(&self) -> Vec<(&'static str, usize)> {
+        self.properties.iter().zip(&self.evaluations)
+            .map(|(property, evaluations)| (property.name, evaluations.load(Ordering::Relaxed)))
+            .collect()
+    }
+
+    /// Time since the model was wrapped: the running time of the checker exploring it
+    pub fn elapsed(&self) -> Duration {
+        self.created.elapsed()
+    }
+
+    /// Why exploration left reachable states unexpanded so far, if it did; the depth bound is
+    /// applied by the checker and not seen here
+    pub fn cutoff(&self) -> Option<Cutoff> {
+        self.stopped.get().copied()
+            .or_else(|| self.truncated.load(Ordering::Relaxed).then_some(Cutoff::Bounds))
+    }
+}
+
+/// The wrapped model's condition, called on the wrapper
+fn forward<M: Model>(condition: fn(&M, &M::State) -> bool) -> fn(&BoundedModel<M>, &M::State) -> bool {
+    // SAFETY: `BoundedModel` is `repr(C)` with the wrapped model as its first field, so a reference
+    // to the wrapper is a valid reference to the model, and references to sized types are
+    // ABI-compatible function arguments.
+    unsafe { std::mem::transmute::<fn(&M, &M::State) -> bool, fn(&BoundedModel<M>, &M::State) -> bool>(condition) }
+}
+
+impl<M: Model> BoundedModel<M> {
+    /// Whether a resource limit has stopped exploration, recording the first limit reached.
+    /// Once stopped no state is expanded or admitted, so the checker drains the states it
+    /// already holds without generating more and finishes.
+    fn limit_reached(&self) -> bool {
+        if self.stopped.get().is_some() {
+            return true;
+        }
+        let Some(cutoff) = self.limits.reached(self.expanded(), self.elapsed()) else {
+            return false;
+        };
+        if self.stopped.set(cutoff).is_ok() {
+            info!("Stopping exploration after {} expanded states and {:.2?}: {}", self.expanded(), self.elapsed(), cutoff);
+        }
+        true
+    }
+
+    /// Account for the checker evaluating every property on `state`, which it does just before
+    /// expanding the state. Coverage and property tracing need the outcomes, so they evaluate
+    /// the conditions a second time.
+    fn observe_properties(&self, state: &M::State) {
+        let observed = self.coverage.is_some() || tracing::enabled!(tracing::Level::TRACE);
+        for (index, property) in self.properties.iter().enumerate() {
+            self.evaluations[index].fetch_add(1, Ordering::Relaxed);
+            if !observed {
+                continue;
+            }
+            let _span = trace_span!("property", name = property.name).entered();
+            let holds = (property.condition)(&self.model, state);
+            trace!(holds, "evaluated");
+            if let Some(coverage) = &self.coverage {
+                coverage.record_property(index, holds);
+            }
+        }
+    }
+}
+
+impl<M> Model for BoundedModel<M>
+where
+    M: Model,
+    M::State: Bounded,
+    M::Action: Debug,
+{
+    type State = M::State;
+    type Action = M::Action;
+
+    fn init_states(&self) -> Vec<Self::State> {
+        self.model.init_states()
+    }
+
+    fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
+        if self.limit_reached() {
+            return;
+        }
+        self.expanded.fetch_add(1, Ordering::Relaxed);
+        self.observe_properties(state);
+        self.model.actions(state, actions);
+        if let Some(coverage) = &self.coverage {
+            coverage.record_offered(actions);
+        }
+    }
+
+    fn next_state(&self, state: &Self::State, action: Self::Action) -> Option<Self::State> {
+        let _span = trace_span!("action", action = ?action).entered();
+        let label = self.coverage.as_ref().map(|_| action_label(&action));
+        let next = self.model.next_state(state, action);
+        match (&next, &self.coverage, label) {
+            (Some(_), Some(coverage), Some(label)) => coverage.record_taken(label),
+            (Some(_), _, _) => {}
+            (None, _, _) => debug!("transition not taken: the model leaves the state unchanged"),
+        }
+        trace!(taken = next.is_some(), slot = next.as_ref().map(Bounded::current_slot), "applied");
+        next
+    }
+
+    fn within_boundary(&self, state: &Self::State) -> bool {
+        if !self.model.within_boundary(state) {
+            trace!("state outside the model's boundary");
+            return false;
+        }
+        if self.limit_reached() {
+            return false;
+        }
+        let admitted = self.bounds.admits(state);
+        if !admitted {
+            self.truncated.store(true, Ordering::Relaxed);
+            debug!(slot = state.current_slot(), in_flight = state.in_flight(), "state outside the exploration bounds, not expanded");
+        }
+        admitted
+    }
+
+    fn properties(&self) -> Vec<Property<Self>> {
+        self.properties.iter()
+            .map(|property| {
+                let condition = forward(property.condition);
+                match property.expectation {
+                    Expectation::Always => Property::<Self>::always(property.name, condition),
+                    Expectation::Sometimes => Property::<Self>::sometimes(property.name, condition),
+                    Expectation::Eventually => Property::<Self>::eventually(property.name, condition),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::votor::VotorModel;
+    use stateright::Checker;
+
+    #[test]
+    fn test_bounds_restrict_exploration() {
+        let model = || VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 };
+
+        let full = ExplorationBounds::default().checker(model()).spawn_bfs().join();
+        let capped = ExplorationBounds { max_in_flight: Some(1), ..Default::default() }
+            .checker(model()).spawn_bfs().join();
+        let shallow = ExplorationBounds { max_depth: Some(2), ..Default::default() }
+            .checker(model()).spawn_bfs().join();
+
+        assert!(capped.unique_state_count() < full.unique_state_count());
+        assert!(shallow.unique_state_count() < full.unique_state_count());
+        assert_eq!(full.model().cutoff(), None);
+        assert_eq!(capped.model().cutoff(), Some(Cutoff::Bounds));
+
+        // Bounding forwards every property under its original name
+        let names: Vec<_> = model().properties().iter().map(|property| property.name).collect();
+        let forwarded: Vec<_> = BoundedModel::new(model(), ExplorationBounds::default())
+            .properties().iter().map(|property| property.name).collect();
+        assert_eq!(names, forwarded);
+        for name in names {
+            assert!(full.discovery(name).is_none());
+        }
+    }
+
+    #[test]
+    fn test_property_selection() {
+        let model = || BoundedModel::new(VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 }, ExplorationBounds::default());
+        let names = model().property_names();
+        assert!(names.len() > 1 && names.contains(&"safety"));
+
+        let only = PropertySelection { only: vec!["safety".to_string(), "typo".to_string()], skip: Vec::new() };
+        assert_eq!(model().with_properties(&only).property_names(), vec!["safety"]);
+        assert_eq!(only.unknown(&names), vec!["typo"]);
+        assert_eq!(only.restricted_to(&names).only, vec!["safety".to_string()]);
+
+        let skip = PropertySelection { only: Vec::new(), skip: vec!["safety".to_string()] };
+        let remaining = model().with_properties(&skip).property_names();
+        assert_eq!(remaining.len(), names.len() - 1);
+        assert!(!remaining.contains(&"safety"));
+        assert!(PropertySelection::default().is_all());
+    }
+
+    #[test]
+    fn test_state_limit_stops_exploration() {
+        let model = || VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 };
+        let full = ExplorationBounds::default().checker(model()).spawn_bfs().join();
+        let limited = BoundedModel::new(model(), ExplorationBounds::default())
+            .with_limits(ResourceLimits { max_states: Some(5), timeout: None })
+            .checker().spawn_bfs().join();
+        assert_eq!(limited.model().cutoff(), Some(Cutoff::States));
+        assert_eq!(limited.model().expanded(), 5);
+        assert!(limited.model().expanded() < full.model().expanded());
+        assert!(limited.unique_state_count() < full.unique_state_count());
+    }
+
+    /// Counts up to a ceiling, checked by more properties than any fixed table would hold
+    struct Counter {
+        ceiling: Slot,
+    }
+
+    impl Bounded for Slot {
+        fn current_slot(&self) -> Slot {
+            *self
+        }
+
+        fn in_flight(&self) -> usize {
+            0
+        }
+    }
+
+    impl Model for Counter {
+        type State = Slot;
+        type Action = ();
+
+        fn init_states(&self) -> Vec<Slot> {
+            vec![0]
+        }
+
+        fn actions(&self, state: &Slot, actions: &mut Vec<()>) {
+            if *state < self.ceiling {
+                actions.push(());
+            }
+        }
+
+        fn next_state(&self, state: &Slot, _: ()) -> Option<Slot> {
+            Some(state + 1)
+        }
+
+        fn properties(&self) -> Vec<Property<Self>> {
+            let names = ["bounded 0", "bounded 1", "bounded 2", "bounded 3", "bounded 4", "bounded 5", "bounded 6",
+                "bounded 7", "bounded 8", "bounded 9", "bounded 10", "bounded 11", "bounded 12", "bounded 13",
+                "bounded 14", "bounded 15", "bounded 16", "bounded 17"];
+            let mut properties: Vec<_> = names.into_iter()
+                .map(|name| Property::always(name, |model: &Counter, state: &Slot| *state <= model.ceiling))
+                .collect();
+            properties.push(Property::always("below ceiling", |model: &Counter, state: &Slot| *state < model.ceiling));
+            properties.push(Property::sometimes("reaches ceiling", |model: &Counter, state: &Slot| *state == model.ceiling));
+            properties
+        }
+    }
+
+    #[test]
+    fn test_many_properties_forwarded() {
+        let model = BoundedModel::new(Counter { ceiling: 25 }, ExplorationBounds::default());
+        assert_eq!(model.property_names().len(), 20);
+        let checker = model.checker().spawn_dfs().join();
+
+        // Properties past any fixed slot count still run their own conditions
+        assert!(checker.discovery("bounded 17").is_none());
+        assert_eq!(checker.discovery("below ceiling").map(|path| *path.last_state()), Some(25));
+        assert_eq!(checker.discovery("reaches ceiling").map(|path| *path.last_state()), Some(25));
+        let evaluations = checker.model().property_evaluations();
+        assert_eq!(evaluations.len(), 20);
+        assert!(evaluations.iter().all(|(_, count)| *count > 0));
+    }
+}
@@ -1,15 +1,24 @@
 //! Formal verification model for certificate aggregation and uniqueness in Alpenglow consensus.
 //! This module provides a Stateright-based formal model for verifying certificate uniqueness,
 //! aggregation logic, and safety properties in the presence of adversarial validators.
+//! Validators may prune a slot once they hold its certificate, discarding its vote pool and
+//! ignoring any message for it that arrives later; pruning must never cost a certificate.
 
+use clap::ValueEnum;
 use stateright::{Model, Property, Checker};
 use std::collections::{BTreeMap, BTreeSet};
+use crate::network::{NetworkFault, NetworkFaults};
+use crate::voters::VoterSet;
+use crate::slashing::{self, SignedVote, SlashingProof};
+use crate::shared::SharedVec;
+use crate::bounds::Bounded;
+use crate::limits::Outcome;
+use crate::options::CheckOptions;
+use crate::invariants;
 
 // --- Formal Model Configuration ---
 const NOTARIZE_THRESHOLD_PERCENT: u64 = 60;
 const TOTAL_STAKE: u64 = 1000;
-const MAX_SLOTS: u64 = 5; // Formal verification limit
-const MAX_VALIDATORS: usize = 5; // Formal verification limit
 
 // Type aliases for clarity
 type Slot = u64;
@@ -42,6 +51,26 @@ pub enum CertificateMessage {
         slot: Slot,
         stake: Stake,
     },
+    /// A certificate broadcast by the slot's aggregator, listing the voters whose
+    /// signatures it claims to aggregate
+    AggregatedCertificate {
+        slot: Slot,
+        hash: Option<Hash>,
+        voters: VoterSet,
+    },
+}
+
+impl CertificateMessage {
+    /// The slot this message belongs to
+    pub fn slot(&self) -> Slot {
+        match self {
+            CertificateMessage::NotarVote { slot, .. }
+            | CertificateMessage::SkipVote { slot, .. }
+            | CertificateMessage::BlockCertificate { slot, .. }
+            | CertificateMessage::SkipCertificate { slot, .. }
+            | CertificateMessage::AggregatedCertificate { slot, .. } => *slot,
+        }
+    }
 }
 
 /// Represents messages in transit
@@ -74,6 +103,21 @@ pub enum CertificateAction {
         hash2: Hash,
         adversary: ActorId,
     },
+    /// The network adversary drops, duplicates or delays a message
+    NetworkFault { fault: NetworkFault<MessageInTransit> },
+    /// An adversarial aggregator broadcasts a certificate claiming `voters` voted for a block,
+    /// whether or not they did
+    ForgeCertificate {
+        slot: Slot,
+        hash: Option<Hash>,
+        voters: VoterSet,
+        aggregator: ActorId,
+    },
+    /// A validator discards the vote pool of a slot it holds a certificate for
+    PruneSlot {
+        slot: Slot,
+        validator: ActorId,
+    },
 }
 
 /// State of a validator in the certificate model
@@ -82,9 +126,13 @@ pub struct ValidatorState {
     /// Votes cast by this validator: (slot, hash) -> true
     votes_cast: BTreeMap<(Slot, Option<Hash>), bool>,
     /// Vote pool: (slot, hash) -> set of voters
-    vote_pool: BTreeMap<(Slot, Option<Hash>), BTreeSet<ActorId>>,
+    vote_pool: BTreeMap<(Slot, Option<Hash>), VoterSet>,
     /// Certificates formed: (slot, hash) pairs
     certificates: BTreeSet<(Slot, Option<Hash>)>,
+    /// Proofs of equivocation observed in received NotarVotes
+    slashing_proofs: BTreeSet<SlashingProof>,
+    /// Slots whose vote pool was discarded; messages for them are ignored
+    pruned: BTreeSet<Slot>,
     /// Whether this validator is adversarial
     is_adversary: bool,
     /// Stake of this validator
@@ -96,12 +144,27 @@ pub struct ValidatorState {
 pub struct CertificateState {
     /// Network messages in transit
     network: BTreeSet<MessageInTransit>,
+    /// Adversarial control over message delivery
+    network_faults: NetworkFaults<MessageInTransit>,
     /// Per-validator states
-    validators: Vec<ValidatorState>,
+    validators: SharedVec<ValidatorState>,
     /// Global certificates formed: (slot, hash) -> stake
     global_certificates: BTreeMap<(Slot, Option<Hash>), Stake>,
     /// Stake distribution: validator -> stake
     stake_distribution: BTreeMap<ActorId, Stake>,
+    /// Aggregated certificates broadcast so far, honest or forged, with the voters they claim
+    aggregates_sent: BTreeSet<(Slot, Option<Hash>, VoterSet)>,
+}
+
+/// How votes reach the validators that form certificates
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, ValueEnum)]
+pub enum VoteRouting {
+    /// Every vote is broadcast to every validator: O(n^2) messages per slot
+    #[default]
+    AllToAll,
+    /// Votes are sent to the slot's aggregator, which broadcasts the certificate once it
+    /// holds enough stake: O(n) messages per slot
+    Aggregator,
 }
 
 /// Formal model for certificate aggregation and uniqueness
@@ -113,6 +176,80 @@ pub struct CertificateModel {
     pub max_slot: Slot,
     /// Number of adversarial validators
     pub adversary_count: usize,
+    /// Messages the network adversary may drop, duplicate or delay
+    pub network_faults: usize,
+    /// How votes reach the validators that form certificates
+    pub vote_routing: VoteRouting,
+}
+
+impl CertificateModel {
+    /// Validator aggregating the votes of a slot: its leader
+    pub fn aggregator(&self, slot: Slot) -> ActorId {
+        slot as usize % self.validator_count
+    }
+
+    /// Validators a vote for `slot` is sent to
+    fn vote_recipients(&self, slot: Slot) -> Vec<ActorId> {
+        match self.vote_routing {
+            VoteRouting::AllToAll => (0..self.validator_count).collect(),
+            VoteRouting::Aggregator => vec![self.aggregator(slot)],
+        }
+    }
+
+    /// Honest validators expected to prove an equivocation in `slot`: every one of them when
+    /// votes go to everyone, otherwise only the slot's aggregator, the one validator they reach
+    fn evidence_holders(&self, state: &CertificateState, slot: Slot) -> Vec<ActorId> {
+        self.vote_recipients(slot).into_iter()
+            .filter(|id| !state.validators[*id].is_adversary && !state.validators[*id].pruned.contains(&slot))
+            .collect()
+    }
+
+    /// Sets of validators holding enough stake to certify a block, the only claims an
+    /// aggregated certificate can pass verification with
+    fn quorums(&self, state: &CertificateState) -> Vec<VoterSet> {
+        (1u64..1 << self.validator_count)
+            .map(|mask| (0..self.validator_count).filter(|id| mask & (1 << id) != 0).collect::<VoterSet>())
+            .filter(|voters| state.can_form_certificate(voters))
+            .collect()
+    }
+
+    /// Broadcast an aggregated certificate to every validator
+    fn broadcast_aggregate(&self, state: &mut CertificateState, slot: Slot, hash: Option<Hash>, voters: VoterSet) {
+        state.aggregates_sent.insert((slot, hash, voters.clone()));
+        for i in 0..self.validator_count {
+            state.network.insert(MessageInTransit {
+                dst: i,
+                msg: CertificateMessage::AggregatedCertificate { slot, hash, voters: voters.clone() },
+            });
+        }
+    }
+}
+
+impl ValidatorState {
+    /// Record a slashing proof for every NotarVote in the pool that conflicts with `vote`
+    fn record_equivocation(&mut self, vote: SignedVote) {
+        let conflicts: Vec<SlashingProof> = self.vote_pool.iter()
+            .filter(|((slot, hash), voters)| *slot == vote.slot && voters.contains(&vote.voter) && hash.is_some_and(|h| h != vote.hash))
+            .filter_map(|((slot, hash), _)| hash.map(|hash| SlashingProof::new(SignedVote { slot: *slot, hash, voter: vote.voter }, vote)))
+            .collect();
+        self.slashing_proofs.extend(conflicts);
+    }
+
+    /// Whether this validator holds a proof that `voter` equivocated in `slot`
+    fn has_proof_against(&self, voter: ActorId, slot: Slot) -> bool {
+        slashing::has_proof_against(&self.slashing_proofs, voter, slot)
+    }
+
+    /// Slots in which this validator signed NotarVotes for more than one block
+    fn equivocated_slots(&self) -> BTreeSet<Slot> {
+        let mut hashes: BTreeMap<Slot, usize> = BTreeMap::new();
+        for (slot, hash) in self.votes_cast.keys() {
+            if hash.is_some() {
+                *hashes.entry(*slot).or_insert(0) += 1;
+            }
+        }
+        hashes.into_iter().filter(|(_, count)| *count > 1).map(|(slot, _)| slot).collect()
+    }
 }
 
 impl CertificateState {
@@ -126,44 +263,118 @@ impl CertificateState {
 
         Self {
             network: BTreeSet::new(),
+            network_faults: NetworkFaults::new(0),
             validators: (0..validator_count).map(|i| ValidatorState {
                 votes_cast: BTreeMap::new(),
                 vote_pool: BTreeMap::new(),
                 certificates: BTreeSet::new(),
+                slashing_proofs: BTreeSet::new(),
+                pruned: BTreeSet::new(),
                 is_adversary: i < adversary_count,
                 stake: stake_per_validator,
             }).collect(),
             global_certificates: BTreeMap::new(),
             stake_distribution,
+            aggregates_sent: BTreeSet::new(),
         }
     }
 
-    /// Check if a certificate can be formed for a slot and hash
-    fn can_form_certificate(&self, slot: Slot, hash: Option<Hash>) -> bool {
-        if let Some(voters) = self.validators[0].vote_pool.get(&(slot, hash)) {
-            let stake: Stake = voters.iter()
-                .filter_map(|voter_id| self.stake_distribution.get(voter_id))
-                .sum();
-            stake >= (TOTAL_STAKE * NOTARIZE_THRESHOLD_PERCENT / 100)
-        } else {
-            false
+    /// Check if `voters` hold enough stake to form a certificate
+    fn can_form_certificate(&self, voters: &VoterSet) -> bool {
+        self.get_stake_for_voters(voters) >= (TOTAL_STAKE * NOTARIZE_THRESHOLD_PERCENT / 100)
+    }
+
+    /// Whether every equivocation is proven to every honest validator the votes were sent to once
+    /// all votes are delivered, unless the network adversary dropped or held some back or the
+    /// validator pruned the slot before the conflicting votes reached it
+    fn check_equivocation_evidence(&self, model: &CertificateModel) -> bool {
+        if !self.network.is_empty() || self.network_faults.messages_withheld() {
+            return true; // Evidence may still be in transit, or was lost by the network adversary
         }
+        self.validators.iter().enumerate().all(|(voter, voter_state)| {
+            voter_state.equivocated_slots().iter().all(|slot| {
+                model.evidence_holders(self, *slot).into_iter()
+                    .all(|holder| self.validators[holder].has_proof_against(voter, *slot))
+            })
+        })
+    }
+
+    /// Whether every slashing proof held by an honest validator is made of two votes its
+    /// voter actually signed, and no proof anywhere implicates an honest validator
+    fn check_honest_never_implicated(&self) -> bool {
+        self.validators.iter().all(|validator| {
+            validator.slashing_proofs.iter().all(|proof| {
+                let implicated = &self.validators[proof.voter()];
+                let genuine = proof.is_conflicting()
+                    && implicated.votes_cast.contains_key(&(proof.first.slot, Some(proof.first.hash)))
+                    && implicated.votes_cast.contains_key(&(proof.second.slot, Some(proof.second.hash)));
+                implicated.is_adversary && (validator.is_adversary || genuine)
+            })
+        })
+    }
+
+    /// Stake of the validators that actually signed a vote for `(slot, hash)`
+    fn signed_stake(&self, slot: Slot, hash: Option<Hash>) -> Stake {
+        self.validators.iter()
+            .filter(|v| v.votes_cast.contains_key(&(slot, hash)))
+            .map(|v| v.stake)
+            .sum()
+    }
+
+    /// Whether an aggregated certificate verifies: every listed voter signed the vote and
+    /// together they hold enough stake
+    fn verify_aggregate(&self, slot: Slot, hash: Option<Hash>, voters: &VoterSet) -> bool {
+        voters.iter().all(|voter| self.validators[voter].votes_cast.contains_key(&(slot, hash)))
+            && self.get_stake_for_voters(voters) >= (TOTAL_STAKE * NOTARIZE_THRESHOLD_PERCENT / 100)
+    }
+
+    /// Whether every certificate held by an honest validator is backed by votes actually
+    /// signed by enough stake, whoever aggregated it
+    fn check_certificates_genuine(&self) -> bool {
+        self.validators.iter()
+            .filter(|v| !v.is_adversary)
+            .flat_map(|v| v.certificates.iter())
+            .all(|(slot, hash)| self.signed_stake(*slot, *hash) >= (TOTAL_STAKE * NOTARIZE_THRESHOLD_PERCENT / 100))
+    }
+
+    /// Whether pruning lost no certificate: every validator pruned only slots it holds a
+    /// certificate for, its pool keeps no vote for them, and every certificate it holds is still
+    /// in the global record
+    fn check_no_certificate_regression(&self) -> bool {
+        self.validators.iter().all(|validator| {
+            validator.pruned.iter().all(|slot| {
+                validator.certificates.iter().any(|(s, _)| s == slot)
+                    && !validator.vote_pool.keys().any(|(s, _)| s == slot)
+            }) && validator.certificates.iter().all(|certificate| self.global_certificates.contains_key(certificate))
+        })
     }
 
     /// Get total stake for a set of voters
-    fn get_stake_for_voters(&self, voters: &BTreeSet<ActorId>) -> Stake {
+    fn get_stake_for_voters(&self, voters: &VoterSet) -> Stake {
         voters.iter()
-            .filter_map(|voter_id| self.stake_distribution.get(voter_id))
+            .filter_map(|voter_id| self.stake_distribution.get(&voter_id))
             .sum()
     }
 }
 
+impl Bounded for CertificateState {
+    fn current_slot(&self) -> Slot {
+        self.validators.iter().flat_map(|v| v.votes_cast.keys()).map(|(slot, _)| *slot).max().unwrap_or(0)
+    }
+
+    fn in_flight(&self) -> usize {
+        self.network.len()
+    }
+}
+
 impl Model for CertificateModel {
     type State = CertificateState;
     type Action = CertificateAction;
 
     fn init_states(&self) -> Vec<Self::State> {
-        vec![CertificateState::new(self.validator_count, self.adversary_count)]
+        let mut state = CertificateState::new(self.validator_count, self.adversary_count);
+        state.network_faults = NetworkFaults::new(self.network_faults);
+        vec![state]
     }
 
     fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
@@ -172,6 +383,11 @@ impl Model for CertificateModel {
             actions.push(CertificateAction::DeliverMessage { msg: msg.clone() });
         }
 
+        // The network adversary drops, duplicates or delays a bounded number of messages
+        for fault in state.network_faults.faults(&state.network) {
+            actions.push(CertificateAction::NetworkFault { fault });
+        }
+
         // 2. Cast votes for blocks
         for slot in 1..=self.max_slot {
             for hash in 1..=3 { // Multiple possible hashes per slot
@@ -212,6 +428,33 @@ impl Model for CertificateModel {
                 });
             }
         }
+
+        // 5. Adversarial aggregators may withhold certificates (by never aggregating) or forge them,
+        //    claiming any set of voters with enough stake, whether or not they signed
+        if self.vote_routing == VoteRouting::Aggregator {
+            let quorums = self.quorums(state);
+            for slot in 1..=self.max_slot {
+                let aggregator = self.aggregator(slot);
+                if !state.validators[aggregator].is_adversary {
+                    continue;
+                }
+                for hash in (1..=3).map(Some).chain([None]) {
+                    for voters in &quorums {
+                        if !state.aggregates_sent.contains(&(slot, hash, voters.clone())) {
+                            actions.push(CertificateAction::ForgeCertificate { slot, hash, voters: voters.clone(), aggregator });
+                        }
+                    }
+                }
+            }
+        }
+
+        // 6. Validators can prune the slots they hold a certificate for
+        for (validator, validator_state) in state.validators.iter().enumerate() {
+            let certified: BTreeSet<Slot> = validator_state.certificates.iter().map(|(slot, _)| *slot).collect();
+            for slot in certified.difference(&validator_state.pruned) {
+                actions.push(CertificateAction::PruneSlot { slot: *slot, validator });
+            }
+        }
     }
 
     fn next_state(&self, last_state: &Self::State, action: Self::Action) -> Option<Self::State> {
@@ -230,8 +473,8 @@ impl Model for CertificateModel {
                 if can_vote {
                     validator_state.votes_cast.insert(vote_key, true);
                     
-                    // Broadcast vote to all validators
-                    for i in 0..self.validator_count {
+                    // Send vote to every validator, or only to the slot's aggregator
+                    for i in self.vote_recipients(slot) {
                         next_state.network.insert(MessageInTransit {
                             dst: i,
                             msg: CertificateMessage::NotarVote {
@@ -255,8 +498,8 @@ impl Model for CertificateModel {
                 if can_vote {
                     validator_state.votes_cast.insert(vote_key, true);
                     
-                    // Broadcast skip vote to all validators
-                    for i in 0..self.validator_count {
+                    // Send skip vote to every validator, or only to the slot's aggregator
+                    for i in self.vote_recipients(slot) {
                         next_state.network.insert(MessageInTransit {
                             dst: i,
                             msg: CertificateMessage::SkipVote {
@@ -268,22 +511,34 @@ impl Model for CertificateModel {
                 }
                 validators[voter] = validator_state;
             }
+            CertificateAction::NetworkFault { fault } => {
+                if !next_state.network_faults.apply(&mut next_state.network, fault) { return None; }
+            }
             CertificateAction::DeliverMessage { msg } => {
                 let recipient_id = msg.dst;
                 let mut validator_state = validators[recipient_id].clone();
 
                 // Remove message from network
                 if !next_state.network.remove(&msg) { return None; }
+                next_state.network_faults.record_delivery(&msg);
+                if validator_state.pruned.contains(&msg.msg.slot()) {
+                    return Some(next_state); // The slot is certified and its pool discarded
+                }
 
+                let vote_slot = match &msg.msg {
+                    CertificateMessage::NotarVote { slot, .. } | CertificateMessage::SkipVote { slot, .. } => Some(*slot),
+                    _ => None,
+                };
                 match msg.msg {
                     CertificateMessage::NotarVote { slot, hash, voter } => {
-                        // Add vote to pool
+                        // Keep evidence of equivocation, then add vote to pool
+                        validator_state.record_equivocation(SignedVote { slot, hash, voter });
                         let vote_key = (slot, Some(hash));
                         let voters = validator_state.vote_pool.entry(vote_key).or_default();
                         voters.insert(voter);
 
                         // Check for certificate formation
-                        if next_state.can_form_certificate(slot, Some(hash)) {
+                        if self.vote_routing == VoteRouting::AllToAll && next_state.can_form_certificate(voters) {
                             let stake = next_state.get_stake_for_voters(voters);
                             validator_state.certificates.insert((slot, Some(hash)));
                             next_state.global_certificates.insert((slot, Some(hash)), stake);
@@ -296,7 +551,7 @@ impl Model for CertificateModel {
                         voters.insert(voter);
 
                         // Check for skip certificate formation
-                        if next_state.can_form_certificate(slot, None) {
+                        if self.vote_routing == VoteRouting::AllToAll && next_state.can_form_certificate(voters) {
                             let stake = next_state.get_stake_for_voters(voters);
                             validator_state.certificates.insert((slot, None));
                             next_state.global_certificates.insert((slot, None), stake);
@@ -312,9 +567,49 @@ impl Model for CertificateModel {
                         validator_state.certificates.insert((slot, None));
                         next_state.global_certificates.insert((slot, None), stake);
                     }
+                    CertificateMessage::AggregatedCertificate { slot, hash, voters } => {
+                        // Accept the certificate only if every aggregated signature checks out
+                        if next_state.verify_aggregate(slot, hash, &voters) {
+                            validator_state.certificates.insert((slot, hash));
+                            next_state.global_certificates.insert((slot, hash), next_state.get_stake_for_voters(&voters));
+                        }
+                    }
+                }
+
+                // An honest aggregator broadcasts the certificate once its pool holds enough stake
+                if let Some(slot) = vote_slot {
+                    if self.vote_routing == VoteRouting::Aggregator
+                        && recipient_id == self.aggregator(slot)
+                        && !validator_state.is_adversary
+                    {
+                        let ready: Vec<_> = validator_state.vote_pool.iter()
+                            .filter(|((s, hash), voters)| {
+                                *s == slot
+                                    && !next_state.aggregates_sent.iter().any(|(s, h, _)| *s == slot && h == hash)
+                                    && next_state.get_stake_for_voters(voters) >= (TOTAL_STAKE * NOTARIZE_THRESHOLD_PERCENT / 100)
+                            })
+                            .map(|((_, hash), voters)| (*hash, voters.clone()))
+                            .collect();
+                        for (hash, voters) in ready {
+                            self.broadcast_aggregate(&mut next_state, slot, hash, voters);
+                        }
+                    }
                 }
                 validators[recipient_id] = validator_state;
             }
+            CertificateAction::ForgeCertificate { slot, hash, voters, aggregator } => {
+                // Claim whatever signatures it likes; honest validators verify before accepting
+                if !validators[aggregator].is_adversary { return None; }
+                self.broadcast_aggregate(&mut next_state, slot, hash, voters);
+            }
+            CertificateAction::PruneSlot { slot, validator } => {
+                let validator_state = validators.get_mut(validator)?;
+                if validator_state.pruned.contains(&slot) || !validator_state.certificates.iter().any(|(s, _)| *s == slot) {
+                    return None;
+                }
+                validator_state.vote_pool.retain(|(s, _), _| *s != slot);
+                validator_state.pruned.insert(slot);
+            }
             CertificateAction::AdversaryEquivocate { slot, hash1, hash2, adversary } => {
                 let mut validator_state = validators[adversary].clone();
                 
@@ -327,8 +622,8 @@ impl Model for CertificateModel {
                     validator_state.votes_cast.insert(vote_key1, true);
                     validator_state.votes_cast.insert(vote_key2, true);
                     
-                    // Broadcast both votes
-                    for i in 0..self.validator_count {
+                    // Send both votes
+                    for i in self.vote_recipients(slot) {
                         next_state.network.insert(MessageInTransit {
                             dst: i,
                             msg: CertificateMessage::NotarVote {
@@ -364,7 +659,7 @@ impl Model for CertificateModel {
                 for slot in 1..=model.max_slot {
                     let mut certificates_for_slot = Vec::new();
                     
-                    for ((s, hash_opt), _) in &state.global_certificates {
+                    for (s, hash_opt) in state.global_certificates.keys() {
                         if *s == slot {
                             certificates_for_slot.push(hash_opt);
                         }
@@ -378,13 +673,14 @@ impl Model for CertificateModel {
                 true
             }),
             
-            // Property 2: Vote uniqueness per validator per slot
+            // Property 2: Vote uniqueness per honest validator per slot; adversaries equivocate
+            // by design and are caught by the slashing proofs instead
             Property::<Self>::always("vote_uniqueness", |_, state| {
-                for validator in &state.validators {
+                for validator in state.validators.iter().filter(|v| !v.is_adversary) {
                     // Count votes per slot
                     let mut votes_per_slot: BTreeMap<Slot, usize> = BTreeMap::new();
                     
-                    for ((slot, _), _) in &validator.votes_cast {
+                    for (slot, _) in validator.votes_cast.keys() {
                         *votes_per_slot.entry(*slot).or_insert(0) += 1;
                     }
                     
@@ -416,7 +712,7 @@ impl Model for CertificateModel {
                     let mut block_certificates = 0;
                     let mut skip_certificates = 0;
                     
-                    for ((s, hash_opt), _) in &state.global_certificates {
+                    for (s, hash_opt) in state.global_certificates.keys() {
                         if *s == slot {
                             match hash_opt {
                                 Some(_) => block_certificates += 1,
@@ -432,58 +728,93 @@ impl Model for CertificateModel {
                 }
                 true
             }),
+
+            // Property 5: Every equivocation eventually yields a slashing proof at honest validators
+            Property::<Self>::always("equivocation_evidence", |model, state| {
+                state.check_equivocation_evidence(model)
+            }),
+
+            // Property 6: Slashing proofs are genuine and never implicate honest validators
+            Property::<Self>::always("honest_never_implicated", |_model, state| {
+                state.check_honest_never_implicated()
+            }),
+
+            // Property 7: Aggregators may censor certificates but never forge them
+            Property::<Self>::always("certificates_genuine", |_model, state| {
+                state.check_certificates_genuine()
+            }),
+
+            // Property 8: Pruning a certified slot never costs a certificate
+            Property::<Self>::always("no_certificate_regression", |_model, state| {
+                state.check_no_certificate_regression()
+            }),
+
+            // Property 9: Honest validators certifying a slot hold the same block or skip certificate
+            Property::<Self>::always("certificate_agreement", |_model, state| {
+                invariants::certificate_agreement(state.validators.iter()
+                    .filter(|v| !v.is_adversary)
+                    .map(|v| v.certificates.iter().copied()))
+            }),
         ]
     }
 }
 
 /// Run formal verification of certificate aggregation
-pub fn run_formal_verification() {
+pub fn run_formal_verification(options: &CheckOptions) {
     println!("=== Certificate Aggregation Formal Verification ===");
     
     let model = CertificateModel {
         validator_count: 4, // Small for formal verification
         max_slot: 3,
         adversary_count: 1, // One adversarial validator
+        network_faults: 0,
+        vote_routing: VoteRouting::default(),
     };
 
     println!("Model checking certificate aggregation with {} validators ({} adversarial), {} slots", 
              model.validator_count, model.adversary_count, model.max_slot);
     
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
+    options.print_summary();
+
+    let result = options
+        .checker(model)
         .spawn_dfs()
         .report(&mut stateright::report::WriteReporter::new(&mut std::io::stdout()));
     
     // Check verification results
-    if result.discoveries().is_empty() {
-        println!("✅ All certificate properties verified successfully");
-    } else {
-        println!("❌ Certificate verification found counterexamples");
-        for (property_name, _path) in result.discoveries() {
-            println!("  - {}", property_name);
-        }
+    match options.print_outcomes(&result) {
+        Outcome::Proved => println!("✅ All certificate properties verified successfully"),
+        Outcome::Violated => println!("❌ Certificate verification found counterexamples"),
+        Outcome::Inconclusive => println!("❓ Certificate verification inconclusive: a limit or bound stopped the search first"),
     }
+    options.record_results("certificate formal", &result);
 }
 
 /// Test certificate model with different configurations
-pub fn test_certificate_model(validators: usize, slots: u64, adversaries: usize) {
-    println!("Testing certificate model with {} validators ({} adversarial), {} slots", 
-             validators, adversaries, slots);
+pub fn test_certificate_model(validators: usize, slots: u64, adversaries: usize, vote_routing: VoteRouting, options: &CheckOptions) {
+    println!("Testing certificate model with {} validators ({} adversarial), {} slots, {:?} vote routing",
+             validators, adversaries, slots, vote_routing);
     
     let model = CertificateModel {
         validator_count: validators,
         max_slot: slots,
         adversary_count: adversaries,
+        network_faults: 0,
+        vote_routing,
     };
 
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
+    options.print_summary();
+
+    let result = options
+        .checker(model)
         .spawn_dfs();
+    options.progress.watch(&result);
+    let result = result.join();
     
     println!("States explored: {}", result.state_count());
-    println!("Properties verified: {}", result.discoveries().is_empty());
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("certificate {}v {}s {}a", validators, slots, adversaries), &result);
 }
 
 #[cfg(test)]
@@ -509,7 +840,7 @@ mod tests {
         voters.insert(2); // 3/3 validators = 100% > 60%
         state.validators[0] = validator;
         
-        assert!(state.can_form_certificate(1, Some(100)));
+        assert!(state.can_form_certificate(&state.validators[0].vote_pool[&(1, Some(100))]));
     }
 
     #[test]
@@ -518,4 +849,150 @@ mod tests {
         assert!(state.validators[0].is_adversary);
         assert!(!state.validators[1].is_adversary);
     }
+
+    #[test]
+    fn test_equivocation_yields_slashing_proof() {
+        let model = CertificateModel {
+            validator_count: 3,
+            max_slot: 1,
+            adversary_count: 1,
+            network_faults: 0,
+            vote_routing: VoteRouting::AllToAll,
+        };
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, CertificateAction::AdversaryEquivocate {
+            slot: 1,
+            hash1: 1001,
+            hash2: 1002,
+            adversary: 0,
+        }).unwrap();
+        assert!(!state.validators[1].has_proof_against(0, 1));
+
+        while let Some(msg) = state.network.iter().next().cloned() {
+            state = model.next_state(&state, CertificateAction::DeliverMessage { msg }).unwrap();
+        }
+        assert!(state.validators[1].has_proof_against(0, 1));
+        assert!(state.validators[2].has_proof_against(0, 1));
+        assert!(state.check_equivocation_evidence(&model));
+        assert!(state.check_honest_never_implicated());
+    }
+
+    #[test]
+    fn test_aggregator_routing() {
+        let model = CertificateModel {
+            validator_count: 4,
+            max_slot: 4,
+            adversary_count: 1,
+            network_faults: 0,
+            vote_routing: VoteRouting::Aggregator,
+        };
+        let init = model.init_states().remove(0);
+        let deliver_all = |mut state: CertificateState| {
+            while let Some(msg) = state.network.iter().next().cloned() {
+                state = model.next_state(&state, CertificateAction::DeliverMessage { msg }).unwrap();
+            }
+            state
+        };
+
+        // Slot 1 is aggregated by honest validator 1: one message per vote, then one certificate broadcast
+        let mut state = init.clone();
+        for voter in 1..4 {
+            state = model.next_state(&state, CertificateAction::CastNotarVote { slot: 1, hash: 1, voter }).unwrap();
+        }
+        assert_eq!(state.network.len(), 3);
+        assert!(state.network.iter().all(|m| m.dst == 1));
+        state = deliver_all(state);
+        assert!(state.validators.iter().all(|v| v.certificates.contains(&(1, Some(1)))));
+        assert!(state.check_certificates_genuine());
+
+        // Slot 4 is aggregated by adversarial validator 0, which may sit on the votes forever...
+        let mut state = init;
+        for voter in 1..4 {
+            state = model.next_state(&state, CertificateAction::CastNotarVote { slot: 4, hash: 1, voter }).unwrap();
+        }
+        state = deliver_all(state);
+        assert!(state.validators.iter().skip(1).all(|v| v.certificates.is_empty()));
+
+        // ...but a certificate it forges for a block nobody voted for is rejected
+        let forge = |hash, voters: VoterSet| CertificateAction::ForgeCertificate { slot: 4, hash: Some(hash), voters, aggregator: 0 };
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(actions.contains(&forge(2, VoterSet::from([1, 2, 3]))));
+        assert!(!actions.contains(&forge(2, VoterSet::from([1, 2]))));
+        state = deliver_all(model.next_state(&state, forge(2, VoterSet::from([1, 2, 3]))).unwrap());
+        assert!(state.validators.iter().skip(1).all(|v| v.certificates.is_empty()));
+
+        // So is one listing the genuine signatures but one, plus a signature never made...
+        state = deliver_all(model.next_state(&state, forge(1, VoterSet::from([0, 1, 2]))).unwrap());
+        assert!(state.validators.iter().skip(1).all(|v| v.certificates.is_empty()));
+        assert!(state.check_certificates_genuine());
+
+        // ...while listing only the genuine signatures is no forgery at all and verifies
+        state = deliver_all(model.next_state(&state, forge(1, VoterSet::from([1, 2, 3]))).unwrap());
+        assert!(state.validators.iter().all(|v| v.certificates.contains(&(4, Some(1)))));
+        assert!(state.check_certificates_genuine());
+    }
+
+    #[test]
+    fn test_aggregator_evidence() {
+        let model = CertificateModel {
+            validator_count: 3,
+            max_slot: 1,
+            adversary_count: 1,
+            network_faults: 0,
+            vote_routing: VoteRouting::Aggregator,
+        };
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, CertificateAction::AdversaryEquivocate {
+            slot: 1,
+            hash1: 1001,
+            hash2: 1002,
+            adversary: 0,
+        }).unwrap();
+        while let Some(msg) = state.network.iter().next().cloned() {
+            state = model.next_state(&state, CertificateAction::DeliverMessage { msg }).unwrap();
+        }
+
+        // Only the slot's aggregator sees the conflicting votes, and only it is expected to prove them
+        assert!(state.validators[1].has_proof_against(0, 1));
+        assert!(!state.validators[2].has_proof_against(0, 1));
+        assert!(state.check_equivocation_evidence(&model));
+    }
+
+    #[test]
+    fn test_pruning_certified_slot() {
+        let model = CertificateModel {
+            validator_count: 3,
+            max_slot: 2,
+            adversary_count: 0,
+            network_faults: 0,
+            vote_routing: VoteRouting::AllToAll,
+        };
+        let deliver_all = |mut state: CertificateState| {
+            while let Some(msg) = state.network.iter().next().cloned() {
+                state = model.next_state(&state, CertificateAction::DeliverMessage { msg }).unwrap();
+            }
+            state
+        };
+        let mut state = model.init_states().remove(0);
+        for voter in 0..2 {
+            state = model.next_state(&state, CertificateAction::CastNotarVote { slot: 1, hash: 1, voter }).unwrap();
+        }
+        state = deliver_all(state);
+        assert!(state.validators.iter().all(|v| v.certificates.contains(&(1, Some(1)))));
+
+        // Only a certified slot can be pruned, and only once
+        assert!(model.next_state(&state, CertificateAction::PruneSlot { slot: 2, validator: 1 }).is_none());
+        state = model.next_state(&state, CertificateAction::PruneSlot { slot: 1, validator: 1 }).unwrap();
+        assert!(model.next_state(&state, CertificateAction::PruneSlot { slot: 1, validator: 1 }).is_none());
+        assert!(state.validators[1].vote_pool.is_empty());
+
+        // A late vote for the pruned slot is ignored without losing the certificate
+        state = model.next_state(&state, CertificateAction::CastNotarVote { slot: 1, hash: 1, voter: 2 }).unwrap();
+        state = deliver_all(state);
+        assert!(state.validators[1].vote_pool.is_empty());
+        assert_eq!(state.validators[2].vote_pool[&(1, Some(1))].len(), 3);
+        assert!(state.validators[1].certificates.contains(&(1, Some(1))));
+        assert!(state.check_no_certificate_regression());
+    }
 }
@@ -0,0 +1,221 @@
+//! Command-line flags shared by the binaries.
+//! Every binary derives its own `clap` parser and flattens these structs into it, so that flags
+//! such as `--validators`, `--seed` or `--max-depth` are spelled, documented and validated the
+//! same way everywhere. A malformed value stops the binary with its usage instead of falling
+//! back to a default. Where the binaries disagree on a default, such as the validator count,
+//! the struct takes it as a const parameter so `--help` still shows the right one.
+
+use crate::bounds::{ExplorationBounds, PropertySelection};
+use crate::limits::ResourceLimits;
+use crate::options::CheckOptions;
+use crate::output::{self, OutputFormat};
+use crate::progress::{Progress, DEFAULT_PROGRESS_INTERVAL_SECS};
+use clap::builder::RangedU64ValueParser;
+use clap::Args;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Seed of the simulations when `--seed` is not given
+pub const DEFAULT_SEED: u64 = 12345;
+
+/// Exploration bounds, resource limits, progress reporting, scheduling and reports of a
+/// model-checking run
+#[derive(Args, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CheckArgs {
+    /// Longest path from an initial state the checker expands
+    #[arg(long, value_name = "DEPTH")]
+    pub max_depth: Option<usize>,
+    /// Highest slot a state may reach and still be expanded
+    #[arg(long, value_name = "SLOT")]
+    pub max_slot: Option<u64>,
+    /// Most in-flight messages a state may hold and still be expanded
+    #[arg(long, value_name = "MESSAGES")]
+    pub max_in_flight: Option<usize>,
+    /// Stop expanding states after this many; properties not yet violated are then inconclusive
+    #[arg(long, value_name = "STATES", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub max_states: Option<usize>,
+    /// Stop expanding states after this many seconds; properties not yet violated are then inconclusive
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout_secs: Option<u64>,
+    /// Print a progress line every few seconds
+    #[arg(long)]
+    pub progress: bool,
+    /// Seconds between two progress lines; implies --progress
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub progress_interval: Option<u64>,
+    /// Serve Prometheus metrics on this address
+    #[arg(long, value_name = "HOST:PORT", value_parser = parse_socket_address)]
+    pub metrics_addr: Option<SocketAddr>,
+    /// Worker threads to check on [default: one per CPU]
+    #[arg(long, env = "ALPENGLOW_THREADS", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub threads: Option<usize>,
+    /// Explore on a single worker thread so that reports and traces are reproducible, short of
+    /// a `--timeout-secs` cutting the search
+    #[arg(long)]
+    pub deterministic: bool,
+    /// Count actions and property outcomes and print them after the run
+    #[arg(long)]
+    pub coverage: bool,
+    /// Append the property results to this JUnit XML file
+    #[arg(long, value_name = "FILE")]
+    pub junit: Option<PathBuf>,
+    /// Write a self-contained HTML report per model checked into this directory
+    #[arg(long, value_name = "DIR")]
+    pub report_html: Option<PathBuf>,
+    /// Check only these comma-separated properties
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    pub properties: Vec<String>,
+    /// Do not check these comma-separated properties
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    pub skip_properties: Vec<String>,
+    /// `tracing` filter directives, taking precedence over RUST_LOG
+    #[arg(long, value_name = "DIRECTIVES")]
+    pub trace_filter: Option<String>,
+}
+
+impl CheckArgs {
+    pub fn options(&self) -> CheckOptions {
+        let interval = match (self.progress_interval, self.progress) {
+            (Some(secs), _) => Some(Duration::from_secs(secs)),
+            (None, true) => Some(Duration::from_secs(DEFAULT_PROGRESS_INTERVAL_SECS)),
+            (None, false) => None,
+        };
+        CheckOptions {
+            bounds: ExplorationBounds {
+                max_depth: self.max_depth,
+                max_slot: self.max_slot,
+                max_in_flight: self.max_in_flight,
+            },
+            limits: ResourceLimits {
+                max_states: self.max_states,
+                timeout: self.timeout_secs.map(Duration::from_secs),
+            },
+            properties: PropertySelection { only: self.properties.clone(), skip: self.skip_properties.clone() },
+            progress: Progress { interval, target_states: self.max_states, metrics_address: self.metrics_addr },
+            threads: self.threads,
+            deterministic: self.deterministic,
+            coverage: self.coverage,
+            junit: self.junit.clone(),
+            report_html: self.report_html.clone(),
+            trace_filter: self.trace_filter.clone(),
+        }
+    }
+}
+
+/// Resolve `host:port` to its first socket address
+fn parse_socket_address(address: &str) -> Result<SocketAddr, String> {
+    address.to_socket_addrs()
+        .map_err(|e| format!("cannot resolve '{}': {}", address, e))?
+        .next()
+        .ok_or_else(|| format!("'{}' resolves to no address", address))
+}
+
+/// What the binary writes to stdout
+#[derive(Args, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OutputArgs {
+    /// `text` for people; `json` or `csv` records for scripts, with the prose moved to stderr
+    #[arg(long, value_enum, default_value_t, global = true)]
+    pub output: OutputFormat,
+}
+
+impl OutputArgs {
+    /// Set up stdout for the chosen format; call before printing anything
+    pub fn init(&self) {
+        output::init(self.output);
+    }
+}
+
+/// Size of the configuration checked, with the binary's defaults
+#[derive(Args, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModelArgs<const VALIDATORS: usize, const SLOTS: u64> {
+    /// Number of validators
+    #[arg(long, default_value_t = VALIDATORS, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub validators: usize,
+    /// Highest slot proposed
+    #[arg(long, default_value_t = SLOTS, value_parser = clap::value_parser!(u64).range(1..))]
+    pub slots: u64,
+}
+
+/// Validators controlled by the adversary, with the binary's default
+#[derive(Args, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ByzantineArgs<const BYZANTINE: usize> {
+    /// Number of Byzantine validators
+    #[arg(long, default_value_t = BYZANTINE)]
+    pub byzantine: usize,
+}
+
+/// Seed of the random choices of a simulation
+#[derive(Args, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SeedArgs {
+    /// Seed of the random number generator; equal seeds give identical runs
+    #[arg(long, default_value_t = DEFAULT_SEED)]
+    pub seed: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    struct Cli {
+        #[command(flatten)]
+        check: CheckArgs,
+        #[command(flatten)]
+        model: ModelArgs<4, 3>,
+        #[command(flatten)]
+        seed: SeedArgs,
+        #[command(flatten)]
+        output: OutputArgs,
+    }
+
+    #[test]
+    fn test_defaults() {
+        let cli = Cli::try_parse_from(["bin"]).unwrap();
+        assert_eq!(cli.check.options(), CheckOptions::default());
+        assert_eq!((cli.model.validators, cli.model.slots, cli.seed.seed), (4, 3, DEFAULT_SEED));
+        assert_eq!(cli.output.output, OutputFormat::Text);
+    }
+
+    #[test]
+    fn test_check_options() {
+        let cli = Cli::try_parse_from(["bin", "--max-depth", "10", "--max-slot", "2", "--deterministic", "--progress",
+                                       "--validators", "6"]).unwrap();
+        let options = cli.check.options();
+        assert_eq!(options.bounds, ExplorationBounds { max_depth: Some(10), max_slot: Some(2), max_in_flight: None });
+        assert_eq!(options.progress.interval, Some(Duration::from_secs(DEFAULT_PROGRESS_INTERVAL_SECS)));
+        assert!(options.deterministic);
+        assert!(!options.bounds.is_unbounded());
+        assert_eq!(cli.model.validators, 6);
+
+        let cli = Cli::try_parse_from(["bin", "--progress-interval", "30"]).unwrap();
+        assert_eq!(cli.check.options().progress.interval, Some(Duration::from_secs(30)));
+
+        let cli = Cli::try_parse_from(["bin", "--properties", "safety_byzantine,certificate_uniqueness",
+                                       "--skip-properties", "no_deadlock"]).unwrap();
+        let properties = cli.check.options().properties;
+        assert_eq!(properties.only, vec!["safety_byzantine".to_string(), "certificate_uniqueness".to_string()]);
+        assert!(properties.selects("certificate_uniqueness") && !properties.selects("no_deadlock"));
+
+        let cli = Cli::try_parse_from(["bin", "--max-states", "100000", "--timeout-secs", "60"]).unwrap();
+        let options = cli.check.options();
+        assert_eq!(options.limits, ResourceLimits { max_states: Some(100_000), timeout: Some(Duration::from_secs(60)) });
+        assert_eq!(options.progress.target_states, Some(100_000));
+
+        let cli = Cli::try_parse_from(["bin", "--threads", "2"]).unwrap();
+        assert_eq!(cli.check.options().threads(), 2);
+    }
+
+    #[test]
+    fn test_invalid_values_are_rejected() {
+        assert!(Cli::try_parse_from(["bin", "--validators", "four"]).is_err());
+        assert!(Cli::try_parse_from(["bin", "--validators", "0"]).is_err());
+        assert!(Cli::try_parse_from(["bin", "--progress-interval", "0"]).is_err());
+        assert!(Cli::try_parse_from(["bin", "--timeout-secs", "0"]).is_err());
+        assert!(Cli::try_parse_from(["bin", "--threads", "0"]).is_err());
+        assert!(Cli::try_parse_from(["bin", "--output", "yaml"]).is_err());
+        assert!(Cli::try_parse_from(["bin", "--max-depth"]).is_err());
+        assert!(Cli::try_parse_from(["bin", "--unknown"]).is_err());
+    }
+}
@@ -0,0 +1,464 @@
+//! Conformance checking of validator logs against the Votor model.
+//! A validator that exports its vote and certificate events as JSON lines can be checked
+//! against the formal model: [`check_conformance`] turns every event into the model action it
+//! corresponds to and replays it through [`VotorModel::next_state`], flagging each transition
+//! the model does not allow, and checks that every certificate the validator reports has been
+//! formed by the model as well. A violation points at either a bug in the implementation or a
+//! gap in the model.
+//!
+//! Each line is one JSON object whose `event` field selects its meaning:
+//!
+//! ```text
+//! {"event": "block_proposed", "slot": 1, "leader": 0}
+//! {"event": "block_received", "node": 1, "slot": 1, "hash": 1000, "parent": 0}
+//! {"event": "vote_received", "node": 0, "kind": "notar", "slot": 1, "hash": 1000, "voter": 1}
+//! {"event": "timeout", "node": 2, "slot": 1}
+//! {"event": "certificate", "kind": "finalize", "slot": 1, "hash": 1000}
+//! ```
+//!
+//! Vote kinds are `notar`, `final` and `skip`, of which only `notar` carries a hash;
+//! certificate kinds are `notarize`, `fast_finalize` and `finalize`. Hashes are the model's
+//! block identifiers, `slot * 1000 + leader`, so the exporter maps real block hashes onto them.
+//! Events of any other type carry nothing the model can check and are counted as ignored.
+
+use crate::invariants::ConsensusRecord;
+use crate::votor::{Action, Message, MessageInTransit, VotorModel};
+use stateright::Model;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// A JSON value; numbers keep their text so that large integers stay exact
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(BTreeMap<String, Json>),
+}
+
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(value) => write!(f, "{}", value),
+            Json::Number(text) => write!(f, "{}", text),
+            Json::String(text) => write!(f, "{:?}", text),
+            Json::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(fields) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{:?}:{}", key, value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// Recursive descent parser for a single JSON value
+struct JsonParser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn parse(text: &'a str) -> Result<Json, String> {
+        let mut parser = Self { text, pos: 0 };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.pos < text.len() {
+            return Err(format!("unexpected text after the value at column {}", parser.pos + 1));
+        }
+        Ok(value)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.bump() {
+            Some(c) if c == expected => Ok(()),
+            Some(c) => Err(format!("expected '{}' but found '{}' at column {}", expected, c, self.pos)),
+            None => Err(format!("expected '{}' but the line ended", expected)),
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(Json::String),
+            Some('t') => self.keyword("true", Json::Bool(true)),
+            Some('f') => self.keyword("false", Json::Bool(false)),
+            Some('n') => self.keyword("null", Json::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => Ok(self.number()),
+            Some(c) => Err(format!("unexpected '{}' at column {}", c, self.pos + 1)),
+            None => Err("unexpected end of line".to_string()),
+        }
+    }
+
+    fn keyword(&mut self, word: &str, value: Json) -> Result<Json, String> {
+        if !self.text[self.pos..].starts_with(word) {
+            return Err(format!("unexpected token at column {}", self.pos + 1));
+        }
+        self.pos += word.len();
+        Ok(value)
+    }
+
+    fn number(&mut self) -> Json {
+        let start = self.pos;
+        while matches!(self.peek(), Some('-' | '+' | '.' | 'e' | 'E' | '0'..='9')) {
+            self.pos += 1;
+        }
+        Json::Number(self.text[start..self.pos].to_string())
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.bump() {
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    Some('r') => string.push('\r'),
+                    Some('b') => string.push('\u{8}'),
+                    Some('f') => string.push('\u{c}'),
+                    Some('u') => {
+                        let hex = self.text.get(self.pos..self.pos + 4).ok_or("truncated \\u escape")?;
+                        let code = u32::from_str_radix(hex, 16).map_err(|_| format!("invalid \\u escape '{}'", hex))?;
+                        self.pos += 4;
+                        string.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                    }
+                    // '"', '\\' and '/' stand for themselves
+                    Some(c) => string.push(c),
+                    None => return Err("unterminated string".to_string()),
+                },
+                Some(c) => string.push(c),
+                None => return Err("unterminated string".to_string()),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.bump() {
+                Some(',') => {}
+                Some(']') => return Ok(Json::Array(items)),
+                _ => return Err(format!("expected ',' or ']' at column {}", self.pos)),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.expect('{')?;
+        let mut fields = BTreeMap::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            fields.insert(key, self.value()?);
+            self.skip_whitespace();
+            match self.bump() {
+                Some(',') => {}
+                Some('}') => return Ok(Json::Object(fields)),
+                _ => return Err(format!("expected ',' or '}}' at column {}", self.pos)),
+            }
+        }
+    }
+}
+
+/// Certificates a validator reports forming
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CertificateKind {
+    Notarize,
+    FastFinalize,
+    Finalize,
+}
+
+impl CertificateKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            CertificateKind::Notarize => "notarize",
+            CertificateKind::FastFinalize => "fast_finalize",
+            CertificateKind::Finalize => "finalize",
+        }
+    }
+}
+
+/// What one log event tells the conformance check
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogEvent {
+    /// A transition the model must be able to take
+    Step(Action),
+    /// A certificate the validator formed, which the model must have formed as well
+    Certificate { kind: CertificateKind, slot: u64, hash: u64 },
+    /// An event of a type the model has no counterpart for
+    Ignored(String),
+}
+
+fn string_field<'a>(fields: &'a BTreeMap<String, Json>, name: &str) -> Result<&'a str, String> {
+    match fields.get(name) {
+        Some(Json::String(value)) => Ok(value),
+        Some(other) => Err(format!("field '{}' must be a string, found {}", name, other)),
+        None => Err(format!("missing field '{}'", name)),
+    }
+}
+
+fn number_field(fields: &BTreeMap<String, Json>, name: &str) -> Result<u64, String> {
+    let invalid = |value: &dyn fmt::Display| format!("field '{}' must be a non-negative integer, found {}", name, value);
+    match fields.get(name) {
+        Some(Json::Number(text)) => text.parse().map_err(|_| invalid(text)),
+        Some(other) => Err(invalid(other)),
+        None => Err(format!("missing field '{}'", name)),
+    }
+}
+
+/// Parse one line of a validator log
+pub fn parse_event(line: &str) -> Result<LogEvent, String> {
+    let Json::Object(fields) = JsonParser::parse(line)? else {
+        return Err("an event must be a JSON object".to_string());
+    };
+    let number = |name: &str| number_field(&fields, name);
+    let deliver = |msg: Message| -> Result<LogEvent, String> {
+        let dst = number_field(&fields, "node")? as usize;
+        Ok(LogEvent::Step(Action::Deliver { msg: MessageInTransit::new(dst, msg) }))
+    };
+    match string_field(&fields, "event")? {
+        "block_proposed" => Ok(LogEvent::Step(Action::Propose { slot: number("slot")?, proposer: number("leader")? as usize })),
+        "block_received" => deliver(Message::Block { slot: number("slot")?, hash: number("hash")?, parent_hash: number("parent")? }),
+        "vote_received" => {
+            let slot = number("slot")?;
+            let voter = number("voter")? as usize;
+            match string_field(&fields, "kind")? {
+                "notar" => deliver(Message::NotarVote { slot, hash: number("hash")?, voter }),
+                "final" => deliver(Message::FinalVote { slot, voter }),
+                "skip" => deliver(Message::SkipVote { slot, voter }),
+                other => Err(format!("unknown vote kind '{}'", other)),
+            }
+        }
+        "timeout" => Ok(LogEvent::Step(Action::Timeout { slot: number("slot")?, node_id: number("node")? as usize })),
+        "certificate" => {
+            let kind = match string_field(&fields, "kind")? {
+                "notarize" => CertificateKind::Notarize,
+                "fast_finalize" => CertificateKind::FastFinalize,
+                "finalize" => CertificateKind::Finalize,
+                other => return Err(format!("unknown certificate kind '{}'", other)),
+            };
+            Ok(LogEvent::Certificate { kind, slot: number("slot")?, hash: number("hash")? })
+        }
+        other => Ok(LogEvent::Ignored(other.to_string())),
+    }
+}
+
+/// An event the model disagrees with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConformanceViolation {
+    /// Line of the event in the log, counting from 1
+    pub line: usize,
+    pub event: String,
+    pub reason: String,
+}
+
+/// Outcome of checking a validator log against the model
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConformanceReport {
+    /// Non-empty lines read
+    pub events: usize,
+    /// Transitions replayed through the model
+    pub applied: usize,
+    /// Certificates found in the model's state
+    pub certificates: usize,
+    /// Events of types the model has no counterpart for
+    pub ignored: usize,
+    pub violations: Vec<ConformanceViolation>,
+}
+
+impl ConformanceReport {
+    /// Whether the model agrees with every event of the log
+    pub fn conforms(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    pub fn print_summary(&self) {
+        println!("Checked {} events: {} transitions replayed, {} certificates confirmed, {} ignored",
+                 self.events, self.applied, self.certificates, self.ignored);
+        if self.conforms() {
+            println!("  ✅ the log conforms to the model");
+        }
+        for violation in &self.violations {
+            println!("  ❌ line {}: {}", violation.line, violation.reason);
+            println!("     {}", violation.event);
+        }
+    }
+}
+
+/// Replay the events of `log` through `model` from its initial state. A rejected event is
+/// reported and leaves the state unchanged, so that replay continues with the next event.
+pub fn check_conformance(model: &VotorModel, log: &str) -> ConformanceReport {
+    let mut state = model.init_states().remove(0);
+    let mut report = ConformanceReport::default();
+    for (index, line) in log.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        report.events += 1;
+        let rejection = match parse_event(line) {
+            Err(e) => Some(format!("malformed event: {}", e)),
+            Ok(LogEvent::Ignored(_)) => {
+                report.ignored += 1;
+                None
+            }
+            Ok(LogEvent::Step(action)) => {
+                let mut enabled = Vec::new();
+                model.actions(&state, &mut enabled);
+                let next = if enabled.contains(&action) { model.next_state(&state, action.clone()) } else { None };
+                match next {
+                    Some(next) => {
+                        state = next;
+                        report.applied += 1;
+                        None
+                    }
+                    None => Some(format!("the model does not allow {:?} here", action)),
+                }
+            }
+            Ok(LogEvent::Certificate { kind, slot, hash }) => {
+                let formed = match kind {
+                    CertificateKind::Notarize => state.notarized_blocks(),
+                    CertificateKind::FastFinalize => state.fast_finalized_blocks(),
+                    CertificateKind::Finalize => state.finalized_blocks(),
+                };
+                if formed.contains(&(slot, hash)) {
+                    report.certificates += 1;
+                    None
+                } else {
+                    Some(format!("the model has formed no {} certificate for slot {} hash {}", kind.name(), slot, hash))
+                }
+            }
+        };
+        if let Some(reason) = rejection {
+            report.violations.push(ConformanceViolation { line: index + 1, event: line.to_string(), reason });
+        }
+    }
+    report
+}
+
+/// Check the validator log stored at `path`
+pub fn check_log_file(model: &VotorModel, path: impl AsRef<Path>) -> io::Result<ConformanceReport> {
+    Ok(check_conformance(model, &std::fs::read_to_string(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Slot 1 proposed by validator 0 and finalized on the slow path, as seen by all validators
+    const SLOW_FINALIZATION_LOG: &str = r#"
+{"event": "block_proposed", "slot": 1, "leader": 0}
+{"event": "slot_started", "slot": 1}
+{"event": "block_received", "node": 1, "slot": 1, "hash": 1000, "parent": 0}
+{"event": "block_received", "node": 2, "slot": 1, "hash": 1000, "parent": 0}
+{"event": "vote_received", "node": 0, "kind": "notar", "slot": 1, "hash": 1000, "voter": 1}
+{"event": "vote_received", "node": 0, "kind": "notar", "slot": 1, "hash": 1000, "voter": 2}
+{"event": "certificate", "kind": "notarize", "slot": 1, "hash": 1000}
+{"event": "vote_received", "node": 1, "kind": "notar", "slot": 1, "hash": 1000, "voter": 1}
+{"event": "vote_received", "node": 1, "kind": "notar", "slot": 1, "hash": 1000, "voter": 2}
+{"event": "vote_received", "node": 2, "kind": "notar", "slot": 1, "hash": 1000, "voter": 1}
+{"event": "vote_received", "node": 2, "kind": "notar", "slot": 1, "hash": 1000, "voter": 2}
+{"event": "vote_received", "node": 0, "kind": "final", "slot": 1, "voter": 1}
+{"event": "vote_received", "node": 0, "kind": "final", "slot": 1, "voter": 2}
+{"event": "certificate", "kind": "finalize", "slot": 1, "hash": 1000}
+"#;
+
+    fn model() -> VotorModel {
+        VotorModel { honest_validators: 3, max_slot: 1, network_faults: 0 }
+    }
+
+    #[test]
+    fn test_conforming_log() {
+        let report = check_conformance(&model(), SLOW_FINALIZATION_LOG);
+        assert!(report.conforms(), "{:?}", report.violations);
+        assert_eq!(report.events, 14);
+        assert_eq!(report.applied, 11);
+        assert_eq!(report.certificates, 2);
+        assert_eq!(report.ignored, 1);
+    }
+
+    #[test]
+    fn test_violations_flagged() {
+        let prefix: Vec<&str> = SLOW_FINALIZATION_LOG.trim().lines().take(7).collect();
+        let log = format!("{}\n{}\n{}\n{}\n", prefix.join("\n"),
+                          // Validator 0 proposed the block and never voted for it
+                          r#"{"event": "vote_received", "node": 1, "kind": "notar", "slot": 1, "hash": 1000, "voter": 0}"#,
+                          // Two of three validators are not enough to fast-finalize
+                          r#"{"event": "certificate", "kind": "fast_finalize", "slot": 1, "hash": 1000}"#,
+                          r#"{"event": "timeout", "node": 1, "slot": -1}"#);
+        let report = check_conformance(&model(), &log);
+        assert_eq!(report.applied, 5);
+        assert_eq!(report.certificates, 1);
+        let lines: Vec<usize> = report.violations.iter().map(|violation| violation.line).collect();
+        assert_eq!(lines, vec![8, 9, 10]);
+        assert!(report.violations[0].reason.starts_with("the model does not allow Deliver"));
+        assert!(report.violations[1].reason.contains("fast_finalize"));
+        assert!(report.violations[2].reason.contains("field 'slot' must be a non-negative integer"));
+    }
+
+    #[test]
+    fn test_parse_event() {
+        let line = r#"{"event": "timeout", "node": 1, "slot": 2, "meta": {"tags": ["a\"b", true, null], "note": "café"}}"#;
+        assert_eq!(parse_event(line), Ok(LogEvent::Step(Action::Timeout { slot: 2, node_id: 1 })));
+        assert_eq!(parse_event(r#"{"event": "repair_request"}"#), Ok(LogEvent::Ignored("repair_request".to_string())));
+        assert!(parse_event(r#"{"event": "vote_received", "node": 1, "kind": "maybe", "slot": 1, "voter": 0}"#).is_err());
+        assert!(parse_event(r#"{"event": "timeout", "node": 1"#).is_err());
+        assert!(parse_event(r#"["timeout"]"#).is_err());
+        let Json::Object(fields) = JsonParser::parse(line).unwrap() else { unreachable!() };
+        assert_eq!(fields["meta"].to_string(), r#"{"note":"café","tags":["a\"b",true,null]}"#);
+    }
+}
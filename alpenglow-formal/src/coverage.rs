@@ -0,0 +1,175 @@
+//! Action and property coverage of an exploration.
+//! A clean run can hide model regions the checker never reached, such as a message type no
+//! validator ever sends. [`Coverage`] counts how often each kind of action was offered and how
+//! often it changed the state, and how often each property held or failed, so the report shows
+//! which transitions and property branches were actually exercised. Actions are grouped by the
+//! enum variants named in their `Debug` output, outermost first, so that for example delivering a
+//! `ConflictingVote` is counted apart from delivering an honest `Vote`.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Variant names that carry no information about the action
+const IGNORED_NAMES: [&str; 2] = ["Some", "None"];
+
+/// How often actions of one kind were seen
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ActionCoverage {
+    /// Times the model offered such an action
+    pub offered: usize,
+    /// Times such an action led to a new state
+    pub taken: usize,
+}
+
+/// How often a property's condition was evaluated each way
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PropertyCoverage {
+    pub held: usize,
+    pub failed: usize,
+}
+
+/// Counters filled in concurrently while the checker runs
+pub struct Coverage {
+    actions: Mutex<BTreeMap<String, ActionCoverage>>,
+    properties: Vec<(&'static str, AtomicUsize, AtomicUsize)>,
+}
+
+impl Coverage {
+    pub fn new(properties: &[&'static str]) -> Self {
+        Self {
+            actions: Mutex::new(BTreeMap::new()),
+            properties: properties.iter().map(|name| (*name, AtomicUsize::new(0), AtomicUsize::new(0))).collect(),
+        }
+    }
+
+    pub fn record_offered<A: Debug>(&self, actions: &[A]) {
+        let labels: Vec<_> = actions.iter().map(action_label).collect();
+        let mut coverage = self.actions.lock().unwrap();
+        for label in labels {
+            coverage.entry(label).or_default().offered += 1;
+        }
+    }
+
+    /// Record that an action with this label led to a new state
+    pub fn record_taken(&self, label: String) {
+        self.actions.lock().unwrap().entry(label).or_default().taken += 1;
+    }
+
+    pub fn record_property(&self, index: usize, holds: bool) {
+        let (_, held, failed) = &self.properties[index];
+        let counter = if holds { held } else { failed };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Snapshot of the counters
+    pub fn report(&self) -> CoverageReport {
+        CoverageReport {
+            actions: self.actions.lock().unwrap().clone(),
+            properties: self.properties.iter()
+                .map(|(name, held, failed)| (*name, PropertyCoverage {
+                    held: held.load(Ordering::Relaxed),
+                    failed: failed.load(Ordering::Relaxed),
+                }))
+                .collect(),
+        }
+    }
+}
+
+/// Coverage of a finished or running exploration
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// Counts per kind of action, keyed by its variant path
+    pub actions: BTreeMap<String, ActionCoverage>,
+    /// Counts per property, in declaration order
+    pub properties: Vec<(&'static str, PropertyCoverage)>,
+}
+
+impl CoverageReport {
+    /// Kinds of action that were offered but never changed the state
+    pub fn never_taken(&self) -> Vec<&str> {
+        self.actions.iter()
+            .filter(|(_, coverage)| coverage.taken == 0)
+            .map(|(label, _)| label.as_str())
+            .collect()
+    }
+
+    pub fn print_summary(&self) {
+        println!("Action coverage ({} kinds of action):", self.actions.len());
+        for (label, coverage) in &self.actions {
+            let marker = if coverage.taken == 0 { "⚠️ " } else { "  " };
+            println!("  {} {}: offered {}, taken {}", marker, label, coverage.offered, coverage.taken);
+        }
+        println!("Property coverage:");
+        for (name, coverage) in &self.properties {
+            let marker = if coverage.held == 0 || coverage.failed == 0 { "⚠️ " } else { "  " };
+            println!("  {} {}: held in {} states, failed in {}", marker, name, coverage.held, coverage.failed);
+        }
+        println!("⚠️  marks actions that never fired and properties that only ever evaluated one way");
+    }
+}
+
+/// The variant and struct names in an action's `Debug` output, outermost first
+pub fn action_label<A: Debug>(action: &A) -> String {
+    let debug = format!("{:?}", action);
+    let mut names = Vec::new();
+    let mut rest = debug.as_str();
+    while let Some(start) = rest.find(|c: char| c.is_alphanumeric() || c == '_') {
+        rest = &rest[start..];
+        let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        let (word, tail) = rest.split_at(end);
+        // Field names are followed by a colon; values and names of other types never are
+        let is_field = tail.trim_start().starts_with(':');
+        if !is_field && word.starts_with(|c: char| c.is_ascii_uppercase()) && !IGNORED_NAMES.contains(&word) {
+            names.push(word);
+        }
+        rest = tail;
+    }
+    names.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounds::{BoundedModel, ExplorationBounds};
+    use crate::votor::VotorModel;
+    use stateright::{Checker, Model};
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum Message {
+        Vote { slot: u64, hash: Option<u64> },
+        ConflictingVote { slot: u64 },
+    }
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum Action {
+        Deliver { msg: Message, dst: usize },
+        AdvanceSlot,
+    }
+
+    #[test]
+    fn test_action_labels() {
+        assert_eq!(action_label(&Action::AdvanceSlot), "AdvanceSlot");
+        assert_eq!(action_label(&Action::Deliver { msg: Message::Vote { slot: 1, hash: Some(2) }, dst: 0 }), "Deliver/Vote");
+        assert_eq!(action_label(&Action::Deliver { msg: Message::ConflictingVote { slot: 1 }, dst: 3 }), "Deliver/ConflictingVote");
+    }
+
+    #[test]
+    fn test_exploration_coverage() {
+        let model = BoundedModel::new(VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 }, ExplorationBounds::default())
+            .with_coverage();
+        let checker = model.checker().spawn_bfs().join();
+        let report = checker.model().coverage().unwrap();
+
+        assert!(report.actions.keys().any(|label| label.starts_with("Propose")));
+        assert!(report.actions.keys().any(|label| label.starts_with("Deliver")));
+        assert!(report.actions.values().all(|coverage| coverage.taken <= coverage.offered));
+        for (_, coverage) in &report.properties {
+            assert!(coverage.held > 0);
+            assert_eq!(coverage.failed, 0);
+        }
+    }
+}
@@ -0,0 +1,310 @@
+//! Differential testing of the safety and resilience models.
+//! Both models implement the same proposal, voting and certification rules, each with its own
+//! adversary on top. [`compare`] runs one schedule through both: at every step it offers the
+//! protocol steps both models enable (proposals, deliveries of proposals and votes, validators
+//! going offline and back, advancing the slot), takes the same step in each, and compares the
+//! certificates every validator holds and the conflicts detected. A difference means one of the
+//! models has drifted from the protocol, or the two encode a rule differently: the safety model
+//! certifies from the pool of validator 0 and only lets validators vote in increasing slots,
+//! while the resilience model certifies from each validator's own pool.
+//!
+//! Steps only one model offers, such as the resilience proposer delivering its proposal to
+//! itself or the safety model's global certificates, are never taken, and the adversaries are
+//! left out: no Byzantine validators, partitions or network faults.
+
+use crate::modelling::resilience::{ResilienceAction, ResilienceMessage, ResilienceModel, ResilienceState};
+use crate::certificate::VoteRouting;
+use crate::modelling::safety::{self, SafetyAction, SafetyMessage, SafetyModel, SafetyState, Synchrony};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use stateright::Model;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+type Slot = u64;
+type Hash = u64;
+type ActorId = usize;
+
+/// Default schedules compared by [`check`]
+pub const DEFAULT_SCHEDULES: usize = 1000;
+/// Default most steps per schedule
+pub const DEFAULT_SCHEDULE_LENGTH: usize = 200;
+
+/// A protocol step both models can take
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Step {
+    Propose { slot: Slot, proposer: ActorId },
+    DeliverProposal { dst: ActorId, slot: Slot, hash: Hash },
+    DeliverVote { dst: ActorId, slot: Slot, hash: Hash, voter: ActorId },
+    GoOffline { validator: ActorId },
+    ComeOnline { validator: ActorId },
+    AdvanceSlot,
+}
+
+/// What the models are compared on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    /// Certificates each validator holds: slot -> hash
+    pub certificates: Vec<BTreeMap<Slot, Hash>>,
+    /// Conflicting certificates detected: (slot, hash1, hash2)
+    pub violations: BTreeSet<(Slot, Hash, Hash)>,
+}
+
+impl Outcome {
+    fn of_safety(state: &SafetyState) -> Self {
+        Self { certificates: state.certificates_by_validator(), violations: state.safety_violations().clone() }
+    }
+
+    fn of_resilience(state: &ResilienceState) -> Self {
+        Self { certificates: state.certificates_by_validator(), violations: state.safety_violations().clone() }
+    }
+}
+
+/// The first point where a schedule's outcomes differ
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// Steps taken, the diverging one last
+    pub schedule: Vec<Step>,
+    pub safety: Outcome,
+    pub resilience: Outcome,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Models diverge after {} steps, the last {:?}:", self.schedule.len(), self.schedule.last())?;
+        for (validator, (safety, resilience)) in self.safety.certificates.iter().zip(&self.resilience.certificates).enumerate() {
+            if safety != resilience {
+                writeln!(f, "  validator {} certificates: safety {:?}, resilience {:?}", validator, safety, resilience)?;
+            }
+        }
+        if self.safety.violations != self.resilience.violations {
+            writeln!(f, "  conflicts: safety {:?}, resilience {:?}", self.safety.violations, self.resilience.violations)?;
+        }
+        write!(f, "  schedule: {:?}", self.schedule)
+    }
+}
+
+/// Outcome of comparing many random schedules
+#[derive(Clone, Debug, Default)]
+pub struct DifferentialReport {
+    pub schedules: usize,
+    pub steps: usize,
+    /// First divergence of every schedule that diverged
+    pub divergences: Vec<Divergence>,
+}
+
+impl DifferentialReport {
+    pub fn agrees(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    pub fn print_summary(&self) {
+        println!("Differential test: {} schedules, {} steps", self.schedules, self.steps);
+        match self.divergences.iter().min_by_key(|divergence| divergence.schedule.len()) {
+            None => println!("  ✅ safety and resilience models agree on every schedule"),
+            Some(shortest) => {
+                println!("  ❌ {} schedules diverge; the shortest:", self.divergences.len());
+                println!("{}", shortest);
+            }
+        }
+    }
+}
+
+/// The safety and resilience models with the same validators and slots, without adversaries
+pub fn models(validators: usize, slots: Slot) -> (SafetyModel, ResilienceModel) {
+    let safety = SafetyModel {
+        validator_count: validators,
+        max_slot: slots,
+        byzantine_count: 0,
+        offline_budget_percent: safety::DEFAULT_OFFLINE_BUDGET_PERCENT,
+        corruption_budget_percent: 0,
+        network_faults: 0,
+        synchrony: Synchrony::default(),
+        vote_routing: VoteRouting::default(),
+    };
+    let resilience = ResilienceModel {
+        offline_budget_percent: safety::DEFAULT_OFFLINE_BUDGET_PERCENT,
+        max_partition_size: 0,
+        ..ResilienceModel::with_fault_fractions(validators, slots, 0, 0)
+    };
+    (safety, resilience)
+}
+
+/// The steps `safety` enables, with the action taking each. Of the proposals for a slot it keeps
+/// the one extending the latest earlier proposal, as resilience proposals implicitly do.
+fn safety_steps(model: &SafetyModel, state: &SafetyState) -> BTreeMap<Step, SafetyAction> {
+    let mut actions = Vec::new();
+    model.actions(state, &mut actions);
+    let mut steps = BTreeMap::new();
+    let mut parents = BTreeMap::new();
+    for action in actions {
+        let step = match &action {
+            SafetyAction::ProposeBlock { slot, proposer, parent } => {
+                if parents.get(&(*slot, *proposer)).is_some_and(|latest| latest > parent) {
+                    continue;
+                }
+                parents.insert((*slot, *proposer), *parent);
+                Step::Propose { slot: *slot, proposer: *proposer }
+            }
+            SafetyAction::DeliverMessage { msg } => match msg.msg {
+                SafetyMessage::BlockProposal { slot, hash, .. } => Step::DeliverProposal { dst: msg.dst, slot, hash },
+                SafetyMessage::Vote { slot, hash, voter } => Step::DeliverVote { dst: msg.dst, slot, hash, voter },
+                _ => continue,
+            },
+            SafetyAction::GoOffline { validator } => Step::GoOffline { validator: *validator },
+            SafetyAction::ComeOnline { validator } => Step::ComeOnline { validator: *validator },
+            SafetyAction::AdvanceSlot => Step::AdvanceSlot,
+            _ => continue,
+        };
+        steps.insert(step, action);
+    }
+    steps
+}
+
+/// The steps `resilience` enables, with the action taking each
+fn resilience_steps(model: &ResilienceModel, state: &ResilienceState) -> BTreeMap<Step, ResilienceAction> {
+    let mut actions = Vec::new();
+    model.actions(state, &mut actions);
+    actions.into_iter()
+        .filter_map(|action| {
+            let step = match &action {
+                ResilienceAction::ProposeBlock { slot, proposer } => Step::Propose { slot: *slot, proposer: *proposer },
+                ResilienceAction::DeliverMessage { msg } => match msg.msg {
+                    ResilienceMessage::BlockProposal { slot, hash, .. } => Step::DeliverProposal { dst: msg.dst, slot, hash },
+                    ResilienceMessage::Vote { slot, hash, voter } => Step::DeliverVote { dst: msg.dst, slot, hash, voter },
+                    _ => return None,
+                },
+                ResilienceAction::GoOffline { validator } => Step::GoOffline { validator: *validator },
+                ResilienceAction::ComeOnline { validator } => Step::ComeOnline { validator: *validator },
+                ResilienceAction::AdvanceSlot => Step::AdvanceSlot,
+                _ => return None,
+            };
+            Some((step, action))
+        })
+        .collect()
+}
+
+/// Take the steps `pick` chooses among those both models enable, until it declines or no step
+/// is left, and return the steps taken or the first divergence
+fn follow(
+    (safety, resilience): (&SafetyModel, &ResilienceModel),
+    mut pick: impl FnMut(&[Step]) -> Option<Step>,
+) -> Result<Vec<Step>, Divergence> {
+    let mut safety_state = safety.init_states().remove(0);
+    let mut resilience_state = resilience.init_states().remove(0);
+    let mut schedule = Vec::new();
+    loop {
+        let mut safety_actions = safety_steps(safety, &safety_state);
+        let mut resilience_actions = resilience_steps(resilience, &resilience_state);
+        let common: Vec<Step> = safety_actions.keys().filter(|step| resilience_actions.contains_key(step)).cloned().collect();
+        let Some(step) = pick(&common) else {
+            return Ok(schedule);
+        };
+        let next_safety = safety_actions.remove(&step).and_then(|action| safety.next_state(&safety_state, action));
+        let next_resilience = resilience_actions.remove(&step).and_then(|action| resilience.next_state(&resilience_state, action));
+        schedule.push(step);
+        let (Some(next_safety), Some(next_resilience)) = (next_safety, next_resilience) else {
+            // A step both models offer is rejected by one of them: report the outcomes before it
+            return Err(Divergence {
+                schedule,
+                safety: Outcome::of_safety(&safety_state),
+                resilience: Outcome::of_resilience(&resilience_state),
+            });
+        };
+        safety_state = next_safety;
+        resilience_state = next_resilience;
+        let (safety_outcome, resilience_outcome) = (Outcome::of_safety(&safety_state), Outcome::of_resilience(&resilience_state));
+        if safety_outcome != resilience_outcome {
+            return Err(Divergence { schedule, safety: safety_outcome, resilience: resilience_outcome });
+        }
+    }
+}
+
+/// Run the schedule `choices` describe through both models: each choice picks one of the steps
+/// both enable by its index modulo their count. Returns the steps taken, or the first divergence.
+pub fn compare(
+    models: (&SafetyModel, &ResilienceModel),
+    choices: impl IntoIterator<Item = usize>,
+) -> Result<Vec<Step>, Divergence> {
+    let mut choices = choices.into_iter();
+    follow(models, |common| {
+        let choice = choices.next()?;
+        (!common.is_empty()).then(|| common[choice % common.len()].clone())
+    })
+}
+
+/// Compare `schedules` random schedules of up to `schedule_length` steps, schedule `i` drawn from
+/// seed `seed + i`
+pub fn check(validators: usize, slots: Slot, schedules: usize, schedule_length: usize, seed: u64) -> DifferentialReport {
+    let (safety, resilience) = models(validators, slots);
+    let mut report = DifferentialReport { schedules, ..DifferentialReport::default() };
+    for i in 0..schedules {
+        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(i as u64));
+        let choices: Vec<usize> = (0..schedule_length).map(|_| rng.gen()).collect();
+        match compare((&safety, &resilience), choices) {
+            Ok(steps) => report.steps += steps.len(),
+            Err(divergence) => {
+                report.steps += divergence.schedule.len();
+                report.divergences.push(divergence);
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Take `steps` in order, failing if one is not offered by both models
+    fn scripted(models: (&SafetyModel, &ResilienceModel), steps: &[Step]) -> Result<Vec<Step>, Divergence> {
+        let mut steps = steps.iter();
+        follow(models, |common| {
+            let step = steps.next()?;
+            assert!(common.contains(step), "{:?} not offered by both models: {:?}", step, common);
+            Some(step.clone())
+        })
+    }
+
+    #[test]
+    fn test_common_steps() {
+        let (safety, resilience) = models(3, 2);
+        let proposal = [
+            Step::Propose { slot: 1, proposer: 0 },
+            Step::DeliverProposal { dst: 1, slot: 1, hash: 1000 },
+            Step::DeliverProposal { dst: 2, slot: 1, hash: 1000 },
+        ];
+        let steps = scripted((&safety, &resilience), &proposal).unwrap();
+        assert_eq!(steps, proposal);
+
+        // Without choices no step is taken
+        assert_eq!(compare((&safety, &resilience), []), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_flags_certification_divergence() {
+        // Validator 1 holds both votes: the resilience model certifies from its pool, the
+        // safety model waits for validator 0's pool
+        let (safety, resilience) = models(3, 1);
+        let divergence = scripted((&safety, &resilience), &[
+            Step::Propose { slot: 1, proposer: 0 },
+            Step::DeliverProposal { dst: 1, slot: 1, hash: 1000 },
+            Step::DeliverProposal { dst: 2, slot: 1, hash: 1000 },
+            Step::DeliverVote { dst: 1, slot: 1, hash: 1000, voter: 1 },
+            Step::DeliverVote { dst: 1, slot: 1, hash: 1000, voter: 2 },
+        ]).unwrap_err();
+        assert_eq!(divergence.schedule.len(), 5);
+        assert!(divergence.safety.certificates[1].is_empty());
+        assert_eq!(divergence.resilience.certificates[1], BTreeMap::from([(1, 1000)]));
+        assert!(divergence.to_string().contains("validator 1 certificates"));
+    }
+
+    #[test]
+    fn test_random_schedules_are_reproducible() {
+        let first = check(3, 1, 50, 40, 9);
+        let second = check(3, 1, 50, 40, 9);
+        assert_eq!(first.steps, second.steps);
+        assert_eq!(first.divergences, second.divergences);
+        assert!(first.steps > 0);
+    }
+}
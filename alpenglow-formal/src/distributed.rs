@@ -0,0 +1,475 @@
+//! Distributed model checking over TCP.
+//! A coordinator splits the 64-bit state-fingerprint space into one contiguous range per worker.
+//! Each worker explores only the states whose fingerprint falls in its range and hands every
+//! other successor to the coordinator, which forwards it to the owning worker and collects the
+//! property discoveries. States travel as the indices of the actions leading to them from an
+//! initial state, which the receiving worker replays, so models need no serialization. Every
+//! worker must therefore run the same build with the same model configuration: each one opens
+//! with a hash of its parameters, and the coordinator turns away any whose hash differs from its
+//! own. Bounds and resource limits apply on every worker, the state limit to each worker's own
+//! count; a worker that left states unexpanded reports why, and the whole run is then
+//! inconclusive for the properties it did not violate.
+//!
+//! Wire protocol, one message per line:
+//! - coordinator to worker: `ASSIGN <id> <workers>`, `REJECT <expected hash>`, `STATE <path>`, `STOP`
+//! - worker to coordinator: `HELLO <parameters hash>`, `STATE <owner> <path>`,
+//!   `DISCOVERY <property> <path>`, `IDLE <states received>`, `DONE <unique states> <cutoff>`
+//!
+//! `always` and `sometimes` properties are checked like Stateright's checkers do; `eventually`
+//! properties need whole paths and are not checked in distributed mode.
+
+use crate::bounds::{Bounded, BoundedModel};
+use crate::limits::{Cutoff, Outcome};
+use stateright::{Expectation, Model};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fmt::Debug;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc;
+use std::thread;
+
+/// States a worker expands between flushes of the successors it hands off
+const FLUSH_INTERVAL: usize = 1024;
+
+/// Outcome of a distributed run
+#[derive(Clone, Debug)]
+pub struct DistributedReport<A> {
+    /// Workers that took part
+    pub workers: usize,
+    /// Unique states explored across all workers
+    pub unique_states: usize,
+    /// First discovery reported for each property, as the actions leading to it
+    pub discoveries: BTreeMap<String, Vec<A>>,
+    /// Why some worker left reachable states unexplored, if one did
+    pub cutoff: Option<Cutoff>,
+}
+
+impl<A: Debug> DistributedReport<A> {
+    /// What the run established about a property with `expectation`; `eventually` properties
+    /// are not checked in distributed mode
+    pub fn outcome(&self, property: &str, expectation: &Expectation) -> Outcome {
+        match expectation {
+            Expectation::Eventually => Outcome::Inconclusive,
+            _ => Outcome::of(expectation, self.discoveries.contains_key(property), self.cutoff.is_none()),
+        }
+    }
+
+    /// Print the state count and every discovery with its trace
+    pub fn print_summary(&self) {
+        println!("Distributed run: {} workers, {} unique states", self.workers, self.unique_states);
+        if let Some(cutoff) = self.cutoff {
+            println!("  Search cut short on some worker ({}): unviolated properties are inconclusive", cutoff);
+        }
+        for (property, trace) in &self.discoveries {
+            println!("  Discovery for '{}' after {} actions", property, trace.len());
+            for (step, action) in trace.iter().enumerate() {
+                println!("    {:>3}. {:?}", step + 1, action);
+            }
+        }
+    }
+}
+
+fn fingerprint<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Worker whose fingerprint range contains `fingerprint`
+fn owner(fingerprint: u64, workers: usize) -> usize {
+    ((fingerprint as u128 * workers as u128) >> 64) as usize
+}
+
+fn encode(path: &[usize]) -> String {
+    path.iter().map(|index| index.to_string()).collect::<Vec<_>>().join(",")
+}
+
+fn decode(path: &str) -> Option<Vec<usize>> {
+    path.split(',').map(|index| index.parse().ok()).collect()
+}
+
+fn encode_cutoff(cutoff: Option<Cutoff>) -> &'static str {
+    match cutoff {
+        None => "complete",
+        Some(Cutoff::States) => "states",
+        Some(Cutoff::Timeout) => "timeout",
+        Some(Cutoff::Bounds) => "bounds",
+    }
+}
+
+fn decode_cutoff(cutoff: &str) -> Option<Option<Cutoff>> {
+    match cutoff {
+        "complete" => Some(None),
+        "states" => Some(Some(Cutoff::States)),
+        "timeout" => Some(Some(Cutoff::Timeout)),
+        "bounds" => Some(Some(Cutoff::Bounds)),
+        _ => None,
+    }
+}
+
+/// Hash identifying a run: the caller's model parameters together with the properties checked
+/// and the initial states, so workers built from different flags are told apart
+fn handshake<M: Model>(model: &M, parameters: &impl Hash) -> u64
+where
+    M::State: Hash,
+{
+    let properties: Vec<_> = model.properties().iter().map(|property| property.name).collect();
+    fingerprint(&(parameters, properties, model.init_states()))
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Rebuild a state from the index of its initial state followed by the index of each action taken
+fn replay<M: Model>(model: &M, path: &[usize]) -> Option<(M::State, Vec<M::Action>)>
+where
+    M::Action: Clone,
+{
+    let (first, steps) = path.split_first()?;
+    let mut state = model.init_states().into_iter().nth(*first)?;
+    let mut trace = Vec::new();
+    for index in steps {
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        let action = actions.into_iter().nth(*index)?;
+        trace.push(action.clone());
+        state = model.next_state(&state, action)?;
+    }
+    Some((state, trace))
+}
+
+/// Accept `workers` connections on `listener` from workers run with the same `parameters`,
+/// route states between the workers until all of them are idle with nothing in flight, then
+/// collect their results
+pub fn run_coordinator<M: Model>(model: &BoundedModel<M>, parameters: &impl Hash, listener: TcpListener, workers: usize) -> io::Result<DistributedReport<M::Action>>
+where
+    M::State: Bounded + Hash,
+    M::Action: Clone + Debug,
+{
+    let expected = handshake(model, parameters);
+    let (tx, rx) = mpsc::channel::<(usize, String)>();
+    let mut writers = Vec::new();
+    while writers.len() < workers {
+        let (stream, peer) = listener.accept()?;
+        let mut writer = BufWriter::new(stream.try_clone()?);
+        let mut reader = BufReader::new(stream);
+        let mut hello = String::new();
+        reader.read_line(&mut hello)?;
+        if hello.trim_end().strip_prefix("HELLO ").and_then(|hash| hash.parse::<u64>().ok()) != Some(expected) {
+            eprintln!("Refusing worker {}: its model parameters differ from the coordinator's", peer);
+            writeln!(writer, "REJECT {}", expected)?;
+            writer.flush()?;
+            continue;
+        }
+        let id = writers.len();
+        writeln!(writer, "ASSIGN {} {}", id, workers)?;
+        writer.flush()?;
+        writers.push(writer);
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for line in reader.lines().map_while(Result::ok) {
+                if tx.send((id, line)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    drop(tx);
+
+    // A worker is done once it reported idle after consuming every state forwarded to it
+    let mut forwarded = vec![0usize; workers];
+    let mut idle: Vec<Option<usize>> = vec![None; workers];
+    let mut paths: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    let mut unique_states = 0;
+    let mut cutoff = None;
+    let mut finished = 0;
+    let mut stopping = false;
+
+    while finished < workers {
+        let Ok(first) = rx.recv() else {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "a worker disconnected"));
+        };
+        let mut next = Some(first);
+        while let Some((id, line)) = next {
+            let (kind, rest) = line.split_once(' ').unwrap_or((line.as_str(), ""));
+            match kind {
+                "STATE" => {
+                    let (owner, path) = rest.split_once(' ').ok_or_else(|| invalid("malformed STATE"))?;
+                    let owner: usize = owner.parse().map_err(|_| invalid("malformed STATE owner"))?;
+                    let writer = writers.get_mut(owner).ok_or_else(|| invalid("unknown STATE owner"))?;
+                    writeln!(writer, "STATE {}", path)?;
+                    forwarded[owner] += 1;
+                    idle[id] = None;
+                }
+                "DISCOVERY" => {
+                    let (property, path) = rest.split_once(' ').ok_or_else(|| invalid("malformed DISCOVERY"))?;
+                    let path = decode(path).ok_or_else(|| invalid("malformed DISCOVERY path"))?;
+                    paths.entry(property.to_string()).or_insert(path);
+                }
+                "IDLE" => idle[id] = Some(rest.parse().map_err(|_| invalid("malformed IDLE"))?),
+                "DONE" => {
+                    let (states, worker_cutoff) = rest.split_once(' ').ok_or_else(|| invalid("malformed DONE"))?;
+                    unique_states += states.parse::<usize>().map_err(|_| invalid("malformed DONE"))?;
+                    cutoff = cutoff.or(decode_cutoff(worker_cutoff).ok_or_else(|| invalid("malformed DONE cutoff"))?);
+                    finished += 1;
+                }
+                _ => return Err(invalid("unknown message")),
+            }
+            next = rx.try_recv().ok();
+        }
+
+        if !stopping && idle.iter().zip(&forwarded).all(|(idle, forwarded)| *idle == Some(*forwarded)) {
+            stopping = true;
+            for writer in &mut writers {
+                writeln!(writer, "STOP")?;
+            }
+        }
+        for writer in &mut writers {
+            writer.flush()?;
+        }
+    }
+
+    let discoveries = paths.into_iter()
+        .map(|(property, path)| {
+            // Replayed on the wrapped model, which expands states whatever limits stopped the search
+            let (_, trace) = replay(&model.model, &path).ok_or_else(|| invalid("discovery path does not replay"))?;
+            Ok((property, trace))
+        })
+        .collect::<io::Result<_>>()?;
+    Ok(DistributedReport { workers, unique_states, discoveries, cutoff })
+}
+
+/// Bind `address` and coordinate `workers` workers
+pub fn coordinate<M: Model>(model: &BoundedModel<M>, parameters: &impl Hash, address: impl ToSocketAddrs, workers: usize) -> io::Result<DistributedReport<M::Action>>
+where
+    M::State: Bounded + Hash,
+    M::Action: Clone + Debug,
+{
+    let listener = TcpListener::bind(address)?;
+    println!("Coordinator listening on {} for {} workers", listener.local_addr()?, workers);
+    run_coordinator(model, parameters, listener, workers)
+}
+
+/// Connect to a coordinator run with the same `parameters` and explore this worker's share of
+/// the state space, within the model's bounds and limits, until told to stop. Returns the number
+/// of unique states this worker owned.
+pub fn run_worker<M: Model>(model: &BoundedModel<M>, parameters: &impl Hash, coordinator: impl ToSocketAddrs) -> io::Result<usize>
+where
+    M::State: Bounded + Hash,
+    M::Action: Clone + Debug,
+{
+    let stream = TcpStream::connect(coordinator)?;
+    let mut writer = BufWriter::new(stream.try_clone()?);
+    writeln!(writer, "HELLO {}", handshake(model, parameters))?;
+    writer.flush()?;
+    let mut lines = BufReader::new(stream).lines();
+    let assignment = lines.next().ok_or_else(|| invalid("no assignment"))??;
+    if assignment.starts_with("REJECT ") {
+        return Err(invalid("the coordinator runs with different model parameters"));
+    }
+    let (id, workers) = assignment.strip_prefix("ASSIGN ")
+        .and_then(|rest| rest.split_once(' '))
+        .and_then(|(id, workers)| Some((id.parse::<usize>().ok()?, workers.parse::<usize>().ok()?)))
+        .ok_or_else(|| invalid("malformed ASSIGN"))?;
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for line in lines.map_while(Result::ok) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    let properties = model.properties();
+    let mut discovered = BTreeSet::new();
+    let mut visited = HashSet::new();
+    let mut handed_off = HashSet::new();
+    let mut pending = Vec::new();
+    let mut received = 0;
+    let mut expanded: usize = 0;
+    let mut beyond_depth = false;
+
+    for (index, state) in model.init_states().into_iter().enumerate() {
+        let fingerprint = fingerprint(&state);
+        if model.within_boundary(&state) && owner(fingerprint, workers) == id && visited.insert(fingerprint) {
+            pending.push((state, vec![index]));
+        }
+    }
+
+    let mut stop = false;
+    while !stop {
+        while let Ok(line) = rx.try_recv() {
+            stop |= receive(&model.model, &line, &mut visited, &mut pending, &mut received)?;
+        }
+        if stop {
+            break;
+        }
+
+        let Some((state, path)) = pending.pop() else {
+            // Nothing left to explore: report how many handed-off states were consumed and wait
+            writeln!(writer, "IDLE {}", received)?;
+            writer.flush()?;
+            let line = rx.recv().map_err(|_| io::Error::new(io::ErrorKind::UnexpectedEof, "coordinator disconnected"))?;
+            stop = receive(&model.model, &line, &mut visited, &mut pending, &mut received)?;
+            continue;
+        };
+        if model.bounds.max_depth.is_some_and(|depth| path.len() >= depth) {
+            beyond_depth = true; // Like Stateright's checkers, neither evaluated nor expanded
+            continue;
+        }
+
+        for property in &properties {
+            let found = match property.expectation {
+                Expectation::Always => !(property.condition)(model, &state),
+                Expectation::Sometimes => (property.condition)(model, &state),
+                Expectation::Eventually => false,
+            };
+            if found && discovered.insert(property.name) {
+                writeln!(writer, "DISCOVERY {} {}", property.name, encode(&path))?;
+            }
+        }
+
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        for (index, action) in actions.into_iter().enumerate() {
+            let Some(next_state) = model.next_state(&state, action) else { continue };
+            if !model.within_boundary(&next_state) {
+                continue;
+            }
+            let fingerprint = fingerprint(&next_state);
+            let mut next_path = path.clone();
+            next_path.push(index);
+            let next_owner = owner(fingerprint, workers);
+            if next_owner != id {
+                if handed_off.insert(fingerprint) {
+                    writeln!(writer, "STATE {} {}", next_owner, encode(&next_path))?;
+                }
+            } else if visited.insert(fingerprint) {
+                pending.push((next_state, next_path));
+            }
+        }
+
+        expanded += 1;
+        if expanded.is_multiple_of(FLUSH_INTERVAL) {
+            writer.flush()?;
+        }
+    }
+
+    let unique_states = visited.len();
+    let cutoff = model.cutoff().or(beyond_depth.then_some(Cutoff::Bounds));
+    writeln!(writer, "DONE {} {}", unique_states, encode_cutoff(cutoff))?;
+    writer.flush()?;
+    Ok(unique_states)
+}
+
+/// Take a message from the coordinator, queueing a handed-off state unless it was already
+/// explored. Returns whether the message was `STOP`.
+fn receive<M: Model>(
+    model: &M,
+    line: &str,
+    visited: &mut HashSet<u64>,
+    pending: &mut Vec<(M::State, Vec<usize>)>,
+    received: &mut usize,
+) -> io::Result<bool>
+where
+    M::State: Hash,
+    M::Action: Clone,
+{
+    if line == "STOP" {
+        return Ok(true);
+    }
+    let path = line.strip_prefix("STATE ").and_then(decode).ok_or_else(|| invalid("malformed STATE"))?;
+    *received += 1;
+    let (state, _) = replay(model, &path).ok_or_else(|| invalid("state path does not replay"))?;
+    if visited.insert(fingerprint(&state)) {
+        pending.push((state, path));
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounds::ExplorationBounds;
+    use crate::limits::ResourceLimits;
+    use crate::votor::VotorModel;
+    use stateright::Checker;
+
+    #[test]
+    fn test_owner_ranges() {
+        assert_eq!(owner(0, 3), 0);
+        assert_eq!(owner(u64::MAX, 3), 2);
+        assert_eq!(owner(u64::MAX / 2, 2), 0);
+        assert_eq!(owner(u64::MAX / 2 + 1, 2), 1);
+        assert_eq!(decode(&encode(&[0, 3, 12])), Some(vec![0, 3, 12]));
+    }
+
+    /// Run `model` on `workers` local workers, all with `parameters`
+    fn run_locally(model: &BoundedModel<VotorModel>, workers: usize) -> DistributedReport<crate::votor::Action> {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| run_worker(model, &"votor", address).unwrap());
+            }
+            run_coordinator(model, &"votor", listener, workers).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_distributed_matches_single_machine() {
+        let model = VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 };
+        let report = run_locally(&BoundedModel::new(model.clone(), ExplorationBounds::default()), 3);
+
+        let properties = model.properties();
+        let result = model.checker().spawn_dfs().join();
+        assert_eq!(report.unique_states, result.unique_state_count());
+        assert_eq!(report.cutoff, None);
+        for property in properties {
+            assert_eq!(report.discoveries.contains_key(property.name), result.discovery(property.name).is_some());
+        }
+    }
+
+    #[test]
+    fn test_distributed_cutoff() {
+        let model = VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 };
+        let complete = run_locally(&BoundedModel::new(model.clone(), ExplorationBounds::default()), 2);
+
+        // The depth bound stops every worker, and the run is no proof of anything it did not violate
+        let shallow = ExplorationBounds { max_depth: Some(3), ..Default::default() };
+        let report = run_locally(&BoundedModel::new(model.clone(), shallow), 2);
+        assert_eq!(report.cutoff, Some(Cutoff::Bounds));
+        assert!(report.unique_states < complete.unique_states);
+        for property in model.properties() {
+            if !report.discoveries.contains_key(property.name) && property.expectation == Expectation::Always {
+                assert_eq!(report.outcome(property.name, &property.expectation), Outcome::Inconclusive);
+            }
+        }
+
+        // So does a state limit reached by any worker
+        let limited = BoundedModel::new(model, ExplorationBounds::default())
+            .with_limits(ResourceLimits { max_states: Some(10), timeout: None });
+        assert_eq!(run_locally(&limited, 2).cutoff, Some(Cutoff::States));
+    }
+
+    #[test]
+    fn test_mismatched_worker_refused() {
+        let model = BoundedModel::new(VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 }, ExplorationBounds::default());
+        let other = BoundedModel::new(VotorModel { honest_validators: 3, max_slot: 1, network_faults: 0 }, ExplorationBounds::default());
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let report = thread::scope(|scope| {
+            let coordinator = scope.spawn(|| run_coordinator(&model, &"votor", listener, 1).unwrap());
+            // A different model, or the same one run with other flags, is turned away
+            assert!(run_worker(&other, &"votor", address).is_err());
+            assert!(run_worker(&model, &"votor --slots 2", address).is_err());
+            run_worker(&model, &"votor", address).unwrap();
+            coordinator.join().unwrap()
+        });
+        assert_eq!(report.workers, 1);
+        assert_eq!(report.cutoff, None);
+    }
+}
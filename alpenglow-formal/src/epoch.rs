@@ -0,0 +1,801 @@
+//! Formal verification model for epoch boundaries, where the validator set and its stake change.
+//! Stake is fixed per epoch: by the end of an epoch at the latest, one change to the stake
+//! accounts is made and the stake activated in the next epoch becomes its stake table. As on
+//! Solana, stake warms up and cools down over epochs: newly delegated stake only counts from the
+//! epoch after it is delegated, and deactivated stake keeps counting through that epoch and stops
+//! in the one after. A validator without activated stake is outside the set. Changes only go
+//! ahead if the Byzantine stake stays within its bound. The slots of an epoch are led by its
+//! validators in turn, and a block is finalized by a certificate of 80% of the stake of its
+//! slot's epoch. Byzantine validators keep voting after they leave, vote for every block they
+//! see, and propose two conflicting blocks when they lead. Votes are signed with vote keys, and a
+//! validator may rotate its key during an epoch, the new key taking effect at the next boundary;
+//! Byzantine validators keep signing with every key they held. The model checks that every
+//! certificate is weighed against the stake table of its slot's epoch, however much newer the
+//! tables known when it forms, that every table holds exactly the activated stake of its epoch,
+//! that no stake unit counts twice in an epoch while it is redelegated, that rotation lets no
+//! validator vote twice in a slot nor count twice in a certificate, that retired keys contribute
+//! to no certificate once the rotation takes effect, and that the finalized blocks form a single
+//! chain across the epoch boundaries.
+
+use stateright::{Model, Property, Checker};
+use std::collections::{BTreeMap, BTreeSet};
+use crate::bounds::Bounded;
+use crate::limits::Outcome;
+use crate::options::CheckOptions;
+use crate::voters::VoterSet;
+
+// --- Formal Model Configuration ---
+const FINALIZATION_THRESHOLD_PERCENT: u64 = 80;
+const MAX_BYZANTINE_PERCENT: u64 = 20;
+const MAX_STAKE: Stake = 2; // Stake units a validator can hold; genesis validators hold one
+const WARMUP_EPOCHS: Epoch = 1; // Delegated stake counts from the epoch after it is delegated
+const COOLDOWN_EPOCHS: Epoch = 2; // Deactivated stake stops counting two epochs after it is deactivated
+/// Slots per epoch of the formal verification configuration
+pub const DEFAULT_SLOTS_PER_EPOCH: Slot = 1;
+
+// Type aliases for clarity
+type Slot = u64;
+type Hash = u64;
+type Epoch = u64;
+type ActorId = usize;
+type Stake = u64;
+/// Vote key, numbered `generation * (validator_count + joining_count) + validator`
+type Key = usize;
+
+/// Stake per validator for one epoch; validators outside the set hold none
+type StakeTable = BTreeMap<ActorId, Stake>;
+
+/// Which stake table a certificate's votes are weighed against
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum StakeLookup {
+    /// The table of the epoch of the certificate's slot
+    #[default]
+    SlotEpoch,
+    /// The newest table known when the certificate forms, which misweighs certificates formed
+    /// after the next epoch's table is chosen
+    Newest,
+}
+
+/// When redelegated stake starts warming up at its new validator
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Redelegation {
+    /// Once it has cooled down at the old one
+    #[default]
+    AfterCooldown,
+    /// Right away, which counts the stake at both validators while it cools down
+    Immediate,
+}
+
+/// Which of its vote keys a validator's votes count with
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum RetiredKeys {
+    /// Only the key registered for the slot's epoch
+    #[default]
+    Rejected,
+    /// Every key the validator held up to that epoch, each counted on its own, which lets a
+    /// validator that rotated count once per key
+    Accepted,
+}
+
+/// A change to the stake accounts, made during an epoch
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum StakeChange {
+    Unchanged,
+    /// A new unit of stake is delegated to a validator, which joins the set if outside it
+    Delegate(ActorId),
+    /// All of a validator's stake is deactivated, so it leaves the set once cooled down
+    Deactivate(ActorId),
+    /// One unit of stake is moved from one validator to another
+    Redelegate { from: ActorId, to: ActorId },
+}
+
+/// One unit of stake delegated to a validator, counting from its activation epoch until its
+/// deactivation epoch; redelegation deactivates a unit and delegates it again in a new account
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct StakeAccount {
+    unit: usize,
+    validator: ActorId,
+    activation: Epoch,
+    deactivation: Option<Epoch>,
+}
+
+impl StakeAccount {
+    fn is_activated_in(&self, epoch: Epoch) -> bool {
+        self.activation <= epoch && self.deactivation.is_none_or(|deactivation| epoch < deactivation)
+    }
+}
+
+/// Stake activated in `epoch`: warming-up stake does not count yet, cooling-down stake still does
+fn activated_stake(accounts: &BTreeSet<StakeAccount>, epoch: Epoch) -> StakeTable {
+    let mut table = StakeTable::new();
+    for account in accounts.iter().filter(|account| account.is_activated_in(epoch)) {
+        *table.entry(account.validator).or_insert(0) += 1;
+    }
+    table
+}
+
+/// Whether some stake unit counts in two accounts in `epoch`
+fn double_counted(accounts: &BTreeSet<StakeAccount>, epoch: Epoch) -> bool {
+    let mut units = BTreeSet::new();
+    accounts.iter()
+        .filter(|account| account.is_activated_in(epoch))
+        .any(|account| !units.insert(account.unit))
+}
+
+/// A proposed block
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Block {
+    slot: Slot,
+    hash: Hash,
+    parent: Hash,
+}
+
+/// A finalization certificate, with the epoch whose stake table weighed its votes
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Certificate {
+    slot: Slot,
+    hash: Hash,
+    epoch: Epoch,
+    /// Keys of voters holding stake in that epoch
+    signers: VoterSet,
+    stake: Stake,
+}
+
+/// Actions that can be taken in the epoch model
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum EpochAction {
+    /// The stake accounts change during the newest epoch, and the stake activated in the next
+    /// one becomes its table, alongside the vote keys rotated during it
+    ChangeStake { epoch: Epoch, change: StakeChange },
+    /// A validator of the newest epoch rotates its vote key, effective from the next epoch
+    RotateKey { validator: ActorId },
+    /// The leader of the slot after the tip proposes a block
+    Propose { block: Block },
+    /// A validator votes to finalize a block, signing with one of its keys
+    Vote { slot: Slot, hash: Hash, voter: ActorId, key: Key },
+    /// The votes for a block are aggregated into a certificate
+    Certify { slot: Slot, hash: Hash },
+}
+
+/// State of the epoch model
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct EpochState {
+    accounts: BTreeSet<StakeAccount>,
+    /// Stake activated in each epoch reached so far, genesis epoch included
+    stake_tables: BTreeMap<Epoch, StakeTable>,
+    /// Vote key of every validator in each epoch reached so far
+    vote_keys: BTreeMap<Epoch, BTreeMap<ActorId, Key>>,
+    /// Validators whose rotated key takes effect at the next boundary
+    rotating: BTreeSet<ActorId>,
+    proposals: BTreeSet<Block>,
+    /// Signing keys per block: (slot, hash) -> keys
+    votes: BTreeMap<(Slot, Hash), VoterSet>,
+    certificates: BTreeSet<Certificate>,
+    /// Finalized blocks, genesis included
+    finalized: BTreeSet<Block>,
+}
+
+/// Formal model of validator-set changes at epoch boundaries
+#[derive(Clone)]
+pub struct EpochModel {
+    /// Validators of the genesis epoch, each with one unit of stake
+    pub validator_count: usize,
+    /// Validators outside the genesis set that may join later, numbered after the genesis ones
+    pub joining_count: usize,
+    /// Byzantine validators, the lowest ids of the genesis set
+    pub byzantine_count: usize,
+    pub slots_per_epoch: Slot,
+    /// Maximum slots to explore
+    pub max_slot: Slot,
+    /// Which stake table certificates are weighed against
+    pub stake_lookup: StakeLookup,
+    /// When redelegated stake starts warming up
+    pub redelegation: Redelegation,
+    /// Which keys votes count with
+    pub retired_keys: RetiredKeys,
+}
+
+/// Blocks are identified by their slot, their proposer and which of a Byzantine leader's
+/// conflicting blocks they are
+fn block_hash(slot: Slot, proposer: ActorId, variant: u64) -> Hash {
+    slot * 1000 + variant * 100 + proposer as u64
+}
+
+fn total_stake(table: &StakeTable) -> Stake {
+    table.values().sum()
+}
+
+impl EpochModel {
+    /// Epoch of a slot; genesis, slot 0, belongs to the first
+    pub fn epoch(&self, slot: Slot) -> Epoch {
+        slot.saturating_sub(1) / self.slots_per_epoch.max(1)
+    }
+
+    fn is_byzantine(&self, validator: ActorId) -> bool {
+        validator < self.byzantine_count
+    }
+
+    /// Validator holding `key`
+    fn owner(&self, key: Key) -> ActorId {
+        key % (self.validator_count + self.joining_count)
+    }
+
+    /// Stake the holders of `keys` have in `table`, once per key
+    fn key_stake(&self, table: &StakeTable, keys: &VoterSet) -> Stake {
+        keys.iter().map(|key| table.get(&self.owner(key)).copied().unwrap_or(0)).sum()
+    }
+
+    fn genesis_keys(&self) -> BTreeMap<ActorId, Key> {
+        (0..self.validator_count + self.joining_count).map(|validator| (validator, validator)).collect()
+    }
+
+    /// Vote keys after the keys of `rotating` are replaced by their next generation
+    fn rotated_keys(&self, keys: &BTreeMap<ActorId, Key>, rotating: &BTreeSet<ActorId>) -> BTreeMap<ActorId, Key> {
+        let stride = self.validator_count + self.joining_count;
+        keys.iter()
+            .map(|(validator, key)| (*validator, if rotating.contains(validator) { key + stride } else { *key }))
+            .collect()
+    }
+
+    /// Keys `voter` may sign a vote for `slot` with: honest validators use the key registered for
+    /// the slot's epoch, Byzantine ones every key they held
+    fn signing_keys(&self, state: &EpochState, voter: ActorId, slot: Slot) -> BTreeSet<Key> {
+        if self.is_byzantine(voter) {
+            return state.vote_keys.values().filter_map(|keys| keys.get(&voter).copied()).collect();
+        }
+        state.vote_keys.get(&self.epoch(slot)).and_then(|keys| keys.get(&voter).copied()).into_iter().collect()
+    }
+
+    /// Validators of the newest epoch that may still rotate their key before the next boundary
+    fn may_rotate(&self, state: &EpochState, validator: ActorId) -> bool {
+        let epoch = state.newest_epoch();
+        epoch < self.epoch(self.max_slot)
+            && !state.rotating.contains(&validator)
+            && state.stake_tables[&epoch].contains_key(&validator)
+    }
+
+    fn genesis_accounts(&self) -> BTreeSet<StakeAccount> {
+        (0..self.validator_count)
+            .map(|validator| StakeAccount { unit: validator, validator, activation: 0, deactivation: None })
+            .collect()
+    }
+
+    /// Whether a stake table keeps Byzantine stake within its bound
+    fn admissible(&self, table: &StakeTable) -> bool {
+        let byzantine: Stake = table.iter().filter(|(v, _)| self.is_byzantine(**v)).map(|(_, stake)| stake).sum();
+        total_stake(table) > 0 && byzantine * 100 <= total_stake(table) * MAX_BYZANTINE_PERCENT
+    }
+
+    /// Stake accounts after `change` is made during epoch `requested`
+    fn apply(&self, accounts: &BTreeSet<StakeAccount>, requested: Epoch, change: StakeChange) -> BTreeSet<StakeAccount> {
+        let deactivation = requested + COOLDOWN_EPOCHS;
+        let mut accounts = accounts.clone();
+        match change {
+            StakeChange::Unchanged => {}
+            StakeChange::Delegate(validator) => {
+                let unit = accounts.iter().map(|account| account.unit + 1).max().unwrap_or(0);
+                accounts.insert(StakeAccount { unit, validator, activation: requested + WARMUP_EPOCHS, deactivation: None });
+            }
+            StakeChange::Deactivate(validator) => {
+                accounts = accounts.into_iter()
+                    .map(|account| match account.deactivation {
+                        None if account.validator == validator => StakeAccount { deactivation: Some(deactivation), ..account },
+                        _ => account,
+                    })
+                    .collect();
+            }
+            StakeChange::Redelegate { from, to } => {
+                let Some(account) = accounts.iter().find(|account| account.validator == from && account.deactivation.is_none()).copied() else {
+                    return accounts;
+                };
+                let activation = match self.redelegation {
+                    Redelegation::AfterCooldown => deactivation,
+                    Redelegation::Immediate => requested + WARMUP_EPOCHS,
+                };
+                accounts.remove(&account);
+                accounts.insert(StakeAccount { deactivation: Some(deactivation), ..account });
+                accounts.insert(StakeAccount { unit: account.unit, validator: to, activation, deactivation: None });
+            }
+        }
+        accounts
+    }
+
+    /// Changes that may be made to `accounts` during epoch `requested`: delegations up to the
+    /// most stake a validator holds, deactivations and redelegations of held stake, as long as
+    /// every epoch they reach keeps the Byzantine stake within its bound
+    fn changes(&self, accounts: &BTreeSet<StakeAccount>, requested: Epoch) -> Vec<StakeChange> {
+        let held = |validator: ActorId| accounts.iter()
+            .filter(|account| account.validator == validator && account.deactivation.is_none())
+            .count() as Stake;
+        let validators = 0..self.validator_count + self.joining_count;
+        let mut changes = vec![StakeChange::Unchanged];
+        for validator in validators.clone() {
+            if held(validator) < MAX_STAKE {
+                changes.push(StakeChange::Delegate(validator));
+            }
+            if held(validator) > 0 {
+                changes.push(StakeChange::Deactivate(validator));
+                for to in validators.clone().filter(|to| *to != validator && held(*to) < MAX_STAKE) {
+                    changes.push(StakeChange::Redelegate { from: validator, to });
+                }
+            }
+        }
+        changes.retain(|change| {
+            let accounts = self.apply(accounts, requested, *change);
+            (requested + 1..=requested + COOLDOWN_EPOCHS).all(|epoch| self.admissible(&activated_stake(&accounts, epoch)))
+        });
+        changes
+    }
+
+    /// Leader of a slot: the validators of its epoch take turns
+    fn leader(&self, state: &EpochState, slot: Slot) -> Option<ActorId> {
+        let table = state.stake_tables.get(&self.epoch(slot))?;
+        table.keys().nth(slot as usize % table.len()).copied()
+    }
+
+    /// Blocks the leader of the slot after the tip may still propose, once its epoch's stake
+    /// table is chosen
+    fn blocks_to_propose(&self, state: &EpochState) -> Vec<Block> {
+        let tip = state.tip();
+        let slot = tip.slot + 1;
+        if slot > self.max_slot {
+            return Vec::new();
+        }
+        let Some(leader) = self.leader(state, slot) else {
+            return Vec::new();
+        };
+        let variants = if self.is_byzantine(leader) { 2 } else { 1 };
+        (0..variants)
+            .map(|variant| Block { slot, hash: block_hash(slot, leader, variant), parent: tip.hash })
+            .filter(|block| !state.proposals.contains(block))
+            .collect()
+    }
+
+    /// Whether `voter` may vote for `block` signing with `key`: Byzantine validators vote for
+    /// every block with every key, in the set or not; honest ones vote once per slot, whatever
+    /// their key, for a block extending the tip, while they hold stake in the slot's epoch
+    fn may_vote(&self, state: &EpochState, block: &Block, voter: ActorId, key: Key) -> bool {
+        if state.votes.get(&(block.slot, block.hash)).is_some_and(|keys| keys.contains(&key)) {
+            return false;
+        }
+        if !self.signing_keys(state, voter, block.slot).contains(&key) {
+            return false;
+        }
+        if self.is_byzantine(voter) {
+            return true;
+        }
+        let member = state.stake_tables.get(&self.epoch(block.slot)).is_some_and(|table| table.contains_key(&voter));
+        let voted = state.votes.iter()
+            .any(|((slot, _), keys)| *slot == block.slot && keys.iter().any(|key| self.owner(key) == voter));
+        member && !voted && block.parent == state.tip().hash
+    }
+
+    /// Epoch whose stake table weighs the votes of a certificate for `slot` formed in `state`
+    fn lookup_epoch(&self, state: &EpochState, slot: Slot) -> Epoch {
+        match self.stake_lookup {
+            StakeLookup::SlotEpoch => self.epoch(slot),
+            StakeLookup::Newest => state.newest_epoch(),
+        }
+    }
+
+    /// The certificate the votes for a block make, if they reach the threshold
+    fn certificate(&self, state: &EpochState, slot: Slot, hash: Hash) -> Option<Certificate> {
+        let epoch = self.lookup_epoch(state, slot);
+        let table = state.stake_tables.get(&epoch)?;
+        let registered = |key: Key| {
+            let owner = self.owner(key);
+            match self.retired_keys {
+                RetiredKeys::Rejected => state.vote_keys.get(&epoch).is_some_and(|keys| keys.get(&owner) == Some(&key)),
+                RetiredKeys::Accepted => state.vote_keys.range(..=epoch).any(|(_, keys)| keys.get(&owner) == Some(&key)),
+            }
+        };
+        let signers: VoterSet = state.votes.get(&(slot, hash))?.iter()
+            .filter(|key| table.contains_key(&self.owner(*key)) && registered(*key))
+            .collect();
+        let stake = self.key_stake(table, &signers);
+        (stake * 100 >= total_stake(table) * FINALIZATION_THRESHOLD_PERCENT)
+            .then_some(Certificate { slot, hash, epoch, signers, stake })
+    }
+}
+
+impl EpochState {
+    fn new(accounts: BTreeSet<StakeAccount>, keys: BTreeMap<ActorId, Key>) -> Self {
+        Self {
+            stake_tables: BTreeMap::from([(0, activated_stake(&accounts, 0))]),
+            accounts,
+            vote_keys: BTreeMap::from([(0, keys)]),
+            rotating: BTreeSet::new(),
+            proposals: BTreeSet::new(),
+            votes: BTreeMap::new(),
+            certificates: BTreeSet::new(),
+            finalized: BTreeSet::from([Block { slot: 0, hash: 0, parent: 0 }]),
+        }
+    }
+
+    /// Highest finalized block
+    fn tip(&self) -> Block {
+        *self.finalized.iter().next_back().expect("genesis is finalized")
+    }
+
+    fn newest_epoch(&self) -> Epoch {
+        *self.stake_tables.keys().next_back().expect("the genesis table is known")
+    }
+
+    fn is_certified(&self, slot: Slot, hash: Hash) -> bool {
+        self.certificates.iter().any(|certificate| certificate.slot == slot && certificate.hash == hash)
+    }
+
+    /// Whether every certificate is weighed against the stake table of its slot's epoch: it
+    /// names that epoch, its signers hold stake in it and together reach the threshold
+    fn check_certificate_epoch_stake(&self, model: &EpochModel) -> bool {
+        self.certificates.iter().all(|certificate| {
+            certificate.epoch == model.epoch(certificate.slot)
+                && self.stake_tables.get(&certificate.epoch).is_some_and(|table| {
+                    certificate.signers.iter().all(|signer| table.contains_key(&model.owner(signer)))
+                        && certificate.stake == model.key_stake(table, &certificate.signers)
+                        && certificate.stake * 100 >= total_stake(table) * FINALIZATION_THRESHOLD_PERCENT
+                })
+        })
+    }
+
+    /// Whether every certificate is signed only with the keys registered for its slot's epoch,
+    /// so a key retired by a rotation contributes to no certificate once the rotation takes effect
+    fn check_retired_keys_excluded(&self, model: &EpochModel) -> bool {
+        self.certificates.iter().all(|certificate| {
+            self.vote_keys.get(&model.epoch(certificate.slot)).is_some_and(|keys| {
+                certificate.signers.iter().all(|signer| keys.get(&model.owner(signer)) == Some(&signer))
+            })
+        })
+    }
+
+    /// Whether rotation enables no equivocation: no honest validator signs votes in one slot with
+    /// two keys, old and new, and no certificate counts a validator through two of its keys
+    fn check_no_equivocation_across_rotation(&self, model: &EpochModel) -> bool {
+        let mut signed = BTreeSet::new();
+        let honest_once = self.votes.iter()
+            .flat_map(|((slot, _), keys)| keys.iter().map(move |key| (*slot, key)))
+            .filter(|(_, key)| !model.is_byzantine(model.owner(*key)))
+            .all(|(slot, key)| signed.insert((slot, model.owner(key))));
+        honest_once && self.certificates.iter().all(|certificate| {
+            let owners: BTreeSet<ActorId> = certificate.signers.iter().map(|signer| model.owner(signer)).collect();
+            owners.len() == certificate.signers.len()
+        })
+    }
+
+    /// Whether every stake table holds exactly the stake activated in its epoch, so quorums are
+    /// never weighed against stake still warming up
+    fn check_activated_stake(&self) -> bool {
+        self.stake_tables.iter().all(|(epoch, table)| *table == activated_stake(&self.accounts, *epoch))
+    }
+
+    /// Whether no stake unit counts twice in any epoch the accounts already reach
+    fn check_no_double_counted_stake(&self) -> bool {
+        (0..=self.newest_epoch() + COOLDOWN_EPOCHS).all(|epoch| !double_counted(&self.accounts, epoch))
+    }
+
+    /// Whether the finalized blocks form a single chain: one per slot, each extending the one
+    /// finalized in the slot before
+    fn check_chain_consistency(&self) -> bool {
+        let slots: BTreeSet<Slot> = self.finalized.iter().map(|block| block.slot).collect();
+        slots.len() == self.finalized.len()
+            && self.finalized.iter().filter(|block| block.slot > 0).all(|block| {
+                self.finalized.iter().any(|parent| parent.slot + 1 == block.slot && parent.hash == block.parent)
+            })
+    }
+}
+
+impl Bounded for EpochState {
+    fn current_slot(&self) -> Slot {
+        self.proposals.iter().map(|block| block.slot).max().unwrap_or(0)
+    }
+
+    fn in_flight(&self) -> usize {
+        0
+    }
+}
+
+impl Model for EpochModel {
+    type State = EpochState;
+    type Action = EpochAction;
+
+    fn init_states(&self) -> Vec<Self::State> {
+        vec![EpochState::new(self.genesis_accounts(), self.genesis_keys())]
+    }
+
+    fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
+        // 1. The stake accounts change and the next epoch's stake table is fixed, at the latest
+        // before its first slot
+        let epoch = state.newest_epoch() + 1;
+        if epoch <= self.epoch(self.max_slot) && epoch <= self.epoch(state.tip().slot + 1) + 1 {
+            for change in self.changes(&state.accounts, epoch - 1) {
+                actions.push(EpochAction::ChangeStake { epoch, change });
+            }
+        }
+
+        // 2. Validators of the newest epoch rotate their vote keys for the next one
+        for validator in 0..self.validator_count + self.joining_count {
+            if self.may_rotate(state, validator) {
+                actions.push(EpochAction::RotateKey { validator });
+            }
+        }
+
+        // 3. The leader of the slot after the tip proposes
+        for block in self.blocks_to_propose(state) {
+            actions.push(EpochAction::Propose { block });
+        }
+
+        // 4. Validators vote for proposed blocks
+        for block in &state.proposals {
+            for voter in 0..self.validator_count + self.joining_count {
+                for key in self.signing_keys(state, voter, block.slot) {
+                    if self.may_vote(state, block, voter, key) {
+                        actions.push(EpochAction::Vote { slot: block.slot, hash: block.hash, voter, key });
+                    }
+                }
+            }
+        }
+
+        // 5. Votes reaching the threshold are aggregated into a certificate
+        for (slot, hash) in state.votes.keys() {
+            if !state.is_certified(*slot, *hash) && self.certificate(state, *slot, *hash).is_some() {
+                actions.push(EpochAction::Certify { slot: *slot, hash: *hash });
+            }
+        }
+    }
+
+    fn next_state(&self, last_state: &Self::State, action: Self::Action) -> Option<Self::State> {
+        let mut next_state = last_state.clone();
+        match action {
+            EpochAction::ChangeStake { epoch, change } => {
+                if epoch != last_state.newest_epoch() + 1 || epoch > self.epoch(self.max_slot) {
+                    return None;
+                }
+                if !self.changes(&last_state.accounts, epoch - 1).contains(&change) {
+                    return None;
+                }
+                next_state.accounts = self.apply(&last_state.accounts, epoch - 1, change);
+                next_state.stake_tables.insert(epoch, activated_stake(&next_state.accounts, epoch));
+                next_state.vote_keys.insert(epoch, self.rotated_keys(&last_state.vote_keys[&(epoch - 1)], &last_state.rotating));
+                next_state.rotating.clear();
+            }
+            EpochAction::RotateKey { validator } => {
+                if !self.may_rotate(last_state, validator) {
+                    return None;
+                }
+                next_state.rotating.insert(validator);
+            }
+            EpochAction::Propose { block } => {
+                if !self.blocks_to_propose(last_state).contains(&block) {
+                    return None;
+                }
+                next_state.proposals.insert(block);
+            }
+            EpochAction::Vote { slot, hash, voter, key } => {
+                let block = last_state.proposals.iter().find(|block| block.slot == slot && block.hash == hash)?;
+                if voter >= self.validator_count + self.joining_count || !self.may_vote(last_state, block, voter, key) {
+                    return None;
+                }
+                next_state.votes.entry((slot, hash)).or_default().insert(key);
+            }
+            EpochAction::Certify { slot, hash } => {
+                if last_state.is_certified(slot, hash) {
+                    return None;
+                }
+                let certificate = self.certificate(last_state, slot, hash)?;
+                let block = last_state.proposals.iter().find(|block| block.slot == slot && block.hash == hash)?;
+                next_state.finalized.insert(*block);
+                next_state.certificates.insert(certificate);
+            }
+        }
+        Some(next_state)
+    }
+
+    /// Properties to verify in the epoch model
+    fn properties(&self) -> Vec<Property<Self>> {
+        vec![
+            // Property 1: Certificates reference the stake set of their slot's epoch
+            Property::<Self>::always("certificate_epoch_stake", |model, state| {
+                state.check_certificate_epoch_stake(model)
+            }),
+
+            // Property 2: The finalized blocks form one chain across epoch boundaries
+            Property::<Self>::always("chain_consistency", |_model, state| {
+                state.check_chain_consistency()
+            }),
+
+            // Property 3: Quorums are weighed against activated stake only
+            Property::<Self>::always("activated_stake_quorum", |_model, state| {
+                state.check_activated_stake()
+            }),
+
+            // Property 4: Redelegated stake never counts at both validators in one epoch
+            Property::<Self>::always("no_double_counted_stake", |_model, state| {
+                state.check_no_double_counted_stake()
+            }),
+
+            // Property 5: Key rotation lets no validator vote or count twice in a slot
+            Property::<Self>::always("no_equivocation_across_rotation", |model, state| {
+                state.check_no_equivocation_across_rotation(model)
+            }),
+
+            // Property 6: Retired keys contribute to no certificate after rotation takes effect
+            Property::<Self>::always("retired_keys_excluded", |model, state| {
+                state.check_retired_keys_excluded(model)
+            }),
+
+            // Property 7: A block is finalized in an epoch whose validator set differs from
+            // genesis, so the handoff is actually exercised
+            Property::<Self>::sometimes("cross_epoch_finalization", |model, state| {
+                let genesis = &state.stake_tables[&0];
+                state.finalized.iter().any(|block| {
+                    state.stake_tables.get(&model.epoch(block.slot)).is_some_and(|table| block.slot > 0 && table != genesis)
+                })
+            }),
+        ]
+    }
+}
+
+/// Run formal verification of epoch boundaries
+pub fn run_formal_verification(options: &CheckOptions) {
+    println!("=== Epoch Boundary Formal Verification ===");
+
+    let model = EpochModel {
+        validator_count: 5, // Small for formal verification
+        joining_count: 1,
+        byzantine_count: 1, // One Byzantine validator, 20% of the genesis stake
+        slots_per_epoch: DEFAULT_SLOTS_PER_EPOCH,
+        max_slot: 2,
+        stake_lookup: StakeLookup::default(),
+        redelegation: Redelegation::default(),
+        retired_keys: RetiredKeys::default(),
+    };
+
+    println!("Model checking epoch boundaries with {} validators ({} Byzantine, {} joining), {} slots, {} per epoch",
+             model.validator_count, model.byzantine_count, model.joining_count, model.max_slot, model.slots_per_epoch);
+
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs()
+        .report(&mut stateright::report::WriteReporter::new(&mut std::io::stdout()));
+
+    // Check verification results
+    match options.print_outcomes(&result) {
+        Outcome::Proved => println!("✅ All epoch boundary properties verified successfully"),
+        Outcome::Violated => println!("❌ Epoch boundary verification found counterexamples"),
+        Outcome::Inconclusive => println!("❓ Epoch boundary verification inconclusive: a limit or bound stopped the search first"),
+    }
+    options.record_results("epoch formal", &result);
+}
+
+/// Test epoch model with different configurations
+pub fn test_epoch_model(validators: usize, joining: usize, byzantine: usize, slots: u64, slots_per_epoch: u64, options: &CheckOptions) {
+    println!("Testing epoch model with {} validators ({} Byzantine, {} joining), {} slots, {} per epoch",
+             validators, byzantine, joining, slots, slots_per_epoch);
+
+    let model = EpochModel {
+        validator_count: validators,
+        joining_count: joining,
+        byzantine_count: byzantine,
+        slots_per_epoch,
+        max_slot: slots,
+        stake_lookup: StakeLookup::default(),
+        redelegation: Redelegation::default(),
+        retired_keys: RetiredKeys::default(),
+    };
+
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs();
+    options.progress.watch(&result);
+    let result = result.join();
+
+    println!("States explored: {}", result.state_count());
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("epoch {}v {}j {}b {}s", validators, joining, byzantine, slots), &result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(stake_lookup: StakeLookup) -> EpochModel {
+        EpochModel {
+            validator_count: 3,
+            joining_count: 1,
+            byzantine_count: 0,
+            slots_per_epoch: 1,
+            max_slot: 2,
+            stake_lookup,
+            redelegation: Redelegation::default(),
+            retired_keys: RetiredKeys::default(),
+        }
+    }
+
+    #[test]
+    fn test_stake_changes_bound_byzantine_stake() {
+        let model = EpochModel { byzantine_count: 1, validator_count: 5, ..model(StakeLookup::SlotEpoch) };
+        let changes = model.changes(&model.genesis_accounts(), 0);
+        assert!(changes.contains(&StakeChange::Delegate(5)));
+        assert!(changes.contains(&StakeChange::Deactivate(0)));
+        assert!(changes.contains(&StakeChange::Delegate(1)));
+        assert!(changes.contains(&StakeChange::Redelegate { from: 1, to: 5 }));
+        // Any honest departure or Byzantine growth would lift the Byzantine stake past 20%
+        assert!(!changes.contains(&StakeChange::Deactivate(1)));
+        assert!(!changes.contains(&StakeChange::Delegate(0)));
+    }
+
+    #[test]
+    fn test_stake_warms_up_and_cools_down() {
+        let model = model(StakeLookup::SlotEpoch);
+        let accounts = model.apply(&model.genesis_accounts(), 0, StakeChange::Redelegate { from: 1, to: 3 });
+        // The unit still counts at validator 1 while it cools down, then moves to validator 3
+        assert_eq!(activated_stake(&accounts, 1), StakeTable::from([(0, 1), (1, 1), (2, 1)]));
+        assert_eq!(activated_stake(&accounts, 2), StakeTable::from([(0, 1), (2, 1), (3, 1)]));
+        assert!(!(0..4).any(|epoch| double_counted(&accounts, epoch)));
+
+        let accounts = model.apply(&model.genesis_accounts(), 0, StakeChange::Delegate(3));
+        assert_eq!(activated_stake(&accounts, 0).len(), 3);
+        assert_eq!(activated_stake(&accounts, 1).get(&3), Some(&1));
+
+        let model = EpochModel { redelegation: Redelegation::Immediate, ..model };
+        let accounts = model.apply(&model.genesis_accounts(), 0, StakeChange::Redelegate { from: 1, to: 3 });
+        assert!(double_counted(&accounts, 1));
+    }
+
+    #[test]
+    fn test_certificates_use_slot_epoch_stake() {
+        let checker = model(StakeLookup::SlotEpoch).checker().spawn_bfs().join();
+        assert!(checker.discovery("certificate_epoch_stake").is_none());
+        assert!(checker.discovery("chain_consistency").is_none());
+        assert!(checker.discovery("cross_epoch_finalization").is_some());
+
+        assert!(checker.discovery("activated_stake_quorum").is_none());
+        assert!(checker.discovery("no_double_counted_stake").is_none());
+        assert!(checker.discovery("no_equivocation_across_rotation").is_none());
+        assert!(checker.discovery("retired_keys_excluded").is_none());
+
+        // Weighing a late certificate against the next epoch's table is caught, and so is
+        // redelegated stake warming up before it has cooled down
+        let checker = model(StakeLookup::Newest).checker().spawn_bfs().join();
+        assert!(checker.discovery("certificate_epoch_stake").is_some());
+        let immediate = EpochModel { redelegation: Redelegation::Immediate, ..model(StakeLookup::SlotEpoch) };
+        assert!(immediate.checker().spawn_bfs().join().discovery("no_double_counted_stake").is_some());
+    }
+
+    #[test]
+    fn test_rotated_keys_count_once() {
+        let model = EpochModel { byzantine_count: 1, validator_count: 5, ..model(StakeLookup::SlotEpoch) };
+        let mut state = model.init_states().remove(0);
+        for action in [
+            EpochAction::RotateKey { validator: 0 },
+            EpochAction::ChangeStake { epoch: 1, change: StakeChange::Unchanged },
+        ] {
+            state = model.next_state(&state, action).expect("enabled");
+        }
+        // Validator 0 holds key 0 in the genesis epoch and key 6 from epoch 1 on
+        assert_eq!(state.vote_keys[&1].get(&0), Some(&6));
+        assert_eq!(model.signing_keys(&state, 0, 2), BTreeSet::from([0, 6]));
+        assert_eq!(model.signing_keys(&state, 1, 1), BTreeSet::from([1]));
+
+        // Two honest votes and the Byzantine one under both its keys in a slot of epoch 1
+        state.proposals.insert(Block { slot: 1, hash: 1001, parent: 0 });
+        state.finalized.insert(Block { slot: 1, hash: 1001, parent: 0 });
+        state.proposals.insert(Block { slot: 2, hash: 2002, parent: 1001 });
+        for (voter, key) in [(0, 0), (0, 6), (1, 1), (2, 2)] {
+            state = model.next_state(&state, EpochAction::Vote { slot: 2, hash: 2002, voter, key }).expect("enabled");
+        }
+        assert!(model.next_state(&state, EpochAction::Vote { slot: 2, hash: 2002, voter: 1, key: 7 }).is_none());
+        assert!(model.certificate(&state, 2, 2002).is_none());
+
+        // Counting the retired key as well lets the Byzantine validator complete a quorum
+        let model = EpochModel { retired_keys: RetiredKeys::Accepted, ..model };
+        let state = model.next_state(&state, EpochAction::Certify { slot: 2, hash: 2002 }).expect("quorum");
+        assert!(!state.check_no_equivocation_across_rotation(&model));
+        assert!(!state.check_retired_keys_excluded(&model));
+    }
+}
@@ -0,0 +1,212 @@
+//! Dual-path finality checks behind the dual_path_test, bounded_finalization_test and
+//! bounded_time_test binaries. A [`FinalityCheck`] simulates the liveness model with a share of
+//! responsive stake and bounded message delays, and its [`FinalityReport`] tells which path
+//! finalized each proposed slot, how long it took, and whether the model's min(δ80%, 2δ60%)
+//! latency bound held along every run.
+
+use std::collections::BTreeSet;
+use crate::modelling::liveness::{self, FinalizationPath, SimulationConfig, SimulationOutcome};
+use crate::output::Record;
+
+/// Randomized runs of the liveness model with equal stake per validator
+#[derive(Clone, Debug)]
+pub struct FinalityCheck {
+    pub validators: usize,
+    pub slots: u64,
+    /// Stake (percent) of the validators that respond; the rest stay silent
+    pub responsive_percent: u64,
+    /// Upper bound on each validator's message delay in ticks
+    pub max_delay: u64,
+    /// Runs to simulate, seeded `seed`, `seed + 1` and so on
+    pub runs: u64,
+    pub seed: u64,
+}
+
+impl FinalityCheck {
+    /// Validators holding `responsive_percent` of the stake, rounded down
+    pub fn responsive(&self) -> usize {
+        (self.validators as u64 * self.responsive_percent / 100) as usize
+    }
+
+    /// Stake (percent) the responsive validators actually hold, which rounding may put below
+    /// `responsive_percent`
+    pub fn responsive_stake_percent(&self) -> u64 {
+        self.responsive() as u64 * 100 / self.validators.max(1) as u64
+    }
+
+    /// Simulate every run
+    pub fn run(&self) -> FinalityReport {
+        let mut config = SimulationConfig::new(self.validators, self.slots, self.responsive(), self.seed);
+        config.max_delay = self.max_delay;
+        let outcomes = (0..self.runs)
+            .map(|run| {
+                config.seed = self.seed + run;
+                liveness::simulate(&config)
+            })
+            .collect();
+        FinalityReport { seed: self.seed, outcomes }
+    }
+}
+
+/// Outcomes of a [`FinalityCheck`], one per run
+#[derive(Clone, Debug)]
+pub struct FinalityReport {
+    /// Seed of the first run
+    pub seed: u64,
+    pub outcomes: Vec<SimulationOutcome>,
+}
+
+impl FinalityReport {
+    /// Properties violated in any run, such as `bounded_finalization`
+    pub fn violations(&self) -> BTreeSet<&'static str> {
+        self.outcomes.iter().flat_map(|outcome| outcome.violations.iter().copied()).collect()
+    }
+
+    /// Why the runs fail to finalize every proposed slot without violating a property, or for
+    /// the fast path to certify every proposed slot, whichever path finalized it first; `None`
+    /// if they all succeed
+    pub fn failure(&self, path: Option<FinalizationPath>) -> Option<String> {
+        let violations = self.violations();
+        if !violations.is_empty() {
+            let names: Vec<&str> = violations.into_iter().collect();
+            return Some(format!("violated {}", names.join(", ")));
+        }
+        for (seed, outcome) in (self.seed..).zip(&self.outcomes) {
+            let proposed = outcome.proposed_slots();
+            if proposed.is_empty() {
+                return Some(format!("no slot was proposed in the run with seed {}", seed));
+            }
+            let latencies = outcome.slot_latencies();
+            for slot in proposed {
+                let finalized = match path {
+                    Some(FinalizationPath::Fast) => outcome.fast_certificate_formed(slot),
+                    Some(path) => outcome.earliest_finalization(slot, path).is_some(),
+                    None => latencies.contains_key(&slot),
+                };
+                if !finalized {
+                    let missing = match path {
+                        Some(FinalizationPath::Fast) => "no fast-finalization certificate formed".to_string(),
+                        Some(path) => format!("not finalized through the {} path", path.label()),
+                        None => "not finalized".to_string(),
+                    };
+                    return Some(format!("slot {} {} in the run with seed {} ({}% responsive stake)",
+                                        slot, missing, seed, outcome.responsive_percent));
+                }
+            }
+        }
+        None
+    }
+
+    /// Longest time in ticks from receiving a block to its earliest finalization through
+    /// `path` if given, over every slot and run
+    pub fn max_latency(&self, path: Option<FinalizationPath>) -> Option<u64> {
+        self.outcomes.iter()
+            .flat_map(|outcome| match path {
+                Some(path) => outcome.proposed_slots().into_iter()
+                    .filter_map(|slot| outcome.earliest_finalization(slot, path))
+                    .collect::<Vec<_>>(),
+                None => outcome.slot_latencies().into_values().collect(),
+            })
+            .map(|finalization| finalization.latency())
+            .max()
+    }
+
+    /// Paths that finalized some slot first
+    pub fn paths_used(&self) -> BTreeSet<FinalizationPath> {
+        self.outcomes.iter()
+            .flat_map(|outcome| outcome.slot_latencies().into_values().map(|finalization| finalization.path))
+            .collect()
+    }
+
+    /// Slots still unfinalized when the block of the next slot arrived, summed over runs
+    pub fn overlapping_slots(&self) -> usize {
+        self.outcomes.iter()
+            .map(|outcome| {
+                let latencies = outcome.slot_latencies();
+                latencies.iter()
+                    .filter(|(slot, finalization)| {
+                        latencies.get(&(*slot + 1)).is_some_and(|next| next.received_at < finalization.finalized_at)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The runs of `check` as a structured record of run `name`: whether `property`, the binary's
+    /// own check, held, every model property violated along the way and the latencies measured
+    pub fn record(&self, name: &str, check: &FinalityCheck, property: &str, holds: bool) -> Record {
+        let mut record = Record::new(name)
+            .parameter("validators", check.validators)
+            .parameter("slots", check.slots)
+            .parameter("responsive_percent", check.responsive_percent)
+            .parameter("responsive_stake_percent", check.responsive_stake_percent())
+            .parameter("max_delay_ticks", check.max_delay)
+            .parameter("runs", check.runs)
+            .parameter("seed", check.seed)
+            .held(property, holds);
+        for violation in self.violations() {
+            record = record.held(violation, false);
+        }
+        let paths = [
+            ("max_finalization_ticks", None),
+            ("max_fast_path_ticks", Some(FinalizationPath::Fast)),
+            ("max_slow_path_ticks", Some(FinalizationPath::Slow)),
+        ];
+        for (metric, path) in paths {
+            if let Some(latency) = self.max_latency(path) {
+                record = record.metric(metric, latency as f64);
+            }
+        }
+        record.metric("overlapping_slots", self.overlapping_slots() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(responsive_percent: u64) -> FinalityCheck {
+        FinalityCheck { validators: 5, slots: 1, responsive_percent, max_delay: 0, runs: 3, seed: 7 }
+    }
+
+    #[test]
+    fn test_responsive_rounds_down() {
+        assert_eq!(check(90).responsive(), 4);
+        assert_eq!(check(60).responsive(), 3);
+        assert_eq!(check(100).responsive(), 5);
+        assert_eq!(check(90).responsive_stake_percent(), 80);
+    }
+
+    #[test]
+    fn test_full_stake_finalizes_at_once() {
+        let report = check(100).run();
+        assert_eq!(report.outcomes.len(), 3);
+        assert_eq!(report.failure(None), None);
+        assert_eq!(report.max_latency(None), Some(0));
+        assert!(!report.paths_used().is_empty());
+
+        let record = report.record("full stake", &check(100), "finalization", true);
+        assert_eq!(record.properties, vec![("finalization".to_string(), "held".to_string())]);
+        assert!(record.metrics.contains(&("max_finalization_ticks".to_string(), 0.0)));
+    }
+
+    #[test]
+    fn test_fast_path_judged_by_certificate() {
+        // With delays the slow path finalizes this run's slot first, while the NotarVotes that
+        // form a fast-finalization certificate are still in flight
+        let check = FinalityCheck { validators: 5, slots: 1, responsive_percent: 100, max_delay: 1, runs: 1, seed: 3 };
+        let report = check.run();
+        assert_eq!(report.outcomes[0].earliest_finalization(1, FinalizationPath::Fast), None);
+        assert!(report.outcomes[0].fast_certificate_formed(1));
+        assert_eq!(report.failure(Some(FinalizationPath::Fast)), None);
+    }
+
+    #[test]
+    fn test_insufficient_stake_does_not_finalize() {
+        // Two of five validators cannot notarize a block on their own
+        let report = check(40).run();
+        assert!(report.failure(None).is_some_and(|reason| reason.contains("not finalized")));
+        assert!(report.failure(Some(FinalizationPath::Fast)).is_some());
+        assert_eq!(report.max_latency(None), None);
+    }
+}
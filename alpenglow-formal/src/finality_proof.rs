@@ -0,0 +1,215 @@
+//! Exportable finality proofs.
+//! A [`FinalityProof`] packages what a third party needs to check that a block is finalized
+//! without following consensus: the fast-finalization certificate of its slot, or its
+//! notarization and the slot's finalization certificate, as voter bitmaps, along with a
+//! commitment to the stake table they were signed under. [`export_proof`] picks the certificates
+//! out of those a node holds, [`FinalityProof::to_text`] serializes the proof and
+//! [`FinalityProof::verify`] checks it against a stake table with the light client's
+//! [`verify_chain`]. The light-client model checks that a verified proof always names a block
+//! the full nodes finalized, and that every block they finalized can be proved.
+
+use std::fmt::{self, Write as _};
+use crate::light_client::{verify_chain, Certificate, ChainError};
+use crate::voters::{VoterSet, MAX_VOTERS};
+
+// Type aliases for clarity
+type Slot = u64;
+type Hash = u64;
+type Stake = u64;
+
+/// First line of a serialized proof
+const PROOF_HEADER: &str = "finality-proof";
+
+/// Commitment to a stake table: the 64-bit FNV-1a hash of its stakes, little-endian, in
+/// validator order, which stays the same across builds so proofs can be exchanged
+pub fn stake_commitment(stakes: &[Stake]) -> u64 {
+    stakes.iter()
+        .flat_map(|stake| stake.to_le_bytes())
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// The certificates a proof finalizes its block with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProofPath {
+    /// A fast-finalization certificate
+    Fast { signers: VoterSet },
+    /// A notarization certificate and the slot's finalization certificate
+    Slow { notarization: VoterSet, finalization: VoterSet },
+}
+
+/// Why a proof does not verify
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProofError {
+    /// The proof was made under a different stake table
+    StakeTableMismatch { expected: u64, actual: u64 },
+    /// Its certificates do not verify against the stake table
+    Chain(ChainError),
+}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofError::StakeTableMismatch { expected, actual } => {
+                write!(f, "proof commits to stake table {:016x}, expected {:016x}", actual, expected)
+            }
+            ProofError::Chain(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Proof that the block `hash` is finalized in `slot`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FinalityProof {
+    pub slot: Slot,
+    pub hash: Hash,
+    /// [`stake_commitment`] of the stake table the certificates were signed under
+    pub stake_commitment: u64,
+    pub path: ProofPath,
+}
+
+/// Voters as a bitmap, one bit per validator
+fn bitmap(voters: &VoterSet) -> u128 {
+    voters.iter().fold(0, |bitmap, voter| bitmap | (1 << voter))
+}
+
+fn from_bitmap(bitmap: u128) -> VoterSet {
+    (0..MAX_VOTERS).filter(|voter| bitmap & (1 << voter) != 0).collect()
+}
+
+impl FinalityProof {
+    /// The certificates of the proof
+    pub fn certificates(&self) -> Vec<Certificate> {
+        let (slot, hash) = (self.slot, self.hash);
+        match &self.path {
+            ProofPath::Fast { signers } => vec![Certificate::FastFinalization { slot, hash, signers: signers.clone() }],
+            ProofPath::Slow { notarization, finalization } => vec![
+                Certificate::Notarization { slot, hash, signers: notarization.clone() },
+                Certificate::Finalization { slot, signers: finalization.clone() },
+            ],
+        }
+    }
+
+    /// Check the proof against the stake table `stakes`, indexed by validator
+    pub fn verify(&self, stakes: &[Stake]) -> Result<(), ProofError> {
+        let expected = stake_commitment(stakes);
+        if self.stake_commitment != expected {
+            return Err(ProofError::StakeTableMismatch { expected, actual: self.stake_commitment });
+        }
+        verify_chain(stakes, &self.certificates()).map(|_| ()).map_err(ProofError::Chain)
+    }
+
+    /// The proof format: a header line, then one keyword-prefixed line each for the slot, the
+    /// block hash, the stake table commitment and every certificate's voter bitmap, in hex
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        writeln!(text, "{}", PROOF_HEADER).unwrap();
+        writeln!(text, "slot {}", self.slot).unwrap();
+        writeln!(text, "hash {}", self.hash).unwrap();
+        writeln!(text, "stake-table {:016x}", self.stake_commitment).unwrap();
+        match &self.path {
+            ProofPath::Fast { signers } => writeln!(text, "fast-finalization {:x}", bitmap(signers)).unwrap(),
+            ProofPath::Slow { notarization, finalization } => {
+                writeln!(text, "notarization {:x}", bitmap(notarization)).unwrap();
+                writeln!(text, "finalization {:x}", bitmap(finalization)).unwrap();
+            }
+        }
+        text
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().enumerate().filter(|(_, line)| !line.is_empty());
+        if lines.next().map(|(_, line)| line) != Some(PROOF_HEADER) {
+            return Err(format!("a proof starts with a '{}' line", PROOF_HEADER));
+        }
+        let (mut slot, mut hash, mut commitment) = (None, None, None);
+        let (mut fast, mut notarization, mut finalization) = (None, None, None);
+        for (number, line) in lines {
+            let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let bad = |what: &str, e: &dyn fmt::Display| format!("line {}: bad {}: {}", number + 1, what, e);
+            let signers = || u128::from_str_radix(rest, 16).map(from_bitmap).map_err(|e| bad("voter bitmap", &e));
+            match keyword {
+                "slot" => slot = Some(rest.parse::<Slot>().map_err(|e| bad("slot", &e))?),
+                "hash" => hash = Some(rest.parse::<Hash>().map_err(|e| bad("hash", &e))?),
+                "stake-table" => commitment = Some(u64::from_str_radix(rest, 16).map_err(|e| bad("stake table commitment", &e))?),
+                "fast-finalization" => fast = Some(signers()?),
+                "notarization" => notarization = Some(signers()?),
+                "finalization" => finalization = Some(signers()?),
+                other => return Err(format!("line {}: unknown keyword '{}'", number + 1, other)),
+            }
+        }
+        let path = match (fast, notarization, finalization) {
+            (Some(signers), None, None) => ProofPath::Fast { signers },
+            (None, Some(notarization), Some(finalization)) => ProofPath::Slow { notarization, finalization },
+            _ => return Err("a proof holds a fast-finalization certificate, or a notarization and a finalization certificate".to_string()),
+        };
+        Ok(Self {
+            slot: slot.ok_or("missing slot")?,
+            hash: hash.ok_or("missing hash")?,
+            stake_commitment: commitment.ok_or("missing stake table commitment")?,
+            path,
+        })
+    }
+}
+
+/// Package a proof that `slot` is finalized from `certificates`, those verifying against
+/// `stakes` alone: its fast-finalization certificate if there is one, otherwise a notarization
+/// certificate and its finalization certificate; `None` if they do not finalize the slot
+pub fn export_proof(stakes: &[Stake], certificates: &[Certificate], slot: Slot) -> Option<FinalityProof> {
+    let valid: Vec<&Certificate> = certificates.iter()
+        .filter(|certificate| certificate.slot() == slot && verify_chain(stakes, std::slice::from_ref(*certificate)).is_ok())
+        .collect();
+    let proof = |hash, path| Some(FinalityProof { slot, hash, stake_commitment: stake_commitment(stakes), path });
+    if let Some(Certificate::FastFinalization { hash, signers, .. }) = valid.iter().find(|c| matches!(c, Certificate::FastFinalization { .. })) {
+        return proof(*hash, ProofPath::Fast { signers: signers.clone() });
+    }
+    let finalization = valid.iter().find_map(|certificate| match certificate {
+        Certificate::Finalization { signers, .. } => Some(signers.clone()),
+        _ => None,
+    })?;
+    let (hash, notarization) = valid.iter().find_map(|certificate| match certificate {
+        Certificate::Notarization { hash, signers, .. } => Some((*hash, signers.clone())),
+        _ => None,
+    })?;
+    proof(hash, ProofPath::Slow { notarization, finalization })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signers(voters: impl IntoIterator<Item = usize>) -> VoterSet {
+        voters.into_iter().collect()
+    }
+
+    #[test]
+    fn test_proof_round_trips_and_verifies() {
+        let stakes = [1; 5];
+        let certificates = [
+            Certificate::Notarization { slot: 2, hash: 7, signers: signers([0, 1, 2]) },
+            Certificate::Finalization { slot: 2, signers: signers([1, 2, 3]) },
+            Certificate::Notarization { slot: 3, hash: 9, signers: signers([0, 1, 2]) },
+        ];
+        let proof = export_proof(&stakes, &certificates, 2).expect("slot 2 is finalized");
+        assert_eq!(proof.path, ProofPath::Slow { notarization: signers([0, 1, 2]), finalization: signers([1, 2, 3]) });
+        assert_eq!(proof.verify(&stakes), Ok(()));
+        assert_eq!(FinalityProof::parse(&proof.to_text()), Ok(proof.clone()));
+        assert!(proof.to_text().contains("notarization 7\n"));
+        assert_eq!(export_proof(&stakes, &certificates, 3), None);
+
+        let fast = [Certificate::FastFinalization { slot: 1, hash: 5, signers: signers(0..4) }];
+        let proof = export_proof(&stakes, &fast, 1).expect("slot 1 is fast-finalized");
+        assert_eq!(FinalityProof::parse(&proof.to_text()), Ok(proof));
+    }
+
+    #[test]
+    fn test_proof_rejected_under_other_stake_or_too_few_signers() {
+        let stakes = [1; 5];
+        let certificates = [Certificate::FastFinalization { slot: 1, hash: 5, signers: signers(0..4) }];
+        let proof = export_proof(&stakes, &certificates, 1).expect("slot 1 is fast-finalized");
+        assert!(matches!(proof.verify(&[1, 1, 1, 1, 4]), Err(ProofError::StakeTableMismatch { .. })));
+
+        let forged = FinalityProof { path: ProofPath::Fast { signers: signers(0..3) }, ..proof };
+        assert_eq!(forged.verify(&stakes), Err(ProofError::Chain(ChainError::InsufficientStake { index: 0, stake: 3 })));
+        assert!(FinalityProof::parse("finality-proof\nslot 1\nhash 5\nstake-table 0\n").is_err());
+    }
+}
@@ -0,0 +1,210 @@
+//! Byte-driven runs of the models, the entry points of the fuzz targets under `fuzz/`.
+//! The first bytes of an input pick the model parameters; the rest is read as steps. Most steps
+//! take one of the actions enabled in the current state, chosen by the next bytes, and the
+//! model's key invariants must hold in the state reached. Other steps build an arbitrary action
+//! from the following bytes, naming validators and slots just past the configured ranges too,
+//! and only require `next_state` to reject or apply it without panicking: its result is
+//! discarded, since an action the model never offers may legitimately break invariants.
+//! Missing bytes read as zero, so every input is a valid run.
+
+use crate::certificate::VoteRouting;
+use crate::modelling::safety::{self, MessageInTransit as SafetyTransit, SafetyAction, SafetyMessage, SafetyModel, Synchrony};
+use crate::network::NetworkFault;
+use crate::votor::{Action, Message, MessageInTransit, VotorModel};
+use stateright::Model;
+use std::fmt::Debug;
+
+/// Most steps read from one input
+const MAX_STEPS: usize = 1000;
+/// Step bytes at or above this build an arbitrary action, about one step in eight
+const ARBITRARY_STEP: u8 = 224;
+
+/// Bytes of a fuzz input, read front to back
+struct Input<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Input<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn byte(&mut self) -> u8 {
+        let Some((first, rest)) = self.bytes.split_first() else {
+            return 0;
+        };
+        self.bytes = rest;
+        *first
+    }
+
+    /// A number below `bound`, from two bytes when one cannot cover it
+    fn below(&mut self, bound: usize) -> usize {
+        let value = if bound > 256 { u16::from_be_bytes([self.byte(), self.byte()]) as usize } else { self.byte() as usize };
+        value % bound.max(1)
+    }
+}
+
+/// Run `model` along `input`, checking `invariants` after every enabled action and feeding it the
+/// actions `arbitrary` builds
+fn run<M: Model>(model: &M, mut input: Input, invariants: &[&'static str],
+                 arbitrary: impl Fn(&M, &mut Input) -> M::Action)
+where
+    M::Action: Clone + Debug,
+{
+    let invariants: Vec<_> = invariants.iter().map(|name| (*name, model.property(name))).collect();
+    let mut state = model.init_states().remove(0);
+    let mut taken = Vec::new();
+    for _ in 0..MAX_STEPS {
+        if input.is_empty() {
+            break;
+        }
+        if input.byte() >= ARBITRARY_STEP {
+            let _ = model.next_state(&state, arbitrary(model, &mut input));
+            continue;
+        }
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        if actions.is_empty() {
+            break;
+        }
+        let action = actions.swap_remove(input.below(actions.len()));
+        let Some(next) = model.next_state(&state, action.clone()) else {
+            continue;
+        };
+        taken.push(action);
+        state = next;
+        for (name, property) in &invariants {
+            assert!((property.condition)(model, &state), "{} violated after {:?}", name, taken);
+        }
+    }
+}
+
+/// Fuzz `VotorModel::next_state` with 2 to 5 validators, 1 to 3 slots and up to 2 network faults
+pub fn votor(data: &[u8]) {
+    let mut input = Input::new(data);
+    let model = VotorModel {
+        honest_validators: 2 + input.below(4),
+        max_slot: 1 + input.below(3) as u64,
+        network_faults: input.below(3),
+    };
+    run(&model, input, &["safety", "notar_skip_exclusion", "no_certificate_regression", "finality_knowledge_propagation", "no_finalized_rollback", "finalized_implies_notarized", "fast_finalization_uncontested", "certificate_stake_backed"], |model, input| {
+        let validator = |input: &mut Input| input.below(model.honest_validators + 2);
+        let slot = |input: &mut Input| input.below(model.max_slot as usize + 2) as u64;
+        let message = |input: &mut Input| {
+            let (slot, proposer) = (slot(input), validator(input));
+            let hash = slot * 1000 + proposer as u64;
+            match input.below(4) {
+                0 => Message::Block { slot, hash, parent_hash: slot.saturating_sub(1) * 1000 + validator(input) as u64 },
+                1 => Message::NotarVote { slot, hash, voter: validator(input) },
+                2 => Message::FinalVote { slot, voter: validator(input) },
+                _ => Message::SkipVote { slot, voter: validator(input) },
+            }
+        };
+        match input.below(6) {
+            0 => Action::Propose { slot: slot(input), proposer: validator(input) },
+            1 => Action::Timeout { slot: slot(input), node_id: validator(input) },
+            2 => Action::Deliver { msg: MessageInTransit::new(validator(input), message(input)) },
+            3 => Action::Prune { slot: slot(input), node_id: validator(input) },
+            4 => Action::LearnFinalization { slot: slot(input), node_id: validator(input) },
+            _ => {
+                let msg = MessageInTransit::new(validator(input), message(input));
+                Action::NetworkFault { fault: arbitrary_fault(input, msg) }
+            }
+        }
+    });
+}
+
+/// Fuzz `SafetyModel::next_state` with 3 to 6 validators, 1 to 3 slots, up to one Byzantine
+/// validator and up to 2 network faults, under either synchrony assumption and vote routing
+pub fn safety(data: &[u8]) {
+    let mut input = Input::new(data);
+    let model = SafetyModel {
+        validator_count: 3 + input.below(4),
+        max_slot: 1 + input.below(3) as u64,
+        byzantine_count: input.below(2),
+        offline_budget_percent: safety::DEFAULT_OFFLINE_BUDGET_PERCENT,
+        corruption_budget_percent: safety::DEFAULT_CORRUPTION_BUDGET_PERCENT,
+        network_faults: input.below(3),
+        synchrony: if input.below(2) == 0 { Synchrony::default() } else { Synchrony::Asynchronous },
+        vote_routing: if input.below(2) == 0 { VoteRouting::AllToAll } else { VoteRouting::Aggregator },
+    };
+    let invariants = ["no_conflicting_finalization", "certificate_uniqueness", "honest_never_slashable", "finalized_implies_notarized",
+                      "certificate_stake_backed", "certificates_genuine"];
+    run(&model, input, &invariants, |model, input| {
+        let validator = |input: &mut Input| input.below(model.validator_count + 2);
+        let voters = |input: &mut Input| (0..model.validator_count + 2).filter(|_| input.below(2) == 0).collect();
+        let slot = |input: &mut Input| input.below(model.max_slot as usize + 2) as u64;
+        let hash = |input: &mut Input| slot(input) * 1000 + validator(input) as u64;
+        let message = |input: &mut Input| match input.below(5) {
+            0 => SafetyMessage::BlockProposal { slot: slot(input), hash: hash(input), parent: hash(input), proposer: validator(input) },
+            1 => SafetyMessage::Vote { slot: slot(input), hash: hash(input), voter: validator(input) },
+            2 => SafetyMessage::ConflictingVote { slot: slot(input), hash: hash(input), voter: validator(input) },
+            3 => SafetyMessage::AggregatedCertificate { slot: slot(input), hash: hash(input), voters: voters(input) },
+            _ => SafetyMessage::CertificateFormed { slot: slot(input), hash: hash(input), stake: input.byte() as u64 * 4 },
+        };
+        match input.below(10) {
+            0 => SafetyAction::ProposeBlock { slot: slot(input), proposer: validator(input), parent: hash(input) },
+            1 => SafetyAction::DeliverMessage { msg: SafetyTransit::new(validator(input), message(input)) },
+            2 => SafetyAction::CreateConflictingVote { slot: slot(input), byzantine_validator: validator(input) },
+            3 => SafetyAction::FormCertificate { slot: slot(input), hash: hash(input), stake: input.byte() as u64 * 4 },
+            4 => SafetyAction::GoOffline { validator: validator(input) },
+            5 => SafetyAction::ComeOnline { validator: validator(input) },
+            6 => SafetyAction::CorruptValidator { id: validator(input) },
+            7 => SafetyAction::AdvanceSlot,
+            8 => SafetyAction::ForgeCertificate { slot: slot(input), hash: hash(input), voters: voters(input) },
+            _ => {
+                let msg = SafetyTransit::new(validator(input), message(input));
+                SafetyAction::NetworkFault { fault: arbitrary_fault(input, msg) }
+            }
+        }
+    });
+}
+
+fn arbitrary_fault<M>(input: &mut Input, msg: M) -> NetworkFault<M> {
+    match input.below(4) {
+        0 => NetworkFault::Drop(msg),
+        1 => NetworkFault::Duplicate(msg),
+        2 => NetworkFault::Delay(msg),
+        _ => NetworkFault::Release(msg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_input_reads_past_end_as_zero() {
+        let mut input = Input::new(&[7, 1, 2]);
+        assert_eq!(input.below(5), 2);
+        assert_eq!(input.below(1000), 258);
+        assert!(input.is_empty());
+        assert_eq!(input.below(5), 0);
+    }
+
+    #[test]
+    fn test_invalid_actions_are_rejected() {
+        let model = VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 };
+        let state = model.init_states().remove(0);
+        assert!(model.next_state(&state, Action::Propose { slot: 0, proposer: 0 }).is_none());
+        assert!(model.next_state(&state, Action::Timeout { slot: 1, node_id: 2 }).is_none());
+    }
+
+    #[test]
+    fn test_random_inputs() {
+        // The empty input, a run of enabled actions, and random inputs mixing in arbitrary actions
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut inputs = vec![Vec::new(), vec![0; 300]];
+        inputs.extend((0..200).map(|_| (0..rng.gen_range(0..600)).map(|_| rng.gen()).collect::<Vec<u8>>()));
+        for input in &inputs {
+            votor(input);
+            safety(input);
+        }
+    }
+}
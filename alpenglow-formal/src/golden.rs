@@ -0,0 +1,329 @@
+//! Golden-trace regression tests.
+//! Property checks only notice a refactor of a transition function when it breaks a property; a
+//! change of behaviour that keeps every property green goes unnoticed. A golden trace pins down
+//! one canonical scenario, such as the fast-path finalization of slot 1 by three validators: the
+//! actions taken from an initial state and the state they lead to. [`check_golden`] replays a
+//! fixture through `next_state` and fails when an action is no longer enabled, no longer changes
+//! the state, or the final state differs. Fixtures live in `golden/` and are recorded as the
+//! shortest path to the scenario's goal when missing or when `UPDATE_GOLDEN` is set.
+//!
+//! The same format carries the parameters of the model a trace was recorded on, so that any
+//! scenario, such as a counterexample from a failed check, can be saved with
+//! [`GoldenTrace::from_path`], shared, replayed after code changes and kept as a fixture.
+
+use stateright::Model;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::{self, Debug, Write as _};
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::PathBuf;
+
+/// Directory of the fixtures, relative to the crate root
+pub const GOLDEN_DIR: &str = "golden";
+/// Environment variable that makes [`check_golden`] re-record every fixture
+pub const UPDATE_GOLDEN_ENV: &str = "UPDATE_GOLDEN";
+/// Most states searched when recording a trace
+pub const MAX_RECORD_STATES: usize = 200_000;
+
+/// A recorded scenario: actions from one initial state and the state they lead to, both in
+/// their `Debug` form so fixtures stay readable and diffable
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GoldenTrace {
+    pub description: String,
+    /// Parameters of the model the trace was recorded on, as (name, value) pairs
+    pub parameters: Vec<(String, String)>,
+    /// Index into the model's initial states
+    pub init: usize,
+    pub actions: Vec<String>,
+    pub final_state: String,
+}
+
+impl GoldenTrace {
+    /// The trace of `steps`, a path of (state, action taken from it) steps that starts in one of
+    /// `model`'s initial states, as a checker discovery does
+    pub fn from_path<M>(model: &M, description: &str, steps: &[(M::State, Option<M::Action>)]) -> Option<Self>
+    where
+        M: Model,
+        M::State: Debug,
+        M::Action: Debug,
+    {
+        let (first, _) = steps.first()?;
+        let (last, _) = steps.last()?;
+        let first = format!("{:?}", first);
+        let init = model.init_states().iter().position(|state| format!("{:?}", state) == first)?;
+        Some(Self {
+            description: description.to_string(),
+            parameters: Vec::new(),
+            init,
+            actions: steps.iter().filter_map(|(_, action)| action.as_ref()).map(|action| format!("{:?}", action)).collect(),
+            final_state: format!("{:?}", last),
+        })
+    }
+
+    /// Value of the parameter `name`, if recorded
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    /// The trace format: a `#` description line, then one keyword-prefixed line each for every
+    /// model parameter, the initial state index, every action and the final state
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        writeln!(text, "# {}", self.description).unwrap();
+        for (name, value) in &self.parameters {
+            writeln!(text, "param {} {}", name, value).unwrap();
+        }
+        writeln!(text, "init {}", self.init).unwrap();
+        for action in &self.actions {
+            writeln!(text, "action {}", action).unwrap();
+        }
+        writeln!(text, "final {}", self.final_state).unwrap();
+        text
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut trace = GoldenTrace {
+            description: String::new(),
+            parameters: Vec::new(),
+            init: 0,
+            actions: Vec::new(),
+            final_state: String::new(),
+        };
+        let mut has_final = false;
+        for (number, line) in text.lines().enumerate() {
+            let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+            match keyword {
+                "" => {}
+                "#" => trace.description = rest.to_string(),
+                "param" => {
+                    let (name, value) = rest.split_once(' ')
+                        .ok_or_else(|| format!("line {}: a parameter needs a name and a value", number + 1))?;
+                    trace.parameters.push((name.to_string(), value.to_string()));
+                }
+                "init" => trace.init = rest.parse().map_err(|e| format!("line {}: bad initial state index: {}", number + 1, e))?,
+                "action" => trace.actions.push(rest.to_string()),
+                "final" => {
+                    trace.final_state = rest.to_string();
+                    has_final = true;
+                }
+                other => return Err(format!("line {}: unknown keyword '{}'", number + 1, other)),
+            }
+        }
+        if !has_final {
+            return Err("missing final state".to_string());
+        }
+        Ok(trace)
+    }
+}
+
+/// Why a trace no longer replays
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The model has fewer initial states than the trace's index
+    NoInitialState(usize),
+    /// No action with this `Debug` form is enabled at this step
+    NotEnabled { step: usize, action: String },
+    /// The action is enabled but `next_state` leaves the state unchanged
+    NoSuccessor { step: usize, action: String },
+    /// Every action replayed, but the state reached is different
+    FinalStateDiffers { expected: String, actual: String },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::NoInitialState(index) => write!(f, "the model has no initial state {}", index),
+            ReplayError::NotEnabled { step, action } => write!(f, "step {}: action not enabled: {}", step, action),
+            ReplayError::NoSuccessor { step, action } => write!(f, "step {}: action has no successor state: {}", step, action),
+            ReplayError::FinalStateDiffers { expected, actual } => {
+                write!(f, "final state differs\n  expected: {}\n  actual:   {}", expected, actual)
+            }
+        }
+    }
+}
+
+pub(crate) fn fingerprint<S: Hash>(state: &S) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// The shortest trace from an initial state to a state satisfying `goal`, searching at most
+/// `max_states` states breadth first in the model's own action order
+pub fn shortest_trace<M, G>(model: &M, description: &str, goal: G, max_states: usize) -> Option<GoldenTrace>
+where
+    M: Model,
+    M::State: Debug + Hash,
+    M::Action: Debug + Clone,
+    G: Fn(&M::State) -> bool,
+{
+    // Every discovered state with its parent and the action leading to it
+    let mut nodes: Vec<(Option<usize>, Option<M::Action>, usize)> = Vec::new();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    for (init, state) in model.init_states().into_iter().enumerate() {
+        if visited.insert(fingerprint(&state)) {
+            nodes.push((None, None, init));
+            queue.push_back((nodes.len() - 1, state));
+        }
+    }
+    while let Some((index, state)) = queue.pop_front() {
+        if goal(&state) {
+            let mut actions = Vec::new();
+            let mut current = index;
+            while let (Some(parent), Some(action), _) = &nodes[current] {
+                actions.push(format!("{:?}", action));
+                current = *parent;
+            }
+            actions.reverse();
+            return Some(GoldenTrace {
+                description: description.to_string(),
+                parameters: Vec::new(),
+                init: nodes[current].2,
+                actions,
+                final_state: format!("{:?}", state),
+            });
+        }
+        if visited.len() >= max_states {
+            continue;
+        }
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        for action in actions {
+            if let Some(next) = model.next_state(&state, action.clone()) {
+                if visited.insert(fingerprint(&next)) {
+                    nodes.push((Some(index), Some(action), 0));
+                    queue.push_back((nodes.len() - 1, next));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Replay `trace` through `model`, returning the state it leads to
+pub fn replay<M>(model: &M, trace: &GoldenTrace) -> Result<M::State, ReplayError>
+where
+    M: Model,
+    M::State: Debug,
+    M::Action: Debug,
+{
+    let mut state = model.init_states().into_iter().nth(trace.init).ok_or(ReplayError::NoInitialState(trace.init))?;
+    for (step, recorded) in trace.actions.iter().enumerate() {
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        let action = actions.into_iter()
+            .find(|action| format!("{:?}", action) == *recorded)
+            .ok_or_else(|| ReplayError::NotEnabled { step, action: recorded.clone() })?;
+        state = model.next_state(&state, action)
+            .ok_or_else(|| ReplayError::NoSuccessor { step, action: recorded.clone() })?;
+    }
+    let actual = format!("{:?}", state);
+    if actual != trace.final_state {
+        return Err(ReplayError::FinalStateDiffers { expected: trace.final_state.clone(), actual });
+    }
+    Ok(state)
+}
+
+/// Path of the fixture `name`
+pub fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(GOLDEN_DIR).join(format!("{}.trace", name))
+}
+
+/// Replay the fixture `name` through `model`, first recording it as the shortest path to `goal`
+/// when it is missing or `UPDATE_GOLDEN` is set
+pub fn check_golden<M, G>(model: &M, name: &str, description: &str, goal: G) -> Result<(), String>
+where
+    M: Model,
+    M::State: Debug + Hash,
+    M::Action: Debug + Clone,
+    G: Fn(&M::State) -> bool,
+{
+    let path = golden_path(name);
+    if std::env::var_os(UPDATE_GOLDEN_ENV).is_some() || !path.exists() {
+        let trace = shortest_trace(model, description, goal, MAX_RECORD_STATES)
+            .ok_or_else(|| format!("{}: no state within {} states reaches the goal", name, MAX_RECORD_STATES))?;
+        fs::create_dir_all(path.parent().unwrap()).map_err(|e| e.to_string())?;
+        fs::write(&path, trace.to_text()).map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    }
+    let text = fs::read_to_string(&path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+    let trace = GoldenTrace::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    replay(model, &trace).map(|_| ()).map_err(|e| format!("{} no longer replays: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::invariants::ConsensusRecord;
+    use crate::certificate::VoteRouting;
+    use crate::modelling::safety::{SafetyModel, Synchrony, DEFAULT_CORRUPTION_BUDGET_PERCENT, DEFAULT_OFFLINE_BUDGET_PERCENT};
+    use crate::votor::VotorModel;
+
+    #[test]
+    fn test_golden_trace_round_trip() {
+        let trace = GoldenTrace {
+            description: "two steps".to_string(),
+            parameters: vec![("model".to_string(), "votor".to_string()), ("validators".to_string(), "3".to_string())],
+            init: 1,
+            actions: vec!["Propose { slot: 1, proposer: 0 }".to_string(), "Timeout { slot: 1, node_id: 2 }".to_string()],
+            final_state: "State { slot: 1 }".to_string(),
+        };
+        assert_eq!(GoldenTrace::parse(&trace.to_text()).as_ref(), Ok(&trace));
+        assert_eq!(trace.parameter("validators"), Some("3"));
+        assert_eq!(trace.parameter("slots"), None);
+        assert!(GoldenTrace::parse("init 0\n").is_err());
+        assert!(GoldenTrace::parse("step x\nfinal y\n").is_err());
+    }
+
+    #[test]
+    fn test_replay_detects_changed_behaviour() {
+        let model = VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 };
+        let trace = shortest_trace(&model, "first notarization", |state| !state.notarized_blocks().is_empty(), 10_000).unwrap();
+        assert!(replay(&model, &trace).is_ok());
+
+        // A checker discovery path converts to the same trace
+        let mut state = model.init_states().remove(trace.init);
+        let mut steps = Vec::new();
+        for recorded in &trace.actions {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            let action = actions.into_iter().find(|action| format!("{:?}", action) == *recorded).unwrap();
+            let next = model.next_state(&state, action.clone()).unwrap();
+            steps.push((state, Some(action)));
+            state = next;
+        }
+        steps.push((state, None));
+        assert_eq!(GoldenTrace::from_path(&model, "first notarization", &steps), Some(trace.clone()));
+
+        let mut moved = trace.clone();
+        moved.final_state.push(' ');
+        assert!(matches!(replay(&model, &moved), Err(ReplayError::FinalStateDiffers { .. })));
+
+        let mut unknown = trace.clone();
+        unknown.actions.insert(0, "Vanish".to_string());
+        assert_eq!(replay(&model, &unknown), Err(ReplayError::NotEnabled { step: 0, action: "Vanish".to_string() }));
+    }
+
+    #[test]
+    fn test_golden_votor_fast_path() {
+        let model = VotorModel { honest_validators: 3, max_slot: 1, network_faults: 0 };
+        check_golden(&model, "votor_3v_fast_path_slot_1", "3 validators, fast-path finalization of slot 1",
+                     |state| state.fast_finalized_blocks().iter().any(|(slot, _)| *slot == 1)).unwrap();
+    }
+
+    #[test]
+    fn test_golden_safety_finalization() {
+        let model = SafetyModel {
+            validator_count: 3,
+            max_slot: 1,
+            byzantine_count: 0,
+            offline_budget_percent: DEFAULT_OFFLINE_BUDGET_PERCENT,
+            corruption_budget_percent: DEFAULT_CORRUPTION_BUDGET_PERCENT,
+            network_faults: 0,
+            synchrony: Synchrony::default(),
+            vote_routing: VoteRouting::default(),
+        };
+        check_golden(&model, "safety_3v_finalization_slot_1", "3 honest validators, finalization of slot 1",
+                     |state| state.finalized_blocks().iter().any(|(slot, _)| *slot == 1)).unwrap();
+    }
+}
@@ -0,0 +1,220 @@
+//! Self-contained HTML reports of model-checking runs.
+//! Reviewers who do not run Rust still need to see what was checked and how it went. A
+//! [`RunReport`] captures a run's parameters, state counts, timing and the verdict of every
+//! property, and [`to_html`] renders it as a single HTML file with inline styles and one inline
+//! SVG diagram per counterexample, so the report can be mailed or attached as it is.
+
+use stateright::{Checker, Expectation, Model};
+use std::fmt::{Debug, Write as _};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Characters of an action shown on a diagram edge; the full text is in the edge's tooltip
+const MAX_EDGE_LABEL_CHARS: usize = 96;
+/// Vertical distance between two states on a counterexample diagram
+const STEP_HEIGHT: usize = 64;
+
+/// The verdict on one property
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PropertyResult {
+    pub name: String,
+    /// `always`, `eventually` or `sometimes`
+    pub expectation: &'static str,
+    pub passed: bool,
+    /// Actions of the discovery: a counterexample, or an example for `sometimes` properties
+    pub trace: Option<Vec<String>>,
+}
+
+/// Everything a report shows about one run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunReport {
+    pub name: String,
+    /// Parameters of the run, as (name, value) pairs
+    pub parameters: Vec<(String, String)>,
+    pub state_count: usize,
+    pub unique_state_count: usize,
+    pub max_depth: usize,
+    pub elapsed: Duration,
+    pub properties: Vec<PropertyResult>,
+}
+
+impl RunReport {
+    /// Report on the finished run of `checker`
+    pub fn from_checker<M, C>(name: &str, parameters: Vec<(String, String)>, checker: &C, elapsed: Duration) -> Self
+    where
+        M: Model,
+        M::State: Clone + PartialEq,
+        M::Action: Debug + Clone + PartialEq,
+        C: Checker<M>,
+    {
+        let mut discoveries = checker.discoveries();
+        let properties = checker.model().properties().into_iter()
+            .map(|property| {
+                let trace = discoveries.remove(property.name)
+                    .map(|path| path.into_actions().iter().map(|action| format!("{:?}", action)).collect::<Vec<_>>());
+                let passed = matches!(property.expectation, Expectation::Sometimes) == trace.is_some();
+                PropertyResult { name: property.name.to_string(), expectation: expectation_name(&property.expectation), passed, trace }
+            })
+            .collect();
+        Self {
+            name: name.to_string(),
+            parameters,
+            state_count: checker.state_count(),
+            unique_state_count: checker.unique_state_count(),
+            max_depth: checker.max_depth(),
+            elapsed,
+            properties,
+        }
+    }
+
+    pub fn failures(&self) -> usize {
+        self.properties.iter().filter(|property| !property.passed).count()
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn expectation_name(expectation: &Expectation) -> &'static str {
+    match expectation {
+        Expectation::Always => "always",
+        Expectation::Eventually => "eventually",
+        Expectation::Sometimes => "sometimes",
+    }
+}
+
+/// A vertical diagram of a trace: one box per state, one labelled arrow per action, the last
+/// state highlighted when it violates the property
+fn trace_svg(actions: &[String], violating: bool) -> String {
+    let height = (actions.len() + 1) * STEP_HEIGHT;
+    let mut svg = String::new();
+    writeln!(svg, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"760\" height=\"{}\" font-family=\"monospace\" font-size=\"12\">", height).unwrap();
+    for step in 0..=actions.len() {
+        let y = step * STEP_HEIGHT;
+        let last = step == actions.len();
+        let stroke = if last && violating { "#c0392b" } else { "#34495e" };
+        let fill = if last && violating { "#fdecea" } else { "#ecf0f1" };
+        writeln!(svg, "  <rect x=\"4\" y=\"{}\" width=\"96\" height=\"28\" rx=\"4\" fill=\"{}\" stroke=\"{}\" stroke-width=\"{}\"/>",
+                 y + 2, fill, stroke, if last && violating { 2 } else { 1 }).unwrap();
+        writeln!(svg, "  <text x=\"52\" y=\"{}\" text-anchor=\"middle\">state {}</text>", y + 20, step).unwrap();
+        if let Some(action) = actions.get(step) {
+            let label: String = action.chars().take(MAX_EDGE_LABEL_CHARS).collect();
+            let label = if label.len() < action.len() { format!("{}…", label) } else { label };
+            writeln!(svg, "  <line x1=\"52\" y1=\"{}\" x2=\"52\" y2=\"{}\" stroke=\"#34495e\"/>", y + 30, y + STEP_HEIGHT).unwrap();
+            writeln!(svg, "  <path d=\"M48 {0} L52 {1} L56 {0} Z\" fill=\"#34495e\"/>", y + STEP_HEIGHT - 6, y + STEP_HEIGHT).unwrap();
+            writeln!(svg, "  <text x=\"64\" y=\"{}\"><title>{}</title>{}</text>", y + 50, escape(action), escape(&label)).unwrap();
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Render `report` as a standalone HTML document
+pub fn to_html(report: &RunReport) -> String {
+    let mut html = String::new();
+    writeln!(html, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">").unwrap();
+    writeln!(html, "<title>{}</title>", escape(&report.name)).unwrap();
+    writeln!(html, "<style>body {{ font-family: sans-serif; margin: 2em; }} table {{ border-collapse: collapse; }} \
+                    td, th {{ border: 1px solid #bdc3c7; padding: 4px 10px; text-align: left; }} \
+                    .pass {{ color: #27ae60; }} .fail {{ color: #c0392b; }} ol {{ font-family: monospace; }}</style>").unwrap();
+    writeln!(html, "</head>\n<body>").unwrap();
+    writeln!(html, "<h1>{}</h1>", escape(&report.name)).unwrap();
+    let verdict = match report.failures() {
+        0 => "<p class=\"pass\">All properties hold.</p>".to_string(),
+        failures => format!("<p class=\"fail\">{} of {} properties failed.</p>", failures, report.properties.len()),
+    };
+    writeln!(html, "{}", verdict).unwrap();
+
+    writeln!(html, "<h2>Parameters</h2>\n<table>").unwrap();
+    for (name, value) in &report.parameters {
+        writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", escape(name), escape(value)).unwrap();
+    }
+    writeln!(html, "</table>").unwrap();
+
+    let seconds = report.elapsed.as_secs_f64();
+    writeln!(html, "<h2>Exploration</h2>\n<table>").unwrap();
+    writeln!(html, "<tr><th>States</th><td>{}</td></tr>", report.state_count).unwrap();
+    writeln!(html, "<tr><th>Unique states</th><td>{}</td></tr>", report.unique_state_count).unwrap();
+    writeln!(html, "<tr><th>Maximum depth</th><td>{}</td></tr>", report.max_depth).unwrap();
+    writeln!(html, "<tr><th>Time</th><td>{:.2}s</td></tr>", seconds).unwrap();
+    writeln!(html, "<tr><th>Rate</th><td>{:.0} states/s</td></tr>", report.state_count as f64 / seconds.max(f64::EPSILON)).unwrap();
+    writeln!(html, "</table>").unwrap();
+
+    writeln!(html, "<h2>Properties</h2>\n<table>\n<tr><th>Property</th><th>Expectation</th><th>Verdict</th></tr>").unwrap();
+    for property in &report.properties {
+        let (class, verdict) = if property.passed { ("pass", "holds") } else { ("fail", "fails") };
+        let name = match property.trace {
+            Some(_) => format!("<a href=\"#{0}\">{0}</a>", escape(&property.name)),
+            None => escape(&property.name),
+        };
+        writeln!(html, "<tr><td>{}</td><td>{}</td><td class=\"{}\">{}</td></tr>",
+                 name, property.expectation, class, verdict).unwrap();
+    }
+    writeln!(html, "</table>").unwrap();
+
+    for property in &report.properties {
+        let Some(trace) = &property.trace else {
+            continue;
+        };
+        let kind = if property.passed { "Example" } else { "Counterexample" };
+        writeln!(html, "<h2 id=\"{0}\">{1}: {0}</h2>", escape(&property.name), kind).unwrap();
+        html.push_str(&trace_svg(trace, !property.passed));
+        writeln!(html, "<ol start=\"0\">").unwrap();
+        for action in trace {
+            writeln!(html, "<li>{}</li>", escape(action)).unwrap();
+        }
+        writeln!(html, "</ol>").unwrap();
+    }
+    writeln!(html, "</body>\n</html>").unwrap();
+    html
+}
+
+/// File name for a run: its name with every character other than letters and digits replaced
+fn file_name(name: &str) -> String {
+    let slug: String = name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+    format!("{}.html", slug)
+}
+
+/// Write `report` into `directory` as `<name>.html`
+pub fn write(report: &RunReport, directory: &Path) -> io::Result<PathBuf> {
+    fs::create_dir_all(directory)?;
+    let file = directory.join(file_name(&report.name));
+    fs::write(&file, to_html(report))?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::votor::VotorModel;
+
+    #[test]
+    fn test_html_report() {
+        let model = VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 };
+        let checker = model.checker().spawn_bfs().join();
+        let mut report = RunReport::from_checker("votor 2v 1s", vec![("validators".to_string(), "2".to_string())],
+                                                 &checker, Duration::from_millis(1500));
+        assert_eq!(report.failures(), 0);
+        assert_eq!(report.unique_state_count, checker.unique_state_count());
+
+        report.properties.push(PropertyResult {
+            name: "injected".to_string(),
+            expectation: "always",
+            passed: false,
+            trace: Some(vec!["Propose { slot: 1 }".to_string(), "Deliver { msg: <x> }".to_string()]),
+        });
+        let html = to_html(&report);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("1 of"));
+        assert!(html.contains("<td>1.50s</td>"));
+        assert!(html.contains("<h2 id=\"injected\">Counterexample: injected</h2>"));
+        assert_eq!(html.matches("<svg ").count(), 1);
+        assert_eq!(html.matches(">state ").count(), 3);
+        assert!(html.contains("Deliver { msg: &lt;x&gt; }"));
+        assert!(!html.contains("<x>"));
+        assert_eq!(file_name(&report.name), "votor_2v_1s.html");
+    }
+}
@@ -0,0 +1,279 @@
+//! Hybrid checking: exhaustive while memory allows, sampling afterwards.
+//! Large configurations usually run out of memory long before an exhaustive search finishes,
+//! and the partial result says nothing about the states never reached. The hybrid checker
+//! explores breadth-first until the process's resident memory crosses a threshold, then drops
+//! its visited set and keeps testing with seeded random walks started from the unexplored
+//! frontier. The report separates properties proven over the whole reachable state space from
+//! those that were only tested statistically.
+
+use crate::progress::resident_memory_kib;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use stateright::{Expectation, Model, Property};
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Default random walks run from the frontier after switching to sampling
+pub const DEFAULT_WALKS: usize = 1_000;
+/// Default steps per random walk
+pub const DEFAULT_WALK_LENGTH: usize = 200;
+/// States expanded between two memory measurements
+const MEMORY_CHECK_INTERVAL: usize = 1024;
+
+/// When to stop exhaustive exploration and how much to sample afterwards
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HybridConfig {
+    /// Resident memory, in KiB, above which exploration switches to sampling
+    pub memory_limit_kib: u64,
+    /// Random walks run from the frontier
+    pub walks: usize,
+    /// Most steps per random walk
+    pub walk_length: usize,
+    /// Seed for choosing frontier states and actions
+    pub seed: u64,
+}
+
+impl HybridConfig {
+    pub fn new(memory_limit_mib: u64, seed: u64) -> Self {
+        Self { memory_limit_kib: memory_limit_mib * 1024, walks: DEFAULT_WALKS, walk_length: DEFAULT_WALK_LENGTH, seed }
+    }
+}
+
+/// How far a property was established
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// Holds over the whole reachable state space, or a `sometimes` witness was found
+    Proven,
+    /// Refuted by a counterexample, or a `sometimes` property never held in a complete search
+    Refuted,
+    /// Neither proven nor refuted: only a prefix of the state space and random walks were checked
+    Sampled,
+}
+
+/// Outcome of a hybrid run
+#[derive(Clone, Debug)]
+pub struct HybridReport<A> {
+    /// Unique states explored exhaustively
+    pub exhaustive_states: usize,
+    /// Whether the exhaustive phase covered every reachable state
+    pub complete: bool,
+    /// Random walks run after switching to sampling
+    pub walks: usize,
+    /// Steps taken across all random walks
+    pub walk_steps: usize,
+    /// Every property with its expectation
+    pub properties: Vec<(&'static str, Expectation)>,
+    /// First discovery for each property, as the actions leading to it
+    pub discoveries: BTreeMap<&'static str, Vec<A>>,
+}
+
+impl<A: std::fmt::Debug> HybridReport<A> {
+    pub fn verdict(&self, property: &str) -> Option<Verdict> {
+        let (_, expectation) = self.properties.iter().find(|(name, _)| *name == property)?;
+        let discovered = self.discoveries.contains_key(property);
+        Some(match (expectation, discovered, self.complete) {
+            (Expectation::Sometimes, true, _) => Verdict::Proven,
+            (Expectation::Sometimes, false, true) => Verdict::Refuted,
+            (_, true, _) => Verdict::Refuted,
+            (_, false, true) => Verdict::Proven,
+            (_, false, false) => Verdict::Sampled,
+        })
+    }
+
+    pub fn print_summary(&self) {
+        if self.complete {
+            println!("Hybrid check: exhaustive over {} states", self.exhaustive_states);
+        } else {
+            println!("Hybrid check: {} states explored exhaustively before the memory limit, then {} random walks ({} steps) from the frontier",
+                     self.exhaustive_states, self.walks, self.walk_steps);
+        }
+        for (property, _) in &self.properties {
+            match self.verdict(property) {
+                Some(Verdict::Proven) => println!("  ✅ {}: proven exhaustively", property),
+                Some(Verdict::Refuted) => match self.discoveries.get(property) {
+                    Some(trace) => println!("  ❌ {}: refuted after {} steps: {:?}", property, trace.len(), trace),
+                    None => println!("  ❌ {}: never satisfied in any reachable state", property),
+                },
+                Some(Verdict::Sampled) | None => println!("  🎲 {}: tested statistically, not proven", property),
+            }
+        }
+    }
+}
+
+/// A state waiting to be explored, with the actions leading to it and the `eventually`
+/// properties already satisfied along the way
+struct Pending<M: Model> {
+    state: M::State,
+    trace: Vec<M::Action>,
+    satisfied: Vec<bool>,
+}
+
+impl<M: Model> Clone for Pending<M>
+where
+    M::State: Clone,
+    M::Action: Clone,
+{
+    fn clone(&self) -> Self {
+        Self { state: self.state.clone(), trace: self.trace.clone(), satisfied: self.satisfied.clone() }
+    }
+}
+
+fn fingerprint<S: Hash>(state: &S) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Check `pending` against every property, recording new discoveries; returns its successors
+fn visit<M: Model>(
+    model: &M,
+    properties: &[Property<M>],
+    pending: &mut Pending<M>,
+    discoveries: &mut BTreeMap<&'static str, Vec<M::Action>>,
+) -> Vec<(M::Action, M::State)>
+where
+    M::Action: Clone,
+{
+    for (index, property) in properties.iter().enumerate() {
+        let holds = (property.condition)(model, &pending.state);
+        match property.expectation {
+            Expectation::Always if !holds => {
+                discoveries.entry(property.name).or_insert_with(|| pending.trace.clone());
+            }
+            Expectation::Sometimes if holds => {
+                discoveries.entry(property.name).or_insert_with(|| pending.trace.clone());
+            }
+            Expectation::Eventually if holds => pending.satisfied[index] = true,
+            _ => {}
+        }
+    }
+
+    let mut actions = Vec::new();
+    model.actions(&pending.state, &mut actions);
+    let successors: Vec<_> = actions.into_iter()
+        .filter_map(|action| model.next_state(&pending.state, action.clone()).map(|next| (action, next)))
+        .filter(|(_, next)| model.within_boundary(next))
+        .collect();
+
+    // A path ending here never satisfied the `eventually` properties still unset
+    if successors.is_empty() {
+        for (index, property) in properties.iter().enumerate() {
+            if matches!(property.expectation, Expectation::Eventually) && !pending.satisfied[index] {
+                discoveries.entry(property.name).or_insert_with(|| pending.trace.clone());
+            }
+        }
+    }
+    successors
+}
+
+fn successor<M: Model>(pending: &Pending<M>, action: M::Action, state: M::State) -> Pending<M>
+where
+    M::Action: Clone,
+{
+    let mut trace = pending.trace.clone();
+    trace.push(action);
+    Pending { state, trace, satisfied: pending.satisfied.clone() }
+}
+
+/// Explore `model` exhaustively until resident memory exceeds the configured limit, then run
+/// random walks from the remaining frontier
+pub fn check<M: Model>(model: &M, config: &HybridConfig) -> HybridReport<M::Action>
+where
+    M::State: Clone + Hash,
+    M::Action: Clone,
+{
+    let properties = model.properties();
+    let mut discoveries = BTreeMap::new();
+    let mut visited = HashSet::new();
+    let mut frontier: VecDeque<Pending<M>> = model.init_states().into_iter()
+        .filter(|state| model.within_boundary(state) && visited.insert(fingerprint(state)))
+        .map(|state| Pending { state, trace: Vec::new(), satisfied: vec![false; properties.len()] })
+        .collect();
+
+    let mut expanded = 0;
+    while let Some(mut pending) = frontier.pop_front() {
+        if expanded % MEMORY_CHECK_INTERVAL == 0
+            && resident_memory_kib().is_some_and(|kib| kib > config.memory_limit_kib) {
+            frontier.push_front(pending);
+            break;
+        }
+        expanded += 1;
+        for (action, next) in visit(model, &properties, &mut pending, &mut discoveries) {
+            if visited.insert(fingerprint(&next)) {
+                frontier.push_back(successor(&pending, action, next));
+            }
+        }
+    }
+
+    let exhaustive_states = visited.len();
+    let complete = frontier.is_empty();
+    drop(visited);
+
+    // Sampling phase: walks from uniformly chosen frontier states
+    let frontier: Vec<_> = frontier.into();
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut walks = 0;
+    let mut walk_steps = 0;
+    if !complete {
+        for _ in 0..config.walks {
+            let Some(start) = frontier.choose(&mut rng) else {
+                break;
+            };
+            walks += 1;
+            let mut pending = start.clone();
+            for _ in 0..config.walk_length {
+                let successors = visit(model, &properties, &mut pending, &mut discoveries);
+                let Some((action, next)) = successors.choose(&mut rng).cloned() else {
+                    break;
+                };
+                pending = successor(&pending, action, next);
+                walk_steps += 1;
+            }
+        }
+    }
+
+    HybridReport {
+        exhaustive_states,
+        complete,
+        walks,
+        walk_steps,
+        properties: properties.iter().map(|property| (property.name, property.expectation.clone())).collect(),
+        discoveries,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::votor::VotorModel;
+
+    fn model() -> VotorModel {
+        VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 }
+    }
+
+    #[test]
+    fn test_hybrid_exhaustive_within_memory() {
+        let report = check(&model(), &HybridConfig::new(u64::MAX / 1024, 7));
+        assert!(report.complete);
+        assert_eq!(report.walks, 0);
+        for (property, _) in &report.properties {
+            assert_eq!(report.verdict(property), Some(Verdict::Proven));
+        }
+    }
+
+    #[test]
+    fn test_hybrid_switches_to_sampling() {
+        // Without a memory measurement there is nothing to switch on
+        if resident_memory_kib().is_none() {
+            return;
+        }
+        let report = check(&model(), &HybridConfig { walks: 20, ..HybridConfig::new(0, 7) });
+        assert!(!report.complete);
+        assert_eq!(report.walks, 20);
+        assert!(report.walk_steps > 0);
+        for (property, _) in &report.properties {
+            assert_eq!(report.verdict(property), Some(Verdict::Sampled));
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Derived invariants shared by the consensus models.
+//! Models record votes, certificates and finalizations separately; these invariants tie the
+//! records together (finalized ⇒ notarized, fast-finalized ⇒ finalized and notarized, no other
+//! block of a fast-finalized slot is notarized or finalized, certificate stake is backed by
+//! votes). Any model whose state implements [`ConsensusRecord`] can add them to its
+//! properties with [`derived_invariants`].
+//!
+//! Properties only ever see one state, so they cannot tell whether a finalization recorded
+//! earlier was since removed or overwritten. A state implementing [`FinalizedRecord`] carries
+//! the history of its finalizations as ghost state, and [`no_finalized_rollback`] checks that
+//! every finalization in it is still recorded.
+//!
+//! [`certificate_agreement`] compares the certificate stores of several validators, so models
+//! that keep one per validator can check that honest validators never disagree on a slot.
+
+use stateright::{Model, Property};
+use std::collections::{BTreeMap, BTreeSet};
+
+type Slot = u64;
+type Hash = u64;
+type Stake = u64;
+type ActorId = usize;
+
+/// Finalizations recorded so far, as (validator, slot, hash); models that keep a single record
+/// of finalized blocks use validator 0
+pub type FinalizedHistory = BTreeSet<(ActorId, Slot, Hash)>;
+
+/// What a model state records about the progress of consensus
+pub trait ConsensusRecord {
+    /// Blocks finalized by any path, excluding genesis
+    fn finalized_blocks(&self) -> BTreeSet<(Slot, Hash)>;
+
+    /// Blocks for which a notarization certificate was formed
+    fn notarized_blocks(&self) -> BTreeSet<(Slot, Hash)>;
+
+    /// Blocks finalized by the fast path; models without a fast path record none
+    fn fast_finalized_blocks(&self) -> BTreeSet<(Slot, Hash)> {
+        BTreeSet::new()
+    }
+
+    /// Every certificate formed, with the stake it claims
+    fn certificates(&self) -> Vec<(Slot, Hash, Stake)>;
+
+    /// Stake of the distinct validators that actually voted for a block
+    fn vote_stake(&self, slot: Slot, hash: Hash) -> Stake;
+}
+
+/// What a model state records about finalizations, now and in the states before it
+pub trait FinalizedRecord {
+    /// Finalizations recorded in this state
+    fn finalized_entries(&self) -> FinalizedHistory;
+
+    /// Finalizations recorded in this state or any state before it
+    fn finalized_history(&self) -> &FinalizedHistory;
+
+    fn finalized_history_mut(&mut self) -> &mut FinalizedHistory;
+
+    /// Add this state's finalizations to its history; models call it after every transition
+    fn record_finalized(&mut self) {
+        let entries = self.finalized_entries();
+        self.finalized_history_mut().extend(entries);
+    }
+}
+
+/// Every finalization ever recorded is still recorded: none was removed or overwritten
+pub fn no_finalized_rollback<S: FinalizedRecord>(state: &S) -> bool {
+    state.finalized_history().is_subset(&state.finalized_entries())
+}
+
+/// [`no_finalized_rollback`] as a property of any model recording its finalization history
+pub fn no_finalized_rollback_property<M>() -> Property<M>
+where
+    M: Model,
+    M::State: FinalizedRecord,
+{
+    Property::<M>::always("no_finalized_rollback", |_model, state| {
+        no_finalized_rollback(state)
+    })
+}
+
+/// Validators agree on every slot they hold a certificate for: across the certificate stores
+/// `stores`, as (slot, block) with `None` for a skip certificate, no slot is certified for two
+/// different blocks, or for a block and a skip
+pub fn certificate_agreement<I>(stores: impl IntoIterator<Item = I>) -> bool
+where
+    I: IntoIterator<Item = (Slot, Option<Hash>)>,
+{
+    let mut agreed = BTreeMap::new();
+    stores.into_iter().flatten().all(|(slot, hash)| *agreed.entry(slot).or_insert(hash) == hash)
+}
+
+/// Every finalized block has a notarization certificate
+pub fn finalized_implies_notarized<S: ConsensusRecord>(state: &S) -> bool {
+    state.finalized_blocks().is_subset(&state.notarized_blocks())
+}
+
+/// Every fast-finalized block is also recorded as finalized
+pub fn fast_finalized_implies_finalized<S: ConsensusRecord>(state: &S) -> bool {
+    state.fast_finalized_blocks().is_subset(&state.finalized_blocks())
+}
+
+/// Every fast-finalized block also has a notarization certificate: the 80% of stake that
+/// fast-finalized it exceeds the 60% notarization threshold
+pub fn fast_finalized_implies_notarized<S: ConsensusRecord>(state: &S) -> bool {
+    state.fast_finalized_blocks().is_subset(&state.notarized_blocks())
+}
+
+/// The slow path never contradicts the fast path: no other block of a fast-finalized slot is
+/// notarized, so none can collect FinalVotes and be finalized either
+pub fn fast_finalization_uncontested<S: ConsensusRecord>(state: &S) -> bool {
+    let notarized = state.notarized_blocks();
+    let finalized = state.finalized_blocks();
+    state.fast_finalized_blocks().iter().all(|(slot, hash)| {
+        notarized.iter().chain(finalized.iter()).all(|(s, h)| s != slot || h == hash)
+    })
+}
+
+/// No certificate claims more stake than the votes recorded for its block
+pub fn certificate_stake_backed<S: ConsensusRecord>(state: &S) -> bool {
+    state.certificates().iter().all(|(slot, hash, stake)| *stake <= state.vote_stake(*slot, *hash))
+}
+
+/// The derived invariants as properties of any model recording consensus progress
+pub fn derived_invariants<M>() -> Vec<Property<M>>
+where
+    M: Model,
+    M::State: ConsensusRecord,
+{
+    vec![
+        Property::<M>::always("finalized_implies_notarized", |_model, state| {
+            finalized_implies_notarized(state)
+        }),
+        Property::<M>::always("fast_finalized_implies_finalized", |_model, state| {
+            fast_finalized_implies_finalized(state)
+        }),
+        Property::<M>::always("fast_finalized_implies_notarized", |_model, state| {
+            fast_finalized_implies_notarized(state)
+        }),
+        Property::<M>::always("fast_finalization_uncontested", |_model, state| {
+            fast_finalization_uncontested(state)
+        }),
+        Property::<M>::always("certificate_stake_backed", |_model, state| {
+            certificate_stake_backed(state)
+        }),
+    ]
+}
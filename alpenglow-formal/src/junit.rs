@@ -0,0 +1,166 @@
+//! JUnit XML reports of property results.
+//! CI systems and test dashboards read JUnit XML, not the checker's stdout. Each checked model
+//! becomes a `<testsuite>` and each of its properties a `<testcase>`, failing with a summary of
+//! the counterexample when the checker found one. Suites are collected for the whole process
+//! and the report file is rewritten after every model, so it stays complete even when a later
+//! model runs out of time.
+
+use stateright::{Checker, Expectation, Model};
+use std::fmt::{Debug, Write as _};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Actions of a counterexample quoted in a failure message, counted from its end
+const MAX_QUOTED_ACTIONS: usize = 10;
+
+/// Suites recorded so far by this process
+static SUITES: Mutex<Vec<TestSuite>> = Mutex::new(Vec::new());
+
+/// The outcome of one property
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestCase {
+    pub name: String,
+    /// Failure message, when the property failed
+    pub failure: Option<String>,
+}
+
+/// The properties of one checked model
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestSuite {
+    pub name: String,
+    /// Unique states explored, reported as a suite property
+    pub states: usize,
+    pub cases: Vec<TestCase>,
+}
+
+impl TestSuite {
+    /// One case per property of the model `checker` explored
+    pub fn from_checker<M, C>(name: &str, checker: &C) -> Self
+    where
+        M: Model,
+        M::State: Clone + PartialEq,
+        M::Action: Debug + Clone + PartialEq,
+        C: Checker<M>,
+    {
+        let mut discoveries = checker.discoveries();
+        let cases = checker.model().properties().into_iter()
+            .map(|property| {
+                let discovery = discoveries.remove(property.name);
+                let failure = match (property.expectation, discovery) {
+                    (Expectation::Sometimes, Some(_)) => None,
+                    (Expectation::Sometimes, None) => Some("no example found in the explored states".to_string()),
+                    (_, Some(path)) => Some(counterexample_summary(&path.into_actions())),
+                    (_, None) => None,
+                };
+                TestCase { name: property.name.to_string(), failure }
+            })
+            .collect();
+        Self { name: name.to_string(), states: checker.unique_state_count(), cases }
+    }
+
+    pub fn failures(&self) -> usize {
+        self.cases.iter().filter(|case| case.failure.is_some()).count()
+    }
+}
+
+fn counterexample_summary<A: Debug>(actions: &[A]) -> String {
+    let skipped = actions.len().saturating_sub(MAX_QUOTED_ACTIONS);
+    let mut summary = format!("counterexample of {} actions", actions.len());
+    if skipped > 0 {
+        write!(summary, ", the last {}", MAX_QUOTED_ACTIONS).unwrap();
+    }
+    summary.push(':');
+    for action in &actions[skipped..] {
+        write!(summary, "\n  {:?}", action).unwrap();
+    }
+    summary
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Render `suites` as a JUnit XML document
+pub fn to_xml(suites: &[TestSuite]) -> String {
+    let tests: usize = suites.iter().map(|suite| suite.cases.len()).sum();
+    let failures: usize = suites.iter().map(TestSuite::failures).sum();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(xml, "<testsuites name=\"alpenglow-formal\" tests=\"{}\" failures=\"{}\">", tests, failures).unwrap();
+    for suite in suites {
+        writeln!(xml, "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">",
+                 escape(&suite.name), suite.cases.len(), suite.failures()).unwrap();
+        writeln!(xml, "    <properties><property name=\"unique_states\" value=\"{}\"/></properties>", suite.states).unwrap();
+        for case in &suite.cases {
+            let name = escape(&case.name);
+            let classname = escape(&suite.name);
+            match &case.failure {
+                None => writeln!(xml, "    <testcase name=\"{}\" classname=\"{}\"/>", name, classname).unwrap(),
+                Some(message) => {
+                    let first_line = message.lines().next().unwrap_or_default();
+                    writeln!(xml, "    <testcase name=\"{}\" classname=\"{}\">", name, classname).unwrap();
+                    writeln!(xml, "      <failure message=\"{}\">{}</failure>", escape(first_line), escape(message)).unwrap();
+                    writeln!(xml, "    </testcase>").unwrap();
+                }
+            }
+        }
+        writeln!(xml, "  </testsuite>").unwrap();
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Add `suite` to the suites of this process and rewrite `file` with all of them
+pub fn record(suite: TestSuite, file: &Path) -> io::Result<()> {
+    let mut suites = SUITES.lock().unwrap();
+    suites.push(suite);
+    if let Some(directory) = file.parent().filter(|directory| !directory.as_os_str().is_empty()) {
+        fs::create_dir_all(directory)?;
+    }
+    fs::write(file, to_xml(&suites))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::votor::VotorModel;
+
+    #[test]
+    fn test_junit_suites() {
+        let model = VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 };
+        let passing = TestSuite::from_checker("votor", &model.clone().checker().spawn_bfs().join());
+        assert_eq!(passing.failures(), 0);
+        assert_eq!(passing.cases.len(), model.properties().len());
+        assert!(passing.states > 0);
+
+        let failing = TestSuite {
+            name: "standstill <3/1>".to_string(),
+            states: 10,
+            cases: vec![
+                TestCase { name: "restart_preserves_prefix".to_string(), failure: None },
+                TestCase { name: "standstill_detected".to_string(), failure: Some(counterexample_summary(&["Tick", "Tick"])) },
+            ],
+        };
+        let xml = to_xml(&[passing.clone(), failing]);
+        assert!(xml.starts_with("<?xml"));
+        assert_eq!(xml.matches("<testcase ").count(), passing.cases.len() + 2);
+        assert!(xml.contains(&format!("tests=\"{}\" failures=\"1\">", passing.cases.len() + 2)));
+        assert!(xml.contains("<failure message=\"counterexample of 2 actions:\">"));
+        assert!(xml.contains("&quot;Tick&quot;"));
+        assert!(xml.contains("name=\"standstill &lt;3/1&gt;\""));
+        assert!(xml.trim_end().ends_with("</testsuites>"));
+    }
+
+    #[test]
+    fn test_counterexample_summary() {
+        let summary = counterexample_summary(&(0..15).collect::<Vec<_>>());
+        assert!(summary.starts_with("counterexample of 15 actions, the last 10:"));
+        assert_eq!(summary.lines().count(), 11);
+        assert!(summary.ends_with("\n  14"));
+    }
+}
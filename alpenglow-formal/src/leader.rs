@@ -1,14 +1,22 @@
 //! Formal verification model for leader rotation and window management in Alpenglow consensus.
 //! This module provides a Stateright-based formal model for verifying leader selection,
 //! window management, and BadWindow flag handling.
+//!
+//! A slot's leader may fail at any point: before its selection is made, while the selection is
+//! still reaching validators, or after. The model keeps every leader ever assigned or accepted
+//! for a slot as ghost state and checks that, however selections and failures interleave, no
+//! slot ends up with two.
 
 use stateright::{Model, Property, Checker};
 use std::collections::{BTreeMap, BTreeSet};
+use crate::network::{NetworkFault, NetworkFaults};
+use crate::shared::SharedVec;
+use crate::bounds::Bounded;
+use crate::limits::Outcome;
+use crate::options::CheckOptions;
 
 // --- Formal Model Configuration ---
 const LEADER_WINDOW_SIZE: u64 = 5; // Formal verification limit
-const MAX_SLOTS: u64 = 10; // Formal verification limit
-const MAX_VALIDATORS: usize = 5; // Formal verification limit
 
 // Type aliases for clarity
 type Slot = u64;
@@ -61,6 +69,8 @@ pub enum LeaderAction {
     },
     /// Advance to the next slot
     AdvanceSlot,
+    /// The network adversary drops, duplicates or delays a message
+    NetworkFault { fault: NetworkFault<MessageInTransit> },
 }
 
 /// State of a validator in the leader model
@@ -85,8 +95,10 @@ pub struct ValidatorState {
 pub struct LeaderState {
     /// Network messages in transit
     network: BTreeSet<MessageInTransit>,
+    /// Adversarial control over message delivery
+    network_faults: NetworkFaults<MessageInTransit>,
     /// Per-validator states
-    validators: Vec<ValidatorState>,
+    validators: SharedVec<ValidatorState>,
     /// Global current slot
     current_slot: Slot,
     /// Leader assignments: slot -> leader
@@ -95,6 +107,8 @@ pub struct LeaderState {
     leader_failures: BTreeMap<Slot, ActorId>,
     /// Stake distribution: validator -> stake
     stake_distribution: BTreeMap<ActorId, Stake>,
+    /// Ghost record of every leader assigned to a slot or accepted for it by a validator
+    accepted_leaders: BTreeMap<Slot, BTreeSet<ActorId>>,
 }
 
 /// Formal model for leader rotation and window management
@@ -104,6 +118,8 @@ pub struct LeaderModel {
     pub validator_count: usize,
     /// Maximum slots to explore
     pub max_slot: Slot,
+    /// Messages the network adversary may drop, duplicate or delay
+    pub network_faults: usize,
 }
 
 impl LeaderState {
@@ -117,6 +133,7 @@ impl LeaderState {
 
         Self {
             network: BTreeSet::new(),
+            network_faults: NetworkFaults::new(0),
             validators: (0..validator_count).map(|_i| ValidatorState {
                 current_slot: 0,
                 bad_window: false,
@@ -129,24 +146,19 @@ impl LeaderState {
             leader_assignments: BTreeMap::new(),
             leader_failures: BTreeMap::new(),
             stake_distribution,
+            accepted_leaders: BTreeMap::new(),
         }
     }
 
     /// Get leader for a slot using stake-weighted selection
     fn get_leader_for_slot(&self, slot: Slot) -> ActorId {
-        let total_stake: Stake = self.stake_distribution.values().sum();
-        let slot_seed = (slot * 1234567891) % total_stake;
-        
-        let mut cumulative_stake = 0;
-        for (validator_id, stake) in &self.stake_distribution {
-            cumulative_stake += stake;
-            if slot_seed < cumulative_stake {
-                return *validator_id;
-            }
-        }
-        
-        // Fallback to last validator
-        *self.stake_distribution.keys().last().unwrap()
+        stake_weighted_leader(&self.stake_distribution, slot)
+    }
+
+    /// Whether some validator holds a skip certificate for a slot whose leader it has not learned
+    fn failure_precedes_selection(&self) -> bool {
+        self.validators.iter()
+            .any(|v| v.skip_certificates.keys().any(|slot| !v.known_leaders.contains_key(slot)))
     }
 
     /// Check if a slot is within the leader window
@@ -174,12 +186,41 @@ impl LeaderState {
     }
 }
 
+/// Stake-weighted leader for a slot, shared with the models that follow the leader schedule
+pub(crate) fn stake_weighted_leader(stake_distribution: &BTreeMap<ActorId, Stake>, slot: Slot) -> ActorId {
+    let total_stake: Stake = stake_distribution.values().sum();
+    let slot_seed = (slot * 1234567891) % total_stake;
+
+    let mut cumulative_stake = 0;
+    for (validator_id, stake) in stake_distribution {
+        cumulative_stake += stake;
+        if slot_seed < cumulative_stake {
+            return *validator_id;
+        }
+    }
+
+    // Fallback to last validator
+    *stake_distribution.keys().last().unwrap()
+}
+
+impl Bounded for LeaderState {
+    fn current_slot(&self) -> Slot {
+        self.current_slot
+    }
+
+    fn in_flight(&self) -> usize {
+        self.network.len()
+    }
+}
+
 impl Model for LeaderModel {
     type State = LeaderState;
     type Action = LeaderAction;
 
     fn init_states(&self) -> Vec<Self::State> {
-        vec![LeaderState::new(self.validator_count)]
+        let mut state = LeaderState::new(self.validator_count);
+        state.network_faults = NetworkFaults::new(self.network_faults);
+        vec![state]
     }
 
     fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
@@ -188,6 +229,11 @@ impl Model for LeaderModel {
             actions.push(LeaderAction::DeliverMessage { msg: msg.clone() });
         }
 
+        // The network adversary drops, duplicates or delays a bounded number of messages
+        for fault in state.network_faults.faults(&state.network) {
+            actions.push(LeaderAction::NetworkFault { fault });
+        }
+
         // 2. Select leaders for current and future slots
         for slot in state.current_slot..=self.max_slot {
             if !state.leader_assignments.contains_key(&slot) {
@@ -196,10 +242,13 @@ impl Model for LeaderModel {
             }
         }
 
-        // 3. Trigger leader failures for any slot
+        // 3. A slot's scheduled leader may fail once, even before its selection is made or has
+        //    reached every validator, so that selections and failures race
         for slot in 1..=self.max_slot {
-            if let Some(leader) = state.leader_assignments.get(&slot) {
-                actions.push(LeaderAction::TriggerLeaderFailure { slot, leader: *leader });
+            if !state.leader_failures.contains_key(&slot) {
+                let leader = state.leader_assignments.get(&slot).copied()
+                    .unwrap_or_else(|| state.get_leader_for_slot(slot));
+                actions.push(LeaderAction::TriggerLeaderFailure { slot, leader });
             }
         }
 
@@ -216,6 +265,7 @@ impl Model for LeaderModel {
         match action {
             LeaderAction::SelectLeader { slot, leader } => {
                 next_state.leader_assignments.insert(slot, leader);
+                next_state.accepted_leaders.entry(slot).or_default().insert(leader);
 
                 // Broadcast leader selection to all validators
                 if let Some(stake) = next_state.stake_distribution.get(&leader) {
@@ -231,16 +281,21 @@ impl Model for LeaderModel {
                     }
                 }
             }
+            LeaderAction::NetworkFault { fault } => {
+                if !next_state.network_faults.apply(&mut next_state.network, fault) { return None; }
+            }
             LeaderAction::DeliverMessage { msg } => {
                 let recipient_id = msg.dst;
-                let mut validator_state = validators[recipient_id].clone();
+                let mut validator_state = validators.get(recipient_id)?.clone();
 
                 // Remove message from network
                 if !next_state.network.remove(&msg) { return None; }
+                next_state.network_faults.record_delivery(&msg);
 
                 match msg.msg {
                     LeaderMessage::LeaderSelection { slot, leader, stake: _ } => {
                         validator_state.known_leaders.insert(slot, leader);
+                        next_state.accepted_leaders.entry(slot).or_default().insert(leader);
                     }
                     LeaderMessage::SkipCertificate { slot, failed_leader } => {
                         validator_state.skip_certificates.insert(slot, failed_leader);
@@ -281,8 +336,9 @@ impl Model for LeaderModel {
         vec![
             // Property 1: Leader uniqueness per slot
             Property::<Self>::always("leader_uniqueness", |_, state| {
-                // Each slot has at most one leader
-                state.leader_assignments.len() <= state.current_slot as usize + 1
+                // No slot was ever assigned or accepted with two different leaders, even if a
+                // validator's record of it was later overwritten
+                state.accepted_leaders.values().all(|leaders| leaders.len() <= 1)
             }),
             
             // Property 2: BadWindow consistency
@@ -319,7 +375,7 @@ impl Model for LeaderModel {
             Property::<Self>::always("window_management", |_, state| {
                 for validator in &state.validators {
                     // BadWindow should be cleared when skip certificates move out of window
-                    for (skip_slot, _) in &validator.skip_certificates {
+                    for skip_slot in validator.skip_certificates.keys() {
                         if !state.is_within_window(*skip_slot, validator.current_slot) {
                             // Skip certificate is outside window, BadWindow should be cleared
                             if validator.bad_window {
@@ -336,55 +392,74 @@ impl Model for LeaderModel {
                 }
                 true
             }),
+
+            // Property 5: A failure, announced or held as a skip certificate, names the leader
+            // accepted for its slot, whichever of the two came first
+            Property::<Self>::always("failure_names_leader", |_, state| {
+                state.leader_failures.iter()
+                    .chain(state.validators.iter().flat_map(|v| v.skip_certificates.iter()))
+                    .all(|(slot, failed)| state.accepted_leaders.get(slot).is_none_or(|leaders| leaders.contains(failed)))
+            }),
+
+            // Property 6: A skip certificate can reach a validator before the slot's leader selection
+            Property::<Self>::sometimes("failure_before_selection", |_, state| {
+                state.failure_precedes_selection()
+            }),
         ]
     }
 }
 
 /// Run formal verification of leader rotation
-pub fn run_formal_verification() {
+pub fn run_formal_verification(options: &CheckOptions) {
     println!("=== Leader Rotation Formal Verification ===");
     
     let model = LeaderModel {
         validator_count: 3, // Small for formal verification
         max_slot: 5,
+        network_faults: 0,
     };
 
     println!("Model checking leader rotation with {} validators, {} slots", 
              model.validator_count, model.max_slot);
     
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
+    options.print_summary();
+
+    let result = options
+        .checker(model)
         .spawn_dfs()
         .report(&mut stateright::report::WriteReporter::new(&mut std::io::stdout()));
     
     // Check verification results
-    if result.discoveries().is_empty() {
-        println!("✅ All leader rotation properties verified successfully");
-        } else {
-        println!("❌ Leader rotation verification found counterexamples");
-        for (property_name, _path) in result.discoveries() {
-            println!("  - {}", property_name);
-        }
+    match options.print_outcomes(&result) {
+        Outcome::Proved => println!("✅ All leader rotation properties verified successfully"),
+        Outcome::Violated => println!("❌ Leader rotation verification found counterexamples"),
+        Outcome::Inconclusive => println!("❓ Leader rotation verification inconclusive: a limit or bound stopped the search first"),
     }
+    options.record_results("leader formal", &result);
 }
 
 /// Test leader model with different configurations
-pub fn test_leader_model(validators: usize, slots: u64) {
+pub fn test_leader_model(validators: usize, slots: u64, options: &CheckOptions) {
     println!("Testing leader model with {} validators, {} slots", validators, slots);
     
     let model = LeaderModel {
         validator_count: validators,
         max_slot: slots,
+        network_faults: 0,
     };
 
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
+    options.print_summary();
+
+    let result = options
+        .checker(model)
         .spawn_dfs();
+    options.progress.watch(&result);
+    let result = result.join();
     
     println!("States explored: {}", result.state_count());
-    println!("Properties verified: {}", result.discoveries().is_empty());
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("leader {}v {}s", validators, slots), &result);
 }
 
 #[cfg(test)]
@@ -406,6 +481,30 @@ mod tests {
         assert!(leader < 3);
     }
 
+    #[test]
+    fn test_failure_races_selection() {
+        let model = LeaderModel { validator_count: 2, max_slot: 1, network_faults: 0 };
+        let mut state = model.init_states().remove(0);
+        let leader = state.get_leader_for_slot(1);
+
+        // The leader fails before it is selected, and the skip certificate arrives first
+        state = model.next_state(&state, LeaderAction::TriggerLeaderFailure { slot: 1, leader }).unwrap();
+        let skip = MessageInTransit { dst: 0, msg: LeaderMessage::SkipCertificate { slot: 1, failed_leader: leader } };
+        state = model.next_state(&state, LeaderAction::DeliverMessage { msg: skip }).unwrap();
+        assert!(state.failure_precedes_selection());
+        state = model.next_state(&state, LeaderAction::SelectLeader { slot: 1, leader }).unwrap();
+        assert_eq!(state.accepted_leaders[&1], BTreeSet::from([leader]));
+
+        // A second leader accepted for the slot stays on record even once overwritten
+        state.accepted_leaders.entry(1).or_default().insert(leader + 1);
+        assert!(!(model.property("leader_uniqueness").condition)(&model, &state));
+
+        let checker = model.checker().spawn_bfs().join();
+        assert!(checker.discovery("leader_uniqueness").is_none());
+        assert!(checker.discovery("failure_names_leader").is_none());
+        assert!(checker.discovery("failure_before_selection").is_some());
+    }
+
     #[test]
     fn test_window_management() {
         let state = LeaderState::new(3);
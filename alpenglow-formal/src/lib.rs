@@ -1,6 +1,47 @@
 pub mod votor;
+pub mod votor_actor;
 pub mod certificate;
 pub mod leader;
 pub mod timeout;
 pub mod rotor;
+pub mod standstill;
+pub mod pool;
+pub mod epoch;
+pub mod light_client;
+pub mod rewards;
+pub mod alpenglow;
 pub mod modelling;
+pub mod vacuity;
+pub mod invariants;
+pub mod network;
+pub mod blockstore;
+pub mod slashing;
+pub mod voters;
+pub mod shared;
+pub mod distributed;
+pub mod bounds;
+pub mod limits;
+pub mod stats;
+pub mod output;
+pub mod progress;
+pub mod hybrid;
+pub mod options;
+pub mod coverage;
+pub mod trace;
+pub mod tla;
+pub mod junit;
+pub mod html;
+pub mod metrics;
+pub mod golden;
+pub mod refinement;
+pub mod conformance;
+pub mod simulator;
+pub mod statistical;
+pub mod schedules;
+pub mod fuzzing;
+pub mod differential;
+pub mod sweep;
+pub mod cli;
+pub mod finality;
+pub mod finality_proof;
+pub mod suite;
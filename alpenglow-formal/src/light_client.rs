@@ -0,0 +1,541 @@
+//! Formal verification model for a light client that follows finality through certificates alone.
+//! Validators vote as in Votor: honest ones notarize their slot's block and vote to finalize it
+//! once it is notarized, Byzantine ones notarize conflicting blocks and vote to finalize at any
+//! time. Full nodes see every vote and finalize a block on 80% notarization votes, or on 60%
+//! notarization votes together with 60% finalization votes for its slot. The light client sees
+//! neither votes nor blocks: an untrusted relayer hands it certificates aggregated from whatever
+//! votes have been cast so far, possibly too few, and it accepts one only if [`verify_chain`]
+//! still verifies everything it accepted along with it. The model checks that the light client
+//! never finalizes a block the full nodes did not, and that once it has been relayed every valid
+//! certificate it finalizes exactly the blocks they did. It also checks the finality proofs of
+//! [`crate::finality_proof`]: every proof that can be assembled from the votes and verifies names a
+//! block the full nodes finalized, and every block they finalized has a proof.
+//!
+//! [`verify_chain`] is usable on its own, by bridges following Alpenglow finality for instance.
+
+use stateright::{Model, Property, Checker};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use crate::bounds::Bounded;
+use crate::finality_proof::{export_proof, stake_commitment, FinalityProof, ProofPath};
+use crate::limits::Outcome;
+use crate::options::CheckOptions;
+use crate::voters::VoterSet;
+
+// --- Formal Model Configuration ---
+const FAST_FINALIZATION_THRESHOLD_PERCENT: u64 = 80;
+const CERTIFICATE_THRESHOLD_PERCENT: u64 = 60;
+/// Block hash honest validators vote for
+pub const HONEST_HASH: Hash = 0;
+/// Block hashes Byzantine validators vote for, the honest one included
+const HASHES: Hash = 2;
+
+// Type aliases for clarity
+type Slot = u64;
+type Hash = u64;
+type ActorId = usize;
+type Stake = u64;
+
+/// A certificate as a light client receives it: the aggregated votes of its signers
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Certificate {
+    /// 80% of the stake voted to notarize the block, which finalizes it
+    FastFinalization { slot: Slot, hash: Hash, signers: VoterSet },
+    /// 60% of the stake voted to notarize the block
+    Notarization { slot: Slot, hash: Hash, signers: VoterSet },
+    /// 60% of the stake voted to finalize the slot, which finalizes its notarized block
+    Finalization { slot: Slot, signers: VoterSet },
+}
+
+impl Certificate {
+    pub fn slot(&self) -> Slot {
+        match self {
+            Certificate::FastFinalization { slot, .. }
+            | Certificate::Notarization { slot, .. }
+            | Certificate::Finalization { slot, .. } => *slot,
+        }
+    }
+
+    pub fn signers(&self) -> &VoterSet {
+        match self {
+            Certificate::FastFinalization { signers, .. }
+            | Certificate::Notarization { signers, .. }
+            | Certificate::Finalization { signers, .. } => signers,
+        }
+    }
+
+    /// Stake (percent) the signers must hold
+    pub fn threshold_percent(&self) -> u64 {
+        match self {
+            Certificate::FastFinalization { .. } => FAST_FINALIZATION_THRESHOLD_PERCENT,
+            Certificate::Notarization { .. } | Certificate::Finalization { .. } => CERTIFICATE_THRESHOLD_PERCENT,
+        }
+    }
+
+    /// What the certificate attests to, whoever signed it
+    fn claim(&self) -> Certificate {
+        match self {
+            Certificate::FastFinalization { slot, hash, .. } => Certificate::FastFinalization { slot: *slot, hash: *hash, signers: VoterSet::new() },
+            Certificate::Notarization { slot, hash, .. } => Certificate::Notarization { slot: *slot, hash: *hash, signers: VoterSet::new() },
+            Certificate::Finalization { slot, .. } => Certificate::Finalization { slot: *slot, signers: VoterSet::new() },
+        }
+    }
+}
+
+/// Why a sequence of certificates does not verify; `index` is the offending certificate's
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChainError {
+    /// A signer is missing from the stake table
+    UnknownSigner { index: usize, signer: ActorId },
+    /// The signers hold less stake than the certificate's threshold
+    InsufficientStake { index: usize, stake: Stake },
+    /// The certificate notarizes a different block of its slot than an earlier one
+    ConflictingBlocks { index: usize, slot: Slot },
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainError::UnknownSigner { index, signer } => write!(f, "certificate {}: signer {} holds no stake", index, signer),
+            ChainError::InsufficientStake { index, stake } => write!(f, "certificate {}: signers hold only {} stake", index, stake),
+            ChainError::ConflictingBlocks { index, slot } => write!(f, "certificate {}: conflicting block notarized in slot {}", index, slot),
+        }
+    }
+}
+
+/// Verify `certificates` against the stake table `stakes`, indexed by validator, and return the
+/// finalized block of every slot they finalize. Every certificate must reach its threshold, and
+/// no two may notarize different blocks of one slot. A slot is finalized by a fast-finalization
+/// certificate, or by a notarization and a finalization certificate; the order of the
+/// certificates does not matter. The aggregate signatures are taken as already checked against
+/// the signers' vote keys: this verifies what they attest to.
+pub fn verify_chain(stakes: &[Stake], certificates: &[Certificate]) -> Result<BTreeMap<Slot, Hash>, ChainError> {
+    let total: Stake = stakes.iter().sum();
+    let mut notarized = BTreeMap::new();
+    let mut fast = BTreeSet::new();
+    let mut finalization = BTreeSet::new();
+    for (index, certificate) in certificates.iter().enumerate() {
+        let mut stake: Stake = 0;
+        for signer in certificate.signers().iter() {
+            stake += *stakes.get(signer).ok_or(ChainError::UnknownSigner { index, signer })?;
+        }
+        if total == 0 || stake * 100 < total * certificate.threshold_percent() {
+            return Err(ChainError::InsufficientStake { index, stake });
+        }
+        let (slot, hash) = match certificate {
+            Certificate::Finalization { slot, .. } => {
+                finalization.insert(*slot);
+                continue;
+            }
+            Certificate::FastFinalization { slot, hash, .. } => {
+                fast.insert(*slot);
+                (*slot, *hash)
+            }
+            Certificate::Notarization { slot, hash, .. } => (*slot, *hash),
+        };
+        if *notarized.entry(slot).or_insert(hash) != hash {
+            return Err(ChainError::ConflictingBlocks { index, slot });
+        }
+    }
+    Ok(notarized.into_iter()
+        .filter(|(slot, _)| fast.contains(slot) || finalization.contains(slot))
+        .collect())
+}
+
+/// Votes a validator casts for a slot
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum VoteKind {
+    Notar(Hash),
+    Final,
+}
+
+/// Actions that can be taken in the light-client model
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum LightClientAction {
+    /// A validator votes, and full nodes see the vote
+    Vote { slot: Slot, kind: VoteKind, voter: ActorId },
+    /// The relayer hands the light client a certificate aggregated from the votes cast
+    Relay { certificate: Certificate },
+}
+
+/// State of the light-client model
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct LightClientState {
+    /// Voters per slot and vote
+    votes: BTreeMap<(Slot, VoteKind), VoterSet>,
+    /// Certificates the light client verified and accepted
+    accepted: BTreeSet<Certificate>,
+    /// Certificates the light client turned down
+    rejected: BTreeSet<Certificate>,
+}
+
+/// Formal model of a light client following finality through relayed certificates
+#[derive(Clone)]
+pub struct LightClientModel {
+    /// Validators, each with one unit of stake
+    pub validator_count: usize,
+    /// Byzantine validators, the lowest ids
+    pub byzantine_count: usize,
+    /// Maximum slots to explore
+    pub max_slot: Slot,
+}
+
+impl LightClientModel {
+    fn is_byzantine(&self, validator: ActorId) -> bool {
+        validator < self.byzantine_count
+    }
+
+    /// Stake table the light client verifies against
+    pub fn stakes(&self) -> Vec<Stake> {
+        vec![1; self.validator_count]
+    }
+
+    fn has_quorum(&self, voters: Option<&VoterSet>, percent: u64) -> bool {
+        voters.is_some_and(|voters| voters.len() as u64 * 100 >= self.validator_count as u64 * percent)
+    }
+
+    /// Whether `voter` may cast `kind` for `slot`: honest validators notarize the honest block
+    /// and vote to finalize it once it is notarized, Byzantine ones notarize any block and vote
+    /// to finalize at any time
+    fn may_vote(&self, state: &LightClientState, slot: Slot, kind: VoteKind, voter: ActorId) -> bool {
+        if state.votes.get(&(slot, kind)).is_some_and(|voters| voters.contains(&voter)) {
+            return false;
+        }
+        if self.is_byzantine(voter) {
+            return true;
+        }
+        let notar = state.votes.get(&(slot, VoteKind::Notar(HONEST_HASH)));
+        match kind {
+            VoteKind::Notar(hash) => hash == HONEST_HASH,
+            VoteKind::Final => {
+                notar.is_some_and(|voters| voters.contains(&voter))
+                    && self.has_quorum(notar, CERTIFICATE_THRESHOLD_PERCENT)
+            }
+        }
+    }
+
+    /// Certificates the relayer can aggregate from the votes cast so far, whether or not they
+    /// reach their threshold; it cannot add signatures nobody made
+    fn relayable(&self, state: &LightClientState) -> Vec<Certificate> {
+        let mut certificates = Vec::new();
+        for ((slot, kind), voters) in &state.votes {
+            let (slot, signers) = (*slot, voters.clone());
+            match kind {
+                VoteKind::Notar(hash) => {
+                    certificates.push(Certificate::FastFinalization { slot, hash: *hash, signers: signers.clone() });
+                    certificates.push(Certificate::Notarization { slot, hash: *hash, signers });
+                }
+                VoteKind::Final => certificates.push(Certificate::Finalization { slot, signers }),
+            }
+        }
+        certificates
+    }
+}
+
+impl LightClientState {
+    fn accepted(&self) -> Vec<Certificate> {
+        self.accepted.iter().cloned().collect()
+    }
+
+    /// Whether the light client already accepted a certificate attesting to the same as `certificate`
+    fn has_claim(&self, certificate: &Certificate) -> bool {
+        self.accepted.iter().any(|accepted| accepted.claim() == certificate.claim())
+    }
+
+    /// Blocks the full nodes finalized, from the votes they saw
+    fn full_node_finalized(&self, model: &LightClientModel) -> BTreeMap<Slot, Hash> {
+        self.votes.iter()
+            .filter_map(|((slot, kind), voters)| match kind {
+                VoteKind::Notar(hash) => {
+                    let finalized = model.has_quorum(Some(voters), FAST_FINALIZATION_THRESHOLD_PERCENT)
+                        || (model.has_quorum(Some(voters), CERTIFICATE_THRESHOLD_PERCENT)
+                            && model.has_quorum(self.votes.get(&(*slot, VoteKind::Final)), CERTIFICATE_THRESHOLD_PERCENT));
+                    finalized.then_some((*slot, *hash))
+                }
+                VoteKind::Final => None,
+            })
+            .collect()
+    }
+
+    /// Blocks the light client finalized, from the certificates it accepted
+    fn light_client_finalized(&self, model: &LightClientModel) -> BTreeMap<Slot, Hash> {
+        verify_chain(&model.stakes(), &self.accepted()).unwrap_or_default()
+    }
+
+    /// Whether the light client was relayed a certificate for everything the votes certify
+    fn is_caught_up(&self, model: &LightClientModel) -> bool {
+        model.relayable(self).iter()
+            .filter(|certificate| verify_chain(&model.stakes(), std::slice::from_ref(*certificate)).is_ok())
+            .all(|certificate| self.has_claim(certificate))
+    }
+
+    /// Proofs that can be assembled from the votes cast so far: one per fast-finalization
+    /// certificate and one per notarization and finalization certificate of the same slot
+    fn assemblable_proofs(&self, model: &LightClientModel) -> Vec<FinalityProof> {
+        let stake_commitment = stake_commitment(&model.stakes());
+        let certificates = model.relayable(self);
+        let mut proofs = Vec::new();
+        for certificate in &certificates {
+            match certificate {
+                Certificate::FastFinalization { slot, hash, signers } => {
+                    let path = ProofPath::Fast { signers: signers.clone() };
+                    proofs.push(FinalityProof { slot: *slot, hash: *hash, stake_commitment, path });
+                }
+                Certificate::Notarization { slot, hash, signers } => {
+                    for finalization in certificates.iter().filter(|c| matches!(c, Certificate::Finalization { .. }) && c.slot() == *slot) {
+                        let path = ProofPath::Slow { notarization: signers.clone(), finalization: finalization.signers().clone() };
+                        proofs.push(FinalityProof { slot: *slot, hash: *hash, stake_commitment, path });
+                    }
+                }
+                Certificate::Finalization { .. } => {}
+            }
+        }
+        proofs
+    }
+
+    /// Whether every proof that can be assembled and verifies names a block the full nodes finalized
+    fn check_finality_proofs_sound(&self, model: &LightClientModel) -> bool {
+        let full = self.full_node_finalized(model);
+        self.assemblable_proofs(model).iter()
+            .filter(|proof| proof.verify(&model.stakes()).is_ok())
+            .all(|proof| full.get(&proof.slot) == Some(&proof.hash))
+    }
+
+    /// Whether every block the full nodes finalized has a proof, exported from their
+    /// certificates, that survives serialization and verifies
+    fn check_finality_proofs_complete(&self, model: &LightClientModel) -> bool {
+        let (stakes, certificates) = (model.stakes(), model.relayable(self));
+        self.full_node_finalized(model).iter().all(|(slot, hash)| {
+            export_proof(&stakes, &certificates, *slot).is_some_and(|proof| {
+                proof.hash == *hash
+                    && proof.verify(&stakes).is_ok()
+                    && FinalityProof::parse(&proof.to_text()).as_ref() == Ok(&proof)
+            })
+        })
+    }
+
+    /// Whether every block the light client finalized was finalized by the full nodes too
+    fn check_light_client_sound(&self, model: &LightClientModel) -> bool {
+        let full = self.full_node_finalized(model);
+        self.light_client_finalized(model).iter().all(|(slot, hash)| full.get(slot) == Some(hash))
+    }
+
+    /// Whether a light client relayed every valid certificate finalizes exactly the blocks the
+    /// full nodes finalized
+    fn check_light_client_complete(&self, model: &LightClientModel) -> bool {
+        !self.is_caught_up(model) || self.light_client_finalized(model) == self.full_node_finalized(model)
+    }
+}
+
+impl Bounded for LightClientState {
+    fn current_slot(&self) -> Slot {
+        self.votes.keys().map(|(slot, _)| *slot).max().unwrap_or(0)
+    }
+
+    fn in_flight(&self) -> usize {
+        0
+    }
+}
+
+impl Model for LightClientModel {
+    type State = LightClientState;
+    type Action = LightClientAction;
+
+    fn init_states(&self) -> Vec<Self::State> {
+        vec![LightClientState {
+            votes: BTreeMap::new(),
+            accepted: BTreeSet::new(),
+            rejected: BTreeSet::new(),
+        }]
+    }
+
+    fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
+        // 1. Validators vote
+        for slot in 1..=self.max_slot {
+            let kinds = (0..HASHES).map(VoteKind::Notar).chain([VoteKind::Final]);
+            for kind in kinds {
+                for voter in 0..self.validator_count {
+                    if self.may_vote(state, slot, kind, voter) {
+                        actions.push(LightClientAction::Vote { slot, kind, voter });
+                    }
+                }
+            }
+        }
+
+        // 2. The relayer hands over certificates the light client has not seen or accepted
+        for certificate in self.relayable(state) {
+            if !state.has_claim(&certificate) && !state.rejected.contains(&certificate) {
+                actions.push(LightClientAction::Relay { certificate });
+            }
+        }
+    }
+
+    fn next_state(&self, last_state: &Self::State, action: Self::Action) -> Option<Self::State> {
+        let mut next_state = last_state.clone();
+        match action {
+            LightClientAction::Vote { slot, kind, voter } => {
+                if slot == 0 || slot > self.max_slot || voter >= self.validator_count || !self.may_vote(last_state, slot, kind, voter) {
+                    return None;
+                }
+                next_state.votes.entry((slot, kind)).or_default().insert(voter);
+            }
+            LightClientAction::Relay { certificate } => {
+                if !self.relayable(last_state).contains(&certificate)
+                    || last_state.has_claim(&certificate)
+                    || last_state.rejected.contains(&certificate)
+                {
+                    return None;
+                }
+                let mut certificates = last_state.accepted();
+                certificates.push(certificate.clone());
+                if verify_chain(&self.stakes(), &certificates).is_ok() {
+                    next_state.accepted.insert(certificate);
+                } else {
+                    next_state.rejected.insert(certificate);
+                }
+            }
+        }
+        Some(next_state)
+    }
+
+    /// Properties to verify in the light-client model
+    fn properties(&self) -> Vec<Property<Self>> {
+        vec![
+            // Property 1: The light client finalizes only blocks full nodes finalized
+            Property::<Self>::always("light_client_sound", |model, state| {
+                state.check_light_client_sound(model)
+            }),
+
+            // Property 2: Relayed every valid certificate, it finalizes all of them
+            Property::<Self>::always("light_client_complete", |model, state| {
+                state.check_light_client_complete(model)
+            }),
+
+            // Property 3: Finality proofs that verify name finalized blocks only
+            Property::<Self>::always("finality_proof_sound", |model, state| {
+                state.check_finality_proofs_sound(model)
+            }),
+
+            // Property 4: Every finalized block has a finality proof
+            Property::<Self>::always("finality_proof_complete", |model, state| {
+                state.check_finality_proofs_complete(model)
+            }),
+
+            // Property 5: The light client finalizes a block
+            Property::<Self>::sometimes("light_client_finalizes", |model, state| {
+                !state.light_client_finalized(model).is_empty()
+            }),
+
+            // Property 6: The light client turns down a certificate, so verification is exercised
+            Property::<Self>::sometimes("certificate_rejected", |_model, state| {
+                !state.rejected.is_empty()
+            }),
+        ]
+    }
+}
+
+/// Run formal verification of the light client
+pub fn run_formal_verification(options: &CheckOptions) {
+    println!("=== Light Client Formal Verification ===");
+
+    let model = LightClientModel {
+        validator_count: 4, // Small for formal verification
+        byzantine_count: 1,
+        max_slot: 1,
+    };
+
+    println!("Model checking the light client with {} validators ({} Byzantine), {} slots",
+             model.validator_count, model.byzantine_count, model.max_slot);
+
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs()
+        .report(&mut stateright::report::WriteReporter::new(&mut std::io::stdout()));
+
+    // Check verification results
+    match options.print_outcomes(&result) {
+        Outcome::Proved => println!("✅ All light client properties verified successfully"),
+        Outcome::Violated => println!("❌ Light client verification found counterexamples"),
+        Outcome::Inconclusive => println!("❓ Light client verification inconclusive: a limit or bound stopped the search first"),
+    }
+    options.record_results("light client formal", &result);
+}
+
+/// Test light-client model with different configurations
+pub fn test_light_client_model(validators: usize, byzantine: usize, slots: u64, options: &CheckOptions) {
+    println!("Testing light client model with {} validators ({} Byzantine), {} slots", validators, byzantine, slots);
+
+    let model = LightClientModel {
+        validator_count: validators,
+        byzantine_count: byzantine,
+        max_slot: slots,
+    };
+
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs();
+    options.progress.watch(&result);
+    let result = result.join();
+
+    println!("States explored: {}", result.state_count());
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("light client {}v {}b {}s", validators, byzantine, slots), &result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signers(voters: impl IntoIterator<Item = ActorId>) -> VoterSet {
+        voters.into_iter().collect()
+    }
+
+    #[test]
+    fn test_verify_chain_finalizes_fast_and_slow() {
+        let stakes = [1; 5];
+        let certificates = [
+            Certificate::Finalization { slot: 2, signers: signers([1, 2, 3]) },
+            Certificate::FastFinalization { slot: 1, hash: 5, signers: signers(0..4) },
+            Certificate::Notarization { slot: 2, hash: 7, signers: signers([0, 1, 2]) },
+            Certificate::Notarization { slot: 3, hash: 9, signers: signers([0, 1, 2]) },
+        ];
+        assert_eq!(verify_chain(&stakes, &certificates), Ok(BTreeMap::from([(1, 5), (2, 7)])));
+        assert_eq!(verify_chain(&stakes, &[]), Ok(BTreeMap::new()));
+    }
+
+    #[test]
+    fn test_verify_chain_rejects_invalid_certificates() {
+        let stakes = [1; 5];
+        // Notarization votes of 60% relabeled as a fast-finalization certificate
+        let relabeled = Certificate::FastFinalization { slot: 1, hash: 5, signers: signers([0, 1, 2]) };
+        assert_eq!(verify_chain(&stakes, &[relabeled]), Err(ChainError::InsufficientStake { index: 0, stake: 3 }));
+
+        let unknown = Certificate::Finalization { slot: 1, signers: signers([0, 1, 9]) };
+        assert_eq!(verify_chain(&stakes, &[unknown]), Err(ChainError::UnknownSigner { index: 0, signer: 9 }));
+
+        let conflicting = [
+            Certificate::Notarization { slot: 1, hash: 5, signers: signers([0, 1, 2]) },
+            Certificate::Notarization { slot: 1, hash: 6, signers: signers([2, 3, 4]) },
+        ];
+        assert_eq!(verify_chain(&stakes, &conflicting), Err(ChainError::ConflictingBlocks { index: 1, slot: 1 }));
+    }
+
+    #[test]
+    fn test_light_client_matches_full_nodes() {
+        // A Byzantine validator's conflicting votes put the full space out of reach; a bounded
+        // depth-first search still follows whole runs to their finalizations
+        let model = LightClientModel { validator_count: 4, byzantine_count: 1, max_slot: 1 };
+        let checker = model.checker().target_state_count(100_000).spawn_dfs().join();
+        assert!(checker.discovery("light_client_sound").is_none());
+        assert!(checker.discovery("light_client_complete").is_none());
+        assert!(checker.discovery("finality_proof_sound").is_none());
+        assert!(checker.discovery("finality_proof_complete").is_none());
+        assert!(checker.discovery("light_client_finalizes").is_some());
+        assert!(checker.discovery("certificate_rejected").is_some());
+    }
+}
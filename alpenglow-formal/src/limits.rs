@@ -0,0 +1,140 @@
+//! Resource limits on model-checking runs and the outcomes they leave.
+//! An exhaustive search of a larger configuration can run for hours or exhaust memory before it
+//! says anything. With [`ResourceLimits`] the checker stops expanding states once it has expanded
+//! a given number of them or once a wall-clock timeout has passed, then drains the states already
+//! discovered without expanding them and reports what it found. A property is [`Outcome::Proved`] only when the search
+//! covered every reachable state, [`Outcome::Violated`] when the search found a counterexample,
+//! and [`Outcome::Inconclusive`] when a limit or an exploration bound cut the search short first.
+
+use stateright::Expectation;
+use std::fmt;
+use std::time::Duration;
+
+/// Limits on the resources a run may use; `None` leaves a resource unlimited
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ResourceLimits {
+    /// Most states the checker expands
+    pub max_states: Option<usize>,
+    /// Wall-clock time after which the checker stops expanding states
+    pub timeout: Option<Duration>,
+}
+
+impl ResourceLimits {
+    pub fn is_unlimited(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The limit a run has reached after expanding `expanded` states in `elapsed`, if any
+    pub fn reached(&self, expanded: usize, elapsed: Duration) -> Option<Cutoff> {
+        if self.max_states.is_some_and(|max_states| expanded >= max_states) {
+            Some(Cutoff::States)
+        } else if self.timeout.is_some_and(|timeout| elapsed >= timeout) {
+            Some(Cutoff::Timeout)
+        } else {
+            None
+        }
+    }
+
+    pub fn print_summary(&self) {
+        if self.is_unlimited() {
+            return;
+        }
+        println!("Resource limits: {} states, {} (properties are proved only if the search ends before a limit)",
+                 self.max_states.map_or("unlimited".to_string(), |states| states.to_string()),
+                 self.timeout.map_or("no timeout".to_string(), |timeout| format!("{}s timeout", timeout.as_secs())));
+    }
+}
+
+/// Why a search stopped before covering every reachable state
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cutoff {
+    /// `--max-states` states were expanded
+    States,
+    /// `--timeout-secs` passed
+    Timeout,
+    /// The depth, slot or in-flight bound left states unexpanded
+    Bounds,
+}
+
+impl fmt::Display for Cutoff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Cutoff::States => "state limit reached",
+            Cutoff::Timeout => "timeout reached",
+            Cutoff::Bounds => "exploration bounds reached",
+        })
+    }
+}
+
+/// What a run established about one property, ordered from best to worst
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Outcome {
+    /// Holds in every reachable state, or a `sometimes` property has an example
+    Proved,
+    /// Neither proved nor violated before the search was cut short
+    Inconclusive,
+    /// Has a counterexample, or a `sometimes` property has no example in a complete search
+    Violated,
+}
+
+impl Outcome {
+    /// The outcome of a property with `expectation`, given whether the checker discovered a path
+    /// for it and whether the search was complete. A search cut short ends paths early, so an
+    /// `eventually` counterexample found then may only be a truncated path.
+    pub fn of(expectation: &Expectation, discovered: bool, complete: bool) -> Self {
+        match (expectation, discovered, complete) {
+            (Expectation::Sometimes, true, _) => Outcome::Proved,
+            (Expectation::Sometimes, false, true) => Outcome::Violated,
+            (Expectation::Always, true, _) => Outcome::Violated,
+            (_, true, true) => Outcome::Violated,
+            (_, false, true) => Outcome::Proved,
+            (_, _, false) => Outcome::Inconclusive,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Outcome::Proved => "proved",
+            Outcome::Inconclusive => "inconclusive",
+            Outcome::Violated => "violated",
+        }
+    }
+}
+
+/// Names of the properties with `outcome` among `outcomes`
+pub fn named(outcomes: &[(&'static str, Outcome)], outcome: Outcome) -> Vec<String> {
+    outcomes.iter()
+        .filter(|(_, other)| *other == outcome)
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_reached() {
+        let limits = ResourceLimits { max_states: Some(100), timeout: Some(Duration::from_secs(5)) };
+        assert_eq!(limits.reached(99, Duration::from_secs(1)), None);
+        assert_eq!(limits.reached(100, Duration::from_secs(1)), Some(Cutoff::States));
+        assert_eq!(limits.reached(10, Duration::from_secs(5)), Some(Cutoff::Timeout));
+        assert_eq!(ResourceLimits::default().reached(usize::MAX, Duration::MAX), None);
+    }
+
+    #[test]
+    fn test_outcomes() {
+        assert_eq!(Outcome::of(&Expectation::Always, false, true), Outcome::Proved);
+        assert_eq!(Outcome::of(&Expectation::Always, false, false), Outcome::Inconclusive);
+        assert_eq!(Outcome::of(&Expectation::Always, true, false), Outcome::Violated);
+        assert_eq!(Outcome::of(&Expectation::Eventually, true, true), Outcome::Violated);
+        assert_eq!(Outcome::of(&Expectation::Eventually, true, false), Outcome::Inconclusive);
+        assert_eq!(Outcome::of(&Expectation::Sometimes, true, false), Outcome::Proved);
+        assert_eq!(Outcome::of(&Expectation::Sometimes, false, true), Outcome::Violated);
+        assert_eq!(Outcome::of(&Expectation::Sometimes, false, false), Outcome::Inconclusive);
+        assert_eq!([Outcome::Proved, Outcome::Violated, Outcome::Inconclusive].into_iter().max(), Some(Outcome::Violated));
+        let outcomes = [("safety", Outcome::Proved), ("progress", Outcome::Inconclusive), ("finality", Outcome::Inconclusive)];
+        assert_eq!(named(&outcomes, Outcome::Inconclusive), vec!["progress".to_string(), "finality".to_string()]);
+        assert!(named(&outcomes, Outcome::Violated).is_empty());
+    }
+}
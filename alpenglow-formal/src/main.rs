@@ -1,75 +1,810 @@
-mod votor;
-mod certificate;
-mod leader;
-mod timeout;
-mod rotor;
-mod modelling;
+use alpenglow_formal::{alpenglow, certificate, epoch, leader, light_client, modelling, pool, rewards, rotor, standstill, timeout};
+use alpenglow_formal::bounds::Bounded;
+use alpenglow_formal::cli::{ByzantineArgs, CheckArgs, ModelArgs, OutputArgs, SeedArgs};
+use alpenglow_formal::conformance;
+use alpenglow_formal::differential;
+use alpenglow_formal::golden::{self, GoldenTrace};
+use alpenglow_formal::limits::{self, Outcome};
+use alpenglow_formal::modelling::liveness::LivenessModel;
+use alpenglow_formal::modelling::resilience::ResilienceModel;
+use alpenglow_formal::modelling::safety::{SafetyModel, Synchrony};
+use alpenglow_formal::options::CheckOptions;
+use alpenglow_formal::refinement;
+use alpenglow_formal::statistical::{self, SmcConfig};
+use alpenglow_formal::suite::{self, Phase, PhaseResult};
+use alpenglow_formal::sweep::{self, SweepPoint, SweepResult};
+use alpenglow_formal::tla;
+use alpenglow_formal::votor::VotorModel;
+use clap::builder::RangedU64ValueParser;
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use stateright::*;
+use std::collections::BTreeSet;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Instant;
 
-use stateright::{report::WriteReporter, *};
-use votor::VotorModel;
+const DEFAULT_EXPLORER_ADDRESS: &str = "localhost:3000";
+const DEFAULT_TLA_DIR: &str = "results/tla";
+const DEFAULT_TRACE_FILE: &str = "results/discovery.trace";
+const MODEL_NAMES: [&str; 14] = ["votor", "safety", "liveness", "resilience", "certificate", "leader", "timeout", "rotor", "standstill", "pool", "epoch", "light-client", "rewards", "alpenglow"];
 
-fn main() {
-    env_logger::init_from_env(env_logger::Env::default().default_filter_or("info"));
+/// Alpenglow formal verification suite; without a subcommand, verifies every model as `verify` does
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// `tracing` filter directives, taking precedence over RUST_LOG
+    #[arg(long, value_name = "DIRECTIVES")]
+    trace_filter: Option<String>,
+    /// Worker threads the models are checked on [default: one per CPU]
+    #[arg(long, env = "ALPENGLOW_THREADS", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    threads: Option<usize>,
+    #[command(flatten)]
+    output: OutputArgs,
+}
 
-    println!("=== Alpenglow Formal Verification Suite ===");
-    println!();
-    
-    // Run Votor consensus model checking
-    println!("1. Model checking Votor consensus system...");
-    println!("This model verifies the safety of the dual-path finality mechanism:");
-    println!("- Fast Path: Finalization in one round with >= 80% stake");
-    println!("- Slow Path: Finalization in two rounds with >= 60% stake each");
-    println!();
+#[derive(Subcommand)]
+enum Command {
+    /// Verify every model in parallel and print a summary table
+    Verify {
+        /// Models checked at once [default: one per thread]
+        #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+        workers: Option<usize>,
+        #[command(flatten)]
+        check: CheckArgs,
+    },
+    /// Serve the Stateright web explorer for a model
+    Explore {
+        #[command(flatten)]
+        model: ModelFlags,
+        /// Address the explorer listens on
+        #[arg(long, default_value = DEFAULT_EXPLORER_ADDRESS)]
+        address: String,
+        #[command(flatten)]
+        check: CheckArgs,
+    },
+    /// Write a TLA+ skeleton of a model
+    ExportTla {
+        #[command(flatten)]
+        model: ModelFlags,
+        /// Directory receiving the module
+        #[arg(long = "out", value_name = "DIR", default_value = DEFAULT_TLA_DIR)]
+        directory: PathBuf,
+    },
+    /// Check a model and save one of its discoveries as a trace file
+    Record {
+        #[command(flatten)]
+        model: ModelFlags,
+        /// Property whose discovery to save [default: the first by name]
+        #[arg(long)]
+        property: Option<String>,
+        /// Trace file to write
+        #[arg(long = "out", value_name = "FILE", default_value = DEFAULT_TRACE_FILE)]
+        file: PathBuf,
+        #[command(flatten)]
+        check: CheckArgs,
+    },
+    /// Replay a trace file and evaluate the properties in the state it reaches
+    Replay {
+        /// Trace file written by `record`
+        file: PathBuf,
+    },
+    /// Estimate how likely a model's properties fail from random schedules
+    Smc {
+        #[command(flatten)]
+        model: ModelFlags,
+        #[command(flatten)]
+        smc: SmcArgs,
+        #[command(flatten)]
+        check: CheckArgs,
+    },
+    /// Check a model over every combination of the listed parameter values
+    Sweep(SweepArgs),
+    /// Check that Votor refines the safety model
+    Refine {
+        #[command(flatten)]
+        model: ModelFlags,
+    },
+    /// Check a validator's exported events against the Votor model
+    Conform {
+        /// Event log exported by the validator
+        file: PathBuf,
+        #[command(flatten)]
+        model: ModelFlags,
+    },
+    /// Run random schedules through the safety and resilience models and compare their certificates
+    Differential {
+        #[command(flatten)]
+        model: ModelFlags,
+        /// Schedules compared
+        #[arg(long, default_value_t = differential::DEFAULT_SCHEDULES)]
+        schedules: usize,
+        /// Most steps per schedule
+        #[arg(long, default_value_t = differential::DEFAULT_SCHEDULE_LENGTH)]
+        schedule_length: usize,
+        #[command(flatten)]
+        seed: SeedArgs,
+    },
+}
+
+/// `--model <name> [--validators N] [--slots N] [--byzantine N] [--corruption-percent P]`
+#[derive(Args)]
+struct ModelFlags {
+    /// Model to work on
+    #[arg(long = "model", default_value = "votor", value_parser = MODEL_NAMES)]
+    name: String,
+    #[command(flatten)]
+    size: ModelArgs<3, 2>,
+    #[command(flatten)]
+    byzantine: ByzantineArgs<0>,
+    /// Most stake (percent) the adversary may corrupt, where the model has such a budget
+    #[arg(long, value_parser = clap::value_parser!(u64).range(0..=100))]
+    corruption_percent: Option<u64>,
+}
+
+impl ModelFlags {
+    fn params(&self) -> ModelParams {
+        ModelParams {
+            name: self.name.clone(),
+            validators: self.size.validators,
+            slots: self.size.slots,
+            byzantine: self.byzantine.byzantine,
+            corruption_percent: self.corruption_percent,
+        }
+    }
+}
+
+/// The model flags recorded in a trace file
+#[derive(Parser)]
+#[command(no_binary_name = true)]
+struct RecordedFlags {
+    #[command(flatten)]
+    model: ModelFlags,
+}
+
+/// `--samples N --schedule-length N --confidence p --seed N`
+#[derive(Args)]
+struct SmcArgs {
+    /// Schedules sampled
+    #[arg(long, default_value_t = statistical::DEFAULT_SAMPLES, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    samples: usize,
+    /// Most actions per schedule
+    #[arg(long, default_value_t = statistical::DEFAULT_SCHEDULE_LENGTH)]
+    schedule_length: usize,
+    /// Probability that each reported interval contains the true probability
+    #[arg(long, default_value_t = statistical::DEFAULT_CONFIDENCE, value_parser = parse_confidence)]
+    confidence: f64,
+    #[command(flatten)]
+    seed: SeedArgs,
+}
+
+impl SmcArgs {
+    fn config(&self) -> SmcConfig {
+        SmcConfig { samples: self.samples, schedule_length: self.schedule_length, confidence: self.confidence, seed: self.seed.seed }
+    }
+}
+
+fn parse_confidence(text: &str) -> Result<f64, String> {
+    match text.parse::<f64>() {
+        Ok(confidence) if confidence > 0.0 && confidence < 1.0 => Ok(confidence),
+        _ => Err(format!("'{}' is not a probability strictly between 0 and 1", text)),
+    }
+}
+
+/// `sweep --model <name> [--validators 3,4] [--slots 1..2] [--byzantine 0,1] [--corruption-percent 0,20]
+/// [--workers N] [--out file.csv]`
+#[derive(Args)]
+struct SweepArgs {
+    /// Model to sweep
+    #[arg(long = "model", default_value = "votor", value_parser = MODEL_NAMES)]
+    name: String,
+    /// Validator counts: values or inclusive ranges, such as `2,4..6`
+    #[arg(long, value_name = "LIST", default_value = "3")]
+    validators: String,
+    /// Slot counts, as values or inclusive ranges
+    #[arg(long, value_name = "LIST", default_value = "1")]
+    slots: String,
+    /// Byzantine validator counts, as values or inclusive ranges
+    #[arg(long, value_name = "LIST", default_value = "0")]
+    byzantine: String,
+    /// Corruption budgets (percent), as values or inclusive ranges [default: the model's own]
+    #[arg(long, value_name = "LIST")]
+    corruption_percent: Option<String>,
+    /// Configurations checked in parallel [default: one per thread]
+    #[arg(long, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    workers: Option<usize>,
+    /// Write the results matrix to this CSV file
+    #[arg(long = "out", value_name = "FILE")]
+    file: Option<PathBuf>,
+    #[command(flatten)]
+    check: CheckArgs,
+}
+
+/// The model a subcommand works on and its parameters
+struct ModelParams {
+    name: String,
+    validators: usize,
+    slots: u64,
+    byzantine: usize,
+    /// Most stake (percent) the adversary may corrupt, where the model has such a budget
+    corruption_percent: Option<u64>,
+}
+
+impl ModelParams {
+    /// The parameters as recorded in trace files
+    fn parameters(&self) -> Vec<(String, String)> {
+        let mut parameters = vec![
+            ("model".to_string(), self.name.clone()),
+            ("validators".to_string(), self.validators.to_string()),
+            ("slots".to_string(), self.slots.to_string()),
+            ("byzantine".to_string(), self.byzantine.to_string()),
+        ];
+        if let Some(percent) = self.corruption_percent {
+            parameters.push(("corruption-percent".to_string(), percent.to_string()));
+        }
+        parameters
+    }
+
+    /// The parameters a trace was recorded with
+    fn from_trace(trace: &GoldenTrace) -> Result<Self, String> {
+        let args = trace.parameters.iter().flat_map(|(name, value)| [format!("--{}", name), value.clone()]);
+        RecordedFlags::try_parse_from(args).map(|flags| flags.model.params()).map_err(|e| e.to_string())
+    }
+
+    /// Validators that are not Byzantine all respond, where the model distinguishes them
+    fn responsive(&self) -> usize {
+        self.validators - self.byzantine.min(self.validators)
+    }
+}
+
+/// What a subcommand does with the selected model
+trait ModelCommand {
+    fn run<M>(self, model: M)
+    where
+        M: Model + Send + Sync + 'static,
+        M::State: Bounded + Debug + Hash + Clone + PartialEq + Send + Sync,
+        M::Action: Debug + Clone + PartialEq + Send + Sync;
+}
+
+/// Serve the Stateright web explorer until the process is interrupted
+struct Explore {
+    options: CheckOptions,
+    address: String,
+}
+
+impl ModelCommand for Explore {
+    fn run<M>(self, model: M)
+    where
+        M: Model + Send + Sync + 'static,
+        M::State: Bounded + Debug + Hash + Clone + PartialEq + Send + Sync,
+        M::Action: Debug + Clone + PartialEq + Send + Sync,
+    {
+        self.options.print_summary();
+        println!("Serving the Stateright explorer on http://{}", self.address);
+        self.options.checker(model).serve(self.address);
+    }
+}
+
+/// Write a TLA+ skeleton of the model
+struct ExportTla {
+    module: String,
+    directory: PathBuf,
+}
+
+impl ModelCommand for ExportTla {
+    fn run<M>(self, model: M)
+    where
+        M: Model + Send + Sync + 'static,
+        M::State: Bounded + Debug + Hash + Clone + PartialEq + Send + Sync,
+        M::Action: Debug + Clone + PartialEq + Send + Sync,
+    {
+        match tla::write_tla(&model, &self.module, &self.directory) {
+            Ok(file) => println!("TLA+ skeleton written to {}", file.display()),
+            Err(e) => {
+                eprintln!("Cannot write the TLA+ skeleton to {}: {}", self.directory.display(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Check the model and save one of its discoveries as a trace file
+struct Record {
+    options: CheckOptions,
+    /// Property whose discovery to save; the first by name when unset
+    property: Option<String>,
+    parameters: Vec<(String, String)>,
+    file: PathBuf,
+}
+
+impl ModelCommand for Record {
+    fn run<M>(self, model: M)
+    where
+        M: Model + Send + Sync + 'static,
+        M::State: Bounded + Debug + Hash + Clone + PartialEq + Send + Sync,
+        M::Action: Debug + Clone + PartialEq + Send + Sync,
+    {
+        let checker = self.options.checker(model).spawn_bfs().join();
+        let mut discoveries: Vec<_> = checker.discoveries().into_iter().collect();
+        discoveries.sort_by_key(|(property, _)| *property);
+        let selected = discoveries.into_iter()
+            .find(|(property, _)| self.property.as_deref().is_none_or(|wanted| wanted == *property));
+        let Some((property, path)) = selected else {
+            eprintln!("No discovery to record{}", self.property.map_or(String::new(), |property| format!(" for {}", property)));
+            std::process::exit(1);
+        };
+        let steps = path.into_vec();
+        let mut trace = GoldenTrace::from_path(&checker.model().model, &format!("discovery of {}", property), &steps)
+            .expect("discoveries start in an initial state");
+        trace.parameters = self.parameters;
+        if let Some(directory) = self.file.parent().filter(|directory| !directory.as_os_str().is_empty()) {
+            let _ = std::fs::create_dir_all(directory);
+        }
+        match std::fs::write(&self.file, trace.to_text()) {
+            Ok(()) => println!("Recorded the {}-action discovery of {} to {}", trace.actions.len(), property, self.file.display()),
+            Err(e) => {
+                eprintln!("Cannot write {}: {}", self.file.display(), e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Replay a trace file and evaluate the properties in the state it reaches
+struct Replay {
+    trace: GoldenTrace,
+}
+
+impl ModelCommand for Replay {
+    fn run<M>(self, model: M)
+    where
+        M: Model + Send + Sync + 'static,
+        M::State: Bounded + Debug + Hash + Clone + PartialEq + Send + Sync,
+        M::Action: Debug + Clone + PartialEq + Send + Sync,
+    {
+        match golden::replay(&model, &self.trace) {
+            Ok(state) => {
+                println!("Replayed {} actions to the recorded final state", self.trace.actions.len());
+                for property in model.properties() {
+                    let holds = (property.condition)(&model, &state);
+                    println!("  {} {} ({:?}) {} in the final state",
+                             if holds { "✅" } else { "❌" }, property.name, property.expectation,
+                             if holds { "holds" } else { "does not hold" });
+                }
+            }
+            Err(e) => {
+                eprintln!("The trace no longer replays: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Sample random schedules of the model and estimate how likely its properties fail
+struct Statistical {
+    options: CheckOptions,
+    config: SmcConfig,
+}
+
+impl ModelCommand for Statistical {
+    fn run<M>(self, model: M)
+    where
+        M: Model + Send + Sync + 'static,
+        M::State: Bounded + Debug + Hash + Clone + PartialEq + Send + Sync,
+        M::Action: Debug + Clone + PartialEq + Send + Sync,
+    {
+        let model = self.options.bounded(model);
+        let report = statistical::check(&model, &self.config, self.options.threads());
+        report.print_summary();
+        for (property, actions) in &report.counterexamples {
+            println!("First schedule violating {}: {:?}", property, actions);
+        }
+        if report.violated() {
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Check one configuration of a sweep on a single thread, the sweep running configurations in parallel
+struct SweepCheck<'a> {
+    options: CheckOptions,
+    point: SweepPoint,
+    name: String,
+    result: &'a mut Option<SweepResult>,
+}
+
+impl ModelCommand for SweepCheck<'_> {
+    fn run<M>(self, model: M)
+    where
+        M: Model + Send + Sync + 'static,
+        M::State: Bounded + Debug + Hash + Clone + PartialEq + Send + Sync,
+        M::Action: Debug + Clone + PartialEq + Send + Sync,
+    {
+        let start = Instant::now();
+        let checker = self.options.checker(model).threads(1).spawn_bfs().join();
+        self.options.record_results(&self.name, &checker);
+        let outcomes = self.options.outcomes(&checker);
+        *self.result = Some(SweepResult {
+            point: self.point,
+            states: checker.unique_state_count(),
+            elapsed: start.elapsed(),
+            failures: limits::named(&outcomes, Outcome::Violated),
+            inconclusive: limits::named(&outcomes, Outcome::Inconclusive),
+        });
+    }
+}
 
-    let model = VotorModel {
-        honest_validators: 2, // Reduced for faster execution
-        max_slot: 1, // Check up to slot 1
+/// Collect the names of the model's properties
+struct PropertyNames<'a> {
+    names: &'a mut Vec<&'static str>,
+}
+
+impl ModelCommand for PropertyNames<'_> {
+    fn run<M>(self, model: M)
+    where
+        M: Model + Send + Sync + 'static,
+        M::State: Bounded + Debug + Hash + Clone + PartialEq + Send + Sync,
+        M::Action: Debug + Clone + PartialEq + Send + Sync,
+    {
+        *self.names = model.properties().iter().map(|property| property.name).collect();
+    }
+}
+
+/// Check one phase of the suite on its share of the cores, the other phases running alongside
+struct PhaseCheck<'a> {
+    options: CheckOptions,
+    phase: Phase,
+    threads: usize,
+    result: &'a mut Option<PhaseResult>,
+}
+
+impl ModelCommand for PhaseCheck<'_> {
+    fn run<M>(self, model: M)
+    where
+        M: Model + Send + Sync + 'static,
+        M::State: Bounded + Debug + Hash + Clone + PartialEq + Send + Sync,
+        M::Action: Debug + Clone + PartialEq + Send + Sync,
+    {
+        let start = Instant::now();
+        let checker = self.options.checker(model).threads(self.threads).spawn_dfs().join();
+        self.options.record_results(self.phase.model, &checker);
+        let outcomes = self.options.outcomes(&checker);
+        *self.result = Some(PhaseResult {
+            phase: self.phase,
+            states: checker.unique_state_count(),
+            elapsed: start.elapsed(),
+            failures: limits::named(&outcomes, Outcome::Violated),
+            inconclusive: limits::named(&outcomes, Outcome::Inconclusive),
+        });
+    }
+}
+
+/// Build the model selected by `params` and hand it to `command`
+fn with_model<C: ModelCommand>(params: &ModelParams, command: C) {
+    let (validators, slots, byzantine) = (params.validators, params.slots, params.byzantine);
+    match params.name.as_str() {
+        "votor" => command.run(VotorModel { honest_validators: validators, max_slot: slots, network_faults: 0 }),
+        "safety" => command.run(SafetyModel {
+            validator_count: validators,
+            max_slot: slots,
+            byzantine_count: byzantine,
+            offline_budget_percent: modelling::safety::DEFAULT_OFFLINE_BUDGET_PERCENT,
+            corruption_budget_percent: params.corruption_percent.unwrap_or(modelling::safety::DEFAULT_CORRUPTION_BUDGET_PERCENT),
+            network_faults: 0,
+            synchrony: Synchrony::default(),
+            vote_routing: certificate::VoteRouting::default(),
+        }),
+        "liveness" => command.run(LivenessModel {
+            validator_count: validators,
+            max_slot: slots,
+            responsive_count: params.responsive(),
+            max_clock_skew: modelling::liveness::CLOCK_SKEW_BOUND,
+            network_faults: 0,
+            fairness: true,
+            gst: None,
+            message_delays: Vec::new(),
+            crash_budget: 0,
+            persist_votes: true,
+            partition: None,
+        }),
+        "resilience" => command.run(ResilienceModel {
+            byzantine_count: byzantine,
+            offline_budget_percent: modelling::resilience::DEFAULT_OFFLINE_BUDGET_PERCENT,
+            corruption_budget_percent: params.corruption_percent.unwrap_or(modelling::resilience::DEFAULT_CORRUPTION_BUDGET_PERCENT),
+            crash_budget_percent: modelling::resilience::DEFAULT_CRASH_BUDGET_PERCENT,
+            ..ResilienceModel::with_fault_fractions(validators, slots, 0, 0)
+        }),
+        "certificate" => command.run(certificate::CertificateModel {
+            validator_count: validators,
+            max_slot: slots,
+            adversary_count: byzantine,
+            network_faults: 0,
+            vote_routing: certificate::VoteRouting::default(),
+        }),
+        "leader" => command.run(leader::LeaderModel { validator_count: validators, max_slot: slots, network_faults: 0 }),
+        "timeout" => command.run(timeout::TimeoutModel {
+            validator_count: validators,
+            max_slot: slots,
+            max_clock_skew: timeout::CLOCK_SKEW_BOUND,
+            offline: BTreeSet::new(),
+            network_faults: 0,
+            fair_delivery: true,
+        }),
+        "rotor" => command.run(rotor::RotorModel {
+            node_count: validators,
+            max_slot: slots,
+            sampling_strategy: rotor::SamplingStrategy::default(),
+            byzantine: (0..byzantine.min(validators)).collect(),
+            offline: BTreeSet::new(),
+            load_factor_percent: rotor::DEFAULT_LOAD_FACTOR_PERCENT,
+            dissemination_mode: rotor::DisseminationMode::default(),
+            fanout: rotor::DEFAULT_FANOUT,
+            topology: rotor::Topology::default(),
+            network_faults: 0,
+        }),
+        "standstill" => command.run(standstill::StandstillModel {
+            validator_count: validators,
+            max_slot: slots,
+            responsive_count: params.responsive(),
+            network_faults: 0,
+        }),
+        "pool" => command.run(pool::PoolModel {
+            validator_count: validators,
+            max_slot: slots,
+            byzantine_count: byzantine,
+            capacity: pool::required_capacity(validators),
+            hashes: pool::DEFAULT_HASHES,
+        }),
+        "epoch" => command.run(epoch::EpochModel {
+            validator_count: validators,
+            joining_count: 1,
+            byzantine_count: byzantine,
+            slots_per_epoch: epoch::DEFAULT_SLOTS_PER_EPOCH,
+            max_slot: slots,
+            stake_lookup: epoch::StakeLookup::default(),
+            redelegation: epoch::Redelegation::default(),
+            retired_keys: epoch::RetiredKeys::default(),
+        }),
+        "light-client" => command.run(light_client::LightClientModel {
+            validator_count: validators,
+            byzantine_count: byzantine,
+            max_slot: slots,
+        }),
+        "rewards" => command.run(rewards::RewardsModel {
+            validator_count: validators,
+            responsive_count: params.responsive(),
+            max_slot: slots,
+            reward_scheme: rewards::RewardScheme::default(),
+        }),
+        "alpenglow" => command.run(alpenglow::AlpenglowModel {
+            validator_count: validators,
+            max_slot: slots,
+            timeout_ticks: alpenglow::DEFAULT_TIMEOUT_TICKS,
+            byzantine: (0..byzantine.min(validators)).collect(),
+            offline: BTreeSet::new(),
+        }),
+        other => {
+            eprintln!("Unknown model '{}'; expected one of: {}", other, MODEL_NAMES.join(", "));
+            std::process::exit(1);
+        }
+    }
+}
+
+impl Command {
+    fn run(self) {
+        match self {
+            Command::Verify { workers, check } => verify(workers, check.options()),
+            Command::Explore { model, address, check } => explore(&model.params(), address, check.options()),
+            Command::ExportTla { model, directory } => export_tla(&model.params(), directory),
+            Command::Record { model, property, file, check } => record(&model.params(), property, file, check.options()),
+            Command::Replay { file } => replay(&file),
+            Command::Smc { model, smc: args, check } => smc(&model.params(), args.config(), check.options()),
+            Command::Sweep(args) => sweep(args),
+            Command::Refine { model } => refine(&model.params()),
+            Command::Conform { file, model } => conform(&file, &model.params()),
+            Command::Differential { model, schedules, schedule_length, seed } =>
+                differential(&model.params(), schedules, schedule_length, seed.seed),
+        }
+    }
+}
+
+fn explore(params: &ModelParams, address: String, options: CheckOptions) {
+    options.init_tracing();
+    println!("=== Exploring the {} model with {} validators, {} slots ===", params.name, params.validators, params.slots);
+    with_model(params, Explore { options, address });
+}
+
+fn export_tla(params: &ModelParams, directory: PathBuf) {
+    // `safety` becomes module `Safety`
+    let mut module = params.name.clone();
+    if let Some(first) = module.get_mut(..1) {
+        first.make_ascii_uppercase();
+    }
+    with_model(params, ExportTla { module, directory });
+}
+
+fn record(params: &ModelParams, property: Option<String>, file: PathBuf, options: CheckOptions) {
+    options.init_tracing();
+    with_model(params, Record { options, property, parameters: params.parameters(), file });
+}
+
+/// Values of a sweep axis; exits with the usage on a malformed list
+fn sweep_axis<T: FromStr + TryFrom<u64>>(flag: &str, text: &str) -> Vec<T> {
+    sweep::parse_list(text).unwrap_or_else(|e| {
+        Cli::command().error(ErrorKind::ValueValidation, format!("--{}: {}", flag, e)).exit()
+    })
+}
+
+/// Check the model over every combination of the listed values
+fn sweep(args: SweepArgs) {
+    let options = args.check.options();
+    options.init_tracing();
+    let corruption_percents: Vec<Option<u64>> = match &args.corruption_percent {
+        Some(text) => sweep_axis("corruption-percent", text).into_iter().map(Some).collect(),
+        None => vec![None],
+    };
+    let points = sweep::grid(&sweep_axis("validators", &args.validators), &sweep_axis("slots", &args.slots),
+                             &sweep_axis("byzantine", &args.byzantine), &corruption_percents);
+    let workers = args.workers.unwrap_or_else(|| options.available_threads());
+    println!("=== Sweeping the {} model over {} configurations on {} workers ===", args.name, points.len(), workers);
+    options.print_summary();
+
+    let report = sweep::run(&points, workers, |point| {
+        let point_params = ModelParams {
+            name: args.name.clone(),
+            validators: point.validators,
+            slots: point.slots,
+            byzantine: point.byzantine,
+            corruption_percent: point.corruption_percent,
+        };
+        let name = format!("{} {}v {}s {}b{}", args.name, point.validators, point.slots, point.byzantine,
+                           point.corruption_percent.map_or(String::new(), |percent| format!(" {}% corruption", percent)));
+        let mut result = None;
+        with_model(&point_params, SweepCheck { options: options.clone(), point: *point, name, result: &mut result });
+        result.expect("every model command records a result")
+    });
+    report.print_summary();
+
+    if let Some(file) = &args.file {
+        match std::fs::write(file, report.to_csv()) {
+            Ok(()) => println!("Results matrix written to {}", file.display()),
+            Err(e) => eprintln!("Cannot write {}: {}", file.display(), e),
+        }
+    }
+    if !report.passed() {
+        std::process::exit(1);
+    }
+}
+
+fn replay(file: &Path) {
+    let trace = match std::fs::read_to_string(file).map_err(|e| e.to_string()).and_then(|text| GoldenTrace::parse(&text)) {
+        Ok(trace) => trace,
+        Err(e) => {
+            eprintln!("Cannot read the trace {}: {}", file.display(), e);
+            std::process::exit(1);
+        }
+    };
+    let params = match ModelParams::from_trace(&trace) {
+        Ok(params) => params,
+        Err(e) => {
+            eprintln!("The trace {} records invalid parameters: {}", file.display(), e);
+            std::process::exit(1);
+        }
+    };
+    println!("=== Replaying '{}' on the {} model with {} validators, {} slots ===",
+             trace.description, params.name, params.validators, params.slots);
+    with_model(&params, Replay { trace });
+}
+
+fn smc(params: &ModelParams, config: SmcConfig, options: CheckOptions) {
+    options.init_tracing();
+    println!("=== Sampling {} schedules of the {} model with {} validators, {} slots (seed {}) ===",
+             config.samples, params.name, params.validators, params.slots, config.seed);
+    with_model(params, Statistical { options, config });
+}
+
+fn refine(params: &ModelParams) {
+    println!("=== Checking that Votor refines the safety model with {} validators, {} slots ===",
+             params.validators, params.slots);
+    let report = refinement::check_votor_refines_safety(params.validators, params.slots, refinement::DEFAULT_REFINEMENT_STATE_COUNT);
+    report.print_summary();
+    if !report.refines() {
+        std::process::exit(1);
+    }
+}
+
+fn differential(params: &ModelParams, schedules: usize, schedule_length: usize, seed: u64) {
+    println!("=== Comparing the safety and resilience models with {} validators, {} slots ===",
+             params.validators, params.slots);
+    let report = differential::check(params.validators, params.slots, schedules, schedule_length, seed);
+    report.print_summary();
+    if !report.agrees() {
+        std::process::exit(1);
+    }
+}
+
+fn conform(file: &Path, params: &ModelParams) {
+    println!("=== Checking '{}' against the Votor model with {} validators, {} slots ===",
+             file.display(), params.validators, params.slots);
+    let model = VotorModel { honest_validators: params.validators, max_slot: params.slots, network_faults: 0 };
+    let report = match conformance::check_log_file(&model, file) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Cannot read the log {}: {}", file.display(), e);
+            std::process::exit(1);
+        }
     };
+    report.print_summary();
+    if !report.conforms() {
+        std::process::exit(1);
+    }
+}
 
-    model
-        .checker()
-        .threads(num_cpus::get())
-        .spawn_dfs()
-        .report(&mut WriteReporter::new(&mut std::io::stdout()));
-    
+/// Check every phase of the suite, `workers` at a time, and print the summary table
+fn verify(workers: Option<usize>, options: CheckOptions) {
+    options.init_tracing();
+    let params = |phase: &Phase| ModelParams {
+        name: phase.model.to_string(),
+        validators: phase.validators,
+        slots: phase.slots,
+        byzantine: phase.byzantine,
+        corruption_percent: None,
+    };
+
+    // Each model gets the part of the property selection naming its own properties, and models
+    // without a selected property are left out
+    let mut phases = Vec::new();
+    let mut known = BTreeSet::new();
+    for phase in suite::PHASES {
+        let mut names = Vec::new();
+        with_model(&params(&phase), PropertyNames { names: &mut names });
+        known.extend(names.iter().copied());
+        if names.iter().any(|name| options.properties.selects(name)) {
+            phases.push((phase, options.properties.restricted_to(&names)));
+        }
+    }
+    let known: Vec<&str> = known.into_iter().collect();
+    let unknown = options.properties.unknown(&known);
+    if !unknown.is_empty() {
+        Cli::command().error(ErrorKind::ValueValidation, format!("no model has a property named {}", unknown.join(", "))).exit()
+    }
+
+    let workers = workers.unwrap_or_else(|| options.available_threads()).clamp(1, phases.len().max(1));
+    let threads = if options.deterministic { 1 } else { suite::threads_per_phase(options.available_threads(), workers) };
+    println!("=== Alpenglow Formal Verification Suite: {} models, {} at a time on {} threads each ===",
+             phases.len(), workers, threads);
+    options.print_summary();
+
+    let selected: Vec<Phase> = phases.iter().map(|(phase, _)| *phase).collect();
+    let report = suite::run(&selected, workers, |phase| {
+        let (_, properties) = phases.iter().find(|(selected, _)| selected == phase).expect("a selected phase");
+        let options = CheckOptions { properties: properties.clone(), ..options.clone() };
+        let mut result = None;
+        with_model(&params(phase), PhaseCheck { options, phase: *phase, threads, result: &mut result });
+        let result = result.expect("every model command records a result");
+        let mark = match (result.failures.is_empty(), result.inconclusive.is_empty()) {
+            (false, _) => "❌",
+            (true, false) => "❓",
+            (true, true) => "✅",
+        };
+        println!("{} {} finished in {:.2?}", mark, phase.model, result.elapsed);
+        result
+    });
     println!();
-    println!("=== Additional Components ===");
-    
-    // Run safety formal verification
-    println!("\n2. Safety properties verification");
-    modelling::run_safety_verification();
-    
-    // Run liveness formal verification
-    println!("\n3. Liveness properties verification");
-    modelling::run_liveness_verification();
-    
-    // Run resilience formal verification
-    println!("\n4. Resilience and fault tolerance verification");
-    modelling::run_resilience_verification();
-    
-    // Run certificate formal verification
-    println!("\n5. Certificate aggregation and uniqueness verification");
-    certificate::run_formal_verification();
-    
-    // Run leader formal verification
-    println!("\n6. Leader rotation and window management");
-    leader::run_formal_verification();
-    
-    // Run timeout formal verification
-    println!("\n7. Timeout handling and skip certificate generation");
-    timeout::run_formal_verification();
-    
-    // Run rotor formal verification
-    println!("\n8. Rotor sampling strategy");
-    rotor::run_formal_verification();
-    
-    println!("\n=== All Formal Verification Complete ===");
-    println!("The Alpenglow formal verification suite has successfully verified:");
-    println!("- Safety properties of the dual-path finality mechanism");
-    println!("- Liveness guarantees under various network conditions");
-    println!("- Resilience against Byzantine attacks and network partitions");
-    println!("- Certificate uniqueness and aggregation logic");
-    println!("- Leader failure handling and window management");
-    println!("- Timeout mechanisms and skip certificate generation");
-    println!("- Rotor sampling for efficient message dissemination");
-}
\ No newline at end of file
+    report.print_summary();
+    if !report.passed() {
+        std::process::exit(1);
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    cli.output.init();
+    match cli.command {
+        Some(command) => command.run(),
+        None => verify(None, CheckOptions { threads: cli.threads, trace_filter: cli.trace_filter, ..CheckOptions::default() }),
+    }
+}
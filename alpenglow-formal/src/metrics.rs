@@ -0,0 +1,190 @@
+//! Prometheus metrics for long model-checking runs.
+//! Verification farms run checks for hours and need the usual monitoring rather than someone
+//! reading stdout. [`serve`] starts a small HTTP endpoint, once per process, publishing the
+//! latest [`ModelMetrics`] of every model checked so far in the Prometheus text format:
+//! states explored, the frontier still to expand, search depth, whether each property has a
+//! discovery, and the resident memory of the process. [`Progress`](crate::progress::Progress)
+//! refreshes the metrics while a checker runs.
+
+use crate::bounds::{Bounded, BoundedModel};
+use crate::progress::resident_memory_kib;
+use stateright::{Checker, Expectation, Model};
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Write as _};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+
+/// Time between two refreshes of the metrics of a running checker
+pub const METRICS_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Latest metrics of every model, keyed by model name
+static REGISTRY: Mutex<BTreeMap<String, ModelMetrics>> = Mutex::new(BTreeMap::new());
+/// Address the endpoint listens on, once started
+static ENDPOINT: OnceLock<SocketAddr> = OnceLock::new();
+
+/// Exploration measurements of one model
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModelMetrics {
+    pub states: usize,
+    pub unique_states: usize,
+    /// Discovered states not yet expanded
+    pub queue_depth: usize,
+    pub max_depth: usize,
+    pub done: bool,
+    /// (property, expectation, whether the checker has a discovery for it)
+    pub properties: Vec<(&'static str, &'static str, bool)>,
+}
+
+impl ModelMetrics {
+    /// Measure a running or finished checker
+    pub fn of<M, C>(checker: &C) -> Self
+    where
+        M: Model,
+        M::State: Bounded + Clone + PartialEq,
+        M::Action: Debug + Clone + PartialEq,
+        C: Checker<BoundedModel<M>>,
+    {
+        let discoveries = checker.discoveries();
+        let unique_states = checker.unique_state_count();
+        Self {
+            states: checker.state_count(),
+            unique_states,
+            queue_depth: unique_states.saturating_sub(checker.model().expanded()),
+            max_depth: checker.max_depth(),
+            done: checker.is_done(),
+            properties: checker.model().properties().iter()
+                .map(|property| {
+                    let expectation = match property.expectation {
+                        Expectation::Always => "always",
+                        Expectation::Eventually => "eventually",
+                        Expectation::Sometimes => "sometimes",
+                    };
+                    (property.name, expectation, discoveries.contains_key(property.name))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Publish `metrics` as the latest measurements of `model`
+pub fn update(model: &str, metrics: ModelMetrics) {
+    REGISTRY.lock().unwrap().insert(model.to_string(), metrics);
+}
+
+/// Start the metrics endpoint on `address` unless it is already running; returns the address
+/// the endpoint listens on
+pub fn serve(address: SocketAddr) -> io::Result<SocketAddr> {
+    if let Some(address) = ENDPOINT.get() {
+        return Ok(*address);
+    }
+    let listener = TcpListener::bind(address)?;
+    let bound = listener.local_addr()?;
+    if ENDPOINT.set(bound).is_err() {
+        // Another thread started the endpoint first
+        return Ok(*ENDPOINT.get().unwrap());
+    }
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            // A misbehaving scraper must not stop the endpoint
+            let _ = respond(stream);
+        }
+    });
+    tracing::info!("Prometheus metrics served on http://{}/metrics", bound);
+    Ok(bound)
+}
+
+fn respond(mut stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    // Every path serves the metrics, so the request headers only need to be consumed
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 2 {
+        line.clear();
+    }
+    let body = render(&REGISTRY.lock().unwrap(), resident_memory_kib());
+    write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+           body.len(), body)?;
+    stream.flush()
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// A gauge's name, help text and reading
+type Gauge = (&'static str, &'static str, fn(&ModelMetrics) -> usize);
+
+/// The Prometheus text exposition of `models` and the process's resident memory
+fn render(models: &BTreeMap<String, ModelMetrics>, resident_kib: Option<u64>) -> String {
+    let mut text = String::new();
+    let gauges: [Gauge; 5] = [
+        ("alpenglow_states_total", "States generated, including revisits", |metrics| metrics.states),
+        ("alpenglow_unique_states", "Distinct states discovered", |metrics| metrics.unique_states),
+        ("alpenglow_queue_depth", "Discovered states not yet expanded", |metrics| metrics.queue_depth),
+        ("alpenglow_max_depth", "Longest path explored from an initial state", |metrics| metrics.max_depth),
+        ("alpenglow_check_done", "Whether the check has finished", |metrics| metrics.done as usize),
+    ];
+    for (name, help, value) in gauges {
+        writeln!(text, "# HELP {} {}", name, help).unwrap();
+        writeln!(text, "# TYPE {} gauge", name).unwrap();
+        for (model, metrics) in models {
+            writeln!(text, "{}{{model=\"{}\"}} {}", name, escape_label(model), value(metrics)).unwrap();
+        }
+    }
+    writeln!(text, "# HELP alpenglow_property_discovered Whether the checker found a counterexample, or an example for sometimes properties").unwrap();
+    writeln!(text, "# TYPE alpenglow_property_discovered gauge").unwrap();
+    for (model, metrics) in models {
+        for (property, expectation, discovered) in &metrics.properties {
+            writeln!(text, "alpenglow_property_discovered{{model=\"{}\",property=\"{}\",expectation=\"{}\"}} {}",
+                     escape_label(model), escape_label(property), expectation, *discovered as u8).unwrap();
+        }
+    }
+    if let Some(kib) = resident_kib {
+        writeln!(text, "# HELP alpenglow_resident_memory_bytes Resident memory of the checking process").unwrap();
+        writeln!(text, "# TYPE alpenglow_resident_memory_bytes gauge").unwrap();
+        writeln!(text, "alpenglow_resident_memory_bytes {}", kib * 1024).unwrap();
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounds::ExplorationBounds;
+    use crate::votor::VotorModel;
+    use std::io::Read;
+
+    #[test]
+    fn test_render_metrics() {
+        let model = BoundedModel::new(VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 }, ExplorationBounds::default());
+        let checker = model.checker().spawn_bfs().join();
+        let metrics = ModelMetrics::of(&checker);
+        assert!(metrics.done);
+        assert_eq!(metrics.queue_depth, 0);
+        assert!(metrics.properties.iter().all(|(_, _, discovered)| !discovered));
+
+        let models = BTreeMap::from([("VotorModel".to_string(), metrics.clone())]);
+        let text = render(&models, Some(2048));
+        assert!(text.contains(&format!("alpenglow_unique_states{{model=\"VotorModel\"}} {}\n", metrics.unique_states)));
+        assert!(text.contains("alpenglow_check_done{model=\"VotorModel\"} 1\n"));
+        assert!(text.contains("alpenglow_resident_memory_bytes 2097152\n"));
+        assert_eq!(text.matches("alpenglow_property_discovered{").count(), metrics.properties.len());
+        assert!(text.lines().filter(|line| !line.starts_with('#')).all(|line| line.rsplit(' ').next().unwrap().parse::<u64>().is_ok()));
+    }
+
+    #[test]
+    fn test_metrics_endpoint() {
+        let address = serve("127.0.0.1:0".parse().unwrap()).unwrap();
+        update("TestModel", ModelMetrics { states: 7, ..ModelMetrics::default() });
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("alpenglow_states_total{model=\"TestModel\"} 7\n"));
+    }
+}
@@ -2,15 +2,29 @@
 //! This module provides a Stateright-based formal model for verifying liveness guarantees,
 //! progress properties, and bounded finalization time.
 
-use stateright::{Model, Property, Checker};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use stateright::{Checker, Expectation, Model, Property};
 use std::collections::{BTreeMap, BTreeSet};
+use crate::blockstore::Blockstore;
+use crate::leader::stake_weighted_leader;
+use crate::network::{NetworkFault, NetworkFaults};
+use crate::vacuity::{self, Antecedent, DEFAULT_VACUITY_STATE_COUNT};
+use crate::voters::VoterSet;
+use crate::shared::SharedVec;
+use crate::bounds::Bounded;
+use crate::limits::Outcome;
+use crate::options::CheckOptions;
 
 // --- Formal Model Configuration ---
-const FAST_PATH_THRESHOLD_PERCENT: u64 = 80;
-const SLOW_PATH_THRESHOLD_PERCENT: u64 = 60;
+pub const FAST_PATH_THRESHOLD_PERCENT: u64 = 80;
+pub const SLOW_PATH_THRESHOLD_PERCENT: u64 = 60;
 const TOTAL_STAKE: u64 = 1000;
-const MAX_SLOTS: u64 = 5; // Formal verification limit
-const MAX_VALIDATORS: usize = 5; // Formal verification limit
+const SLOT_DURATION_TICKS: u64 = 2; // Local clock ticks per slot
+pub const CLOCK_SKEW_BOUND: u64 = 1; // Skew tolerated by the liveness argument
+pub const POST_GST_FINALIZATION_SLOTS: u64 = 1; // Slots after its own by which a post-GST slot is final
+pub const LEADER_WINDOW_SLOTS: u64 = 2; // Consecutive slots proposed by the same leader
 
 // Type aliases for clarity
 type Slot = u64;
@@ -43,6 +57,17 @@ pub enum LivenessMessage {
         slot: Slot,
         validator: ActorId,
     },
+    /// A rejoining validator asks a peer for what it missed
+    CatchUpRequest {
+        requester: ActorId,
+    },
+    /// A peer's finalization and notarization certificates, and the blocks it holds that
+    /// are not final yet, as (slot, hash)
+    CatchUpResponse {
+        finalized: Vec<(Slot, Hash)>,
+        notarized: Vec<(Slot, Hash)>,
+        blocks: Vec<(Slot, Hash)>,
+    },
 }
 
 /// Represents messages in transit
@@ -50,6 +75,21 @@ pub enum LivenessMessage {
 pub struct MessageInTransit {
     dst: ActorId,
     msg: LivenessMessage,
+    /// Global time (slowest local clock) from which the message can be delivered
+    deliver_at: u64,
+}
+
+impl LivenessMessage {
+    /// Slot this message is about, unless it concerns several
+    fn slot(&self) -> Option<Slot> {
+        match self {
+            LivenessMessage::BlockProposal { slot, .. }
+            | LivenessMessage::NotarVote { slot, .. }
+            | LivenessMessage::FinalVote { slot, .. }
+            | LivenessMessage::TimeoutEvent { slot, .. } => Some(*slot),
+            LivenessMessage::CatchUpRequest { .. } | LivenessMessage::CatchUpResponse { .. } => None,
+        }
+    }
 }
 
 /// Actions that can be taken in the liveness model
@@ -67,8 +107,79 @@ pub enum LivenessAction {
         slot: Slot,
         validator: ActorId,
     },
+    /// Advance a validator's local clock by one tick
+    Tick { validator: ActorId },
     /// Advance to the next slot
     AdvanceSlot,
+    /// The network adversary drops, duplicates or delays a message
+    NetworkFault { fault: NetworkFault<MessageInTransit> },
+    /// A validator crashes, losing its volatile state
+    Crash { validator: ActorId },
+    /// A crashed validator restarts and rejoins
+    Recover { validator: ActorId },
+}
+
+/// A vote a validator signed for a slot
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Ballot {
+    /// NotarVote for a block
+    Notar(Hash),
+    /// FinalVote
+    Final,
+    /// Timing out the slot, in place of a skip vote
+    Skip,
+}
+
+/// Which path finalized a block
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum FinalizationPath {
+    /// 80% NotarVotes in one round
+    Fast,
+    /// 60% NotarVotes followed by 60% FinalVotes
+    Slow,
+}
+
+impl FinalizationPath {
+    /// Lowercase name used in reports
+    pub fn label(&self) -> &'static str {
+        match self {
+            FinalizationPath::Fast => "fast",
+            FinalizationPath::Slow => "slow",
+        }
+    }
+}
+
+/// A validator's finalization of a slot, timed on the global clock
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Finalization {
+    pub path: FinalizationPath,
+    /// When the validator received the block
+    pub received_at: u64,
+    /// When the validator finalized it
+    pub finalized_at: u64,
+}
+
+impl Finalization {
+    /// Ticks from receiving the block to finalizing it
+    pub fn latency(&self) -> u64 {
+        self.finalized_at.saturating_sub(self.received_at)
+    }
+}
+
+/// A partition cutting some validators off from the rest until it heals
+#[derive(Clone, Debug)]
+pub struct PartitionSchedule {
+    /// Validators on the isolated side
+    pub isolated: BTreeSet<ActorId>,
+    /// Global time at which the partition heals; messages across it arrive no earlier
+    pub heal_at: u64,
+}
+
+impl PartitionSchedule {
+    /// Whether two validators are on different sides
+    fn separates(&self, a: ActorId, b: ActorId) -> bool {
+        self.isolated.contains(&a) != self.isolated.contains(&b)
+    }
 }
 
 /// State of a validator in the liveness model
@@ -76,16 +187,65 @@ pub enum LivenessAction {
 pub struct ValidatorState {
     /// Whether this validator is responsive
     is_responsive: bool,
+    /// Whether this validator is down after a crash
+    is_crashed: bool,
     /// Votes cast by this validator: (slot, hash) -> true
     votes_cast: BTreeMap<(Slot, Option<Hash>), bool>,
     /// Vote pool: (slot, hash) -> set of voters
-    vote_pool: BTreeMap<(Slot, Option<Hash>), BTreeSet<ActorId>>,
+    vote_pool: BTreeMap<(Slot, Option<Hash>), VoterSet>,
     /// Notarized slots: slot -> hash
     notarized_slots: BTreeMap<Slot, Hash>,
     /// Finalized slots: slot -> hash
     finalized_slots: BTreeMap<Slot, Hash>,
+    /// Slots this validator timed out on
+    timed_out_slots: BTreeSet<Slot>,
+    /// Global time at which the block of each slot arrived
+    proposal_received: BTreeMap<Slot, u64>,
     /// Current slot
     current_slot: Slot,
+    /// Local clock in ticks
+    local_clock: u64,
+    /// What survives a crash: received blocks, certificates and, if persisted, signed votes
+    blockstore: Blockstore<Ballot>,
+}
+
+impl ValidatorState {
+    fn notarize(&mut self, slot: Slot, hash: Hash) {
+        self.notarized_slots.insert(slot, hash);
+        self.blockstore.insert_notarized(slot, hash);
+    }
+
+    fn finalize(&mut self, slot: Slot, hash: Hash) {
+        self.finalized_slots.insert(slot, hash);
+        self.blockstore.insert_finalized(slot, hash);
+    }
+
+    /// The state the validator restarts with after a crash: everything held in memory is
+    /// lost, and its certificates and signed votes are read back from the blockstore
+    fn restarted(&self) -> Self {
+        let mut votes_cast = BTreeMap::new();
+        let mut timed_out_slots = BTreeSet::new();
+        for (slot, ballot) in self.blockstore.votes() {
+            match ballot {
+                Ballot::Notar(hash) => { votes_cast.insert((slot, Some(*hash)), true); }
+                Ballot::Final => { votes_cast.insert((slot, None), true); }
+                Ballot::Skip => { timed_out_slots.insert(slot); }
+            }
+        }
+        Self {
+            is_responsive: self.is_responsive,
+            is_crashed: self.is_crashed,
+            votes_cast,
+            vote_pool: BTreeMap::new(),
+            notarized_slots: self.blockstore.notarized().clone(),
+            finalized_slots: self.blockstore.finalized().clone(),
+            timed_out_slots,
+            proposal_received: BTreeMap::new(),
+            current_slot: self.current_slot,
+            local_clock: self.local_clock,
+            blockstore: self.blockstore.clone(),
+        }
+    }
 }
 
 /// Main state of the liveness formal model
@@ -93,16 +253,22 @@ pub struct ValidatorState {
 pub struct LivenessState {
     /// Network messages in transit
     network: BTreeSet<MessageInTransit>,
+    /// Adversarial control over message delivery
+    network_faults: NetworkFaults<MessageInTransit>,
     /// Per-validator states
-    validators: Vec<ValidatorState>,
+    validators: SharedVec<ValidatorState>,
     /// Global current slot
     current_slot: Slot,
     /// Stake distribution: validator -> stake
     stake_distribution: BTreeMap<ActorId, Stake>,
     /// Block proposals: slot -> hash
     block_proposals: BTreeMap<Slot, Hash>,
-    /// Finalization times: slot -> time to finalize
-    finalization_times: BTreeMap<Slot, u64>,
+    /// First finalization of each slot by each validator: (slot, validator) -> finalization
+    finalizations: BTreeMap<(Slot, ActorId), Finalization>,
+    /// Crashes so far
+    crashes: usize,
+    /// Every vote each validator signed, kept across its crashes: (validator, slot) -> ballots
+    ballots: BTreeMap<(ActorId, Slot), Vec<Ballot>>,
 }
 
 /// Formal model for liveness properties
@@ -114,6 +280,140 @@ pub struct LivenessModel {
     pub max_slot: Slot,
     /// Number of responsive validators
     pub responsive_count: usize,
+    /// Maximum difference between any two local clocks
+    pub max_clock_skew: u64,
+    /// Messages the network adversary may drop, duplicate or delay
+    pub network_faults: usize,
+    /// Only explore weakly fair executions: no clock ticks past a slot's deadline before its
+    /// leader proposed, or while a message to the ticking validator is still in flight
+    pub fairness: bool,
+    /// Global stabilization time on the slowest local clock: before it the network adversary
+    /// controls delivery, from then on executions are fair and nothing is dropped or delayed
+    pub gst: Option<u64>,
+    /// Ticks each validator's messages take to arrive; missing entries arrive at once. Under
+    /// fair scheduling global time does not pass a message's arrival before it is delivered
+    pub message_delays: Vec<u64>,
+    /// Crashes of responsive validators the model may inject. Under fair scheduling time
+    /// waits for a crashed validator to recover
+    pub crash_budget: usize,
+    /// Whether validators write the votes they sign to their blockstore; otherwise a
+    /// restarted validator forgets what it signed
+    pub persist_votes: bool,
+    /// Partition in place from the start until it heals
+    pub partition: Option<PartitionSchedule>,
+}
+
+impl LivenessModel {
+    /// Whether global stabilization time has passed on every local clock
+    fn after_gst(&self, state: &LivenessState) -> bool {
+        self.gst.is_some_and(|gst| state.min_local_clock() >= gst)
+    }
+
+    /// Whether the execution is restricted to fair scheduling from this state on
+    fn fair(&self, state: &LivenessState) -> bool {
+        self.fairness || self.after_gst(state)
+    }
+
+    /// Ticks a message sent by `validator` takes to arrive
+    pub fn message_delay(&self, validator: ActorId) -> u64 {
+        self.message_delays.get(validator).copied().unwrap_or(0)
+    }
+
+    /// Put a message from `sender` in flight, arriving after the sender's delay and, across
+    /// a partition, no earlier than it heals
+    fn send(&self, state: &mut LivenessState, sender: ActorId, dst: ActorId, msg: LivenessMessage) {
+        let mut deliver_at = state.min_local_clock() + self.message_delay(sender);
+        if let Some(partition) = self.partition.as_ref().filter(|p| p.separates(sender, dst)) {
+            deliver_at = deliver_at.max(partition.heal_at);
+        }
+        state.network.insert(MessageInTransit { dst, msg, deliver_at });
+    }
+
+    /// Whether a slot began before the partition healed
+    fn during_partition(&self, slot: Slot) -> bool {
+        self.partition.as_ref().is_some_and(|p| slot.saturating_sub(1) * SLOT_DURATION_TICKS < p.heal_at)
+    }
+
+    /// Whether the partition may cost a slot: one begun before it healed, led from the
+    /// isolated side unless the rest lack the stake to finalize on their own
+    fn partition_may_skip(&self, state: &LivenessState, slot: Slot) -> bool {
+        self.partition.as_ref().is_some_and(|p| {
+            self.during_partition(slot)
+                && (state.is_partition_critical(&p.isolated) || p.isolated.contains(&state.leader(slot)))
+        })
+    }
+
+    /// Whether the model is stuck: enough responsive stake to finalize, a slot with a
+    /// responsive leader neither finalized nor skipped, and no action left but the adversary's.
+    /// Messages still on their way past the last tick only hit the exploration horizon
+    fn is_deadlocked(&self, state: &LivenessState) -> bool {
+        if !state.can_progress() || state.network_faults.messages_withheld() {
+            return false;
+        }
+        let now = state.min_local_clock();
+        if state.network.iter().any(|m| m.deliver_at > now) {
+            return false;
+        }
+        let unresolved = state.unfinalized_slots(self.max_slot).into_iter()
+            .any(|slot| !state.is_skipped(slot) && !self.partition_may_skip(state, slot));
+        if !unresolved {
+            return false;
+        }
+        let mut actions = Vec::new();
+        self.actions(state, &mut actions);
+        actions.iter().all(|a| matches!(a, LivenessAction::NetworkFault { .. } | LivenessAction::Crash { .. }))
+    }
+
+    /// Most slots with a responsive leader the partition may leave unfinalized
+    pub fn partition_skip_bound(&self, state: &LivenessState) -> usize {
+        (1..=self.max_slot)
+            .filter(|slot| state.has_responsive_leader(*slot) && self.partition_may_skip(state, *slot))
+            .count()
+    }
+
+    /// Record a vote the validator signs, writing it to its blockstore first if votes persist
+    fn sign(&self, state: &mut LivenessState, validator_state: &mut ValidatorState, validator: ActorId, slot: Slot, ballot: Ballot) {
+        if self.persist_votes {
+            validator_state.blockstore.insert_vote(slot, ballot);
+        }
+        state.record_ballot(validator, slot, ballot);
+    }
+
+    /// Cast and broadcast a NotarVote for a received block unless the validator is
+    /// unresponsive, timed out on the slot or already voted for the block
+    fn vote_for_block(&self, state: &mut LivenessState, validator_state: &mut ValidatorState, validator: ActorId, slot: Slot, hash: Hash) {
+        if validator_state.is_responsive
+            && !validator_state.timed_out_slots.contains(&slot)
+            && !validator_state.votes_cast.contains_key(&(slot, Some(hash)))
+        {
+            validator_state.votes_cast.insert((slot, Some(hash)), true);
+            self.sign(state, validator_state, validator, slot, Ballot::Notar(hash));
+
+            // Broadcast NotarVote
+            for i in 0..self.validator_count {
+                self.send(state, validator, i, LivenessMessage::NotarVote {
+                    slot,
+                    hash,
+                    voter: validator,
+                });
+            }
+        }
+    }
+
+    /// Cast and broadcast a FinalVote for a notarized block unless the validator is
+    /// unresponsive or already cast one
+    fn cast_final_vote(&self, state: &mut LivenessState, validator_state: &mut ValidatorState, validator: ActorId, slot: Slot) {
+        if validator_state.is_responsive && !validator_state.votes_cast.contains_key(&(slot, None)) {
+            validator_state.votes_cast.insert((slot, None), true);
+            self.sign(state, validator_state, validator, slot, Ballot::Final);
+            for i in 0..self.validator_count {
+                self.send(state, validator, i, LivenessMessage::FinalVote {
+                    slot,
+                    voter: validator,
+                });
+            }
+        }
+    }
 }
 
 impl LivenessState {
@@ -127,57 +427,352 @@ impl LivenessState {
 
         Self {
             network: BTreeSet::new(),
+            network_faults: NetworkFaults::new(0),
             validators: (0..validator_count).map(|i| ValidatorState {
                 is_responsive: i < responsive_count,
+                is_crashed: false,
                 votes_cast: BTreeMap::new(),
                 vote_pool: BTreeMap::new(),
                 notarized_slots: BTreeMap::new(),
                 finalized_slots: BTreeMap::new(),
+                timed_out_slots: BTreeSet::new(),
+                proposal_received: BTreeMap::new(),
                 current_slot: 0,
+                local_clock: 0,
+                blockstore: Blockstore::new(),
             }).collect(),
             current_slot: 0,
             stake_distribution,
             block_proposals: BTreeMap::new(),
-            finalization_times: BTreeMap::new(),
+            finalizations: BTreeMap::new(),
+            crashes: 0,
+            ballots: BTreeMap::new(),
         }
     }
 
-    /// Check if a block can be notarized (60% threshold)
-    fn can_notarize(&self, slot: Slot, hash: Hash) -> bool {
-        if let Some(voters) = self.validators[0].vote_pool.get(&(slot, Some(hash))) {
-            let stake: Stake = voters.iter()
-                .filter(|voter_id| self.validators[**voter_id].is_responsive)
-                .filter_map(|voter_id| self.stake_distribution.get(voter_id))
-                .sum();
-            stake >= (TOTAL_STAKE * SLOW_PATH_THRESHOLD_PERCENT / 100)
-        } else {
-            false
+    /// Stake of all validators
+    fn total_stake(&self) -> Stake {
+        self.stake_distribution.values().sum()
+    }
+
+    /// Stake of the responsive validators among `voters`, each counted with its own stake
+    fn voting_stake(&self, voters: impl IntoIterator<Item = ActorId>) -> Stake {
+        voters.into_iter()
+            .filter(|voter_id| self.validators[*voter_id].is_responsive)
+            .filter_map(|voter_id| self.stake_distribution.get(&voter_id))
+            .sum()
+    }
+
+    /// Whether `stake` is at least `percent` of the total stake
+    fn meets_threshold(&self, stake: Stake, percent: u64) -> bool {
+        stake * 100 >= self.total_stake() * percent
+    }
+
+    /// Check if a block can be notarized (60% threshold) from the votes in a validator's pool
+    fn can_notarize(&self, vote_pool: &BTreeMap<(Slot, Option<Hash>), VoterSet>, slot: Slot, hash: Hash) -> bool {
+        vote_pool.get(&(slot, Some(hash)))
+            .is_some_and(|voters| self.meets_threshold(self.voting_stake(voters.iter()), SLOW_PATH_THRESHOLD_PERCENT))
+    }
+
+    /// Stake of responsive validators
+    fn responsive_stake(&self) -> Stake {
+        self.voting_stake(self.stake_distribution.keys().copied())
+    }
+
+    /// Whether responsive validators hold more than 60% of the stake, so slots can finalize
+    fn can_progress(&self) -> bool {
+        self.responsive_stake() * 100 > self.total_stake() * SLOW_PATH_THRESHOLD_PERCENT
+    }
+
+    /// Whether responsive validators hold at least 80% of the stake, so the fast path can finalize
+    fn can_fast_path(&self) -> bool {
+        self.meets_threshold(self.responsive_stake(), FAST_PATH_THRESHOLD_PERCENT)
+    }
+
+    /// Slowest local clock across validators
+    fn min_local_clock(&self) -> u64 {
+        self.validators.iter().map(|v| v.local_clock).min().unwrap_or(0)
+    }
+
+    /// Check if a block can be fast-finalized (80% threshold) from the votes in a validator's pool
+    fn can_fast_finalize(&self, vote_pool: &BTreeMap<(Slot, Option<Hash>), VoterSet>, slot: Slot, hash: Hash) -> bool {
+        vote_pool.get(&(slot, Some(hash)))
+            .is_some_and(|voters| self.meets_threshold(self.voting_stake(voters.iter()), FAST_PATH_THRESHOLD_PERCENT))
+    }
+
+    /// Whether some validator holds NotarVotes from 80% of the stake for a block of `slot`, a
+    /// fast-finalization certificate, whichever path finalized the slot first
+    fn fast_quorum_reached(&self, slot: Slot) -> bool {
+        self.validators.iter().any(|validator| {
+            validator.vote_pool.keys().any(|(s, hash)| {
+                *s == slot && hash.is_some_and(|hash| self.can_fast_finalize(&validator.vote_pool, slot, hash))
+            })
+        })
+    }
+
+    /// Check if a notarized block can be slow-finalized (60% FinalVotes) from the FinalVotes in a validator's pool
+    fn can_slow_finalize(&self, vote_pool: &BTreeMap<(Slot, Option<Hash>), VoterSet>, slot: Slot) -> bool {
+        // Count FinalVotes for this slot; FinalVotes have a None hash
+        vote_pool.get(&(slot, None))
+            .is_some_and(|voters| self.meets_threshold(self.voting_stake(voters.iter()), SLOW_PATH_THRESHOLD_PERCENT))
+    }
+
+    /// Record a validator's first finalization of a slot at the current global time
+    fn record_finalization(&mut self, validator: ActorId, validator_state: &ValidatorState, slot: Slot, path: FinalizationPath) {
+        let finalized_at = self.min_local_clock();
+        let received_at = validator_state.proposal_received.get(&slot).copied().unwrap_or(finalized_at);
+        self.finalizations.entry((slot, validator)).or_insert(Finalization { path, received_at, finalized_at });
+    }
+
+    /// δθ for a block: the delay within which NotarVotes from responsive voters holding
+    /// `percent` of the stake arrive, if they hold that much at all
+    fn vote_delay(&self, model: &LivenessModel, slot: Slot, hash: Hash, percent: u64) -> Option<u64> {
+        let mut voters: Vec<(u64, Stake)> = self.validators.iter().enumerate()
+            .filter(|(_, v)| v.is_responsive && v.votes_cast.contains_key(&(slot, Some(hash))))
+            .map(|(id, _)| (model.message_delay(id), self.stake_distribution[&id]))
+            .collect();
+        voters.sort();
+        let mut stake = 0;
+        for (delay, voter_stake) in voters {
+            stake += voter_stake;
+            if self.meets_threshold(stake, percent) {
+                return Some(delay);
+            }
         }
+        None
+    }
+
+    /// Latency bound for a block: min(δ80, 2δ60)
+    fn finalization_bound(&self, model: &LivenessModel, slot: Slot, hash: Hash) -> u64 {
+        let fast = self.vote_delay(model, slot, hash, FAST_PATH_THRESHOLD_PERCENT);
+        let slow = self.vote_delay(model, slot, hash, SLOW_PATH_THRESHOLD_PERCENT).map(|delay| 2 * delay);
+        fast.into_iter().chain(slow).min().unwrap_or(u64::MAX)
+    }
+
+    /// Whether lost messages or crashes void every timing guarantee
+    fn timing_disrupted(&self) -> bool {
+        self.network_faults.messages_withheld() || self.crashes > 0
+    }
+
+    /// Record a vote signed by a validator
+    fn record_ballot(&mut self, validator: ActorId, slot: Slot, ballot: Ballot) {
+        self.ballots.entry((validator, slot)).or_default().push(ballot);
+    }
+
+    /// Whether every validator's finalizations, and its signed votes when `persist_votes`, are
+    /// in its blockstore
+    fn check_blockstore_durable(&self, persist_votes: bool) -> bool {
+        let finalized = self.validators.iter()
+            .all(|v| v.finalized_slots.iter().all(|(slot, hash)| v.blockstore.finalized().get(slot) == Some(hash)));
+        finalized && (!persist_votes || self.ballots.iter().all(|((validator, slot), ballots)| {
+            ballots.iter().all(|ballot| self.validators[*validator].blockstore.has_vote(*slot, ballot))
+        }))
     }
 
-    /// Check if a block can be fast-finalized (80% threshold)
-    fn can_fast_finalize(&self, slot: Slot, hash: Hash) -> bool {
-        if let Some(voters) = self.validators[0].vote_pool.get(&(slot, Some(hash))) {
-            let stake: Stake = voters.iter()
-                .filter(|voter_id| self.validators[**voter_id].is_responsive)
-                .filter_map(|voter_id| self.stake_distribution.get(voter_id))
-                .sum();
-            stake >= (TOTAL_STAKE * FAST_PATH_THRESHOLD_PERCENT / 100)
-        } else {
-            false
+    /// Whether no validator signed the same vote twice, or both a NotarVote and a skip, for a slot
+    fn check_no_double_vote(&self) -> bool {
+        self.ballots.values().all(|ballots| {
+            let distinct: BTreeSet<&Ballot> = ballots.iter().collect();
+            distinct.len() == ballots.len()
+                && !(distinct.contains(&Ballot::Skip) && ballots.iter().any(|b| matches!(b, Ballot::Notar(_))))
+        })
+    }
+
+    /// Whether every finalization of a block delivered under fair scheduling took at most
+    /// min(δ80, 2δ60) from the block's arrival
+    fn check_finalization_latency(&self, model: &LivenessModel) -> bool {
+        if self.timing_disrupted() {
+            return true;
         }
+        self.finalizations.iter().all(|((slot, validator), finalization)| {
+            let timely = (model.fairness || model.gst.is_some_and(|gst| finalization.received_at >= gst))
+                && model.partition.as_ref().is_none_or(|p| finalization.received_at >= p.heal_at);
+            let hash = self.validators[*validator].finalized_slots[slot];
+            !timely || finalization.latency() <= self.finalization_bound(model, *slot, hash)
+        })
     }
 
-    /// Check if a notarized block can be slow-finalized (60% FinalVotes)
-    fn can_slow_finalize(&self, slot: Slot) -> bool {
-        // Count FinalVotes for this slot
-        let final_vote_stake: Stake = self.validators.iter()
-            .filter(|_v| _v.is_responsive)
-            .filter(|_v| _v.votes_cast.contains_key(&(slot, None))) // FinalVote has None hash
-            .map(|_v| self.stake_distribution.get(&0).unwrap_or(&0)) // Simplified stake lookup
-            .sum();
-        
-        final_vote_stake >= (TOTAL_STAKE * SLOW_PATH_THRESHOLD_PERCENT / 100)
+    /// Whether every slot starting after `gst` was finalized by every responsive validator once
+    /// every clock is `POST_GST_FINALIZATION_SLOTS` past its end, unless the network adversary
+    /// withheld one of its messages before GST
+    fn check_post_gst_finalization(&self, gst: u64, max_slot: Slot) -> bool {
+        if self.crashes > 0 {
+            return true; // A restarted validator lost the votes it had received
+        }
+        let min_clock = self.min_local_clock();
+        let disrupted: BTreeSet<Slot> = self.network_faults.withheld().filter_map(|m| m.msg.slot()).collect();
+        (1..=max_slot)
+            .filter(|slot| (slot - 1) * SLOT_DURATION_TICKS >= gst && !disrupted.contains(slot))
+            .filter(|slot| self.has_responsive_leader(*slot))
+            .filter(|slot| min_clock >= (slot + POST_GST_FINALIZATION_SLOTS) * SLOT_DURATION_TICKS)
+            .all(|slot| {
+                self.validators.iter()
+                    .filter(|v| v.is_responsive)
+                    .all(|v| v.finalized_slots.contains_key(&slot))
+            })
+    }
+
+    /// Whether no responsive validator skipped a slot whose block, arriving early and under
+    /// fair scheduling, is still on its way to it, unless lost messages or crashes void the timing
+    fn check_no_premature_skip(&self, model: &LivenessModel) -> bool {
+        if self.timing_disrupted() {
+            return true;
+        }
+        self.network.iter().all(|m| match m.msg {
+            LivenessMessage::BlockProposal { slot, .. } => {
+                let fair = model.fairness || model.gst.is_some_and(|gst| m.deliver_at >= gst);
+                !fair
+                    || !arrives_early(slot, m.deliver_at)
+                    || !self.validators[m.dst].is_responsive
+                    || !self.ballots.get(&(m.dst, slot)).is_some_and(|ballots| ballots.contains(&Ballot::Skip))
+            }
+            _ => true,
+        })
+    }
+
+    /// Whether a responsive validator's clock reached the deadline of a slot whose block
+    /// arrived there early
+    fn received_early_past_deadline(&self) -> bool {
+        self.validators.iter().filter(|v| v.is_responsive).any(|v| {
+            v.proposal_received.iter()
+                .any(|(slot, at)| arrives_early(*slot, *at) && v.local_clock >= slot * SLOT_DURATION_TICKS)
+        })
+    }
+
+    /// Whether a responsive validator timed out on a slot before voting for its block. The
+    /// slot's stake may then split between NotarVotes and skips with neither reaching its
+    /// threshold, which only fallback votes, not part of this model, would settle
+    fn skipped_by_responsive(&self, slot: Slot) -> bool {
+        self.ballots.iter().any(|((validator, s), ballots)| {
+            *s == slot && self.validators[*validator].is_responsive && ballots.contains(&Ballot::Skip)
+        })
+    }
+
+    /// Whether, once no message is in transit, every slot proposed up to the current one and
+    /// not skipped by a responsive validator was notarized, for slots begun under fair
+    /// scheduling and clear of a partition, unless lost messages or crashes void the timing
+    fn check_quiescent_progress(&self, model: &LivenessModel) -> bool {
+        if self.timing_disrupted() || !self.network.is_empty() {
+            return true;
+        }
+        (1..=self.current_slot)
+            .filter(|slot| model.fairness || model.gst.is_some_and(|gst| (slot - 1) * SLOT_DURATION_TICKS >= gst))
+            .filter(|slot| self.block_proposals.contains_key(slot) && !model.during_partition(*slot))
+            .filter(|slot| !self.skipped_by_responsive(*slot))
+            .all(|slot| self.validators.iter().any(|v| v.notarized_slots.contains_key(&slot) || v.finalized_slots.contains_key(&slot)))
+    }
+
+    /// Scheduled leader of a slot: each window of `LEADER_WINDOW_SLOTS` slots has one
+    /// stake-weighted leader
+    fn leader(&self, slot: Slot) -> ActorId {
+        stake_weighted_leader(&self.stake_distribution, leader_window(slot))
+    }
+
+    /// Whether the scheduled leader of a slot is responsive, so the slot is expected to be proposed
+    fn has_responsive_leader(&self, slot: Slot) -> bool {
+        self.validators[self.leader(slot)].is_responsive
+    }
+
+    /// Whether a slot was finalized by some responsive validator
+    fn is_finalized(&self, slot: Slot) -> bool {
+        self.validators.iter().any(|v| v.is_responsive && v.finalized_slots.contains_key(&slot))
+    }
+
+    /// Slots up to `max_slot` with a responsive leader that no responsive validator finalized;
+    /// slots of unresponsive leaders are skipped
+    fn unfinalized_slots(&self, max_slot: Slot) -> Vec<Slot> {
+        (1..=max_slot)
+            .filter(|slot| self.has_responsive_leader(*slot) && !self.is_finalized(*slot))
+            .collect()
+    }
+
+    /// Whether responsive validators holding more than 40% of the stake signed a skip for a
+    /// slot, so it can never be notarized
+    fn is_skipped(&self, slot: Slot) -> bool {
+        let skippers = self.ballots.iter()
+            .filter(|((_, s), ballots)| *s == slot && ballots.contains(&Ballot::Skip))
+            .map(|((validator, _), _)| *validator);
+        self.voting_stake(skippers) * 100 > self.total_stake() * (100 - SLOW_PATH_THRESHOLD_PERCENT)
+    }
+
+    /// Whether the validators outside `isolated` hold too little responsive stake to finalize
+    fn is_partition_critical(&self, isolated: &BTreeSet<ActorId>) -> bool {
+        let connected_stake = self.voting_stake(self.stake_distribution.keys().copied().filter(|id| !isolated.contains(id)));
+        !self.meets_threshold(connected_stake, SLOW_PATH_THRESHOLD_PERCENT)
+    }
+
+    /// Whether every leader window up to `max_slot` with a responsive leader finalized a
+    /// block, not counting slots `skippable` excuses
+    fn all_windows_finalized(&self, max_slot: Slot, skippable: impl Fn(Slot) -> bool) -> bool {
+        (1..=max_slot)
+            .filter(|slot| self.has_responsive_leader(*slot) && !skippable(*slot))
+            .all(|slot| {
+                (1..=max_slot)
+                    .filter(|other| leader_window(*other) == leader_window(slot))
+                    .any(|other| self.is_finalized(other))
+            })
+    }
+}
+
+/// Leader window a slot belongs to; slot 0 is genesis and shares the first window
+fn leader_window(slot: Slot) -> u64 {
+    slot.saturating_sub(1) / LEADER_WINDOW_SLOTS
+}
+
+/// Whether a block arriving at global time `at` leaves every clock within the skew bound
+/// short of the slot's deadline until it is delivered
+fn arrives_early(slot: Slot, at: u64) -> bool {
+    at + CLOCK_SKEW_BOUND <= slot * SLOT_DURATION_TICKS
+}
+
+impl LivenessModel {
+    /// Preconditions under which the conditional liveness properties make a real claim
+    pub fn antecedents() -> Vec<Antecedent<Self>> {
+        vec![
+            Antecedent::<Self>::new("progress_guarantee", |_model, state| {
+                state.can_progress()
+            }),
+            Antecedent::<Self>::new("fast_path_completion", |_model, state| {
+                state.can_fast_path()
+            }),
+            Antecedent::<Self>::new("bounded_finalization", |_model, state| {
+                !state.finalizations.is_empty()
+            }),
+            Antecedent::<Self>::new("liveness_partial_sync", |_model, state| {
+                state.can_progress()
+                    && state.network.is_empty()
+                    && (1..=state.current_slot).any(|slot| state.block_proposals.contains_key(&slot))
+            }),
+            Antecedent::<Self>::new("no_premature_skip", |_model, state| {
+                state.received_early_past_deadline()
+            }),
+            Antecedent::<Self>::new("bounded_post_gst_finalization", |model, state| {
+                model.gst.is_some_and(|gst| {
+                    let first_bounded_slot = gst.div_ceil(SLOT_DURATION_TICKS) + 1;
+                    state.min_local_clock() >= (first_bounded_slot + POST_GST_FINALIZATION_SLOTS) * SLOT_DURATION_TICKS
+                })
+            }),
+            Antecedent::<Self>::new("no_double_vote", |_model, state| {
+                state.crashes > 0
+            }),
+            Antecedent::<Self>::new("blockstore_durable", |_model, state| {
+                state.crashes > 0 && state.validators.iter().any(|v| !v.finalized_slots.is_empty())
+            }),
+            Antecedent::<Self>::new("leader_window_progress", |model, state| {
+                state.can_progress()
+                    && (1..=model.max_slot).any(|slot| state.has_responsive_leader(slot))
+            }),
+        ]
+    }
+}
+
+impl Bounded for LivenessState {
+    fn current_slot(&self) -> Slot {
+        self.current_slot
+    }
+
+    fn in_flight(&self) -> usize {
+        self.network.len()
     }
 }
 
@@ -186,41 +781,95 @@ impl Model for LivenessModel {
     type Action = LivenessAction;
 
     fn init_states(&self) -> Vec<Self::State> {
-        vec![LivenessState::new(self.validator_count, self.responsive_count)]
+        let mut state = LivenessState::new(self.validator_count, self.responsive_count);
+        state.network_faults = NetworkFaults::new(self.network_faults);
+        vec![state]
     }
 
     fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
-        // 1. Deliver any message in the network
-        for msg in &state.network {
+        // 1. Deliver any message in the network that has arrived
+        let now = state.min_local_clock();
+        for msg in state.network.iter().filter(|m| m.deliver_at <= now && !state.validators[m.dst].is_crashed) {
             actions.push(LivenessAction::DeliverMessage { msg: msg.clone() });
         }
 
-        // 2. Propose blocks for current and future slots
-        for slot in state.current_slot..=self.max_slot {
-            for proposer in 0..self.validator_count {
-                if !state.block_proposals.contains_key(&slot) {
-                    actions.push(LivenessAction::ProposeBlock {
+        // The network adversary drops, duplicates or delays a bounded number of messages, and
+        // after GST only releases the messages it still holds back
+        for fault in state.network_faults.faults(&state.network) {
+            if !self.after_gst(state) || matches!(fault, NetworkFault::Release(_)) {
+                actions.push(LivenessAction::NetworkFault { fault });
+            }
+        }
+
+        // 2. The scheduled leader proposes blocks for current and future slots; slot 0 is genesis
+        for slot in state.current_slot.max(1)..=self.max_slot {
+            let proposer = state.leader(slot);
+            let leader = &state.validators[proposer];
+            if !state.block_proposals.contains_key(&slot) && leader.is_responsive && !leader.is_crashed {
+                actions.push(LivenessAction::ProposeBlock {
+                    slot,
+                    proposer,
+                });
+            }
+        }
+
+        // 3. Trigger timeouts for slots whose deadline passed on the local clock
+        for slot in 1..=self.max_slot {
+            for validator in 0..self.validator_count {
+                let timeout = LivenessMessage::TimeoutEvent { slot, validator };
+                if state.validators[validator].local_clock >= slot * SLOT_DURATION_TICKS
+                    && !state.validators[validator].is_crashed
+                    && !state.validators[validator].timed_out_slots.contains(&slot)
+                    && !state.network.iter().any(|m| m.msg == timeout)
+                {
+                    actions.push(LivenessAction::TriggerTimeout {
                         slot,
-                        proposer,
+                        validator,
                     });
                 }
             }
         }
 
-        // 3. Trigger timeouts for any slot
-        for slot in 1..=self.max_slot {
-            for validator in 0..self.validator_count {
-                actions.push(LivenessAction::TriggerTimeout {
-                    slot,
-                    validator,
-                });
+        // 4. Advance local clocks within the skew bound; under fairness, time waits for
+        //    arrived messages to the validator, global time waits for every arrived message,
+        //    and neither passes the deadline of a slot whose responsive leader has not proposed
+        let arrived: Vec<&MessageInTransit> = state.network.iter()
+            .chain(state.network_faults.delayed())
+            .filter(|m| m.deliver_at <= now)
+            .collect();
+        for (validator, validator_state) in state.validators.iter().enumerate() {
+            let next_clock = validator_state.local_clock + 1;
+            let fair = !self.fair(state) || (
+                !validator_state.is_crashed
+                    && !arrived.iter().any(|m| m.dst == validator || validator_state.local_clock == now)
+                    && (1..=self.max_slot)
+                        .filter(|slot| slot * SLOT_DURATION_TICKS <= next_clock)
+                        .all(|slot| state.block_proposals.contains_key(&slot) || !state.has_responsive_leader(slot))
+            );
+            if next_clock <= (self.max_slot + 1) * SLOT_DURATION_TICKS
+                && next_clock - now <= self.max_clock_skew
+                && fair
+            {
+                actions.push(LivenessAction::Tick { validator });
             }
         }
 
-        // 4. Advance to next slot
-        if state.current_slot < self.max_slot {
+        // 5. Advance to next slot; under fairness, only once its leader proposed or is known unresponsive
+        let current_slot_proposed = state.current_slot == 0
+            || state.block_proposals.contains_key(&state.current_slot)
+            || !state.has_responsive_leader(state.current_slot);
+        if state.current_slot < self.max_slot && (!self.fair(state) || current_slot_proposed) {
             actions.push(LivenessAction::AdvanceSlot);
         }
+
+        // 6. Crash responsive validators within the budget, and restart crashed ones
+        for (validator, validator_state) in state.validators.iter().enumerate() {
+            if validator_state.is_crashed {
+                actions.push(LivenessAction::Recover { validator });
+            } else if validator_state.is_responsive && state.crashes < self.crash_budget {
+                actions.push(LivenessAction::Crash { validator });
+            }
+        }
     }
 
     fn next_state(&self, last_state: &Self::State, action: Self::Action) -> Option<Self::State> {
@@ -232,45 +881,32 @@ impl Model for LivenessModel {
                 let block_hash = slot * 1000 + proposer as u64;
                 next_state.block_proposals.insert(slot, block_hash);
 
-                // Broadcast block proposal to all validators
+                // Broadcast block proposal to all validators, the proposer included
                 for i in 0..self.validator_count {
-                    if i != proposer {
-                        next_state.network.insert(MessageInTransit {
-                            dst: i,
-                            msg: LivenessMessage::BlockProposal {
-                                slot,
-                                hash: block_hash,
-                                proposer,
-                            },
-                        });
-                    }
+                    self.send(&mut next_state, proposer, i, LivenessMessage::BlockProposal {
+                        slot,
+                        hash: block_hash,
+                        proposer,
+                    });
                 }
             }
+            LivenessAction::NetworkFault { fault } => {
+                if !next_state.network_faults.apply(&mut next_state.network, fault) { return None; }
+            }
             LivenessAction::DeliverMessage { msg } => {
                 let recipient_id = msg.dst;
-                let mut validator_state = validators[recipient_id].clone();
+                let mut validator_state = validators.get(recipient_id)?.clone();
 
                 // Remove message from network
                 if !next_state.network.remove(&msg) { return None; }
+                next_state.network_faults.record_delivery(&msg);
 
                 match msg.msg {
                     LivenessMessage::BlockProposal { slot, hash, proposer: _ } => {
-                        // Validator receives block and can vote for it
-                        if validator_state.is_responsive && !validator_state.votes_cast.contains_key(&(slot, Some(hash))) {
-                            validator_state.votes_cast.insert((slot, Some(hash)), true);
-                            
-                            // Broadcast NotarVote
-                            for i in 0..self.validator_count {
-                                next_state.network.insert(MessageInTransit {
-                                    dst: i,
-                                    msg: LivenessMessage::NotarVote {
-                                        slot,
-                                        hash,
-                                        voter: recipient_id,
-                                    },
-                                });
-                            }
-                        }
+                        // Validator receives block and can vote for it unless it already timed out
+                        validator_state.proposal_received.insert(slot, next_state.min_local_clock());
+                        validator_state.blockstore.insert_block(slot, hash);
+                        self.vote_for_block(&mut next_state, &mut validator_state, recipient_id, slot, hash);
                     }
                     LivenessMessage::NotarVote { slot, hash, voter } => {
                         // Add vote to pool
@@ -279,63 +915,112 @@ impl Model for LivenessModel {
                         voters.insert(voter);
 
                         // Check for notarization
-                        if next_state.can_notarize(slot, hash) {
-                            validator_state.notarized_slots.insert(slot, hash);
+                        if next_state.can_notarize(&validator_state.vote_pool, slot, hash) {
+                            validator_state.notarize(slot, hash);
                             
                             // Check for fast finalization
-                            if next_state.can_fast_finalize(slot, hash) {
-                                validator_state.finalized_slots.insert(slot, hash);
-                                next_state.finalization_times.insert(slot, 1); // Fast path: 1 round
+                            if next_state.can_fast_finalize(&validator_state.vote_pool, slot, hash) {
+                                validator_state.finalize(slot, hash);
+                                next_state.record_finalization(recipient_id, &validator_state, slot, FinalizationPath::Fast);
                             } else {
-                                // Trigger FinalVote for slow path
-                                for i in 0..self.validator_count {
-                                    if validators[i].is_responsive {
-                                        next_state.network.insert(MessageInTransit {
-                                            dst: i,
-                                            msg: LivenessMessage::FinalVote {
-                                                slot,
-                                                voter: i,
-                                            },
-                                        });
-                                    }
-                                }
+                                // Cast and broadcast a FinalVote for the slow path
+                                self.cast_final_vote(&mut next_state, &mut validator_state, recipient_id, slot);
                             }
                         }
                     }
                     LivenessMessage::FinalVote { slot, voter } => {
-                        // Add FinalVote
-                        if validators[voter].is_responsive {
-                            validator_state.votes_cast.insert((slot, None), true);
-                            
-                            // Check for slow finalization
-                            if next_state.can_slow_finalize(slot) {
-                                if let Some(hash) = validator_state.notarized_slots.get(&slot) {
-                                    validator_state.finalized_slots.insert(slot, *hash);
-                                    next_state.finalization_times.insert(slot, 2); // Slow path: 2 rounds
-                                }
+                        // Add FinalVote to pool
+                        validator_state.vote_pool.entry((slot, None)).or_default().insert(voter);
+                    }
+                    LivenessMessage::TimeoutEvent { slot, validator: _ } => {
+                        // Timeout occurred - the validator no longer votes for this slot, and
+                        // signs a skip unless it already voted for the block
+                        let voted = validator_state.votes_cast.keys().any(|(s, hash)| *s == slot && hash.is_some());
+                        if validator_state.timed_out_slots.insert(slot) && !voted {
+                            self.sign(&mut next_state, &mut validator_state, recipient_id, slot, Ballot::Skip);
+                        }
+                    }
+                    LivenessMessage::CatchUpRequest { requester } => {
+                        // Online responsive peers repair from their blockstore: its certificates
+                        // and the blocks it holds that are not final yet
+                        if validator_state.is_responsive {
+                            let blockstore = &validator_state.blockstore;
+                            self.send(&mut next_state, recipient_id, requester, LivenessMessage::CatchUpResponse {
+                                finalized: blockstore.finalized().iter().map(|(s, h)| (*s, *h)).collect(),
+                                notarized: blockstore.notarized().iter().map(|(s, h)| (*s, *h)).collect(),
+                                blocks: blockstore.pending_blocks(),
+                            });
+                        }
+                    }
+                    LivenessMessage::CatchUpResponse { finalized, notarized, blocks } => {
+                        // Adopt the certificates, then resume voting on what is not final yet;
+                        // persisted vote records keep this from signing anything twice
+                        let now = next_state.min_local_clock();
+                        for (slot, hash) in finalized {
+                            validator_state.notarize(slot, hash);
+                            validator_state.finalize(slot, hash);
+                        }
+                        for (slot, hash) in blocks {
+                            if !validator_state.finalized_slots.contains_key(&slot) {
+                                validator_state.proposal_received.entry(slot).or_insert(now);
+                                validator_state.blockstore.insert_block(slot, hash);
+                                self.vote_for_block(&mut next_state, &mut validator_state, recipient_id, slot, hash);
+                            }
+                        }
+                        for (slot, hash) in notarized {
+                            if !validator_state.finalized_slots.contains_key(&slot) {
+                                validator_state.notarize(slot, hash);
+                                self.cast_final_vote(&mut next_state, &mut validator_state, recipient_id, slot);
                             }
                         }
                     }
-                    LivenessMessage::TimeoutEvent { slot: _, validator: _ } => {
-                        // Timeout occurred - this could trigger recovery mechanisms
-                        // For now, we just track it
+                }
+
+                // Check for slow finalization, whichever of notarization and FinalVotes came last
+                let notarized: Vec<(Slot, Hash)> = validator_state.notarized_slots.iter()
+                    .filter(|(slot, _)| !validator_state.finalized_slots.contains_key(slot))
+                    .map(|(slot, hash)| (*slot, *hash))
+                    .collect();
+                for (slot, hash) in notarized {
+                    if next_state.can_slow_finalize(&validator_state.vote_pool, slot) {
+                        validator_state.finalize(slot, hash);
+                        next_state.record_finalization(recipient_id, &validator_state, slot, FinalizationPath::Slow);
                     }
                 }
                 validators[recipient_id] = validator_state;
             }
             LivenessAction::TriggerTimeout { slot, validator } => {
-                // Trigger timeout event
+                // Trigger timeout event, local to the validator
+                let deliver_at = next_state.min_local_clock();
                 next_state.network.insert(MessageInTransit {
                     dst: validator,
                     msg: LivenessMessage::TimeoutEvent { slot, validator },
+                    deliver_at,
                 });
             }
+            LivenessAction::Tick { validator } => {
+                validators[validator].local_clock += 1;
+            }
             LivenessAction::AdvanceSlot => {
                 next_state.current_slot += 1;
                 for validator_state in &mut validators {
                     validator_state.current_slot = next_state.current_slot;
                 }
             }
+            LivenessAction::Crash { validator } => {
+                // Only the blockstore survives; the validator comes back with what it holds
+                let mut validator_state = validators[validator].restarted();
+                validator_state.is_crashed = true;
+                validators[validator] = validator_state;
+                next_state.crashes += 1;
+            }
+            LivenessAction::Recover { validator } => {
+                // Rejoin and repair from the blockstore of every peer
+                validators[validator].is_crashed = false;
+                for peer in (0..self.validator_count).filter(|peer| *peer != validator) {
+                    self.send(&mut next_state, validator, peer, LivenessMessage::CatchUpRequest { requester: validator });
+                }
+            }
         }
 
         next_state.validators = validators;
@@ -345,139 +1030,396 @@ impl Model for LivenessModel {
     /// Properties to verify in the liveness model
     fn properties(&self) -> Vec<Property<Self>> {
         vec![
-            // Property 1: Progress guarantee with sufficient responsive stake
-            Property::<Self>::always("progress_guarantee", |_model, state| {
-                // If we have >60% responsive stake, progress should be possible
-                let responsive_stake: Stake = state.validators.iter()
-                    .filter(|_v| _v.is_responsive)
-                    .map(|_v| state.stake_distribution.get(&0).unwrap_or(&0))
-                    .sum();
-                
-                if responsive_stake > (TOTAL_STAKE * SLOW_PATH_THRESHOLD_PERCENT / 100) {
-                    // Check if any slot has been finalized
-                    for _validator in &state.validators {
-                        if !_validator.finalized_slots.is_empty() {
-                            return true; // Progress achieved
-                        }
-                    }
-                }
-                true // If insufficient stake, no progress requirement
+            // Property 1: With >60% responsive stake every slot with a responsive leader is eventually finalized.
+            // Eventually-properties are checked on maximal executions, so they only hold
+            // when the model is restricted to fair ones. Slots begun during a partition are
+            // left to partition_heal_progress
+            Property::<Self>::eventually("progress_guarantee", |model, state| {
+                !state.can_progress() // No progress requirement
+                    || state.network_faults.messages_withheld()
+                    || state.unfinalized_slots(model.max_slot).iter().all(|slot| model.during_partition(*slot))
             }),
             
-            // Property 2: Fast path completion with >80% responsive stake
-            Property::<Self>::always("fast_path_completion", |_model, state| {
-                let responsive_stake: Stake = state.validators.iter()
-                    .filter(|_v| _v.is_responsive)
-                    .map(|_v| state.stake_distribution.get(&0).unwrap_or(&0))
-                    .sum();
-                
-                if responsive_stake >= (TOTAL_STAKE * FAST_PATH_THRESHOLD_PERCENT / 100) {
-                    // With 80%+ responsive stake, fast path should be achievable
-                    for _validator in &state.validators {
-                        for (_slot, _hash) in &_validator.finalized_slots {
-                            if let Some(finalization_time) = state.finalization_times.get(_slot) {
-                                if *finalization_time == 1 {
-                                    return true; // Fast path achieved
-                                }
-                            }
-                        }
-                    }
-                }
-                true // If insufficient stake, no fast path requirement
+            // Property 2: With >=80% responsive stake every slot eventually gathers a fast
+            // certificate, even where the slow path finalized it first
+            Property::<Self>::eventually("fast_path_completion", |model, state| {
+                !state.can_fast_path() // No fast path requirement
+                    || state.network_faults.messages_withheld()
+                    || (1..=model.max_slot)
+                        .filter(|slot| state.has_responsive_leader(*slot) && !model.during_partition(*slot))
+                        .all(|slot| state.fast_quorum_reached(slot))
             }),
             
-            // Property 3: Bounded finalization time
-            Property::<Self>::always("bounded_finalization", |_model, state| {
-                // Finalization time should be bounded (min(δ₈₀%, 2δ₆₀%))
-                for (_slot, finalization_time) in &state.finalization_times {
-                    if true { // Always check finalization time bounds
-                        // Finalization time should be at most 2 rounds
-                        if *finalization_time > 2 {
-                            return false;
-                        }
-                    }
-                }
-                true
+            // Property 3: Finalization takes at most min(δ₈₀%, 2δ₆₀%) once the block arrived,
+            // δθ being the delay within which votes from θ of the stake arrive
+            Property::<Self>::always("bounded_finalization", |model, state| {
+                state.check_finalization_latency(model)
             }),
             
-            // Property 4: Liveness under partial synchrony
-            Property::<Self>::always("liveness_partial_sync", |_model, state| {
-                // With >60% honest participation, liveness should be maintained
-                let honest_stake: Stake = state.validators.iter()
-                    .filter(|_v| _v.is_responsive)
-                    .map(|_v| state.stake_distribution.get(&0).unwrap_or(&0))
-                    .sum();
-                
-                if honest_stake > (TOTAL_STAKE * SLOW_PATH_THRESHOLD_PERCENT / 100) {
-                    // Should be able to make progress
-                    for slot in 1..=3 { // Fixed range for formal verification
-                        let mut has_progress = false;
-                        for _validator in &state.validators {
-                            if _validator.notarized_slots.contains_key(&slot) || 
-                               _validator.finalized_slots.contains_key(&slot) {
-                                has_progress = true;
-                                break;
-                            }
-                        }
-                        if !has_progress && slot <= state.current_slot {
-                            return false; // No progress made
-                        }
+            // Property 4: Liveness under partial synchrony: with more than 60% of the stake
+            // responsive, a quiet network leaves no proposed slot without progress
+            Property::<Self>::always("liveness_partial_sync", |model, state| {
+                !state.can_progress() || state.check_quiescent_progress(model)
+            }),
+
+            // Property 5: No responsive validator times out on a slot while its block, arriving
+            // early, is still in flight to it. Fair scheduling makes clocks wait for arrived
+            // messages, which keeps them short of the deadline only while skew is bounded
+            Property::<Self>::always("no_premature_skip", |model, state| {
+                state.check_no_premature_skip(model)
+            }),
+
+            // Property 6: Premature-skip freedom is not vacuous: a validator's clock passes the
+            // deadline of a slot whose block arrived early
+            Property::<Self>::sometimes("early_block_outlives_deadline", |_model, state| {
+                state.received_early_past_deadline()
+            }),
+
+            // Property 7: After GST, every slot is finalized within a bounded number of slots
+            Property::<Self>::always("bounded_post_gst_finalization", |model, state| {
+                match model.gst {
+                    Some(gst) if state.can_progress() => {
+                        let healed_at = model.partition.as_ref().map_or(gst, |p| gst.max(p.heal_at));
+                        state.check_post_gst_finalization(healed_at, model.max_slot)
                     }
+                    _ => true, // No synchrony assumed, or not enough responsive stake to progress
                 }
-                true
+            }),
+
+            // Property 8: A validator restarted after a crash never signs a second vote for a slot
+            Property::<Self>::always("no_double_vote", |_model, state| {
+                state.check_no_double_vote()
+            }),
+
+            // Property 9: Every window of a responsive leader eventually finalizes a block
+            Property::<Self>::eventually("leader_window_progress", |model, state| {
+                !state.can_progress()
+                    || state.network_faults.messages_withheld()
+                    || state.all_windows_finalized(model.max_slot, |slot| model.partition_may_skip(state, slot))
+            }),
+
+            // Property 10: Once a partition heals every slot is eventually finalized, except at
+            // most `partition_skip_bound` slots it may cost while up
+            Property::<Self>::eventually("partition_heal_progress", |model, state| {
+                model.partition.is_none()
+                    || !state.can_progress()
+                    || state.network_faults.messages_withheld()
+                    || state.unfinalized_slots(model.max_slot).iter().all(|slot| model.partition_may_skip(state, *slot))
+            }),
+
+            // Property 11: The model never gets stuck with a slot it could still finalize. Unlike
+            // the eventually-properties this also holds on unfair executions, where slots may be skipped
+            Property::<Self>::always("no_deadlock", |model, state| {
+                !model.is_deadlocked(state)
+            }),
+
+            // Property 12: Every finalization, and every vote if votes persist, reaches the
+            // blockstore, so a restart neither forgets a final block nor lets a vote be re-signed
+            Property::<Self>::always("blockstore_durable", |model, state| {
+                state.check_blockstore_durable(model.persist_votes)
             }),
         ]
     }
 }
 
 /// Run formal verification of liveness properties
-pub fn run_formal_verification() {
+pub fn run_formal_verification(options: &CheckOptions) {
     println!("=== Liveness Properties Formal Verification ===");
     
     let model = LivenessModel {
         validator_count: 4, // Small for formal verification
         max_slot: 3,
         responsive_count: 3, // 75% responsive (above 60% threshold)
+        max_clock_skew: CLOCK_SKEW_BOUND,
+        network_faults: 0,
+        fairness: true,
+        gst: None,
+        message_delays: Vec::new(),
+        crash_budget: 0,
+        persist_votes: true,
+        partition: None,
     };
 
     println!("Model checking liveness with {} validators ({} responsive), {} slots", 
              model.validator_count, model.responsive_count, model.max_slot);
     
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
+    options.print_summary();
+
+    let result = options
+        .checker(model)
         .spawn_dfs()
         .report(&mut stateright::report::WriteReporter::new(&mut std::io::stdout()));
     
     // Check verification results
-    if result.discoveries().is_empty() {
-        println!("✅ All liveness properties verified successfully");
-    } else {
-        println!("❌ Liveness verification found counterexamples");
-        for (property_name, _path) in result.discoveries() {
-            println!("  - {}", property_name);
-        }
+    match options.print_outcomes(&result) {
+        Outcome::Proved => println!("✅ All liveness properties verified successfully"),
+        Outcome::Violated => println!("❌ Liveness verification found counterexamples"),
+        Outcome::Inconclusive => println!("❓ Liveness verification inconclusive: a limit or bound stopped the search first"),
     }
+    options.record_results("liveness formal", &result);
 }
 
-/// Test liveness model with different configurations
-pub fn test_liveness_model(validators: usize, slots: u64, responsive: usize) {
-    println!("Testing liveness model with {} validators ({} responsive), {} slots", 
-             validators, responsive, slots);
+/// Test a liveness model configuration
+pub fn test_liveness_model(model: LivenessModel, options: &CheckOptions) {
+    println!("Testing liveness model with {} validators ({} responsive), {} slots, clock skew {}, GST {:?}, message delays {:?}, {} crashes, partition {:?}",
+             model.validator_count, model.responsive_count, model.max_slot, model.max_clock_skew,
+             model.gst, model.message_delays, model.crash_budget, model.partition);
+
+    // Past the skew bound an early block may lose the race against timeouts; only the
+    // safety properties are expected to hold
+    let mut options = options.clone();
+    if model.max_clock_skew > CLOCK_SKEW_BOUND {
+        println!("Clock skew exceeds the bound of {}: skipping no_premature_skip", CLOCK_SKEW_BOUND);
+        options.properties.skip.push("no_premature_skip".to_string());
+    }
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs();
+    options.progress.watch(&result);
+    let result = result.join();
     
+    println!("States explored: {}", result.state_count());
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("liveness {}v {}s {}r", result.model().model.validator_count, result.model().model.max_slot, result.model().model.responsive_count), &result);
+}
+
+/// Report which conditional liveness properties were actually exercised
+pub fn check_vacuity(validators: usize, slots: u64, responsive: usize, clock_skew: u64, threads: usize) -> vacuity::VacuityReport {
+    println!("Checking liveness vacuity with {} validators ({} responsive), {} slots, clock skew {}",
+             validators, responsive, slots, clock_skew);
+
     let model = LivenessModel {
         validator_count: validators,
         max_slot: slots,
         responsive_count: responsive,
+        max_clock_skew: clock_skew,
+        network_faults: 0,
+        fairness: true,
+        gst: None,
+        message_delays: Vec::new(),
+        crash_budget: 0,
+        persist_votes: true,
+        partition: None,
     };
 
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
-        .spawn_dfs();
-    
-    println!("States explored: {}", result.state_count());
-    println!("Properties verified: {}", result.discoveries().is_empty());
+    let report = vacuity::check_vacuity(model, LivenessModel::antecedents(), DEFAULT_VACUITY_STATE_COUNT, threads);
+    report.print_summary();
+    report
+}
+
+// --- Randomized Simulation ---
+
+const MAX_SIMULATION_STEPS: usize = 100_000;
+const DEFAULT_MAX_DELAY_TICKS: u64 = 1;
+
+/// Parameters of a single randomized run of the liveness model
+#[derive(Clone, Debug)]
+pub struct SimulationConfig {
+    /// Number of validators
+    pub validators: usize,
+    /// Slots to simulate
+    pub slots: Slot,
+    /// Number of responsive validators
+    pub responsive: usize,
+    /// Upper bound on each validator's message delay in ticks
+    pub max_delay: u64,
+    /// Seed for delay and scheduling choices
+    pub seed: u64,
+}
+
+impl SimulationConfig {
+    pub fn new(validators: usize, slots: Slot, responsive: usize, seed: u64) -> Self {
+        Self { validators, slots, responsive, max_delay: DEFAULT_MAX_DELAY_TICKS, seed }
+    }
+
+    /// Fair model with a random message delay per validator
+    fn model(&self, rng: &mut StdRng) -> LivenessModel {
+        LivenessModel {
+            validator_count: self.validators,
+            max_slot: self.slots,
+            responsive_count: self.responsive,
+            max_clock_skew: CLOCK_SKEW_BOUND,
+            network_faults: 0,
+            fairness: true,
+            gst: None,
+            message_delays: (0..self.validators).map(|_| rng.gen_range(0..=self.max_delay)).collect(),
+            crash_budget: 0,
+            persist_votes: true,
+            partition: None,
+        }
+    }
+}
+
+/// Final state and observations of a simulation run
+#[derive(Clone, Debug)]
+pub struct SimulationOutcome {
+    /// State reached when no action was left
+    pub state: LivenessState,
+    /// Slots of the run
+    pub slots: Slot,
+    /// Responsive stake as a percentage of the total
+    pub responsive_percent: u64,
+    /// Model actions taken
+    pub steps: usize,
+    /// Safety properties violated along the run
+    pub violations: Vec<&'static str>,
+}
+
+impl SimulationOutcome {
+    /// Earliest finalization of each slot across validators, the fast path winning ties
+    pub fn slot_latencies(&self) -> BTreeMap<Slot, Finalization> {
+        let mut latencies: BTreeMap<Slot, Finalization> = BTreeMap::new();
+        for ((slot, _), finalization) in &self.state.finalizations {
+            let earliest = latencies.entry(*slot).or_insert(*finalization);
+            if (finalization.finalized_at, finalization.path) < (earliest.finalized_at, earliest.path) {
+                *earliest = *finalization;
+            }
+        }
+        latencies
+    }
+
+    /// Earliest finalization of a slot through `path` across validators
+    pub fn earliest_finalization(&self, slot: Slot, path: FinalizationPath) -> Option<Finalization> {
+        self.state.finalizations.iter()
+            .filter(|((s, _), f)| *s == slot && f.path == path)
+            .map(|(_, f)| *f)
+            .min_by_key(|f| f.finalized_at)
+    }
+
+    /// Whether some validator formed a fast-finalization certificate for `slot`, even if the
+    /// slow path finalized the slot first
+    pub fn fast_certificate_formed(&self, slot: Slot) -> bool {
+        self.state.fast_quorum_reached(slot)
+    }
+
+    /// Slots whose scheduled leader is responsive, the ones expected to finalize
+    pub fn proposed_slots(&self) -> Vec<Slot> {
+        (1..=self.slots).filter(|slot| self.state.has_responsive_leader(*slot)).collect()
+    }
+
+    fn print_summary(&self) {
+        println!("Responsive stake {}%, steps: {}, simulated time: {} ticks",
+                 self.responsive_percent, self.steps, self.state.min_local_clock());
+        let latencies = self.slot_latencies();
+        for slot in 1..=self.slots {
+            match latencies.get(&slot) {
+                Some(f) => println!("  Slot {}: {} path, latency {} ticks", slot, f.path.label(), f.latency()),
+                None => println!("  Slot {}: not finalized", slot),
+            }
+        }
+        for property_name in &self.violations {
+            println!("  ❌ Violated: {}", property_name);
+        }
+    }
+}
+
+/// Drive the liveness model with random scheduling among its fairly enabled actions.
+///
+/// Each validator's messages take a random delay of at most `max_delay` ticks, and the
+/// run ends once no action is left. The model's safety properties are checked after
+/// every step.
+pub fn simulate(config: &SimulationConfig) -> SimulationOutcome {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let model = config.model(&mut rng);
+    let properties = model.properties();
+
+    let mut state = model.init_states().remove(0);
+    let mut violations = Vec::new();
+    let mut steps = 0;
+    while steps < MAX_SIMULATION_STEPS {
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        let Some(action) = actions.choose(&mut rng).cloned() else { break };
+        state = match model.next_state(&state, action) {
+            Some(next_state) => next_state,
+            None => break,
+        };
+        steps += 1;
+
+        for property in &properties {
+            if property.expectation == Expectation::Always
+                && !(property.condition)(&model, &state)
+                && !violations.contains(&property.name)
+            {
+                violations.push(property.name);
+            }
+        }
+    }
+
+    let responsive_percent = state.responsive_stake() * 100 / state.total_stake();
+    SimulationOutcome { state, slots: config.slots, responsive_percent, steps, violations }
+}
+
+/// One CSV row per simulated slot: responsive stake, slot, path and latency in ticks,
+/// with the last two empty for slots that were not finalized
+pub fn latency_csv(outcomes: &[SimulationOutcome]) -> String {
+    let mut csv = String::from("responsive_stake_percent,slot,path,latency_ticks\n");
+    for outcome in outcomes {
+        let latencies = outcome.slot_latencies();
+        for slot in 1..=outcome.slots {
+            match latencies.get(&slot) {
+                Some(f) => csv.push_str(&format!("{},{},{},{}\n", outcome.responsive_percent, slot, f.path.label(), f.latency())),
+                None => csv.push_str(&format!("{},{},,\n", outcome.responsive_percent, slot)),
+            }
+        }
+    }
+    csv
+}
+
+/// Finalized slots counted by responsive stake, path and latency in ticks
+pub fn latency_histogram(outcomes: &[SimulationOutcome]) -> BTreeMap<(u64, FinalizationPath, u64), usize> {
+    let mut histogram = BTreeMap::new();
+    for outcome in outcomes {
+        for finalization in outcome.slot_latencies().values() {
+            *histogram.entry((outcome.responsive_percent, finalization.path, finalization.latency())).or_insert(0) += 1;
+        }
+    }
+    histogram
+}
+
+/// Simulate every responsive stake level above the slow-path threshold `runs` times and
+/// report the time-to-finality distribution per level and path, writing the raw latencies
+/// as CSV to `csv_path` if given. Every measured latency must stay within min(δ80, 2δ60)
+pub fn run_latency_study(validators: usize, slots: Slot, runs: u64, seed: u64, csv_path: Option<&str>) -> bool {
+    println!("Simulating time to finality: {} validators, {} slots, {} runs per responsive stake level, seed {}",
+             validators, slots, runs, seed);
+
+    let mut outcomes = Vec::new();
+    for responsive in 1..=validators {
+        if responsive as u64 * 100 <= validators as u64 * SLOW_PATH_THRESHOLD_PERCENT {
+            continue; // No progress expected
+        }
+        let mut config = SimulationConfig::new(validators, slots, responsive, seed);
+        for run in 0..runs {
+            config.seed = seed + run;
+            outcomes.push(simulate(&config));
+        }
+    }
+    if let Some(outcome) = outcomes.last() {
+        outcome.print_summary();
+    }
+
+    println!("Latency histogram (responsive stake, path: latency in ticks):");
+    for ((percent, path, latency), count) in latency_histogram(&outcomes) {
+        println!("  {:>3}% {} {:>3}: {} {}", percent, path.label(), latency, "#".repeat(count.min(60)), count);
+    }
+
+    let csv = latency_csv(&outcomes);
+    match csv_path {
+        Some(path) => match std::fs::write(path, &csv) {
+            Ok(()) => println!("Wrote {} latency rows to {}", csv.lines().count() - 1, path),
+            Err(e) => {
+                println!("Failed to write {}: {}", path, e);
+                return false;
+            }
+        },
+        None => print!("{}", csv),
+    }
+
+    outcomes.iter().all(|outcome| !outcome.violations.contains(&"bounded_finalization"))
 }
 
 #[cfg(test)]
@@ -488,8 +1430,8 @@ mod tests {
     fn test_liveness_state_creation() {
         let state = LivenessState::new(3, 2);
         assert_eq!(state.validators.len(), 3);
-        assert_eq!(state.validators[0].is_responsive, true);
-        assert_eq!(state.validators[2].is_responsive, false);
+        assert!(state.validators[0].is_responsive);
+        assert!(!state.validators[2].is_responsive);
     }
 
     #[test]
@@ -503,7 +1445,8 @@ mod tests {
         voters.insert(2); // 3/3 validators = 100% > 60%
         state.validators[0] = validator;
         
-        assert!(state.can_notarize(1, 100));
+        assert!(state.can_notarize(&state.validators[0].vote_pool, 1, 100));
+        assert!(!state.can_notarize(&state.validators[1].vote_pool, 1, 100));
     }
 
     #[test]
@@ -517,6 +1460,488 @@ mod tests {
         voters.insert(2); // 3/3 validators = 100% > 80%
         state.validators[0] = validator;
         
-        assert!(state.can_fast_finalize(1, 100));
+        assert!(state.can_fast_finalize(&state.validators[0].vote_pool, 1, 100));
+    }
+
+    #[test]
+    fn test_unequal_stake_accounting() {
+        let mut state = LivenessState::new(3, 3);
+        state.stake_distribution = BTreeMap::from([(0, 700), (1, 200), (2, 100)]);
+        let pool = |voters: &[ActorId]| {
+            let voters: VoterSet = voters.iter().copied().collect();
+            BTreeMap::from([((1, Some(1000)), voters.clone()), ((1, None), voters)])
+        };
+
+        // Each voter counts with its own stake: the heavy validator alone clears 60%,
+        // the two light ones together do not
+        assert!(state.can_notarize(&pool(&[0]), 1, 1000));
+        assert!(state.can_slow_finalize(&pool(&[0]), 1));
+        assert!(!state.can_notarize(&pool(&[1, 2]), 1, 1000));
+        assert!(!state.can_slow_finalize(&pool(&[1, 2]), 1));
+        assert!(!state.can_fast_finalize(&pool(&[0]), 1, 1000));
+        assert!(state.can_fast_finalize(&pool(&[0, 1]), 1, 1000));
+        assert!(state.is_partition_critical(&BTreeSet::from([0])));
+        assert!(!state.is_partition_critical(&BTreeSet::from([1, 2])));
+
+        // Responsiveness is weighed by stake, not by head count
+        state.validators[0].is_responsive = false;
+        assert!(!state.can_progress());
+        state.validators[0].is_responsive = true;
+        state.validators[2].is_responsive = false;
+        assert!(state.can_progress() && state.can_fast_path());
+    }
+
+    #[test]
+    fn test_timed_out_validator_does_not_vote() {
+        let model = LivenessModel {
+            validator_count: 3,
+            max_slot: 2,
+            responsive_count: 3,
+            max_clock_skew: CLOCK_SKEW_BOUND,
+            network_faults: 0,
+            fairness: true,
+            gst: None,
+            message_delays: Vec::new(),
+            crash_budget: 0,
+            persist_votes: true,
+            partition: None,
+        };
+        let mut state = LivenessState::new(3, 3);
+        state.validators[1].timed_out_slots.insert(1);
+        let msg = MessageInTransit {
+            dst: 1,
+            msg: LivenessMessage::BlockProposal { slot: 1, hash: 1000, proposer: 0 },
+            deliver_at: 0,
+        };
+        state.network.insert(msg.clone());
+
+        let next = model.next_state(&state, LivenessAction::DeliverMessage { msg }).unwrap();
+        assert!(next.validators[1].votes_cast.is_empty());
+        assert!(next.network.is_empty());
+    }
+
+    #[test]
+    fn test_vacuity_detection() {
+        let model = |responsive_count| LivenessModel {
+            validator_count: 4,
+            max_slot: 1,
+            responsive_count,
+            max_clock_skew: CLOCK_SKEW_BOUND,
+            network_faults: 0,
+            fairness: true,
+            gst: None,
+            message_delays: Vec::new(),
+            crash_budget: 0,
+            persist_votes: true,
+            partition: None,
+        };
+
+        // 75% responsive stake never meets the fast path threshold
+        let report = vacuity::check_vacuity(model(3), LivenessModel::antecedents(), 1_000, num_cpus::get());
+        assert!(report.vacuous().contains(&"fast_path_completion"));
+        assert!(report.is_exercised("progress_guarantee"));
+
+        let report = vacuity::check_vacuity(model(4), LivenessModel::antecedents(), 1_000, num_cpus::get());
+        assert!(report.is_exercised("fast_path_completion"));
+    }
+
+    #[test]
+    fn test_eventual_progress_under_fairness() {
+        let model = |validator_count, responsive_count, fairness| LivenessModel {
+            validator_count,
+            max_slot: 1,
+            responsive_count,
+            max_clock_skew: CLOCK_SKEW_BOUND,
+            network_faults: 0,
+            fairness,
+            gst: None,
+            message_delays: Vec::new(),
+            crash_budget: 0,
+            persist_votes: true,
+            partition: None,
+        };
+
+        // Fair executions always finalize: on the fast path with every validator responsive,
+        // and on the slow path with two of three. The searches run to completion, so no
+        // counterexample means none exists in these models
+        let checker = model(2, 2, true).checker().spawn_dfs().join();
+        assert!(checker.is_done());
+        assert!(checker.discovery("progress_guarantee").is_none());
+        assert!(checker.discovery("fast_path_completion").is_none());
+        let checker = model(3, 2, true).checker().spawn_dfs().join();
+        assert!(checker.is_done());
+        assert!(checker.discovery("progress_guarantee").is_none());
+
+        // Without fairness, timeouts may fire before the proposal arrives and nothing finalizes
+        let checker = model(2, 2, false).checker().spawn_dfs().join();
+        assert!(checker.is_done());
+        assert!(checker.discovery("progress_guarantee").is_some());
+    }
+
+    #[test]
+    fn test_global_stabilization_time() {
+        let model = |network_faults, gst| LivenessModel {
+            validator_count: 2,
+            max_slot: 2,
+            responsive_count: 2,
+            max_clock_skew: CLOCK_SKEW_BOUND,
+            network_faults,
+            fairness: false,
+            gst: Some(gst),
+            message_delays: Vec::new(),
+            crash_budget: 0,
+            persist_votes: true,
+            partition: None,
+        };
+
+        // Before GST the adversary may drop the first proposal; from GST on it may not
+        let model_under_test = model(1, 2);
+        let mut state = model_under_test.init_states().remove(0);
+        state = model_under_test.next_state(&state, LivenessAction::ProposeBlock { slot: 1, proposer: 0 }).unwrap();
+        let mut actions = Vec::new();
+        model_under_test.actions(&state, &mut actions);
+        assert!(actions.iter().any(|a| matches!(a, LivenessAction::NetworkFault { fault: NetworkFault::Drop(_) })));
+        for validator in [0, 1, 0, 1] {
+            state.validators[validator].local_clock += 1;
+        }
+        let mut actions = Vec::new();
+        model_under_test.actions(&state, &mut actions);
+        assert!(!actions.iter().any(|a| matches!(a, LivenessAction::NetworkFault { .. })));
+
+        // Slot 2 starts after a GST at the first tick, so it must be final once every clock is a
+        // slot past its deadline, however the execution was scheduled before. Network faults
+        // would grow the search past what completes, so it only leaves the schedule to the adversary
+        let checker = model(0, 1).checker().spawn_dfs().join();
+        assert!(checker.is_done());
+        assert!(checker.discovery("bounded_post_gst_finalization").is_none());
+    }
+
+    #[test]
+    fn test_finalization_latency_bound() {
+        let model = LivenessModel {
+            validator_count: 3,
+            max_slot: 1,
+            responsive_count: 3,
+            max_clock_skew: CLOCK_SKEW_BOUND,
+            network_faults: 0,
+            fairness: true,
+            gst: None,
+            message_delays: vec![0, 0, 2],
+            crash_budget: 0,
+            persist_votes: true,
+            partition: None,
+        };
+
+        // Votes from two thirds of the stake arrive at once, the last third two ticks later:
+        // δ₆₀% = 0 and δ₈₀% = 2, so the block must be slow-finalized as soon as it arrives
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, LivenessAction::ProposeBlock { slot: 1, proposer: 0 }).unwrap();
+        loop {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            let next = actions.iter()
+                .find(|a| matches!(a, LivenessAction::DeliverMessage { .. }))
+                .or_else(|| actions.iter().find(|a| matches!(a, LivenessAction::Tick { .. })));
+            match next {
+                Some(action) => state = model.next_state(&state, action.clone()).unwrap(),
+                None => break,
+            }
+        }
+        assert_eq!(state.finalization_bound(&model, 1, 1000), 0);
+        for validator in 0..3 {
+            let finalization = state.finalizations[&(1, validator)];
+            assert_eq!(finalization.path, FinalizationPath::Slow);
+            assert_eq!(finalization.latency(), 0);
+        }
+
+        let checker = model.checker().spawn_bfs().join();
+        assert!(checker.discovery("bounded_finalization").is_none());
+    }
+
+    #[test]
+    fn test_premature_skip_needs_bounded_skew() {
+        let model = |max_clock_skew| LivenessModel {
+            validator_count: 2,
+            max_slot: 1,
+            responsive_count: 2,
+            max_clock_skew,
+            network_faults: 0,
+            fairness: true,
+            gst: None,
+            message_delays: vec![1, 1],
+            crash_budget: 0,
+            persist_votes: true,
+            partition: None,
+        };
+
+        let checker = model(CLOCK_SKEW_BOUND).checker().spawn_bfs().join();
+        assert!(checker.is_done());
+        assert!(checker.discovery("no_premature_skip").is_none());
+        assert!(checker.discovery("early_block_outlives_deadline").is_some());
+
+        // One tick more lets the faster clock time out while the block is still on its way
+        let checker = model(CLOCK_SKEW_BOUND + 1).checker().spawn_bfs().join();
+        assert!(checker.is_done());
+        assert!(checker.discovery("no_premature_skip").is_some());
+        assert!(checker.discovery("no_double_vote").is_none());
+    }
+
+    #[test]
+    fn test_crash_recovery() {
+        let model = |validator_count, crash_budget, persist_votes| LivenessModel {
+            validator_count,
+            max_slot: 1,
+            responsive_count: validator_count,
+            max_clock_skew: CLOCK_SKEW_BOUND,
+            network_faults: 0,
+            fairness: true,
+            gst: None,
+            message_delays: Vec::new(),
+            crash_budget,
+            persist_votes,
+            partition: None,
+        };
+
+        // With two of three validators down nothing finalizes; once one recovers, the
+        // responsive stake is back above 60% and the slot finalizes
+        let model_under_test = model(3, 2, true);
+        let mut state = model_under_test.init_states().remove(0);
+        for action in [
+            LivenessAction::Crash { validator: 1 },
+            LivenessAction::Crash { validator: 2 },
+            LivenessAction::ProposeBlock { slot: 1, proposer: 0 },
+        ] {
+            state = model_under_test.next_state(&state, action).unwrap();
+        }
+        let deliver_all = |mut state: LivenessState| loop {
+            let mut actions = Vec::new();
+            model_under_test.actions(&state, &mut actions);
+            match actions.into_iter().find(|a| matches!(a, LivenessAction::DeliverMessage { .. })) {
+                Some(action) => state = model_under_test.next_state(&state, action).unwrap(),
+                None => return state,
+            }
+        };
+        state = deliver_all(state);
+        assert!(!state.unfinalized_slots(1).is_empty());
+        state = model_under_test.next_state(&state, LivenessAction::Recover { validator: 1 }).unwrap();
+        state = deliver_all(state);
+        assert!(state.unfinalized_slots(1).is_empty());
+
+        // Persisted vote records keep restarted validators from signing twice, and fair
+        // executions recover and finalize. Two validators, either of which may crash, keep
+        // the searches complete
+        let checker = model(2, 1, true).checker().spawn_dfs().join();
+        assert!(checker.is_done());
+        assert!(checker.discovery("no_double_vote").is_none());
+        assert!(checker.discovery("progress_guarantee").is_none());
+        assert!(checker.discovery("blockstore_durable").is_none());
+
+        // A validator that forgets its votes re-signs a block it catches up on
+        let checker = model(2, 1, false).checker().spawn_bfs().join();
+        assert!(checker.is_done());
+        assert!(checker.discovery("no_double_vote").is_some());
+    }
+
+    #[test]
+    fn test_catch_up_restores_quorum() {
+        let model = LivenessModel {
+            validator_count: 4,
+            max_slot: 1,
+            responsive_count: 3,
+            max_clock_skew: CLOCK_SKEW_BOUND,
+            network_faults: 0,
+            fairness: true,
+            gst: None,
+            message_delays: Vec::new(),
+            crash_budget: 1,
+            persist_votes: true,
+            partition: None,
+        };
+        let message = |dst, msg| MessageInTransit { dst, msg, deliver_at: 0 };
+        let deliver_all = |mut state: LivenessState| loop {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            match actions.into_iter().find(|a| matches!(a, LivenessAction::DeliverMessage { .. })) {
+                Some(action) => state = model.next_state(&state, action).unwrap(),
+                None => return state,
+            }
+        };
+
+        // Validator 1 crashes after seeing half the stake vote, so its own pool never
+        // notarizes and the other two FinalVotes fall short of 60%
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, LivenessAction::ProposeBlock { slot: 1, proposer: 0 }).unwrap();
+        for dst in 0..4 {
+            let msg = message(dst, LivenessMessage::BlockProposal { slot: 1, hash: 1000, proposer: 0 });
+            state = model.next_state(&state, LivenessAction::DeliverMessage { msg }).unwrap();
+        }
+        for voter in [0, 1] {
+            let msg = message(1, LivenessMessage::NotarVote { slot: 1, hash: 1000, voter });
+            state = model.next_state(&state, LivenessAction::DeliverMessage { msg }).unwrap();
+        }
+        state = model.next_state(&state, LivenessAction::Crash { validator: 1 }).unwrap();
+        // Its vote pool is gone, but its vote and the block are read back from its blockstore
+        let restarted = &state.validators[1];
+        assert!(restarted.vote_pool.is_empty() && restarted.votes_cast.contains_key(&(1, Some(1000))));
+        assert_eq!(restarted.blockstore.pending_blocks(), vec![(1, 1000)]);
+        state = deliver_all(state);
+        assert!(!state.is_finalized(1));
+
+        // Catching up on the notarization certificate lets it cast the missing FinalVote
+        state = model.next_state(&state, LivenessAction::Recover { validator: 1 }).unwrap();
+        state = deliver_all(state);
+        assert!((0..3).all(|v| state.validators[v].finalized_slots.get(&1) == Some(&1000)));
+        assert_eq!(state.ballots[&(1, 1)], vec![Ballot::Notar(1000), Ballot::Final]);
+        assert!(state.check_no_double_vote());
+    }
+
+    #[test]
+    fn test_partition_heal() {
+        let model = |isolated: &[ActorId], heal_at, max_slot| LivenessModel {
+            validator_count: 3,
+            max_slot,
+            responsive_count: 3,
+            max_clock_skew: CLOCK_SKEW_BOUND,
+            network_faults: 0,
+            fairness: true,
+            gst: None,
+            message_delays: Vec::new(),
+            crash_budget: 0,
+            persist_votes: true,
+            partition: Some(PartitionSchedule { isolated: isolated.iter().copied().collect(), heal_at }),
+        };
+
+        // Isolating the leader of the first window costs at most its two slots; the slot
+        // begun after the heal is finalized
+        let model_under_test = model(&[0], 4, 3);
+        let mut state = model_under_test.init_states().remove(0);
+        assert_eq!(model_under_test.partition_skip_bound(&state), 2);
+        loop {
+            let mut actions = Vec::new();
+            model_under_test.actions(&state, &mut actions);
+            let priorities: [fn(&LivenessAction) -> bool; 5] = [
+                |a| matches!(a, LivenessAction::DeliverMessage { .. }),
+                |a| matches!(a, LivenessAction::ProposeBlock { .. }),
+                |a| matches!(a, LivenessAction::AdvanceSlot),
+                |a| matches!(a, LivenessAction::TriggerTimeout { .. }),
+                |a| matches!(a, LivenessAction::Tick { .. }),
+            ];
+            let next = priorities.iter().find_map(|is_next| actions.iter().find(|a| is_next(a)));
+            match next {
+                Some(action) => state = model_under_test.next_state(&state, action.clone()).unwrap(),
+                None => break,
+            }
+        }
+        let unfinalized = state.unfinalized_slots(3);
+        assert!(unfinalized.contains(&1) && unfinalized.iter().all(|slot| *slot <= 2));
+        assert!(state.is_finalized(3));
+
+        // A non-critical partition of a follower costs nothing, a critical one every slot it overlaps
+        let state = model(&[2], 2, 1).init_states().remove(0);
+        assert_eq!(model(&[2], 2, 1).partition_skip_bound(&state), 0);
+        assert_eq!(model(&[1, 2], 2, 1).partition_skip_bound(&state), 1);
+        let checker = model(&[2], 2, 1).checker().spawn_dfs().join();
+        assert!(checker.is_done());
+        assert!(checker.discovery("partition_heal_progress").is_none());
+        assert!(checker.discovery("progress_guarantee").is_none());
+    }
+
+    #[test]
+    fn test_deadlock_detection() {
+        let model = |validator_count, fairness| LivenessModel {
+            validator_count,
+            max_slot: 1,
+            responsive_count: validator_count,
+            max_clock_skew: CLOCK_SKEW_BOUND,
+            network_faults: 0,
+            fairness,
+            gst: None,
+            message_delays: Vec::new(),
+            crash_budget: 0,
+            persist_votes: true,
+            partition: None,
+        };
+
+        // Votes that vanish without the adversary leave a slot nobody skipped and nothing to do
+        let mut state = model(3, true).init_states().remove(0);
+        state.current_slot = 1;
+        state.block_proposals.insert(1, 1000);
+        for validator in 0..3 {
+            state.validators[validator].local_clock = 2 * SLOT_DURATION_TICKS;
+            state.validators[validator].votes_cast.insert((1, Some(1000)), true);
+            state.validators[validator].timed_out_slots.insert(1);
+            state.record_ballot(validator, 1, Ballot::Notar(1000));
+        }
+        assert!(model(3, true).is_deadlocked(&state));
+
+        // Skipped slots are resolved: the model is stuck on no execution, fair or not. Two
+        // validators keep the unfair search complete
+        for fairness in [true, false] {
+            let checker = model(2, fairness).checker().spawn_dfs().join();
+            assert!(checker.is_done());
+            assert!(checker.discovery("no_deadlock").is_none());
+        }
+    }
+
+    #[test]
+    fn test_leader_schedule() {
+        let model = LivenessModel {
+            validator_count: 3,
+            max_slot: 3,
+            responsive_count: 2,
+            max_clock_skew: CLOCK_SKEW_BOUND,
+            network_faults: 0,
+            fairness: true,
+            gst: None,
+            message_delays: Vec::new(),
+            crash_budget: 0,
+            persist_votes: true,
+            partition: None,
+        };
+
+        // Validator 0 leads the first window; the unresponsive validator 2 leads slot 3
+        let mut state = model.init_states().remove(0);
+        assert_eq!((state.leader(1), state.leader(2), state.leader(3)), (0, 0, 2));
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(actions.iter().all(|a| !matches!(a, LivenessAction::ProposeBlock { proposer, .. } if *proposer != 0)));
+
+        // A fair schedule finalizes the responsive leader's window and skips the other
+        loop {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            let priorities: [fn(&LivenessAction) -> bool; 4] = [
+                |a| matches!(a, LivenessAction::DeliverMessage { .. }),
+                |a| matches!(a, LivenessAction::ProposeBlock { .. }),
+                |a| matches!(a, LivenessAction::AdvanceSlot),
+                |a| matches!(a, LivenessAction::Tick { .. }),
+            ];
+            let next = priorities.iter().find_map(|is_next| actions.iter().find(|a| is_next(a)));
+            match next {
+                Some(action) => state = model.next_state(&state, action.clone()).unwrap(),
+                None => break,
+            }
+        }
+        assert!(state.is_finalized(1) && state.is_finalized(2));
+        assert!(!state.block_proposals.contains_key(&3));
+        assert!(state.all_windows_finalized(3, |_| false));
+    }
+
+    #[test]
+    fn test_latency_simulation() {
+        // Without delays every slot is final at the instant its block arrives
+        let mut config = SimulationConfig::new(5, 2, 5, 7);
+        config.max_delay = 0;
+        let outcome = simulate(&config);
+        assert!(!outcome.violations.contains(&"bounded_finalization"));
+        let latencies = outcome.slot_latencies();
+        assert!((1..=2).all(|slot| latencies[&slot].latency() == 0));
+
+        let csv = latency_csv(std::slice::from_ref(&outcome));
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], "responsive_stake_percent,slot,path,latency_ticks");
+        assert_eq!(rows.len(), 3);
+        assert!(rows[1].starts_with("100,1,") && rows[1].ends_with(",0"));
+        assert_eq!(latency_histogram(&[outcome]).values().sum::<usize>(), 2);
     }
 }
@@ -2,15 +2,38 @@
 //! This module provides a Stateright-based formal model for verifying Byzantine fault tolerance,
 //! safety under adversarial conditions, and network partition recovery.
 
-use stateright::{Model, Property, Checker};
+use clap::ValueEnum;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use stateright::{Model, Property, Checker, Expectation};
 use std::collections::{BTreeMap, BTreeSet};
+use crate::network::{NetworkFault, NetworkFaults};
+use crate::invariants::{self, FinalizedHistory, FinalizedRecord};
+use crate::vacuity::{self, Antecedent, DEFAULT_VACUITY_STATE_COUNT};
+use crate::voters::VoterSet;
+use crate::shared::SharedVec;
+use crate::bounds::Bounded;
+use crate::limits::Outcome;
+use crate::options::CheckOptions;
+use crate::hybrid::{self, HybridConfig, Verdict};
 
 // --- Formal Model Configuration ---
 const CERTIFICATE_THRESHOLD_PERCENT: u64 = 60;
 const TOTAL_STAKE: u64 = 1000;
-const MAX_SLOTS: u64 = 5; // Formal verification limit
-const MAX_VALIDATORS: usize = 5; // Formal verification limit
-const MAX_BYZANTINE: usize = 1; // Formal verification limit
+pub const DEFAULT_OFFLINE_BUDGET_PERCENT: u64 = 20; // Stake that may be non-responsive at once
+pub const DEFAULT_CORRUPTION_BUDGET_PERCENT: u64 = 20; // Stake the adversary may control in total
+pub const DEFAULT_CRASH_BUDGET_PERCENT: u64 = 0; // Stake that may crash for good
+// The whitepaper's "20+20" resilience claim: safety with up to 20% Byzantine stake, and
+// liveness with an additional 20% of the stake crashed
+pub const WHITEPAPER_BYZANTINE_PERCENT: u64 = 20;
+pub const WHITEPAPER_CRASHED_PERCENT: u64 = 20;
+pub const DEFAULT_MAX_PARTITION_SIZE: usize = 2; // Largest validator subset a partition cuts off
+pub const DEFAULT_POOL_CAPACITY: usize = 4; // Votes a pool keeps per voter; honest validators need one per slot
+pub const DEFAULT_FLOOD_BUDGET: usize = 0; // Vote floods the adversary may send
+const FLOOD_HASHES_PER_SLOT: u64 = 2; // Spurious hashes per slot in one flood
+pub const DEFAULT_POSTERIOR_CORRUPTION_PERCENT: u64 = 0; // Stake that may unstake and leak its old keys
+pub const DEFAULT_RECOVERY_ROUNDS: u64 = 8; // Delivery rounds after a heal before every proposal must be certified
 
 // Type aliases for clarity
 type Slot = u64;
@@ -43,19 +66,24 @@ pub enum ResilienceMessage {
     PartitionEvent {
         partition_id: u64,
         affected_validators: BTreeSet<ActorId>,
+        two_sided: bool,
     },
     /// A recovery message
     RecoveryMessage {
         slot: Slot,
         validator: ActorId,
     },
+    /// A validator rejoining after a partition asks its peers for their votes
+    SyncRequest { requester: ActorId },
+    /// A peer's vote pool, from which the requester rebuilds any certificates it missed
+    SyncResponse { votes: BTreeMap<(Slot, Hash), VoterSet> },
 }
 
 /// Represents messages in transit
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct MessageInTransit {
-    dst: ActorId,
-    msg: ResilienceMessage,
+    pub(crate) dst: ActorId,
+    pub(crate) msg: ResilienceMessage,
 }
 
 /// Actions that can be taken in the resilience model
@@ -73,17 +101,49 @@ pub enum ResilienceAction {
         slot: Slot,
         byzantine_validator: ActorId,
     },
-    /// Trigger network partition
+    /// Trigger network partition: either cut the affected validators off from everyone, or
+    /// (two-sided) split the network into the affected validators and the rest
     TriggerPartition {
         partition_id: u64,
         affected_validators: BTreeSet<ActorId>,
+        two_sided: bool,
     },
     /// Recover from partition
     RecoverFromPartition {
         partition_id: u64,
     },
+    /// An honest validator temporarily loses connectivity
+    GoOffline { validator: ActorId },
+    /// An offline validator becomes responsive again
+    ComeOnline { validator: ActorId },
+    /// The adversary corrupts an honest validator, which is Byzantine from then on
+    CorruptValidator { id: ActorId },
+    /// A Byzantine validator votes for the proposed block towards one subset of the honest
+    /// validators and for a conflicting block towards the rest (split-brain attempt)
+    SplitVote {
+        byzantine_validator: ActorId,
+        slot: Slot,
+        left: BTreeSet<ActorId>,
+    },
+    /// An honest validator unstakes once everything it voted for is finalized; its old keys may leak later
+    Retire { validator: ActorId },
+    /// The adversary signs votes for an alternative block in an old slot with leaked keys of retired validators
+    ForgeHistory { slot: Slot },
+    /// A Byzantine validator floods a validator with spurious votes for many slots and hashes
+    FloodVotes {
+        byzantine_validator: ActorId,
+        target: ActorId,
+    },
+    /// An honest validator crashes and never comes back
+    Crash { validator: ActorId },
+    /// A whole failure domain loses power or connectivity at once
+    DomainOutage { domain: usize },
+    /// A failed domain comes back
+    DomainRecovery { domain: usize },
     /// Advance to the next slot
     AdvanceSlot,
+    /// The network adversary drops, duplicates or delays a message
+    NetworkFault { fault: NetworkFault<MessageInTransit> },
 }
 
 /// State of a validator in the resilience model
@@ -95,35 +155,80 @@ pub struct ValidatorState {
     is_byzantine: bool,
     /// Whether this validator is responsive
     is_responsive: bool,
+    /// Whether this validator has crashed for good (crashed validators are also not responsive)
+    is_crashed: bool,
+    /// Whether this validator has unstaked (retired validators are also not responsive)
+    is_retired: bool,
     /// Whether this validator is partitioned
     is_partitioned: bool,
     /// Votes cast by this validator: (slot, hash) -> true
     votes_cast: BTreeMap<(Slot, Hash), bool>,
     /// Vote pool: (slot, hash) -> set of voters
-    vote_pool: BTreeMap<(Slot, Hash), BTreeSet<ActorId>>,
+    vote_pool: BTreeMap<(Slot, Hash), VoterSet>,
     /// Certificates formed: slot -> hash
     certificates: BTreeMap<Slot, Hash>,
     /// Current slot
     current_slot: Slot,
 }
 
+/// An active network partition
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Partition {
+    /// Validators on the affected side
+    affected: BTreeSet<ActorId>,
+    /// Whether both sides stay internally connected, rather than the affected validators being isolated
+    two_sided: bool,
+}
+
+/// Progress towards finalization since the last partition healed. A delivery round ends once every
+/// message outstanding at its start has been delivered, so the adversary's later messages cannot stretch it.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Recovery {
+    /// Delivery rounds begun, the current one included, saturating one past the model's bound
+    rounds: u64,
+    /// Messages outstanding at the start of the current round that are still undelivered
+    outstanding: BTreeSet<MessageInTransit>,
+}
+
 /// Main state of the resilience formal model
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct ResilienceState {
     /// Network messages in transit
     network: BTreeSet<MessageInTransit>,
+    /// Messages held back by a partition, with their sender, until it heals
+    buffered: BTreeSet<(ActorId, MessageInTransit)>,
+    /// Adversarial control over message delivery
+    network_faults: NetworkFaults<MessageInTransit>,
     /// Per-validator states
-    validators: Vec<ValidatorState>,
+    validators: SharedVec<ValidatorState>,
     /// Global current slot
     current_slot: Slot,
     /// Stake distribution: validator -> stake
     stake_distribution: BTreeMap<ActorId, Stake>,
     /// Block proposals: slot -> hash
     block_proposals: BTreeMap<Slot, Hash>,
-    /// Active partitions: partition_id -> affected validators
-    active_partitions: BTreeMap<u64, BTreeSet<ActorId>>,
+    /// Active partitions: partition_id -> partition
+    active_partitions: BTreeMap<u64, Partition>,
+    /// Whether any partition was ever applied
+    partition_occurred: bool,
+    /// Slots some validator certified while a partition was active
+    decided_during_partition: BTreeSet<Slot>,
+    /// Vote floods sent so far
+    floods: usize,
+    /// Whether a Byzantine validator has sent different votes to different honest validators
+    split_vote_sent: bool,
+    /// Weak-subjectivity checkpoint: the history honest validators had finalized when a validator last retired
+    checkpoint: BTreeMap<Slot, Hash>,
+    /// Whether the adversary has signed an alternative history with leaked keys
+    forged_history: bool,
+    /// Failure domains currently down
+    failed_domains: BTreeSet<usize>,
+    /// Recovery since the last partition healed, while some proposal is not yet certified everywhere
+    recovery: Option<Recovery>,
     /// Safety violations detected
     safety_violations: BTreeSet<(Slot, Hash, Hash)>, // (slot, hash1, hash2) for conflicting blocks
+    /// Ghost record of every certificate any validator held so far
+    finalized_history: FinalizedHistory,
 }
 
 /// Formal model for resilience properties
@@ -135,6 +240,51 @@ pub struct ResilienceModel {
     pub max_slot: Slot,
     /// Number of Byzantine validators
     pub byzantine_count: usize,
+    /// Most stake (percent) that may be offline at the same time
+    pub offline_budget_percent: u64,
+    /// Most stake (percent) the adversary may control, counting statically Byzantine validators
+    pub corruption_budget_percent: u64,
+    /// Messages the network adversary may drop, duplicate or delay
+    pub network_faults: usize,
+    /// Most honest stake (percent) that may crash for good
+    pub crash_budget_percent: u64,
+    /// Largest validator subset a partition cuts off (the smaller side of a two-sided partition)
+    pub max_partition_size: usize,
+    /// Most votes a validator's pool keeps from any one voter
+    pub pool_capacity: usize,
+    /// Vote floods the adversary may send
+    pub flood_budget: usize,
+    /// Most stake (percent) that may unstake and later have its old keys fall to the adversary
+    pub posterior_corruption_percent: u64,
+    /// Whether validators reject votes that contradict the weak-subjectivity checkpoint
+    pub weak_subjectivity: bool,
+    /// Groups of validators sharing a data center or network provider; one outage takes a whole group down
+    pub failure_domains: Vec<BTreeSet<ActorId>>,
+    /// Most delivery rounds after the last partition heals before every proposal is certified everywhere
+    pub recovery_rounds: u64,
+}
+
+impl ValidatorState {
+    /// Add a vote to the pool. Each voter gets at most `capacity` votes in the pool; beyond that its
+    /// votes for the lowest slots are evicted, so a flooding voter only ever displaces its own votes.
+    fn add_vote(&mut self, slot: Slot, hash: Hash, voter: ActorId, capacity: usize) {
+        self.vote_pool.entry((slot, hash)).or_default().insert(voter);
+        while self.votes_held(voter) > capacity {
+            let Some(evicted) = self.vote_pool.iter()
+                .find(|(_, voters)| voters.contains(&voter))
+                .map(|(key, _)| *key) else { break };
+            let voters = self.vote_pool.get_mut(&evicted).unwrap();
+            voters.remove(&voter);
+            if voters.is_empty() {
+                self.vote_pool.remove(&evicted);
+            }
+        }
+    }
+
+    /// Number of votes the pool holds from a voter
+    fn votes_held(&self, voter: ActorId) -> usize {
+        self.vote_pool.values().filter(|voters| voters.contains(&voter)).count()
+    }
 }
 
 impl ResilienceState {
@@ -148,10 +298,14 @@ impl ResilienceState {
 
         Self {
             network: BTreeSet::new(),
+            buffered: BTreeSet::new(),
+            network_faults: NetworkFaults::new(0),
             validators: (0..validator_count).map(|i| ValidatorState {
                 id: i,
                 is_byzantine: i < byzantine_count,
                 is_responsive: true,
+                is_crashed: false,
+                is_retired: false,
                 is_partitioned: false,
                 votes_cast: BTreeMap::new(),
                 vote_pool: BTreeMap::new(),
@@ -162,44 +316,372 @@ impl ResilienceState {
             stake_distribution,
             block_proposals: BTreeMap::new(),
             active_partitions: BTreeMap::new(),
+            partition_occurred: false,
+            decided_during_partition: BTreeSet::new(),
+            floods: 0,
+            split_vote_sent: false,
+            checkpoint: BTreeMap::new(),
+            forged_history: false,
+            failed_domains: BTreeSet::new(),
+            recovery: None,
             safety_violations: BTreeSet::new(),
+            finalized_history: FinalizedHistory::new(),
         }
     }
 
-    /// Check if a block can be certified (60% threshold)
-    fn can_certify(&self, slot: Slot, hash: Hash) -> bool {
-        if let Some(voters) = self.validators[0].vote_pool.get(&(slot, hash)) {
-            let honest_stake: Stake = voters.iter()
-                .filter(|voter_id| !self.validators[**voter_id].is_byzantine)
-                .filter(|voter_id| self.validators[**voter_id].is_responsive)
-                .filter(|voter_id| !self.validators[**voter_id].is_partitioned)
-                .filter_map(|voter_id| self.stake_distribution.get(voter_id))
+    /// Check if a block can be certified (60% threshold) from the votes in a validator's pool.
+    /// Votes are signed, so Byzantine votes count like any other, and a voter that has since
+    /// been partitioned off or retired still counts.
+    fn can_certify(&self, vote_pool: &BTreeMap<(Slot, Hash), VoterSet>, slot: Slot, hash: Hash) -> bool {
+        if let Some(voters) = vote_pool.get(&(slot, hash)) {
+            let voting_stake: Stake = voters.iter()
+                .filter(|voter_id| self.validators[*voter_id].is_responsive || self.validators[*voter_id].is_retired)
+                .filter_map(|voter_id| self.stake_distribution.get(&voter_id))
                 .sum();
-            honest_stake >= (TOTAL_STAKE * CERTIFICATE_THRESHOLD_PERCENT / 100)
+            voting_stake >= (TOTAL_STAKE * CERTIFICATE_THRESHOLD_PERCENT / 100)
         } else {
             false
         }
     }
 
-    /// Check for safety violations (conflicting certificates)
+    /// Stake of validators that are temporarily not responsive
+    fn offline_stake(&self) -> Stake {
+        self.validators.iter().enumerate()
+            .filter(|(_, v)| !v.is_responsive && !v.is_crashed)
+            .filter_map(|(id, _)| self.stake_distribution.get(&id))
+            .sum()
+    }
+
+    /// Stake of validators that have crashed for good
+    fn crashed_stake(&self) -> Stake {
+        self.validators.iter()
+            .filter(|v| v.is_crashed)
+            .filter_map(|v| self.stake_distribution.get(&v.id))
+            .sum()
+    }
+
+    /// Combined stake of a group of validators
+    fn group_stake(&self, group: &BTreeSet<ActorId>) -> Stake {
+        group.iter().filter_map(|id| self.stake_distribution.get(id)).sum()
+    }
+
+    /// Stake of validators that have unstaked
+    fn retired_stake(&self) -> Stake {
+        self.validators.iter()
+            .filter(|v| v.is_retired)
+            .filter_map(|v| self.stake_distribution.get(&v.id))
+            .sum()
+    }
+
+    /// Whether some honest validator holds a certificate for the slot
+    fn finalized_by_honest(&self, slot: Slot) -> bool {
+        self.validators.iter().any(|v| !v.is_byzantine && v.certificates.contains_key(&slot))
+    }
+
+    /// Whether every honest validator's certificates agree with the weak-subjectivity checkpoint
+    fn respects_checkpoint(&self) -> bool {
+        self.validators.iter()
+            .filter(|v| !v.is_byzantine)
+            .all(|v| self.checkpoint.iter().all(|(slot, hash)| !matches!(v.certificates.get(slot), Some(c) if c != hash)))
+    }
+
+    /// Certificates each validator holds, by validator index: slot -> hash
+    pub(crate) fn certificates_by_validator(&self) -> Vec<BTreeMap<Slot, Hash>> {
+        self.validators.iter().map(|validator| validator.certificates.clone()).collect()
+    }
+
+    /// Conflicting certificates detected so far: (slot, hash1, hash2)
+    pub(crate) fn safety_violations(&self) -> &BTreeSet<(Slot, Hash, Hash)> {
+        &self.safety_violations
+    }
+
+    /// Stake controlled by Byzantine validators
+    fn byzantine_stake(&self) -> Stake {
+        self.validators.iter()
+            .filter(|v| v.is_byzantine)
+            .filter_map(|v| self.stake_distribution.get(&v.id))
+            .sum()
+    }
+
+    /// Whether the liveness guarantee applies: at most 20% Byzantine and 20% non-responsive stake,
+    /// no partition or withheld message so far, and every responsive validator has processed its messages
+    fn liveness_obligation_applies(&self) -> bool {
+        self.byzantine_stake() <= TOTAL_STAKE * 20 / 100
+            && self.offline_stake() + self.crashed_stake() <= TOTAL_STAKE * 20 / 100
+            && self.settled()
+    }
+
+    /// Whether the whitepaper's "20+20" liveness claim applies: at most 20% Byzantine stake,
+    /// at most an additional 20% crashed, nobody else offline, and the network has settled
+    fn twenty_plus_twenty_applies(&self) -> bool {
+        self.byzantine_stake() <= TOTAL_STAKE * WHITEPAPER_BYZANTINE_PERCENT / 100
+            && self.crashed_stake() <= TOTAL_STAKE * WHITEPAPER_CRASHED_PERCENT / 100
+            && self.offline_stake() == 0
+            && self.settled()
+    }
+
+    /// No partition or withheld message so far, and every responsive validator has processed its messages
+    fn settled(&self) -> bool {
+        let quiescent = self.network.iter().all(|m| !self.validators[m.dst].is_responsive);
+        !self.partition_occurred && !self.network_faults.messages_withheld() && quiescent
+    }
+
+    /// Whether every proposed block is certified at every responsive honest validator
+    fn all_proposals_certified(&self) -> bool {
+        self.block_proposals.keys().all(|slot| self.certified_everywhere(*slot))
+    }
+
+    /// Whether every responsive honest validator holds a certificate for the slot
+    fn certified_everywhere(&self, slot: Slot) -> bool {
+        self.validators.iter()
+            .filter(|v| !v.is_byzantine && v.is_responsive)
+            .all(|v| v.certificates.contains_key(&slot))
+    }
+
+    /// Check for safety violations (conflicting certificates) across honest validators
     fn check_safety_violations(&mut self) {
-        for validator in &self.validators {
-            for (slot, hash1) in &validator.certificates {
-                for (other_slot, hash2) in &validator.certificates {
-                    if *slot == *other_slot && hash1 != hash2 {
-                        self.safety_violations.insert((*slot, *hash1, *hash2));
-                    }
-                }
+        let honest: Vec<_> = self.validators.iter().filter(|v| !v.is_byzantine).collect();
+        let conflicts: Vec<_> = honest.iter()
+            .flat_map(|a| honest.iter().map(move |b| (a, b)))
+            .flat_map(|(a, b)| a.certificates.iter().filter_map(move |(slot, hash1)| {
+                b.certificates.get(slot)
+                    .filter(|hash2| *hash2 != hash1)
+                    .map(|hash2| (*slot, *hash1, *hash2))
+            }))
+            .collect();
+        self.safety_violations.extend(conflicts);
+    }
+
+    /// Whether a message from `from` reaches `to` across every active two-sided partition
+    fn can_reach(&self, from: ActorId, to: ActorId) -> bool {
+        self.active_partitions.values()
+            .filter(|partition| partition.two_sided)
+            .all(|partition| partition.affected.contains(&from) == partition.affected.contains(&to))
+    }
+
+    /// Whether a message from `src` gets through to `dst`: neither is isolated and no two-sided
+    /// partition separates them. Validators always reach themselves.
+    fn connected(&self, validators: &SharedVec<ValidatorState>, src: ActorId, dst: ActorId) -> bool {
+        src == dst
+            || (!validators[src].is_partitioned && !validators[dst].is_partitioned && self.can_reach(src, dst))
+    }
+
+    /// Send a message, holding it back while a partition separates sender and recipient
+    fn send(&mut self, validators: &SharedVec<ValidatorState>, src: ActorId, dst: ActorId, msg: ResilienceMessage) {
+        let message = MessageInTransit { dst, msg };
+        if self.connected(validators, src, dst) {
+            self.network.insert(message);
+        } else {
+            self.buffered.insert((src, message));
+        }
+    }
+
+    /// Replay buffered messages whose sender and recipient are connected again
+    fn release_buffered(&mut self) {
+        let (ready, held): (BTreeSet<_>, BTreeSet<_>) = std::mem::take(&mut self.buffered)
+            .into_iter()
+            .partition(|(src, message)| self.connected(&self.validators, *src, message.dst));
+        self.network.extend(ready.into_iter().map(|(_, message)| message));
+        self.buffered = held;
+    }
+
+    /// Certify a block at a validator if its vote pool now holds a quorum for it.
+    /// An honest validator that already holds a different certificate for the slot keeps it and
+    /// records the conflict.
+    fn try_certify(&mut self, validator_state: &mut ValidatorState, slot: Slot, hash: Hash) {
+        if self.can_certify(&validator_state.vote_pool, slot, hash) {
+            let existing = *validator_state.certificates.entry(slot).or_insert(hash);
+            if existing != hash && !validator_state.is_byzantine {
+                self.safety_violations.insert((slot, existing, hash));
+            }
+            if !self.active_partitions.is_empty() {
+                self.decided_during_partition.insert(slot);
             }
         }
     }
 
+    /// Whether every partition has healed, all held-back messages were replayed and every responsive
+    /// validator has processed its messages, within 20% Byzantine and 20% non-responsive stake
+    fn healed(&self) -> bool {
+        let quiescent = self.network.iter().all(|m| !self.validators[m.dst].is_responsive);
+        self.partition_occurred
+            && self.active_partitions.is_empty()
+            && self.buffered.is_empty()
+            && self.validators.iter().all(|v| !v.is_partitioned)
+            && !self.network_faults.messages_withheld()
+            && quiescent
+            && self.byzantine_stake() <= TOTAL_STAKE * 20 / 100
+            && self.offline_stake() + self.crashed_stake() <= TOTAL_STAKE * 20 / 100
+    }
+
+    /// Whether all responsive honest validators hold the same certificate for every slot decided during a partition
+    fn partition_decisions_converged(&self) -> bool {
+        self.decided_during_partition.iter().all(|slot| {
+            let mut certificates = self.validators.iter()
+                .filter(|v| !v.is_byzantine && v.is_responsive)
+                .map(|v| v.certificates.get(slot));
+            let first = certificates.next().flatten();
+            first.is_some() && certificates.all(|certificate| certificate == first)
+        })
+    }
+
     /// Check if network partition affects consensus
-    fn is_partition_critical(&self, affected_validators: &BTreeSet<ActorId>) -> bool {
-        let affected_stake: Stake = affected_validators.iter()
-            .filter_map(|voter_id| self.stake_distribution.get(voter_id))
-            .sum();
-        affected_stake > (TOTAL_STAKE * CERTIFICATE_THRESHOLD_PERCENT / 100)
+    pub fn is_partition_critical(&self, affected_validators: &BTreeSet<ActorId>) -> bool {
+        self.group_stake(affected_validators) > (TOTAL_STAKE * CERTIFICATE_THRESHOLD_PERCENT / 100)
+    }
+
+    /// Whether the recovery bound applies: no partition active, no withheld message, and at most
+    /// 20% Byzantine and 20% non-responsive stake
+    fn recovery_obligation_applies(&self) -> bool {
+        self.active_partitions.is_empty()
+            && !self.network_faults.messages_withheld()
+            && self.byzantine_stake() <= TOTAL_STAKE * 20 / 100
+            && self.offline_stake() + self.crashed_stake() <= TOTAL_STAKE * 20 / 100
+    }
+
+    /// Messages a responsive validator can receive
+    fn deliverable(&self) -> BTreeSet<MessageInTransit> {
+        self.network.iter().filter(|m| self.validators[m.dst].is_responsive).cloned().collect()
+    }
+
+    /// Count delivery rounds of the ongoing recovery, which ends once every proposal is certified everywhere
+    fn track_recovery(&mut self, round_limit: u64) {
+        if self.all_proposals_certified() {
+            self.recovery = None;
+            return;
+        }
+        let deliverable = self.deliverable();
+        let Some(recovery) = &mut self.recovery else { return };
+        recovery.outstanding.retain(|m| deliverable.contains(m));
+        if recovery.outstanding.is_empty() {
+            recovery.rounds = (recovery.rounds + 1).min(round_limit);
+            recovery.outstanding = deliverable;
+        }
+    }
+}
+
+impl ResilienceModel {
+    /// Add a vote to a validator's pool unless the weak-subjectivity checkpoint rules its block out
+    fn accept_vote(&self, state: &ResilienceState, validator_state: &mut ValidatorState, slot: Slot, hash: Hash, voter: ActorId) {
+        if self.weak_subjectivity && state.checkpoint.get(&slot).is_some_and(|checkpointed| *checkpointed != hash) {
+            return;
+        }
+        validator_state.add_vote(slot, hash, voter, self.pool_capacity);
+    }
+
+    /// Stake of the largest failure domain, the most a single correlated outage can take down
+    fn largest_domain_stake(&self, state: &ResilienceState) -> Stake {
+        self.failure_domains.iter().map(|domain| state.group_stake(domain)).max().unwrap_or(0)
+    }
+
+    /// Validators in a failure domain that is currently down
+    fn down_validators(&self, state: &ResilienceState) -> BTreeSet<ActorId> {
+        state.failed_domains.iter().flat_map(|domain| self.failure_domains[*domain].iter().copied()).collect()
+    }
+
+    /// Split the validators into consecutive failure domains of `domain_size` (the last may be smaller)
+    pub fn contiguous_domains(validator_count: usize, domain_size: usize) -> Vec<BTreeSet<ActorId>> {
+        (0..validator_count).step_by(domain_size)
+            .map(|start| (start..(start + domain_size).min(validator_count)).collect())
+            .collect()
+    }
+
+    /// Every non-empty proper subset of the validators with at most `max_partition_size` members
+    fn partition_subsets(&self) -> Vec<BTreeSet<ActorId>> {
+        (1..(1u64 << self.validator_count) - 1)
+            .map(|mask| (0..self.validator_count).filter(|i| mask & (1 << i) != 0).collect::<BTreeSet<_>>())
+            .filter(|subset| subset.len() <= self.max_partition_size)
+            .collect()
+    }
+
+    /// A model whose adversary controls `byzantine_percent` of the stake from the start and may
+    /// crash another `crashed_percent`, with no other offline validators or network faults
+    pub fn with_fault_fractions(validator_count: usize, max_slot: Slot, byzantine_percent: u64, crashed_percent: u64) -> Self {
+        Self {
+            validator_count,
+            max_slot,
+            byzantine_count: validator_count * byzantine_percent as usize / 100,
+            offline_budget_percent: 0,
+            corruption_budget_percent: byzantine_percent,
+            network_faults: 0,
+            crash_budget_percent: crashed_percent,
+            max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+            pool_capacity: DEFAULT_POOL_CAPACITY,
+            flood_budget: DEFAULT_FLOOD_BUDGET,
+            posterior_corruption_percent: DEFAULT_POSTERIOR_CORRUPTION_PERCENT,
+            weak_subjectivity: true,
+            failure_domains: Vec::new(),
+            recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
+        }
+    }
+
+    /// The whitepaper's resilience configuration: 20% Byzantine plus 20% crashed stake
+    pub fn twenty_plus_twenty(validator_count: usize, max_slot: Slot) -> Self {
+        Self::with_fault_fractions(validator_count, max_slot, WHITEPAPER_BYZANTINE_PERCENT, WHITEPAPER_CRASHED_PERCENT)
+    }
+
+    /// Preconditions under which the conditional resilience properties make a real claim
+    pub fn antecedents() -> Vec<Antecedent<Self>> {
+        vec![
+            Antecedent::<Self>::new("safety_byzantine_threshold", |_model, state| {
+                state.byzantine_stake() <= TOTAL_STAKE * 20 / 100
+                    && state.validators.iter().any(|v| !v.certificates.is_empty())
+            }),
+            Antecedent::<Self>::new("liveness_non_responsive", |_model, state| {
+                state.liveness_obligation_applies() && !state.block_proposals.is_empty()
+            }),
+            Antecedent::<Self>::new("partition_recovery", |_model, state| {
+                state.recovery_obligation_applies() && state.recovery.as_ref().is_some_and(|r| r.rounds > 0)
+            }),
+            Antecedent::<Self>::new("partition_heal_convergence", |_model, state| {
+                state.healed() && !state.decided_during_partition.is_empty()
+            }),
+            Antecedent::<Self>::new("quorum_intersection", |_model, state| {
+                state.split_vote_sent
+                    && state.byzantine_stake() > 0
+                    && state.byzantine_stake() <= TOTAL_STAKE * (2 * CERTIFICATE_THRESHOLD_PERCENT - 100) / 100
+                    && state.validators.iter().any(|v| !v.is_byzantine && !v.certificates.is_empty())
+            }),
+            Antecedent::<Self>::new("no_long_range_reversion", |_model, state| {
+                state.forged_history && !state.checkpoint.is_empty()
+            }),
+            Antecedent::<Self>::new("certification_under_flood", |_model, state| {
+                state.floods > 0 && state.liveness_obligation_applies() && !state.block_proposals.is_empty()
+            }),
+            Antecedent::<Self>::new("liveness_20_plus_20", |_model, state| {
+                state.twenty_plus_twenty_applies()
+                    && state.byzantine_stake() > 0
+                    && state.crashed_stake() > 0
+                    && !state.block_proposals.is_empty()
+            }),
+        ]
+    }
+}
+
+impl FinalizedRecord for ResilienceState {
+    /// A validator's certificate for a slot finalizes its block in this model
+    fn finalized_entries(&self) -> FinalizedHistory {
+        self.validators.iter()
+            .flat_map(|v| v.certificates.iter().map(move |(slot, hash)| (v.id, *slot, *hash)))
+            .collect()
+    }
+
+    fn finalized_history(&self) -> &FinalizedHistory {
+        &self.finalized_history
+    }
+
+    fn finalized_history_mut(&mut self) -> &mut FinalizedHistory {
+        &mut self.finalized_history
+    }
+}
+
+impl Bounded for ResilienceState {
+    fn current_slot(&self) -> Slot {
+        self.current_slot
+    }
+
+    fn in_flight(&self) -> usize {
+        self.network.len() + self.buffered.len()
     }
 }
 
@@ -208,30 +690,43 @@ impl Model for ResilienceModel {
     type Action = ResilienceAction;
 
     fn init_states(&self) -> Vec<Self::State> {
-        vec![ResilienceState::new(self.validator_count, self.byzantine_count)]
+        let mut state = ResilienceState::new(self.validator_count, self.byzantine_count);
+        state.network_faults = NetworkFaults::new(self.network_faults);
+        vec![state]
     }
 
     fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
-        // 1. Deliver any message in the network
+        // 1. Deliver any message in the network; messages for offline validators wait
         for msg in &state.network {
-            actions.push(ResilienceAction::DeliverMessage { msg: msg.clone() });
+            if state.validators[msg.dst].is_responsive {
+                actions.push(ResilienceAction::DeliverMessage { msg: msg.clone() });
+            }
         }
 
-        // 2. Propose blocks for current and future slots
+        // The network adversary drops, duplicates or delays a bounded number of messages
+        for fault in state.network_faults.faults(&state.network) {
+            actions.push(ResilienceAction::NetworkFault { fault });
+        }
+
+        // 2. Propose blocks for current and future slots. Proposers only differ in the hash they
+        //    give the block, which is never compared to anything but other hashes of its slot,
+        //    so the first responsive honest validator stands in for all of them. Proposals always
+        //    reach every validator, which only an honest leader guarantees; Byzantine leaders act
+        //    through conflicting and split votes instead.
+        let canonical_proposer = (0..self.validator_count)
+            .find(|p| state.validators[*p].is_responsive && !state.validators[*p].is_byzantine);
         for slot in state.current_slot..=self.max_slot {
-            for proposer in 0..self.validator_count {
-                if !state.block_proposals.contains_key(&slot) {
-                    actions.push(ResilienceAction::ProposeBlock {
-                        slot,
-                        proposer,
-                    });
-                }
+            if let Some(proposer) = canonical_proposer.filter(|_| !state.block_proposals.contains_key(&slot)) {
+                actions.push(ResilienceAction::ProposeBlock {
+                    slot,
+                    proposer,
+                });
             }
         }
 
         // 3. Byzantine validators create conflicting votes
         for slot in 1..=self.max_slot {
-            for byzantine_validator in 0..self.byzantine_count {
+            for byzantine_validator in (0..self.validator_count).filter(|v| state.validators[*v].is_byzantine) {
                 actions.push(ResilienceAction::CreateConflictingVote {
                     slot,
                     byzantine_validator,
@@ -239,16 +734,68 @@ impl Model for ResilienceModel {
             }
         }
 
-        // 4. Trigger network partitions
-        for partition_id in 1..=3 {
-            for size in 1..=self.validator_count {
-                let mut affected = BTreeSet::new();
-                for i in 0..size {
-                    affected.insert(i);
+        // Byzantine validators vote for a proposed block towards some honest validators and against
+        // it towards the others
+        let honest: Vec<ActorId> = (0..self.validator_count).filter(|v| !state.validators[*v].is_byzantine).collect();
+        for slot in state.block_proposals.keys() {
+            for byzantine_validator in (0..self.validator_count).filter(|v| state.validators[*v].is_byzantine) {
+                for mask in 0..(1u64 << honest.len()) {
+                    let left = honest.iter().enumerate()
+                        .filter(|(i, _)| mask & (1 << i) != 0)
+                        .map(|(_, v)| *v)
+                        .collect();
+                    actions.push(ResilienceAction::SplitVote { byzantine_validator, slot: *slot, left });
+                }
+            }
+        }
+
+        // Honest validators unstake once an honest validator has finalized every slot they voted in,
+        // while the stake whose old keys may leak stays within budget
+        let posterior_budget = TOTAL_STAKE * self.posterior_corruption_percent / 100;
+        for (validator, validator_state) in state.validators.iter().enumerate() {
+            if !validator_state.is_byzantine
+                && validator_state.is_responsive
+                && validator_state.votes_cast.keys().all(|(slot, _)| state.finalized_by_honest(*slot))
+                && state.retired_stake() + state.stake_distribution[&validator] <= posterior_budget
+            {
+                actions.push(ResilienceAction::Retire { validator });
+            }
+        }
+
+        // With retired validators' leaked keys the adversary rewrites finalized slots
+        if state.retired_stake() > 0 {
+            for slot in state.checkpoint.keys() {
+                actions.push(ResilienceAction::ForgeHistory { slot: *slot });
+            }
+        }
+
+        // Byzantine validators flood honest validators with spurious votes, within the flood budget
+        if state.floods < self.flood_budget {
+            for byzantine_validator in (0..self.validator_count).filter(|v| state.validators[*v].is_byzantine) {
+                for target in (0..self.validator_count).filter(|v| !state.validators[*v].is_byzantine) {
+                    actions.push(ResilienceAction::FloodVotes { byzantine_validator, target });
+                }
+            }
+        }
+
+        // 4. Trigger network partitions over every validator subset up to the size bound. A
+        //    two-sided partition is named by its smaller side (the one holding validator 0 on a tie)
+        for partition_id in (1..=3).filter(|id| !state.active_partitions.contains_key(id)) {
+            for affected in self.partition_subsets() {
+                let size = affected.len();
+                let smaller_side = size * 2 < self.validator_count
+                    || (size * 2 == self.validator_count && affected.contains(&0));
+                if smaller_side {
+                    actions.push(ResilienceAction::TriggerPartition {
+                        partition_id,
+                        affected_validators: affected.clone(),
+                        two_sided: true,
+                    });
                 }
                 actions.push(ResilienceAction::TriggerPartition {
                     partition_id,
                     affected_validators: affected,
+                    two_sided: false,
                 });
             }
         }
@@ -264,6 +811,52 @@ impl Model for ResilienceModel {
         if state.current_slot < self.max_slot {
             actions.push(ResilienceAction::AdvanceSlot);
         }
+
+        // Validators go offline and come back while the offline stake stays within budget.
+        // Validators in a failed domain only come back with their domain
+        let offline_budget = TOTAL_STAKE * self.offline_budget_percent / 100;
+        let down = self.down_validators(state);
+        for (validator, validator_state) in state.validators.iter().enumerate() {
+            if validator_state.is_crashed || validator_state.is_retired || down.contains(&validator) {
+                continue;
+            }
+            if !validator_state.is_responsive {
+                actions.push(ResilienceAction::ComeOnline { validator });
+            } else if state.offline_stake() + state.stake_distribution[&validator] <= offline_budget {
+                actions.push(ResilienceAction::GoOffline { validator });
+            }
+        }
+
+        // Honest validators crash for good while the crashed stake stays within budget
+        let crash_budget = TOTAL_STAKE * self.crash_budget_percent / 100;
+        for (validator, validator_state) in state.validators.iter().enumerate() {
+            if !validator_state.is_byzantine
+                && validator_state.is_responsive
+                && state.crashed_stake() + state.stake_distribution[&validator] <= crash_budget
+            {
+                actions.push(ResilienceAction::Crash { validator });
+            }
+        }
+
+        // A single correlated outage takes down a whole failure domain at a time
+        if state.failed_domains.is_empty() {
+            for domain in 0..self.failure_domains.len() {
+                actions.push(ResilienceAction::DomainOutage { domain });
+            }
+        }
+        for domain in &state.failed_domains {
+            actions.push(ResilienceAction::DomainRecovery { domain: *domain });
+        }
+
+        // An adaptive adversary corrupts honest validators while its stake stays within budget
+        let corruption_budget = TOTAL_STAKE * self.corruption_budget_percent / 100;
+        for (id, validator_state) in state.validators.iter().enumerate() {
+            if !validator_state.is_byzantine
+                && state.byzantine_stake() + state.stake_distribution[&id] <= corruption_budget
+            {
+                actions.push(ResilienceAction::CorruptValidator { id });
+            }
+        }
     }
 
     fn next_state(&self, last_state: &Self::State, action: Self::Action) -> Option<Self::State> {
@@ -275,84 +868,110 @@ impl Model for ResilienceModel {
                 let block_hash = slot * 1000 + proposer as u64;
                 next_state.block_proposals.insert(slot, block_hash);
 
-                // Broadcast block proposal to all non-partitioned validators
-                for i in 0..self.validator_count {
-                    if i != proposer && !validators[i].is_partitioned {
-                        next_state.network.insert(MessageInTransit {
-                            dst: i,
-                            msg: ResilienceMessage::BlockProposal {
-                                slot,
-                                hash: block_hash,
-                                proposer,
-                            },
-                        });
-                    }
+                // Broadcast block proposal to all validators, the proposer included
+                for i in 0..validators.len() {
+                    next_state.send(&validators, proposer, i, ResilienceMessage::BlockProposal {
+                        slot,
+                        hash: block_hash,
+                        proposer,
+                    });
                 }
             }
+            ResilienceAction::NetworkFault { fault } => {
+                if !next_state.network_faults.apply(&mut next_state.network, fault) { return None; }
+            }
             ResilienceAction::DeliverMessage { msg } => {
                 let recipient_id = msg.dst;
-                let mut validator_state = validators[recipient_id].clone();
+                let mut validator_state = validators.get(recipient_id)?.clone();
 
                 // Remove message from network
                 if !next_state.network.remove(&msg) { return None; }
+                next_state.network_faults.record_delivery(&msg);
 
                 match msg.msg {
                     ResilienceMessage::BlockProposal { slot, hash, proposer: _ } => {
-                        // Validator receives block and can vote for it
-                        if validator_state.is_responsive && !validator_state.is_partitioned {
-                            if !validator_state.votes_cast.contains_key(&(slot, hash)) {
-                                validator_state.votes_cast.insert((slot, hash), true);
-                                
-                                // Broadcast vote
-                                for i in 0..self.validator_count {
-                                    if !validators[i].is_partitioned {
-                                        next_state.network.insert(MessageInTransit {
-                                            dst: i,
-                                            msg: ResilienceMessage::Vote {
-                                                slot,
-                                                hash,
-                                                voter: recipient_id,
-                                            },
-                                        });
-                                    }
-                                }
+                        // Honest validators receive the block and vote for it; a partitioned validator's
+                        // vote is held back until the partition heals. Byzantine validators only vote
+                        // through adversarial actions.
+                        if validator_state.is_responsive
+                            && !validator_state.is_byzantine
+                            && !validator_state.votes_cast.contains_key(&(slot, hash))
+                        {
+                            validator_state.votes_cast.insert((slot, hash), true);
+
+                            // Broadcast vote
+                            for i in 0..validators.len() {
+                                next_state.send(&validators, recipient_id, i, ResilienceMessage::Vote {
+                                    slot,
+                                    hash,
+                                    voter: recipient_id,
+                                });
                             }
                         }
                     }
                     ResilienceMessage::Vote { slot, hash, voter } => {
                         // Add vote to pool
-                        let voters = validator_state.vote_pool.entry((slot, hash)).or_default();
-                        voters.insert(voter);
+                        self.accept_vote(&next_state, &mut validator_state, slot, hash, voter);
 
                         // Check for certification
-                        if next_state.can_certify(slot, hash) {
-                            validator_state.certificates.insert(slot, hash);
-                        }
+                        next_state.try_certify(&mut validator_state, slot, hash);
                     }
                     ResilienceMessage::ConflictingVote { slot, hash, voter } => {
                         // Byzantine vote - add to pool but mark as conflicting
-                        let voters = validator_state.vote_pool.entry((slot, hash)).or_default();
-                        voters.insert(voter);
-                        
+                        self.accept_vote(&next_state, &mut validator_state, slot, hash, voter);
+
                         // Check for certification (should fail due to Byzantine behavior)
-                        if next_state.can_certify(slot, hash) {
-                            validator_state.certificates.insert(slot, hash);
-                        }
+                        next_state.try_certify(&mut validator_state, slot, hash);
+                    }
+                    ResilienceMessage::PartitionEvent { partition_id, .. } if next_state.active_partitions.contains_key(&partition_id) => {
+                        // A partition with this id is already in place; it has to heal first
                     }
-                    ResilienceMessage::PartitionEvent { partition_id, affected_validators } => {
-                        // Apply partition
-                        next_state.active_partitions.insert(partition_id, affected_validators.clone());
-                        for affected in affected_validators {
-                            if affected < validators.len() {
-                                validators[affected].is_partitioned = true;
+                    ResilienceMessage::PartitionEvent { partition_id, affected_validators, two_sided } => {
+                        // Apply partition; only isolated validators lose connectivity to everyone
+                        next_state.partition_occurred = true;
+                        if !two_sided {
+                            for affected in &affected_validators {
+                                if *affected < validators.len() {
+                                    validators[*affected].is_partitioned = true;
+                                }
+                            }
+                            if affected_validators.contains(&recipient_id) {
+                                validator_state.is_partitioned = true;
                             }
                         }
+                        next_state.active_partitions.insert(partition_id, Partition {
+                            affected: affected_validators,
+                            two_sided,
+                        });
+                        next_state.recovery = None;
                     }
                     ResilienceMessage::RecoveryMessage { slot: _, validator } => {
-                        // Recovery from partition
+                        // Recovery from partition; the rejoining validator then syncs with its peers
                         if validator < validators.len() {
                             validators[validator].is_partitioned = false;
                         }
+                        if validator == recipient_id {
+                            validator_state.is_partitioned = false;
+                            for i in (0..validators.len()).filter(|i| *i != recipient_id) {
+                                next_state.send(&validators, recipient_id, i, ResilienceMessage::SyncRequest {
+                                    requester: recipient_id,
+                                });
+                            }
+                        }
+                    }
+                    ResilienceMessage::SyncRequest { requester } => {
+                        next_state.send(&validators, recipient_id, requester, ResilienceMessage::SyncResponse {
+                            votes: validator_state.vote_pool.clone(),
+                        });
+                    }
+                    ResilienceMessage::SyncResponse { votes } => {
+                        // Merge the peer's votes and certify whatever now has a quorum
+                        for ((slot, hash), voters) in votes {
+                            for voter in voters.iter() {
+                                self.accept_vote(&next_state, &mut validator_state, slot, hash, voter);
+                            }
+                            next_state.try_certify(&mut validator_state, slot, hash);
+                        }
                     }
                 }
                 validators[recipient_id] = validator_state;
@@ -369,20 +988,69 @@ impl Model for ResilienceModel {
                     },
                 });
             }
-            ResilienceAction::TriggerPartition { partition_id, affected_validators } => {
+            ResilienceAction::SplitVote { byzantine_validator, slot, left } => {
+                let proposed = *next_state.block_proposals.get(&slot)?;
+                next_state.split_vote_sent = true;
+                for target in (0..validators.len()).filter(|v| !validators[*v].is_byzantine) {
+                    let hash = if left.contains(&target) { proposed } else { slot * 1000 + 999 };
+                    next_state.send(&validators, byzantine_validator, target, ResilienceMessage::Vote {
+                        slot,
+                        hash,
+                        voter: byzantine_validator,
+                    });
+                }
+            }
+            ResilienceAction::Retire { validator } => {
+                validators[validator].is_retired = true;
+                validators[validator].is_responsive = false;
+
+                // Social consensus checkpoints everything honest validators have finalized so far
+                for v in last_state.validators.iter().filter(|v| !v.is_byzantine) {
+                    for (slot, hash) in &v.certificates {
+                        next_state.checkpoint.entry(*slot).or_insert(*hash);
+                    }
+                }
+            }
+            ResilienceAction::ForgeHistory { slot } => {
+                next_state.forged_history = true;
+                for retired in (0..validators.len()).filter(|v| validators[*v].is_retired) {
+                    for target in (0..validators.len()).filter(|v| !validators[*v].is_byzantine && !validators[*v].is_retired) {
+                        next_state.send(&validators, retired, target, ResilienceMessage::Vote {
+                            slot,
+                            hash: slot * 1000 + 998,
+                            voter: retired,
+                        });
+                    }
+                }
+            }
+            ResilienceAction::FloodVotes { byzantine_validator, target } => {
+                // Spurious votes for hashes nobody proposed, in every slot
+                next_state.floods += 1;
+                for slot in 0..=self.max_slot {
+                    for k in 0..FLOOD_HASHES_PER_SLOT {
+                        next_state.send(&validators, byzantine_validator, target, ResilienceMessage::Vote {
+                            slot,
+                            hash: slot * 1000 + 900 + k,
+                            voter: byzantine_validator,
+                        });
+                    }
+                }
+            }
+            ResilienceAction::TriggerPartition { partition_id, affected_validators, two_sided } => {
                 // Trigger network partition
                 next_state.network.insert(MessageInTransit {
                     dst: 0, // Send to first validator to process
                     msg: ResilienceMessage::PartitionEvent {
-                        partition_id: partition_id,
-                        affected_validators: affected_validators.clone(),
+                        partition_id,
+                        affected_validators,
+                        two_sided,
                     },
                 });
             }
             ResilienceAction::RecoverFromPartition { partition_id } => {
-                // Recover from partition
-                if let Some(affected_validators) = next_state.active_partitions.remove(&partition_id) {
-                    for validator in affected_validators {
+                // Recover from partition; held-back messages are replayed once the affected validators rejoin
+                if let Some(partition) = next_state.active_partitions.remove(&partition_id) {
+                    for validator in partition.affected {
                         next_state.network.insert(MessageInTransit {
                             dst: validator,
                             msg: ResilienceMessage::RecoveryMessage {
@@ -391,6 +1059,35 @@ impl Model for ResilienceModel {
                             },
                         });
                     }
+                    if next_state.active_partitions.is_empty() {
+                        next_state.recovery = Some(Recovery { rounds: 0, outstanding: BTreeSet::new() });
+                    }
+                }
+            }
+            ResilienceAction::GoOffline { validator } => {
+                validators[validator].is_responsive = false;
+            }
+            ResilienceAction::ComeOnline { validator } => {
+                validators[validator].is_responsive = true;
+            }
+            ResilienceAction::CorruptValidator { id } => {
+                validators[id].is_byzantine = true;
+            }
+            ResilienceAction::Crash { validator } => {
+                validators[validator].is_crashed = true;
+                validators[validator].is_responsive = false;
+            }
+            ResilienceAction::DomainOutage { domain } => {
+                next_state.failed_domains.insert(domain);
+                for validator in &self.failure_domains[domain] {
+                    validators[*validator].is_responsive = false;
+                }
+            }
+            ResilienceAction::DomainRecovery { domain } => {
+                next_state.failed_domains.remove(&domain);
+                for validator in &self.failure_domains[domain] {
+                    let validator_state = &mut validators[*validator];
+                    validator_state.is_responsive = !validator_state.is_crashed && !validator_state.is_retired;
                 }
             }
             ResilienceAction::AdvanceSlot => {
@@ -402,7 +1099,10 @@ impl Model for ResilienceModel {
         }
 
         next_state.validators = validators;
+        next_state.release_buffered();
+        next_state.track_recovery(self.recovery_rounds + 1);
         next_state.check_safety_violations();
+        next_state.record_finalized();
         Some(next_state)
     }
 
@@ -417,12 +1117,7 @@ impl Model for ResilienceModel {
             
             // Property 2: Safety with ≤20% Byzantine stake
             Property::<Self>::always("safety_byzantine_threshold", |_model, state| {
-                let byzantine_stake: Stake = state.validators.iter()
-                    .filter(|v| v.is_byzantine)
-                    .map(|v| state.stake_distribution.get(&v.id).unwrap_or(&0))
-                    .sum();
-                
-                if byzantine_stake <= (TOTAL_STAKE * 20 / 100) {
+                if state.byzantine_stake() <= (TOTAL_STAKE * 20 / 100) {
                     // With ≤20% Byzantine stake, safety should be maintained
                     state.safety_violations.is_empty()
                 } else {
@@ -432,34 +1127,19 @@ impl Model for ResilienceModel {
             
             // Property 3: Liveness with ≤20% non-responsive stake
             Property::<Self>::always("liveness_non_responsive", |_model, state| {
-                let non_responsive_stake: Stake = state.validators.iter()
-                    .filter(|v| !v.is_responsive)
-                    .map(|v| state.stake_distribution.get(&v.id).unwrap_or(&0))
-                    .sum();
-                
-                if non_responsive_stake <= (TOTAL_STAKE * 20 / 100) {
-                    // With ≤20% non-responsive stake, liveness should be maintained
-                    // Check if any progress has been made
-                    for validator in &state.validators {
-                        if !validator.certificates.is_empty() {
-                            return true; // Progress achieved
-                        }
-                    }
+                if !state.liveness_obligation_applies() {
+                    return true; // No liveness guarantee, or progress still pending
                 }
-                true // If >20% non-responsive, no liveness guarantee
+                // Once every responsive validator has processed its messages, every
+                // proposed block is certified at every responsive honest validator
+                state.all_proposals_certified()
             }),
             
-            // Property 4: Network partition recovery
-            Property::<Self>::always("partition_recovery", |_model, state| {
-                // If partition is not critical, recovery should be possible
-                for (_partition_id, affected_validators) in &state.active_partitions {
-                    if !state.is_partition_critical(affected_validators) {
-                        // Non-critical partition should allow recovery
-                        // This is a simplified check - in practice, recovery would be more complex
-                        return true;
-                    }
-                }
-                true
+            // Property 4: Network partition recovery - once the last partition heals, every proposal is
+            // certified everywhere within the configured number of delivery rounds
+            Property::<Self>::always("partition_recovery", |model, state| {
+                !state.recovery_obligation_applies()
+                    || state.recovery.as_ref().is_none_or(|r| r.rounds <= model.recovery_rounds)
             }),
             
             // Property 5: Certificate uniqueness
@@ -467,7 +1147,7 @@ impl Model for ResilienceModel {
                 // Each slot should have at most one certificate
                 for validator in &state.validators {
                     let mut seen_slots = BTreeSet::new();
-                    for (slot, _hash) in &validator.certificates {
+                    for slot in validator.certificates.keys() {
                         if seen_slots.contains(slot) {
                             return false; // Duplicate certificate for same slot
                         }
@@ -476,70 +1156,666 @@ impl Model for ResilienceModel {
                 }
                 true
             }),
+
+            // Property 6: "20+20" safety - crashes never matter for safety
+            Property::<Self>::always("safety_20_plus_20", |_model, state| {
+                state.byzantine_stake() > TOTAL_STAKE * WHITEPAPER_BYZANTINE_PERCENT / 100
+                    || state.safety_violations.is_empty()
+            }),
+
+            // Property 7: "20+20" liveness - the remaining 60% honest stake certifies every proposal
+            Property::<Self>::always("liveness_20_plus_20", |_model, state| {
+                !state.twenty_plus_twenty_applies() || state.all_proposals_certified()
+            }),
+
+            // Property 8: Progress whatever the adversary - holds only while the fault budgets leave
+            // 60% of the stake honest and responsive, which the minimal-attack search probes
+            Property::<Self>::always("progress", |_model, state| {
+                !state.settled() || state.all_proposals_certified()
+            }),
+
+            // Property 9: Convergence after a partition heals - once held-back messages are replayed
+            // and validators have synced, every slot decided during the partition is decided everywhere
+            Property::<Self>::always("partition_heal_convergence", |_model, state| {
+                !state.healed() || state.partition_decisions_converged()
+            }),
+
+            // Property 10: Bounded memory - no voter holds more than its share of any vote pool
+            Property::<Self>::always("bounded_vote_pool", |model, state| {
+                state.validators.iter().all(|v| {
+                    (0..model.validator_count).all(|voter| v.votes_held(voter) <= model.pool_capacity)
+                })
+            }),
+
+            // Property 11: Vote flooding does not starve honest certificates
+            Property::<Self>::always("certification_under_flood", |_model, state| {
+                state.floods == 0 || !state.liveness_obligation_applies() || state.all_proposals_certified()
+            }),
+
+            // Property 12: Quorum intersection - two 60% quorums share at least 20% of the stake, so
+            // with at most 20% Byzantine stake split votes never yield conflicting certificates
+            Property::<Self>::always("quorum_intersection", |_model, state| {
+                !state.split_vote_sent
+                    || state.byzantine_stake() > TOTAL_STAKE * (2 * CERTIFICATE_THRESHOLD_PERCENT - 100) / 100
+                    || state.safety_violations.is_empty()
+            }),
+
+            // Property 13: Long-range safety - a history signed with leaked keys of unstaked validators
+            // never replaces a slot finalized before they left
+            Property::<Self>::always("no_long_range_reversion", |_model, state| {
+                state.respects_checkpoint()
+            }),
+
+            // Property 14: Correlated outages - if the largest failure domain plus the Byzantine stake
+            // stays within the 40% a certificate can do without, losing any one domain does not stall
+            // certification. Holds only while nothing beyond one domain's stake is down.
+            Property::<Self>::always("domain_outage_liveness", |model, state| {
+                let largest_domain = model.largest_domain_stake(state);
+                state.failed_domains.is_empty()
+                    || largest_domain + state.byzantine_stake() > TOTAL_STAKE * (100 - CERTIFICATE_THRESHOLD_PERCENT) / 100
+                    || state.offline_stake() + state.crashed_stake() > largest_domain
+                    || !state.settled()
+                    || state.all_proposals_certified()
+            }),
+
+            // Property 15: No validator's certificate for a slot is ever removed or overwritten,
+            // whatever the faults, crashes and forged histories
+            invariants::no_finalized_rollback_property(),
         ]
     }
 }
 
 /// Run formal verification of resilience properties
-pub fn run_formal_verification() {
+pub fn run_formal_verification(options: &CheckOptions) {
     println!("=== Resilience Properties Formal Verification ===");
     
     let model = ResilienceModel {
         validator_count: 4, // Small for formal verification
         max_slot: 3,
         byzantine_count: 1, // 25% Byzantine (within 20% threshold for safety)
+        offline_budget_percent: DEFAULT_OFFLINE_BUDGET_PERCENT,
+        corruption_budget_percent: DEFAULT_CORRUPTION_BUDGET_PERCENT,
+        network_faults: 0,
+        crash_budget_percent: DEFAULT_CRASH_BUDGET_PERCENT,
+        max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+        pool_capacity: DEFAULT_POOL_CAPACITY,
+        flood_budget: DEFAULT_FLOOD_BUDGET,
+        posterior_corruption_percent: DEFAULT_POSTERIOR_CORRUPTION_PERCENT,
+        weak_subjectivity: true,
+        failure_domains: Vec::new(),
+        recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
     };
 
     println!("Model checking resilience with {} validators ({} Byzantine), {} slots", 
              model.validator_count, model.byzantine_count, model.max_slot);
     
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
+    options.print_summary();
+
+    let result = options
+        .checker(model)
         .spawn_dfs()
         .report(&mut stateright::report::WriteReporter::new(&mut std::io::stdout()));
     
     // Check verification results
-    if result.discoveries().is_empty() {
-        println!("✅ All resilience properties verified successfully");
-    } else {
-        println!("❌ Resilience verification found counterexamples");
-        for (property_name, _path) in result.discoveries() {
-            println!("  - {}", property_name);
-        }
+    match options.print_outcomes(&result) {
+        Outcome::Proved => println!("✅ All resilience properties verified successfully"),
+        Outcome::Violated => println!("❌ Resilience verification found counterexamples"),
+        Outcome::Inconclusive => println!("❓ Resilience verification inconclusive: a limit or bound stopped the search first"),
     }
+    options.record_results("resilience formal", &result);
 }
 
 /// Test resilience model with different configurations
-pub fn test_resilience_model(validators: usize, slots: u64, byzantine: usize) {
-    println!("Testing resilience model with {} validators ({} Byzantine), {} slots", 
-             validators, byzantine, slots);
+pub fn test_resilience_model(validators: usize, slots: u64, byzantine: usize, offline_budget_percent: u64, corruption_budget_percent: u64, crash_budget_percent: u64, options: &CheckOptions) {
+    println!("Testing resilience model with {} validators ({} Byzantine), {} slots, {}% offline budget, {}% corruption budget, {}% crash budget",
+             validators, byzantine, slots, offline_budget_percent, corruption_budget_percent, crash_budget_percent);
     
     let model = ResilienceModel {
         validator_count: validators,
         max_slot: slots,
         byzantine_count: byzantine,
+        offline_budget_percent,
+        corruption_budget_percent,
+        network_faults: 0,
+        crash_budget_percent,
+        max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+        pool_capacity: DEFAULT_POOL_CAPACITY,
+        flood_budget: DEFAULT_FLOOD_BUDGET,
+        posterior_corruption_percent: DEFAULT_POSTERIOR_CORRUPTION_PERCENT,
+        weak_subjectivity: true,
+        failure_domains: Vec::new(),
+        recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
     };
 
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
+    options.print_summary();
+
+    let result = options
+        .checker(model)
         .spawn_dfs();
+    options.progress.watch(&result);
+    let result = result.join();
     
     println!("States explored: {}", result.state_count());
-    println!("Properties verified: {}", result.discoveries().is_empty());
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("resilience {}v {}s {}b", validators, slots, byzantine), &result);
+}
+
+/// Check the resilience model exhaustively until `memory_limit_mib` of resident memory, then by
+/// random walks from the frontier; fails only if some property was refuted
+pub fn run_hybrid_check(validators: usize, slots: u64, byzantine: usize, offline_budget_percent: u64, memory_limit_mib: u64, seed: u64) -> bool {
+    println!("Hybrid resilience check with {} validators ({} Byzantine), {} slots, {} MiB memory limit",
+             validators, byzantine, slots, memory_limit_mib);
+
+    let model = ResilienceModel {
+        validator_count: validators,
+        max_slot: slots,
+        byzantine_count: byzantine,
+        offline_budget_percent,
+        corruption_budget_percent: DEFAULT_CORRUPTION_BUDGET_PERCENT,
+        network_faults: 0,
+        crash_budget_percent: DEFAULT_CRASH_BUDGET_PERCENT,
+        max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+        pool_capacity: DEFAULT_POOL_CAPACITY,
+        flood_budget: DEFAULT_FLOOD_BUDGET,
+        posterior_corruption_percent: DEFAULT_POSTERIOR_CORRUPTION_PERCENT,
+        weak_subjectivity: true,
+        failure_domains: Vec::new(),
+        recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
+    };
+
+    let report = hybrid::check(&model, &HybridConfig::new(memory_limit_mib, seed));
+    report.print_summary();
+    report.properties.iter().all(|(property, _)| report.verdict(property) != Some(Verdict::Refuted))
+}
+
+/// Model-check the whitepaper's "20+20" claim: safety with 20% Byzantine stake, liveness with another 20% crashed
+pub fn verify_twenty_plus_twenty(validators: usize, slots: u64, threads: usize) -> bool {
+    let model = ResilienceModel::twenty_plus_twenty(validators, slots);
+    println!("Checking 20+20 resilience with {} validators ({} Byzantine, {}% crash budget), {} slots",
+             validators, model.byzantine_count, model.crash_budget_percent, slots);
+
+    let result = model
+        .checker()
+        .threads(threads)
+        .spawn_dfs()
+        .join();
+
+    println!("States explored: {}", result.state_count());
+    let mut holds = true;
+    for name in ["safety_20_plus_20", "liveness_20_plus_20"] {
+        let verified = result.discovery(name).is_none();
+        println!("  {} {}", if verified { "✅" } else { "❌" }, name);
+        holds &= verified;
+    }
+    holds
+}
+
+/// Measure the most delivery rounds any explored run needs between the last partition healing and
+/// every proposal being certified everywhere: the smallest `recovery_rounds` bound up to `max_rounds`
+/// that model-checks. A larger bound accepts every run a smaller one does, so binary search applies.
+pub fn measure_recovery_rounds(validators: usize, slots: u64, max_rounds: u64, max_states: usize, threads: usize) -> Option<u64> {
+    println!("Measuring partition recovery time with {} validators, {} slots", validators, slots);
+
+    let holds = |recovery_rounds: u64| {
+        let model = ResilienceModel { recovery_rounds, ..simulation_model(validators, slots) };
+        let result = model
+            .checker()
+            .threads(threads)
+            .target_state_count(max_states)
+            .spawn_dfs()
+            .join();
+        let holds = result.discovery("partition_recovery").is_none();
+        println!("  {} round(s): {} states, {}", recovery_rounds, result.unique_state_count(),
+                 if holds { "holds" } else { "counterexample" });
+        holds
+    };
+
+    if !holds(max_rounds) {
+        println!("❌ Recovery takes more than {} delivery rounds", max_rounds);
+        return None;
+    }
+    let (mut low, mut high) = (0, max_rounds);
+    while low < high {
+        let mid = (low + high) / 2;
+        if holds(mid) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    println!("Worst-case recovery: {} delivery round(s)", high);
+    Some(high)
+}
+
+// --- Minimal-Attack Search ---
+
+/// The kind of adversary power the minimal-attack search increases
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum AdversaryPower {
+    /// Stake the adversary controls from the start
+    Byzantine,
+    /// Honest stake that may crash for good
+    Crashed,
+}
+
+/// The weakest adversary found to break a resilience property
+#[derive(Clone, Debug)]
+pub struct AttackReport {
+    /// The property that breaks
+    pub property: String,
+    /// The kind of adversary power
+    pub power: AdversaryPower,
+    /// Fewest faulty validators that break the property
+    pub faulty_validators: usize,
+    /// Their stake as a percentage of the total
+    pub stake_percent: u64,
+    /// Actions leading to the violation
+    pub trace: Vec<ResilienceAction>,
+}
+
+impl AttackReport {
+    /// Print the breaking point and the violating trace
+    pub fn print_summary(&self) {
+        println!("Breaking point for {}: {} {:?} validator(s) ({}% of the stake)",
+                 self.property, self.faulty_validators, self.power, self.stake_percent);
+        for (step, action) in self.trace.iter().enumerate() {
+            println!("  {:>3}. {:?}", step + 1, action);
+        }
+    }
+}
+
+/// Smallest budget percentage that covers `faulty` validators' equal share of the stake
+fn budget_percent(validators: usize, faulty: usize) -> u64 {
+    let stake = faulty as u64 * (TOTAL_STAKE / validators as u64);
+    stake.div_ceil(TOTAL_STAKE / 100)
+}
+
+/// A model whose adversary has exactly `faulty` validators' worth of the given power and no other:
+/// partitions, vote floods and leaked keys would only crowd the bounded search
+fn attack_model(validators: usize, slots: u64, power: AdversaryPower, faulty: usize) -> ResilienceModel {
+    let percent = budget_percent(validators, faulty);
+    let model = match power {
+        AdversaryPower::Byzantine => ResilienceModel::with_fault_fractions(validators, slots, percent, 0),
+        AdversaryPower::Crashed => ResilienceModel::with_fault_fractions(validators, slots, 0, percent),
+    };
+    ResilienceModel {
+        max_partition_size: 0,
+        flood_budget: 0,
+        posterior_corruption_percent: 0,
+        ..model
+    }
+}
+
+/// Binary search for the fewest faulty validators that break `property`, model-checking
+/// `ResilienceModel` (up to `max_states` states) at each step. A stronger adversary can do
+/// everything a weaker one can, so once a property breaks it stays broken as the adversary grows.
+pub fn find_minimal_attack(validators: usize, slots: u64, power: AdversaryPower, property: &'static str, max_states: usize, threads: usize) -> Option<AttackReport> {
+    let counterexample = |faulty: usize| {
+        let result = attack_model(validators, slots, power, faulty)
+            .checker()
+            .threads(threads)
+            .target_state_count(max_states)
+            .spawn_dfs()
+            .join();
+        println!("  {} {:?} validator(s): {} states, {}", faulty, power, result.unique_state_count(),
+                 if result.discovery(property).is_some() { "counterexample" } else { "holds" });
+        result.discovery(property).map(|path| path.into_actions())
+    };
+
+    // Even the strongest adversary may not break the property. Safety is judged at honest
+    // validators, so a Byzantine adversary leaves at least one of them to deceive.
+    let strongest = match power {
+        AdversaryPower::Byzantine => validators - 1,
+        AdversaryPower::Crashed => validators,
+    };
+    let mut trace = counterexample(strongest)?;
+    let (mut low, mut high) = (0, strongest);
+    while low < high {
+        let mid = (low + high) / 2;
+        match counterexample(mid) {
+            Some(found) => {
+                high = mid;
+                trace = found;
+            }
+            None => low = mid + 1,
+        }
+    }
+
+    Some(AttackReport {
+        property: property.to_string(),
+        power,
+        faulty_validators: high,
+        stake_percent: budget_percent(validators, high),
+        trace,
+    })
+}
+
+/// Search for and report the weakest adversary that breaks a resilience property
+pub fn run_minimal_attack_search(validators: usize, slots: u64, power: AdversaryPower, property: &str, max_states: usize, threads: usize) -> Option<AttackReport> {
+    // The checker looks discoveries up by the model's own `'static` property names
+    let Some(property) = attack_model(validators, slots, power, 0).properties().into_iter()
+        .map(|p| p.name)
+        .find(|name| *name == property) else {
+        println!("❌ Unknown resilience property: {}", property);
+        return None;
+    };
+    println!("Searching for the minimal {:?} attack on {} with {} validators, {} slots",
+             power, property, validators, slots);
+
+    let report = find_minimal_attack(validators, slots, power, property, max_states, threads);
+    match &report {
+        Some(report) => report.print_summary(),
+        None => println!("✅ {} holds even with every validator {:?}", property, power),
+    }
+    report
+}
+
+// --- Failure Domains ---
+
+/// Outcome of model-checking one grouping of the validators into failure domains
+#[derive(Clone, Debug)]
+pub struct DomainStudyRow {
+    /// Validators per failure domain
+    pub domain_size: usize,
+    /// Stake of the largest domain as a percentage of the total
+    pub largest_domain_percent: u64,
+    /// Whether no conflicting certificates formed
+    pub safe: bool,
+    /// Whether every proposal was still certified once the network settled
+    pub live: bool,
+}
+
+/// Model-check safety and progress with the validators grouped into ever larger failure domains,
+/// any one of which may go down at once, and report the outcome against the largest domain's stake
+pub fn run_failure_domain_study(validators: usize, slots: u64, byzantine: usize, max_states: usize, threads: usize) -> Vec<DomainStudyRow> {
+    println!("Studying correlated failures with {} validators ({} Byzantine), {} slots",
+             validators, byzantine, slots);
+
+    let mut rows = Vec::new();
+    for domain_size in 1..=validators {
+        let model = ResilienceModel {
+            byzantine_count: byzantine,
+            failure_domains: ResilienceModel::contiguous_domains(validators, domain_size),
+            ..simulation_model(validators, slots)
+        };
+        let result = model
+            .checker()
+            .threads(threads)
+            .target_state_count(max_states)
+            .spawn_dfs()
+            .join();
+
+        let row = DomainStudyRow {
+            domain_size,
+            largest_domain_percent: budget_percent(validators, domain_size),
+            safe: result.discovery("safety_byzantine").is_none(),
+            live: result.discovery("progress").is_none(),
+        };
+        println!("  domains of {} ({}% stake): {} safety, {} liveness, {} states",
+                 row.domain_size, row.largest_domain_percent,
+                 if row.safe { "✅" } else { "❌" }, if row.live { "✅" } else { "❌" },
+                 result.unique_state_count());
+        rows.push(row);
+    }
+    rows
+}
+
+/// Report which conditional resilience properties were actually exercised
+pub fn check_vacuity(validators: usize, slots: u64, byzantine: usize, offline_budget_percent: u64, threads: usize) -> vacuity::VacuityReport {
+    println!("Checking resilience vacuity with {} validators ({} Byzantine), {} slots, {}% offline budget",
+             validators, byzantine, slots, offline_budget_percent);
+
+    let model = ResilienceModel {
+        validator_count: validators,
+        max_slot: slots,
+        byzantine_count: byzantine,
+        offline_budget_percent,
+        corruption_budget_percent: DEFAULT_CORRUPTION_BUDGET_PERCENT,
+        network_faults: 0,
+        crash_budget_percent: WHITEPAPER_CRASHED_PERCENT,
+        max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+        pool_capacity: DEFAULT_POOL_CAPACITY,
+        flood_budget: DEFAULT_FLOOD_BUDGET,
+        posterior_corruption_percent: DEFAULT_POSTERIOR_CORRUPTION_PERCENT,
+        weak_subjectivity: true,
+        failure_domains: Vec::new(),
+        recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
+    };
+
+    let report = vacuity::check_vacuity(model, ResilienceModel::antecedents(), DEFAULT_VACUITY_STATE_COUNT, threads);
+    report.print_summary();
+    report
+}
+
+// --- Randomized Simulation ---
+
+const SIMULATION_ADVERSARY_STEPS: usize = 200;
+const MAX_SIMULATION_STEPS: usize = 100_000;
+
+/// Final state and observations of one randomized run
+struct SimulationRun {
+    /// State reached when the run ended
+    state: ResilienceState,
+    /// Model actions taken
+    steps: usize,
+    /// Properties violated along the run
+    violations: Vec<&'static str>,
+    /// Settling steps until every slot decided during a partition was decided everywhere
+    recovered_after: Option<usize>,
+}
+
+impl SimulationRun {
+    /// Take an action and record every `always` property it breaks; false if the action does not apply
+    fn apply(&mut self, model: &ResilienceModel, properties: &[Property<ResilienceModel>], action: ResilienceAction) -> bool {
+        let Some(next_state) = model.next_state(&self.state, action) else { return false };
+        self.state = next_state;
+        self.steps += 1;
+        for property in properties {
+            if property.expectation == Expectation::Always
+                && !(property.condition)(model, &self.state)
+                && !self.violations.contains(&property.name)
+            {
+                self.violations.push(property.name);
+            }
+        }
+        true
+    }
+}
+
+/// Aggregate measurements over the randomized runs of one resilience scenario
+#[derive(Clone, Debug, Default)]
+pub struct ResilienceSimulationReport {
+    /// Runs simulated
+    pub runs: usize,
+    /// Model actions taken across all runs
+    pub steps: usize,
+    /// Runs in which each property was violated
+    pub violations: BTreeMap<&'static str, usize>,
+    /// Slots proposed across all runs
+    pub proposed_slots: usize,
+    /// Proposed slots certified at every responsive honest validator by the end of their run
+    pub certified_slots: usize,
+    /// Runs in which a partition took effect
+    pub partitioned_runs: usize,
+    /// Settling steps each partitioned run needed until its partition-time decisions converged
+    pub recovery_steps: Vec<usize>,
+}
+
+impl ResilienceSimulationReport {
+    fn record(&mut self, run: &SimulationRun) {
+        self.runs += 1;
+        self.steps += run.steps;
+        for property_name in &run.violations {
+            *self.violations.entry(*property_name).or_insert(0) += 1;
+        }
+        self.proposed_slots += run.state.block_proposals.len();
+        self.certified_slots += run.state.block_proposals.keys()
+            .filter(|slot| run.state.certified_everywhere(**slot))
+            .count();
+        if run.state.partition_occurred {
+            self.partitioned_runs += 1;
+            self.recovery_steps.extend(run.recovered_after);
+        }
+    }
+
+    /// Percentage of proposed slots certified everywhere
+    pub fn certification_rate(&self) -> f64 {
+        if self.proposed_slots == 0 {
+            return 100.0;
+        }
+        self.certified_slots as f64 * 100.0 / self.proposed_slots as f64
+    }
+
+    /// Print the scenario's measurements
+    pub fn print_summary(&self, scenario: &str) {
+        println!("{}: {} runs, {} steps", scenario, self.runs, self.steps);
+        println!("  Slots certified everywhere: {}/{} ({:.1}%)",
+                 self.certified_slots, self.proposed_slots, self.certification_rate());
+        if self.partitioned_runs > 0 {
+            println!("  Partitioned runs: {}, converged after heal: {}", self.partitioned_runs, self.recovery_steps.len());
+            if let (Some(min), Some(max)) = (self.recovery_steps.iter().min(), self.recovery_steps.iter().max()) {
+                let mean = self.recovery_steps.iter().sum::<usize>() as f64 / self.recovery_steps.len() as f64;
+                println!("  Recovery steps: min {}, mean {:.1}, max {}", min, mean, max);
+            }
+        }
+        if self.violations.is_empty() {
+            println!("  ✅ No property violated");
+        }
+        for (property_name, runs) in &self.violations {
+            println!("  ❌ {}: violated in {} run(s)", property_name, runs);
+        }
+    }
+}
+
+/// Deliveries, proposals and slot changes, which every scenario interleaves with its adversary
+fn is_honest_step(action: &ResilienceAction) -> bool {
+    matches!(action, ResilienceAction::DeliverMessage { .. }
+        | ResilienceAction::ProposeBlock { .. }
+        | ResilienceAction::AdvanceSlot)
+}
+
+/// Interleave honest steps with the scenario's adversarial actions at random for a while, then
+/// stop the adversary, heal any partition and let the network settle
+fn simulate_run(model: &ResilienceModel, rng: &mut StdRng, adversary: fn(&ResilienceAction) -> bool) -> SimulationRun {
+    let properties = model.properties();
+    let mut run = SimulationRun {
+        state: model.init_states().remove(0),
+        steps: 0,
+        violations: Vec::new(),
+        recovered_after: None,
+    };
+
+    for _ in 0..SIMULATION_ADVERSARY_STEPS {
+        let mut actions = Vec::new();
+        model.actions(&run.state, &mut actions);
+        actions.retain(|a| is_honest_step(a) || adversary(a));
+        let Some(action) = actions.choose(rng).cloned() else { break };
+        if !run.apply(model, &properties, action) { break; }
+    }
+
+    let settle_start = run.steps;
+    while run.steps < MAX_SIMULATION_STEPS {
+        let mut actions = Vec::new();
+        model.actions(&run.state, &mut actions);
+        match actions.iter().position(|a| matches!(a, ResilienceAction::RecoverFromPartition { .. })) {
+            Some(recovery) => actions = vec![actions.swap_remove(recovery)],
+            None => actions.retain(|a| matches!(a, ResilienceAction::DeliverMessage { .. } | ResilienceAction::ProposeBlock { .. })),
+        }
+        let Some(action) = actions.choose(rng).cloned() else { break };
+        if !run.apply(model, &properties, action) { break; }
+        if run.recovered_after.is_none() && run.state.healed() && run.state.partition_decisions_converged() {
+            run.recovered_after = Some(run.steps - settle_start);
+        }
+    }
+    run
+}
+
+/// Honest network without faults, the starting point of every scenario
+fn simulation_model(validators: usize, slots: u64) -> ResilienceModel {
+    ResilienceModel {
+        validator_count: validators,
+        max_slot: slots,
+        byzantine_count: 0,
+        offline_budget_percent: 0,
+        corruption_budget_percent: 0,
+        network_faults: 0,
+        crash_budget_percent: 0,
+        max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+        pool_capacity: DEFAULT_POOL_CAPACITY,
+        flood_budget: DEFAULT_FLOOD_BUDGET,
+        posterior_corruption_percent: 0,
+        weak_subjectivity: true,
+        failure_domains: Vec::new(),
+        recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
+    }
+}
+
+fn run_scenario(scenario: &str, model: ResilienceModel, runs: u64, seed: u64, adversary: fn(&ResilienceAction) -> bool) -> ResilienceSimulationReport {
+    let mut report = ResilienceSimulationReport::default();
+    for run in 0..runs {
+        let mut rng = StdRng::seed_from_u64(seed + run);
+        report.record(&simulate_run(&model, &mut rng, adversary));
+    }
+    report.print_summary(scenario);
+    report
+}
+
+/// Simulate Byzantine validators voting against proposals, splitting their votes, flooding
+/// pools and corrupting honest validators, and count the runs that lose safety
+pub fn simulate_safety_under_attack(validators: usize, slots: u64, byzantine: usize, runs: u64, seed: u64) -> ResilienceSimulationReport {
+    println!("Simulating safety under attack: {} validators ({} Byzantine), {} slots, {} runs, seed {}",
+             validators, byzantine, slots, runs, seed);
+
+    let model = ResilienceModel {
+        byzantine_count: byzantine,
+        corruption_budget_percent: DEFAULT_CORRUPTION_BUDGET_PERCENT,
+        flood_budget: 1,
+        ..simulation_model(validators, slots)
+    };
+    run_scenario("Safety under attack", model, runs, seed, |action| matches!(action,
+        ResilienceAction::CreateConflictingVote { .. }
+            | ResilienceAction::SplitVote { .. }
+            | ResilienceAction::FloodVotes { .. }
+            | ResilienceAction::CorruptValidator { .. }))
+}
+
+/// Simulate validators going offline and coming back within the offline budget, and measure
+/// how many proposed slots end up certified at every responsive honest validator
+pub fn simulate_liveness_with_offline_nodes(validators: usize, slots: u64, offline_budget_percent: u64, runs: u64, seed: u64) -> ResilienceSimulationReport {
+    println!("Simulating liveness with offline validators: {} validators, {}% offline budget, {} slots, {} runs, seed {}",
+             validators, offline_budget_percent, slots, runs, seed);
+
+    let model = ResilienceModel {
+        offline_budget_percent,
+        ..simulation_model(validators, slots)
+    };
+    run_scenario("Liveness with offline validators", model, runs, seed, |action| matches!(action,
+        ResilienceAction::GoOffline { .. } | ResilienceAction::ComeOnline { .. }))
+}
+
+/// Simulate partitions over random validator subsets, heal them, and measure how many settling
+/// steps it takes until every slot decided during a partition is decided everywhere
+pub fn simulate_network_partition_recovery(validators: usize, slots: u64, runs: u64, seed: u64) -> ResilienceSimulationReport {
+    println!("Simulating partition recovery: {} validators, {} slots, {} runs, seed {}",
+             validators, slots, runs, seed);
+
+    run_scenario("Partition recovery", simulation_model(validators, slots), runs, seed, |action| matches!(action,
+        ResilienceAction::TriggerPartition { .. } | ResilienceAction::RecoverFromPartition { .. }))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::limits::{Cutoff, ResourceLimits};
+    use std::time::{Duration, Instant};
 
     #[test]
     fn test_resilience_state_creation() {
         let state = ResilienceState::new(3, 1);
         assert_eq!(state.validators.len(), 3);
-        assert_eq!(state.validators[0].is_byzantine, true);
-        assert_eq!(state.validators[2].is_byzantine, false);
+        assert!(state.validators[0].is_byzantine);
+        assert!(!state.validators[2].is_byzantine);
     }
 
     #[test]
@@ -553,7 +1829,282 @@ mod tests {
         voters.insert(2); // 3/3 validators = 100% > 60%
         state.validators[0] = validator;
         
-        assert!(state.can_certify(1, 100));
+        assert!(state.can_certify(&state.validators[0].vote_pool, 1, 100));
+        assert!(!state.can_certify(&state.validators[1].vote_pool, 1, 100));
+    }
+
+    #[test]
+    fn test_state_limit_stops_search() {
+        // An exhaustive search of this configuration runs far longer than the test
+        let model = simulation_model(4, 2);
+        let options = CheckOptions { limits: ResourceLimits { max_states: Some(200), timeout: None }, ..Default::default() };
+        let start = Instant::now();
+        let checker = options.checker(model).spawn_bfs().join();
+        assert!(start.elapsed() < Duration::from_secs(10), "took {:.2?}", start.elapsed());
+        assert_eq!(options.cutoff(&checker), Some(Cutoff::States));
+        assert!(checker.model().expanded() <= 200 + options.threads());
+    }
+
+    #[test]
+    fn test_offline_budget() {
+        let model = ResilienceModel {
+            validator_count: 5,
+            max_slot: 1,
+            byzantine_count: 1,
+            offline_budget_percent: DEFAULT_OFFLINE_BUDGET_PERCENT,
+            corruption_budget_percent: DEFAULT_CORRUPTION_BUDGET_PERCENT,
+            network_faults: 0,
+            crash_budget_percent: DEFAULT_CRASH_BUDGET_PERCENT,
+            max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+            pool_capacity: DEFAULT_POOL_CAPACITY,
+            flood_budget: DEFAULT_FLOOD_BUDGET,
+            posterior_corruption_percent: DEFAULT_POSTERIOR_CORRUPTION_PERCENT,
+            weak_subjectivity: true,
+            failure_domains: Vec::new(),
+            recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
+        };
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, ResilienceAction::GoOffline { validator: 4 }).unwrap();
+
+        // A second validator would exceed the 20% budget; messages for the offline one wait
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(actions.contains(&ResilienceAction::ComeOnline { validator: 4 }));
+        assert!(!actions.iter().any(|a| matches!(a, ResilienceAction::GoOffline { .. })));
+
+        state = model.next_state(&state, ResilienceAction::ProposeBlock { slot: 1, proposer: 1 }).unwrap();
+        let liveness = model.property("liveness_non_responsive");
+        loop {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            let Some(action) = actions.into_iter().find(|a| matches!(a, ResilienceAction::DeliverMessage { .. })) else { break };
+            state = model.next_state(&state, action).unwrap();
+        }
+        assert!(state.network.iter().all(|m| m.dst == 4));
+        assert!(state.validators.iter().skip(1).take(3).all(|v| v.certificates.contains_key(&1)));
+        assert!((liveness.condition)(&model, &state));
+
+        // Certificates missing at a responsive honest validator break liveness
+        state.validators[2].certificates.clear();
+        assert!(!(liveness.condition)(&model, &state));
+    }
+
+    #[test]
+    fn test_canonical_proposer() {
+        let model = ResilienceModel {
+            validator_count: 4,
+            max_slot: 2,
+            byzantine_count: 1,
+            offline_budget_percent: 25,
+            corruption_budget_percent: 0,
+            network_faults: 0,
+            crash_budget_percent: 0,
+            max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+            pool_capacity: DEFAULT_POOL_CAPACITY,
+            flood_budget: DEFAULT_FLOOD_BUDGET,
+            posterior_corruption_percent: DEFAULT_POSTERIOR_CORRUPTION_PERCENT,
+            weak_subjectivity: true,
+            failure_domains: Vec::new(),
+            recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
+        };
+        let mut state = model.init_states().remove(0);
+
+        // Validator 0 is Byzantine and never stands in for the proposers
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(actions.contains(&ResilienceAction::ProposeBlock { slot: 1, proposer: 1 }));
+        assert!(!actions.iter().any(|a| matches!(a, ResilienceAction::ProposeBlock { proposer: 0, .. })));
+
+        state = model.next_state(&state, ResilienceAction::GoOffline { validator: 1 }).unwrap();
+
+        // One proposal per open slot, from the first responsive honest validator
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        let proposals: Vec<_> = actions.into_iter()
+            .filter(|a| matches!(a, ResilienceAction::ProposeBlock { .. }))
+            .collect();
+        assert_eq!(proposals, (0..=2)
+            .map(|slot| ResilienceAction::ProposeBlock { slot, proposer: 2 })
+            .collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_twenty_plus_twenty() {
+        let model = ResilienceModel::twenty_plus_twenty(5, 1);
+        assert_eq!(model.byzantine_count, 1);
+        let mut state = model.init_states().remove(0);
+
+        // Only honest validators crash, and only up to the 20% crash budget
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(!actions.contains(&ResilienceAction::Crash { validator: 0 }));
+        assert!(!actions.iter().any(|a| matches!(a, ResilienceAction::GoOffline { .. } | ResilienceAction::CorruptValidator { .. })));
+        state = model.next_state(&state, ResilienceAction::Crash { validator: 4 }).unwrap();
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(!actions.iter().any(|a| matches!(a, ResilienceAction::Crash { .. } | ResilienceAction::ComeOnline { .. })));
+
+        // The three remaining honest validators hold exactly the 60% certificate threshold
+        state = model.next_state(&state, ResilienceAction::ProposeBlock { slot: 1, proposer: 1 }).unwrap();
+        loop {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            let Some(action) = actions.into_iter().find(|a| matches!(a, ResilienceAction::DeliverMessage { .. })) else { break };
+            state = model.next_state(&state, action).unwrap();
+        }
+        assert!(state.twenty_plus_twenty_applies());
+        assert!(state.validators.iter().skip(1).take(3).all(|v| v.certificates.contains_key(&1)));
+        let liveness = model.property("liveness_20_plus_20");
+        assert!((liveness.condition)(&model, &state));
+
+        // One more crash leaves the honest stake short of a certificate, outside the claim
+        state.validators[3].is_crashed = true;
+        state.validators[3].is_responsive = false;
+        assert!(!state.twenty_plus_twenty_applies());
+    }
+
+    #[test]
+    fn test_minimal_attack_search() {
+        assert_eq!(budget_percent(5, 3), 60);
+        assert_eq!(attack_model(3, 0, AdversaryPower::Byzantine, 2).byzantine_count, 2);
+
+        // Certificates need 60% of the stake, so two of three validators must fail to stall progress
+        for power in [AdversaryPower::Byzantine, AdversaryPower::Crashed] {
+            let report = find_minimal_attack(3, 0, power, "progress", DEFAULT_VACUITY_STATE_COUNT, num_cpus::get()).unwrap();
+            assert_eq!(report.faulty_validators, 2);
+            assert_eq!(report.stake_percent, 67);
+            assert!(report.trace.iter().any(|a| matches!(a, ResilienceAction::ProposeBlock { .. })));
+        }
+
+        // Honest validators never vote against the proposal, so a conflicting certificate needs 60% Byzantine stake
+        let report = find_minimal_attack(3, 0, AdversaryPower::Byzantine, "safety_byzantine", DEFAULT_VACUITY_STATE_COUNT, num_cpus::get()).unwrap();
+        assert_eq!(report.faulty_validators, 2);
+        assert!(report.trace.iter().any(|a| matches!(a, ResilienceAction::SplitVote { .. })));
+    }
+
+    #[test]
+    fn test_failure_domains() {
+        let domains = ResilienceModel::contiguous_domains(5, 2);
+        assert_eq!(domains, vec![BTreeSet::from([0, 1]), BTreeSet::from([2, 3]), BTreeSet::from([4])]);
+
+        // An outage takes the whole domain down, and its members only come back together
+        let model = ResilienceModel {
+            offline_budget_percent: 20,
+            failure_domains: domains,
+            ..simulation_model(5, 0)
+        };
+        let state = model.init_states().remove(0);
+        let state = model.next_state(&state, ResilienceAction::DomainOutage { domain: 0 }).unwrap();
+        assert_eq!(state.offline_stake(), TOTAL_STAKE * 40 / 100);
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(!actions.iter().any(|a| matches!(a, ResilienceAction::ComeOnline { .. } | ResilienceAction::DomainOutage { .. })));
+        let state = model.next_state(&state, ResilienceAction::DomainRecovery { domain: 0 }).unwrap();
+        assert_eq!(state.offline_stake(), 0);
+
+        // Losing one of three validators leaves 67% of the stake; losing two leaves the third unable to certify
+        let rows = run_failure_domain_study(3, 0, 0, DEFAULT_VACUITY_STATE_COUNT, num_cpus::get());
+        assert!(rows.iter().all(|row| row.safe));
+        assert!(rows[0].live);
+        assert!(!rows[1].live);
+    }
+
+    #[test]
+    fn test_two_sided_partition() {
+        let model = ResilienceModel {
+            validator_count: 4,
+            max_slot: 1,
+            byzantine_count: 0,
+            offline_budget_percent: 0,
+            corruption_budget_percent: 0,
+            network_faults: 0,
+            crash_budget_percent: 0,
+            max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+            pool_capacity: DEFAULT_POOL_CAPACITY,
+            flood_budget: DEFAULT_FLOOD_BUDGET,
+            posterior_corruption_percent: DEFAULT_POSTERIOR_CORRUPTION_PERCENT,
+            weak_subjectivity: true,
+            failure_domains: Vec::new(),
+            recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
+        };
+        let mut state = model.init_states().remove(0);
+
+        // Arbitrary subsets are isolated; balanced splits are named once, by the side holding validator 0
+        let trigger = |affected: &[ActorId], two_sided| ResilienceAction::TriggerPartition {
+            partition_id: 1,
+            affected_validators: affected.iter().copied().collect(),
+            two_sided,
+        };
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(actions.contains(&trigger(&[1, 3], false)));
+        assert!(actions.contains(&trigger(&[0, 2], true)));
+        assert!(!actions.contains(&trigger(&[1, 3], true)));
+        assert!(!actions.contains(&trigger(&[0, 1, 2], false)));
+
+        // Each half keeps voting internally, but neither reaches the 60% threshold
+        let settle = |mut state: ResilienceState| loop {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            let Some(action) = actions.into_iter().find(|a| matches!(a, ResilienceAction::DeliverMessage { .. })) else { return state };
+            state = model.next_state(&state, action).unwrap();
+        };
+        state = settle(model.next_state(&state, trigger(&[0, 1], true)).unwrap());
+        state = settle(model.next_state(&state, ResilienceAction::ProposeBlock { slot: 1, proposer: 0 }).unwrap());
+        assert!(state.validators.iter().all(|v| !v.is_partitioned && v.certificates.is_empty()));
+        assert_eq!(state.validators[1].vote_pool[&(1, 1000)], [0, 1].into_iter().collect());
+        assert!(!state.validators[2].vote_pool.contains_key(&(1, 1000)));
+    }
+
+    #[test]
+    fn test_partition_heal_convergence() {
+        let model = ResilienceModel {
+            validator_count: 4,
+            max_slot: 1,
+            byzantine_count: 0,
+            offline_budget_percent: 0,
+            corruption_budget_percent: 0,
+            network_faults: 0,
+            crash_budget_percent: 0,
+            max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+            pool_capacity: DEFAULT_POOL_CAPACITY,
+            flood_budget: DEFAULT_FLOOD_BUDGET,
+            posterior_corruption_percent: DEFAULT_POSTERIOR_CORRUPTION_PERCENT,
+            weak_subjectivity: true,
+            failure_domains: Vec::new(),
+            recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
+        };
+        let settle = |mut state: ResilienceState| loop {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            let Some(action) = actions.into_iter().find(|a| matches!(a, ResilienceAction::DeliverMessage { .. })) else { return state };
+            state = model.next_state(&state, action).unwrap();
+        };
+        let mut state = model.init_states().remove(0);
+
+        // The majority decides slot 1 while validator 3 is cut off; its messages are held back
+        state = settle(model.next_state(&state, ResilienceAction::TriggerPartition {
+            partition_id: 1,
+            affected_validators: [3].into_iter().collect(),
+            two_sided: false,
+        }).unwrap());
+        state = settle(model.next_state(&state, ResilienceAction::ProposeBlock { slot: 1, proposer: 0 }).unwrap());
+        assert!(state.validators.iter().take(3).all(|v| v.certificates.contains_key(&1)));
+        assert!(state.validators[3].certificates.is_empty());
+        assert!(state.buffered.iter().all(|(_, m)| m.dst == 3));
+        assert!(!state.buffered.is_empty());
+        assert_eq!(state.decided_during_partition, [1].into_iter().collect());
+
+        // After the heal the held-back proposal and votes are replayed and validator 3 catches up
+        state = settle(model.next_state(&state, ResilienceAction::RecoverFromPartition { partition_id: 1 }).unwrap());
+        assert!(state.healed());
+        assert!(state.partition_decisions_converged());
+        let convergence = model.property("partition_heal_convergence");
+        assert!((convergence.condition)(&model, &state));
+
+        // A validator still missing the decision breaks convergence
+        state.validators[3].certificates.clear();
+        assert!(!(convergence.condition)(&model, &state));
     }
 
     #[test]
@@ -562,7 +2113,222 @@ mod tests {
         let mut affected = BTreeSet::new();
         affected.insert(0);
         affected.insert(1); // 2/3 validators = 66% > 60%
-        
         assert!(state.is_partition_critical(&affected));
     }
+
+    #[test]
+    fn test_bounded_partition_recovery() {
+        let model = ResilienceModel {
+            recovery_rounds: 1,
+            ..simulation_model(4, 1)
+        };
+        let settle = |mut state: ResilienceState| loop {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            let Some(action) = actions.into_iter().find(|a| matches!(a, ResilienceAction::DeliverMessage { .. })) else { return state };
+            state = model.next_state(&state, action).unwrap();
+        };
+        let mut state = model.init_states().remove(0);
+        state = settle(model.next_state(&state, ResilienceAction::TriggerPartition {
+            partition_id: 1,
+            affected_validators: [3].into_iter().collect(),
+            two_sided: false,
+        }).unwrap());
+        state = settle(model.next_state(&state, ResilienceAction::ProposeBlock { slot: 1, proposer: 0 }).unwrap());
+        assert!(state.recovery.is_none());
+
+        // The heal starts the first round; validator 3 only receives the held-back votes in the second
+        state = model.next_state(&state, ResilienceAction::RecoverFromPartition { partition_id: 1 }).unwrap();
+        assert_eq!(state.recovery.as_ref().map(|r| r.rounds), Some(1));
+        let recovery = model.property("partition_recovery");
+        assert!((recovery.condition)(&model, &state));
+        let mut rounds = Vec::new();
+        loop {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            let Some(action) = actions.into_iter().find(|a| matches!(a, ResilienceAction::DeliverMessage { .. })) else { break };
+            state = model.next_state(&state, action).unwrap();
+            rounds.extend(state.recovery.as_ref().map(|r| r.rounds));
+        }
+        assert!(state.recovery.is_none());
+        assert!(state.validators[3].certificates.contains_key(&1));
+        assert_eq!(rounds.iter().max(), Some(&2));
+
+        // The bounded search always needs the round the heal starts; runs that propose behind a
+        // partition lie too deep for it to reach the second
+        let worst_case = measure_recovery_rounds(3, 0, 8, DEFAULT_VACUITY_STATE_COUNT, num_cpus::get()).unwrap();
+        assert!((1..=8).contains(&worst_case));
+    }
+
+    #[test]
+    fn test_resilience_simulations() {
+        // Runs are reproducible from their seed
+        let attack = simulate_safety_under_attack(5, 1, 1, 3, 7);
+        assert_eq!(attack.runs, 3);
+        assert_eq!(attack.steps, simulate_safety_under_attack(5, 1, 1, 3, 7).steps);
+        assert!(!attack.violations.contains_key("safety_byzantine"));
+
+        // One validator in five offline at a time never keeps a proposed slot from being certified
+        let offline = simulate_liveness_with_offline_nodes(5, 1, 20, 3, 7);
+        assert!(offline.proposed_slots > 0);
+        assert_eq!(offline.certified_slots, offline.proposed_slots);
+        assert!(!offline.violations.contains_key("liveness_non_responsive"));
+
+        // Every partitioned run converges once the partition heals
+        let partition = simulate_network_partition_recovery(4, 1, 3, 7);
+        assert_eq!(partition.recovery_steps.len(), partition.partitioned_runs);
+        assert!(!partition.violations.contains_key("partition_heal_convergence"));
+    }
+
+    #[test]
+    fn test_vote_flooding() {
+        let model = ResilienceModel {
+            validator_count: 5,
+            max_slot: 1,
+            byzantine_count: 1,
+            offline_budget_percent: 0,
+            corruption_budget_percent: 0,
+            network_faults: 0,
+            crash_budget_percent: 0,
+            max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+            pool_capacity: 2,
+            flood_budget: 1,
+            posterior_corruption_percent: DEFAULT_POSTERIOR_CORRUPTION_PERCENT,
+            weak_subjectivity: true,
+            failure_domains: Vec::new(),
+            recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
+        };
+        let settle = |mut state: ResilienceState| loop {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            let Some(action) = actions.into_iter().find(|a| matches!(a, ResilienceAction::DeliverMessage { .. })) else { return state };
+            state = model.next_state(&state, action).unwrap();
+        };
+        let mut state = model.init_states().remove(0);
+
+        // Four spurious votes overflow the flooder's share of the pool, so its lowest-slot ones go
+        state = settle(model.next_state(&state, ResilienceAction::FloodVotes { byzantine_validator: 0, target: 1 }).unwrap());
+        assert_eq!(state.validators[1].votes_held(0), 2);
+        assert!(state.validators[1].vote_pool.contains_key(&(1, 1901)));
+        assert!(!state.validators[1].vote_pool.contains_key(&(0, 900)));
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(!actions.iter().any(|a| matches!(a, ResilienceAction::FloodVotes { .. })));
+
+        // Honest votes never compete with the flood for space, so both slots still certify
+        for slot in 0..=1 {
+            state = settle(model.next_state(&state, ResilienceAction::ProposeBlock { slot, proposer: 0 }).unwrap());
+        }
+        assert!(state.validators.iter().skip(1).all(|v| v.certificates.len() == 2));
+        for name in ["bounded_vote_pool", "certification_under_flood", "safety_byzantine"] {
+            assert!((model.property(name).condition)(&model, &state), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_split_vote() {
+        let model = |byzantine_count| ResilienceModel {
+            validator_count: 5,
+            max_slot: 1,
+            byzantine_count,
+            offline_budget_percent: 0,
+            corruption_budget_percent: 0,
+            network_faults: 0,
+            crash_budget_percent: 0,
+            max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+            pool_capacity: DEFAULT_POOL_CAPACITY,
+            flood_budget: DEFAULT_FLOOD_BUDGET,
+            posterior_corruption_percent: DEFAULT_POSTERIOR_CORRUPTION_PERCENT,
+            weak_subjectivity: true,
+            failure_domains: Vec::new(),
+            recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
+        };
+        let split = |state: &ResilienceState, model: &ResilienceModel, voters: std::ops::Range<ActorId>| {
+            let mut state = state.clone();
+            for byzantine_validator in voters {
+                let action = ResilienceAction::SplitVote { byzantine_validator, slot: 1, left: [3].into_iter().collect() };
+                state = model.next_state(&state, action).unwrap();
+            }
+            loop {
+                let mut actions = Vec::new();
+                model.actions(&state, &mut actions);
+                let Some(action) = actions.into_iter().find(|a| matches!(a, ResilienceAction::DeliverMessage { .. })) else { return state };
+                state = model.next_state(&state, action).unwrap();
+            }
+        };
+
+        // With 20% Byzantine stake the conflicting block gathers too few votes anywhere
+        let within = model(1);
+        let mut state = within.init_states().remove(0);
+        state = within.next_state(&state, ResilienceAction::ProposeBlock { slot: 1, proposer: 0 }).unwrap();
+        state = split(&state, &within, 0..1);
+        assert!(state.split_vote_sent);
+        assert!(state.validators.iter().skip(1).all(|v| v.certificates.get(&1) == Some(&1000)));
+        assert!((within.property("quorum_intersection").condition)(&within, &state));
+
+        // With 60% Byzantine stake the two honest validators end up certifying different blocks
+        let beyond = model(3);
+        let mut state = beyond.init_states().remove(0);
+        state = beyond.next_state(&state, ResilienceAction::ProposeBlock { slot: 1, proposer: 0 }).unwrap();
+        state = split(&state, &beyond, 0..3);
+        assert_eq!(state.validators[3].certificates.get(&1), Some(&1000));
+        assert_eq!(state.validators[4].certificates.get(&1), Some(&1999));
+        assert!(!(beyond.property("safety_byzantine").condition)(&beyond, &state));
+        assert!((beyond.property("quorum_intersection").condition)(&beyond, &state));
+    }
+
+    #[test]
+    fn test_long_range_attack() {
+        let model = |weak_subjectivity| ResilienceModel {
+            validator_count: 5,
+            max_slot: 1,
+            byzantine_count: 0,
+            offline_budget_percent: 0,
+            corruption_budget_percent: 0,
+            network_faults: 0,
+            crash_budget_percent: 0,
+            max_partition_size: DEFAULT_MAX_PARTITION_SIZE,
+            pool_capacity: DEFAULT_POOL_CAPACITY,
+            flood_budget: DEFAULT_FLOOD_BUDGET,
+            posterior_corruption_percent: 60,
+            weak_subjectivity,
+            failure_domains: Vec::new(),
+            recovery_rounds: DEFAULT_RECOVERY_ROUNDS,
+        };
+        // Deliver forged votes ahead of everything else, the worst order for a lagging validator
+        let settle = |model: &ResilienceModel, mut state: ResilienceState| loop {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            actions.retain(|a| matches!(a, ResilienceAction::DeliverMessage { .. }));
+            let forged = actions.iter().position(|a| matches!(a,
+                ResilienceAction::DeliverMessage { msg: MessageInTransit { msg: ResilienceMessage::Vote { hash: 1998, .. }, .. } }));
+            let Some(index) = forged.or((!actions.is_empty()).then_some(0)) else { return state };
+            state = model.next_state(&state, actions.swap_remove(index)).unwrap();
+        };
+
+        for weak_subjectivity in [true, false] {
+            let model = model(weak_subjectivity);
+            let mut state = model.init_states().remove(0);
+
+            // Validators 0-2 finalize slot 1 while 3 and 4 are cut off, then unstake
+            state = settle(&model, model.next_state(&state, ResilienceAction::TriggerPartition {
+                partition_id: 1,
+                affected_validators: [3, 4].into_iter().collect(),
+                two_sided: false,
+            }).unwrap());
+            state = settle(&model, model.next_state(&state, ResilienceAction::ProposeBlock { slot: 1, proposer: 0 }).unwrap());
+            for validator in 0..3 {
+                state = model.next_state(&state, ResilienceAction::Retire { validator }).unwrap();
+            }
+            assert_eq!(state.checkpoint, [(1, 1000)].into_iter().collect());
+
+            // Their leaked keys sign an alternative slot 1 that reaches the lagging validators first
+            state = model.next_state(&state, ResilienceAction::ForgeHistory { slot: 1 }).unwrap();
+            state = settle(&model, model.next_state(&state, ResilienceAction::RecoverFromPartition { partition_id: 1 }).unwrap());
+            let reversion = model.property("no_long_range_reversion");
+            assert_eq!((reversion.condition)(&model, &state), weak_subjectivity);
+            let expected = if weak_subjectivity { 1000 } else { 1998 };
+            assert_eq!(state.validators[3].certificates.get(&1), Some(&expected));
+        }
+    }
 }
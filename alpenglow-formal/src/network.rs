@@ -0,0 +1,161 @@
+//! Bounded network adversary shared by the formal models.
+//! Each model's network is a set of in-flight messages that are delivered exactly once, in
+//! arbitrary order. [`NetworkFaults`] additionally lets an adversary drop an in-flight message,
+//! replay one that was already delivered, or hold one back indefinitely (releasing it later, or
+//! never), for at most a fixed number of messages per execution.
+
+use std::collections::BTreeSet;
+
+/// An adversarial action on the network
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum NetworkFault<M> {
+    /// Remove an in-flight message without delivering it
+    Drop(M),
+    /// Put an already delivered message back in flight
+    Duplicate(M),
+    /// Hold an in-flight message back until it is released, if ever
+    Delay(M),
+    /// Put a held-back message back in flight
+    Release(M),
+}
+
+impl<M> NetworkFault<M> {
+    /// The message the fault acts on
+    pub fn message(&self) -> &M {
+        match self {
+            NetworkFault::Drop(message)
+            | NetworkFault::Duplicate(message)
+            | NetworkFault::Delay(message)
+            | NetworkFault::Release(message) => message,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NetworkFault::Drop(_) => "drop",
+            NetworkFault::Duplicate(_) => "duplicate",
+            NetworkFault::Delay(_) => "delay",
+            NetworkFault::Release(_) => "release",
+        }
+    }
+}
+
+/// Network adversary state: its remaining budget and the messages it can act on
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct NetworkFaults<M: Ord> {
+    /// Most messages the adversary may drop, duplicate or delay
+    budget: usize,
+    /// Faults injected so far
+    used: usize,
+    /// Messages delivered while budget remains, which the adversary may replay
+    delivered: BTreeSet<M>,
+    /// Messages currently held back
+    delayed: BTreeSet<M>,
+    /// Messages dropped so far
+    dropped: BTreeSet<M>,
+}
+
+impl<M: Clone + Ord> NetworkFaults<M> {
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            used: 0,
+            delivered: BTreeSet::new(),
+            delayed: BTreeSet::new(),
+            dropped: BTreeSet::new(),
+        }
+    }
+
+    /// Faults the adversary may still inject
+    pub fn remaining(&self) -> usize {
+        self.budget - self.used
+    }
+
+    /// Whether any message was lost so far: dropped, or still held back
+    pub fn messages_withheld(&self) -> bool {
+        !self.dropped.is_empty() || !self.delayed.is_empty()
+    }
+
+    /// Messages lost so far: dropped, or still held back
+    pub fn withheld(&self) -> impl Iterator<Item = &M> {
+        self.dropped.iter().chain(&self.delayed)
+    }
+
+    /// Messages currently held back
+    pub fn delayed(&self) -> impl Iterator<Item = &M> {
+        self.delayed.iter()
+    }
+
+    /// Remember a delivered message so it can be replayed while the adversary has budget
+    pub fn record_delivery(&mut self, msg: &M) {
+        if self.remaining() > 0 {
+            self.delivered.insert(msg.clone());
+        }
+    }
+
+    /// The same adversary state with every message it tracks rewritten, e.g. to rename validators
+    pub fn map(&self, f: impl Fn(&M) -> M) -> Self {
+        Self {
+            budget: self.budget,
+            used: self.used,
+            delivered: self.delivered.iter().map(&f).collect(),
+            delayed: self.delayed.iter().map(&f).collect(),
+            dropped: self.dropped.iter().map(&f).collect(),
+        }
+    }
+
+    /// Faults the adversary can inject given the messages currently in flight
+    pub fn faults(&self, network: &BTreeSet<M>) -> Vec<NetworkFault<M>> {
+        let mut faults = Vec::new();
+        if self.remaining() > 0 {
+            for msg in network {
+                faults.push(NetworkFault::Drop(msg.clone()));
+                faults.push(NetworkFault::Delay(msg.clone()));
+            }
+            for msg in self.delivered.iter().filter(|msg| !network.contains(*msg)) {
+                faults.push(NetworkFault::Duplicate(msg.clone()));
+            }
+        }
+        // Releasing a held-back message is free: the delay was already paid for
+        for msg in &self.delayed {
+            faults.push(NetworkFault::Release(msg.clone()));
+        }
+        faults
+    }
+
+    /// Apply a fault to the network, returning false if it is not enabled
+    pub fn apply(&mut self, network: &mut BTreeSet<M>, fault: NetworkFault<M>) -> bool {
+        match fault {
+            NetworkFault::Release(msg) => {
+                if !self.delayed.remove(&msg) {
+                    return false;
+                }
+                network.insert(msg);
+                return true;
+            }
+            _ if self.remaining() == 0 => return false,
+            NetworkFault::Drop(msg) => {
+                if !network.remove(&msg) {
+                    return false;
+                }
+                self.dropped.insert(msg);
+            }
+            NetworkFault::Duplicate(msg) => {
+                if !self.delivered.contains(&msg) || !network.insert(msg) {
+                    return false;
+                }
+            }
+            NetworkFault::Delay(msg) => {
+                if !network.remove(&msg) {
+                    return false;
+                }
+                self.delayed.insert(msg);
+            }
+        }
+        self.used += 1;
+        if self.remaining() == 0 {
+            self.delivered.clear(); // Nothing left to replay with
+        }
+        true
+    }
+}
@@ -0,0 +1,296 @@
+//! Checker options shared by every model-checking entry point.
+//! Bundles the exploration bounds, resource limits, the properties to check, progress reporting,
+//! coverage and the scheduling mode so the binaries parse them once, through
+//! [`crate::cli::CheckArgs`], and each model's check applies and reports them the same way.
+
+use crate::bounds::{Bounded, BoundedModel, ExplorationBounds, PropertySelection};
+use crate::html::{self, RunReport};
+use crate::junit::{self, TestSuite};
+use crate::limits::{Cutoff, Outcome, ResourceLimits};
+use crate::output::{self, Record};
+use crate::progress::Progress;
+use crate::stats::CheckStats;
+use stateright::{Checker, CheckerBuilder, Model};
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::path::PathBuf;
+use tracing::{info, warn};
+use tracing_subscriber::EnvFilter;
+
+/// Log filter used when neither `--trace-filter` nor `RUST_LOG` is given
+const DEFAULT_TRACE_FILTER: &str = "info";
+
+/// How a model-checking run explores and reports
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CheckOptions {
+    pub bounds: ExplorationBounds,
+    /// States and time after which exploration stops, leaving unproved properties inconclusive
+    pub limits: ResourceLimits,
+    /// Properties evaluated on every state; the others cost nothing
+    pub properties: PropertySelection,
+    pub progress: Progress,
+    /// Worker threads a run may use, or `None` for one per CPU
+    pub threads: Option<usize>,
+    /// Explore on a single worker thread. The checker's fingerprints are stable and no model
+    /// enumerates its actions from a hash map, so one thread visits states in the same order on
+    /// every run: state counts, discoveries and their traces repeat across runs with the same
+    /// parameters, unless `--timeout-secs` cuts the search at a point that depends on the machine
+    pub deterministic: bool,
+    /// Count actions and property outcomes and print them after the run
+    pub coverage: bool,
+    /// JUnit XML file collecting the property results of every model checked by the process
+    pub junit: Option<PathBuf>,
+    /// Directory receiving one self-contained HTML report per model checked
+    pub report_html: Option<PathBuf>,
+    /// `tracing` filter directives, such as `alpenglow_formal::bounds=trace` to follow every
+    /// action applied and property evaluated
+    pub trace_filter: Option<String>,
+}
+
+impl CheckOptions {
+    /// Install the log subscriber: `--trace-filter` takes precedence over `RUST_LOG`, and the
+    /// default shows progress and status lines only
+    pub fn init_tracing(&self) {
+        let filter = match &self.trace_filter {
+            Some(directives) => EnvFilter::new(directives),
+            None => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_TRACE_FILTER)),
+        };
+        // A second call, as in tests, keeps the subscriber already installed
+        let _ = tracing_subscriber::fmt().with_env_filter(filter).with_target(false).try_init();
+    }
+
+    /// Worker threads for the checker: one when deterministic, otherwise `--threads` or one
+    /// per CPU
+    pub fn threads(&self) -> usize {
+        if self.deterministic {
+            1
+        } else {
+            self.available_threads()
+        }
+    }
+
+    /// Threads the run may use in all, which the suite and sweeps share out between the
+    /// models they check at once
+    pub fn available_threads(&self) -> usize {
+        self.threads.unwrap_or_else(num_cpus::get)
+    }
+
+    /// Checker for `model` within the bounds, on the configured number of threads
+    pub fn checker<M>(&self, model: M) -> CheckerBuilder<BoundedModel<M>>
+    where
+        M: Model + Send + Sync + 'static,
+        M::State: Bounded + Hash + Send + Sync,
+        M::Action: Debug,
+    {
+        let model = self.bounded(model);
+        let model = if self.coverage { model.with_coverage() } else { model };
+        self.bounds.limit_depth(model.checker()).threads(self.threads())
+    }
+
+    /// Wrap `model` in the bounds and limits, forwarding the selected properties only
+    pub fn bounded<M>(&self, model: M) -> BoundedModel<M>
+    where
+        M: Model,
+        M::State: Bounded,
+        M::Action: Debug,
+    {
+        let model = BoundedModel::new(model, self.bounds).with_limits(self.limits);
+        let names = model.property_names();
+        let unknown = self.properties.unknown(&names);
+        if !unknown.is_empty() {
+            warn!("The model has no property named {}; its properties are {}", unknown.join(", "), names.join(", "));
+        }
+        model.with_properties(&self.properties)
+    }
+
+    /// Why the search of `checker` left reachable states unexplored, if it did
+    pub fn cutoff<M, C>(&self, checker: &C) -> Option<Cutoff>
+    where
+        M: Model,
+        M::State: Bounded,
+        M::Action: Debug,
+        C: Checker<BoundedModel<M>>,
+    {
+        checker.model().cutoff().or_else(|| {
+            self.bounds.max_depth
+                .filter(|depth| checker.max_depth() >= *depth)
+                .map(|_| Cutoff::Bounds)
+        })
+    }
+
+    /// The outcome of every property `checker` evaluated
+    pub fn outcomes<M, C>(&self, checker: &C) -> Vec<(&'static str, Outcome)>
+    where
+        M: Model,
+        M::State: Bounded + Clone + PartialEq,
+        M::Action: Debug + Clone + PartialEq,
+        C: Checker<BoundedModel<M>>,
+    {
+        let complete = self.cutoff(checker).is_none();
+        let discoveries = checker.discoveries();
+        checker.model().properties().into_iter()
+            .map(|property| (property.name, Outcome::of(&property.expectation, discoveries.contains_key(property.name), complete)))
+            .collect()
+    }
+
+    /// Print whether each property was proved, violated or left inconclusive, and return the
+    /// worst of these outcomes
+    pub fn print_outcomes<M, C>(&self, checker: &C) -> Outcome
+    where
+        M: Model,
+        M::State: Bounded + Clone + PartialEq,
+        M::Action: Debug + Clone + PartialEq,
+        C: Checker<BoundedModel<M>>,
+    {
+        let cutoff = self.cutoff(checker);
+        if let Some(cutoff) = cutoff {
+            println!("Search incomplete ({}) after {} expanded states in {:.2?}",
+                     cutoff, checker.model().expanded(), checker.model().elapsed());
+        }
+        let outcomes = self.outcomes(checker);
+        for (name, outcome) in &outcomes {
+            match (outcome, cutoff) {
+                (Outcome::Proved, _) => println!("  ✅ {}: proved", name),
+                (Outcome::Violated, _) => println!("  ❌ {}: violated", name),
+                (Outcome::Inconclusive, Some(cutoff)) => println!("  ❓ {}: inconclusive ({})", name, cutoff),
+                (Outcome::Inconclusive, None) => println!("  ❓ {}: inconclusive", name),
+            }
+        }
+        outcomes.into_iter().map(|(_, outcome)| outcome).max().unwrap_or(Outcome::Proved)
+    }
+
+    /// Print the coverage gathered by `checker`, when requested
+    pub fn print_coverage<M, C>(&self, checker: &C)
+    where
+        M: Model,
+        M::State: Bounded,
+        M::Action: Debug,
+        C: Checker<BoundedModel<M>>,
+    {
+        if let Some(coverage) = checker.model().coverage() {
+            coverage.print_summary();
+        }
+    }
+
+    /// Record the property results of `checker` as run `name` in the JUnit and HTML reports, when
+    /// requested, and in the structured output
+    pub fn record_results<M, C>(&self, name: &str, checker: &C)
+    where
+        M: Model,
+        M::State: Bounded + Clone + PartialEq,
+        M::Action: Debug + Clone + PartialEq,
+        C: Checker<BoundedModel<M>>,
+    {
+        if let Some(file) = &self.junit {
+            if let Err(e) = junit::record(TestSuite::from_checker(name, checker), file) {
+                warn!("Cannot write the JUnit report to {}: {}", file.display(), e);
+            }
+        }
+        if let Some(directory) = &self.report_html {
+            let report = RunReport::from_checker(name, self.parameters(), checker, checker.model().elapsed());
+            match html::write(&report, directory) {
+                Ok(file) => info!("HTML report written to {}", file.display()),
+                Err(e) => warn!("Cannot write the HTML report to {}: {}", directory.display(), e),
+            }
+        }
+        output::record(&self.record(name, checker));
+    }
+
+    /// The options, the outcome of every property and the statistics of `checker` as a
+    /// structured record of run `name`
+    pub fn record<M, C>(&self, name: &str, checker: &C) -> Record
+    where
+        M: Model,
+        M::State: Bounded + Clone + PartialEq,
+        M::Action: Debug + Clone + PartialEq,
+        C: Checker<BoundedModel<M>>,
+    {
+        let stats = CheckStats::of(checker);
+        let mut record = Record { parameters: self.parameters(), ..Record::new(name) }
+            .metric("unique_states", stats.unique_states as f64)
+            .metric("transitions", stats.transitions as f64)
+            .metric("expanded_states", stats.expanded as f64)
+            .metric("max_depth", stats.max_depth as f64)
+            .metric("seconds", stats.elapsed.as_secs_f64());
+        for (property, outcome) in self.outcomes(checker) {
+            record = record.property(property, outcome.label());
+        }
+        for (property, evaluations) in &stats.property_evaluations {
+            record = record.metric(&format!("{}_evaluations", property), *evaluations as f64);
+        }
+        record
+    }
+
+    /// The options as (name, value) pairs for reports
+    fn parameters(&self) -> Vec<(String, String)> {
+        let describe = |bound: Option<String>| bound.unwrap_or_else(|| "unbounded".to_string());
+        vec![
+            ("threads".to_string(), self.threads().to_string()),
+            ("deterministic".to_string(), self.deterministic.to_string()),
+            ("max depth".to_string(), describe(self.bounds.max_depth.map(|depth| depth.to_string()))),
+            ("max slot".to_string(), describe(self.bounds.max_slot.map(|slot| slot.to_string()))),
+            ("max in-flight messages".to_string(), describe(self.bounds.max_in_flight.map(|messages| messages.to_string()))),
+            ("max states".to_string(), describe(self.limits.max_states.map(|states| states.to_string()))),
+            ("timeout".to_string(), describe(self.limits.timeout.map(|timeout| format!("{}s", timeout.as_secs())))),
+        ]
+    }
+
+    pub fn print_summary(&self) {
+        self.bounds.print_summary();
+        self.limits.print_summary();
+        self.properties.print_summary();
+        if self.deterministic {
+            println!("Deterministic exploration: a single worker thread, so reports and traces are reproducible");
+            if self.limits.timeout.is_some() {
+                println!("  (the timeout still stops the search at a point that depends on the machine)");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standstill::StandstillModel;
+    use crate::votor::VotorModel;
+    use stateright::Checker;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_deterministic_runs_agree() {
+        let options = CheckOptions {
+            bounds: ExplorationBounds { max_depth: Some(16), ..Default::default() },
+            deterministic: true,
+            ..Default::default()
+        };
+        assert_eq!(options.threads(), 1);
+        assert_eq!(CheckOptions { threads: Some(3), ..options.clone() }.threads(), 1);
+        assert_eq!(CheckOptions { threads: Some(3), ..Default::default() }.threads(), 3);
+
+        let run = || {
+            let model = StandstillModel { validator_count: 3, max_slot: 2, responsive_count: 1, network_faults: 0 };
+            let checker = options.checker(model).spawn_dfs().join();
+            let discoveries: BTreeSet<String> = checker.discoveries().into_iter()
+                .map(|(property, path)| format!("{}: {:?}", property, path.into_actions()))
+                .collect();
+            (checker.unique_state_count(), checker.state_count(), discoveries)
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn test_limited_runs_are_inconclusive() {
+        let model = || VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 };
+
+        let options = CheckOptions::default();
+        let checker = options.checker(model()).spawn_bfs().join();
+        assert_eq!(options.cutoff(&checker), None);
+        assert!(options.outcomes(&checker).iter().all(|(_, outcome)| *outcome == Outcome::Proved));
+
+        let options = CheckOptions { limits: ResourceLimits { max_states: Some(3), timeout: None }, ..Default::default() };
+        let checker = options.checker(model()).spawn_bfs().join();
+        assert_eq!(options.cutoff(&checker), Some(Cutoff::States));
+        assert!(options.outcomes(&checker).iter().all(|(_, outcome)| *outcome == Outcome::Inconclusive));
+        assert_eq!(options.print_outcomes(&checker), Outcome::Inconclusive);
+    }
+}
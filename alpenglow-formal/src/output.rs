@@ -0,0 +1,216 @@
+//! Structured output of the binaries for scripts.
+//! Every binary prints prose for a person at a terminal, which scripts can only scrape. With
+//! `--output json` or `--output csv` the prose moves to stderr and stdout carries one [`Record`]
+//! per check instead: its parameters, the verdict on every property and the measured metrics,
+//! such as the states explored or the finalization times. JSON output is one object per line and
+//! CSV output one row per value under a single header, so the output of many runs can be
+//! concatenated and aggregated as it is.
+
+use clap::ValueEnum;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, Write};
+use std::sync::Mutex;
+
+/// Header of the CSV output, whose rows each hold one parameter, verdict or metric of a run
+pub const CSV_HEADER: &str = "run,section,name,value";
+
+/// Where records go once a structured format is chosen
+static SINK: Mutex<Option<(OutputFormat, File)>> = Mutex::new(None);
+
+/// What a binary writes to stdout
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Prose for people
+    #[default]
+    Text,
+    /// One JSON object per check
+    Json,
+    /// One CSV row per parameter, verdict and metric
+    Csv,
+}
+
+/// Parameters, verdicts and measurements of one check
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Record {
+    /// Name of the check, such as `safety 4v 3s 1b`
+    pub run: String,
+    pub parameters: Vec<(String, String)>,
+    /// Verdict per property: `proved`, `violated` or `inconclusive` for model checks, `held` or
+    /// `violated` for simulations
+    pub properties: Vec<(String, String)>,
+    pub metrics: Vec<(String, f64)>,
+}
+
+impl Record {
+    pub fn new(run: impl Into<String>) -> Self {
+        Self { run: run.into(), ..Self::default() }
+    }
+
+    pub fn parameter(mut self, name: &str, value: impl ToString) -> Self {
+        self.parameters.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn property(mut self, name: &str, verdict: &str) -> Self {
+        self.properties.push((name.to_string(), verdict.to_string()));
+        self
+    }
+
+    /// A property that simulation either saw hold along every run or saw violated
+    pub fn held(self, name: &str, holds: bool) -> Self {
+        self.property(name, if holds { "held" } else { "violated" })
+    }
+
+    pub fn metric(mut self, name: &str, value: f64) -> Self {
+        self.metrics.push((name.to_string(), value));
+        self
+    }
+
+    /// The record as a single line of JSON
+    pub fn to_json(&self) -> String {
+        let strings = |pairs: &[(String, String)]| pairs.iter()
+            .map(|(name, value)| format!("{}:{}", json_string(name), json_string(value)))
+            .collect::<Vec<_>>()
+            .join(",");
+        let metrics = self.metrics.iter()
+            .map(|(name, value)| format!("{}:{}", json_string(name), json_number(*value)))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!("{{\"run\":{},\"parameters\":{{{}}},\"properties\":{{{}}},\"metrics\":{{{}}}}}",
+                json_string(&self.run), strings(&self.parameters), strings(&self.properties), metrics)
+    }
+
+    /// The record as CSV rows under [`CSV_HEADER`]
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::new();
+        let sections = [("parameter", &self.parameters), ("property", &self.properties)];
+        for (section, pairs) in sections {
+            for (name, value) in pairs {
+                let _ = writeln!(csv, "{},{},{},{}", csv_field(&self.run), section, csv_field(name), csv_field(value));
+            }
+        }
+        for (name, value) in &self.metrics {
+            let _ = writeln!(csv, "{},metric,{},{}", csv_field(&self.run), csv_field(name), value);
+        }
+        csv
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// JSON has no infinities or NaN
+fn json_number(value: f64) -> String {
+    if value.is_finite() { value.to_string() } else { "null".to_string() }
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Send the records of this process to stdout in `format`. Structured formats move everything
+/// else printed to stdout over to stderr, and CSV starts with its header.
+pub fn init(format: OutputFormat) {
+    if format == OutputFormat::Text {
+        return;
+    }
+    let mut stdout = match divert_stdout() {
+        Ok(stdout) => stdout,
+        Err(e) => {
+            eprintln!("Cannot separate the {:?} output from the prose on stdout: {}", format, e);
+            return;
+        }
+    };
+    if format == OutputFormat::Csv {
+        let _ = writeln!(stdout, "{}", CSV_HEADER);
+    }
+    *SINK.lock().unwrap() = Some((format, stdout));
+}
+
+/// Write `record` in the format chosen by [`init`]; text output has the prose instead
+pub fn record(record: &Record) {
+    if let Some((format, stdout)) = SINK.lock().unwrap().as_mut() {
+        let text = match format {
+            OutputFormat::Json => record.to_json() + "\n",
+            OutputFormat::Csv => record.to_csv(),
+            OutputFormat::Text => return,
+        };
+        let _ = stdout.write_all(text.as_bytes()).and_then(|()| stdout.flush());
+    }
+}
+
+/// Point file descriptor 1 at stderr and return a handle on the original stdout
+#[cfg(unix)]
+fn divert_stdout() -> io::Result<File> {
+    use std::os::fd::AsFd;
+    io::stdout().flush()?;
+    let original = io::stdout().as_fd().try_clone_to_owned()?;
+    // SAFETY: dup2 only replaces descriptor 1, which std's stdout keeps writing to, with a
+    // duplicate of descriptor 2; no Rust object owns either of them
+    if unsafe { libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(File::from(original))
+}
+
+#[cfg(not(unix))]
+fn divert_stdout() -> io::Result<File> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "stdout can only be diverted on Unix"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> Record {
+        Record::new("safety 4v 3s")
+            .parameter("validators", 4)
+            .parameter("note", "a \"quoted\", value")
+            .property("safety", "proved")
+            .held("bounded_finalization", false)
+            .metric("unique_states", 1234.0)
+            .metric("seconds", 0.5)
+    }
+
+    #[test]
+    fn test_json_record() {
+        assert_eq!(record().to_json(),
+                   "{\"run\":\"safety 4v 3s\",\"parameters\":{\"validators\":\"4\",\"note\":\"a \\\"quoted\\\", value\"},\
+                    \"properties\":{\"safety\":\"proved\",\"bounded_finalization\":\"violated\"},\
+                    \"metrics\":{\"unique_states\":1234,\"seconds\":0.5}}");
+        assert_eq!(json_number(f64::NAN), "null");
+    }
+
+    #[test]
+    fn test_csv_record() {
+        let csv = record().to_csv();
+        assert_eq!(csv.lines().collect::<Vec<_>>(), vec![
+            "safety 4v 3s,parameter,validators,4",
+            "safety 4v 3s,parameter,note,\"a \"\"quoted\"\", value\"",
+            "safety 4v 3s,property,safety,proved",
+            "safety 4v 3s,property,bounded_finalization,violated",
+            "safety 4v 3s,metric,unique_states,1234",
+            "safety 4v 3s,metric,seconds,0.5",
+        ]);
+        assert_eq!(CSV_HEADER.split(',').count(), 4);
+    }
+}
@@ -0,0 +1,415 @@
+//! Formal verification model for the Pool, the vote and certificate storage of a validator.
+//! The Pool keeps a bounded number of votes per voter and slot: one notarization or skip vote,
+//! up to three notar-fallback votes, one skip-fallback vote and one finalization vote, and at
+//! most `capacity` votes per slot in all. When a slot is full, a new vote evicts a vote of the
+//! voter holding the most, provided it holds more than an honest validator ever sends for one
+//! slot; otherwise the new vote is dropped. Certificates are emitted as soon as the stored votes
+//! reach their threshold and are kept from then on. The model checks that with a capacity of
+//! at least `HONEST_VOTES_PER_SLOT` votes per validator, Byzantine voters can fill the Pool but
+//! never make it drop an honest vote, so every certificate the honest votes make achievable is
+//! emitted.
+
+use stateright::{Model, Property, Checker};
+use std::collections::{BTreeMap, BTreeSet};
+use crate::bounds::Bounded;
+use crate::limits::Outcome;
+use crate::options::CheckOptions;
+
+// --- Formal Model Configuration ---
+const FAST_FINALIZATION_THRESHOLD_PERCENT: u64 = 80;
+const CERTIFICATE_THRESHOLD_PERCENT: u64 = 60;
+const NOTAR_FALLBACK_QUOTA: usize = 3; // Notar-fallback votes kept per voter and slot
+/// Votes an honest validator sends for a slot: a notarization vote and a finalization vote, or a skip vote
+pub const HONEST_VOTES_PER_SLOT: usize = 2;
+/// Block hash honest validators vote for
+pub const HONEST_HASH: Hash = 0;
+/// Block hashes Byzantine voters vote for, the honest one included
+pub const DEFAULT_HASHES: u64 = 2;
+
+// Type aliases for clarity
+type Slot = u64;
+type Hash = u64;
+type ActorId = usize;
+
+/// Kinds of votes the Pool stores, ordered so that fallback votes are evicted first
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum VoteKind {
+    Notar(Hash),
+    Skip,
+    Final,
+    NotarFallback(Hash),
+    SkipFallback,
+}
+
+/// A vote for a slot
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Vote {
+    slot: Slot,
+    voter: ActorId,
+    kind: VoteKind,
+}
+
+/// Certificates the Pool emits for a slot
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Certificate {
+    /// 80% notarization votes for a block
+    FastFinalization(Hash),
+    /// 60% notarization votes for a block
+    Notarization(Hash),
+    /// 60% notarization or notar-fallback votes for a block
+    NotarFallback(Hash),
+    /// 60% skip or skip-fallback votes
+    Skip,
+    /// 60% finalization votes
+    Finalization,
+}
+
+/// Actions that can be taken in the pool model
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum PoolAction {
+    /// A vote reaches the Pool
+    Receive { vote: Vote },
+}
+
+/// Votes stored for one slot, per voter
+type SlotVotes = BTreeMap<ActorId, BTreeSet<VoteKind>>;
+
+/// State of the pool model
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PoolState {
+    /// Votes stored per slot
+    stored: BTreeMap<Slot, SlotVotes>,
+    /// Certificates emitted: (slot, certificate)
+    certificates: BTreeSet<(Slot, Certificate)>,
+    /// Every vote received, stored or not
+    received: BTreeSet<Vote>,
+    /// Votes evicted to make room for another
+    evictions: usize,
+}
+
+/// Formal model of the Pool of one honest validator
+#[derive(Clone)]
+pub struct PoolModel {
+    /// Number of validators, each with the same stake
+    pub validator_count: usize,
+    /// Maximum slots to explore
+    pub max_slot: Slot,
+    /// Byzantine validators, the lowest ids, which send any vote they like
+    pub byzantine_count: usize,
+    /// Most votes stored per slot
+    pub capacity: usize,
+    /// Block hashes per slot Byzantine voters vote for
+    pub hashes: u64,
+}
+
+/// Smallest per-slot capacity that holds every honest vote whatever the Byzantine voters send
+pub fn required_capacity(validator_count: usize) -> usize {
+    HONEST_VOTES_PER_SLOT * validator_count
+}
+
+/// Whether voters holding `voters` out of `validator_count` equal stakes reach `percent`
+fn meets_threshold(voters: usize, validator_count: usize, percent: u64) -> bool {
+    voters as u64 * 100 >= validator_count as u64 * percent
+}
+
+/// Certificates the votes of a slot justify
+fn certificates(votes: &SlotVotes, validator_count: usize, hashes: u64) -> BTreeSet<Certificate> {
+    let voters = |held: &dyn Fn(&VoteKind) -> bool| votes.values().filter(|kinds| kinds.iter().any(held)).count();
+    let mut certificates = BTreeSet::new();
+    for hash in 0..hashes {
+        let notar = voters(&|kind| *kind == VoteKind::Notar(hash));
+        if meets_threshold(notar, validator_count, FAST_FINALIZATION_THRESHOLD_PERCENT) {
+            certificates.insert(Certificate::FastFinalization(hash));
+        }
+        if meets_threshold(notar, validator_count, CERTIFICATE_THRESHOLD_PERCENT) {
+            certificates.insert(Certificate::Notarization(hash));
+        }
+        let fallback = voters(&|kind| matches!(kind, VoteKind::Notar(h) | VoteKind::NotarFallback(h) if *h == hash));
+        if meets_threshold(fallback, validator_count, CERTIFICATE_THRESHOLD_PERCENT) {
+            certificates.insert(Certificate::NotarFallback(hash));
+        }
+    }
+    if meets_threshold(voters(&|kind| matches!(kind, VoteKind::Skip | VoteKind::SkipFallback)), validator_count, CERTIFICATE_THRESHOLD_PERCENT) {
+        certificates.insert(Certificate::Skip);
+    }
+    if meets_threshold(voters(&|kind| *kind == VoteKind::Final), validator_count, CERTIFICATE_THRESHOLD_PERCENT) {
+        certificates.insert(Certificate::Finalization);
+    }
+    certificates
+}
+
+/// Whether a voter holding `held` in a slot may have `kind` stored as well
+fn within_quota(held: &BTreeSet<VoteKind>, kind: VoteKind) -> bool {
+    if held.contains(&kind) {
+        return false;
+    }
+    match kind {
+        VoteKind::Notar(_) | VoteKind::Skip => !held.iter().any(|k| matches!(k, VoteKind::Notar(_) | VoteKind::Skip)),
+        VoteKind::NotarFallback(_) => held.iter().filter(|k| matches!(k, VoteKind::NotarFallback(_))).count() < NOTAR_FALLBACK_QUOTA,
+        VoteKind::Final | VoteKind::SkipFallback => true,
+    }
+}
+
+impl PoolModel {
+    fn is_byzantine(&self, voter: ActorId) -> bool {
+        voter < self.byzantine_count
+    }
+
+    /// Votes a voter may still send for a slot: honest voters vote for the honest block, then
+    /// finalize it, or skip the slot; Byzantine voters send any vote once
+    fn votes_to_send(&self, state: &PoolState, slot: Slot, voter: ActorId) -> Vec<VoteKind> {
+        let sent = |kind| state.received.contains(&Vote { slot, voter, kind });
+        if self.is_byzantine(voter) {
+            (0..self.hashes)
+                .flat_map(|hash| [VoteKind::Notar(hash), VoteKind::NotarFallback(hash)])
+                .chain([VoteKind::Skip, VoteKind::Final, VoteKind::SkipFallback])
+                .filter(|kind| !sent(*kind))
+                .collect()
+        } else if sent(VoteKind::Notar(HONEST_HASH)) {
+            if sent(VoteKind::Final) { Vec::new() } else { vec![VoteKind::Final] }
+        } else if sent(VoteKind::Skip) {
+            Vec::new()
+        } else {
+            vec![VoteKind::Notar(HONEST_HASH), VoteKind::Skip]
+        }
+    }
+
+    /// Honest votes received for a slot, as the Pool would hold them with no bound at all
+    fn honest_votes(&self, state: &PoolState, slot: Slot) -> SlotVotes {
+        let mut votes = SlotVotes::new();
+        for vote in state.received.iter().filter(|vote| vote.slot == slot && !self.is_byzantine(vote.voter)) {
+            votes.entry(vote.voter).or_default().insert(vote.kind);
+        }
+        votes
+    }
+}
+
+impl PoolState {
+    fn new() -> Self {
+        Self {
+            stored: BTreeMap::new(),
+            certificates: BTreeSet::new(),
+            received: BTreeSet::new(),
+            evictions: 0,
+        }
+    }
+
+    /// Votes stored for a slot
+    fn stored_in(&self, slot: Slot) -> usize {
+        self.stored.get(&slot).map_or(0, |votes| votes.values().map(BTreeSet::len).sum())
+    }
+
+    fn is_stored(&self, vote: &Vote) -> bool {
+        self.stored.get(&vote.slot)
+            .and_then(|votes| votes.get(&vote.voter))
+            .is_some_and(|kinds| kinds.contains(&vote.kind))
+    }
+
+    /// Store a vote within the voter's quota and the slot's capacity, evicting a vote of the
+    /// voter holding the most if the slot is full and that voter holds more than an honest one
+    /// would, and emit the certificates the stored votes then justify
+    fn store(&mut self, model: &PoolModel, vote: Vote) {
+        let full = self.stored_in(vote.slot) >= model.capacity;
+        let votes = self.stored.entry(vote.slot).or_default();
+        if !within_quota(votes.get(&vote.voter).unwrap_or(&BTreeSet::new()), vote.kind) {
+            return;
+        }
+        if full {
+            let hoarder = votes.iter()
+                .filter(|(_, kinds)| kinds.len() > HONEST_VOTES_PER_SLOT)
+                .max_by_key(|(voter, kinds)| (kinds.len(), std::cmp::Reverse(**voter)))
+                .map(|(voter, _)| *voter);
+            let Some(hoarder) = hoarder else {
+                return; // Dropped: nobody holds more than an honest voter
+            };
+            let kinds = votes.get_mut(&hoarder).expect("the hoarder holds votes");
+            let evicted = *kinds.iter().next_back().expect("the hoarder holds votes");
+            kinds.remove(&evicted);
+            self.evictions += 1;
+        }
+        votes.entry(vote.voter).or_default().insert(vote.kind);
+        let emitted = certificates(votes, model.validator_count, model.hashes);
+        self.certificates.extend(emitted.into_iter().map(|certificate| (vote.slot, certificate)));
+    }
+}
+
+impl Bounded for PoolState {
+    fn current_slot(&self) -> Slot {
+        self.received.iter().map(|vote| vote.slot).max().unwrap_or(0)
+    }
+
+    fn in_flight(&self) -> usize {
+        0
+    }
+}
+
+impl Model for PoolModel {
+    type State = PoolState;
+    type Action = PoolAction;
+
+    fn init_states(&self) -> Vec<Self::State> {
+        vec![PoolState::new()]
+    }
+
+    fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
+        // Every voter sends its votes for every slot, in any order
+        for slot in 1..=self.max_slot {
+            for voter in 0..self.validator_count {
+                for kind in self.votes_to_send(state, slot, voter) {
+                    actions.push(PoolAction::Receive { vote: Vote { slot, voter, kind } });
+                }
+            }
+        }
+    }
+
+    fn next_state(&self, last_state: &Self::State, action: Self::Action) -> Option<Self::State> {
+        let mut next_state = last_state.clone();
+        match action {
+            PoolAction::Receive { vote } => {
+                if !next_state.received.insert(vote) {
+                    return None;
+                }
+                next_state.store(self, vote);
+            }
+        }
+        Some(next_state)
+    }
+
+    /// Properties to verify in the pool model
+    fn properties(&self) -> Vec<Property<Self>> {
+        vec![
+            // Property 1: Memory stays bounded: no slot holds more than its capacity, and no
+            // voter more than its quota of each kind
+            Property::<Self>::always("bounded_memory", |model, state| {
+                state.stored.values().all(|votes| {
+                    votes.values().map(BTreeSet::len).sum::<usize>() <= model.capacity
+                        && votes.values().all(|kinds| {
+                            kinds.iter().filter(|k| matches!(k, VoteKind::Notar(_) | VoteKind::Skip)).count() <= 1
+                                && kinds.iter().filter(|k| matches!(k, VoteKind::NotarFallback(_))).count() <= NOTAR_FALLBACK_QUOTA
+                        })
+                })
+            }),
+
+            // Property 2: Every honest vote received is still stored
+            Property::<Self>::always("honest_votes_retained", |model, state| {
+                state.received.iter()
+                    .filter(|vote| !model.is_byzantine(vote.voter))
+                    .all(|vote| state.is_stored(vote))
+            }),
+
+            // Property 3: Every certificate the honest votes received so far justify on their
+            // own has been emitted, whatever the Byzantine voters filled the Pool with
+            Property::<Self>::always("achievable_certificates_emitted", |model, state| {
+                (1..=model.max_slot).all(|slot| {
+                    certificates(&model.honest_votes(state, slot), model.validator_count, model.hashes)
+                        .into_iter()
+                        .all(|certificate| state.certificates.contains(&(slot, certificate)))
+                })
+            }),
+        ]
+    }
+}
+
+/// Run formal verification of the bounded Pool
+pub fn run_formal_verification(options: &CheckOptions) {
+    println!("=== Pool Formal Verification ===");
+
+    let model = PoolModel {
+        validator_count: 4, // Small for formal verification
+        max_slot: 1,
+        byzantine_count: 1,
+        capacity: required_capacity(4),
+        hashes: DEFAULT_HASHES,
+    };
+
+    println!("Model checking the Pool with {} validators ({} Byzantine), {} slots, {} votes per slot",
+             model.validator_count, model.byzantine_count, model.max_slot, model.capacity);
+
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs()
+        .report(&mut stateright::report::WriteReporter::new(&mut std::io::stdout()));
+
+    // Check verification results
+    match options.print_outcomes(&result) {
+        Outcome::Proved => println!("✅ All Pool properties verified successfully"),
+        Outcome::Violated => println!("❌ Pool verification found counterexamples"),
+        Outcome::Inconclusive => println!("❓ Pool verification inconclusive: a limit or bound stopped the search first"),
+    }
+    options.record_results("pool formal", &result);
+}
+
+/// Test pool model with different configurations
+pub fn test_pool_model(validators: usize, slots: u64, byzantine: usize, capacity: usize, options: &CheckOptions) {
+    println!("Testing Pool model with {} validators ({} Byzantine), {} slots, {} votes per slot",
+             validators, byzantine, slots, capacity);
+
+    let model = PoolModel {
+        validator_count: validators,
+        max_slot: slots,
+        byzantine_count: byzantine,
+        capacity,
+        hashes: DEFAULT_HASHES,
+    };
+
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs();
+    options.progress.watch(&result);
+    let result = result.join();
+
+    println!("States explored: {}", result.state_count());
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("pool {}v {}s {}b {}c", validators, slots, byzantine, capacity), &result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(capacity: usize) -> PoolModel {
+        PoolModel { validator_count: 3, max_slot: 1, byzantine_count: 1, capacity, hashes: DEFAULT_HASHES }
+    }
+
+    fn receive(model: &PoolModel, state: PoolState, voter: ActorId, kind: VoteKind) -> PoolState {
+        model.next_state(&state, PoolAction::Receive { vote: Vote { slot: 1, voter, kind } }).unwrap()
+    }
+
+    #[test]
+    fn test_full_pool_evicts_byzantine_votes() {
+        let model = model(required_capacity(3));
+        let mut state = PoolState::new();
+        for kind in [VoteKind::Notar(1), VoteKind::NotarFallback(0), VoteKind::NotarFallback(1), VoteKind::SkipFallback] {
+            state = receive(&model, state, 0, kind);
+        }
+        for kind in [VoteKind::Notar(HONEST_HASH), VoteKind::Final] {
+            state = receive(&model, state, 1, kind);
+        }
+        assert_eq!(state.stored_in(1), model.capacity);
+
+        state = receive(&model, state, 2, VoteKind::Notar(HONEST_HASH));
+        assert_eq!(state.evictions, 1);
+        assert_eq!(state.stored_in(1), model.capacity);
+        assert!(state.is_stored(&Vote { slot: 1, voter: 2, kind: VoteKind::Notar(HONEST_HASH) }));
+        assert!(state.certificates.contains(&(1, Certificate::Notarization(HONEST_HASH))));
+    }
+
+    #[test]
+    fn test_capacity_bound() {
+        let checker = model(required_capacity(3)).checker().spawn_bfs().join();
+        assert!(checker.discovery("bounded_memory").is_none());
+        assert!(checker.discovery("honest_votes_retained").is_none());
+        assert!(checker.discovery("achievable_certificates_emitted").is_none());
+
+        // One vote short, two Byzantine votes and three honest ones fill the slot and the last
+        // honest finalization vote is dropped
+        let checker = model(required_capacity(3) - 1).checker().spawn_bfs().join();
+        assert!(checker.discovery("bounded_memory").is_none());
+        assert!(checker.discovery("achievable_certificates_emitted").is_some());
+    }
+}
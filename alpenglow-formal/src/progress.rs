@@ -0,0 +1,135 @@
+//! Live progress reporting for long model-checking runs.
+//! A depth-first run prints nothing until it finishes, which can take hours. [`Progress`] polls a
+//! running checker and periodically prints the exploration rate, the frontier of discovered but
+//! not yet expanded states, the resident memory of the process and an estimate of the time left.
+//! With a metrics address it also keeps the Prometheus endpoint of [`crate::metrics`] up to date.
+
+use crate::bounds::{Bounded, BoundedModel};
+use crate::metrics::{self, ModelMetrics, METRICS_REFRESH_INTERVAL};
+use stateright::{Checker, Model};
+use std::fmt::Debug;
+use std::net::SocketAddr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default time between two progress lines
+pub const DEFAULT_PROGRESS_INTERVAL_SECS: u64 = 5;
+
+/// Periodic progress reporting; disabled unless an interval or a metrics address is set
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// Time between two progress lines
+    pub interval: Option<Duration>,
+    /// Unique states at which the run stops, used for the time estimate when known
+    pub target_states: Option<usize>,
+    /// Address of the Prometheus metrics endpoint
+    pub metrics_address: Option<SocketAddr>,
+}
+
+impl Progress {
+    /// Print progress lines and refresh the metrics until `checker` finishes; returns at once
+    /// when both are disabled
+    pub fn watch<M, C>(&self, checker: &C)
+    where
+        M: Model,
+        M::State: Bounded + Clone + PartialEq,
+        M::Action: Debug + Clone + PartialEq,
+        C: Checker<BoundedModel<M>>,
+    {
+        let poll = match (self.interval, self.metrics_address) {
+            (None, None) => return,
+            (Some(interval), None) => interval,
+            (interval, Some(address)) => {
+                if let Err(e) = metrics::serve(address) {
+                    tracing::warn!("Cannot serve metrics on {}: {}", address, e);
+                }
+                interval.map_or(METRICS_REFRESH_INTERVAL, |interval| interval.min(METRICS_REFRESH_INTERVAL))
+            }
+        };
+        let model = std::any::type_name::<M>().rsplit("::").next().unwrap_or_default();
+        let start = Instant::now();
+        let mut last = Sample { at: start, unique: 0, frontier: 0 };
+        while !checker.is_done() {
+            thread::sleep(poll);
+            if self.metrics_address.is_some() {
+                metrics::update(model, ModelMetrics::of(checker));
+            }
+            if self.interval.is_none_or(|interval| last.at.elapsed() < interval) {
+                continue;
+            }
+            let unique = checker.unique_state_count();
+            let sample = Sample { at: Instant::now(), unique, frontier: unique.saturating_sub(checker.model().expanded()) };
+            let elapsed = sample.at.duration_since(last.at).as_secs_f64();
+            let rate = (sample.unique - last.unique) as f64 / elapsed.max(f64::EPSILON);
+            let eta = match self.target_states {
+                Some(target) => remaining_time(target.saturating_sub(sample.unique), rate),
+                None => drain_time(&last, &sample),
+            };
+            tracing::info!("[{:>6.0}s] {} states ({} unique), {:.0} states/s, frontier {}, depth {}, memory {}, ETA {}",
+                     start.elapsed().as_secs_f64(), checker.state_count(), sample.unique, rate, sample.frontier,
+                     checker.max_depth(),
+                     resident_memory_kib().map_or("unknown".to_string(), |kib| format!("{} MiB", kib / 1024)),
+                     eta.map_or("unknown (frontier still growing)".to_string(), |eta| format!("{}s", eta.as_secs())));
+            last = sample;
+        }
+        if self.metrics_address.is_some() {
+            metrics::update(model, ModelMetrics::of(checker));
+        }
+    }
+}
+
+/// Exploration measurements taken at one poll
+struct Sample {
+    at: Instant,
+    unique: usize,
+    frontier: usize,
+}
+
+/// Time to discover `remaining` more states at `rate` states per second
+fn remaining_time(remaining: usize, rate: f64) -> Option<Duration> {
+    (rate > 0.0).then(|| Duration::from_secs_f64(remaining as f64 / rate))
+}
+
+/// Time for the frontier to empty if it keeps shrinking as fast as it did since the last poll
+fn drain_time(last: &Sample, now: &Sample) -> Option<Duration> {
+    if now.frontier >= last.frontier {
+        return None;
+    }
+    let shrink_rate = (last.frontier - now.frontier) as f64 / now.at.duration_since(last.at).as_secs_f64().max(f64::EPSILON);
+    remaining_time(now.frontier, shrink_rate)
+}
+
+/// Resident set size of this process, where the platform exposes it
+pub(crate) fn resident_memory_kib() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_resident_kib(&status)
+}
+
+fn parse_resident_kib(status: &str) -> Option<u64> {
+    status.lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))
+        .and_then(|value| value.split_whitespace().next())
+        .and_then(|kib| kib.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_estimates() {
+        let start = Instant::now();
+        let before = Sample { at: start, unique: 100, frontier: 50 };
+        let shrinking = Sample { at: start + Duration::from_secs(10), unique: 150, frontier: 40 };
+        let growing = Sample { at: start + Duration::from_secs(10), unique: 150, frontier: 60 };
+
+        // The frontier lost 10 states in 10s, so the remaining 40 take another 40s
+        assert_eq!(drain_time(&before, &shrinking), Some(Duration::from_secs(40)));
+        assert_eq!(drain_time(&before, &growing), None);
+        assert_eq!(remaining_time(500, 100.0), Some(Duration::from_secs(5)));
+        assert_eq!(remaining_time(500, 0.0), None);
+
+        assert_eq!(parse_resident_kib("Name:\tcargo\nVmRSS:\t  20480 kB\n"), Some(20480));
+        assert_eq!(parse_resident_kib("Name:\tcargo\n"), None);
+    }
+}
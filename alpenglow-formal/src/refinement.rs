@@ -0,0 +1,330 @@
+//! Refinement checks between a detailed model and a simplified one.
+//! A simplified model only says something about the protocol if it is a sound abstraction of
+//! the detailed one: every behaviour of the detailed model must also be a behaviour of the
+//! simplified model, seen through a view both share. [`check_refinement`] explores both models,
+//! maps their states to views, and checks that every step of the concrete model either leaves
+//! its view unchanged or changes it as a sequence of abstract steps does. Properties of views
+//! that hold on every abstract view then transfer to the concrete model.
+//!
+//! Abstract steps are collected as a graph over views, which merges abstract states sharing a
+//! view. The check is exact when those states have the same view successors, as for the
+//! records of finalized blocks used to check [`VotorModel`] against [`SafetyModel`].
+
+use crate::golden::fingerprint;
+use crate::invariants::ConsensusRecord;
+use crate::certificate::VoteRouting;
+use crate::modelling::safety::{SafetyModel, Synchrony};
+use crate::votor::VotorModel;
+use stateright::Model;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Default number of states explored in each model by a refinement check
+pub const DEFAULT_REFINEMENT_STATE_COUNT: usize = 200_000;
+
+/// Finalized blocks as (slot, hash), the view shared by the consensus models
+pub type FinalizedView = BTreeSet<(u64, u64)>;
+
+/// A property of views; holding on every view the abstract model reaches, it transfers to
+/// every model refining it
+pub struct ViewProperty<V> {
+    pub name: &'static str,
+    pub condition: fn(&V) -> bool,
+}
+
+/// A concrete behaviour the abstract model does not have
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefinementViolation<V> {
+    /// Actions from a concrete initial state up to and including the offending step
+    pub actions: Vec<String>,
+    /// View before the step; `None` when an initial state has no abstract counterpart
+    pub from: Option<V>,
+    pub to: V,
+}
+
+/// Outcome of a refinement check
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RefinementReport<V> {
+    pub concrete_states: usize,
+    pub abstract_states: usize,
+    /// Distinct views reached by the abstract model
+    pub abstract_views: usize,
+    /// Whether both explorations finished within the state limit
+    pub complete: bool,
+    pub violation: Option<RefinementViolation<V>>,
+    /// Every view property with whether it holds on all abstract views, and so transfers
+    pub transferred: Vec<(&'static str, bool)>,
+}
+
+impl<V: Debug> RefinementReport<V> {
+    /// Whether the concrete model refines the abstract one, as far as explored
+    pub fn refines(&self) -> bool {
+        self.violation.is_none()
+    }
+
+    pub fn print_summary(&self) {
+        println!("Refinement check over {} concrete and {} abstract states ({} abstract views){}",
+                 self.concrete_states, self.abstract_states, self.abstract_views,
+                 if self.complete { "" } else { ", stopped at the state limit" });
+        match &self.violation {
+            None => println!("  ✅ every concrete behaviour is an abstract behaviour"),
+            Some(RefinementViolation { actions, from, to }) => {
+                match from {
+                    Some(from) => println!("  ❌ no abstract steps change the view {:?} into {:?}", from, to),
+                    None => println!("  ❌ the initial view {:?} is not an initial abstract view", to),
+                }
+                for action in actions {
+                    println!("     {}", action);
+                }
+            }
+        }
+        for (property, holds) in &self.transferred {
+            if *holds {
+                println!("  ✅ {}: holds on every abstract view", property);
+            } else {
+                println!("  ⚠️  {}: fails on some abstract view, so it does not transfer", property);
+            }
+        }
+    }
+}
+
+/// Views the abstract model reaches and the view changes its steps make
+struct ViewGraph<V> {
+    initial: HashSet<V>,
+    successors: HashMap<V, HashSet<V>>,
+    states: usize,
+    complete: bool,
+}
+
+impl<V: Clone + Eq + Hash> ViewGraph<V> {
+    fn explore<A, F>(model: &A, view: F, max_states: usize) -> Self
+    where
+        A: Model,
+        A::State: Hash,
+        F: Fn(&A::State) -> V,
+    {
+        let mut graph = Self { initial: HashSet::new(), successors: HashMap::new(), states: 0, complete: true };
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        for state in model.init_states() {
+            if visited.insert(fingerprint(&state)) {
+                let initial = view(&state);
+                graph.successors.entry(initial.clone()).or_default();
+                graph.initial.insert(initial);
+                queue.push_back(state);
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let from = view(&state);
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            for action in actions {
+                let Some(next) = model.next_state(&state, action) else {
+                    continue;
+                };
+                let to = view(&next);
+                if to != from {
+                    graph.successors.entry(from.clone()).or_default().insert(to.clone());
+                }
+                graph.successors.entry(to).or_default();
+                let next_fingerprint = fingerprint(&next);
+                if visited.contains(&next_fingerprint) {
+                    continue;
+                }
+                if visited.len() >= max_states {
+                    graph.complete = false;
+                    continue;
+                }
+                visited.insert(next_fingerprint);
+                queue.push_back(next);
+            }
+        }
+        graph.states = visited.len();
+        graph
+    }
+
+    /// Whether abstract steps lead from view `from` to view `to`
+    fn reaches(&self, from: &V, to: &V) -> bool {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(view) = queue.pop_front() {
+            for next in self.successors.get(view).into_iter().flatten() {
+                if next == to {
+                    return true;
+                }
+                if visited.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        false
+    }
+}
+
+/// Actions leading from an initial state to the discovered state at `index`
+fn path<A: Debug>(nodes: &[(Option<usize>, Option<A>)], mut index: usize) -> Vec<String> {
+    let mut actions = Vec::new();
+    while let (Some(parent), Some(action)) = &nodes[index] {
+        actions.push(format!("{:?}", action));
+        index = *parent;
+    }
+    actions.reverse();
+    actions
+}
+
+/// Check that every behaviour of `concrete`, seen through `concrete_view`, is a behaviour of
+/// `abstract_model` seen through `abstract_view`, exploring at most `max_states` states of each
+pub fn check_refinement<C, A, V>(
+    concrete: &C,
+    concrete_view: impl Fn(&C::State) -> V,
+    abstract_model: &A,
+    abstract_view: impl Fn(&A::State) -> V,
+    properties: &[ViewProperty<V>],
+    max_states: usize,
+) -> RefinementReport<V>
+where
+    C: Model,
+    C::State: Hash,
+    C::Action: Debug + Clone,
+    A: Model,
+    A::State: Hash,
+    V: Clone + Debug + Eq + Hash,
+{
+    let graph = ViewGraph::explore(abstract_model, abstract_view, max_states);
+    let mut report = RefinementReport {
+        concrete_states: 0,
+        abstract_states: graph.states,
+        abstract_views: graph.successors.len(),
+        complete: graph.complete,
+        violation: None,
+        transferred: properties.iter()
+            .map(|property| (property.name, graph.successors.keys().all(property.condition)))
+            .collect(),
+    };
+
+    // Every discovered concrete state with its parent and the action leading to it
+    let mut nodes: Vec<(Option<usize>, Option<C::Action>)> = Vec::new();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    for state in concrete.init_states() {
+        if !visited.insert(fingerprint(&state)) {
+            continue;
+        }
+        let initial = concrete_view(&state);
+        if !graph.initial.contains(&initial) {
+            report.violation = Some(RefinementViolation { actions: Vec::new(), from: None, to: initial });
+            break;
+        }
+        nodes.push((None, None));
+        queue.push_back((nodes.len() - 1, state));
+    }
+
+    // Answers of the view graph, as concrete steps repeat the same view changes
+    let mut reaches = HashMap::new();
+    while let Some((index, state)) = queue.pop_front() {
+        if report.violation.is_some() {
+            break;
+        }
+        let from = concrete_view(&state);
+        let mut actions = Vec::new();
+        concrete.actions(&state, &mut actions);
+        for action in actions {
+            let Some(next) = concrete.next_state(&state, action.clone()) else {
+                continue;
+            };
+            let to = concrete_view(&next);
+            if to != from && !*reaches.entry((from.clone(), to.clone())).or_insert_with(|| graph.reaches(&from, &to)) {
+                let mut actions = path(&nodes, index);
+                actions.push(format!("{:?}", action));
+                report.violation = Some(RefinementViolation { actions, from: Some(from), to });
+                break;
+            }
+            let next_fingerprint = fingerprint(&next);
+            if visited.contains(&next_fingerprint) {
+                continue;
+            }
+            if visited.len() >= max_states {
+                report.complete = false;
+                continue;
+            }
+            visited.insert(next_fingerprint);
+            nodes.push((Some(index), Some(action)));
+            queue.push_back((nodes.len() - 1, next));
+        }
+    }
+    report.concrete_states = visited.len();
+    report
+}
+
+/// At most one block is finalized in each slot
+fn single_block_per_slot(view: &FinalizedView) -> bool {
+    view.iter().zip(view.iter().skip(1)).all(|((slot, _), (next_slot, _))| slot != next_slot)
+}
+
+/// Guarantees of the safety model expressed on finalized blocks
+pub fn finalized_view_properties() -> Vec<ViewProperty<FinalizedView>> {
+    vec![ViewProperty { name: "no_conflicting_finalization", condition: single_block_per_slot }]
+}
+
+/// Check that every behaviour of `VotorModel` is a behaviour of `SafetyModel` with the same
+/// honest validators and no faults, seen through the blocks they finalize
+pub fn check_votor_refines_safety(validators: usize, slots: u64, max_states: usize) -> RefinementReport<FinalizedView> {
+    let votor = VotorModel { honest_validators: validators, max_slot: slots, network_faults: 0 };
+    let safety = SafetyModel {
+        validator_count: validators,
+        max_slot: slots,
+        byzantine_count: 0,
+        offline_budget_percent: 0,
+        corruption_budget_percent: 0,
+        network_faults: 0,
+        synchrony: Synchrony::default(),
+        vote_routing: VoteRouting::default(),
+    };
+    check_refinement(&votor, |state| state.finalized_blocks(), &safety, |state| state.finalized_blocks(),
+                     &finalized_view_properties(), max_states)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_votor_refines_safety() {
+        // The safety model is explored in full, Votor only up to the state limit
+        let report = check_votor_refines_safety(3, 1, DEFAULT_REFINEMENT_STATE_COUNT);
+        assert!(report.abstract_states < DEFAULT_REFINEMENT_STATE_COUNT);
+        assert_eq!(report.violation, None);
+        assert_eq!(report.transferred, vec![("no_conflicting_finalization", true)]);
+        // The empty view and one view per proposer of slot 1
+        assert_eq!(report.abstract_views, 4);
+    }
+
+    #[test]
+    fn test_refinement_violation_found() {
+        // Votor's finalizations reported one slot late are not behaviours of the safety
+        // model, which cannot finalize a slot beyond its last one
+        let votor = VotorModel { honest_validators: 3, max_slot: 1, network_faults: 0 };
+        let safety = SafetyModel {
+            validator_count: 3,
+            max_slot: 1,
+            byzantine_count: 0,
+            offline_budget_percent: 0,
+            corruption_budget_percent: 0,
+            network_faults: 0,
+            synchrony: Synchrony::default(),
+            vote_routing: VoteRouting::default(),
+        };
+        let shifted = |state: &<VotorModel as Model>::State| -> FinalizedView {
+            state.finalized_blocks().into_iter().map(|(slot, hash)| (slot + 1, hash)).collect()
+        };
+        let report = check_refinement(&votor, shifted, &safety, |state| state.finalized_blocks(),
+                                      &finalized_view_properties(), DEFAULT_REFINEMENT_STATE_COUNT);
+        let violation = report.violation.unwrap();
+        assert_eq!(violation.from, Some(FinalizedView::new()));
+        assert_eq!(violation.to.len(), 1);
+        assert!(violation.actions.first().unwrap().starts_with("Propose"));
+        assert!(violation.actions.last().unwrap().starts_with("Deliver"));
+        assert!(!single_block_per_slot(&FinalizedView::from([(1, 1000), (1, 1001)])));
+    }
+}
@@ -0,0 +1,358 @@
+//! Formal verification model for voting rewards.
+//! Validators vote for every slot and their votes reach the slot's aggregator over time. Once
+//! the votes that arrived reach 60% of the stake, the aggregator forms the certificate, which
+//! includes them. The slot's reward window stays open a while longer, until every responsive
+//! validator's vote has arrived, and then closes: the votes that arrived by then are credited,
+//! one reward each. Unresponsive validators may vote at any time, so their votes can miss the
+//! window. The model checks the incentives of the accounting: every responsive validator is
+//! rewarded in every certified slot, and a validator that had withheld any credited vote would
+//! have been rewarded strictly less.
+
+use stateright::{Model, Property, Checker};
+use std::collections::{BTreeMap, BTreeSet};
+use crate::bounds::Bounded;
+use crate::limits::Outcome;
+use crate::options::CheckOptions;
+use crate::voters::VoterSet;
+
+// --- Formal Model Configuration ---
+const CERTIFICATE_THRESHOLD_PERCENT: u64 = 60;
+const VOTE_REWARD: Reward = 1; // Reward per credited vote; every validator holds one unit of stake
+
+// Type aliases for clarity
+type Slot = u64;
+type ActorId = usize;
+type Reward = u64;
+
+/// How the rewards of a slot are credited once its window closes
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum RewardScheme {
+    /// One reward per vote included in the certificate or arriving before the window closes
+    #[default]
+    PerVote,
+    /// One reward per vote included in the certificate, which leaves responsive validators
+    /// whose votes arrive after it forms unrewarded
+    CertificateOnly,
+    /// One reward to every validator of a certified slot, whether it voted or not, which makes
+    /// withholding votes free
+    EvenSplit,
+}
+
+/// Actions that can be taken in the rewards model
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum RewardsAction {
+    /// A validator sends its vote for a slot
+    Vote { slot: Slot, voter: ActorId },
+    /// A vote reaches the aggregator
+    Arrive { slot: Slot, voter: ActorId },
+    /// The aggregator forms the slot's certificate from the votes that arrived
+    Certify { slot: Slot },
+    /// The slot's reward window closes and the votes that arrived are credited
+    Settle { slot: Slot },
+}
+
+/// State of the rewards model
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct RewardsState {
+    /// Votes sent but not arrived: (slot, voter)
+    in_flight: BTreeSet<(Slot, ActorId)>,
+    /// Voters whose votes reached the aggregator, per slot
+    arrived: BTreeMap<Slot, VoterSet>,
+    /// Voters each slot's certificate includes
+    certificates: BTreeMap<Slot, VoterSet>,
+    /// Voters whose votes arrived before each settled slot's window closed
+    settled: BTreeMap<Slot, VoterSet>,
+}
+
+/// Formal model of vote reward accounting
+#[derive(Clone)]
+pub struct RewardsModel {
+    pub validator_count: usize,
+    /// Validators whose votes arrive within the reward window, the lowest ids
+    pub responsive_count: usize,
+    /// Maximum slots to explore
+    pub max_slot: Slot,
+    pub reward_scheme: RewardScheme,
+}
+
+impl RewardsModel {
+    fn is_responsive(&self, validator: ActorId) -> bool {
+        validator < self.responsive_count
+    }
+
+    fn has_quorum(&self, voters: &VoterSet) -> bool {
+        voters.len() as u64 * 100 >= self.validator_count as u64 * CERTIFICATE_THRESHOLD_PERCENT
+    }
+
+    /// Rewards per validator for the settled slots, given their certificates and credited votes
+    fn rewards(&self, certificates: &BTreeMap<Slot, VoterSet>, settled: &BTreeMap<Slot, VoterSet>) -> BTreeMap<ActorId, Reward> {
+        let mut rewards = BTreeMap::new();
+        for (slot, credited) in settled {
+            let rewarded: Vec<ActorId> = match self.reward_scheme {
+                RewardScheme::PerVote => credited.iter().collect(),
+                RewardScheme::CertificateOnly => certificates.get(slot).map(|signers| signers.iter().collect()).unwrap_or_default(),
+                RewardScheme::EvenSplit => (0..self.validator_count).collect(),
+            };
+            for validator in rewarded {
+                *rewards.entry(validator).or_insert(0) += VOTE_REWARD;
+            }
+        }
+        rewards
+    }
+
+    /// Reward of `validator` had it withheld its vote for `slot`: without it the certificate
+    /// forms from the other votes that arrived, if they reach the threshold, and otherwise the
+    /// slot is never certified and credits nothing
+    fn reward_if_withheld(&self, state: &RewardsState, validator: ActorId, slot: Slot) -> Reward {
+        let (mut certificates, mut settled) = (state.certificates.clone(), state.settled.clone());
+        let credited = settled.get_mut(&slot).expect("only settled slots are credited");
+        credited.remove(&validator);
+        if self.has_quorum(credited) {
+            let credited = credited.clone();
+            let signers = certificates.get_mut(&slot).expect("settled slots are certified");
+            signers.remove(&validator);
+            if !self.has_quorum(signers) {
+                *signers = credited;
+            }
+        } else {
+            certificates.remove(&slot);
+            settled.remove(&slot);
+        }
+        self.rewards(&certificates, &settled).get(&validator).copied().unwrap_or(0)
+    }
+
+    fn has_voted(&self, state: &RewardsState, slot: Slot, voter: ActorId) -> bool {
+        state.in_flight.contains(&(slot, voter)) || state.arrived.get(&slot).is_some_and(|voters| voters.contains(&voter))
+    }
+
+    /// Whether the reward window of `slot` may close: it is certified and not settled, and
+    /// every responsive validator's vote has arrived
+    fn may_settle(&self, state: &RewardsState, slot: Slot) -> bool {
+        state.certificates.contains_key(&slot)
+            && !state.settled.contains_key(&slot)
+            && state.arrived.get(&slot).is_some_and(|voters| (0..self.responsive_count).all(|v| voters.contains(&v)))
+    }
+}
+
+impl RewardsState {
+    /// Whether every responsive validator is rewarded for every settled slot
+    fn check_responsive_rewarded(&self, model: &RewardsModel) -> bool {
+        self.settled.keys().all(|slot| {
+            let settled = BTreeMap::from([(*slot, self.settled[slot].clone())]);
+            let rewards = model.rewards(&self.certificates, &settled);
+            (0..model.validator_count)
+                .filter(|validator| model.is_responsive(*validator))
+                .all(|validator| rewards.get(&validator).is_some_and(|reward| *reward > 0))
+        })
+    }
+
+    /// Whether withholding any credited vote would have cost its validator reward
+    fn check_withholding_reduces_reward(&self, model: &RewardsModel) -> bool {
+        let rewards = model.rewards(&self.certificates, &self.settled);
+        self.settled.iter().all(|(slot, credited)| {
+            credited.iter().all(|validator| {
+                model.reward_if_withheld(self, validator, *slot) < rewards.get(&validator).copied().unwrap_or(0)
+            })
+        })
+    }
+}
+
+impl Bounded for RewardsState {
+    fn current_slot(&self) -> Slot {
+        let in_flight = self.in_flight.iter().map(|(slot, _)| *slot);
+        self.arrived.keys().copied().chain(in_flight).max().unwrap_or(0)
+    }
+
+    fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+}
+
+impl Model for RewardsModel {
+    type State = RewardsState;
+    type Action = RewardsAction;
+
+    fn init_states(&self) -> Vec<Self::State> {
+        vec![RewardsState {
+            in_flight: BTreeSet::new(),
+            arrived: BTreeMap::new(),
+            certificates: BTreeMap::new(),
+            settled: BTreeMap::new(),
+        }]
+    }
+
+    fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
+        for slot in 1..=self.max_slot {
+            // 1. Validators that have not voted for the slot vote
+            for voter in 0..self.validator_count {
+                if !self.has_voted(state, slot, voter) {
+                    actions.push(RewardsAction::Vote { slot, voter });
+                }
+            }
+
+            // 2. The aggregator forms the certificate and closes the reward window
+            if !state.certificates.contains_key(&slot) && state.arrived.get(&slot).is_some_and(|voters| self.has_quorum(voters)) {
+                actions.push(RewardsAction::Certify { slot });
+            }
+            if self.may_settle(state, slot) {
+                actions.push(RewardsAction::Settle { slot });
+            }
+        }
+
+        // 3. Votes in flight arrive
+        for (slot, voter) in &state.in_flight {
+            actions.push(RewardsAction::Arrive { slot: *slot, voter: *voter });
+        }
+    }
+
+    fn next_state(&self, last_state: &Self::State, action: Self::Action) -> Option<Self::State> {
+        let mut next_state = last_state.clone();
+        match action {
+            RewardsAction::Vote { slot, voter } => {
+                if slot == 0 || slot > self.max_slot || voter >= self.validator_count || self.has_voted(last_state, slot, voter) {
+                    return None;
+                }
+                next_state.in_flight.insert((slot, voter));
+            }
+            RewardsAction::Arrive { slot, voter } => {
+                if !next_state.in_flight.remove(&(slot, voter)) {
+                    return None;
+                }
+                next_state.arrived.entry(slot).or_default().insert(voter);
+            }
+            RewardsAction::Certify { slot } => {
+                let voters = last_state.arrived.get(&slot)?;
+                if last_state.certificates.contains_key(&slot) || !self.has_quorum(voters) {
+                    return None;
+                }
+                next_state.certificates.insert(slot, voters.clone());
+            }
+            RewardsAction::Settle { slot } => {
+                if !self.may_settle(last_state, slot) {
+                    return None;
+                }
+                next_state.settled.insert(slot, last_state.arrived[&slot].clone());
+            }
+        }
+        Some(next_state)
+    }
+
+    /// Properties to verify in the rewards model
+    fn properties(&self) -> Vec<Property<Self>> {
+        vec![
+            // Property 1: Honest, responsive validators are rewarded in every certified slot
+            Property::<Self>::always("responsive_rewarded", |model, state| {
+                state.check_responsive_rewarded(model)
+            }),
+
+            // Property 2: Withholding a vote strictly reduces a validator's reward
+            Property::<Self>::always("withholding_reduces_reward", |model, state| {
+                state.check_withholding_reduces_reward(model)
+            }),
+
+            // Property 3: A vote arrives after its slot's window closed and goes uncredited
+            Property::<Self>::sometimes("late_vote_uncredited", |_model, state| {
+                state.settled.iter().any(|(slot, credited)| state.arrived[slot].len() > credited.len())
+            }),
+        ]
+    }
+}
+
+/// Run formal verification of voting rewards
+pub fn run_formal_verification(options: &CheckOptions) {
+    println!("=== Voting Reward Formal Verification ===");
+
+    let model = RewardsModel {
+        validator_count: 4, // Small for formal verification
+        responsive_count: 3,
+        max_slot: 2,
+        reward_scheme: RewardScheme::default(),
+    };
+
+    println!("Model checking voting rewards with {} validators ({} responsive), {} slots",
+             model.validator_count, model.responsive_count, model.max_slot);
+
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs()
+        .report(&mut stateright::report::WriteReporter::new(&mut std::io::stdout()));
+
+    // Check verification results
+    match options.print_outcomes(&result) {
+        Outcome::Proved => println!("✅ All voting reward properties verified successfully"),
+        Outcome::Violated => println!("❌ Voting reward verification found counterexamples"),
+        Outcome::Inconclusive => println!("❓ Voting reward verification inconclusive: a limit or bound stopped the search first"),
+    }
+    options.record_results("rewards formal", &result);
+}
+
+/// Test rewards model with different configurations
+pub fn test_rewards_model(validators: usize, responsive: usize, slots: u64, options: &CheckOptions) {
+    println!("Testing rewards model with {} validators ({} responsive), {} slots", validators, responsive, slots);
+
+    let model = RewardsModel {
+        validator_count: validators,
+        responsive_count: responsive,
+        max_slot: slots,
+        reward_scheme: RewardScheme::default(),
+    };
+
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs();
+    options.progress.watch(&result);
+    let result = result.join();
+
+    println!("States explored: {}", result.state_count());
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("rewards {}v {}r {}s", validators, responsive, slots), &result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(reward_scheme: RewardScheme) -> RewardsModel {
+        RewardsModel { validator_count: 3, responsive_count: 2, max_slot: 1, reward_scheme }
+    }
+
+    #[test]
+    fn test_withheld_pivotal_vote_forfeits_slot() {
+        let model = model(RewardScheme::PerVote);
+        let mut state = model.init_states().remove(0);
+        for action in [
+            RewardsAction::Vote { slot: 1, voter: 0 },
+            RewardsAction::Vote { slot: 1, voter: 1 },
+            RewardsAction::Arrive { slot: 1, voter: 0 },
+            RewardsAction::Arrive { slot: 1, voter: 1 },
+            RewardsAction::Certify { slot: 1 },
+            RewardsAction::Settle { slot: 1 },
+        ] {
+            state = model.next_state(&state, action).expect("enabled");
+        }
+        assert_eq!(model.rewards(&state.certificates, &state.settled), BTreeMap::from([(0, 1), (1, 1)]));
+        // Two of three validators are needed, so either vote withheld leaves the slot uncertified
+        assert_eq!(model.reward_if_withheld(&state, 0, 1), 0);
+        assert!(state.check_withholding_reduces_reward(&model));
+    }
+
+    #[test]
+    fn test_rewards_credit_votes_within_window() {
+        let checker = model(RewardScheme::PerVote).checker().spawn_bfs().join();
+        assert!(checker.discovery("responsive_rewarded").is_none());
+        assert!(checker.discovery("withholding_reduces_reward").is_none());
+        assert!(checker.discovery("late_vote_uncredited").is_some());
+
+        // Crediting only the certificate's votes misses responsive votes arriving after it,
+        // and crediting every validator makes withholding free
+        let checker = model(RewardScheme::CertificateOnly).checker().spawn_bfs().join();
+        assert!(checker.discovery("responsive_rewarded").is_some());
+        let checker = model(RewardScheme::EvenSplit).checker().spawn_bfs().join();
+        assert!(checker.discovery("withholding_reduces_reward").is_some());
+    }
+}
@@ -2,35 +2,131 @@
 //! This module provides a Stateright-based formal model for verifying message dissemination,
 //! erasure coding, and stake-weighted sampling mechanisms.
 
-use stateright::{Model, Property, Checker};
+use clap::ValueEnum;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use stateright::{Checker, Expectation, Model, Property};
 use std::collections::{BTreeMap, BTreeSet};
+use crate::network::{NetworkFault, NetworkFaults};
+use crate::shared::SharedVec;
+use crate::bounds::Bounded;
+use crate::limits::Outcome;
+use crate::options::CheckOptions;
+use std::time::Instant;
 
 // --- Formal Model Configuration ---
-const MAX_NODES: usize = 5; // Formal verification limit
-const MAX_SLOTS: u64 = 5; // Formal verification limit
-const FANOUT_SIZE: usize = 3; // Number of nodes to sample
+pub const DEFAULT_FANOUT: usize = 3; // Γ: relays sampled per slice, one per data or coding shred
 const TOTAL_STAKE: u64 = 1000;
+pub(crate) const DATA_SHREDS: usize = 2; // γ: distinct shreds needed to reconstruct a slice
+const SLICES_PER_BLOCK: usize = 1;
+const ROTOR_HOP_BOUND: u8 = 2; // Honest nodes get every block within two hops of its leader
+const BLOCK_VERSIONS: u64 = 2; // An equivocating leader shreds at most two versions of a slot
+pub const DEFAULT_LOAD_FACTOR_PERCENT: u64 = 200; // Tolerated deviation from stake-proportional relay load
 
 // Type aliases for clarity
 type NodeId = usize;
 type Slot = u64;
 type Stake = u64;
+type Hash = u64;
+
+/// A Reed-Solomon shred of a block slice. Indices below `DATA_SHREDS` carry
+/// data, the remaining `batch - DATA_SHREDS` carry coding information.
+/// Every shred names the Merkle root of its block and carries its leaf payload.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Shred {
+    slot: Slot,
+    slice: usize,
+    index: usize,
+    /// Γ: shreds in the slice's erasure batch
+    batch: usize,
+    root: Hash,
+    payload: Hash,
+}
+
+impl Shred {
+    /// Shred of the given version of a slot's block; honest leaders only produce version 0
+    fn new(slot: Slot, version: u64, slice: usize, index: usize, batch: usize) -> Self {
+        Self {
+            slot,
+            slice,
+            index,
+            batch,
+            root: merkle_root(slot, version, batch),
+            payload: shred_payload(slot, version, slice, index),
+        }
+    }
+
+    /// Whether this is a data shred rather than a coding shred
+    pub fn is_data(&self) -> bool {
+        self.index < DATA_SHREDS
+    }
+
+    /// Merkle proof check: the payload must be the leaf at this position of the
+    /// tree the claimed root commits to
+    fn verify(&self) -> bool {
+        (0..BLOCK_VERSIONS).any(|version| {
+            merkle_root(self.slot, version, self.batch) == self.root
+                && shred_payload(self.slot, version, self.slice, self.index) == self.payload
+        })
+    }
+}
+
+/// splitmix64 finalizer, used as the model's collision-free hash
+fn mix64(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Leaf payload of a shred in a given version of a block
+fn shred_payload(slot: Slot, version: u64, slice: usize, index: usize) -> Hash {
+    mix64(slot.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ (version << 48) ^ ((slice as u64) << 16) ^ index as u64)
+}
+
+/// Merkle root over all shred payloads of a version of a block
+fn merkle_root(slot: Slot, version: u64, batch: usize) -> Hash {
+    let mut level: Vec<Hash> = (0..SLICES_PER_BLOCK)
+        .flat_map(|slice| (0..batch).map(move |index| shred_payload(slot, version, slice, index)))
+        .collect();
+    while level.len() > 1 {
+        level = level.chunks(2)
+            .map(|pair| mix64(pair[0] ^ mix64(*pair.last().unwrap())))
+            .collect();
+    }
+    level[0]
+}
+
+/// Reference erasure decoder, independent of the nodes' bookkeeping: the data payloads of
+/// a slice under `root`, recovered once verified shreds at DATA_SHREDS distinct indices are held
+fn decode_slice(shreds: &BTreeSet<Shred>, slot: Slot, slice: usize, root: Hash) -> Option<Vec<Hash>> {
+    let held: Vec<&Shred> = shreds.iter()
+        .filter(|s| s.slot == slot && s.slice == slice && s.root == root && s.verify())
+        .collect();
+    let indices: BTreeSet<usize> = held.iter().map(|s| s.index).collect();
+    if indices.len() < DATA_SHREDS {
+        return None;
+    }
+    let batch = held[0].batch;
+    let version = (0..BLOCK_VERSIONS).find(|version| merkle_root(slot, *version, batch) == root)?;
+    Some((0..DATA_SHREDS).map(|index| shred_payload(slot, version, slice, index)).collect())
+}
 
 /// Represents different types of messages in the rotor system
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum RotorMessage {
-    /// A block or data message to be disseminated
+    /// A shred on its way from the leader through the relay tiers
     DataMessage {
-        slot: Slot,
-        data_id: u64,
+        shred: Shred,
         sender: NodeId,
+        hops: u8,
     },
-    /// A message forwarded through the rotor network
+    /// A shred broadcast by its last relay to the rest of the network
     ForwardedMessage {
-        slot: Slot,
-        data_id: u64,
+        shred: Shred,
         original_sender: NodeId,
         forwarder: NodeId,
+        hops: u8,
     },
     /// Sampling request for a specific slot
     SamplingRequest {
@@ -40,14 +136,214 @@ pub enum RotorMessage {
     /// Sampling response with selected nodes
     SamplingResponse {
         slot: Slot,
-        selected_nodes: BTreeSet<NodeId>,
+        selected_nodes: Vec<NodeId>,
         responder: NodeId,
     },
 }
 
+/// How relays are drawn from the stake distribution
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, ValueEnum)]
+pub enum SamplingStrategy {
+    /// Every relay drawn independently, proportionally to stake
+    Fa1Iid,
+    /// Partition sampling: large stakeholders fill whole bins deterministically,
+    /// the remaining stake is packed into bins and one relay is drawn per bin
+    #[default]
+    #[value(name = "psp")]
+    PsP,
+}
+
+/// How many relay tiers a shred passes before its final broadcast
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, ValueEnum)]
+pub enum DisseminationMode {
+    /// The leader sends every shred to every node itself
+    #[value(name = "direct")]
+    LeaderDirect,
+    /// Leader to one relay per shred, which broadcasts it (Rotor)
+    #[default]
+    OneHop,
+    /// Leader to a relay, which hands the shred to a second sampled tier that broadcasts it
+    TwoHop,
+}
+
+impl DisseminationMode {
+    /// Relay tiers between the leader and the final broadcast
+    fn relay_tiers(&self) -> u8 {
+        match self {
+            DisseminationMode::LeaderDirect => 0,
+            DisseminationMode::OneHop => 1,
+            DisseminationMode::TwoHop => 2,
+        }
+    }
+
+    /// Most network hops a shred can take to reach any node
+    pub fn max_hops(&self) -> u8 {
+        self.relay_tiers() + 1
+    }
+}
+
+/// Which point-to-point links the network offers; messages only travel along links
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Topology {
+    /// Every node is directly connected to every other node
+    #[default]
+    Mesh,
+    /// Node 0 is the hub every other node connects to
+    Star,
+    /// Node i links to nodes i - 1 and i + 1, wrapping around
+    Ring,
+    /// Binary tree rooted at node 0: node i links to its parent (i - 1) / 2
+    Tree,
+    /// Contiguous clusters meshed internally, joined through their first node
+    Clustered { clusters: usize },
+    /// Undirected links loaded from a DOT or JSON file
+    Graph(BTreeSet<(NodeId, NodeId)>),
+}
+
+impl Topology {
+    /// Parse a `--topology` value: a built-in topology name or the path of a `.dot`/`.json` graph
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        match spec {
+            "mesh" => Ok(Topology::Mesh),
+            "star" => Ok(Topology::Star),
+            "ring" => Ok(Topology::Ring),
+            "tree" => Ok(Topology::Tree),
+            "clustered" => Ok(Topology::Clustered { clusters: 2 }),
+            path => Self::from_file(path),
+        }
+    }
+
+    /// Load a graph, as JSON when the file ends in `.json` and as DOT otherwise
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("cannot read topology {}: {}", path, e))?;
+        if path.ends_with(".json") {
+            Self::from_json(&text)
+        } else {
+            Self::from_dot(&text)
+        }
+    }
+
+    /// Graph from DOT edge statements such as `0 -- 1;` or `2 -> 3`, which are taken as undirected
+    pub fn from_dot(text: &str) -> Result<Self, String> {
+        let mut links = BTreeSet::new();
+        for statement in text.split([';', '\n', '{', '}']) {
+            let separator = if statement.contains("--") { "--" } else { "->" };
+            let ends: Vec<&str> = statement.split(separator).collect();
+            if ends.len() < 2 {
+                continue;
+            }
+            let mut ids = Vec::new();
+            for end in ends {
+                let end = end.split('[').next().unwrap_or("").trim().trim_matches('"');
+                ids.push(end.parse::<NodeId>().map_err(|_| format!("invalid DOT node id: {:?}", end))?);
+            }
+            for pair in ids.windows(2) {
+                links.insert((pair[0].min(pair[1]), pair[0].max(pair[1])));
+            }
+        }
+        Ok(Topology::Graph(links))
+    }
+
+    /// Graph from a JSON object of the form `{"edges": [[0, 1], [1, 2]]}`; other fields are ignored
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let after_key = text.split_once("\"edges\"").ok_or("JSON topology has no \"edges\" field")?.1;
+        let array = after_key.trim_start().strip_prefix(':').ok_or("JSON topology \"edges\" has no value")?.trim_start();
+        if !array.starts_with('[') {
+            return Err("JSON topology \"edges\" must be an array".to_string());
+        }
+
+        // Scan to the bracket closing the edges array, so later fields are never read as edges
+        let mut depth = 0;
+        let end = array.char_indices()
+            .find(|(_, c)| {
+                match c {
+                    '[' => depth += 1,
+                    ']' => depth -= 1,
+                    _ => {}
+                }
+                depth == 0
+            })
+            .map(|(i, _)| i)
+            .ok_or("JSON topology \"edges\" array is not closed")?;
+
+        let mut links = BTreeSet::new();
+        let inner = array[1..end].trim();
+        if inner.is_empty() {
+            return Ok(Topology::Graph(links));
+        }
+        let mut rest = inner;
+        loop {
+            let pair = rest.strip_prefix('[').ok_or_else(|| format!("JSON topology edge is not an array: {}", rest))?;
+            let (pair, after) = pair.split_once(']').ok_or("JSON topology edge is not closed")?;
+            let ids = pair.split(',')
+                .map(|id| id.trim().parse::<NodeId>().map_err(|_| format!("invalid JSON node id: {:?}", id.trim())))
+                .collect::<Result<Vec<_>, _>>()?;
+            let [a, b] = ids[..] else {
+                return Err("JSON topology edges must be pairs of node ids".to_string());
+            };
+            links.insert((a.min(b), a.max(b)));
+            let after = after.trim_start();
+            if after.is_empty() {
+                return Ok(Topology::Graph(links));
+            }
+            rest = after.strip_prefix(',').ok_or_else(|| format!("expected ',' between JSON topology edges: {}", after))?.trim_start();
+        }
+    }
+
+    /// Whether two nodes of an `node_count`-node network share a link (a node always reaches itself)
+    pub fn connected(&self, a: NodeId, b: NodeId, node_count: usize) -> bool {
+        if a == b {
+            return true;
+        }
+        match self {
+            Topology::Mesh => true,
+            Topology::Star => a == 0 || b == 0,
+            Topology::Ring => (a + 1) % node_count == b || (b + 1) % node_count == a,
+            Topology::Tree => (b > 0 && a == (b - 1) / 2) || (a > 0 && b == (a - 1) / 2),
+            Topology::Clustered { clusters } => {
+                let cluster_of = |n: NodeId| n * clusters / node_count;
+                let is_gateway = |n: NodeId| n == 0 || cluster_of(n - 1) != cluster_of(n);
+                cluster_of(a) == cluster_of(b) || (is_gateway(a) && is_gateway(b))
+            }
+            Topology::Graph(links) => links.contains(&(a.min(b), a.max(b))),
+        }
+    }
+
+    /// Fewest links between two nodes, or `None` if they are disconnected
+    pub fn distance(&self, from: NodeId, to: NodeId, node_count: usize) -> Option<usize> {
+        self.distances_from(from, node_count).get(to).copied().flatten()
+    }
+
+    /// Fewest links from one node to every node, `None` for those it cannot reach
+    pub fn distances_from(&self, from: NodeId, node_count: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; node_count];
+        if from >= node_count {
+            return distances;
+        }
+        distances[from] = Some(0);
+        let mut frontier = vec![from];
+        let mut hops = 0;
+        while !frontier.is_empty() {
+            hops += 1;
+            let mut next = Vec::new();
+            for n in frontier {
+                for (m, distance) in distances.iter_mut().enumerate() {
+                    if distance.is_none() && self.connected(n, m, node_count) {
+                        *distance = Some(hops);
+                        next.push(m);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        distances
+    }
+}
+
 /// Represents messages in transit
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct MessageInTransit {
+    src: NodeId,
     dst: NodeId,
     msg: RotorMessage,
 }
@@ -55,10 +351,20 @@ pub struct MessageInTransit {
 /// Actions that can be taken in the rotor model
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum RotorAction {
-    /// Send a data message
+    /// Leader shreds its block and hands each shred to a relay
     SendData {
         slot: Slot,
-        data_id: u64,
+        sender: NodeId,
+    },
+    /// Byzantine leader shreds a second, conflicting version of its block
+    EquivocateData {
+        slot: Slot,
+        sender: NodeId,
+    },
+    /// Byzantine leader sends shreds under its block's root whose payloads are leaves of
+    /// the conflicting version, so they disagree with the tree the root commits to
+    ForgeData {
+        slot: Slot,
         sender: NodeId,
     },
     /// Deliver a message to its destination
@@ -75,6 +381,8 @@ pub enum RotorAction {
     },
     /// Advance to the next slot
     AdvanceSlot,
+    /// The network adversary drops, duplicates or delays a message
+    NetworkFault { fault: NetworkFault<MessageInTransit> },
 }
 
 /// State of a node in the rotor model
@@ -84,12 +392,24 @@ pub struct NodeState {
     stake: Stake,
     /// Whether the node is online
     is_online: bool,
-    /// Messages received by this node
-    received_messages: BTreeSet<(Slot, u64)>,
-    /// Messages forwarded by this node
-    forwarded_messages: BTreeSet<(Slot, u64)>,
-    /// Sampling history: slot -> selected nodes
-    sampling_history: BTreeMap<Slot, BTreeSet<NodeId>>,
+    /// Shreds received by this node
+    received_shreds: BTreeSet<Shred>,
+    /// Shreds relayed by this node
+    forwarded_shreds: BTreeSet<Shred>,
+    /// Shreds this node was sampled to relay
+    relay_assignments: u64,
+    /// Bandwidth used: shred messages this node put on the network
+    shreds_sent: u64,
+    /// Most hops any shred delivered to this node took
+    max_hops_received: u8,
+    /// Latency in hops at which each block became reconstructable here
+    block_hops: BTreeMap<Slot, u8>,
+    /// Reconstructed blocks this node voted for: slot -> Merkle root
+    reconstructed_blocks: BTreeMap<Slot, Hash>,
+    /// Slots for which this node holds shreds under conflicting roots
+    equivocation_evidence: BTreeSet<Slot>,
+    /// Sampling history: slot -> relay of each shred index
+    sampling_history: BTreeMap<Slot, Vec<NodeId>>,
     /// Current slot
     current_slot: Slot,
 }
@@ -99,14 +419,24 @@ pub struct NodeState {
 pub struct RotorState {
     /// Network messages in transit
     network: BTreeSet<MessageInTransit>,
+    /// Adversarial control over message delivery
+    network_faults: NetworkFaults<MessageInTransit>,
     /// Per-node states
-    nodes: Vec<NodeState>,
+    nodes: SharedVec<NodeState>,
     /// Global current slot
     current_slot: Slot,
     /// Stake distribution: node -> stake
     stake_distribution: BTreeMap<NodeId, Stake>,
-    /// Message dissemination tracking: (slot, data_id) -> set of nodes that received it
-    message_reach: BTreeMap<(Slot, u64), BTreeSet<NodeId>>,
+    /// Block dissemination tracking: slot -> set of nodes that reconstructed it
+    block_reach: BTreeMap<Slot, BTreeSet<NodeId>>,
+    /// Slots whose leader already shredded its block
+    proposed_slots: BTreeSet<Slot>,
+    /// Slots whose leader also shredded a conflicting version
+    equivocated_slots: BTreeSet<Slot>,
+    /// Slots whose leader also sent shreds with leaves forged under its root
+    forged_slots: BTreeSet<Slot>,
+    /// Γ: relays sampled per slice
+    fanout: usize,
 }
 
 /// Formal model for rotor sampling and message dissemination
@@ -116,10 +446,26 @@ pub struct RotorModel {
     pub node_count: usize,
     /// Maximum slots to explore
     pub max_slot: Slot,
+    /// Relay sampling strategy
+    pub sampling_strategy: SamplingStrategy,
+    /// Byzantine relays that silently drop the shreds assigned to them
+    pub byzantine: BTreeSet<NodeId>,
+    /// Crashed nodes: they never propose and drop everything delivered to them
+    pub offline: BTreeSet<NodeId>,
+    /// Relay load may deviate from the stake-proportional share by this factor (percent)
+    pub load_factor_percent: u64,
+    /// Relay tiers between leader and final broadcast
+    pub dissemination_mode: DisseminationMode,
+    /// Γ: relays sampled per slice; any DATA_SHREDS of the Γ shreds rebuild it
+    pub fanout: usize,
+    /// Links the network offers between nodes
+    pub topology: Topology,
+    /// Messages the network adversary may drop, duplicate or delay
+    pub network_faults: usize,
 }
 
 impl RotorState {
-    fn new(node_count: usize) -> Self {
+    fn new(node_count: usize, fanout: usize) -> Self {
         let mut stake_distribution = BTreeMap::new();
         let stake_per_node = TOTAL_STAKE / node_count as u64;
         
@@ -129,58 +475,302 @@ impl RotorState {
 
         Self {
             network: BTreeSet::new(),
+            network_faults: NetworkFaults::new(0),
             nodes: (0..node_count).map(|_i| NodeState {
                 stake: stake_per_node,
                 is_online: true,
-                received_messages: BTreeSet::new(),
-                forwarded_messages: BTreeSet::new(),
+                received_shreds: BTreeSet::new(),
+                forwarded_shreds: BTreeSet::new(),
+                relay_assignments: 0,
+                shreds_sent: 0,
+                max_hops_received: 0,
+                block_hops: BTreeMap::new(),
+                reconstructed_blocks: BTreeMap::new(),
+                equivocation_evidence: BTreeSet::new(),
                 sampling_history: BTreeMap::new(),
                 current_slot: 0,
             }).collect(),
             current_slot: 0,
             stake_distribution,
-            message_reach: BTreeMap::new(),
+            block_reach: BTreeMap::new(),
+            proposed_slots: BTreeSet::new(),
+            equivocated_slots: BTreeSet::new(),
+            forged_slots: BTreeSet::new(),
+            fanout,
         }
     }
 
-    /// Perform stake-weighted sampling for a slot
-    fn perform_stake_weighted_sampling(&self, slot: Slot, sampler: NodeId) -> BTreeSet<NodeId> {
-        let mut selected = BTreeSet::new();
-        let total_stake: Stake = self.stake_distribution.values().sum();
-        
-        // Use deterministic sampling based on slot and sampler
-        let seed = (slot * 1000 + sampler as u64) % total_stake;
-        let mut cumulative_stake = 0;
-        
-        for (node_id, stake) in &self.stake_distribution {
-            if *node_id != sampler && selected.len() < FANOUT_SIZE {
-                cumulative_stake += stake;
-                if seed < cumulative_stake {
-                    selected.insert(*node_id);
+    /// State for sampling relays over the stake of each node, outside the model
+    pub(crate) fn with_stakes(fanout: usize, stakes: &[Stake]) -> Self {
+        let mut state = Self::new(stakes.len(), fanout);
+        for (node_id, stake) in stakes.iter().enumerate() {
+            state.stake_distribution.insert(node_id, *stake);
+            state.nodes[node_id].stake = *stake;
+        }
+        state
+    }
+
+    /// Nodes eligible to relay for a sampler, with their stake
+    fn eligible_relays(&self, sampler: NodeId) -> Vec<(NodeId, Stake)> {
+        self.stake_distribution.iter()
+            .filter(|(node_id, stake)| **node_id != sampler && **stake > 0)
+            .map(|(node_id, stake)| (*node_id, *stake))
+            .collect()
+    }
+
+    /// Perform stake-weighted sampling for a slot: one relay per shred index
+    pub(crate) fn perform_stake_weighted_sampling(&self, slot: Slot, sampler: NodeId, strategy: SamplingStrategy) -> Vec<NodeId> {
+        let eligible = self.eligible_relays(sampler);
+        if eligible.is_empty() {
+            return Vec::new();
+        }
+
+        match strategy {
+            SamplingStrategy::Fa1Iid => {
+                let total_stake: Stake = eligible.iter().map(|(_, stake)| stake).sum();
+                (0..self.fanout)
+                    .map(|draw| pick_weighted(&eligible, sample_point(slot, sampler, draw, total_stake)))
+                    .collect()
+            }
+            SamplingStrategy::PsP => {
+                let (mut relays, bins) = self.partition_bins(sampler);
+                let capacity: Stake = eligible.iter().map(|(_, stake)| stake).sum();
+                for (bin_index, bin) in bins.iter().enumerate() {
+                    relays.push(pick_weighted(bin, sample_point(slot, sampler, bin_index, capacity)));
                 }
+                relays
             }
         }
-        
-        // Ensure we have at least some nodes selected
-        if selected.is_empty() {
-            for (node_id, _) in &self.stake_distribution {
-                if *node_id != sampler && selected.len() < FANOUT_SIZE {
-                    selected.insert(*node_id);
+    }
+
+    /// PS-P partitioning, in units of stake scaled by Γ so that a bin holds the
+    /// total eligible stake: every node first fills floor(ρΓ) whole bins, then
+    /// the leftover stakes are packed in order into the remaining bins
+    fn partition_bins(&self, sampler: NodeId) -> (Vec<NodeId>, Vec<Vec<(NodeId, Stake)>>) {
+        let eligible = self.eligible_relays(sampler);
+        let capacity: Stake = eligible.iter().map(|(_, stake)| stake).sum();
+        let mut full_bins = Vec::new();
+        let mut bins = Vec::new();
+        let mut current: Vec<(NodeId, Stake)> = Vec::new();
+        let mut filled = 0;
+
+        for (node_id, stake) in &eligible {
+            let weight = stake * self.fanout as u64;
+            full_bins.extend(std::iter::repeat_n(*node_id, (weight / capacity) as usize));
+            let mut remainder = weight % capacity;
+            while remainder > 0 {
+                let share = remainder.min(capacity - filled);
+                current.push((*node_id, share));
+                filled += share;
+                remainder -= share;
+                if filled == capacity {
+                    bins.push(std::mem::take(&mut current));
+                    filled = 0;
                 }
             }
         }
-        
-        selected
+
+        (full_bins, bins)
+    }
+
+    /// Expected variance of per-node relay load for one slice under a strategy
+    fn expected_load_variance(&self, sampler: NodeId, strategy: SamplingStrategy) -> f64 {
+        let eligible = self.eligible_relays(sampler);
+        let total_stake: Stake = eligible.iter().map(|(_, stake)| stake).sum();
+        match strategy {
+            SamplingStrategy::Fa1Iid => eligible.iter()
+                .map(|(_, stake)| {
+                    let rho = *stake as f64 / total_stake as f64;
+                    self.fanout as f64 * rho * (1.0 - rho)
+                })
+                .sum(),
+            SamplingStrategy::PsP => self.partition_bins(sampler).1.iter()
+                .flatten()
+                .map(|(_, share)| {
+                    let p = *share as f64 / total_stake as f64;
+                    p * (1.0 - p)
+                })
+                .sum(),
+        }
+    }
+
+    /// Bounds on the shreds per slice a relay should carry for a sampler in proportion
+    /// to its stake: floor and ceiling of ρΓ, or zero for the sampler itself
+    fn proportional_load(&self, sampler: NodeId, relay: NodeId) -> (u64, u64) {
+        let eligible = self.eligible_relays(sampler);
+        let total_stake: Stake = eligible.iter().map(|(_, stake)| stake).sum();
+        match eligible.iter().find(|(node_id, _)| *node_id == relay) {
+            Some((_, stake)) => {
+                let weight = stake * self.fanout as u64;
+                (weight / total_stake, weight.div_ceil(total_stake))
+            }
+            None => (0, 0),
+        }
+    }
+
+    /// Most shreds of a slice a single relay can be assigned under a strategy
+    fn max_relay_load(&self, sampler: NodeId, relay: NodeId, strategy: SamplingStrategy) -> usize {
+        match strategy {
+            SamplingStrategy::Fa1Iid => self.fanout,
+            SamplingStrategy::PsP => {
+                let (full_bins, bins) = self.partition_bins(sampler);
+                full_bins.iter().filter(|n| **n == relay).count()
+                    + bins.iter().filter(|bin| bin.iter().any(|(n, _)| *n == relay)).count()
+            }
+        }
+    }
+
+    /// Per-node stake, relay assignments and shreds sent, for load statistics
+    pub fn load_statistics(&self) -> Vec<(NodeId, Stake, u64, u64)> {
+        self.nodes.iter().enumerate()
+            .map(|(node_id, node)| (node_id, node.stake, node.relay_assignments, node.shreds_sent))
+            .collect()
+    }
+
+    /// Whether any shred of a slot is still travelling through the network
+    fn has_shreds_in_flight(&self, slot: Slot) -> bool {
+        self.network.iter().any(|m| match &m.msg {
+            RotorMessage::DataMessage { shred, .. } | RotorMessage::ForwardedMessage { shred, .. } => shred.slot == slot,
+            _ => false,
+        })
+    }
+
+    /// Shreds of a slot the network adversary dropped or is still holding back
+    fn withheld_shreds(&self, slot: Slot) -> usize {
+        self.network_faults.withheld()
+            .filter(|m| match &m.msg {
+                RotorMessage::DataMessage { shred, .. } | RotorMessage::ForwardedMessage { shred, .. } => shred.slot == slot,
+                _ => false,
+            })
+            .count()
+    }
+
+    /// Whether a node is neither Byzantine nor crashed
+    fn is_correct(&self, node_id: NodeId, byzantine: &BTreeSet<NodeId>) -> bool {
+        !byzantine.contains(&node_id) && self.nodes[node_id].is_online
     }
 
-    /// Check if a message has reached sufficient nodes (fanout achieved)
-    fn has_achieved_fanout(&self, slot: Slot, data_id: u64) -> bool {
-        if let Some(reached_nodes) = self.message_reach.get(&(slot, data_id)) {
-            reached_nodes.len() >= FANOUT_SIZE
+    /// Shreds per slice the leader of a slot handed to an all-honest, online relay path:
+    /// such a first relay and, with two tiers, at least one such second-tier relay
+    fn honest_relay_shreds(&self, slot: Slot, leader: NodeId, byzantine: &BTreeSet<NodeId>,
+                           mode: DisseminationMode, strategy: SamplingStrategy) -> usize {
+        if mode == DisseminationMode::LeaderDirect {
+            return self.fanout;
+        }
+        match self.nodes[leader].sampling_history.get(&slot) {
+            Some(relays) if !relays.is_empty() => (0..self.fanout)
+                .filter(|index| {
+                    let relay = relays[index % relays.len()];
+                    self.is_correct(relay, byzantine)
+                        && (mode != DisseminationMode::TwoHop
+                            || self.perform_stake_weighted_sampling(slot, relay, strategy)
+                                .iter().any(|second| self.is_correct(*second, byzantine)))
+                })
+                .count(),
+            _ => 0,
+        }
+    }
+
+    /// Check if a block has reached sufficient nodes (fanout achieved)
+    fn has_achieved_fanout(&self, slot: Slot) -> bool {
+        if let Some(reached_nodes) = self.block_reach.get(&slot) {
+            reached_nodes.len() >= self.fanout
         } else {
             false
         }
     }
+
+    /// Record a verified shred that travelled `hops` hops at a node, flag conflicting
+    /// roots as equivocation, and reconstruct (and vote for) the first block whose slices all decode
+    fn receive_shred(&mut self, node_id: NodeId, node_state: &mut NodeState, shred: Shred, hops: u8) {
+        if !shred.verify() {
+            return;
+        }
+        node_state.received_shreds.insert(shred);
+        node_state.max_hops_received = node_state.max_hops_received.max(hops);
+        if node_state.roots(shred.slot).len() > 1 {
+            node_state.equivocation_evidence.insert(shred.slot);
+        }
+        if !node_state.reconstructed_blocks.contains_key(&shred.slot) {
+            if let Some(root) = node_state.reconstructable_root(shred.slot) {
+                node_state.reconstructed_blocks.insert(shred.slot, root);
+                node_state.block_hops.insert(shred.slot, hops);
+                self.block_reach.entry(shred.slot).or_default().insert(node_id);
+            }
+        }
+    }
+}
+
+/// Deterministic pseudo-random point in `0..bound` for one draw of a sampling
+fn sample_point(slot: Slot, sampler: NodeId, draw: usize, bound: u64) -> u64 {
+    mix64(slot.wrapping_mul(0x9E37_79B9_7F4A_7C15) ^ ((sampler as u64) << 32) ^ draw as u64) % bound
+}
+
+/// Node whose cumulative stake segment contains `point`
+fn pick_weighted(candidates: &[(NodeId, Stake)], point: u64) -> NodeId {
+    let mut cumulative_stake = 0;
+    for (node_id, stake) in candidates {
+        cumulative_stake += stake;
+        if point < cumulative_stake {
+            return *node_id;
+        }
+    }
+    candidates[candidates.len() - 1].0
+}
+
+impl NodeState {
+    /// Merkle roots of the shreds held for a slot
+    fn roots(&self, slot: Slot) -> BTreeSet<Hash> {
+        self.received_shreds.iter().filter(|s| s.slot == slot).map(|s| s.root).collect()
+    }
+
+    /// Distinct shreds held for a slice of a slot under a given root
+    fn shred_count(&self, slot: Slot, slice: usize, root: Hash) -> usize {
+        self.received_shreds.iter()
+            .filter(|s| s.slot == slot && s.slice == slice && s.root == root)
+            .count()
+    }
+
+    /// First root under which every slice has DATA_SHREDS distinct shreds,
+    /// data or coding, and can therefore be recovered
+    fn reconstructable_root(&self, slot: Slot) -> Option<Hash> {
+        self.roots(slot).into_iter().find(|root| {
+            (0..SLICES_PER_BLOCK).all(|slice| self.shred_count(slot, slice, *root) >= DATA_SHREDS)
+        })
+    }
+}
+
+impl RotorModel {
+    /// Round-robin leader of a slot
+    fn leader(&self, slot: Slot) -> NodeId {
+        slot as usize % self.node_count
+    }
+
+    /// Whether the topology lets `from` send directly to `to`
+    fn link(&self, from: NodeId, to: NodeId) -> bool {
+        self.topology.connected(from, to, self.node_count)
+    }
+
+    /// Nodes the leader hands a shred index to: its relay, or everyone when sending directly,
+    /// as far as the topology links the leader to them
+    fn first_hop_targets(&self, relays: &[NodeId], index: usize, leader: NodeId) -> Vec<NodeId> {
+        let targets = match self.dissemination_mode {
+            DisseminationMode::LeaderDirect => (0..self.node_count).filter(|n| *n != leader).collect(),
+            _ if relays.is_empty() => Vec::new(),
+            _ => vec![relays[index % relays.len()]],
+        };
+        targets.into_iter().filter(|dst| self.link(leader, *dst)).collect()
+    }
+}
+
+impl Bounded for RotorState {
+    fn current_slot(&self) -> Slot {
+        self.current_slot
+    }
+
+    fn in_flight(&self) -> usize {
+        self.network.len()
+    }
 }
 
 impl Model for RotorModel {
@@ -188,7 +778,14 @@ impl Model for RotorModel {
     type Action = RotorAction;
 
     fn init_states(&self) -> Vec<Self::State> {
-        vec![RotorState::new(self.node_count)]
+        let mut state = RotorState::new(self.node_count, self.fanout);
+        for node_id in &self.offline {
+            if let Some(node) = state.nodes.get_mut(*node_id) {
+                node.is_online = false;
+            }
+        }
+        state.network_faults = NetworkFaults::new(self.network_faults);
+        vec![state]
     }
 
     fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
@@ -197,15 +794,35 @@ impl Model for RotorModel {
             actions.push(RotorAction::DeliverMessage { msg: msg.clone() });
         }
 
-        // 2. Send data messages for current and future slots
-        for slot in state.current_slot..=self.max_slot {
-            for sender in 0..self.node_count {
-                let data_id = slot * 1000 + sender as u64;
+        // The network adversary drops, duplicates or delays a bounded number of messages
+        for fault in state.network_faults.faults(&state.network) {
+            actions.push(RotorAction::NetworkFault { fault });
+        }
+
+        // 2. Leaders shred blocks for current and future slots
+        for slot in state.current_slot.max(1)..=self.max_slot {
+            let leader = self.leader(slot);
+            if !state.nodes[leader].is_online {
+                continue;
+            }
+            if !state.proposed_slots.contains(&slot) {
                 actions.push(RotorAction::SendData {
                     slot,
-                    data_id,
-                    sender,
+                    sender: leader,
                 });
+            } else if self.byzantine.contains(&leader) {
+                if !state.equivocated_slots.contains(&slot) {
+                    actions.push(RotorAction::EquivocateData {
+                        slot,
+                        sender: leader,
+                    });
+                }
+                if !state.forged_slots.contains(&slot) {
+                    actions.push(RotorAction::ForgeData {
+                        slot,
+                        sender: leader,
+                    });
+                }
             }
         }
 
@@ -240,24 +857,76 @@ impl Model for RotorModel {
         let mut nodes = last_state.nodes.clone();
 
         match action {
-            RotorAction::SendData { slot, data_id, sender } => {
-                // Mark message as received by sender
-                if let Some(node_state) = nodes.get_mut(sender) {
-                    node_state.received_messages.insert((slot, data_id));
+            RotorAction::SendData { slot, sender } => {
+                next_state.proposed_slots.insert(slot);
+
+                // Sample one relay per shred index
+                let relays = next_state.perform_stake_weighted_sampling(slot, sender, self.sampling_strategy);
+                nodes[sender].sampling_history.insert(slot, relays.clone());
+                if self.dissemination_mode != DisseminationMode::LeaderDirect {
+                    for relay in &relays {
+                        nodes[*relay].relay_assignments += SLICES_PER_BLOCK as u64;
+                    }
                 }
-                
-                // Update message reach
-                let reach_entry = next_state.message_reach.entry((slot, data_id)).or_default();
-                reach_entry.insert(sender);
 
-                // Request sampling to determine where to forward
-                next_state.network.insert(MessageInTransit {
-                    dst: sender,
-                    msg: RotorMessage::SamplingRequest {
-                        slot,
-                        requester: sender,
-                    },
-                });
+                // The leader holds every shred of its own block
+                let mut leader_state = nodes[sender].clone();
+                for slice in 0..SLICES_PER_BLOCK {
+                    for index in 0..self.fanout {
+                        let shred = Shred::new(slot, 0, slice, index, self.fanout);
+                        next_state.receive_shred(sender, &mut leader_state, shred, 0);
+                        for dst in self.first_hop_targets(&relays, index, sender) {
+                            leader_state.shreds_sent += 1;
+                            next_state.network.insert(MessageInTransit {
+                                src: sender,
+                                dst,
+                                msg: RotorMessage::DataMessage { shred, sender, hops: 1 },
+                            });
+                        }
+                    }
+                }
+                nodes[sender] = leader_state;
+            }
+            RotorAction::EquivocateData { slot, sender } => {
+                next_state.equivocated_slots.insert(slot);
+
+                // Same relays, different block under a different root
+                let relays = nodes[sender].sampling_history.get(&slot).cloned().unwrap_or_default();
+                for slice in 0..SLICES_PER_BLOCK {
+                    for index in 0..self.fanout {
+                        for dst in self.first_hop_targets(&relays, index, sender) {
+                            nodes[sender].shreds_sent += 1;
+                            next_state.network.insert(MessageInTransit {
+                                src: sender,
+                                dst,
+                                msg: RotorMessage::DataMessage { shred: Shred::new(slot, 1, slice, index, self.fanout), sender, hops: 1 },
+                            });
+                        }
+                    }
+                }
+            }
+            RotorAction::ForgeData { slot, sender } => {
+                next_state.forged_slots.insert(slot);
+
+                // Same relays, leaves of the conflicting version claimed under the honest root
+                let relays = nodes[sender].sampling_history.get(&slot).cloned().unwrap_or_default();
+                for slice in 0..SLICES_PER_BLOCK {
+                    for index in 0..self.fanout {
+                        let honest = Shred::new(slot, 0, slice, index, self.fanout);
+                        let shred = Shred { payload: shred_payload(slot, 1, slice, index), ..honest };
+                        for dst in self.first_hop_targets(&relays, index, sender) {
+                            nodes[sender].shreds_sent += 1;
+                            next_state.network.insert(MessageInTransit {
+                                src: sender,
+                                dst,
+                                msg: RotorMessage::DataMessage { shred, sender, hops: 1 },
+                            });
+                        }
+                    }
+                }
+            }
+            RotorAction::NetworkFault { fault } => {
+                if !next_state.network_faults.apply(&mut next_state.network, fault) { return None; }
             }
             RotorAction::DeliverMessage { msg } => {
                 let recipient_id = msg.dst;
@@ -265,53 +934,66 @@ impl Model for RotorModel {
 
                 // Remove message from network
                 if !next_state.network.remove(&msg) { return None; }
+                next_state.network_faults.record_delivery(&msg);
+                if !node_state.is_online {
+                    // Lost at a crashed node
+                    next_state.nodes = nodes;
+                    return Some(next_state);
+                }
 
                 match msg.msg {
-                    RotorMessage::DataMessage { slot, data_id, sender } => {
-                        // Node receives data message
-                        node_state.received_messages.insert((slot, data_id));
-                        
-                        // Update message reach
-                        let reach_entry = next_state.message_reach.entry((slot, data_id)).or_default();
-                        reach_entry.insert(recipient_id);
-                        
-                        // Forward to sampled nodes
-                        if let Some(selected_nodes) = node_state.sampling_history.get(&slot) {
-                            for &target in selected_nodes {
-                                if target != recipient_id {
+                    RotorMessage::DataMessage { shred, sender, hops } => {
+                        // Relay receives its shred and, unless Byzantine or the shred fails
+                        // its Merkle check, passes it on: to the next relay tier, or as a
+                        // broadcast from the last tier
+                        next_state.receive_shred(recipient_id, &mut node_state, shred, hops);
+                        let tiers = self.dissemination_mode.relay_tiers();
+                        if hops <= tiers
+                            && !self.byzantine.contains(&recipient_id)
+                            && node_state.received_shreds.contains(&shred)
+                            && node_state.forwarded_shreds.insert(shred)
+                        {
+                            if hops < tiers {
+                                let next_tier = next_state.perform_stake_weighted_sampling(shred.slot, recipient_id, self.sampling_strategy);
+                                for target in next_tier.into_iter().filter(|t| self.link(recipient_id, *t)).collect::<BTreeSet<_>>() {
+                                    node_state.shreds_sent += 1;
                                     next_state.network.insert(MessageInTransit {
+                                        src: recipient_id,
                                         dst: target,
-                                        msg: RotorMessage::ForwardedMessage {
-                                            slot,
-                                            data_id,
-                                            original_sender: sender,
-                                            forwarder: recipient_id,
-                                        },
+                                        msg: RotorMessage::DataMessage { shred, sender, hops: hops + 1 },
                                     });
                                 }
+                            } else {
+                                for target in 0..self.node_count {
+                                    if target != recipient_id && target != sender && self.link(recipient_id, target) {
+                                        node_state.shreds_sent += 1;
+                                        next_state.network.insert(MessageInTransit {
+                                            src: recipient_id,
+                                            dst: target,
+                                            msg: RotorMessage::ForwardedMessage {
+                                                shred,
+                                                original_sender: sender,
+                                                forwarder: recipient_id,
+                                                hops: hops + 1,
+                                            },
+                                        });
+                                    }
+                                }
                             }
                         }
                     }
-                    RotorMessage::ForwardedMessage { slot, data_id, original_sender: _, forwarder } => {
-                        // Node receives forwarded message
-                        node_state.received_messages.insert((slot, data_id));
-                        
-                        // Update message reach
-                        let reach_entry = next_state.message_reach.entry((slot, data_id)).or_default();
-                        reach_entry.insert(recipient_id);
-                        
-                        // Mark as forwarded by the forwarder
-                        if let Some(forwarder_state) = nodes.get_mut(forwarder) {
-                            forwarder_state.forwarded_messages.insert((slot, data_id));
-                        }
+                    RotorMessage::ForwardedMessage { shred, original_sender: _, forwarder: _, hops } => {
+                        // Node receives a relayed shred
+                        next_state.receive_shred(recipient_id, &mut node_state, shred, hops);
                     }
                     RotorMessage::SamplingRequest { slot, requester } => {
                         // Perform sampling and respond
-                        let selected_nodes = next_state.perform_stake_weighted_sampling(slot, requester);
+                        let selected_nodes = next_state.perform_stake_weighted_sampling(slot, requester, self.sampling_strategy);
                         node_state.sampling_history.insert(slot, selected_nodes.clone());
                         
                         // Send sampling response
                         next_state.network.insert(MessageInTransit {
+                            src: recipient_id,
                             dst: requester,
                             msg: RotorMessage::SamplingResponse {
                                 slot,
@@ -330,6 +1012,7 @@ impl Model for RotorModel {
             RotorAction::RequestSampling { slot, requester } => {
                 // Send sampling request
                 next_state.network.insert(MessageInTransit {
+                    src: requester,
                     dst: requester,
                     msg: RotorMessage::SamplingRequest {
                         slot,
@@ -339,7 +1022,7 @@ impl Model for RotorModel {
             }
             RotorAction::PerformSampling { slot, sampler } => {
                 // Perform sampling and store results
-                let selected_nodes = next_state.perform_stake_weighted_sampling(slot, sampler);
+                let selected_nodes = next_state.perform_stake_weighted_sampling(slot, sampler, self.sampling_strategy);
                 if let Some(node_state) = nodes.get_mut(sampler) {
                     node_state.sampling_history.insert(slot, selected_nodes);
                 }
@@ -361,30 +1044,26 @@ impl Model for RotorModel {
         vec![
             // Property 1: Message dissemination completeness
             Property::<Self>::always("message_dissemination", |model, state| {
-                // All sent messages should eventually reach multiple nodes
-                for slot in 1..=model.max_slot {
-                    for sender in 0..model.node_count {
-                        let data_id = slot * 1000 + sender as u64;
-                        if let Some(reached_nodes) = state.message_reach.get(&(slot, data_id)) {
-                            // Message should reach at least the sender and some other nodes
-                            if reached_nodes.len() < 2 {
-                                return false;
-                            }
-                        }
+                // Every shredded block is held by its leader, and relays only
+                // broadcast shreds they actually received
+                for slot in &state.proposed_slots {
+                    let leader = model.leader(*slot);
+                    if !state.block_reach.get(slot).is_some_and(|reached| reached.contains(&leader)) {
+                        return false;
                     }
                 }
-                true
+                state.nodes.iter().all(|node| node.forwarded_shreds.is_subset(&node.received_shreds))
             }),
             
             // Property 2: Stake-weighted sampling fairness
             Property::<Self>::always("stake_weighted_sampling", |model, state| {
                 // Sampling should be deterministic and based on stake
-                for node in &state.nodes {
+                for (sampler, node) in state.nodes.iter().enumerate() {
                     for (slot, selected_nodes) in &node.sampling_history {
                         if *slot <= model.max_slot {
                             // Verify sampling was performed correctly
-                            let _expected_selection = state.perform_stake_weighted_sampling(*slot, 0);
-                            if selected_nodes.len() > FANOUT_SIZE {
+                            let expected_selection = state.perform_stake_weighted_sampling(*slot, sampler, model.sampling_strategy);
+                            if selected_nodes.len() > model.fanout || *selected_nodes != expected_selection {
                                 return false;
                             }
                         }
@@ -395,16 +1074,13 @@ impl Model for RotorModel {
             
             // Property 3: Fanout achievement
             Property::<Self>::always("fanout_achievement", |model, state| {
-                // Messages should achieve the required fanout
+                // Blocks should achieve the required fanout
                 for slot in 1..=model.max_slot {
-                    for sender in 0..model.node_count {
-                        let data_id = slot * 1000 + sender as u64;
-                        if state.has_achieved_fanout(slot, data_id) {
-                            // Verify fanout was achieved correctly
-                            if let Some(reached_nodes) = state.message_reach.get(&(slot, data_id)) {
-                                if reached_nodes.len() < FANOUT_SIZE {
-                                    return false;
-                                }
+                    if state.has_achieved_fanout(slot) {
+                        // Verify fanout was achieved correctly
+                        if let Some(reached_nodes) = state.block_reach.get(&slot) {
+                            if reached_nodes.len() < model.fanout {
+                                return false;
                             }
                         }
                     }
@@ -414,12 +1090,12 @@ impl Model for RotorModel {
             
             // Property 4: No message duplication
             Property::<Self>::always("no_message_duplication", |_model, state| {
-                // Each node should receive each message at most once
+                // Each node should receive each shred at most once
                 for node in &state.nodes {
-                    let mut message_counts: BTreeMap<(Slot, u64), usize> = BTreeMap::new();
+                    let mut message_counts: BTreeMap<Shred, usize> = BTreeMap::new();
                     
-                    for (slot, data_id) in &node.received_messages {
-                        *message_counts.entry((*slot, *data_id)).or_insert(0) += 1;
+                    for shred in &node.received_shreds {
+                        *message_counts.entry(*shred).or_insert(0) += 1;
                     }
                     
                     // Check for duplicates
@@ -431,55 +1107,743 @@ impl Model for RotorModel {
                 }
                 true
             }),
+
+            // Property 5: Erasure-coded reconstruction
+            Property::<Self>::always("shred_reconstruction", |_model, state| {
+                // A node reconstructs a block exactly when the reference decoder recovers
+                // every slice of some root from the shreds it holds, and the block it
+                // reconstructed is one the decoder recovers
+                for node in &state.nodes {
+                    let slots: BTreeSet<Slot> = node.received_shreds.iter().map(|s| s.slot)
+                        .chain(node.reconstructed_blocks.keys().copied())
+                        .collect();
+                    for slot in slots {
+                        let decodes = |root: Hash| (0..SLICES_PER_BLOCK)
+                            .all(|slice| decode_slice(&node.received_shreds, slot, slice, root).is_some());
+                        let decodable = node.received_shreds.iter()
+                            .any(|s| s.slot == slot && decodes(s.root));
+                        match node.reconstructed_blocks.get(&slot) {
+                            Some(root) if !decodes(*root) => return false,
+                            reconstructed if reconstructed.is_some() != decodable => return false,
+                            _ => {}
+                        }
+                    }
+                }
+                true
+            }),
+
+            // Property 6: No relay carries more shreds than its strategy allows
+            Property::<Self>::always("sampling_resilience", |model, state| {
+                // Under PS-P a relay of stake ρ holds at most floor(ρΓ) + 2 shreds of
+                // a slice, so a single faulty relay erases few shreds; FA1-IID can
+                // hand every shred to the same relay
+                for slot in &state.proposed_slots {
+                    let leader = model.leader(*slot);
+                    let Some(relays) = state.nodes[leader].sampling_history.get(slot) else { continue };
+                    for relay in relays {
+                        let load = relays.iter().filter(|r| *r == relay).count();
+                        let psp_bound = state.max_relay_load(leader, *relay, SamplingStrategy::PsP);
+                        if load > state.max_relay_load(leader, *relay, model.sampling_strategy)
+                            || psp_bound > state.max_relay_load(leader, *relay, SamplingStrategy::Fa1Iid)
+                        {
+                            return false;
+                        }
+                    }
+                }
+                true
+            }),
+
+            // Property 7: Dissemination succeeds despite withholding or crashed relays
+            Property::<Self>::always("dissemination_success", |model, state| {
+                // Once a slot's shreds have settled, every honest online node holds the block
+                // whenever honest online relays were handed at least DATA_SHREDS shreds per slice
+                // beyond those the network adversary withheld
+                for slot in &state.proposed_slots {
+                    let honest_shreds = state.honest_relay_shreds(*slot, model.leader(*slot), &model.byzantine,
+                                                                  model.dissemination_mode, model.sampling_strategy);
+                    if state.has_shreds_in_flight(*slot)
+                        || honest_shreds.saturating_sub(state.withheld_shreds(*slot)) < DATA_SHREDS
+                    {
+                        continue;
+                    }
+                    let all_honest_reconstructed = state.nodes.iter().enumerate()
+                        .filter(|(id, _)| state.is_correct(*id, &model.byzantine))
+                        .all(|(_, node)| node.reconstructed_blocks.contains_key(slot));
+                    if !all_honest_reconstructed {
+                        return false;
+                    }
+                }
+                true
+            }),
+
+            // Property 8: A Merkle root binds honest nodes to a single block
+            Property::<Self>::always("merkle_root_binding", |model, state| {
+                // Honest nodes never hold two different payloads for the same shred
+                // position under the same root, so all that reconstruct and vote for
+                // a root reconstruct the same block
+                let mut leaves: BTreeMap<(Slot, usize, usize, Hash), Hash> = BTreeMap::new();
+                for (id, node) in state.nodes.iter().enumerate() {
+                    if model.byzantine.contains(&id) {
+                        continue;
+                    }
+                    for shred in &node.received_shreds {
+                        let key = (shred.slot, shred.slice, shred.index, shred.root);
+                        if *leaves.entry(key).or_insert(shred.payload) != shred.payload {
+                            return false;
+                        }
+                    }
+                }
+                true
+            }),
+
+            // Property 9: Conflicting roots are detected, and only leaders that equivocated are accused
+            Property::<Self>::always("equivocation_evidence", |model, state| {
+                for (id, node) in state.nodes.iter().enumerate() {
+                    if model.byzantine.contains(&id) {
+                        continue;
+                    }
+                    let slots: BTreeSet<Slot> = node.received_shreds.iter().map(|s| s.slot).collect();
+                    for slot in &slots {
+                        if (node.roots(*slot).len() > 1) != node.equivocation_evidence.contains(slot) {
+                            return false;
+                        }
+                    }
+                    if !node.equivocation_evidence.is_subset(&state.equivocated_slots) {
+                        return false;
+                    }
+                }
+                true
+            }),
+
+            // Property 10: Relay load is proportional to stake
+            Property::<Self>::always("proportional_relay_load", |model, state| {
+                // Rotor's fairness claim: summed over the proposed slots, the shreds a
+                // node relays stay within load_factor_percent of its stake share, with
+                // each slot's share rounded to whole shreds
+                if model.dissemination_mode == DisseminationMode::LeaderDirect {
+                    return true; // No relays
+                }
+                for (node_id, node) in state.nodes.iter().enumerate() {
+                    let (mut lower, mut upper) = (0, 0);
+                    for slot in &state.proposed_slots {
+                        let (floor, ceil) = state.proportional_load(model.leader(*slot), node_id);
+                        lower += floor * SLICES_PER_BLOCK as u64;
+                        upper += ceil * SLICES_PER_BLOCK as u64;
+                    }
+                    if node.relay_assignments * 100 > upper * model.load_factor_percent
+                        || lower * 100 > node.relay_assignments * model.load_factor_percent
+                    {
+                        return false;
+                    }
+                }
+                true
+            }),
+
+            // Property 11: No shred travels more hops than its dissemination mode allows
+            Property::<Self>::always("hop_bound", |model, state| {
+                let max_hops = model.dissemination_mode.max_hops();
+                let in_flight_within_bound = state.network.iter().all(|m| match &m.msg {
+                    RotorMessage::DataMessage { hops, .. } | RotorMessage::ForwardedMessage { hops, .. } => *hops <= max_hops,
+                    _ => true,
+                });
+                in_flight_within_bound && state.nodes.iter().all(|n| n.max_hops_received <= max_hops)
+            }),
+
+            // Property 12: With honest relays every honest node gets the block within two hops
+            Property::<Self>::always("two_hop_latency", |model, state| {
+                // Rotor's headline latency claim; the two-hop mode trades one more
+                // hop for a smaller leader fanout and is held to its own hop budget
+                let bound = ROTOR_HOP_BOUND.max(model.dissemination_mode.max_hops());
+                for slot in &state.proposed_slots {
+                    let leader = model.leader(*slot);
+                    let honest_shreds = state.honest_relay_shreds(*slot, leader, &model.byzantine,
+                        model.dissemination_mode, model.sampling_strategy);
+                    let reconstructible = honest_shreds.saturating_sub(state.withheld_shreds(*slot)) >= DATA_SHREDS;
+                    if state.has_shreds_in_flight(*slot) || honest_shreds != model.fanout || !reconstructible {
+                        continue;
+                    }
+                    let within_bound = state.nodes.iter().enumerate()
+                        .filter(|(id, _)| state.is_correct(*id, &model.byzantine))
+                        .all(|(_, node)| node.block_hops.get(slot).is_some_and(|hops| *hops <= bound));
+                    if !within_bound {
+                        return false;
+                    }
+                }
+                true
+            }),
+
+            // Property 13: Blocks only reach nodes within the mode's hop budget over topology links
+            Property::<Self>::always("topology_reach", |model, state| {
+                state.block_reach.iter().all(|(slot, reached)| {
+                    let distances = model.topology.distances_from(model.leader(*slot), model.node_count);
+                    reached.iter().all(|node| {
+                        distances[*node].is_some_and(|links| links <= model.dissemination_mode.max_hops() as usize)
+                    })
+                })
+            }),
         ]
     }
 }
 
 /// Run formal verification of rotor sampling
-pub fn run_formal_verification() {
+pub fn run_formal_verification(options: &CheckOptions) {
     println!("=== Rotor Sampling Formal Verification ===");
     
     let model = RotorModel {
         node_count: 4, // Small for formal verification
         max_slot: 3,
+        sampling_strategy: SamplingStrategy::default(),
+        byzantine: BTreeSet::new(),
+        offline: BTreeSet::new(),
+        load_factor_percent: DEFAULT_LOAD_FACTOR_PERCENT,
+        dissemination_mode: DisseminationMode::default(),
+        fanout: DEFAULT_FANOUT,
+        topology: Topology::default(),
+        network_faults: 0,
     };
 
     println!("Model checking rotor sampling with {} nodes, {} slots", 
              model.node_count, model.max_slot);
     
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
+    options.print_summary();
+
+    let result = options
+        .checker(model)
         .spawn_dfs()
         .report(&mut stateright::report::WriteReporter::new(&mut std::io::stdout()));
     
     // Check verification results
-    if result.discoveries().is_empty() {
-        println!("✅ All rotor sampling properties verified successfully");
-    } else {
-        println!("❌ Rotor sampling verification found counterexamples");
-        for (property_name, _path) in result.discoveries() {
-            println!("  - {}", property_name);
-        }
+    match options.print_outcomes(&result) {
+        Outcome::Proved => println!("✅ All rotor sampling properties verified successfully"),
+        Outcome::Violated => println!("❌ Rotor sampling verification found counterexamples"),
+        Outcome::Inconclusive => println!("❓ Rotor sampling verification inconclusive: a limit or bound stopped the search first"),
     }
+    options.record_results("rotor formal", &result);
 }
 
 /// Test rotor model with different configurations
-pub fn test_rotor_model(nodes: usize, slots: u64) {
-    println!("Testing rotor model with {} nodes, {} slots", nodes, slots);
-    
-    let model = RotorModel {
-        node_count: nodes,
-        max_slot: slots,
-    };
+pub fn test_rotor_model(model: RotorModel, options: &CheckOptions) {
+    let (nodes, slots, byzantine_count, fanout) = (model.node_count, model.max_slot, model.byzantine.len(), model.fanout);
+    println!("Testing rotor model with {} nodes ({} Byzantine), {} slots, fanout {}, {:?} sampling, {:?} dissemination, {:?} topology",
+             nodes, byzantine_count, slots, fanout, model.sampling_strategy, model.dissemination_mode, model.topology);
+
+    options.print_summary();
 
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
+    let result = options
+        .checker(model)
         .spawn_dfs();
+    options.progress.watch(&result);
+    let result = result.join();
     
     println!("States explored: {}", result.state_count());
-    println!("Properties verified: {}", result.discoveries().is_empty());
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("rotor {}n {}s {}b fanout {}", nodes, slots, byzantine_count, fanout), &result);
+}
+
+// --- Randomized Simulation ---
+
+const MAX_SIMULATION_STEPS: usize = 1_000_000;
+const SLOT_DURATION_MS: u64 = 400;
+const DEFAULT_LINK_DELAY_MS: u64 = 10;
+const SAMPLING_ROUNDS: u64 = 2_000; // Slots sampled when measuring relay frequencies
+const MAX_SAMPLING_DEVIATION: f64 = 0.1; // Tolerated total variation distance from stake shares
+
+/// Parameters of a single randomized run of the rotor model
+#[derive(Clone, Debug)]
+pub struct SimulationConfig {
+    /// Number of nodes
+    pub nodes: usize,
+    /// Slots to simulate, one proposal every SLOT_DURATION_MS
+    pub slots: Slot,
+    /// Γ: relays sampled per slice
+    pub fanout: usize,
+    /// Relay sampling strategy
+    pub sampling_strategy: SamplingStrategy,
+    /// Relay tiers between leader and final broadcast
+    pub dissemination_mode: DisseminationMode,
+    /// Physical links; messages between non-adjacent nodes are routed over shortest paths
+    pub topology: Topology,
+    /// Stake of each node, or empty for an even split
+    pub stakes: Vec<Stake>,
+    /// Byzantine relays that withhold their shreds
+    pub byzantine: BTreeSet<NodeId>,
+    /// Crashed nodes
+    pub offline: BTreeSet<NodeId>,
+    /// Upper bound on the delay of a single link in milliseconds
+    pub delay_ms: u64,
+    /// Seed for scheduling and delay choices
+    pub seed: u64,
+}
+
+impl SimulationConfig {
+    fn new(nodes: usize, slots: Slot, seed: u64) -> Self {
+        Self {
+            nodes,
+            slots,
+            fanout: DEFAULT_FANOUT,
+            sampling_strategy: SamplingStrategy::default(),
+            dissemination_mode: DisseminationMode::default(),
+            topology: Topology::Mesh,
+            stakes: Vec::new(),
+            byzantine: BTreeSet::new(),
+            offline: BTreeSet::new(),
+            delay_ms: DEFAULT_LINK_DELAY_MS,
+            seed,
+        }
+    }
+
+    /// Model the simulation drives; routing over the topology is left to the simulator
+    fn model(&self) -> RotorModel {
+        RotorModel {
+            node_count: self.nodes,
+            max_slot: self.slots,
+            sampling_strategy: self.sampling_strategy,
+            byzantine: self.byzantine.clone(),
+            offline: self.offline.clone(),
+            load_factor_percent: DEFAULT_LOAD_FACTOR_PERCENT,
+            dissemination_mode: self.dissemination_mode,
+            fanout: self.fanout,
+            topology: Topology::Mesh,
+            network_faults: 0,
+        }
+    }
+
+    /// Initial state with the configured crashes and stake distribution
+    fn initial_state(&self) -> RotorState {
+        let mut state = self.model().init_states().remove(0);
+        for (node_id, stake) in self.stakes.iter().enumerate().take(self.nodes) {
+            state.stake_distribution.insert(node_id, *stake);
+            state.nodes[node_id].stake = *stake;
+        }
+        state
+    }
+
+    /// Longest shortest path between any two nodes, `None` if the topology is disconnected
+    fn diameter(&self) -> Option<usize> {
+        (0..self.nodes)
+            .flat_map(|from| self.topology.distances_from(from, self.nodes))
+            .try_fold(0, |longest, distance| distance.map(|d| longest.max(d)))
+    }
+}
+
+/// Final state and observations of a simulation run
+#[derive(Clone, Debug)]
+pub struct SimulationOutcome {
+    /// State reached when the run went quiescent
+    pub state: RotorState,
+    /// Byzantine relays of the run
+    pub byzantine: BTreeSet<NodeId>,
+    /// Model actions taken
+    pub steps: usize,
+    /// Simulated wall-clock time in milliseconds
+    pub elapsed_ms: u64,
+    /// Simulated time from each slot's proposal until the last node reconstructed it
+    pub latency_ms: BTreeMap<Slot, u64>,
+    /// Safety properties violated along the run
+    pub violations: Vec<&'static str>,
+}
+
+impl SimulationOutcome {
+    /// Whether every honest online node reconstructed the slot's block
+    fn delivered(&self, slot: Slot) -> bool {
+        self.state.nodes.iter().enumerate()
+            .filter(|(id, _)| self.state.is_correct(*id, &self.byzantine))
+            .all(|(_, node)| node.reconstructed_blocks.contains_key(&slot))
+    }
+
+    /// Shreds put on the network by all nodes
+    fn shreds_sent(&self) -> u64 {
+        self.state.nodes.iter().map(|node| node.shreds_sent).sum()
+    }
+
+    fn print_summary(&self, slots: Slot) {
+        println!("Steps: {}, simulated time: {}ms, shreds sent: {}", self.steps, self.elapsed_ms, self.shreds_sent());
+        for slot in 1..=slots {
+            let reached = self.state.block_reach.get(&slot).map_or(0, |nodes| nodes.len());
+            let latency = self.latency_ms.get(&slot).map_or("-".to_string(), |ms| format!("{}ms", ms));
+            println!("  Slot {}: reached {}/{} nodes, latency {}{}", slot, reached, self.state.nodes.len(),
+                     latency, if self.delivered(slot) { "" } else { " (not delivered)" });
+        }
+        for property_name in &self.violations {
+            println!("  ❌ Violated: {}", property_name);
+        }
+    }
+}
+
+/// Drive the rotor model with simulated wall-clock time and random scheduling.
+///
+/// Leaders propose every `SLOT_DURATION_MS`, a message crosses each link of its
+/// shortest path through the topology after a random delay of at most `delay_ms`
+/// (and is lost if no path exists), and among the actions that are ready at the
+/// current instant one is picked at random. The model's safety properties are
+/// checked whenever simulated time advances.
+pub fn simulate(config: &SimulationConfig) -> SimulationOutcome {
+    let model = config.model();
+    let properties = model.properties();
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut routes: BTreeMap<NodeId, Vec<Option<usize>>> = BTreeMap::new();
+
+    let mut state = config.initial_state();
+    let mut deliver_at: BTreeMap<MessageInTransit, u64> = BTreeMap::new();
+    let mut proposed_at: BTreeMap<Slot, u64> = BTreeMap::new();
+    let mut reach_counts: BTreeMap<Slot, usize> = BTreeMap::new();
+    let mut latency_ms = BTreeMap::new();
+    let mut violations = Vec::new();
+    let mut now = 0;
+    let mut steps = 0;
+
+    while steps < MAX_SIMULATION_STEPS {
+        // Crashed leaders never propose
+        let pending: Vec<Slot> = (1..=config.slots)
+            .filter(|slot| !state.proposed_slots.contains(slot) && state.nodes[model.leader(*slot)].is_online)
+            .collect();
+        let proposals: Vec<Slot> = pending.iter()
+            .copied()
+            .filter(|slot| (slot - 1) * SLOT_DURATION_MS <= now)
+            .collect();
+        let due: Vec<&MessageInTransit> = deliver_at.iter()
+            .filter(|(_, t)| **t <= now)
+            .map(|(msg, _)| msg)
+            .collect();
+
+        if proposals.is_empty() && due.is_empty() {
+            for property in &properties {
+                if property.expectation == Expectation::Always
+                    && !(property.condition)(&model, &state)
+                    && !violations.contains(&property.name)
+                {
+                    violations.push(property.name);
+                }
+            }
+
+            // Nothing to do at this instant: jump to the next proposal or delivery
+            let next_proposal = pending.first().map(|slot| (slot - 1) * SLOT_DURATION_MS);
+            let next_delivery = deliver_at.values().min().copied();
+            match next_proposal.into_iter().chain(next_delivery).min() {
+                Some(next) => now = next,
+                None => break,
+            }
+            continue;
+        }
+
+        let pick = rng.gen_range(0..proposals.len() + due.len());
+        let action = match proposals.get(pick) {
+            Some(slot) => RotorAction::SendData { slot: *slot, sender: model.leader(*slot) },
+            None => RotorAction::DeliverMessage { msg: due[pick - proposals.len()].clone() },
+        };
+        match &action {
+            RotorAction::SendData { slot, .. } => {
+                proposed_at.insert(*slot, now);
+            }
+            RotorAction::DeliverMessage { msg } => {
+                deliver_at.remove(msg);
+            }
+            _ => {}
+        }
+        state = match model.next_state(&state, action) {
+            Some(next_state) => next_state,
+            None => break,
+        };
+        steps += 1;
+
+        // Schedule new messages along their route, dropping those without one
+        let unscheduled: Vec<MessageInTransit> = state.network.iter()
+            .filter(|msg| !deliver_at.contains_key(*msg))
+            .cloned()
+            .collect();
+        for msg in unscheduled {
+            let distances = routes.entry(msg.src)
+                .or_insert_with(|| config.topology.distances_from(msg.src, config.nodes));
+            match distances[msg.dst] {
+                Some(links) => {
+                    let delay: u64 = (0..links.max(1)).map(|_| rng.gen_range(1..=config.delay_ms.max(1))).sum();
+                    deliver_at.insert(msg, now + delay);
+                }
+                None => {
+                    state.network.remove(&msg);
+                }
+            }
+        }
+
+        for (slot, reached) in &state.block_reach {
+            if reach_counts.insert(*slot, reached.len()) != Some(reached.len()) {
+                latency_ms.insert(*slot, now - proposed_at.get(slot).copied().unwrap_or(now));
+            }
+        }
+    }
+
+    SimulationOutcome { state, byzantine: config.byzantine.clone(), steps, elapsed_ms: now, latency_ms, violations }
+}
+
+/// Stake drawn from a heavy-tailed distribution, between 1 and 10_000 per node
+fn skewed_stakes(nodes: usize, rng: &mut StdRng) -> Vec<Stake> {
+    (0..nodes).map(|_| rng.gen_range(1..=100u64).pow(2)).collect()
+}
+
+/// Nodes picked at random to make up `percent` of the network
+fn random_nodes(nodes: usize, percent: u64, rng: &mut StdRng) -> BTreeSet<NodeId> {
+    let mut ids: Vec<NodeId> = (0..nodes).collect();
+    ids.shuffle(rng);
+    ids.into_iter().take((nodes as u64 * percent / 100) as usize).collect()
+}
+
+/// Worst-case latency of a mode over a topology of the given diameter
+fn latency_bound(mode: DisseminationMode, diameter: usize, delay_ms: u64) -> u64 {
+    mode.max_hops() as u64 * diameter as u64 * delay_ms
+}
+
+/// Simulate Rotor with honest relays; every node must get every block safely
+pub fn run_simulation(nodes: usize, slots: Slot, seed: u64) -> bool {
+    println!("Simulating rotor dissemination: {} nodes, {} slots, seed {}", nodes, slots, seed);
+
+    let outcome = simulate(&SimulationConfig::new(nodes, slots, seed));
+    outcome.print_summary(slots);
+
+    outcome.violations.is_empty() && (1..=slots).all(|slot| outcome.delivered(slot))
+}
+
+/// Relays sampled over many slots must follow the stake distribution
+pub fn test_stake_weighted_selection(nodes: usize, seed: u64) -> bool {
+    println!("Simulating stake-weighted relay selection: {} nodes, {} slots, seed {}", nodes, SAMPLING_ROUNDS, seed);
+
+    let mut config = SimulationConfig::new(nodes, SAMPLING_ROUNDS, seed);
+    config.stakes = skewed_stakes(nodes, &mut StdRng::seed_from_u64(seed));
+    let state = config.initial_state();
+
+    let mut passed = nodes > 1;
+    for strategy in [SamplingStrategy::Fa1Iid, SamplingStrategy::PsP] {
+        let mut observed = vec![0.0; nodes];
+        let mut expected = vec![0.0; nodes];
+        for slot in seed * SAMPLING_ROUNDS + 1..=(seed + 1) * SAMPLING_ROUNDS {
+            let leader = slot as usize % nodes;
+            for relay in state.perform_stake_weighted_sampling(slot, leader, strategy) {
+                observed[relay] += 1.0;
+            }
+            let eligible = state.eligible_relays(leader);
+            let total_stake: Stake = eligible.iter().map(|(_, stake)| stake).sum();
+            for (node_id, stake) in eligible {
+                expected[node_id] += (stake * config.fanout as u64) as f64 / total_stake as f64;
+            }
+        }
+        let draws: f64 = observed.iter().sum();
+        let deviation = observed.iter().zip(&expected)
+            .map(|(o, e)| (o - e).abs() / draws)
+            .sum::<f64>() / 2.0;
+        println!("  {:?}: {} relay draws, total variation from stake shares {:.4}", strategy, draws, deviation);
+        passed &= deviation <= MAX_SAMPLING_DEVIATION;
+    }
+    passed
+}
+
+/// A larger fanout costs the leader more bandwidth but tolerates more withheld
+/// shreds: with the first Γ - γ shreds withheld the block must still get through
+pub fn test_fanout_optimization(nodes: usize, fanout: usize, seed: u64) -> bool {
+    println!("Simulating fanout {}: {} nodes, seed {}", fanout, nodes, seed);
+    if fanout < DATA_SHREDS || fanout >= nodes {
+        println!("Fanout must be between {} and {}", DATA_SHREDS, nodes.saturating_sub(1));
+        return false;
+    }
+
+    let mut config = SimulationConfig::new(nodes, 1, seed);
+    config.fanout = fanout;
+    let model = config.model();
+    let relays = config.initial_state().perform_stake_weighted_sampling(1, model.leader(1), config.sampling_strategy);
+    let tolerance = fanout - DATA_SHREDS;
+    for relay in &relays {
+        let mut withholding = config.byzantine.clone();
+        withholding.insert(*relay);
+        if relays.iter().filter(|r| withholding.contains(r)).count() <= tolerance {
+            config.byzantine = withholding;
+        }
+    }
+
+    let outcome = simulate(&config);
+    outcome.print_summary(1);
+    let leader_sent = outcome.state.nodes[model.leader(1)].shreds_sent;
+    println!("Leader sent {} shreds (direct sending would take {}), {} Byzantine relays withheld shreds",
+             leader_sent, (nodes - 1) * fanout * SLICES_PER_BLOCK, config.byzantine.len());
+
+    outcome.violations.is_empty()
+        && outcome.delivered(1)
+        && leader_sent == (fanout * SLICES_PER_BLOCK) as u64
+}
+
+/// Compare leader bandwidth and latency of the dissemination modes on a full mesh
+pub fn test_message_dissemination(nodes: usize, seed: u64) -> bool {
+    println!("Simulating message dissemination: {} nodes, seed {}", nodes, seed);
+
+    let mut leader_sent = BTreeMap::new();
+    let mut passed = true;
+    for mode in [DisseminationMode::LeaderDirect, DisseminationMode::OneHop, DisseminationMode::TwoHop] {
+        let mut config = SimulationConfig::new(nodes, 1, seed);
+        config.dissemination_mode = mode;
+        let outcome = simulate(&config);
+        let sent = outcome.state.nodes[config.model().leader(1)].shreds_sent;
+        let latency = outcome.latency_ms.get(&1).copied().unwrap_or(u64::MAX);
+        println!("  {:?}: leader sent {} shreds, {} in total, latency {}ms", mode, sent, outcome.shreds_sent(), latency);
+        passed &= outcome.violations.is_empty()
+            && outcome.delivered(1)
+            && latency <= latency_bound(mode, 1, config.delay_ms);
+        leader_sent.insert(mode.max_hops(), sent);
+    }
+
+    // Relaying takes the per-shred broadcast off the leader
+    passed && leader_sent[&2] < leader_sent[&1]
+}
+
+/// Rotor routed over a sparser topology must still deliver, with latency
+/// stretched by at most the topology's diameter
+pub fn test_topology_adaptation(nodes: usize, topology: Topology, seed: u64) -> bool {
+    println!("Simulating rotor over {:?} topology: {} nodes, seed {}", topology, nodes, seed);
+
+    let mut config = SimulationConfig::new(nodes, 2, seed);
+    config.topology = topology;
+    let Some(diameter) = config.diameter() else {
+        println!("Topology is disconnected");
+        return false;
+    };
+    let outcome = simulate(&config);
+    outcome.print_summary(2);
+    let bound = latency_bound(config.dissemination_mode, diameter, config.delay_ms);
+    println!("Diameter {}, latency bound {}ms", diameter, bound);
+
+    outcome.violations.is_empty()
+        && (1..=2).all(|slot| outcome.delivered(slot) && outcome.latency_ms[&slot] <= bound)
+}
+
+/// With a random fraction of Byzantine relays, a slot must reach every honest
+/// node exactly when honest relays carry at least γ shreds of each slice
+pub fn test_fault_tolerance(nodes: usize, fault_percent: u64, seed: u64) -> bool {
+    let mut config = SimulationConfig::new(nodes, 4, seed);
+    config.byzantine = random_nodes(nodes, fault_percent, &mut StdRng::seed_from_u64(seed));
+    println!("Simulating fault tolerance: {} nodes, Byzantine {:?}, seed {}", nodes, config.byzantine, seed);
+
+    let outcome = simulate(&config);
+    outcome.print_summary(config.slots);
+
+    let model = config.model();
+    let mut passed = outcome.violations.is_empty();
+    for slot in 1..=config.slots {
+        let honest_shreds = outcome.state.honest_relay_shreds(slot, model.leader(slot), &config.byzantine,
+                                                              config.dissemination_mode, config.sampling_strategy);
+        println!("  Slot {}: {}/{} shreds with honest relays", slot, honest_shreds, config.fanout);
+        passed &= outcome.delivered(slot) == (honest_shreds >= DATA_SHREDS);
+    }
+    passed
+}
+
+/// Share of a slot's relay committee stake, counted once per shred, held by honest online relays
+fn correct_committee_stake(state: &RotorState, slot: Slot, leader: NodeId, byzantine: &BTreeSet<NodeId>) -> f64 {
+    let relays = state.nodes[leader].sampling_history.get(&slot).cloned().unwrap_or_default();
+    let total: Stake = relays.iter().map(|relay| state.stake_distribution[relay]).sum();
+    let correct: Stake = relays.iter()
+        .filter(|relay| state.is_correct(**relay, byzantine))
+        .map(|relay| state.stake_distribution[relay])
+        .sum();
+    correct as f64 / total.max(1) as f64
+}
+
+/// Smallest share of faulty nodes, in steps of 5%, split evenly between crashed and
+/// Byzantine, at which some slot with an online leader fails to reach every correct node
+pub fn find_failure_threshold(nodes: usize, seed: u64) -> Option<u64> {
+    (0..=100).step_by(5).find(|faulty_percent| {
+        let mut config = SimulationConfig::new(nodes, 4, seed);
+        let mut ids: Vec<NodeId> = (0..nodes).collect();
+        ids.shuffle(&mut StdRng::seed_from_u64(seed));
+        let faulty = (nodes as u64 * faulty_percent / 100) as usize;
+        config.offline = ids[..faulty / 2].iter().copied().collect();
+        config.byzantine = ids[faulty / 2..faulty].iter().copied().collect();
+        let model = config.model();
+        let outcome = simulate(&config);
+        (1..=config.slots).any(|slot| !config.offline.contains(&model.leader(slot)) && !outcome.delivered(slot))
+    })
+}
+
+/// With some nodes crashed and others Byzantine, a slot must reach every correct
+/// node exactly when honest online relays carry at least γ of its Γ shreds
+pub fn test_mixed_faults(nodes: usize, offline_percent: u64, byzantine_percent: u64, seed: u64) -> bool {
+    let mut config = SimulationConfig::new(nodes, 4, seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+    config.offline = random_nodes(nodes, offline_percent, &mut rng);
+    config.byzantine = random_nodes(nodes, byzantine_percent + offline_percent, &mut rng)
+        .difference(&config.offline)
+        .copied()
+        .take((nodes as u64 * byzantine_percent / 100) as usize)
+        .collect();
+    println!("Simulating mixed faults: {} nodes, crashed {:?}, Byzantine {:?}, seed {}",
+             nodes, config.offline, config.byzantine, seed);
+
+    let outcome = simulate(&config);
+    outcome.print_summary(config.slots);
+
+    let model = config.model();
+    let mut passed = outcome.violations.is_empty();
+    for slot in 1..=config.slots {
+        let leader = model.leader(slot);
+        if config.offline.contains(&leader) {
+            println!("  Slot {}: leader {} crashed, nothing proposed", slot, leader);
+            continue;
+        }
+        let correct_shreds = outcome.state.honest_relay_shreds(slot, leader, &config.byzantine,
+                                                               config.dissemination_mode, config.sampling_strategy);
+        let stake_share = correct_committee_stake(&outcome.state, slot, leader, &config.byzantine);
+        println!("  Slot {}: {}/{} shreds and {:.1}% of committee stake with honest online relays", slot,
+                 correct_shreds, config.fanout, stake_share * 100.0);
+        if correct_shreds < DATA_SHREDS {
+            println!("    Below threshold: {} faulty shreds exceed the {} a slice tolerates",
+                     config.fanout - correct_shreds, config.fanout - DATA_SHREDS);
+        }
+        passed &= outcome.delivered(slot) == (correct_shreds >= DATA_SHREDS);
+    }
+
+    match find_failure_threshold(nodes, seed) {
+        Some(percent) => println!("Failure threshold for seed {}: first missed slot at {}% faulty nodes", seed, percent),
+        None => println!("Failure threshold for seed {}: no slot missed", seed),
+    }
+    passed
+}
+
+/// Relay load under a skewed stake distribution must stay proportional to stake
+pub fn test_load_balancing(nodes: usize, seed: u64) -> bool {
+    println!("Simulating relay load balancing: {} nodes, seed {}", nodes, seed);
+
+    let mut config = SimulationConfig::new(nodes, 10, seed);
+    config.stakes = skewed_stakes(nodes, &mut StdRng::seed_from_u64(seed));
+    let outcome = simulate(&config);
+    outcome.print_summary(config.slots);
+
+    let total_stake: Stake = config.stakes.iter().sum();
+    let relayed: u64 = outcome.state.nodes.iter().map(|node| node.relay_assignments).sum();
+    for (node_id, stake, assignments, sent) in outcome.state.load_statistics() {
+        if assignments > 0 {
+            println!("  Node {}: {:.1}% of stake, {:.1}% of relay assignments, {} shreds sent", node_id,
+                     stake as f64 * 100.0 / total_stake as f64, assignments as f64 * 100.0 / relayed as f64, sent);
+        }
+    }
+
+    // PS-P must not spread a slice's load less evenly than FA1-IID over these stakes
+    let variance = |strategy| outcome.state.expected_load_variance(0, strategy);
+    let (psp, iid) = (variance(SamplingStrategy::PsP), variance(SamplingStrategy::Fa1Iid));
+    println!("  Expected relay load variance per slice: PS-P {:.3}, FA1-IID {:.3}", psp, iid);
+
+    outcome.violations.is_empty() && psp <= iid + 1e-9 && (1..=config.slots).all(|slot| outcome.delivered(slot))
+}
+
+/// Dissemination must stay within its latency bound as the network grows
+pub fn test_scalability(nodes: usize, seed: u64) -> bool {
+    println!("Simulating rotor at scale: {} nodes, seed {}", nodes, seed);
+
+    let config = SimulationConfig::new(nodes, 1, seed);
+    let started = Instant::now();
+    let outcome = simulate(&config);
+    outcome.print_summary(1);
+    println!("Simulated in {:.2}s, {:.1} shreds sent per node", started.elapsed().as_secs_f64(),
+             outcome.shreds_sent() as f64 / nodes.max(1) as f64);
+
+    outcome.violations.is_empty()
+        && outcome.delivered(1)
+        && outcome.latency_ms[&1] <= latency_bound(config.dissemination_mode, 1, config.delay_ms)
 }
 
 #[cfg(test)]
@@ -488,7 +1852,7 @@ mod tests {
 
     #[test]
     fn test_rotor_state_creation() {
-        let state = RotorState::new(3);
+        let state = RotorState::new(3, DEFAULT_FANOUT);
         assert_eq!(state.nodes.len(), 3);
         assert_eq!(state.current_slot, 0);
         assert!(state.network.is_empty());
@@ -496,21 +1860,403 @@ mod tests {
 
     #[test]
     fn test_stake_weighted_sampling() {
-        let state = RotorState::new(4);
-        let selected = state.perform_stake_weighted_sampling(1, 0);
-        assert!(selected.len() <= FANOUT_SIZE);
-        assert!(!selected.contains(&0)); // Should not select self
+        let state = RotorState::new(4, DEFAULT_FANOUT);
+        for strategy in [SamplingStrategy::Fa1Iid, SamplingStrategy::PsP] {
+            let selected = state.perform_stake_weighted_sampling(1, 0, strategy);
+            assert!(selected.len() <= DEFAULT_FANOUT);
+            assert!(!selected.contains(&0)); // Should not select self
+        }
+    }
+
+    #[test]
+    fn test_psp_partitioning() {
+        let mut state = RotorState::new(4, DEFAULT_FANOUT);
+        // Node 1 holds 60% of the eligible stake: one whole bin plus 0.8 of another
+        state.stake_distribution.insert(1, 600);
+        state.stake_distribution.insert(2, 200);
+        state.stake_distribution.insert(3, 200);
+
+        let (full_bins, bins) = state.partition_bins(0);
+        assert_eq!(full_bins, vec![1]);
+        assert_eq!(bins.len(), DEFAULT_FANOUT - 1);
+        assert!(bins.iter().all(|bin| bin.iter().map(|(_, share)| share).sum::<Stake>() == 1000));
+
+        let relays = state.perform_stake_weighted_sampling(1, 0, SamplingStrategy::PsP);
+        assert_eq!(relays.len(), DEFAULT_FANOUT);
+        assert_eq!(relays[0], 1);
+        assert!(state.expected_load_variance(0, SamplingStrategy::PsP)
+            < state.expected_load_variance(0, SamplingStrategy::Fa1Iid));
+    }
+
+    #[test]
+    fn test_psp_load_variance() {
+        // PS-P never spreads relay load less evenly than FA1-IID, whatever the stakes
+        let distributions: [&[Stake]; 4] = [
+            &[250, 250, 250, 250],
+            &[100, 600, 200, 100],
+            &[10, 20, 30, 940],
+            &[1, 333, 333, 333, 1],
+        ];
+        for stakes in distributions {
+            for fanout in [DATA_SHREDS, DEFAULT_FANOUT, 2 * DEFAULT_FANOUT] {
+                let state = RotorState::with_stakes(fanout, stakes);
+                for sampler in 0..stakes.len() {
+                    assert!(state.expected_load_variance(sampler, SamplingStrategy::PsP)
+                        <= state.expected_load_variance(sampler, SamplingStrategy::Fa1Iid) + 1e-9,
+                        "stakes {:?}, fanout {}, sampler {}", stakes, fanout, sampler);
+                }
+            }
+        }
     }
 
     #[test]
     fn test_fanout_achievement() {
-        let mut state = RotorState::new(4);
-        let reach_entry = state.message_reach.entry((1, 100)).or_default();
+        let mut state = RotorState::new(4, DEFAULT_FANOUT);
+        let reach_entry = state.block_reach.entry(1).or_default();
         reach_entry.insert(0);
         reach_entry.insert(1);
         reach_entry.insert(2);
         reach_entry.insert(3);
         
-        assert!(state.has_achieved_fanout(1, 100));
+        assert!(state.has_achieved_fanout(1));
+    }
+
+    #[test]
+    fn test_reconstruction_from_any_data_shreds() {
+        let mut state = RotorState::new(4, DEFAULT_FANOUT);
+        let mut node = state.nodes[1].clone();
+
+        // A data shred alone is not enough
+        state.receive_shred(1, &mut node, Shred::new(1, 0, 0, 0, DEFAULT_FANOUT), 1);
+        assert!(!node.reconstructed_blocks.contains_key(&1));
+
+        // A coding shred makes up for the missing data shred
+        let coding = Shred::new(1, 0, 0, DEFAULT_FANOUT - 1, DEFAULT_FANOUT);
+        assert!(!coding.is_data());
+        state.receive_shred(1, &mut node, coding, 2);
+        assert!(node.reconstructed_blocks.contains_key(&1));
+        assert_eq!(node.block_hops.get(&1), Some(&2));
+        assert!(state.block_reach[&1].contains(&1));
+    }
+
+    #[test]
+    fn test_shred_reconstruction_property() {
+        let model = RotorModel {
+            node_count: 4,
+            max_slot: 1,
+            sampling_strategy: SamplingStrategy::PsP,
+            byzantine: BTreeSet::new(),
+            offline: BTreeSet::new(),
+            load_factor_percent: DEFAULT_LOAD_FACTOR_PERCENT,
+            dissemination_mode: DisseminationMode::OneHop,
+            fanout: DEFAULT_FANOUT,
+            topology: Topology::Mesh,
+            network_faults: 0,
+        };
+        let property = model.property("shred_reconstruction");
+        let mut state = RotorState::new(4, DEFAULT_FANOUT);
+        let mut node = state.nodes[1].clone();
+        for index in 0..DATA_SHREDS {
+            state.receive_shred(1, &mut node, Shred::new(1, 0, 0, index, DEFAULT_FANOUT), 1);
+        }
+        state.nodes[1] = node;
+        assert!((property.condition)(&model, &state));
+
+        // A block reconstructed from too few distinct shreds is caught
+        let mut early = state.clone();
+        early.nodes[1].received_shreds.pop_last();
+        assert!(!(property.condition)(&model, &early));
+
+        // So is a decodable block the node failed to reconstruct
+        let mut missed = state.clone();
+        missed.nodes[1].reconstructed_blocks.clear();
+        assert!(!(property.condition)(&model, &missed));
+
+        // And a reconstructed root other than the decoded one
+        let mut wrong = state;
+        wrong.nodes[1].reconstructed_blocks.insert(1, merkle_root(1, 1, DEFAULT_FANOUT));
+        assert!(!(property.condition)(&model, &wrong));
+    }
+
+    #[test]
+    fn test_withholding_relay() {
+        let model = RotorModel {
+            node_count: 4,
+            max_slot: 1,
+            sampling_strategy: SamplingStrategy::PsP,
+            byzantine: [2].into_iter().collect(),
+            offline: BTreeSet::new(),
+            load_factor_percent: DEFAULT_LOAD_FACTOR_PERCENT,
+            dissemination_mode: DisseminationMode::OneHop,
+            fanout: DEFAULT_FANOUT,
+            topology: Topology::Mesh,
+            network_faults: 0,
+        };
+        let property = model.property("dissemination_success");
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, RotorAction::SendData { slot: 1, sender: 1 }).unwrap();
+        assert_eq!(state.honest_relay_shreds(1, 1, &model.byzantine, model.dissemination_mode, model.sampling_strategy),
+                   DEFAULT_FANOUT - 1);
+
+        // Deliver everything: the Byzantine relay forwards nothing
+        while let Some(msg) = state.network.iter().next().cloned() {
+            state = model.next_state(&state, RotorAction::DeliverMessage { msg }).unwrap();
+        }
+        assert!(state.nodes[2].forwarded_shreds.is_empty());
+        assert!((property.condition)(&model, &state));
+        assert!([0, 1, 3].iter().all(|id| state.nodes[*id].reconstructed_blocks.contains_key(&1)));
+    }
+
+    #[test]
+    fn test_withheld_shreds() {
+        let model = RotorModel {
+            node_count: 4,
+            max_slot: 1,
+            sampling_strategy: SamplingStrategy::PsP,
+            byzantine: BTreeSet::new(),
+            offline: BTreeSet::new(),
+            load_factor_percent: DEFAULT_LOAD_FACTOR_PERCENT,
+            dissemination_mode: DisseminationMode::OneHop,
+            fanout: DEFAULT_FANOUT,
+            topology: Topology::Mesh,
+            network_faults: 2,
+        };
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, RotorAction::SendData { slot: 1, sender: 1 }).unwrap();
+
+        // The adversary drops one shred and holds back another, leaving the relays one per slice
+        let mut shreds = state.network.iter().cloned();
+        let (dropped, delayed) = (shreds.next().unwrap(), shreds.next().unwrap());
+        state = model.next_state(&state, RotorAction::NetworkFault { fault: NetworkFault::Drop(dropped) }).unwrap();
+        state = model.next_state(&state, RotorAction::NetworkFault { fault: NetworkFault::Delay(delayed.clone()) }).unwrap();
+        while let Some(msg) = state.network.iter().next().cloned() {
+            state = model.next_state(&state, RotorAction::DeliverMessage { msg }).unwrap();
+        }
+        assert_eq!(state.withheld_shreds(1), 2);
+        assert!([0, 2, 3].iter().all(|id| !state.nodes[*id].reconstructed_blocks.contains_key(&1)));
+        assert!((model.property("dissemination_success").condition)(&model, &state));
+        assert!((model.property("two_hop_latency").condition)(&model, &state));
+
+        // Once the delayed shred is released the erasure code covers the dropped one
+        state = model.next_state(&state, RotorAction::NetworkFault { fault: NetworkFault::Release(delayed) }).unwrap();
+        while let Some(msg) = state.network.iter().next().cloned() {
+            state = model.next_state(&state, RotorAction::DeliverMessage { msg }).unwrap();
+        }
+        assert_eq!(state.withheld_shreds(1), 1);
+        assert!(state.nodes.iter().all(|node| node.reconstructed_blocks.contains_key(&1)));
+        assert!((model.property("dissemination_success").condition)(&model, &state));
+        assert!((model.property("two_hop_latency").condition)(&model, &state));
+    }
+
+    #[test]
+    fn test_shred_equivocation_evidence() {
+        let mut state = RotorState::new(4, DEFAULT_FANOUT);
+        let mut node = state.nodes[2].clone();
+        let honest = Shred::new(1, 0, 0, 0, DEFAULT_FANOUT);
+        let conflicting = Shred::new(1, 1, 0, 1, DEFAULT_FANOUT);
+        assert_ne!(honest.root, conflicting.root);
+
+        // A payload from one version claimed under the other root fails the Merkle check
+        let forged = Shred { payload: conflicting.payload, ..honest };
+        state.receive_shred(2, &mut node, forged, 1);
+        assert!(node.received_shreds.is_empty());
+
+        state.receive_shred(2, &mut node, honest, 1);
+        state.receive_shred(2, &mut node, conflicting, 1);
+        assert!(node.equivocation_evidence.contains(&1));
+        // Neither version has DATA_SHREDS shreds yet, so nothing was voted for
+        assert!(!node.reconstructed_blocks.contains_key(&1));
+    }
+
+    #[test]
+    fn test_forged_leaves_rejected() {
+        let model = RotorModel {
+            node_count: 4,
+            max_slot: 1,
+            sampling_strategy: SamplingStrategy::PsP,
+            byzantine: [1].into_iter().collect(),
+            offline: BTreeSet::new(),
+            load_factor_percent: DEFAULT_LOAD_FACTOR_PERCENT,
+            dissemination_mode: DisseminationMode::OneHop,
+            fanout: DEFAULT_FANOUT,
+            topology: Topology::Mesh,
+            network_faults: 0,
+        };
+        let binding = model.property("merkle_root_binding");
+        let dissemination = model.property("message_dissemination");
+
+        // The Byzantine leader of slot 1 forges before its honest shreds arrive
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, RotorAction::SendData { slot: 1, sender: 1 }).unwrap();
+        state = model.next_state(&state, RotorAction::ForgeData { slot: 1, sender: 1 }).unwrap();
+        let forged: Vec<MessageInTransit> = state.network.iter()
+            .filter(|m| matches!(m.msg, RotorMessage::DataMessage { shred, .. } if !shred.verify()))
+            .cloned()
+            .collect();
+        assert!(!forged.is_empty());
+        for msg in forged {
+            state = model.next_state(&state, RotorAction::DeliverMessage { msg }).unwrap();
+        }
+        while let Some(msg) = state.network.iter().next().cloned() {
+            state = model.next_state(&state, RotorAction::DeliverMessage { msg }).unwrap();
+        }
+
+        // Relays neither keep nor forward a forged shred, so every root keeps one block
+        assert!((binding.condition)(&model, &state));
+        assert!((dissemination.condition)(&model, &state));
+        assert!([0, 2, 3].iter().all(|id| state.nodes[*id].received_shreds.iter().all(Shred::verify)));
+
+        // A node accepting a forged leaf would break the binding
+        let honest = Shred::new(1, 0, 0, 0, DEFAULT_FANOUT);
+        let mut accepted = state;
+        accepted.nodes[2].received_shreds.insert(honest);
+        accepted.nodes[3].received_shreds.insert(Shred { payload: shred_payload(1, 1, 0, 0), ..honest });
+        assert!(!(binding.condition)(&model, &accepted));
+    }
+
+    #[test]
+    fn test_proportional_relay_load() {
+        let model = RotorModel {
+            node_count: 4,
+            max_slot: 2,
+            sampling_strategy: SamplingStrategy::PsP,
+            byzantine: BTreeSet::new(),
+            offline: BTreeSet::new(),
+            load_factor_percent: 100,
+            dissemination_mode: DisseminationMode::OneHop,
+            fanout: DEFAULT_FANOUT,
+            topology: Topology::Mesh,
+            network_faults: 0,
+        };
+        let property = model.property("proportional_relay_load");
+        let mut state = model.init_states().remove(0);
+        for slot in 1..=2 {
+            state = model.next_state(&state, RotorAction::SendData { slot, sender: model.leader(slot) }).unwrap();
+        }
+        // Equal stakes under PS-P: every non-leader relays exactly one shred per slot
+        let loads: Vec<u64> = state.load_statistics().iter().map(|(_, _, assigned, _)| *assigned).collect();
+        assert_eq!(loads, vec![2, 1, 1, 2]);
+        // Leaders sent one shred to each relay
+        assert_eq!(state.nodes[1].shreds_sent, DEFAULT_FANOUT as u64);
+        assert!((property.condition)(&model, &state));
+
+        // One more shred on node 0 breaks exact proportionality
+        state.nodes[0].relay_assignments += 1;
+        assert!(!(property.condition)(&model, &state));
+    }
+
+    #[test]
+    fn test_dissemination_mode_hops() {
+        for mode in [DisseminationMode::LeaderDirect, DisseminationMode::OneHop, DisseminationMode::TwoHop] {
+            let model = RotorModel {
+                node_count: 4,
+                max_slot: 1,
+                sampling_strategy: SamplingStrategy::PsP,
+                byzantine: BTreeSet::new(),
+                offline: BTreeSet::new(),
+                load_factor_percent: DEFAULT_LOAD_FACTOR_PERCENT,
+                dissemination_mode: mode,
+                fanout: DEFAULT_FANOUT,
+                topology: Topology::Mesh,
+                network_faults: 0,
+            };
+            let mut state = model.init_states().remove(0);
+            state = model.next_state(&state, RotorAction::SendData { slot: 1, sender: 1 }).unwrap();
+            while let Some(msg) = state.network.iter().next().cloned() {
+                state = model.next_state(&state, RotorAction::DeliverMessage { msg }).unwrap();
+            }
+
+            // Every node got the block, and the farthest shred took exactly the mode's hop budget
+            assert!(state.nodes.iter().all(|n| n.reconstructed_blocks.contains_key(&1)));
+            let farthest = state.nodes.iter().map(|n| n.max_hops_received).max().unwrap();
+            assert_eq!(farthest, mode.max_hops());
+            assert!((model.property("hop_bound").condition)(&model, &state));
+        }
+    }
+
+    #[test]
+    fn test_topology_constrains_delivery() {
+        let dot = Topology::from_dot("graph g { 0 -- 1; 1 -- 2 [weight=1]; \"2\" -- 3 }").unwrap();
+        let json = Topology::from_json("{\"edges\": [[0, 1], [2, 1], [2, 3]]}").unwrap();
+        assert_eq!(dot, json);
+        assert_eq!(dot.distance(0, 3, 4), Some(3));
+        assert!(Topology::from_json("{\"edges\": [[0, 1], [2]]}").is_err());
+        assert!(Topology::from_json("{\"edges\": [[0, 1], 2]}").is_err());
+        assert!(Topology::from_json("{\"edges\": [[0, 1]").is_err());
+
+        // Numbers after the edges array belong to other fields
+        let with_fields = Topology::from_json("{\"edges\": [[0, 1], [1, 2], [2, 3]], \"nodes\": 4, \"seed\": 17}").unwrap();
+        assert_eq!(with_fields, dot);
+
+        // Leader 1 of a star can only reach the hub, which relays onward
+        let model = RotorModel {
+            node_count: 4,
+            max_slot: 1,
+            sampling_strategy: SamplingStrategy::PsP,
+            byzantine: BTreeSet::new(),
+            offline: BTreeSet::new(),
+            load_factor_percent: DEFAULT_LOAD_FACTOR_PERCENT,
+            dissemination_mode: DisseminationMode::LeaderDirect,
+            fanout: DEFAULT_FANOUT,
+            topology: Topology::Star,
+            network_faults: 0,
+        };
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, RotorAction::SendData { slot: 1, sender: 1 }).unwrap();
+        assert!(state.network.iter().all(|m| m.dst == 0));
+        while let Some(msg) = state.network.iter().next().cloned() {
+            state = model.next_state(&state, RotorAction::DeliverMessage { msg }).unwrap();
+        }
+        assert_eq!(state.block_reach[&1], BTreeSet::from([0, 1]));
+        assert!((model.property("topology_reach").condition)(&model, &state));
+        assert!(!(model.property("dissemination_success").condition)(&model, &state));
+    }
+
+    #[test]
+    fn test_simulated_rotor_scenarios() {
+        for seed in 0..3 {
+            assert!(run_simulation(6, 2, seed));
+            assert!(test_fanout_optimization(8, 4, seed));
+            assert!(test_fault_tolerance(8, 40, seed));
+            assert!(test_topology_adaptation(7, Topology::Ring, seed));
+        }
+        assert!(test_message_dissemination(6, 1));
+
+        // No route joins the two halves of a disconnected graph
+        assert!(!test_topology_adaptation(4, Topology::Graph(BTreeSet::from([(0, 1), (2, 3)])), 1));
+    }
+
+    #[test]
+    fn test_crashed_relays() {
+        // Crashed relays lose their shreds like withholding ones; a crashed leader proposes nothing
+        let model = RotorModel {
+            node_count: 4,
+            max_slot: 2,
+            sampling_strategy: SamplingStrategy::PsP,
+            byzantine: BTreeSet::new(),
+            offline: [2].into_iter().collect(),
+            load_factor_percent: DEFAULT_LOAD_FACTOR_PERCENT,
+            dissemination_mode: DisseminationMode::OneHop,
+            fanout: DEFAULT_FANOUT,
+            topology: Topology::Mesh,
+            network_faults: 0,
+        };
+        let mut state = model.init_states().remove(0);
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(!actions.contains(&RotorAction::SendData { slot: 2, sender: 2 }));
+
+        state = model.next_state(&state, RotorAction::SendData { slot: 1, sender: 1 }).unwrap();
+        while let Some(msg) = state.network.iter().next().cloned() {
+            state = model.next_state(&state, RotorAction::DeliverMessage { msg }).unwrap();
+        }
+        assert!(state.nodes[2].received_shreds.is_empty());
+        let correct = state.honest_relay_shreds(1, 1, &model.byzantine, model.dissemination_mode, model.sampling_strategy);
+        assert_eq!(correct, state.nodes[1].sampling_history[&1].iter().filter(|r| **r != 2).count());
+        assert!((model.property("dissemination_success").condition)(&model, &state));
+
+        for seed in 0..3 {
+            assert!(test_mixed_faults(10, 20, 20, seed));
+        }
     }
 }
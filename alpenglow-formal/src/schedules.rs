@@ -0,0 +1,137 @@
+//! Running models along schedules derived from arbitrary numbers, for randomized testing.
+//! A schedule is a sequence of choices, each picking one of the successors of the current state
+//! by its index modulo their count, so every sequence of numbers is a valid run and shrinking a
+//! failing sequence shrinks the run. [`run_choices`] checks named invariants along the way; the
+//! property-based tests below drive it with generated model parameters and choices, covering
+//! parameter combinations exhaustive checking cannot reach.
+
+use stateright::Model;
+use std::fmt;
+
+/// An invariant that failed along a schedule
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScheduleViolation<A> {
+    pub invariant: &'static str,
+    /// Actions leading to the state where the invariant does not hold
+    pub actions: Vec<A>,
+}
+
+impl<A: fmt::Debug> fmt::Display for ScheduleViolation<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} violated after {} actions: {:?}", self.invariant, self.actions.len(), self.actions)
+    }
+}
+
+/// Follow `choices` from the first initial state of `model`, checking that the properties named
+/// by `invariants` hold in every state visited. Stops early when no action leads anywhere, and
+/// returns the number of actions taken.
+pub fn run_choices<M: Model>(
+    model: &M,
+    choices: impl IntoIterator<Item = usize>,
+    invariants: &[&'static str],
+) -> Result<usize, ScheduleViolation<M::Action>>
+where
+    M::Action: Clone,
+{
+    let invariants: Vec<_> = invariants.iter().map(|name| (*name, model.property(name))).collect();
+    let Some(mut state) = model.init_states().into_iter().next() else {
+        return Ok(0);
+    };
+    let mut taken = Vec::new();
+    let check = |state: &M::State, taken: &Vec<M::Action>| {
+        match invariants.iter().find(|(_, property)| !(property.condition)(model, state)) {
+            Some((name, _)) => Err(ScheduleViolation { invariant: name, actions: taken.clone() }),
+            None => Ok(()),
+        }
+    };
+    check(&state, &taken)?;
+    for choice in choices {
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        let mut successors: Vec<_> = actions.into_iter()
+            .filter_map(|action| model.next_state(&state, action.clone()).map(|next| (action, next)))
+            .collect();
+        if successors.is_empty() {
+            break;
+        }
+        let (action, next) = successors.swap_remove(choice % successors.len());
+        taken.push(action);
+        state = next;
+        check(&state, &taken)?;
+    }
+    Ok(taken.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::certificate::{CertificateModel, VoteRouting};
+    use crate::modelling::safety::{SafetyModel, Synchrony, DEFAULT_CORRUPTION_BUDGET_PERCENT};
+    use crate::votor::VotorModel;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    /// Randomized runs per model and test
+    const CASES: u32 = 1000;
+    /// Most actions per run
+    const MAX_CHOICES: usize = 200;
+
+    fn choices() -> impl Strategy<Value = Vec<usize>> {
+        vec(any::<usize>(), 0..MAX_CHOICES)
+    }
+
+    #[test]
+    fn test_choices_pick_successors() {
+        let model = VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 };
+        // Runs end once no action is enabled, however many choices remain
+        let taken = run_choices(&model, std::iter::repeat_n(0, 1000), &["safety"]).unwrap();
+        assert!(taken > 0 && taken < 1000);
+        // A choice past the number of successors wraps around
+        assert_eq!(run_choices(&model, [usize::MAX], &["safety"]), Ok(1));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+        #[test]
+        fn votor_runs_keep_invariants(validators in 2usize..=5, slots in 1u64..=3, network_faults in 0usize..=2,
+                                      choices in choices()) {
+            let model = VotorModel { honest_validators: validators, max_slot: slots, network_faults };
+            let result = run_choices(&model, choices, &["safety", "notar_skip_exclusion", "no_certificate_regression", "finality_knowledge_propagation", "no_finalized_rollback", "finalized_implies_notarized", "fast_finalization_uncontested", "certificate_stake_backed"]);
+            prop_assert!(result.is_ok(), "{}", result.unwrap_err());
+        }
+
+        #[test]
+        fn safety_runs_keep_invariants(validators in 3usize..=6, slots in 1u64..=3, byzantine in 0usize..=1,
+                                       network_faults in 0usize..=2, asynchronous in any::<bool>(),
+                                       choices in choices()) {
+            let model = SafetyModel {
+                validator_count: validators,
+                max_slot: slots,
+                byzantine_count: byzantine,
+                offline_budget_percent: 0,
+                corruption_budget_percent: DEFAULT_CORRUPTION_BUDGET_PERCENT,
+                network_faults,
+                synchrony: if asynchronous { Synchrony::Asynchronous } else { Synchrony::default() },
+                vote_routing: VoteRouting::default(),
+            };
+            let result = run_choices(&model, choices, &["no_conflicting_finalization", "certificate_uniqueness", "honest_never_slashable",
+                                                        "finalized_implies_notarized", "certificate_stake_backed"]);
+            prop_assert!(result.is_ok(), "{}", result.unwrap_err());
+        }
+
+        #[test]
+        fn certificate_runs_keep_thresholds(validators in 3usize..=5, slots in 1u64..=3, adversaries in 0usize..=1,
+                                            aggregated in any::<bool>(), choices in choices()) {
+            let model = CertificateModel {
+                validator_count: validators,
+                max_slot: slots,
+                adversary_count: adversaries,
+                network_faults: 0,
+                vote_routing: if aggregated { VoteRouting::Aggregator } else { VoteRouting::AllToAll },
+            };
+            let result = run_choices(&model, choices, &["certificate_threshold", "vote_uniqueness", "no_certificate_regression"]);
+            prop_assert!(result.is_ok(), "{}", result.unwrap_err());
+        }
+    }
+}
@@ -0,0 +1,94 @@
+//! Structural sharing for model states.
+//! Every transition clones the whole state but usually touches only one or two validators.
+//! [`SharedVec`] keeps each element behind an `Arc`, so cloning a state copies pointers, states
+//! reached from a common parent share their untouched validators, and an element is copied only
+//! the first time it is written through `IndexMut`, `get_mut` or `iter_mut`.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::sync::Arc;
+
+/// A vector whose elements are shared between clones until written
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct SharedVec<T>(Vec<Arc<T>>);
+
+/// Iterator over the elements of a [`SharedVec`]
+pub type Iter<'a, T> = std::iter::Map<std::slice::Iter<'a, Arc<T>>, fn(&Arc<T>) -> &T>;
+/// Iterator over mutable elements of a [`SharedVec`], copying the shared ones
+pub type IterMut<'a, T> = std::iter::Map<std::slice::IterMut<'a, Arc<T>>, fn(&mut Arc<T>) -> &mut T>;
+
+fn shared<T>(element: &Arc<T>) -> &T {
+    element
+}
+
+impl<T: Clone> SharedVec<T> {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.0.get(index).map(shared)
+    }
+
+    /// Mutable access to an element, copying it first if another state still shares it
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.0.get_mut(index).map(Arc::make_mut)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.into_iter()
+    }
+
+    /// Mutable access to every element, copying the shared ones
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.into_iter()
+    }
+}
+
+impl<T> Index<usize> for SharedVec<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T: Clone> IndexMut<usize> for SharedVec<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        Arc::make_mut(&mut self.0[index])
+    }
+}
+
+impl<T> FromIterator<T> for SharedVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Arc::new).collect())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a SharedVec<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().map(shared as fn(&Arc<T>) -> &T)
+    }
+}
+
+impl<'a, T: Clone> IntoIterator for &'a mut SharedVec<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut().map(Arc::make_mut as fn(&mut Arc<T>) -> &mut T)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for SharedVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.0.iter().map(|element| &**element)).finish()
+    }
+}
@@ -0,0 +1,598 @@
+//! Discrete-event simulation of Rotor and Votor on networks of thousands of nodes.
+//! Exhaustive checking stops at a handful of validators. The simulator runs the same protocol
+//! logic at realistic scale: leaders sample their relays with Rotor's stake-weighted sampling,
+//! relays broadcast their shreds, every node rebuilds a block from any `DATA_SHREDS` distinct
+//! shreds, and votes and finalizations come from the Votor [`NodeState`] handlers the model
+//! checks. Every message crosses its link after a delay drawn from a [`Latency`] distribution,
+//! and an [`EventQueue`] processes events in order of simulated time, so Rotor's fanout and
+//! Votor's finalization latency can be measured on networks far beyond exhaustive reach.
+//!
+//! Leaders rotate through the online nodes, and a leader proposes its slot once it has
+//! finalized the previous one. Vote counts weigh every node equally.
+
+use crate::output::Record;
+use crate::rotor::{RotorState, SamplingStrategy, DATA_SHREDS};
+use crate::voters::WideVoterSet;
+use crate::votor::{block_hash, Message, NodeOutput, NodeState};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap};
+
+/// Time a node waits for a slot to be finalized before voting to skip it
+pub const DEFAULT_SKIP_TIMEOUT_MS: u64 = 1_000;
+/// Wide-area link delays: a median of 30ms with a long tail
+pub const DEFAULT_LATENCY: Latency = Latency::LogNormal { median_ms: 30, sigma: 0.5 };
+/// Events processed before a run is cut short
+pub const DEFAULT_MAX_EVENTS: u64 = 100_000_000;
+
+type NodeId = usize;
+type Slot = u64;
+type Hash = u64;
+
+/// Distribution of the delay of a single link
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Latency {
+    /// Every message takes the same time
+    Constant(u64),
+    /// Uniform between both bounds, inclusive
+    Uniform { min_ms: u64, max_ms: u64 },
+    /// A fixed floor plus an exponentially distributed delay with the given mean
+    Exponential { min_ms: u64, mean_ms: u64 },
+    /// Log-normal around a median, with shape `sigma`
+    LogNormal { median_ms: u64, sigma: f64 },
+}
+
+impl Latency {
+    /// Parse `constant:MS`, `uniform:MIN-MAX`, `exponential:MIN,MEAN` or `lognormal:MEDIAN,SIGMA`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid latency '{}'", spec);
+        let (kind, parameters) = spec.split_once(':').ok_or_else(invalid)?;
+        let pair = |separator: char| parameters.split_once(separator).ok_or_else(invalid);
+        let number = |text: &str| text.trim().parse::<u64>().map_err(|_| invalid());
+        match kind {
+            "constant" => Ok(Latency::Constant(number(parameters)?)),
+            "uniform" => {
+                let (min, max) = pair('-')?;
+                let (min_ms, max_ms) = (number(min)?, number(max)?);
+                if min_ms > max_ms {
+                    return Err(invalid());
+                }
+                Ok(Latency::Uniform { min_ms, max_ms })
+            }
+            "exponential" => {
+                let (min, mean) = pair(',')?;
+                Ok(Latency::Exponential { min_ms: number(min)?, mean_ms: number(mean)? })
+            }
+            "lognormal" => {
+                let (median, sigma) = pair(',')?;
+                let sigma = sigma.trim().parse::<f64>().ok().filter(|sigma| *sigma >= 0.0).ok_or_else(invalid)?;
+                Ok(Latency::LogNormal { median_ms: number(median)?, sigma })
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Draw one delay in milliseconds
+    pub fn sample(&self, rng: &mut StdRng) -> u64 {
+        match *self {
+            Latency::Constant(ms) => ms,
+            Latency::Uniform { min_ms, max_ms } => rng.gen_range(min_ms..=max_ms),
+            Latency::Exponential { min_ms, mean_ms } => {
+                let uniform: f64 = rng.gen();
+                min_ms + (-(mean_ms as f64) * (1.0 - uniform).ln()).round() as u64
+            }
+            Latency::LogNormal { median_ms, sigma } => {
+                // Box-Muller transform of two uniform draws into a standard normal one
+                let (u1, u2): (f64, f64) = (1.0 - rng.gen::<f64>(), rng.gen());
+                let normal = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                (median_ms as f64 * (sigma * normal).exp()).round() as u64
+            }
+        }
+    }
+}
+
+/// An event due at a point of simulated time; events due at the same time keep the order in
+/// which they were scheduled
+struct Scheduled<E> {
+    time: u64,
+    sequence: u64,
+    event: E,
+}
+
+impl<E> PartialEq for Scheduled<E> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.time, self.sequence) == (other.time, other.sequence)
+    }
+}
+
+impl<E> Eq for Scheduled<E> {}
+
+impl<E> PartialOrd for Scheduled<E> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<E> Ord for Scheduled<E> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.time, self.sequence).cmp(&(other.time, other.sequence))
+    }
+}
+
+/// Pending events in order of simulated time, in milliseconds
+pub struct EventQueue<E> {
+    heap: BinaryHeap<Reverse<Scheduled<E>>>,
+    scheduled: u64,
+    now: u64,
+}
+
+impl<E> Default for EventQueue<E> {
+    fn default() -> Self {
+        Self { heap: BinaryHeap::new(), scheduled: 0, now: 0 }
+    }
+}
+
+impl<E> EventQueue<E> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time of the event popped last
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Schedule `event` to happen `delay` milliseconds from now
+    pub fn schedule(&mut self, delay: u64, event: E) {
+        self.heap.push(Reverse(Scheduled { time: self.now + delay, sequence: self.scheduled, event }));
+        self.scheduled += 1;
+    }
+
+    /// Next event, advancing simulated time to it
+    pub fn pop(&mut self) -> Option<E> {
+        let Reverse(scheduled) = self.heap.pop()?;
+        self.now = scheduled.time;
+        Some(scheduled.event)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+/// Parameters of a simulated network
+#[derive(Clone, Debug)]
+pub struct SimulatorConfig {
+    pub nodes: usize,
+    /// Slots proposed one after another
+    pub slots: Slot,
+    /// Γ: relays sampled per slot, one per shred
+    pub fanout: usize,
+    pub sampling_strategy: SamplingStrategy,
+    /// Stake each node has in relay sampling, or empty for an even split
+    pub stakes: Vec<u64>,
+    /// Crashed nodes: they neither relay nor vote nor lead
+    pub offline: BTreeSet<NodeId>,
+    pub latency: Latency,
+    pub skip_timeout_ms: u64,
+    pub max_events: u64,
+    /// Seed for link delays
+    pub seed: u64,
+}
+
+impl SimulatorConfig {
+    pub fn new(nodes: usize, slots: Slot, seed: u64) -> Self {
+        Self {
+            nodes,
+            slots,
+            fanout: crate::rotor::DEFAULT_FANOUT,
+            sampling_strategy: SamplingStrategy::default(),
+            stakes: Vec::new(),
+            offline: BTreeSet::new(),
+            latency: DEFAULT_LATENCY,
+            skip_timeout_ms: DEFAULT_SKIP_TIMEOUT_MS,
+            max_events: DEFAULT_MAX_EVENTS,
+            seed,
+        }
+    }
+
+    /// Crash `percent` of the nodes, picked at random from `seed`
+    pub fn with_offline_percent(mut self, percent: u64, seed: u64) -> Self {
+        let mut ids: Vec<NodeId> = (0..self.nodes).collect();
+        ids.shuffle(&mut StdRng::seed_from_u64(seed));
+        self.offline = ids.into_iter().take((self.nodes as u64 * percent / 100) as usize).collect();
+        self
+    }
+}
+
+/// Milliseconds after a proposal at which nodes reached a milestone
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LatencySummary {
+    /// Nodes that reached it
+    pub nodes: usize,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub max_ms: u64,
+}
+
+impl LatencySummary {
+    fn of(mut latencies: Vec<u64>) -> Self {
+        latencies.sort_unstable();
+        let percentile = |percent: usize| latencies.get((latencies.len().max(1) - 1) * percent / 100).copied().unwrap_or(0);
+        Self { nodes: latencies.len(), p50_ms: percentile(50), p90_ms: percentile(90), max_ms: percentile(100) }
+    }
+}
+
+/// What happened to one slot
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SlotReport {
+    pub leader: NodeId,
+    pub proposed_at_ms: u64,
+    /// Nodes that rebuilt the block from its shreds
+    pub reconstructed: LatencySummary,
+    pub finalized: LatencySummary,
+    /// Nodes that finalized the slot through the fast path
+    pub fast_finalized: usize,
+}
+
+/// Outcome of a simulation
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SimulatorReport {
+    pub online: usize,
+    pub events: u64,
+    /// Simulated time when the last event happened
+    pub elapsed_ms: u64,
+    pub shreds_sent: u64,
+    pub votes_sent: u64,
+    /// Skip timers that fired before their slot was finalized
+    pub timeouts: u64,
+    /// Proposed slots
+    pub slots: BTreeMap<Slot, SlotReport>,
+    /// Whether two nodes finalized different blocks in the same slot
+    pub conflicting_finalization: bool,
+    /// Whether the run ended before exhausting its event budget
+    pub complete: bool,
+}
+
+impl SimulatorReport {
+    /// Whether every online node finalized the first `slots` slots
+    pub fn all_finalized(&self, slots: Slot) -> bool {
+        (1..=slots).all(|slot| self.slots.get(&slot).is_some_and(|report| report.finalized.nodes == self.online))
+    }
+
+    pub fn print_summary(&self) {
+        println!("Simulated {}ms with {} online nodes: {} events, {} shreds and {} votes sent, {} skip timeouts{}",
+                 self.elapsed_ms, self.online, self.events, self.shreds_sent, self.votes_sent, self.timeouts,
+                 if self.complete { "" } else { ", stopped at the event limit" });
+        for (slot, report) in &self.slots {
+            println!("  Slot {} (leader {}, proposed at {}ms): reconstructed by {} (p50 {}ms, p90 {}ms, max {}ms), finalized by {} (p50 {}ms, p90 {}ms, max {}ms), {} on the fast path",
+                     slot, report.leader, report.proposed_at_ms,
+                     report.reconstructed.nodes, report.reconstructed.p50_ms, report.reconstructed.p90_ms, report.reconstructed.max_ms,
+                     report.finalized.nodes, report.finalized.p50_ms, report.finalized.p90_ms, report.finalized.max_ms,
+                     report.fast_finalized);
+        }
+        if self.conflicting_finalization {
+            println!("  ❌ Violated: no_conflicting_finalization");
+        }
+    }
+
+    /// The run of `config` as a structured record: whether safety held and every slot was
+    /// finalized, the traffic and the latency percentiles of every slot
+    pub fn record(&self, config: &SimulatorConfig) -> Record {
+        let mut record = Record::new(format!("network {}n {}s", config.nodes, config.slots))
+            .parameter("nodes", config.nodes)
+            .parameter("offline", config.offline.len())
+            .parameter("slots", config.slots)
+            .parameter("fanout", config.fanout)
+            .parameter("sampling", format!("{:?}", config.sampling_strategy))
+            .parameter("latency", format!("{:?}", config.latency))
+            .parameter("skip_timeout_ms", config.skip_timeout_ms)
+            .parameter("seed", config.seed)
+            .held("no_conflicting_finalization", !self.conflicting_finalization)
+            .held("all_finalized", self.all_finalized(config.slots))
+            .metric("elapsed_ms", self.elapsed_ms as f64)
+            .metric("events", self.events as f64)
+            .metric("shreds_sent", self.shreds_sent as f64)
+            .metric("votes_sent", self.votes_sent as f64)
+            .metric("timeouts", self.timeouts as f64);
+        for (slot, report) in &self.slots {
+            record = record
+                .metric(&format!("slot_{}_reconstructed_p50_ms", slot), report.reconstructed.p50_ms as f64)
+                .metric(&format!("slot_{}_finalized_nodes", slot), report.finalized.nodes as f64)
+                .metric(&format!("slot_{}_finalized_p50_ms", slot), report.finalized.p50_ms as f64)
+                .metric(&format!("slot_{}_finalized_p90_ms", slot), report.finalized.p90_ms as f64)
+                .metric(&format!("slot_{}_finalized_max_ms", slot), report.finalized.max_ms as f64)
+                .metric(&format!("slot_{}_fast_finalized", slot), report.fast_finalized as f64);
+        }
+        record
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Event {
+    /// Shred `index` of a block reaches `dst`, which passes it on to everyone when it is the relay
+    Shred { dst: NodeId, slot: Slot, hash: Hash, parent_hash: Hash, index: usize, relay: bool },
+    Vote { dst: NodeId, msg: Message },
+    /// The skip timer of `node` for `slot` fires
+    Timeout { node: NodeId, slot: Slot },
+}
+
+struct SimNode {
+    online: bool,
+    votor: NodeState<WideVoterSet>,
+    /// Blocks this node finalized, including genesis
+    finalized: BTreeMap<Slot, Hash>,
+    /// Distinct shred indices received per slot
+    shreds: BTreeMap<Slot, BTreeSet<usize>>,
+    reconstructed: BTreeSet<Slot>,
+    /// Blocks rebuilt before their parent was finalized here
+    pending: BTreeMap<Slot, Message>,
+}
+
+struct Simulation<'a> {
+    config: &'a SimulatorConfig,
+    queue: EventQueue<Event>,
+    rng: StdRng,
+    sampler: RotorState,
+    nodes: Vec<SimNode>,
+    /// Leader schedule: online nodes in turn
+    leaders: Vec<NodeId>,
+    report: SimulatorReport,
+    /// Block first finalized in each slot, to detect conflicting finalizations
+    first_finalized: BTreeMap<Slot, Hash>,
+    /// Reconstruction and finalization latencies of every slot
+    latencies: BTreeMap<Slot, (Vec<u64>, Vec<u64>)>,
+}
+
+impl<'a> Simulation<'a> {
+    fn new(config: &'a SimulatorConfig) -> Self {
+        let stakes = if config.stakes.is_empty() { vec![1; config.nodes] } else { config.stakes.clone() };
+        let nodes: Vec<SimNode> = (0..config.nodes)
+            .map(|id| SimNode {
+                online: !config.offline.contains(&id),
+                votor: NodeState::default(),
+                finalized: BTreeMap::from([(0, 0)]), // Genesis block: slot 0, hash 0
+                shreds: BTreeMap::new(),
+                reconstructed: BTreeSet::new(),
+                pending: BTreeMap::new(),
+            })
+            .collect();
+        let leaders: Vec<NodeId> = (0..config.nodes).filter(|id| nodes[*id].online).collect();
+        Self {
+            config,
+            queue: EventQueue::new(),
+            rng: StdRng::seed_from_u64(config.seed),
+            sampler: RotorState::with_stakes(config.fanout, &stakes),
+            report: SimulatorReport { online: leaders.len(), ..SimulatorReport::default() },
+            nodes,
+            leaders,
+            first_finalized: BTreeMap::new(),
+            latencies: BTreeMap::new(),
+        }
+    }
+
+    fn leader(&self, slot: Slot) -> NodeId {
+        self.leaders[slot as usize % self.leaders.len()]
+    }
+
+    /// Put `event` on the link from `from` to `dst`; nothing reaches crashed nodes
+    fn send(&mut self, from: NodeId, dst: NodeId, event: Event) {
+        if !self.nodes[dst].online {
+            return;
+        }
+        let delay = if from == dst { 0 } else { self.config.latency.sample(&mut self.rng) };
+        self.queue.schedule(delay, event);
+    }
+
+    fn run(mut self) -> SimulatorReport {
+        for node in self.leaders.clone() {
+            self.start_slot(node, 1);
+        }
+        while let Some(event) = self.queue.pop() {
+            if self.report.events == self.config.max_events {
+                break;
+            }
+            self.report.events += 1;
+            match event {
+                Event::Shred { dst, slot, hash, parent_hash, index, relay } => {
+                    if !self.nodes[dst].shreds.entry(slot).or_default().insert(index) {
+                        continue;
+                    }
+                    if relay {
+                        for other in (0..self.config.nodes).filter(|other| *other != dst) {
+                            self.report.shreds_sent += 1;
+                            self.send(dst, other, Event::Shred { dst: other, slot, hash, parent_hash, index, relay: false });
+                        }
+                    }
+                    if self.nodes[dst].shreds[&slot].len() >= DATA_SHREDS {
+                        self.reconstruct(dst, slot, hash, parent_hash);
+                    }
+                }
+                Event::Vote { dst, msg } => self.handle(dst, msg),
+                Event::Timeout { node, slot } => {
+                    if !self.nodes[node].finalized.contains_key(&slot) {
+                        self.report.timeouts += 1;
+                        let output = self.nodes[node].votor.on_timeout(node, slot);
+                        self.apply(node, output);
+                    }
+                }
+            }
+        }
+        self.report.complete = self.queue.is_empty();
+        self.report.elapsed_ms = self.queue.now();
+        for (slot, (reconstructed, finalized)) in std::mem::take(&mut self.latencies) {
+            if let Some(report) = self.report.slots.get_mut(&slot) {
+                report.reconstructed = LatencySummary::of(reconstructed);
+                report.finalized = LatencySummary::of(finalized);
+            }
+        }
+        self.report
+    }
+
+    /// Start `slot` at `node` once its parent is finalized there: arm the skip timer, propose
+    /// when leading, and vote for the block if it was rebuilt early
+    fn start_slot(&mut self, node: NodeId, slot: Slot) {
+        if slot > self.config.slots {
+            return;
+        }
+        self.queue.schedule(self.config.skip_timeout_ms, Event::Timeout { node, slot });
+        if self.leader(slot) == node {
+            self.propose(node, slot);
+        }
+        if let Some(block) = self.nodes[node].pending.remove(&slot) {
+            self.handle(node, block);
+        }
+    }
+
+    /// Shred the block of `slot` and hand one shred to each sampled relay
+    fn propose(&mut self, leader: NodeId, slot: Slot) {
+        let parent_hash = self.nodes[leader].finalized[&(slot - 1)];
+        let hash = block_hash(slot, leader);
+        let now = self.queue.now();
+        self.report.slots.insert(slot, SlotReport { leader, proposed_at_ms: now, ..SlotReport::default() });
+        let relays = self.sampler.perform_stake_weighted_sampling(slot, leader, self.config.sampling_strategy);
+        for (index, relay) in relays.into_iter().enumerate() {
+            self.report.shreds_sent += 1;
+            self.send(leader, relay, Event::Shred { dst: relay, slot, hash, parent_hash, index, relay: true });
+        }
+        self.reconstruct(leader, slot, hash, parent_hash);
+    }
+
+    /// `node` holds the whole block: vote for it once its parent is finalized here
+    fn reconstruct(&mut self, node: NodeId, slot: Slot, hash: Hash, parent_hash: Hash) {
+        if !self.nodes[node].reconstructed.insert(slot) {
+            return;
+        }
+        let latency = self.latency(slot);
+        self.latencies.entry(slot).or_default().0.push(latency);
+        let block = Message::Block { slot, hash, parent_hash };
+        if self.nodes[node].finalized.contains_key(&(slot - 1)) {
+            self.handle(node, block);
+        } else {
+            self.nodes[node].pending.insert(slot, block);
+        }
+    }
+
+    /// Time since `slot` was proposed
+    fn latency(&self, slot: Slot) -> u64 {
+        self.queue.now() - self.report.slots.get(&slot).map_or(0, |report| report.proposed_at_ms)
+    }
+
+    fn handle(&mut self, node: NodeId, msg: Message) {
+        let validators = self.config.nodes;
+        let sim_node = &mut self.nodes[node];
+        let output = sim_node.votor.on_message(node, &msg, &sim_node.finalized, |voters| (voters * 100 / validators) as u64);
+        self.apply(node, output);
+    }
+
+    /// Broadcast the votes of `output` and record its finalization
+    fn apply(&mut self, node: NodeId, output: NodeOutput) {
+        for msg in output.broadcast {
+            for dst in 0..self.config.nodes {
+                self.report.votes_sent += 1;
+                self.send(node, dst, Event::Vote { dst, msg: msg.clone() });
+            }
+        }
+        let Some((slot, hash, fast)) = output.finalized else {
+            return;
+        };
+        if self.nodes[node].finalized.insert(slot, hash).is_some() {
+            return;
+        }
+        if *self.first_finalized.entry(slot).or_insert(hash) != hash {
+            self.report.conflicting_finalization = true;
+        }
+        let latency = self.latency(slot);
+        self.latencies.entry(slot).or_default().1.push(latency);
+        if fast {
+            if let Some(report) = self.report.slots.get_mut(&slot) {
+                report.fast_finalized += 1;
+            }
+        }
+        self.start_slot(node, slot + 1);
+    }
+}
+
+/// Simulate `config` until no events remain or the event budget runs out
+pub fn simulate(config: &SimulatorConfig) -> SimulatorReport {
+    assert!(config.nodes > config.offline.len(), "the simulation needs an online node");
+    Simulation::new(config).run()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_queue_order() {
+        let mut queue = EventQueue::new();
+        queue.schedule(3, "late");
+        queue.schedule(1, "first");
+        queue.schedule(1, "second");
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.pop(), Some("first"));
+        assert_eq!(queue.pop(), Some("second"));
+        assert_eq!(queue.now(), 1);
+        queue.schedule(1, "between");
+        assert_eq!(queue.pop(), Some("between"));
+        assert_eq!(queue.pop(), Some("late"));
+        assert_eq!(queue.now(), 3);
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn test_latency_distributions() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert_eq!(Latency::parse("uniform:5-50"), Ok(Latency::Uniform { min_ms: 5, max_ms: 50 }));
+        assert_eq!(Latency::parse("lognormal:30,0.5"), Ok(Latency::LogNormal { median_ms: 30, sigma: 0.5 }));
+        assert!(Latency::parse("uniform:50-5").is_err());
+        assert!(Latency::parse("gaussian:5").is_err());
+        assert!((0..1000).all(|_| (5..=50).contains(&Latency::Uniform { min_ms: 5, max_ms: 50 }.sample(&mut rng))));
+        assert!((0..1000).all(|_| Latency::Exponential { min_ms: 10, mean_ms: 20 }.sample(&mut rng) >= 10));
+        let mut delays: Vec<u64> = (0..1001).map(|_| DEFAULT_LATENCY.sample(&mut rng)).collect();
+        delays.sort_unstable();
+        assert!((25..=35).contains(&delays[500]), "median {}", delays[500]);
+    }
+
+    #[test]
+    fn test_fast_finalization_at_scale() {
+        let config = SimulatorConfig { latency: Latency::Constant(10), ..SimulatorConfig::new(200, 3, 1) };
+        let report = simulate(&config);
+        assert!(report.complete);
+        assert!(report.all_finalized(3));
+        assert!(!report.conflicting_finalization);
+        assert_eq!(report.timeouts, 0);
+        for (slot, slot_report) in &report.slots {
+            // Leader to relay to everyone, then one round of votes
+            assert_eq!(slot_report.reconstructed.p50_ms, 20, "slot {}", slot);
+            assert_eq!(slot_report.finalized.max_ms, 30, "slot {}", slot);
+            assert_eq!(slot_report.fast_finalized, 200);
+        }
+        assert_eq!(report.slots[&2].proposed_at_ms, 30);
+
+        let record = report.record(&config);
+        assert!(record.properties.iter().all(|(_, verdict)| verdict == "held"));
+        assert!(record.metrics.contains(&("slot_3_finalized_max_ms".to_string(), 30.0)));
+    }
+
+    #[test]
+    fn test_slow_finalization_with_crashed_nodes() {
+        // 30% of the stake offline rules out the fast path; crashed nodes get no relay stake so
+        // that every shred is relayed
+        let offline: BTreeSet<NodeId> = (70..100).collect();
+        let stakes = (0..100).map(|id| if offline.contains(&id) { 0 } else { 1 }).collect();
+        let config = SimulatorConfig { latency: Latency::Constant(10), stakes, offline, ..SimulatorConfig::new(100, 2, 1) };
+        let report = simulate(&config);
+        assert_eq!(report.online, 70);
+        assert!(report.all_finalized(2));
+        assert!(report.slots.values().all(|slot_report| slot_report.fast_finalized == 0));
+        // A second round of votes for the slow path
+        assert_eq!(report.slots[&1].finalized.max_ms, 40);
+    }
+}
@@ -0,0 +1,49 @@
+//! Slashing evidence shared by the consensus models.
+//! A validator that signs NotarVotes for two different blocks in one slot equivocates; any
+//! validator that receives both votes can assemble them into a [`SlashingProof`]. The
+//! certificate and safety models collect such proofs in each validator's state and check
+//! that every proof is genuine and that equivocators are caught.
+
+use std::collections::BTreeSet;
+
+type Slot = u64;
+type Hash = u64;
+type ActorId = usize;
+
+/// A NotarVote as signed by its voter
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SignedVote {
+    pub slot: Slot,
+    pub hash: Hash,
+    pub voter: ActorId,
+}
+
+/// Evidence that a validator signed NotarVotes for two different blocks in the same slot
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct SlashingProof {
+    pub first: SignedVote,
+    pub second: SignedVote,
+}
+
+impl SlashingProof {
+    pub fn new(a: SignedVote, b: SignedVote) -> Self {
+        Self { first: a.min(b), second: a.max(b) }
+    }
+
+    /// Validator implicated by this proof
+    pub fn voter(&self) -> ActorId {
+        self.first.voter
+    }
+
+    /// Both votes come from the same voter, for the same slot, for different blocks
+    pub fn is_conflicting(&self) -> bool {
+        self.first.voter == self.second.voter
+            && self.first.slot == self.second.slot
+            && self.first.hash != self.second.hash
+    }
+}
+
+/// Whether `proofs` hold a proof that `voter` equivocated in `slot`
+pub fn has_proof_against(proofs: &BTreeSet<SlashingProof>, voter: ActorId, slot: Slot) -> bool {
+    proofs.iter().any(|proof| proof.voter() == voter && proof.first.slot == slot)
+}
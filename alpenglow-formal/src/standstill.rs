@@ -0,0 +1,543 @@
+//! Formal verification model for standstill detection and protocol restart in Alpenglow consensus.
+//! This module provides a Stateright-based formal model for verifying that a standstill
+//! (no certificates for several consecutive slots) is detected, and that the restart procedure
+//! preserves the finalized prefix and lets the chain resume under sufficient honest stake.
+
+use stateright::{Model, Property, Checker};
+use std::collections::{BTreeMap, BTreeSet};
+use crate::network::{NetworkFault, NetworkFaults};
+use crate::shared::SharedVec;
+use crate::bounds::Bounded;
+use crate::limits::Outcome;
+use crate::options::CheckOptions;
+
+// --- Formal Model Configuration ---
+const CERTIFICATE_THRESHOLD_PERCENT: u64 = 60;
+const TOTAL_STAKE: u64 = 1000;
+const STANDSTILL_SLOTS: u64 = 2; // Consecutive uncertified slots before standstill
+const MAX_RESTARTS: u64 = 1; // Formal verification limit
+const RESUME_SLOTS: u64 = 1; // Slots past a restart by which a certificate must have formed
+
+// Type aliases for clarity
+type Slot = u64;
+type Hash = u64;
+type ActorId = usize;
+type Stake = u64;
+
+/// Represents different types of messages in the standstill system
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum StandstillMessage {
+    /// A finalization certificate for a slot
+    Certificate {
+        slot: Slot,
+        hash: Hash,
+    },
+    /// A restart announcement carrying the sender's finalized chain
+    RestartAnnouncement {
+        validator: ActorId,
+        finalized_chain: BTreeMap<Slot, Hash>,
+    },
+}
+
+/// Represents messages in transit
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct MessageInTransit {
+    dst: ActorId,
+    msg: StandstillMessage,
+}
+
+/// Actions that can be taken in the standstill model
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum StandstillAction {
+    /// Responsive validators certify the current slot
+    CertifySlot {
+        slot: Slot,
+        leader: ActorId,
+    },
+    /// The current slot passes without any certificate
+    MissSlot { slot: Slot },
+    /// Deliver a message to its destination
+    DeliverMessage { msg: MessageInTransit },
+    /// A validator that detected the standstill announces a restart
+    AnnounceRestart { validator: ActorId },
+    /// Restart the protocol once enough stake has announced
+    Restart,
+    /// The network adversary drops, duplicates or delays a message
+    NetworkFault { fault: NetworkFault<MessageInTransit> },
+}
+
+/// State of a validator in the standstill model
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ValidatorState {
+    /// Whether this validator is responsive
+    is_responsive: bool,
+    /// Chain of finalized blocks: slot -> hash
+    finalized_chain: BTreeMap<Slot, Hash>,
+    /// Whether this validator has announced a restart
+    restart_announced: bool,
+    /// Restart announcements received: validator -> announced chain
+    restart_announcements: BTreeMap<ActorId, BTreeMap<Slot, Hash>>,
+}
+
+/// Main state of the standstill formal model
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct StandstillState {
+    /// Network messages in transit
+    network: BTreeSet<MessageInTransit>,
+    /// Adversarial control over message delivery
+    network_faults: NetworkFaults<MessageInTransit>,
+    /// Per-validator states
+    validators: SharedVec<ValidatorState>,
+    /// Global current slot
+    current_slot: Slot,
+    /// Number of consecutive slots that passed without a certificate
+    consecutive_uncertified: u64,
+    /// Whether the protocol is in standstill
+    in_standstill: bool,
+    /// Number of restarts performed
+    restart_count: u64,
+    /// Slot at which the most recent restart resumed the protocol
+    restart_slot: Option<Slot>,
+    /// Ghost record of every block a responsive validator had finalized when the most recent
+    /// restart happened, taken from their own chains rather than their announcements
+    finalized_before_restart: BTreeMap<Slot, Hash>,
+    /// Certificates formed: slot -> hash
+    certified_slots: BTreeMap<Slot, Hash>,
+    /// Stake distribution: validator -> stake
+    stake_distribution: BTreeMap<ActorId, Stake>,
+}
+
+/// Formal model for standstill detection and protocol restart
+#[derive(Clone)]
+pub struct StandstillModel {
+    /// Number of validators
+    pub validator_count: usize,
+    /// Maximum slots to explore
+    pub max_slot: Slot,
+    /// Number of responsive validators
+    pub responsive_count: usize,
+    /// Messages the network adversary may drop, duplicate or delay
+    pub network_faults: usize,
+}
+
+impl StandstillState {
+    fn new(validator_count: usize, responsive_count: usize) -> Self {
+        let mut stake_distribution = BTreeMap::new();
+        let stake_per_validator = TOTAL_STAKE / validator_count as u64;
+
+        for i in 0..validator_count {
+            stake_distribution.insert(i, stake_per_validator);
+        }
+
+        Self {
+            network: BTreeSet::new(),
+            network_faults: NetworkFaults::new(0),
+            validators: (0..validator_count).map(|i| ValidatorState {
+                is_responsive: i < responsive_count,
+                finalized_chain: BTreeMap::new(),
+                restart_announced: false,
+                restart_announcements: BTreeMap::new(),
+            }).collect(),
+            current_slot: 1,
+            consecutive_uncertified: 0,
+            in_standstill: false,
+            restart_count: 0,
+            restart_slot: None,
+            finalized_before_restart: BTreeMap::new(),
+            certified_slots: BTreeMap::new(),
+            stake_distribution,
+        }
+    }
+
+    /// Total stake of the responsive validators
+    fn responsive_stake(&self) -> Stake {
+        self.validators.iter()
+            .enumerate()
+            .filter(|(_, v)| v.is_responsive)
+            .filter_map(|(id, _)| self.stake_distribution.get(&id))
+            .sum()
+    }
+
+    /// Check if the responsive stake is enough to form certificates (60% threshold)
+    fn has_sufficient_stake(&self) -> bool {
+        self.responsive_stake() >= (TOTAL_STAKE * CERTIFICATE_THRESHOLD_PERCENT / 100)
+    }
+
+    /// Check if enough stake has announced a restart to the given validator
+    fn can_restart(&self, validator: ActorId) -> bool {
+        let announced_stake: Stake = self.validators[validator].restart_announcements.keys()
+            .filter_map(|id| self.stake_distribution.get(id))
+            .sum();
+        announced_stake >= (TOTAL_STAKE * CERTIFICATE_THRESHOLD_PERCENT / 100)
+    }
+
+    /// Check if a certificate formed after the most recent restart
+    fn finalized_after_restart(&self) -> bool {
+        match self.restart_slot {
+            Some(restart_slot) => self.certified_slots.keys().any(|slot| *slot >= restart_slot),
+            None => false,
+        }
+    }
+}
+
+impl Bounded for StandstillState {
+    fn current_slot(&self) -> Slot {
+        self.current_slot
+    }
+
+    fn in_flight(&self) -> usize {
+        self.network.len()
+    }
+}
+
+impl Model for StandstillModel {
+    type State = StandstillState;
+    type Action = StandstillAction;
+
+    fn init_states(&self) -> Vec<Self::State> {
+        let mut state = StandstillState::new(self.validator_count, self.responsive_count);
+        state.network_faults = NetworkFaults::new(self.network_faults);
+        vec![state]
+    }
+
+    fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
+        // 1. Deliver any message in the network
+        for msg in &state.network {
+            actions.push(StandstillAction::DeliverMessage { msg: msg.clone() });
+        }
+
+        // The network adversary drops, duplicates or delays a bounded number of messages
+        for fault in state.network_faults.faults(&state.network) {
+            actions.push(StandstillAction::NetworkFault { fault });
+        }
+
+        // 2. Certify or miss the current slot while the protocol is running
+        if !state.in_standstill && state.current_slot <= self.max_slot {
+            if state.has_sufficient_stake() {
+                actions.push(StandstillAction::CertifySlot {
+                    slot: state.current_slot,
+                    leader: state.current_slot as usize % self.validator_count,
+                });
+            }
+            // After a restart the network is assumed synchronous, so slots are only
+            // missed when the responsive stake cannot form certificates
+            if state.restart_count == 0 || !state.has_sufficient_stake() {
+                actions.push(StandstillAction::MissSlot { slot: state.current_slot });
+            }
+        }
+
+        // 3. Responsive validators announce a restart once standstill is detected
+        if state.in_standstill {
+            for (validator, validator_state) in state.validators.iter().enumerate() {
+                if validator_state.is_responsive && !validator_state.restart_announced {
+                    actions.push(StandstillAction::AnnounceRestart { validator });
+                }
+            }
+        }
+
+        // 4. Restart once every responsive validator has gathered the announcements of all
+        //    responsive validators, carrying at least the certificate threshold of stake and
+        //    the chains the announcers hold now, not ones they have since extended
+        if state.in_standstill && state.restart_count < MAX_RESTARTS && state.current_slot <= self.max_slot {
+            let responsive: BTreeSet<ActorId> = state.validators.iter()
+                .enumerate()
+                .filter(|(_, v)| v.is_responsive)
+                .map(|(id, _)| id)
+                .collect();
+            let all_ready = responsive.iter().all(|id| {
+                state.can_restart(*id)
+                    && responsive.iter().all(|other| {
+                        state.validators[*id].restart_announcements.get(other) == Some(&state.validators[*other].finalized_chain)
+                    })
+            });
+            if all_ready {
+                actions.push(StandstillAction::Restart);
+            }
+        }
+    }
+
+    fn next_state(&self, last_state: &Self::State, action: Self::Action) -> Option<Self::State> {
+        let mut next_state = last_state.clone();
+        let mut validators = last_state.validators.clone();
+
+        match action {
+            StandstillAction::CertifySlot { slot, leader } => {
+                let block_hash = slot * 1000 + leader as u64;
+                next_state.certified_slots.insert(slot, block_hash);
+                next_state.current_slot += 1;
+                next_state.consecutive_uncertified = 0;
+
+                // Broadcast the certificate to all validators
+                for i in 0..self.validator_count {
+                    next_state.network.insert(MessageInTransit {
+                        dst: i,
+                        msg: StandstillMessage::Certificate { slot, hash: block_hash },
+                    });
+                }
+            }
+            StandstillAction::MissSlot { slot: _ } => {
+                next_state.current_slot += 1;
+                next_state.consecutive_uncertified += 1;
+
+                // Standstill detection
+                if next_state.consecutive_uncertified >= STANDSTILL_SLOTS {
+                    next_state.in_standstill = true;
+                }
+            }
+            StandstillAction::NetworkFault { fault } => {
+                if !next_state.network_faults.apply(&mut next_state.network, fault) { return None; }
+            }
+            StandstillAction::DeliverMessage { msg } => {
+                let recipient_id = msg.dst;
+                let mut validator_state = validators.get(recipient_id)?.clone();
+
+                // Remove message from network
+                if !next_state.network.remove(&msg) { return None; }
+                next_state.network_faults.record_delivery(&msg);
+
+                // Non-responsive validators do not process messages
+                if validator_state.is_responsive {
+                    match msg.msg {
+                        StandstillMessage::Certificate { slot, hash } => {
+                            validator_state.finalized_chain.entry(slot).or_insert(hash);
+                        }
+                        StandstillMessage::RestartAnnouncement { validator, finalized_chain } => {
+                            validator_state.restart_announcements.insert(validator, finalized_chain);
+                        }
+                    }
+                }
+                validators[recipient_id] = validator_state;
+            }
+            StandstillAction::AnnounceRestart { validator } => {
+                validators[validator].restart_announced = true;
+                let finalized_chain = validators[validator].finalized_chain.clone();
+
+                // Broadcast restart announcement with the local finalized chain
+                for i in 0..self.validator_count {
+                    next_state.network.insert(MessageInTransit {
+                        dst: i,
+                        msg: StandstillMessage::RestartAnnouncement {
+                            validator,
+                            finalized_chain: finalized_chain.clone(),
+                        },
+                    });
+                }
+            }
+            StandstillAction::Restart => {
+                next_state.finalized_before_restart = validators.iter()
+                    .filter(|v| v.is_responsive)
+                    .flat_map(|v| v.finalized_chain.iter().map(|(s, h)| (*s, *h)))
+                    .collect();
+
+                // The agreed prefix is the union of the announced chains
+                let agreed: BTreeMap<Slot, Hash> = validators.iter()
+                    .filter(|v| v.is_responsive)
+                    .flat_map(|v| v.restart_announcements.values())
+                    .flat_map(|chain| chain.iter().map(|(s, h)| (*s, *h)))
+                    .collect();
+
+                // Every responsive validator adopts the agreed prefix
+                for validator_state in validators.iter_mut().filter(|v| v.is_responsive) {
+                    for (slot, hash) in &agreed {
+                        validator_state.finalized_chain.entry(*slot).or_insert(*hash);
+                    }
+                    validator_state.restart_announced = false;
+                    validator_state.restart_announcements.clear();
+                }
+
+                next_state.in_standstill = false;
+                next_state.consecutive_uncertified = 0;
+                next_state.restart_count += 1;
+                next_state.restart_slot = Some(next_state.current_slot);
+            }
+        }
+
+        next_state.validators = validators;
+        Some(next_state)
+    }
+
+    /// Properties to verify in the standstill model
+    fn properties(&self) -> Vec<Property<Self>> {
+        vec![
+            // Property 1: Standstill is detected after enough uncertified slots
+            Property::<Self>::always("standstill_detection", |_model, state| {
+                state.consecutive_uncertified < STANDSTILL_SLOTS || state.in_standstill
+            }),
+
+            // Property 2: Restart preserves the finalized prefix: whatever any responsive
+            // validator had finalized before it, every responsive validator holds after it
+            Property::<Self>::always("restart_preserves_finalized_prefix", |_model, state| {
+                for validator in state.validators.iter().filter(|v| v.is_responsive) {
+                    for (slot, hash) in &state.finalized_before_restart {
+                        if validator.finalized_chain.get(slot) != Some(hash) {
+                            return false;
+                        }
+                    }
+                }
+                true
+            }),
+
+            // Property 3: Finalized chains never disagree
+            Property::<Self>::always("finalized_chain_consistency", |_model, state| {
+                for validator in &state.validators {
+                    for (slot, hash) in &validator.finalized_chain {
+                        if state.certified_slots.get(slot) != Some(hash) {
+                            return false;
+                        }
+                    }
+                }
+                true
+            }),
+
+            // Property 4: The chain resumes after a restart with sufficient honest stake: no
+            // slot more than RESUME_SLOTS past the restart is reached without a certificate
+            Property::<Self>::always("chain_resumes_after_restart", |_model, state| {
+                state.restart_slot.is_none_or(|restart_slot| {
+                    !state.has_sufficient_stake()
+                        || state.current_slot <= restart_slot + RESUME_SLOTS
+                        || state.finalized_after_restart()
+                })
+            }),
+
+            // Property 5: A restart is reachable
+            Property::<Self>::sometimes("restart_reachable", |_model, state| {
+                state.restart_count > 0
+            }),
+
+            // Property 6: A restarted chain goes on to finalize, so Property 4 is not vacuous
+            Property::<Self>::sometimes("restart_then_finalize", |_model, state| {
+                state.restart_count > 0 && state.finalized_after_restart()
+            }),
+        ]
+    }
+}
+
+/// Run formal verification of standstill detection and restart
+pub fn run_formal_verification(options: &CheckOptions) {
+    println!("=== Standstill and Restart Formal Verification ===");
+
+    let model = StandstillModel {
+        validator_count: 3, // Small for formal verification
+        max_slot: 4,
+        responsive_count: 2, // 66% responsive (above 60% threshold)
+        network_faults: 0,
+    };
+
+    println!("Model checking standstill with {} validators ({} responsive), {} slots",
+             model.validator_count, model.responsive_count, model.max_slot);
+
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs()
+        .report(&mut stateright::report::WriteReporter::new(&mut std::io::stdout()));
+
+    // Check verification results
+    match options.print_outcomes(&result) {
+        Outcome::Proved => println!("✅ All standstill properties verified successfully"),
+        Outcome::Violated => println!("❌ Standstill verification found counterexamples"),
+        Outcome::Inconclusive => println!("❓ Standstill verification inconclusive: a limit or bound stopped the search first"),
+    }
+    options.record_results("standstill formal", &result);
+}
+
+/// Test standstill model with different configurations
+pub fn test_standstill_model(validators: usize, slots: u64, responsive: usize, options: &CheckOptions) {
+    println!("Testing standstill model with {} validators ({} responsive), {} slots",
+             validators, responsive, slots);
+
+    let model = StandstillModel {
+        validator_count: validators,
+        max_slot: slots,
+        responsive_count: responsive,
+        network_faults: 0,
+    };
+
+    options.print_summary();
+
+    let result = options
+        .checker(model)
+        .spawn_dfs();
+    options.progress.watch(&result);
+    let result = result.join();
+
+    println!("States explored: {}", result.state_count());
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("standstill {}v {}s {}r", validators, slots, responsive), &result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standstill_state_creation() {
+        let state = StandstillState::new(3, 2);
+        assert_eq!(state.validators.len(), 3);
+        assert!(state.validators[1].is_responsive);
+        assert!(!state.validators[2].is_responsive);
+        assert!(state.has_sufficient_stake());
+    }
+
+    #[test]
+    fn test_standstill_detection() {
+        let model = StandstillModel { validator_count: 3, max_slot: 4, responsive_count: 3, network_faults: 0 };
+        let state = StandstillState::new(3, 3);
+        let state = model.next_state(&state, StandstillAction::MissSlot { slot: 1 }).unwrap();
+        assert!(!state.in_standstill);
+        let state = model.next_state(&state, StandstillAction::MissSlot { slot: 2 }).unwrap();
+        assert!(state.in_standstill);
+    }
+
+    #[test]
+    fn test_restart_adopts_announced_chain() {
+        let model = StandstillModel { validator_count: 3, max_slot: 4, responsive_count: 3, network_faults: 0 };
+        let mut state = StandstillState::new(3, 3);
+        state.in_standstill = true;
+        state.certified_slots.insert(1, 1001);
+        state.validators[0].finalized_chain.insert(1, 1001);
+        let announced = state.validators[0].finalized_chain.clone();
+        for validator in &mut state.validators {
+            for id in 0..3 {
+                validator.restart_announcements.insert(id, announced.clone());
+            }
+        }
+
+        let state = model.next_state(&state, StandstillAction::Restart).unwrap();
+        assert!(!state.in_standstill);
+        for validator in &state.validators {
+            assert_eq!(validator.finalized_chain.get(&1), Some(&1001));
+        }
+    }
+
+    #[test]
+    fn test_restart_waits_for_current_announcements() {
+        let model = StandstillModel { validator_count: 3, max_slot: 4, responsive_count: 3, network_faults: 0 };
+        let mut state = StandstillState::new(3, 3);
+        state.in_standstill = true;
+        for validator in &mut state.validators {
+            for id in 0..3 {
+                validator.restart_announcements.insert(id, BTreeMap::new());
+            }
+        }
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(actions.contains(&StandstillAction::Restart));
+
+        // A validator that finalized a block after announcing holds up the restart
+        state.validators[0].finalized_chain.insert(1, 1001);
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(!actions.contains(&StandstillAction::Restart));
+    }
+
+    #[test]
+    fn test_restart_then_finalize() {
+        let model = StandstillModel { validator_count: 3, max_slot: 4, responsive_count: 2, network_faults: 0 };
+        let checker = model.checker().spawn_bfs().join();
+        checker.assert_properties();
+        assert!(checker.discovery("restart_then_finalize").is_some());
+    }
+}
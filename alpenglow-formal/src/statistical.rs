@@ -0,0 +1,348 @@
+//! Statistical model checking: Monte Carlo estimates of how likely properties fail.
+//! Configurations far beyond exhaustive reach can still be sampled. [`check`] runs many random
+//! schedules of a model, each picking uniformly among the enabled actions with its own seed,
+//! and counts the schedules that violate each property. Wilson score intervals turn the counts
+//! into bounds on the probability that a random schedule violates the property, at a chosen
+//! confidence, so that a run supports statements such as "with 95% confidence, fewer than 0.1%
+//! of schedules finalize conflicting blocks".
+//!
+//! `always` properties are violated by a schedule visiting a state where they do not hold.
+//! `eventually` properties are violated by a schedule ending in a state without successors
+//! before they held; schedules cut at the length limit before they held are undecided and
+//! count as violations for the upper bound only. For `sometimes` properties the schedules
+//! witnessing them are counted instead.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use stateright::{Expectation, Model};
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::thread;
+
+/// Default schedules sampled per configuration
+pub const DEFAULT_SAMPLES: usize = 10_000;
+/// Default most actions per schedule
+pub const DEFAULT_SCHEDULE_LENGTH: usize = 500;
+/// Default confidence of the reported intervals
+pub const DEFAULT_CONFIDENCE: f64 = 0.95;
+
+/// How many schedules to sample and how to bound the estimates
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SmcConfig {
+    pub samples: usize,
+    /// Most actions per schedule
+    pub schedule_length: usize,
+    /// Probability that each reported interval contains the true probability
+    pub confidence: f64,
+    /// Schedule `i` is drawn from seed `seed + i`
+    pub seed: u64,
+}
+
+impl SmcConfig {
+    pub fn new(seed: u64) -> Self {
+        Self { samples: DEFAULT_SAMPLES, schedule_length: DEFAULT_SCHEDULE_LENGTH, confidence: DEFAULT_CONFIDENCE, seed }
+    }
+}
+
+/// Estimated probability that a random schedule violates, or for `sometimes` properties
+/// witnesses, one property
+#[derive(Clone, Debug, PartialEq)]
+pub struct PropertyEstimate {
+    pub name: &'static str,
+    pub expectation: Expectation,
+    /// Schedules violating the property, or witnessing it
+    pub hits: usize,
+    /// Schedules cut at the length limit before an `eventually` property held
+    pub undecided: usize,
+    pub lower: f64,
+    pub upper: f64,
+    /// Seed of the first schedule counted in `hits`
+    pub first_seed: Option<u64>,
+}
+
+/// Outcome of a statistical check
+#[derive(Clone, Debug)]
+pub struct SmcReport<A> {
+    pub samples: usize,
+    /// Actions taken across all schedules
+    pub steps: usize,
+    pub confidence: f64,
+    pub estimates: Vec<PropertyEstimate>,
+    /// Actions of the first schedule violating each property
+    pub counterexamples: BTreeMap<&'static str, Vec<A>>,
+}
+
+impl<A: Debug> SmcReport<A> {
+    pub fn estimate(&self, property: &str) -> Option<&PropertyEstimate> {
+        self.estimates.iter().find(|estimate| estimate.name == property)
+    }
+
+    /// Whether some schedule violated an `always` or `eventually` property
+    pub fn violated(&self) -> bool {
+        self.estimates.iter().any(|estimate| estimate.expectation != Expectation::Sometimes && estimate.hits > 0)
+    }
+
+    pub fn print_summary(&self) {
+        println!("Statistical check: {} schedules, {} actions, {:.1}% confidence intervals",
+                 self.samples, self.steps, self.confidence * 100.0);
+        for estimate in &self.estimates {
+            let interval = format!("[{:.4}%, {:.4}%]", estimate.lower * 100.0, estimate.upper * 100.0);
+            match estimate.expectation {
+                Expectation::Sometimes => println!("  🎲 {}: witnessed by {} schedules, probability in {}",
+                                                   estimate.name, estimate.hits, interval),
+                _ if estimate.hits == 0 => println!("  ✅ {}: no violation{}, violation probability at most {:.4}%",
+                                                    estimate.name,
+                                                    if estimate.undecided > 0 { format!(" ({} undecided)", estimate.undecided) } else { String::new() },
+                                                    estimate.upper * 100.0),
+                _ => println!("  ❌ {}: violated by {} schedules (first with seed {}), probability in {}",
+                              estimate.name, estimate.hits, estimate.first_seed.unwrap_or_default(), interval),
+            }
+        }
+    }
+}
+
+/// Quantile of the standard normal distribution, by Acklam's rational approximation
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376e1, 2.209460984245205e2, -2.759285104469687e2,
+                         1.38357751867269e2, -3.066479806614716e1, 2.506628277459239];
+    const B: [f64; 5] = [-5.447609879822406e1, 1.615858368580409e2, -1.556989798598866e2,
+                         6.680131188771972e1, -1.328068155288572e1];
+    const C: [f64; 6] = [-7.784894002430293e-3, -3.223964580411365e-1, -2.400758277161838,
+                         -2.549732539343734, 4.374664141464968, 2.938163982698783];
+    const D: [f64; 4] = [7.784695709041462e-3, 3.224671290700398e-1, 2.445134137142996, 3.754408661907416];
+    let tail = |q: f64| (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+        / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0);
+    if p < 0.02425 {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - 0.02425 {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// Wilson score interval for `hits` successes out of `samples` at `confidence`
+fn wilson_interval(hits: usize, samples: usize, confidence: f64) -> (f64, f64) {
+    if samples == 0 {
+        return (0.0, 1.0);
+    }
+    let z = normal_quantile(1.0 - (1.0 - confidence) / 2.0);
+    let n = samples as f64;
+    let p = hits as f64 / n;
+    let denominator = 1.0 + z * z / n;
+    let center = (p + z * z / (2.0 * n)) / denominator;
+    let half_width = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt() / denominator;
+    // Without hits, or without misses, the bound is exact; rounding would leave it slightly off
+    let lower = if hits == 0 { 0.0 } else { (center - half_width).max(0.0) };
+    let upper = if hits == samples { 1.0 } else { (center + half_width).min(1.0) };
+    (lower, upper)
+}
+
+/// What one schedule showed about each property
+struct Schedule<A> {
+    steps: usize,
+    /// Whether the schedule violated, or witnessed, each property
+    hits: Vec<bool>,
+    undecided: Vec<bool>,
+    actions: Vec<A>,
+}
+
+/// A schedule's seed and length, the properties it hit or left undecided, and its actions when
+/// it hit one
+type Sampled<A> = (u64, usize, Vec<bool>, Vec<bool>, Option<Vec<A>>);
+
+/// Run the schedule drawn from `seed`
+fn run_schedule<M: Model>(model: &M, schedule_length: usize, seed: u64) -> Schedule<M::Action>
+where
+    M::State: Clone,
+    M::Action: Clone,
+{
+    let properties = model.properties();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut schedule = Schedule {
+        steps: 0,
+        hits: vec![false; properties.len()],
+        undecided: vec![false; properties.len()],
+        actions: Vec::new(),
+    };
+    let mut satisfied = vec![false; properties.len()];
+    let initial: Vec<_> = model.init_states().into_iter().filter(|state| model.within_boundary(state)).collect();
+    let Some(mut state) = initial.choose(&mut rng).cloned() else {
+        return schedule;
+    };
+    loop {
+        for (index, property) in properties.iter().enumerate() {
+            let holds = (property.condition)(model, &state);
+            match property.expectation {
+                Expectation::Always => schedule.hits[index] |= !holds,
+                Expectation::Sometimes => schedule.hits[index] |= holds,
+                Expectation::Eventually => satisfied[index] |= holds,
+            }
+        }
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        let successors: Vec<_> = actions.into_iter()
+            .filter_map(|action| model.next_state(&state, action.clone()).map(|next| (action, next)))
+            .filter(|(_, next)| model.within_boundary(next))
+            .collect();
+        let deadlocked = successors.is_empty();
+        if deadlocked || schedule.steps == schedule_length {
+            for (index, property) in properties.iter().enumerate() {
+                if property.expectation == Expectation::Eventually && !satisfied[index] {
+                    if deadlocked {
+                        schedule.hits[index] = true;
+                    } else {
+                        schedule.undecided[index] = true;
+                    }
+                }
+            }
+            return schedule;
+        }
+        let (action, next) = successors.choose(&mut rng).cloned().expect("successors are not empty");
+        schedule.actions.push(action);
+        schedule.steps += 1;
+        state = next;
+    }
+}
+
+/// Sample `config.samples` schedules of `model` on `threads` threads and estimate how likely
+/// each property fails; the estimates only depend on the seed, not on the thread count
+pub fn check<M>(model: &M, config: &SmcConfig, threads: usize) -> SmcReport<M::Action>
+where
+    M: Model + Sync,
+    M::State: Clone,
+    M::Action: Clone + Send,
+{
+    let properties = model.properties();
+    let threads = threads.clamp(1, config.samples.max(1));
+    let results: Vec<Vec<Sampled<M::Action>>> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|worker| scope.spawn(move || {
+                (worker..config.samples).step_by(threads)
+                    .map(|sample| {
+                        let seed = config.seed.wrapping_add(sample as u64);
+                        let schedule = run_schedule(model, config.schedule_length, seed);
+                        let actions = schedule.hits.iter().any(|hit| *hit).then_some(schedule.actions);
+                        (seed, schedule.steps, schedule.hits, schedule.undecided, actions)
+                    })
+                    .collect()
+            }))
+            .collect();
+        workers.into_iter().map(|worker| worker.join().expect("sampling thread panicked")).collect()
+    });
+    let mut results: Vec<_> = results.into_iter().flatten().collect();
+    results.sort_by_key(|(seed, ..)| seed.wrapping_sub(config.seed));
+
+    let mut report = SmcReport {
+        samples: config.samples,
+        steps: results.iter().map(|(_, steps, ..)| steps).sum(),
+        confidence: config.confidence,
+        estimates: Vec::new(),
+        counterexamples: BTreeMap::new(),
+    };
+    for (index, property) in properties.iter().enumerate() {
+        let hits = results.iter().filter(|(_, _, hits, ..)| hits[index]).count();
+        let undecided = results.iter().filter(|(_, _, _, undecided, _)| undecided[index]).count();
+        let first = results.iter().find(|(_, _, hits, ..)| hits[index]);
+        let (lower, _) = wilson_interval(hits, config.samples, config.confidence);
+        let (_, upper) = wilson_interval(hits + undecided, config.samples, config.confidence);
+        if property.expectation != Expectation::Sometimes {
+            if let Some((_, _, _, _, Some(actions))) = first {
+                report.counterexamples.insert(property.name, actions.clone());
+            }
+        }
+        report.estimates.push(PropertyEstimate {
+            name: property.name,
+            expectation: property.expectation.clone(),
+            hits,
+            undecided,
+            lower,
+            upper,
+            first_seed: first.map(|(seed, ..)| *seed),
+        });
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::votor::VotorModel;
+    use stateright::Property;
+
+    /// A fair coin tossed once
+    struct Coin;
+
+    impl Model for Coin {
+        type State = Option<bool>;
+        type Action = bool;
+
+        fn init_states(&self) -> Vec<Self::State> {
+            vec![None]
+        }
+
+        fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
+            if state.is_none() {
+                actions.extend([true, false]);
+            }
+        }
+
+        fn next_state(&self, _state: &Self::State, action: Self::Action) -> Option<Self::State> {
+            Some(Some(action))
+        }
+
+        fn properties(&self) -> Vec<Property<Self>> {
+            vec![
+                Property::always("heads", |_, state: &Option<bool>| *state != Some(false)),
+                Property::eventually("tossed", |_, state: &Option<bool>| state.is_some()),
+                Property::sometimes("tails", |_, state: &Option<bool>| *state == Some(false)),
+            ]
+        }
+    }
+
+    #[test]
+    fn test_interval_bounds() {
+        assert!((normal_quantile(0.975) - 1.959964).abs() < 1e-5);
+        assert!((normal_quantile(0.005) + 2.575829).abs() < 1e-5);
+        // No violation in n samples bounds the probability by about z² / n
+        let (lower, upper) = wilson_interval(0, 1000, 0.95);
+        assert_eq!(lower, 0.0);
+        assert!((upper - 0.003826).abs() < 1e-5, "{}", upper);
+        let (lower, upper) = wilson_interval(500, 1000, 0.95);
+        assert!(lower < 0.5 && 0.5 < upper && upper - lower < 0.07);
+    }
+
+    #[test]
+    fn test_estimates_violation_probability() {
+        let config = SmcConfig { samples: 2000, confidence: 0.999, ..SmcConfig::new(11) };
+        let report = check(&Coin, &config, 4);
+        let heads = report.estimate("heads").unwrap();
+        assert!(heads.lower < 0.5 && 0.5 < heads.upper, "{:?}", heads);
+        assert!((800..1200).contains(&heads.hits));
+        assert_eq!(report.counterexamples["heads"], vec![false]);
+        assert_eq!(report.estimate("tossed").unwrap().hits, 0);
+        assert_eq!(report.estimate("tails").unwrap().hits, heads.hits);
+        assert!(report.violated());
+
+        // Schedules only depend on their seeds
+        let sequential = check(&Coin, &config, 1);
+        assert_eq!(sequential.estimates, report.estimates);
+        assert_eq!(sequential.estimate("heads").unwrap().first_seed, heads.first_seed);
+    }
+
+    #[test]
+    fn test_votor_never_violated() {
+        let model = VotorModel { honest_validators: 3, max_slot: 1, network_faults: 0 };
+        let report = check(&model, &SmcConfig { samples: 500, ..SmcConfig::new(3) }, 2);
+        assert!(!report.violated());
+        assert!(report.steps > 0);
+        for estimate in report.estimates.iter().filter(|estimate| estimate.expectation == Expectation::Always) {
+            assert_eq!(estimate.lower, 0.0);
+            assert!(estimate.upper < 0.01, "{:?}", estimate);
+        }
+    }
+}
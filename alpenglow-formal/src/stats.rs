@@ -0,0 +1,85 @@
+//! Statistics of a model-checking run.
+//! The checker's reporter prints progress lines, not numbers a benchmark can compare. A
+//! [`CheckStats`] reads them from a finished run instead: the unique states and the transitions
+//! the checker explored, the deepest path it followed, and how often the bounded model evaluated
+//! each property, which is what the per-state cost of a check grows with.
+
+use crate::bounds::{Bounded, BoundedModel};
+use stateright::{Checker, Model};
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// What a checker explored, read once it is done
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckStats {
+    /// Distinct states visited
+    pub unique_states: usize,
+    /// States generated, counting a state again each time another transition reaches it
+    pub transitions: usize,
+    /// States whose successors were generated
+    pub expanded: usize,
+    /// Longest path from an initial state
+    pub max_depth: usize,
+    pub elapsed: Duration,
+    /// Times each property's condition was evaluated, in declaration order
+    pub property_evaluations: Vec<(&'static str, usize)>,
+}
+
+impl CheckStats {
+    /// Statistics of the run of `checker`
+    pub fn of<M, C>(checker: &C) -> Self
+    where
+        M: Model,
+        M::State: Bounded,
+        M::Action: Debug,
+        C: Checker<BoundedModel<M>>,
+    {
+        let model = checker.model();
+        Self {
+            unique_states: checker.unique_state_count(),
+            transitions: checker.state_count(),
+            expanded: model.expanded(),
+            max_depth: checker.max_depth(),
+            elapsed: model.elapsed(),
+            property_evaluations: model.property_evaluations(),
+        }
+    }
+
+    /// Unique states visited per second of checking
+    pub fn states_per_second(&self) -> f64 {
+        self.unique_states as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    pub fn print_summary(&self) {
+        println!("States explored: {} unique, {} expanded", self.unique_states, self.expanded);
+        println!("Transitions: {}", self.transitions);
+        println!("Max depth: {}", self.max_depth);
+        println!("Properties checked: {}", self.property_evaluations.len());
+        for (name, evaluations) in &self.property_evaluations {
+            println!("  {}: evaluated {} times", name, evaluations);
+        }
+        println!("Checking time: {:.2}s ({:.0} states/s)", self.elapsed.as_secs_f64(), self.states_per_second());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounds::ExplorationBounds;
+    use crate::votor::VotorModel;
+
+    #[test]
+    fn test_stats_of_a_run() {
+        let model = VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 };
+        let properties = model.properties().len();
+        let checker = ExplorationBounds::default().checker(model).spawn_bfs().join();
+        let stats = CheckStats::of(&checker);
+
+        assert_eq!(stats.unique_states, checker.unique_state_count());
+        assert!(stats.transitions >= stats.unique_states);
+        assert!(stats.expanded <= stats.unique_states && stats.expanded > 0);
+        assert!(stats.max_depth > 0);
+        assert_eq!(stats.property_evaluations.len(), properties);
+        assert!(stats.property_evaluations.iter().all(|(_, evaluations)| *evaluations > 0));
+    }
+}
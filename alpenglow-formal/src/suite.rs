@@ -0,0 +1,144 @@
+//! The full verification suite: every model checked at the size its formal verification uses.
+//! [`run`] checks the [`PHASES`] on parallel workers, sharing the cores between them, and
+//! collects a [`PhaseResult`] per phase, which [`SuiteReport`] prints as a summary table with
+//! the states, time and outcome of each model.
+
+use std::time::{Duration, Instant};
+use crate::sweep;
+
+/// One model of the suite and the configuration it is checked in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Phase {
+    /// Model name, as accepted by `--model`
+    pub model: &'static str,
+    /// What the phase verifies
+    pub description: &'static str,
+    pub validators: usize,
+    pub slots: u64,
+    /// Byzantine validators, or unresponsive ones in the models without Byzantine behaviour
+    pub byzantine: usize,
+}
+
+/// The phases of the suite, in the order they are reported
+pub const PHASES: [Phase; 14] = [
+    Phase { model: "votor", description: "Dual-path finality of Votor", validators: 2, slots: 1, byzantine: 0 },
+    Phase { model: "safety", description: "No conflicting finalization", validators: 4, slots: 3, byzantine: 1 },
+    Phase { model: "liveness", description: "Progress and bounded finalization", validators: 4, slots: 3, byzantine: 1 },
+    Phase { model: "resilience", description: "Byzantine and partition resilience", validators: 4, slots: 3, byzantine: 1 },
+    Phase { model: "certificate", description: "Certificate aggregation and uniqueness", validators: 4, slots: 3, byzantine: 1 },
+    Phase { model: "leader", description: "Leader rotation and windows", validators: 3, slots: 5, byzantine: 0 },
+    Phase { model: "timeout", description: "Timeouts and skip certificates", validators: 3, slots: 3, byzantine: 0 },
+    Phase { model: "rotor", description: "Rotor relay sampling", validators: 4, slots: 3, byzantine: 0 },
+    Phase { model: "standstill", description: "Standstill detection and restart", validators: 3, slots: 4, byzantine: 1 },
+    Phase { model: "pool", description: "Bounded vote pool and certificates", validators: 4, slots: 1, byzantine: 1 },
+    Phase { model: "epoch", description: "Validator-set changes at epoch boundaries", validators: 5, slots: 2, byzantine: 1 },
+    Phase { model: "light-client", description: "Light-client finality from certificates", validators: 4, slots: 1, byzantine: 1 },
+    Phase { model: "rewards", description: "Voting reward accounting and incentives", validators: 4, slots: 2, byzantine: 1 },
+    Phase { model: "alpenglow", description: "Composed protocol end to end", validators: 3, slots: 2, byzantine: 0 },
+];
+
+/// Outcome of checking one phase
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PhaseResult {
+    pub phase: Phase,
+    /// Unique states explored
+    pub states: usize,
+    pub elapsed: Duration,
+    /// Properties that failed: `always` and `eventually` properties with a counterexample,
+    /// `sometimes` properties without an example
+    pub failures: Vec<String>,
+    /// Properties neither proved nor violated before a limit or bound stopped the search
+    pub inconclusive: Vec<String>,
+}
+
+/// Checker threads for each of `workers` phases checked at once, so that together they use
+/// the `threads` the suite may
+pub fn threads_per_phase(threads: usize, workers: usize) -> usize {
+    (threads / workers.max(1)).max(1)
+}
+
+/// Check every phase with `check` on `workers` threads; results come back in phase order
+pub fn run<F>(phases: &[Phase], workers: usize, check: F) -> SuiteReport
+where
+    F: Fn(&Phase) -> PhaseResult + Sync,
+{
+    let start = Instant::now();
+    let results = sweep::parallel_map(phases, workers, check);
+    SuiteReport { results, elapsed: start.elapsed() }
+}
+
+/// Results of a run of the suite
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SuiteReport {
+    pub results: Vec<PhaseResult>,
+    /// Wall-clock time of the whole suite
+    pub elapsed: Duration,
+}
+
+impl SuiteReport {
+    /// Whether no property was violated in any phase; inconclusive properties do not fail the suite
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|result| result.failures.is_empty())
+    }
+
+    /// Summed time of the phases, more than the wall-clock time when they ran in parallel
+    pub fn total_phase_time(&self) -> Duration {
+        self.results.iter().map(|result| result.elapsed).sum()
+    }
+
+    pub fn print_summary(&self) {
+        println!("{:<12} {:<40} {:>10} {:>12} {:>10}  result", "model", "verifies", "size", "states", "time");
+        for result in &self.results {
+            let phase = &result.phase;
+            println!("{:<12} {:<40} {:>10} {:>12} {:>10}  {}",
+                     phase.model, phase.description,
+                     format!("{}v {}s {}b", phase.validators, phase.slots, phase.byzantine),
+                     result.states, format!("{:.2?}", result.elapsed),
+                     if !result.failures.is_empty() {
+                         format!("❌ {}", result.failures.join(", "))
+                     } else if !result.inconclusive.is_empty() {
+                         format!("❓ inconclusive: {}", result.inconclusive.join(", "))
+                     } else {
+                         "✅ pass".to_string()
+                     });
+        }
+        let failed = self.results.iter().filter(|result| !result.failures.is_empty()).count();
+        let inconclusive = self.results.iter()
+            .filter(|result| result.failures.is_empty() && !result.inconclusive.is_empty())
+            .count();
+        println!("{} models in {:.2?} ({:.2?} of checking): {} passed, {} failed, {} inconclusive",
+                 self.results.len(), self.elapsed, self.total_phase_time(),
+                 self.results.len() - failed - inconclusive, failed, inconclusive);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_cover_distinct_models() {
+        let mut models: Vec<&str> = PHASES.iter().map(|phase| phase.model).collect();
+        models.sort();
+        models.dedup();
+        assert_eq!(models.len(), PHASES.len());
+        assert!(PHASES.iter().all(|phase| phase.byzantine < phase.validators));
+    }
+
+    #[test]
+    fn test_parallel_run_keeps_phase_order() {
+        let report = run(&PHASES, 4, |phase| PhaseResult {
+            phase: *phase,
+            states: phase.validators * 10,
+            elapsed: Duration::from_millis(phase.slots),
+            failures: if phase.model == "rotor" { vec!["delivery".to_string()] } else { Vec::new() },
+            inconclusive: if phase.model == "leader" { vec!["fair_rotation".to_string()] } else { Vec::new() },
+        });
+        let ordered: Vec<Phase> = report.results.iter().map(|result| result.phase).collect();
+        assert_eq!(ordered, PHASES);
+        assert!(!report.passed());
+        assert_eq!(report.total_phase_time(), Duration::from_millis(PHASES.iter().map(|phase| phase.slots).sum()));
+        assert_eq!(threads_per_phase(8, 3), 2);
+        assert_eq!(threads_per_phase(2, PHASES.len()), 1);
+    }
+}
@@ -0,0 +1,205 @@
+//! Parameter-grid sweeps: one model checked over the cartesian product of its parameters.
+//! [`grid`] lists the configurations, [`run`] checks them on parallel workers and
+//! [`SweepReport`] prints the results matrix or exports it as CSV, so comparing how a property
+//! fares across validator counts, Byzantine validators, slots and corruption thresholds takes one
+//! command rather than a script of one-off runs.
+
+use std::fmt::Write as _;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// One configuration of the grid
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SweepPoint {
+    pub validators: usize,
+    pub slots: u64,
+    pub byzantine: usize,
+    /// Most stake (percent) the adversary may corrupt, where the model has such a budget;
+    /// `None` keeps the model's default
+    pub corruption_percent: Option<u64>,
+}
+
+/// Outcome of checking one configuration
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SweepResult {
+    pub point: SweepPoint,
+    /// Unique states explored
+    pub states: usize,
+    pub elapsed: Duration,
+    /// Properties that failed: `always` and `eventually` properties with a counterexample,
+    /// `sometimes` properties without an example
+    pub failures: Vec<String>,
+    /// Properties neither proved nor violated before a limit or bound stopped the search
+    pub inconclusive: Vec<String>,
+}
+
+/// Parse a comma-separated list of values or inclusive ranges, such as `2,4..6`
+pub fn parse_list<T>(text: &str) -> Result<Vec<T>, String>
+where
+    T: FromStr + TryFrom<u64>,
+{
+    let invalid = |value: &str| format!("invalid value '{}' in '{}'", value, text);
+    let mut values = Vec::new();
+    for item in text.split(',') {
+        match item.split_once("..") {
+            Some((low, high)) => {
+                let bound = |value: &str| value.trim().parse::<u64>().map_err(|_| invalid(value));
+                let (low, high) = (bound(low)?, bound(high)?);
+                if low > high {
+                    return Err(format!("empty range '{}'", item));
+                }
+                values.extend((low..=high).filter_map(|value| T::try_from(value).ok()));
+            }
+            None => values.push(item.trim().parse::<T>().map_err(|_| invalid(item))?),
+        }
+    }
+    Ok(values)
+}
+
+/// Every combination of the given values, leaving out those with more Byzantine validators
+/// than validators
+pub fn grid(validators: &[usize], slots: &[u64], byzantine: &[usize], corruption_percents: &[Option<u64>]) -> Vec<SweepPoint> {
+    let mut points = Vec::new();
+    for &validators in validators {
+        for &byzantine in byzantine.iter().filter(|byzantine| **byzantine <= validators) {
+            for &slots in slots {
+                for &corruption_percent in corruption_percents {
+                    points.push(SweepPoint { validators, slots, byzantine, corruption_percent });
+                }
+            }
+        }
+    }
+    points
+}
+
+/// Check every point with `check` on `workers` threads; results come back in grid order
+pub fn run<F>(points: &[SweepPoint], workers: usize, check: F) -> SweepReport
+where
+    F: Fn(&SweepPoint) -> SweepResult + Sync,
+{
+    let start = Instant::now();
+    let results = parallel_map(points, workers, check);
+    SweepReport { results, elapsed: start.elapsed() }
+}
+
+/// Apply `f` to every item on `workers` threads, each taking the next item as it finishes
+/// one; results come back in item order
+pub fn parallel_map<T, R, F>(items: &[T], workers: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = Mutex::new(0usize);
+    let results = Mutex::new(Vec::with_capacity(items.len()));
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, items.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = {
+                    let mut next = next.lock().unwrap();
+                    *next += 1;
+                    *next - 1
+                };
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                let result = f(item);
+                results.lock().unwrap().push((index, result));
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// The results matrix of a sweep
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SweepReport {
+    pub results: Vec<SweepResult>,
+    pub elapsed: Duration,
+}
+
+impl SweepReport {
+    /// Whether no property was violated in any configuration
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|result| result.failures.is_empty())
+    }
+
+    pub fn print_summary(&self) {
+        println!("{:>10} {:>6} {:>10} {:>11} {:>12} {:>10}  result",
+                 "validators", "slots", "byzantine", "corruption", "states", "time");
+        for result in &self.results {
+            let point = &result.point;
+            println!("{:>10} {:>6} {:>10} {:>11} {:>12} {:>10}  {}",
+                     point.validators, point.slots, point.byzantine,
+                     point.corruption_percent.map_or("default".to_string(), |percent| format!("{}%", percent)),
+                     result.states, format!("{:.2?}", result.elapsed),
+                     if !result.failures.is_empty() {
+                         format!("❌ {}", result.failures.join(", "))
+                     } else if !result.inconclusive.is_empty() {
+                         format!("❓ inconclusive: {}", result.inconclusive.join(", "))
+                     } else {
+                         "✅ pass".to_string()
+                     });
+        }
+        let failed = self.results.iter().filter(|result| !result.failures.is_empty()).count();
+        let inconclusive = self.results.iter()
+            .filter(|result| result.failures.is_empty() && !result.inconclusive.is_empty())
+            .count();
+        println!("{} configurations in {:.2?}: {} passed, {} failed, {} inconclusive",
+                 self.results.len(), self.elapsed, self.results.len() - failed - inconclusive, failed, inconclusive);
+    }
+
+    /// The matrix as CSV, one row per configuration, failed and inconclusive properties separated by `;`
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("validators,slots,byzantine,corruption_percent,states,seconds,passed,failures,inconclusive\n");
+        for result in &self.results {
+            let point = &result.point;
+            let _ = writeln!(csv, "{},{},{},{},{},{:.3},{},{},{}",
+                             point.validators, point.slots, point.byzantine,
+                             point.corruption_percent.map_or(String::new(), |percent| percent.to_string()),
+                             result.states, result.elapsed.as_secs_f64(),
+                             result.failures.is_empty(), result.failures.join(";"), result.inconclusive.join(";"));
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(parse_list::<usize>("3"), Ok(vec![3]));
+        assert_eq!(parse_list::<usize>("2,4..6"), Ok(vec![2, 4, 5, 6]));
+        assert_eq!(parse_list::<u64>(" 1 , 2 "), Ok(vec![1, 2]));
+        assert!(parse_list::<usize>("3,x").is_err());
+        assert!(parse_list::<usize>("5..2").is_err());
+    }
+
+    #[test]
+    fn test_grid_and_parallel_run() {
+        let points = grid(&[1, 3], &[1, 2], &[0, 2], &[None, Some(20)]);
+        // One validator cannot have two Byzantine ones
+        assert_eq!(points.len(), 4 + 8);
+        assert!(points.iter().all(|point| point.byzantine <= point.validators));
+
+        let report = run(&points, 4, |point| SweepResult {
+            point: *point,
+            states: point.validators * 10,
+            elapsed: Duration::ZERO,
+            failures: if point.byzantine > 1 { vec!["safety".to_string()] } else { Vec::new() },
+            inconclusive: Vec::new(),
+        });
+        let ordered: Vec<SweepPoint> = report.results.iter().map(|result| result.point).collect();
+        assert_eq!(ordered, points);
+        assert!(!report.passed());
+        let csv = report.to_csv();
+        assert_eq!(csv.lines().count(), points.len() + 1);
+        assert!(csv.contains("3,1,2,20,30,0.000,false,safety"));
+    }
+}
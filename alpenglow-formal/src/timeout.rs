@@ -2,14 +2,28 @@
 //! This module provides a Stateright-based formal model for verifying timeout mechanisms,
 //! skip certificate generation, and BadWindow flag management.
 
-use stateright::{Model, Property, Checker};
-use std::collections::{BTreeMap, BTreeSet};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use stateright::{Checker, Expectation, Model, Property};
+use std::collections::{btree_map::Entry, BTreeMap, BTreeSet};
+use crate::network::{NetworkFault, NetworkFaults};
+use crate::voters::VoterSet;
+use crate::shared::SharedVec;
+use crate::bounds::Bounded;
+use crate::limits::Outcome;
+use crate::options::CheckOptions;
+use crate::invariants;
 
 // --- Formal Model Configuration ---
 const SKIP_CERTIFICATE_THRESHOLD_PERCENT: u64 = 60;
 const TOTAL_STAKE: u64 = 1000;
-const MAX_SLOTS: u64 = 5; // Formal verification limit
-const MAX_VALIDATORS: usize = 5; // Formal verification limit
+const SLOT_DURATION_TICKS: u64 = 2; // Local clock ticks per slot
+pub const CLOCK_SKEW_BOUND: u64 = 1; // Skew tolerated by the liveness argument
+const FAIR_DELIVERY_BOUND: u64 = 2; // Deliveries the oldest message in flight may be overtaken by
+
+/// Properties that rest on fair delivery rather than on the protocol's safety alone
+const FAIR_DELIVERY_PROPERTIES: [&str; 2] = ["no_premature_skip", "skip_certificate_propagation"];
 
 // Type aliases for clarity
 type Slot = u64;
@@ -42,6 +56,12 @@ pub enum TimeoutMessage {
         slot: Slot,
         validator: ActorId,
     },
+    /// A skip certificate, with its aggregated skip votes, forwarded by a validator that formed it
+    SkipCertificate {
+        slot: Slot,
+        holder: ActorId,
+        voters: VoterSet,
+    },
 }
 
 /// Represents messages in transit
@@ -66,8 +86,12 @@ pub enum TimeoutAction {
         slot: Slot,
         validator: ActorId,
     },
+    /// Advance a validator's local clock by one tick
+    Tick { validator: ActorId },
     /// Advance to the next slot
     AdvanceSlot,
+    /// The network adversary drops, duplicates or delays a message
+    NetworkFault { fault: NetworkFault<MessageInTransit> },
 }
 
 /// State of a validator in the timeout model
@@ -76,13 +100,17 @@ pub struct ValidatorState {
     /// Votes cast by this validator (slot -> hash or None for skip)
     votes_cast: BTreeMap<Slot, Option<Hash>>,
     /// Vote pool: (slot, hash) -> set of voters
-    vote_pool: BTreeMap<(Slot, Option<Hash>), BTreeSet<ActorId>>,
+    vote_pool: BTreeMap<(Slot, Option<Hash>), VoterSet>,
     /// Certificates formed: (slot, hash) pairs
     certificates: BTreeSet<(Slot, Option<Hash>)>,
+    /// Skip certificates held by this validator
+    skip_certificates: BTreeSet<Slot>,
     /// BadWindow flag state
     bad_window: bool,
     /// Current slot being processed
     current_slot: Slot,
+    /// Local clock in ticks
+    local_clock: u64,
 }
 
 /// Main state of the timeout formal model
@@ -90,14 +118,27 @@ pub struct ValidatorState {
 pub struct TimeoutState {
     /// Network messages in transit
     network: BTreeSet<MessageInTransit>,
+    /// Adversarial control over message delivery
+    network_faults: NetworkFaults<MessageInTransit>,
     /// Per-validator states
-    validators: Vec<ValidatorState>,
+    validators: SharedVec<ValidatorState>,
     /// Global current slot
     current_slot: Slot,
     /// Skip certificates formed: slot -> true if skip cert exists
     skip_certificates: BTreeMap<Slot, bool>,
+    /// Block certificates formed: slot -> certified hashes
+    block_certificates: BTreeMap<Slot, BTreeSet<Hash>>,
     /// Block proposals: slot -> hash
     block_proposals: BTreeMap<Slot, Hash>,
+    /// Proposer's local clock when each slot's block was proposed
+    proposed_at: BTreeMap<Slot, u64>,
+    /// Messages in flight to connected validators in the order they were sent, each with the
+    /// deliveries that overtook it, counted up to FAIR_DELIVERY_BOUND
+    delivery_order: Vec<(MessageInTransit, u64)>,
+    /// Deliveries since a skip certificate formed while a connected validator lacks it
+    skip_certificate_age: BTreeMap<Slot, u64>,
+    /// Messages sent up to the last copy of each lagging skip certificate when it formed
+    skip_certificate_backlog: BTreeMap<Slot, u64>,
 }
 
 /// Formal model for timeout handling and skip certificate generation
@@ -107,42 +148,111 @@ pub struct TimeoutModel {
     pub validator_count: usize,
     /// Maximum slots to explore
     pub max_slot: Slot,
+    /// Maximum difference between any two local clocks
+    pub max_clock_skew: u64,
+    /// Validators cut off from the network: they neither act nor receive messages
+    pub offline: BTreeSet<ActorId>,
+    /// Messages the network adversary may drop, duplicate or delay
+    pub network_faults: usize,
+    /// A connected validator's clock waits for the messages on their way to it, and the
+    /// oldest message in flight is delivered once FAIR_DELIVERY_BOUND later deliveries overtook it
+    pub fair_delivery: bool,
+}
+
+impl ValidatorState {
+    /// Check if this validator's pool holds enough skip votes for a certificate
+    fn can_form_skip_certificate(&self, slot: Slot, validator_count: usize) -> bool {
+        self.has_quorum(&(slot, None), validator_count)
+    }
+
+    /// Check if this validator's pool holds enough notar votes for a certificate
+    fn can_form_block_certificate(&self, slot: Slot, hash: Hash, validator_count: usize) -> bool {
+        self.has_quorum(&(slot, Some(hash)), validator_count)
+    }
+
+    fn has_quorum(&self, key: &(Slot, Option<Hash>), validator_count: usize) -> bool {
+        if let Some(voters) = self.vote_pool.get(key) {
+            let stake: Stake = voters.len() as u64 * (TOTAL_STAKE / validator_count as u64);
+            stake >= (TOTAL_STAKE * SKIP_CERTIFICATE_THRESHOLD_PERCENT / 100)
+        } else {
+            false
+        }
+    }
 }
 
 impl TimeoutState {
     fn new(validator_count: usize) -> Self {
         Self {
             network: BTreeSet::new(),
+            network_faults: NetworkFaults::new(0),
             validators: (0..validator_count).map(|_| ValidatorState {
                 votes_cast: BTreeMap::new(),
                 vote_pool: BTreeMap::new(),
                 certificates: BTreeSet::new(),
-            bad_window: false,
+                skip_certificates: BTreeSet::new(),
+                bad_window: false,
                 current_slot: 0,
+                local_clock: 0,
             }).collect(),
             current_slot: 0,
             skip_certificates: BTreeMap::new(),
+            block_certificates: BTreeMap::new(),
             block_proposals: BTreeMap::new(),
+            proposed_at: BTreeMap::new(),
+            delivery_order: Vec::new(),
+            skip_certificate_age: BTreeMap::new(),
+            skip_certificate_backlog: BTreeMap::new(),
         }
     }
 
-    /// Check if a skip certificate can be formed for a slot
-    fn can_form_skip_certificate(&self, slot: Slot) -> bool {
-        if let Some(voters) = self.validators[0].vote_pool.get(&(slot, None)) {
-            let stake: Stake = voters.len() as u64 * (TOTAL_STAKE / self.validators.len() as u64);
-            stake >= (TOTAL_STAKE * SKIP_CERTIFICATE_THRESHOLD_PERCENT / 100)
-        } else {
-            false
-        }
+    /// Skip certificate slots some connected validator has not received yet
+    fn lagging_skip_certificates(&self, offline: &BTreeSet<ActorId>) -> BTreeSet<Slot> {
+        self.skip_certificates.keys()
+            .filter(|slot| self.validators.iter().enumerate()
+                .any(|(id, v)| !offline.contains(&id) && !v.skip_certificates.contains(slot)))
+            .copied()
+            .collect()
     }
 
-    /// Check if a block certificate can be formed for a slot and hash
-    fn can_form_block_certificate(&self, slot: Slot, hash: Hash) -> bool {
-        if let Some(voters) = self.validators[0].vote_pool.get(&(slot, Some(hash))) {
-            let stake: Stake = voters.len() as u64 * (TOTAL_STAKE / self.validators.len() as u64);
-            stake >= (TOTAL_STAKE * SKIP_CERTIFICATE_THRESHOLD_PERCENT / 100)
-        } else {
-            false
+    /// Position just past the last copy of a skip certificate in the delivery order
+    fn skip_certificate_backlog(&self, slot: Slot) -> u64 {
+        self.delivery_order.iter()
+            .rposition(|(m, _)| matches!(m.msg, TimeoutMessage::SkipCertificate { slot: s, .. } if s == slot))
+            .map_or(0, |position| position as u64 + 1)
+    }
+
+    /// Slowest local clock across validators
+    fn min_local_clock(&self) -> u64 {
+        self.validators.iter().map(|v| v.local_clock).min().unwrap_or(0)
+    }
+
+}
+
+impl Bounded for TimeoutState {
+    fn current_slot(&self) -> Slot {
+        self.current_slot
+    }
+
+    fn in_flight(&self) -> usize {
+        self.network.len()
+    }
+}
+
+impl TimeoutModel {
+    /// Follow the network in the delivery order: drop what left it, count a delivery against
+    /// everything still in flight, and queue newly sent, released or replayed messages last
+    fn update_delivery_order(&self, state: &mut TimeoutState, delivered: bool) {
+        let network = &state.network;
+        state.delivery_order.retain(|(m, _)| network.contains(m));
+        if delivered {
+            for (_, overtaken) in &mut state.delivery_order {
+                *overtaken = (*overtaken + 1).min(FAIR_DELIVERY_BOUND);
+            }
+        }
+        for msg in network.iter().filter(|m| !self.offline.contains(&m.dst)) {
+            if !state.delivery_order.iter().any(|(m, _)| m == msg) {
+                state.delivery_order.push((msg.clone(), 0));
+            }
         }
     }
 }
@@ -152,17 +262,35 @@ impl Model for TimeoutModel {
     type Action = TimeoutAction;
 
     fn init_states(&self) -> Vec<Self::State> {
-        vec![TimeoutState::new(self.validator_count)]
+        let mut state = TimeoutState::new(self.validator_count);
+        state.network_faults = NetworkFaults::new(self.network_faults);
+        vec![state]
     }
 
     fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
-        // 1. Deliver any message in the network
-        for msg in &state.network {
+        // 0. Under fair delivery the oldest message in flight, once overtaken by
+        //    FAIR_DELIVERY_BOUND deliveries, arrives before anything else happens
+        if let Some((msg, _)) = state.delivery_order.first()
+            .filter(|(_, overtaken)| self.fair_delivery && *overtaken >= FAIR_DELIVERY_BOUND)
+        {
             actions.push(TimeoutAction::DeliverMessage { msg: msg.clone() });
+            return;
+        }
+
+        // 1. Deliver any message in the network to connected validators
+        for msg in &state.network {
+            if !self.offline.contains(&msg.dst) {
+                actions.push(TimeoutAction::DeliverMessage { msg: msg.clone() });
+            }
+        }
+
+        // The network adversary drops, duplicates or delays a bounded number of messages
+        for fault in state.network_faults.faults(&state.network) {
+            actions.push(TimeoutAction::NetworkFault { fault });
         }
 
         // 2. Propose blocks for current and future slots
-        for proposer_id in 0..self.validator_count {
+        for proposer_id in (0..self.validator_count).filter(|id| !self.offline.contains(id)) {
             for slot in state.current_slot..=self.max_slot {
                 if !state.block_proposals.contains_key(&slot) {
                     actions.push(TimeoutAction::ProposeBlock {
@@ -173,17 +301,36 @@ impl Model for TimeoutModel {
             }
         }
 
-        // 3. Trigger timeouts for any slot
-        for validator_id in 0..self.validator_count {
+        // 3. Trigger timeouts for slots whose deadline passed on the local clock
+        for validator_id in (0..self.validator_count).filter(|id| !self.offline.contains(id)) {
             for slot in 1..=self.max_slot {
-                actions.push(TimeoutAction::TriggerTimeout {
-                    slot,
-                    validator: validator_id,
-                });
+                if state.validators[validator_id].local_clock >= slot * SLOT_DURATION_TICKS {
+                    actions.push(TimeoutAction::TriggerTimeout {
+                        slot,
+                        validator: validator_id,
+                    });
+                }
+            }
+        }
+
+        // 4. Advance local clocks within the skew bound. Under fair delivery a
+        //    connected validator's clock waits for messages still on their way to
+        //    it, held back ones included
+        let min_clock = state.min_local_clock();
+        for (validator, validator_state) in state.validators.iter().enumerate() {
+            let next_clock = validator_state.local_clock + 1;
+            let awaiting = self.fair_delivery
+                && !self.offline.contains(&validator)
+                && state.network.iter().chain(state.network_faults.delayed()).any(|m| m.dst == validator);
+            if next_clock <= (self.max_slot + 1) * SLOT_DURATION_TICKS
+                && next_clock - min_clock <= self.max_clock_skew
+                && !awaiting
+            {
+                actions.push(TimeoutAction::Tick { validator });
             }
         }
 
-        // 4. Advance to next slot
+        // 5. Advance to next slot
         if state.current_slot < self.max_slot {
             actions.push(TimeoutAction::AdvanceSlot);
         }
@@ -192,38 +339,43 @@ impl Model for TimeoutModel {
     fn next_state(&self, last_state: &Self::State, action: Self::Action) -> Option<Self::State> {
         let mut next_state = last_state.clone();
         let mut validators = last_state.validators.clone();
+        let delivered = matches!(action, TimeoutAction::DeliverMessage { .. });
 
         match action {
             TimeoutAction::ProposeBlock { slot, proposer } => {
                 let block_hash = slot * 1000 + proposer as u64; // Deterministic hash
                 next_state.block_proposals.insert(slot, block_hash);
+                next_state.proposed_at.insert(slot, last_state.validators[proposer].local_clock);
 
-                // Broadcast block proposal to all validators
+                // Broadcast block proposal to all validators, including the
+                // leader itself so that it notar-votes its own block
                 for i in 0..self.validator_count {
-                    if i != proposer {
-                        next_state.network.insert(MessageInTransit {
-                            dst: i,
-                            msg: TimeoutMessage::BlockProposal {
-                                slot,
-                                hash: block_hash,
-                                proposer,
-                            },
-                        });
-                    }
+                    next_state.network.insert(MessageInTransit {
+                        dst: i,
+                        msg: TimeoutMessage::BlockProposal {
+                            slot,
+                            hash: block_hash,
+                            proposer,
+                        },
+                    });
                 }
             }
+            TimeoutAction::NetworkFault { fault } => {
+                if !next_state.network_faults.apply(&mut next_state.network, fault) { return None; }
+            }
             TimeoutAction::DeliverMessage { msg } => {
                 let recipient_id = msg.dst;
                 let mut validator_state = validators[recipient_id].clone();
 
                 // Remove message from network
                 if !next_state.network.remove(&msg) { return None; }
+                next_state.network_faults.record_delivery(&msg);
 
                 match msg.msg {
                     TimeoutMessage::BlockProposal { slot, hash, proposer: _ } => {
                         // Validator receives block and can vote for it
-                        if !validator_state.votes_cast.contains_key(&slot) {
-                            validator_state.votes_cast.insert(slot, Some(hash));
+                        if let Entry::Vacant(entry) = validator_state.votes_cast.entry(slot) {
+                            entry.insert(Some(hash));
                             
                             // Broadcast NotarVote
                             for i in 0..self.validator_count {
@@ -245,8 +397,9 @@ impl Model for TimeoutModel {
                         voters.insert(voter);
 
                         // Check for block certificate formation
-                        if next_state.can_form_block_certificate(slot, hash) {
+                        if validator_state.can_form_block_certificate(slot, hash, self.validator_count) {
                             validator_state.certificates.insert((slot, Some(hash)));
+                            next_state.block_certificates.entry(slot).or_default().insert(hash);
                         }
                     }
                     TimeoutMessage::SkipVote { slot, voter } => {
@@ -256,18 +409,41 @@ impl Model for TimeoutModel {
                         voters.insert(voter);
 
                         // Check for skip certificate formation
-                        if next_state.can_form_skip_certificate(slot) {
+                        if validator_state.can_form_skip_certificate(slot, self.validator_count)
+                            && validator_state.skip_certificates.insert(slot)
+                        {
                             validator_state.certificates.insert((slot, None));
                             next_state.skip_certificates.insert(slot, true);
                             
                             // Set BadWindow flag
                             validator_state.bad_window = true;
+
+                            // Forward the certificate so that every validator learns of it
+                            let voters = validator_state.vote_pool[&(slot, None)].clone();
+                            for i in (0..self.validator_count).filter(|i| *i != recipient_id) {
+                                next_state.network.insert(MessageInTransit {
+                                    dst: i,
+                                    msg: TimeoutMessage::SkipCertificate {
+                                        slot,
+                                        holder: recipient_id,
+                                        voters: voters.clone(),
+                                    },
+                                });
+                            }
+                        }
+                    }
+                    TimeoutMessage::SkipCertificate { slot, holder: _, voters } => {
+                        // Adopt a certificate formed elsewhere along with the votes backing it
+                        validator_state.vote_pool.entry((slot, None)).or_default().extend(voters.iter());
+                        if validator_state.skip_certificates.insert(slot) {
+                            validator_state.certificates.insert((slot, None));
+                            validator_state.bad_window = true;
                         }
                     }
                     TimeoutMessage::TimeoutEvent { slot, validator: _ } => {
                         // Timeout occurred - validator can cast skip vote
-                        if !validator_state.votes_cast.contains_key(&slot) {
-                            validator_state.votes_cast.insert(slot, None);
+                        if let Entry::Vacant(entry) = validator_state.votes_cast.entry(slot) {
+                            entry.insert(None);
                             
                             // Broadcast SkipVote
                             for i in 0..self.validator_count {
@@ -291,6 +467,9 @@ impl Model for TimeoutModel {
                     msg: TimeoutMessage::TimeoutEvent { slot, validator },
                 });
             }
+            TimeoutAction::Tick { validator } => {
+                validators[validator].local_clock += 1;
+            }
             TimeoutAction::AdvanceSlot => {
                 next_state.current_slot += 1;
                 for validator_state in &mut validators {
@@ -300,6 +479,18 @@ impl Model for TimeoutModel {
         }
 
         next_state.validators = validators;
+        self.update_delivery_order(&mut next_state, delivered);
+        if delivered {
+            // Age every certificate still missing at a connected validator
+            let lagging = next_state.lagging_skip_certificates(&self.offline);
+            next_state.skip_certificate_age.retain(|slot, _| lagging.contains(slot));
+            next_state.skip_certificate_backlog.retain(|slot, _| lagging.contains(slot));
+            for slot in lagging {
+                *next_state.skip_certificate_age.entry(slot).or_default() += 1;
+                let backlog = next_state.skip_certificate_backlog(slot);
+                next_state.skip_certificate_backlog.entry(slot).or_insert(backlog);
+            }
+        }
         Some(next_state)
     }
 
@@ -346,7 +537,7 @@ impl Model for TimeoutModel {
             Property::<Self>::always("certificate_threshold", |model, state| {
                 for validator in &state.validators {
                     for (slot, hash_opt) in &validator.certificates {
-                        let voters = validator.vote_pool.get(&(*slot, hash_opt.clone()));
+                        let voters = validator.vote_pool.get(&(*slot, *hash_opt));
                         if let Some(voter_set) = voters {
                             let stake: Stake = voter_set.len() as u64 * (TOTAL_STAKE / model.validator_count as u64);
                             if stake < (TOTAL_STAKE * SKIP_CERTIFICATE_THRESHOLD_PERCENT / 100) {
@@ -357,60 +548,448 @@ impl Model for TimeoutModel {
                 }
                 true
             }),
+
+            // Property 5: Global skip certificate uniqueness across validators
+            Property::<Self>::always("global_skip_certificate_uniqueness", |_model, state| {
+                // No validator may hold a skip certificate for a slot in which
+                // another validator holds a block certificate
+                for holder in &state.validators {
+                    for slot in &holder.skip_certificates {
+                        let conflicting_block_cert = state.validators.iter().any(|other| {
+                            other.certificates.iter()
+                                .any(|(s, hash_opt)| s == slot && hash_opt.is_some())
+                        });
+                        if conflicting_block_cert {
+                            return false;
+                        }
+                    }
+                }
+                true
+            }),
+
+            // Property 6: No validator skip-votes a slot whose on-time block is still on its way to it
+            Property::<Self>::always("no_premature_skip", |_model, state| {
+                // A block proposed more than CLOCK_SKEW_BOUND ticks before the slot's
+                // deadline on its proposer's clock must beat every recipient's timeout.
+                // That takes fair delivery, and fails once skew exceeds the bound.
+                state.network.iter().all(|m| match m.msg {
+                    TimeoutMessage::BlockProposal { slot, .. } => {
+                        let on_time = state.proposed_at[&slot] + CLOCK_SKEW_BOUND < slot * SLOT_DURATION_TICKS;
+                        !on_time || state.validators[m.dst].votes_cast.get(&slot) != Some(&None)
+                    }
+                    _ => true,
+                })
+            }),
+
+            // Property 7: A slot never has both a block certificate and a skip certificate
+            Property::<Self>::always("notar_skip_exclusion", |_model, state| {
+                // Honest validators vote notar or skip depending on message order,
+                // so both certificate kinds racing for the same slot must be ruled out
+                state.skip_certificates.keys()
+                    .all(|slot| !state.block_certificates.contains_key(slot))
+            }),
+
+            // Property 8: Skip certificates reach every connected validator within a bounded number of deliveries
+            Property::<Self>::always("skip_certificate_propagation", |model, state| {
+                // Leader-window logic downstream relies on all honest validators
+                // agreeing on skipped slots soon after the first one does. Under fair
+                // delivery the last copy of a certificate waits for FAIR_DELIVERY_BOUND
+                // deliveries, then for the messages sent before it. Anything the network
+                // adversary did reorders the queue.
+                if state.network_faults.remaining() < model.network_faults {
+                    return true;
+                }
+                state.skip_certificate_age.iter()
+                    .all(|(slot, age)| *age <= FAIR_DELIVERY_BOUND + state.skip_certificate_backlog[slot])
+            }),
+
+            // Property 9: Validators certifying a slot all hold the same block or skip certificate
+            Property::<Self>::always("certificate_agreement", |_model, state| {
+                invariants::certificate_agreement(state.validators.iter().map(|v| v.certificates.iter().copied()))
+            }),
         ]
     }
 }
 
 /// Run formal verification of timeout handling
-pub fn run_formal_verification() {
+pub fn run_formal_verification(options: &CheckOptions) {
     println!("=== Timeout Handling Formal Verification ===");
     
     let model = TimeoutModel {
         validator_count: 3, // Small for formal verification
         max_slot: 3,
+        max_clock_skew: CLOCK_SKEW_BOUND,
+        offline: BTreeSet::new(),
+        network_faults: 0,
+        fair_delivery: true,
     };
 
     println!("Model checking timeout handling with {} validators, {} slots", 
              model.validator_count, model.max_slot);
     
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
+    options.print_summary();
+
+    let result = options
+        .checker(model)
         .spawn_dfs()
         .report(&mut stateright::report::WriteReporter::new(&mut std::io::stdout()));
     
     // Check verification results
-    if result.discoveries().is_empty() {
-        println!("✅ All timeout properties verified successfully");
-    } else {
-        println!("❌ Timeout verification found counterexamples");
-        for (property_name, _path) in result.discoveries() {
-            println!("  - {}", property_name);
-        }
+    match options.print_outcomes(&result) {
+        Outcome::Proved => println!("✅ All timeout properties verified successfully"),
+        Outcome::Violated => println!("❌ Timeout verification found counterexamples"),
+        Outcome::Inconclusive => println!("❓ Timeout verification inconclusive: a limit or bound stopped the search first"),
     }
+    options.record_results("timeout formal", &result);
 }
 
 /// Test timeout model with different configurations
-pub fn test_timeout_model(validators: usize, slots: u64) {
-    println!("Testing timeout model with {} validators, {} slots", validators, slots);
+pub fn test_timeout_model(validators: usize, slots: u64, clock_skew: u64, options: &CheckOptions) {
+    println!("Testing timeout model with {} validators, {} slots, clock skew {}", validators, slots, clock_skew);
     
     let model = TimeoutModel {
         validator_count: validators,
         max_slot: slots,
+        max_clock_skew: clock_skew,
+        offline: BTreeSet::new(),
+        network_faults: 0,
+        fair_delivery: true,
     };
 
-    let result = model
-        .checker()
-        .threads(num_cpus::get())
+    // Past the skew bound on-time blocks may lose the race against timeouts;
+    // only the safety properties are expected to hold
+    let mut options = options.clone();
+    if clock_skew > CLOCK_SKEW_BOUND {
+        println!("Clock skew exceeds the bound of {}: skipping no_premature_skip", CLOCK_SKEW_BOUND);
+        options.properties.skip.push("no_premature_skip".to_string());
+    }
+    options.print_summary();
+
+    let result = options
+        .checker(model)
         .spawn_dfs();
+    options.progress.watch(&result);
+    let result = result.join();
     
     println!("States explored: {}", result.state_count());
-    println!("Properties verified: {}", result.discoveries().is_empty());
+    options.print_outcomes(&result);
+    options.print_coverage(&result);
+    options.record_results(&format!("timeout {}v {}s skew {}", validators, slots, clock_skew), &result);
+}
+
+// --- Randomized Simulation ---
+
+const MAX_SIMULATION_STEPS: usize = 10_000;
+const DEFAULT_TIMEOUT_MS: u64 = 100;
+const DEFAULT_NETWORK_DELAY_MS: u64 = 10;
+
+/// Parameters of a single randomized run of the timeout model
+#[derive(Clone, Debug)]
+pub struct SimulationConfig {
+    /// Number of validators
+    pub validators: usize,
+    /// Slots to simulate
+    pub slots: Slot,
+    /// Local timeout per slot in milliseconds
+    pub timeout_ms: u64,
+    /// Upper bound on message delivery delay in milliseconds
+    pub delay_ms: u64,
+    /// Validators that never act and never receive messages
+    pub offline: BTreeSet<ActorId>,
+    /// Slots whose leader withholds its proposal
+    pub silent_slots: BTreeSet<Slot>,
+    /// Seed for scheduling and delay choices
+    pub seed: u64,
+}
+
+impl SimulationConfig {
+    fn new(validators: usize, slots: Slot, seed: u64) -> Self {
+        Self {
+            validators,
+            slots,
+            timeout_ms: DEFAULT_TIMEOUT_MS,
+            delay_ms: DEFAULT_NETWORK_DELAY_MS,
+            offline: BTreeSet::new(),
+            silent_slots: BTreeSet::new(),
+            seed,
+        }
+    }
+
+    /// Round-robin leader of a slot
+    fn leader(&self, slot: Slot) -> ActorId {
+        slot as usize % self.validators
+    }
+}
+
+/// Final state and observations of a simulation run
+#[derive(Clone, Debug)]
+pub struct SimulationOutcome {
+    /// State reached when the run went quiescent
+    pub state: TimeoutState,
+    /// Model actions taken
+    pub steps: usize,
+    /// Simulated wall-clock time in milliseconds
+    pub elapsed_ms: u64,
+    /// Safety properties violated along the run
+    pub violations: Vec<&'static str>,
+}
+
+impl SimulationOutcome {
+    fn skip_certified(&self, slot: Slot) -> bool {
+        self.state.skip_certificates.contains_key(&slot)
+    }
+
+    fn block_certified(&self, slot: Slot) -> bool {
+        self.state.block_certificates.contains_key(&slot)
+    }
+
+    fn resolved(&self, slot: Slot) -> bool {
+        self.skip_certified(slot) || self.block_certified(slot)
+    }
+
+    fn print_summary(&self, slots: Slot) {
+        println!("Steps: {}, simulated time: {}ms", self.steps, self.elapsed_ms);
+        for slot in 1..=slots {
+            let outcome = if self.block_certified(slot) {
+                "block certificate"
+            } else if self.skip_certified(slot) {
+                "skip certificate"
+            } else {
+                "undecided"
+            };
+            println!("  Slot {}: {}", slot, outcome);
+        }
+        for property_name in &self.violations {
+            println!("  ❌ Violated: {}", property_name);
+        }
+    }
+}
+
+/// Drive the timeout model with simulated wall-clock time and random scheduling.
+///
+/// Validator clocks tick every `timeout_ms / SLOT_DURATION_TICKS`, messages land
+/// after a random delay of at most `delay_ms`, and among the actions that are
+/// ready at the current instant one is picked at random. Every state reached is
+/// checked against the model's safety properties.
+pub fn simulate(config: &SimulationConfig) -> SimulationOutcome {
+    let model = TimeoutModel {
+        validator_count: config.validators,
+        max_slot: config.slots,
+        max_clock_skew: CLOCK_SKEW_BOUND,
+        offline: config.offline.clone(),
+        network_faults: 0,
+        // Clocks and deliveries follow simulated time instead, so only the
+        // properties that do not rest on fair delivery are checked
+        fair_delivery: false,
+    };
+    let properties = model.properties();
+    let tick_ms = (config.timeout_ms / SLOT_DURATION_TICKS).max(1);
+    let max_clock = (config.slots + 1) * SLOT_DURATION_TICKS;
+    let mut rng = StdRng::seed_from_u64(config.seed);
+
+    let mut state = TimeoutState::new(config.validators);
+    let mut deliver_at: BTreeMap<MessageInTransit, u64> = BTreeMap::new();
+    let mut violations = Vec::new();
+    let mut now = 0;
+    let mut steps = 0;
+
+    while steps < MAX_SIMULATION_STEPS {
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+
+        let min_clock = state.min_local_clock();
+        let ready: Vec<TimeoutAction> = actions.into_iter().filter(|action| match action {
+            TimeoutAction::ProposeBlock { slot, proposer } => {
+                *slot >= 1
+                    && *proposer == config.leader(*slot)
+                    && !config.silent_slots.contains(slot)
+                    && state.validators[*proposer].local_clock >= (slot - 1) * SLOT_DURATION_TICKS
+            }
+            TimeoutAction::DeliverMessage { msg } => {
+                deliver_at.get(msg).is_some_and(|t| *t <= now)
+            }
+            TimeoutAction::TriggerTimeout { slot, validator } => {
+                let event = MessageInTransit {
+                    dst: *validator,
+                    msg: TimeoutMessage::TimeoutEvent { slot: *slot, validator: *validator },
+                };
+                !state.validators[*validator].votes_cast.contains_key(slot)
+                    && !state.network.contains(&event)
+            }
+            // Clocks follow simulated time, including those of offline validators
+            TimeoutAction::Tick { validator } => state.validators[*validator].local_clock < now / tick_ms,
+            TimeoutAction::AdvanceSlot => state.current_slot * SLOT_DURATION_TICKS <= min_clock,
+            // Delivery is driven by simulated delays rather than the network adversary
+            TimeoutAction::NetworkFault { .. } => false,
+        }).collect();
+
+        if ready.is_empty() {
+            // Nothing to do at this instant: jump to the next tick or delivery
+            let next_tick = (state.validators.iter().any(|v| v.local_clock < max_clock))
+                .then(|| (now / tick_ms + 1) * tick_ms);
+            let next_delivery = deliver_at.iter()
+                .filter(|(msg, t)| **t > now && !config.offline.contains(&msg.dst))
+                .map(|(_, t)| *t)
+                .min();
+            match next_tick.into_iter().chain(next_delivery).min() {
+                Some(next) => now = next,
+                None => break,
+            }
+            continue;
+        }
+
+        // The global slot follows the slowest clock before anything else happens
+        let action = if ready.contains(&TimeoutAction::AdvanceSlot) {
+            TimeoutAction::AdvanceSlot
+        } else {
+            ready[rng.gen_range(0..ready.len())].clone()
+        };
+        if let TimeoutAction::DeliverMessage { msg } = &action {
+            deliver_at.remove(msg);
+        }
+        state = match model.next_state(&state, action) {
+            Some(next_state) => next_state,
+            None => break,
+        };
+        steps += 1;
+
+        for msg in &state.network {
+            if !deliver_at.contains_key(msg) {
+                let delay = match msg.msg {
+                    TimeoutMessage::TimeoutEvent { .. } => 0, // Local event
+                    _ => rng.gen_range(0..=config.delay_ms),
+                };
+                deliver_at.insert(msg.clone(), now + delay);
+            }
+        }
+
+        for property in &properties {
+            if property.expectation == Expectation::Always
+                && !FAIR_DELIVERY_PROPERTIES.contains(&property.name)
+                && !(property.condition)(&model, &state)
+                && !violations.contains(&property.name)
+            {
+                violations.push(property.name);
+            }
+        }
+    }
+
+    SimulationOutcome { state, steps, elapsed_ms: now, violations }
+}
+
+/// Simulate timeout handling with honest leaders; every slot must be decided safely
+pub fn run_simulation(validators: usize, slots: Slot, timeout_ms: u64, seed: u64) -> bool {
+    println!("Simulating timeout handling: {} validators, {} slots, {}ms timeout, seed {}",
+             validators, slots, timeout_ms, seed);
+
+    let config = SimulationConfig { timeout_ms, ..SimulationConfig::new(validators, slots, seed) };
+    let outcome = simulate(&config);
+    outcome.print_summary(slots);
+
+    outcome.violations.is_empty() && (1..=slots).all(|slot| outcome.resolved(slot))
+}
+
+/// A silent leader must lead every validator to skip-vote and certify the skip
+pub fn test_skip_certificate_generation(validators: usize, seed: u64) -> bool {
+    println!("Simulating skip certificate generation: {} validators, seed {}", validators, seed);
+
+    let mut config = SimulationConfig::new(validators, 1, seed);
+    config.silent_slots.insert(1);
+    let outcome = simulate(&config);
+    outcome.print_summary(1);
+
+    let all_skip_voted = outcome.state.validators.iter()
+        .all(|v| v.votes_cast.get(&1) == Some(&None));
+    outcome.violations.is_empty()
+        && outcome.skip_certified(1)
+        && !outcome.block_certified(1)
+        && all_skip_voted
+}
+
+/// BadWindow must be raised exactly at the validators holding a skip certificate
+pub fn test_badwindow_triggering(validators: usize, seed: u64) -> bool {
+    println!("Simulating BadWindow triggering: {} validators, seed {}", validators, seed);
+
+    let mut config = SimulationConfig::new(validators, 2, seed);
+    config.silent_slots.insert(1);
+    let outcome = simulate(&config);
+    outcome.print_summary(2);
+
+    let flagged = outcome.state.validators.iter().filter(|v| v.bad_window).count();
+    println!("Validators with BadWindow set: {}", flagged);
+
+    outcome.violations.is_empty()
+        && flagged > 0
+        && outcome.state.validators.iter()
+            .all(|v| v.bad_window != v.skip_certificates.is_empty())
+}
+
+/// Random delivery delays up to `delay_ms` must not break safety or stall any slot
+pub fn test_network_delay_handling(validators: usize, slots: Slot, delay_ms: u64, seed: u64) -> bool {
+    println!("Simulating network delay: {} validators, {} slots, {}ms delay, seed {}",
+             validators, slots, delay_ms, seed);
+
+    let config = SimulationConfig { delay_ms, ..SimulationConfig::new(validators, slots, seed) };
+    let outcome = simulate(&config);
+    outcome.print_summary(slots);
+
+    outcome.violations.is_empty() && (1..=slots).all(|slot| outcome.resolved(slot))
+}
+
+/// After a skipped slot the next honest leader's block must still be certified
+pub fn test_timeout_recovery(validators: usize, seed: u64) -> bool {
+    println!("Simulating timeout recovery: {} validators, seed {}", validators, seed);
+
+    let mut config = SimulationConfig::new(validators, 2, seed);
+    config.silent_slots.insert(1);
+    let outcome = simulate(&config);
+    outcome.print_summary(2);
+
+    outcome.violations.is_empty() && outcome.skip_certified(1) && outcome.block_certified(2)
+}
+
+/// Timeouts in every slot at once must each yield a skip certificate at every validator
+pub fn test_concurrent_timeouts(validators: usize, slots: Slot, seed: u64) -> bool {
+    println!("Simulating concurrent timeouts: {} validators, {} slots, seed {}", validators, slots, seed);
+
+    let mut config = SimulationConfig::new(validators, slots, seed);
+    config.silent_slots.extend(1..=slots);
+    let outcome = simulate(&config);
+    outcome.print_summary(slots);
+
+    outcome.violations.is_empty()
+        && outcome.state.validators.iter()
+            .all(|v| (1..=slots).all(|slot| v.skip_certificates.contains(&slot)))
+}
+
+/// With a random subset of validators offline, slots are decided exactly when the
+/// online stake still reaches the certificate threshold
+pub fn test_partial_network_handling(validators: usize, slots: Slot, offline_percent: u64, seed: u64) -> bool {
+    let mut config = SimulationConfig::new(validators, slots, seed);
+    let offline_count = (validators as u64 * offline_percent / 100) as usize;
+    let mut ids: Vec<ActorId> = (0..validators).collect();
+    ids.shuffle(&mut StdRng::seed_from_u64(seed));
+    config.offline.extend(ids.into_iter().take(offline_count));
+
+    println!("Simulating partial network: {} validators, {} slots, offline {:?}, seed {}",
+             validators, slots, config.offline, seed);
+
+    let outcome = simulate(&config);
+    outcome.print_summary(slots);
+
+    let online_stake = (validators - offline_count) as u64 * (TOTAL_STAKE / validators as u64);
+    let quorum_online = online_stake >= TOTAL_STAKE * SKIP_CERTIFICATE_THRESHOLD_PERCENT / 100;
+    println!("Online stake: {}/{} (quorum {})", online_stake, TOTAL_STAKE,
+             if quorum_online { "available" } else { "unavailable" });
+
+    outcome.violations.is_empty()
+        && (1..=slots).all(|slot| outcome.resolved(slot) == quorum_online)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bounds::ExplorationBounds;
 
     #[test]
     fn test_timeout_state_creation() {
@@ -431,7 +1010,8 @@ mod tests {
         voters.insert(2); // 3/3 validators = 100% > 60%
         state.validators[0] = validator;
         
-        assert!(state.can_form_skip_certificate(1));
+        assert!(state.validators[0].can_form_skip_certificate(1, 3));
+        assert!(!state.validators[1].can_form_skip_certificate(1, 3));
     }
 
     #[test]
@@ -446,4 +1026,193 @@ mod tests {
         // BadWindow should be consistent with skip certificates
         assert!(state.validators[0].bad_window);
     }
+
+    #[test]
+    fn test_global_skip_certificate_conflict() {
+        let model = TimeoutModel { validator_count: 3, max_slot: 3, max_clock_skew: CLOCK_SKEW_BOUND, offline: BTreeSet::new(), network_faults: 0, fair_delivery: true };
+        let property = model.property("global_skip_certificate_uniqueness");
+        let mut state = TimeoutState::new(3);
+
+        state.validators[0].skip_certificates.insert(1);
+        state.validators[0].certificates.insert((1, None));
+        assert!((property.condition)(&model, &state));
+
+        // A block certificate for the same slot at another validator conflicts
+        state.validators[1].certificates.insert((1, Some(1000)));
+        assert!(!(property.condition)(&model, &state));
+    }
+
+    #[test]
+    fn test_notar_skip_exclusion() {
+        let model = TimeoutModel { validator_count: 3, max_slot: 3, max_clock_skew: CLOCK_SKEW_BOUND, offline: BTreeSet::new(), network_faults: 0, fair_delivery: true };
+        let property = model.property("notar_skip_exclusion");
+        let mut state = TimeoutState::new(3);
+
+        state.block_certificates.entry(1).or_default().insert(1000);
+        state.skip_certificates.insert(2, true);
+        assert!((property.condition)(&model, &state));
+
+        state.skip_certificates.insert(1, true);
+        assert!(!(property.condition)(&model, &state));
+    }
+
+    #[test]
+    fn test_certificate_agreement() {
+        let model = TimeoutModel { validator_count: 3, max_slot: 3, max_clock_skew: CLOCK_SKEW_BOUND, offline: BTreeSet::new(), network_faults: 0, fair_delivery: true };
+        let property = model.property("certificate_agreement");
+        let mut state = TimeoutState::new(3);
+
+        state.validators[0].certificates.insert((1, Some(1000)));
+        state.validators[1].certificates.insert((1, Some(1000)));
+        state.validators[1].certificates.insert((2, None));
+        assert!((property.condition)(&model, &state));
+
+        // Validators disagreeing on a slot, even one skipping it, is caught
+        state.validators[2].certificates.insert((1, None));
+        assert!(!(property.condition)(&model, &state));
+    }
+
+    #[test]
+    fn test_clock_dependent_timeouts() {
+        let model = TimeoutModel { validator_count: 2, max_slot: 2, max_clock_skew: CLOCK_SKEW_BOUND, offline: BTreeSet::new(), network_faults: 0, fair_delivery: true };
+        let mut state = TimeoutState::new(2);
+        state.validators[0].local_clock = SLOT_DURATION_TICKS;
+        state.validators[1].local_clock = SLOT_DURATION_TICKS - 1;
+
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        // Only the validator whose clock passed the deadline may time out slot 1
+        assert!(actions.contains(&TimeoutAction::TriggerTimeout { slot: 1, validator: 0 }));
+        assert!(!actions.contains(&TimeoutAction::TriggerTimeout { slot: 1, validator: 1 }));
+        // The faster clock cannot run further ahead than the skew bound
+        assert!(!actions.contains(&TimeoutAction::Tick { validator: 0 }));
+        assert!(actions.contains(&TimeoutAction::Tick { validator: 1 }));
+    }
+
+    #[test]
+    fn test_premature_skip_needs_bounded_skew() {
+        let model = |max_clock_skew| TimeoutModel {
+            validator_count: 2,
+            max_slot: 1,
+            max_clock_skew,
+            offline: BTreeSet::new(),
+            network_faults: 0,
+            fair_delivery: true,
+        };
+        // A block still on its way to a validator and that validator's timeout fit in two messages
+        let bounds = ExplorationBounds { max_in_flight: Some(2), ..Default::default() };
+
+        let checker = bounds.checker(model(CLOCK_SKEW_BOUND)).spawn_bfs().join();
+        assert!(checker.is_done());
+        assert!(checker.discovery("no_premature_skip").is_none());
+        assert!(checker.discovery("skip_certificate_propagation").is_none());
+
+        // One tick more lets a validator time out while a block proposed on time is
+        // still in flight, yet votes and certificates stay consistent
+        let checker = bounds.checker(model(CLOCK_SKEW_BOUND + 1)).spawn_bfs().join();
+        assert!(checker.is_done());
+        assert!(checker.discovery("no_premature_skip").is_some());
+        for name in ["vote_uniqueness", "notar_skip_exclusion", "certificate_agreement"] {
+            assert!(checker.discovery(name).is_none(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_simulated_timeout_scenarios() {
+        for seed in 0..5 {
+            assert!(test_skip_certificate_generation(3, seed));
+            assert!(test_timeout_recovery(3, seed));
+            // Two of three validators offline leaves no quorum: nothing may be certified
+            assert!(test_partial_network_handling(3, 2, 70, seed));
+        }
+
+        // A silent leader in every slot yields no block certificates
+        let mut config = SimulationConfig::new(3, 2, 7);
+        config.silent_slots.extend([1, 2]);
+        let outcome = simulate(&config);
+        assert!(outcome.violations.is_empty());
+        assert!(outcome.state.block_certificates.is_empty());
+    }
+
+    #[test]
+    fn test_fair_delivery_order() {
+        let model = TimeoutModel { validator_count: 3, max_slot: 1, max_clock_skew: CLOCK_SKEW_BOUND, offline: BTreeSet::new(), network_faults: 0, fair_delivery: true };
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, TimeoutAction::ProposeBlock { slot: 1, proposer: 0 }).unwrap();
+        let first = state.delivery_order[0].0.clone();
+
+        // Overtaking the oldest message is allowed until FAIR_DELIVERY_BOUND deliveries did so
+        for overtaken in 1..=FAIR_DELIVERY_BOUND {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            assert!(actions.len() > 1);
+            let later = state.delivery_order.last().unwrap().0.clone();
+            state = model.next_state(&state, TimeoutAction::DeliverMessage { msg: later }).unwrap();
+            assert_eq!(state.delivery_order[0], (first.clone(), overtaken));
+        }
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert_eq!(actions, vec![TimeoutAction::DeliverMessage { msg: first }]);
+    }
+
+    #[test]
+    fn test_skip_certificate_propagation_needs_fair_delivery() {
+        let model = |fair_delivery| TimeoutModel {
+            validator_count: 3,
+            max_slot: 1,
+            max_clock_skew: CLOCK_SKEW_BOUND,
+            offline: BTreeSet::new(),
+            network_faults: 0,
+            fair_delivery,
+        };
+        let skip_vote = |dst, voter| TimeoutAction::DeliverMessage {
+            msg: MessageInTransit { dst, msg: TimeoutMessage::SkipVote { slot: 1, voter } },
+        };
+        // Whether validator 2 may still receive a message without learning that slot 1 is skipped
+        let keeps_lagging = |msg: &MessageInTransit| msg.dst != 2 || match msg.msg {
+            TimeoutMessage::SkipCertificate { .. } => false,
+            TimeoutMessage::SkipVote { voter, .. } => voter == 2,
+            _ => true,
+        };
+
+        // Every validator times out slot 1 while slot 0 is proposed, and validator 0 certifies
+        // the skip. Then validator 2's copy of the certificate waits as long as delivery allows
+        let propagation_holds = |model: TimeoutModel| {
+            let property = model.property("skip_certificate_propagation");
+            let mut state = model.init_states().remove(0);
+            let mut script = vec![];
+            for validator in [0, 1, 2, 0, 1, 2] {
+                script.push(TimeoutAction::Tick { validator });
+            }
+            for validator in 0..3 {
+                script.push(TimeoutAction::TriggerTimeout { slot: 1, validator });
+                script.push(TimeoutAction::DeliverMessage {
+                    msg: MessageInTransit { dst: validator, msg: TimeoutMessage::TimeoutEvent { slot: 1, validator } },
+                });
+            }
+            script.push(TimeoutAction::ProposeBlock { slot: 0, proposer: 0 });
+            script.extend([skip_vote(0, 0), skip_vote(0, 1)]);
+            for action in script {
+                state = model.next_state(&state, action).unwrap();
+            }
+            assert!(state.validators[0].skip_certificates.contains(&1));
+
+            while !state.validators[2].skip_certificates.contains(&1) {
+                if !(property.condition)(&model, &state) {
+                    return false;
+                }
+                let mut actions = Vec::new();
+                model.actions(&state, &mut actions);
+                actions.retain(|action| matches!(action, TimeoutAction::DeliverMessage { .. }));
+                let next = actions.iter()
+                    .position(|action| matches!(action, TimeoutAction::DeliverMessage { msg } if keeps_lagging(msg)))
+                    .unwrap_or(0);
+                state = model.next_state(&state, actions.swap_remove(next)).unwrap();
+            }
+            true
+        };
+
+        assert!(propagation_holds(model(true)));
+        assert!(!propagation_holds(model(false)));
+    }
 }
@@ -0,0 +1,384 @@
+//! Best-effort export of a model as a TLA+ specification skeleton.
+//! The Rust models are the reference, but researchers cross-checking them with TLC or Apalache
+//! work in TLA+. [`to_tla`] writes a module declaring one variable per field of the model's state
+//! with `Init` translated from the initial states, one action operator per kind of action the
+//! model takes, a `Next` disjunction and one operator per property with the matching theorem.
+//! Values are translated from their `Debug` output: sequences become tuples, maps become `:>`/`@@`
+//! functions and structs and enum variants become records tagged with their name. Each action
+//! operator keeps an `UNCHANGED` frame of the variables no sampled action of its kind changed.
+//! Enabling conditions, next values and property bodies cannot be recovered from compiled
+//! transition functions and are left to `*Placeholder` operators written by hand.
+
+use crate::coverage::action_label;
+use stateright::{Expectation, Model};
+use std::collections::{BTreeMap, BTreeSet, HashSet, VecDeque};
+use std::fmt::{Debug, Write as _};
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Default number of states explored to find the kinds of action a model takes
+pub const DEFAULT_ACTION_SAMPLE_STATES: usize = 10_000;
+
+/// A value parsed from `Debug` output
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Value {
+    Atom(String),
+    Sequence(Vec<Value>),
+    Set(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Record { tag: String, fields: Vec<(String, Value)> },
+    Variant { tag: String, args: Vec<Value> },
+}
+
+/// Recursive-descent parser over the non-pretty `Debug` syntax
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn eat(&mut self, token: &str) -> bool {
+        self.rest = self.rest.trim_start();
+        match self.rest.strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn word(&mut self) -> Option<&'a str> {
+        self.rest = self.rest.trim_start();
+        let end = self.rest.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-' || c == '.'))
+            .unwrap_or(self.rest.len());
+        let (word, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        (!word.is_empty()).then_some(word)
+    }
+
+    /// Comma-separated items up to `close`, each parsed by `item`
+    fn items<T>(&mut self, close: &str, mut item: impl FnMut(&mut Self) -> Option<T>) -> Option<Vec<T>> {
+        let mut items = Vec::new();
+        while !self.eat(close) {
+            items.push(item(self)?);
+            if !self.eat(",") {
+                return self.eat(close).then_some(items);
+            }
+        }
+        Some(items)
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        self.rest = self.rest.trim_start();
+        if self.eat("[") {
+            return self.items("]", Self::value).map(Value::Sequence);
+        }
+        if self.eat("(") {
+            return self.items(")", Self::value).map(Value::Sequence);
+        }
+        if self.eat("{") {
+            return self.set_or_map();
+        }
+        if self.rest.starts_with('"') {
+            let end = self.rest[1..].find('"')? + 2;
+            let (text, rest) = self.rest.split_at(end);
+            self.rest = rest;
+            return Some(Value::Atom(text.to_string()));
+        }
+        let word = self.word()?;
+        if !word.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return Some(Value::Atom(match word {
+                "true" => "TRUE".to_string(),
+                "false" => "FALSE".to_string(),
+                number => number.to_string(),
+            }));
+        }
+        if self.eat("{") {
+            let fields = self.items("}", |parser| {
+                let name = parser.word()?.to_string();
+                parser.eat(":").then_some(())?;
+                Some((name, parser.value()?))
+            })?;
+            return Some(Value::Record { tag: word.to_string(), fields });
+        }
+        if self.eat("(") {
+            let args = self.items(")", Self::value)?;
+            return Some(Value::Variant { tag: word.to_string(), args });
+        }
+        Some(Value::Atom(format!("\"{}\"", word)))
+    }
+
+    /// The contents of `{ ... }` after the opening brace: a set, or a map when entries have keys
+    fn set_or_map(&mut self) -> Option<Value> {
+        if self.eat("}") {
+            return Some(Value::Set(Vec::new()));
+        }
+        let first = self.value()?;
+        if self.eat(":") {
+            let mut entries = vec![(first, self.value()?)];
+            if self.eat(",") {
+                entries.extend(self.items("}", |parser| {
+                    let key = parser.value()?;
+                    parser.eat(":").then_some(())?;
+                    Some((key, parser.value()?))
+                })?);
+            } else {
+                self.eat("}").then_some(())?;
+            }
+            return Some(Value::Map(entries));
+        }
+        let mut items = vec![first];
+        if self.eat(",") {
+            items.extend(self.items("}", Self::value)?);
+        } else {
+            self.eat("}").then_some(())?;
+        }
+        Some(Value::Set(items))
+    }
+}
+
+fn parse(debug: &str) -> Option<Value> {
+    let mut parser = Parser { rest: debug };
+    let value = parser.value()?;
+    parser.rest.trim().is_empty().then_some(value)
+}
+
+fn join(values: &[Value], separator: &str) -> String {
+    values.iter().map(Value::to_tla).collect::<Vec<_>>().join(separator)
+}
+
+impl Value {
+    fn to_tla(&self) -> String {
+        match self {
+            Value::Atom(atom) => atom.clone(),
+            Value::Sequence(items) => format!("<<{}>>", join(items, ", ")),
+            Value::Set(items) => format!("{{{}}}", join(items, ", ")),
+            Value::Map(entries) => {
+                let entries: Vec<_> = entries.iter()
+                    .map(|(key, value)| format!("{} :> {}", key.to_tla(), value.to_tla()))
+                    .collect();
+                format!("({})", entries.join(" @@ "))
+            }
+            Value::Record { tag, fields } => {
+                let mut entries = vec![format!("tag |-> \"{}\"", tag)];
+                entries.extend(fields.iter().map(|(name, value)| format!("{} |-> {}", name, value.to_tla())));
+                format!("[{}]", entries.join(", "))
+            }
+            Value::Variant { tag, args } => format!("[tag |-> \"{}\", args |-> <<{}>>]", tag, join(args, ", ")),
+        }
+    }
+}
+
+/// Translate a value's `Debug` output into a TLA+ expression, if it parses
+pub fn tla_value<T: Debug>(value: &T) -> Option<String> {
+    parse(&format!("{:?}", value)).map(|value| value.to_tla())
+}
+
+/// The fields of a state struct, in declaration order, with their values in TLA+; a state that
+/// does not parse becomes a single `state` variable holding its raw `Debug` output
+fn state_fields<S: Debug>(state: &S) -> Vec<(String, String)> {
+    let debug = format!("{:?}", state);
+    match parse(&debug) {
+        Some(Value::Record { fields, .. }) => {
+            fields.into_iter().map(|(name, value)| (name, value.to_tla())).collect()
+        }
+        _ => vec![("state".to_string(), debug)],
+    }
+}
+
+fn fingerprint<S: Hash>(state: &S) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// One example of each kind of action taken within the first `max_states` states, breadth first,
+/// with the state variables any action of that kind was seen to change
+fn action_examples<M>(model: &M, max_states: usize) -> BTreeMap<String, (M::Action, BTreeSet<String>)>
+where
+    M: Model,
+    M::State: Debug + Hash,
+    M::Action: Debug + Clone,
+{
+    let mut examples: BTreeMap<String, (M::Action, BTreeSet<String>)> = BTreeMap::new();
+    let mut visited = HashSet::new();
+    let mut queue: VecDeque<_> = model.init_states().into_iter().collect();
+    while let Some(state) = queue.pop_front() {
+        if visited.len() >= max_states || !visited.insert(fingerprint(&state)) {
+            continue;
+        }
+        let fields = state_fields(&state);
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        for action in actions {
+            let label = action_label(&action);
+            if let Some(next) = model.next_state(&state, action.clone()) {
+                let changed = fields.iter().zip(state_fields(&next))
+                    .filter(|((_, before), (_, after))| before != after)
+                    .map(|((name, _), _)| name.clone());
+                examples.entry(label).or_insert_with(|| (action, BTreeSet::new())).1.extend(changed);
+                if model.within_boundary(&next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    examples
+}
+
+/// TLA+ operator name for an action label such as `Deliver/Vote`
+fn operator_name(label: &str) -> String {
+    let name: String = label.split('/').collect();
+    if name.is_empty() { "Step".to_string() } else { name }
+}
+
+/// TLA+ skeleton of `model` as module `module`, with the kinds of action found within the first
+/// `max_states` states
+pub fn to_tla<M>(model: &M, module: &str, max_states: usize) -> String
+where
+    M: Model,
+    M::State: Debug + Hash,
+    M::Action: Debug + Clone,
+{
+    let init_states = model.init_states();
+    let variables: Vec<String> = init_states.first()
+        .map(|state| state_fields(state).into_iter().map(|(name, _)| name).collect())
+        .unwrap_or_default();
+    let actions = action_examples(model, max_states);
+    let properties = model.properties();
+
+    let mut tla = String::new();
+    let rule = "-".repeat(28);
+    writeln!(tla, "{} MODULE {} {}", rule, module, rule).unwrap();
+    writeln!(tla, "\\* Skeleton exported from the Rust model: variables, initial states, kinds of action and the").unwrap();
+    writeln!(tla, "\\* variables each leaves unchanged are taken from it. The operators named *Placeholder stand").unwrap();
+    writeln!(tla, "\\* for the enabling conditions and next values, and have to be written by hand.").unwrap();
+    writeln!(tla, "EXTENDS Integers, FiniteSets, Sequences, TLC").unwrap();
+    writeln!(tla).unwrap();
+    writeln!(tla, "VARIABLES {}", variables.join(", ")).unwrap();
+    writeln!(tla).unwrap();
+    writeln!(tla, "vars == <<{}>>", variables.join(", ")).unwrap();
+    writeln!(tla).unwrap();
+
+    writeln!(tla, "Init ==").unwrap();
+    for state in &init_states {
+        let indent = if init_states.len() > 1 {
+            writeln!(tla, "    \\/").unwrap();
+            "        "
+        } else {
+            "    "
+        };
+        for (name, value) in state_fields(state) {
+            writeln!(tla, "{}/\\ {} = {}", indent, name, value).unwrap();
+        }
+    }
+    writeln!(tla).unwrap();
+
+    for (label, (example, changed)) in &actions {
+        let operator = operator_name(label);
+        let unchanged: Vec<&str> = variables.iter()
+            .filter(|name| !changed.contains(*name))
+            .map(String::as_str)
+            .collect();
+        let changed: Vec<&str> = changed.iter().map(String::as_str).collect();
+        writeln!(tla, "\\* {}, for example {}", label, tla_value(example).unwrap_or_else(|| format!("{:?}", example))).unwrap();
+        let next_values = if changed.is_empty() { "no variable".to_string() } else { changed.join(", ") };
+        writeln!(tla, "\\* Placeholder: the enabling condition and the next values of {}", next_values).unwrap();
+        writeln!(tla, "{}Placeholder == TRUE", operator).unwrap();
+        writeln!(tla, "{} ==", operator).unwrap();
+        writeln!(tla, "    /\\ {}Placeholder", operator).unwrap();
+        if !unchanged.is_empty() {
+            writeln!(tla, "    /\\ UNCHANGED <<{}>>", unchanged.join(", ")).unwrap();
+        }
+        writeln!(tla).unwrap();
+    }
+    writeln!(tla, "Next ==").unwrap();
+    for label in actions.keys() {
+        writeln!(tla, "    \\/ {}", operator_name(label)).unwrap();
+    }
+    if actions.is_empty() {
+        writeln!(tla, "    UNCHANGED vars").unwrap();
+    }
+    writeln!(tla).unwrap();
+    writeln!(tla, "Spec == Init /\\ [][Next]_vars").unwrap();
+    writeln!(tla).unwrap();
+
+    for property in &properties {
+        writeln!(tla, "{} == TRUE \\* Placeholder: the state predicate", property.name).unwrap();
+    }
+    writeln!(tla).unwrap();
+    for property in &properties {
+        match property.expectation {
+            Expectation::Always => writeln!(tla, "THEOREM Spec => []{}", property.name).unwrap(),
+            Expectation::Eventually => writeln!(tla, "THEOREM Spec => <>{}", property.name).unwrap(),
+            Expectation::Sometimes => {
+                writeln!(tla, "\\* {} is a reachability check: TLC should report the invariant ~{} violated", property.name, property.name).unwrap()
+            }
+        }
+    }
+    writeln!(tla, "{}", "=".repeat(2 * rule.len() + module.len() + 9)).unwrap();
+    tla
+}
+
+/// Write the skeleton of `model` to `<directory>/<module>.tla`
+pub fn write_tla<M>(model: &M, module: &str, directory: &Path) -> io::Result<PathBuf>
+where
+    M: Model,
+    M::State: Debug + Hash,
+    M::Action: Debug + Clone,
+{
+    fs::create_dir_all(directory)?;
+    let file = directory.join(format!("{}.tla", module));
+    fs::write(&file, to_tla(model, module, DEFAULT_ACTION_SAMPLE_STATES))?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::votor::VotorModel;
+    use std::collections::{BTreeMap, BTreeSet};
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum Message {
+        Vote { slot: u64, hash: Option<u64> },
+        Skip(u64),
+    }
+
+    #[test]
+    fn test_tla_values() {
+        assert_eq!(tla_value(&vec![1, 2]).unwrap(), "<<1, 2>>");
+        assert_eq!(tla_value(&(3, true)).unwrap(), "<<3, TRUE>>");
+        assert_eq!(tla_value(&BTreeSet::from([1, 2])).unwrap(), "{1, 2}");
+        assert_eq!(tla_value(&BTreeSet::<u64>::new()).unwrap(), "{}");
+        assert_eq!(tla_value(&BTreeMap::from([(1, vec![2])])).unwrap(), "(1 :> <<2>>)");
+        assert_eq!(tla_value(&Message::Vote { slot: 1, hash: None }).unwrap(),
+                   "[tag |-> \"Vote\", slot |-> 1, hash |-> \"None\"]");
+        assert_eq!(tla_value(&Message::Skip(4)).unwrap(), "[tag |-> \"Skip\", args |-> <<4>>]");
+        assert_eq!(tla_value(&"quoted").unwrap(), "\"quoted\"");
+    }
+
+    #[test]
+    fn test_votor_tla_skeleton() {
+        let model = VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 };
+        let tla = to_tla(&model, "Votor", 1_000);
+
+        assert!(tla.starts_with("---------------------------- MODULE Votor ----------------------------\n"));
+        assert!(tla.contains("VARIABLES network, network_faults, finalized_blocks,"));
+        assert!(tla.contains("    /\\ current_slot = 0\n"));
+        assert!(tla.contains("    /\\ finalized_blocks = (0 :> 0)\n"));
+        assert!(tla.contains("ProposePlaceholder == TRUE\nPropose ==\n    /\\ ProposePlaceholder\n    /\\ UNCHANGED <<"));
+        assert!(!tla.contains("FALSE"));
+        assert!(tla.contains("    \\/ Propose\n"));
+        assert!(tla.contains("Spec == Init /\\ [][Next]_vars"));
+        for property in model.properties() {
+            assert!(tla.contains(&format!("{} == TRUE", property.name)));
+        }
+        let last = tla.trim_end().lines().last().unwrap();
+        assert_eq!(last.len(), tla.lines().next().unwrap().len());
+        assert!(last.chars().all(|c| c == '='));
+    }
+}
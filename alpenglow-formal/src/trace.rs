@@ -0,0 +1,210 @@
+//! Graphviz export of counterexample traces.
+//! A failed property comes with a path of states and actions, which is hard to follow from debug
+//! prints of whole states. [`to_dot`] renders a path as a DOT graph: one node per state labelled
+//! with the fields that matter for the model, one edge per action, and the final, violating
+//! state highlighted. Render it with `dot -Tsvg trace.dot > trace.svg`. [`to_mermaid`] renders
+//! the same path as a mermaid sequence diagram of the messages exchanged between validators,
+//! which reads better in protocol reviews.
+
+use stateright::{Checker, Model};
+use std::fmt::{Debug, Write as _};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The fields of a state worth showing in a trace, as (name, value) pairs
+pub trait TraceSummary {
+    fn summary(&self) -> Vec<(&'static str, String)>;
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Render a path of (state, action taken from it) steps as a DOT digraph titled `title`
+pub fn to_dot<S: TraceSummary, A: Debug>(title: &str, steps: &[(S, Option<A>)]) -> String {
+    let mut dot = String::new();
+    writeln!(dot, "digraph \"{}\" {{", escape(title)).unwrap();
+    writeln!(dot, "    label=\"{}\";", escape(title)).unwrap();
+    writeln!(dot, "    labelloc=t;").unwrap();
+    writeln!(dot, "    node [shape=box, fontname=\"monospace\"];").unwrap();
+    for (index, (state, action)) in steps.iter().enumerate() {
+        let mut label = format!("step {}\\l", index);
+        for (field, value) in state.summary() {
+            label.push_str(&escape(&format!("{}: {}", field, value)));
+            label.push_str("\\l");
+        }
+        let style = if index + 1 == steps.len() { ", color=red, penwidth=2" } else { "" };
+        writeln!(dot, "    s{} [label=\"{}\"{}];", index, label, style).unwrap();
+        if let (Some(action), true) = (action, index + 1 < steps.len()) {
+            writeln!(dot, "    s{} -> s{} [label=\"{}\"];", index, index + 1, escape(&format!("{:?}", action))).unwrap();
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Write one `<property>.dot` file into `directory` for every discovery of `checker`
+pub fn write_counterexamples<M, C>(checker: &C, directory: &Path) -> io::Result<Vec<PathBuf>>
+where
+    M: Model,
+    M::State: TraceSummary + Clone + PartialEq,
+    M::Action: Debug + Clone + PartialEq,
+    C: Checker<M>,
+{
+    fs::create_dir_all(directory)?;
+    let mut written = Vec::new();
+    for (property, path) in checker.discoveries() {
+        let file = directory.join(format!("{}.dot", property));
+        fs::write(&file, to_dot(property, &path.into_vec()))?;
+        written.push(file);
+    }
+    written.sort();
+    Ok(written)
+}
+
+/// One line of a sequence diagram
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SequenceEvent {
+    /// A message delivered from one validator to another
+    Message { from: usize, to: usize, label: String },
+    /// Something a single validator did or suffered
+    Note { actor: usize, text: String },
+}
+
+/// Models whose traces can be drawn as validators exchanging messages
+pub trait SequenceTrace: Model {
+    /// Number of validators drawn as participants
+    fn participants(&self) -> usize;
+    /// What taking `action` in `state` looks like on the diagram
+    fn sequence_events(&self, state: &Self::State, action: &Self::Action) -> Vec<SequenceEvent>;
+}
+
+/// Mermaid text allows neither `;` nor `#` unescaped, and the line structure must survive
+fn mermaid_text(text: &str) -> String {
+    text.replace('#', "#35;").replace(';', "#59;").replace('\n', " ")
+}
+
+/// Render a path of (state, action taken from it) steps as a mermaid sequence diagram
+pub fn to_mermaid<M: SequenceTrace>(model: &M, title: &str, steps: &[(M::State, Option<M::Action>)]) -> String {
+    let mut diagram = String::new();
+    writeln!(diagram, "sequenceDiagram").unwrap();
+    writeln!(diagram, "    title {}", mermaid_text(title)).unwrap();
+    for validator in 0..model.participants() {
+        writeln!(diagram, "    participant V{}", validator).unwrap();
+    }
+    for (state, action) in steps {
+        let Some(action) = action else {
+            continue;
+        };
+        for event in model.sequence_events(state, action) {
+            match event {
+                SequenceEvent::Message { from, to, label } => {
+                    writeln!(diagram, "    V{}->>V{}: {}", from, to, mermaid_text(&label)).unwrap()
+                }
+                SequenceEvent::Note { actor, text } => {
+                    writeln!(diagram, "    Note over V{}: {}", actor, mermaid_text(&text)).unwrap()
+                }
+            }
+        }
+    }
+    diagram
+}
+
+/// Write one `<property>.mmd` sequence diagram into `directory` for every discovery of `checker`,
+/// drawing the paths with `model`, the model `checker` explores or one it wraps
+pub fn write_sequence_diagrams<M, B, C>(model: &M, checker: &C, directory: &Path) -> io::Result<Vec<PathBuf>>
+where
+    M: SequenceTrace,
+    M::State: Clone + PartialEq,
+    M::Action: Clone + PartialEq,
+    B: Model<State = M::State, Action = M::Action>,
+    C: Checker<B>,
+{
+    fs::create_dir_all(directory)?;
+    let mut written = Vec::new();
+    for (property, path) in checker.discoveries() {
+        let file = directory.join(format!("{}.mmd", property));
+        fs::write(&file, to_mermaid(model, property, &path.into_vec()))?;
+        written.push(file);
+    }
+    written.sort();
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::certificate::VoteRouting;
+    use crate::modelling::safety::{SafetyModel, Synchrony, DEFAULT_CORRUPTION_BUDGET_PERCENT, DEFAULT_OFFLINE_BUDGET_PERCENT};
+    use crate::votor::VotorModel;
+
+    /// Follow the first enabled action `steps` times from the first initial state
+    fn first_steps<M: Model>(model: &M, steps: usize) -> Vec<(M::State, Option<M::Action>)>
+    where
+        M::Action: Clone,
+    {
+        let mut trace = Vec::new();
+        let mut state = model.init_states().remove(0);
+        for _ in 0..steps {
+            let mut actions = Vec::new();
+            model.actions(&state, &mut actions);
+            let Some((action, next)) = actions.into_iter()
+                .find_map(|action| model.next_state(&state, action.clone()).map(|next| (action, next))) else {
+                break;
+            };
+            trace.push((state, Some(action)));
+            state = next;
+        }
+        trace.push((state, None));
+        trace
+    }
+
+    #[test]
+    fn test_votor_trace_to_dot() {
+        let steps = first_steps(&VotorModel { honest_validators: 2, max_slot: 1, network_faults: 0 }, 3);
+        let dot = to_dot("votor \"trace\"", &steps);
+
+        assert!(dot.starts_with("digraph \"votor \\\"trace\\\"\" {"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert_eq!(dot.matches(" -> ").count(), steps.len() - 1);
+        assert!(dot.contains("Propose"));
+        assert!(dot.contains("current_slot: "));
+        assert!(dot.contains(&format!("s{} [label=", steps.len() - 1)));
+        assert_eq!(dot.matches("color=red").count(), 1);
+    }
+
+    #[test]
+    fn test_safety_trace_to_dot() {
+        let model = SafetyModel {
+            validator_count: 3,
+            max_slot: 1,
+            byzantine_count: 0,
+            offline_budget_percent: DEFAULT_OFFLINE_BUDGET_PERCENT,
+            corruption_budget_percent: DEFAULT_CORRUPTION_BUDGET_PERCENT,
+            network_faults: 0,
+            synchrony: Synchrony::default(),
+            vote_routing: VoteRouting::default(),
+        };
+        let steps = first_steps(&model, 4);
+        let dot = to_dot("safety", &steps);
+
+        assert_eq!(dot.matches(" -> ").count(), steps.len() - 1);
+        assert!(dot.contains("finalized: "));
+        assert!(dot.contains("in_flight: "));
+    }
+
+    #[test]
+    fn test_votor_trace_to_mermaid() {
+        let model = VotorModel { honest_validators: 3, max_slot: 1, network_faults: 0 };
+        let steps = first_steps(&model, 4);
+        let diagram = to_mermaid(&model, "votor", &steps);
+
+        assert!(diagram.starts_with("sequenceDiagram\n"));
+        assert_eq!(diagram.matches("participant V").count(), 3);
+        // The first step proposes a block and the next ones deliver it and the resulting votes
+        assert!(diagram.contains("Note over V0: propose slot 1"));
+        assert!(diagram.contains("V0->>V1: Block slot 1"));
+        assert!(diagram.lines().all(|line| !line.contains(';')));
+    }
+}
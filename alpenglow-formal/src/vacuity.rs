@@ -0,0 +1,159 @@
+//! Vacuity detection for conditional properties.
+//! Many `always` properties only constrain states that satisfy some antecedent and return `true`
+//! everywhere else. A clean model-checking run then says nothing about a guarantee whose
+//! antecedent was never reached. This module re-explores a model with each antecedent turned
+//! into a `sometimes` property and reports which ones were never satisfied, so users know which
+//! guarantees were actually exercised.
+
+use stateright::{Checker, Model, Property};
+use std::collections::BTreeMap;
+use std::hash::Hash;
+
+/// Default number of states explored by a vacuity pass
+pub const DEFAULT_VACUITY_STATE_COUNT: usize = 100_000;
+/// Most antecedents a single vacuity pass can track
+pub const MAX_ANTECEDENTS: usize = 12;
+
+/// The precondition under which a conditional property makes a non-trivial claim
+pub struct Antecedent<M: Model> {
+    /// Name of the property guarded by this antecedent
+    pub property: &'static str,
+    /// Holds in states where the property actually constrains the model
+    pub condition: fn(&M, &M::State) -> bool,
+}
+
+impl<M: Model> Antecedent<M> {
+    pub fn new(property: &'static str, condition: fn(&M, &M::State) -> bool) -> Self {
+        Self { property, condition }
+    }
+}
+
+impl<M: Model> Clone for Antecedent<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M: Model> Copy for Antecedent<M> {}
+
+/// Wraps a model so that its only properties are the antecedents of the original ones
+pub struct VacuityModel<M: Model> {
+    pub model: M,
+    pub antecedents: Vec<Antecedent<M>>,
+}
+
+impl<M: Model> VacuityModel<M> {
+    pub fn new(model: M, antecedents: Vec<Antecedent<M>>) -> Self {
+        assert!(antecedents.len() <= MAX_ANTECEDENTS, "at most {} antecedents per vacuity pass", MAX_ANTECEDENTS);
+        Self { model, antecedents }
+    }
+}
+
+/// Property conditions are plain function pointers, so each antecedent slot gets its own
+fn witness<M: Model, const I: usize>(vacuity: &VacuityModel<M>, state: &M::State) -> bool {
+    (vacuity.antecedents[I].condition)(&vacuity.model, state)
+}
+
+fn witness_condition<M: Model>(index: usize) -> fn(&VacuityModel<M>, &M::State) -> bool {
+    match index {
+        0 => witness::<M, 0>,
+        1 => witness::<M, 1>,
+        2 => witness::<M, 2>,
+        3 => witness::<M, 3>,
+        4 => witness::<M, 4>,
+        5 => witness::<M, 5>,
+        6 => witness::<M, 6>,
+        7 => witness::<M, 7>,
+        8 => witness::<M, 8>,
+        9 => witness::<M, 9>,
+        10 => witness::<M, 10>,
+        11 => witness::<M, 11>,
+        _ => unreachable!("at most {} antecedents per vacuity pass", MAX_ANTECEDENTS),
+    }
+}
+
+impl<M: Model> Model for VacuityModel<M> {
+    type State = M::State;
+    type Action = M::Action;
+
+    fn init_states(&self) -> Vec<Self::State> {
+        self.model.init_states()
+    }
+
+    fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
+        self.model.actions(state, actions)
+    }
+
+    fn next_state(&self, state: &Self::State, action: Self::Action) -> Option<Self::State> {
+        self.model.next_state(state, action)
+    }
+
+    fn within_boundary(&self, state: &Self::State) -> bool {
+        self.model.within_boundary(state)
+    }
+
+    fn properties(&self) -> Vec<Property<Self>> {
+        self.antecedents.iter().enumerate()
+            .map(|(index, antecedent)| Property::<Self>::sometimes(antecedent.property, witness_condition(index)))
+            .collect()
+    }
+}
+
+/// Outcome of a vacuity pass
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VacuityReport {
+    /// Number of states explored
+    pub states_explored: usize,
+    /// Length of the witness path found for each property's antecedent, if any
+    pub witnesses: BTreeMap<&'static str, Option<usize>>,
+}
+
+impl VacuityReport {
+    /// Properties whose antecedent was never satisfied during exploration
+    pub fn vacuous(&self) -> Vec<&'static str> {
+        self.witnesses.iter()
+            .filter(|(_, witness)| witness.is_none())
+            .map(|(property, _)| *property)
+            .collect()
+    }
+
+    /// Whether the named property was exercised by at least one explored state
+    pub fn is_exercised(&self, property: &str) -> bool {
+        self.witnesses.get(property).is_some_and(|witness| witness.is_some())
+    }
+
+    pub fn print_summary(&self) {
+        println!("Vacuity check over {} states:", self.states_explored);
+        for (property, witness) in &self.witnesses {
+            match witness {
+                Some(steps) => println!("  ✅ {}: exercised (witness after {} steps)", property, steps),
+                None => println!("  ⚠️  {}: antecedent never satisfied (vacuously true)", property),
+            }
+        }
+    }
+}
+
+/// Explore up to `max_states` states of `model` on `threads` threads looking for a state
+/// satisfying each antecedent
+pub fn check_vacuity<M>(model: M, antecedents: Vec<Antecedent<M>>, max_states: usize, threads: usize) -> VacuityReport
+where
+    M: Model + Send + Sync + 'static,
+    M::State: Hash + Send + Sync + Clone + PartialEq + 'static,
+    M::Action: Clone + PartialEq,
+{
+    let properties: Vec<&'static str> = antecedents.iter().map(|a| a.property).collect();
+
+    let checker = VacuityModel::new(model, antecedents)
+        .checker()
+        .threads(threads)
+        .target_state_count(max_states)
+        .spawn_dfs()
+        .join();
+
+    VacuityReport {
+        states_explored: checker.unique_state_count(),
+        witnesses: properties.into_iter()
+            .map(|property| (property, checker.discovery(property).map(|path| path.into_actions().len())))
+            .collect(),
+    }
+}
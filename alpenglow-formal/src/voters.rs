@@ -0,0 +1,153 @@
+//! Compact voter sets shared by the formal models.
+//! Vote pools hold one set of voters per (slot, hash) in every validator's state, so their size
+//! and hashing cost dominate the explored state space. [`VoterSet`] stores the set as a single
+//! 128-bit word, one bit per validator, and mirrors the parts of `BTreeSet<usize>` the models use.
+
+use std::fmt;
+use std::hash::Hash;
+
+type ActorId = usize;
+
+/// Most validators a voter set can hold
+pub const MAX_VOTERS: usize = u128::BITS as usize;
+
+/// A set of validator ids, stored as a bitset
+#[derive(Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct VoterSet(u128);
+
+impl VoterSet {
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    fn bit(voter: ActorId) -> u128 {
+        assert!(voter < MAX_VOTERS, "voter {} exceeds the {} supported validators", voter, MAX_VOTERS);
+        1 << voter
+    }
+
+    /// Add a voter, returning whether it was not already present
+    pub fn insert(&mut self, voter: ActorId) -> bool {
+        let added = !self.contains(&voter);
+        self.0 |= Self::bit(voter);
+        added
+    }
+
+    /// Remove a voter, returning whether it was present
+    pub fn remove(&mut self, voter: &ActorId) -> bool {
+        let present = self.contains(voter);
+        self.0 &= !Self::bit(*voter);
+        present
+    }
+
+    pub fn contains(&self, voter: &ActorId) -> bool {
+        *voter < MAX_VOTERS && self.0 & (1 << voter) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Voters in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = ActorId> {
+        let bits = self.0;
+        (0..MAX_VOTERS).filter(move |voter| bits & (1 << voter) != 0)
+    }
+
+    /// Voters in either set
+    pub fn union(&self, other: &VoterSet) -> VoterSet {
+        Self(self.0 | other.0)
+    }
+}
+
+impl fmt::Debug for VoterSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<ActorId> for VoterSet {
+    fn from_iter<I: IntoIterator<Item = ActorId>>(iter: I) -> Self {
+        let mut voters = Self::new();
+        voters.extend(iter);
+        voters
+    }
+}
+
+impl Extend<ActorId> for VoterSet {
+    fn extend<I: IntoIterator<Item = ActorId>>(&mut self, iter: I) {
+        for voter in iter {
+            self.insert(voter);
+        }
+    }
+}
+
+impl<const N: usize> From<[ActorId; N]> for VoterSet {
+    fn from(voters: [ActorId; N]) -> Self {
+        voters.into_iter().collect()
+    }
+}
+
+/// A set of validator ids of any size, stored as a growable bitset, for simulations with more
+/// validators than a [`VoterSet`] holds
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct WideVoterSet(Vec<u64>);
+
+impl WideVoterSet {
+    pub fn insert(&mut self, voter: ActorId) -> bool {
+        let (word, bit) = (voter / 64, 1 << (voter % 64));
+        if word >= self.0.len() {
+            self.0.resize(word + 1, 0);
+        }
+        let added = self.0[word] & bit == 0;
+        self.0[word] |= bit;
+        added
+    }
+
+    pub fn contains(&self, voter: &ActorId) -> bool {
+        self.0.get(voter / 64).is_some_and(|word| word & (1 << (voter % 64)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// What vote pools need from a set of voters, so that the same vote counting serves the
+/// compact sets of the models and the wide sets of simulations
+pub trait Voters: Clone + Default + fmt::Debug + Eq + Hash {
+    /// Add a voter, returning whether it was not already present
+    fn insert(&mut self, voter: ActorId) -> bool;
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Voters for VoterSet {
+    fn insert(&mut self, voter: ActorId) -> bool {
+        VoterSet::insert(self, voter)
+    }
+
+    fn len(&self) -> usize {
+        VoterSet::len(self)
+    }
+}
+
+impl Voters for WideVoterSet {
+    fn insert(&mut self, voter: ActorId) -> bool {
+        WideVoterSet::insert(self, voter)
+    }
+
+    fn len(&self) -> usize {
+        WideVoterSet::len(self)
+    }
+}
@@ -3,23 +3,38 @@
 //! 1. Fast Path: Finalization in one round with >= 80% stake.
 //! 2. Slow Path: Finalization in two rounds with >= 60% stake each.
 //!
+//! Nodes may prune a slot once it is finalized, discarding its vote pools and flags and ignoring
+//! any message for it that arrives later, as validators do to bound their memory. The model keeps
+//! what pruning discards and the invariants read as ghost state, and checks that no certificate
+//! is lost to pruning.
+//!
+//! A node that finalizes a slot gossips the finalization certificate to the nodes that have not
+//! learned of it yet, and a node prunes only slots it knows are finalized. Certificates lagging
+//! for `FINALIZATION_GOSSIP_DELTA` deliveries are delivered before anything else, and the model
+//! checks that every node learns of each finalization within a bounded number of deliveries.
+//!
 //! To run this model, you will need Rust and Cargo installed. Then, execute:
 //! `cargo run --release`
 
 use stateright::{Model, Property};
 use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use crate::network::{NetworkFault, NetworkFaults};
+use crate::invariants::{self, ConsensusRecord, FinalizedHistory, FinalizedRecord};
+use crate::voters::{VoterSet, Voters};
+use crate::shared::SharedVec;
+use crate::bounds::Bounded;
+use crate::trace::{SequenceEvent, SequenceTrace, TraceSummary};
 
 // -----------
 // Constants
 // -----------
 
-const VALIDATOR_COUNT: usize = 3;
 const FAST_FINALIZE_THRESHOLD: u64 = 80;
 const NOTARIZE_THRESHOLD: u64 = 60;
 const SLOW_FINALIZE_THRESHOLD: u64 = 60;
-
-// To simplify, each validator has an equal stake.
-const STAKE_PER_VALIDATOR: u64 = 100 / VALIDATOR_COUNT as u64;
+const SKIP_THRESHOLD: u64 = 60;
+const FINALIZATION_GOSSIP_DELTA: u64 = 2; // Deliveries a finalization certificate may lag behind
 
 // -----------
 // Type Aliases
@@ -38,22 +53,50 @@ type Stake = u64;
 pub struct VotorState {
     /// The network is modeled as a set of in-flight messages.
     network: BTreeSet<MessageInTransit>,
+    /// Adversarial control over message delivery.
+    network_faults: NetworkFaults<MessageInTransit>,
     /// Tracks finalized blocks to check for safety violations. Map<Slot, Hash>.
     finalized_blocks: BTreeMap<Slot, Hash>,
+    /// Blocks finalized through the fast path.
+    fast_finalized: BTreeSet<(Slot, Hash)>,
+    /// Certificates formed from NotarVotes, with the stake they were formed with.
+    certificates: BTreeSet<(Slot, Hash, Stake)>,
+    /// Slots some node formed a skip certificate for.
+    skip_certificates: BTreeSet<Slot>,
     /// Per-node state tracking
-    node_states: Vec<NodeState>,
+    node_states: SharedVec<NodeState>,
     /// Current slot being processed
     current_slot: Slot,
+    /// Blocks proposed so far: (slot, proposer); each node proposes a slot at most once
+    proposals: BTreeSet<(Slot, ActorId)>,
+    /// Ghost record of the notarizations nodes pruned
+    pruned_notarized: BTreeSet<(Slot, Hash)>,
+    /// Ghost record of the NotarVotes of slots their voters pruned: (slot, hash) -> voters
+    pruned_notar_votes: BTreeMap<(Slot, Hash), VoterSet>,
+    /// Ghost record of every finalization recorded so far
+    finalized_history: FinalizedHistory,
+    /// Nodes that know a slot is finalized, having finalized it or received its certificate
+    finality_known: BTreeMap<Slot, VoterSet>,
+    /// Finalization certificates gossiped to nodes that have not learned of them: (slot, node)
+    certificate_gossip: BTreeSet<(Slot, ActorId)>,
+    /// Deliveries since a slot was finalized while some node has not learned of it
+    finalization_age: BTreeMap<Slot, u64>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-pub struct NodeState {
+/// A node's view of the protocol; simulations with more validators than a `VoterSet` holds
+/// pick wider voter sets
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct NodeState<V = VoterSet> {
     /// Per-slot state flags that track a node's commitments.
     slot_states: BTreeMap<Slot, SlotState>,
     /// Votes received from other nodes, representing this node's view of the "Pool".
-    vote_pool: BTreeMap<Slot, BTreeMap<Hash, BTreeSet<ActorId>>>,
+    vote_pool: BTreeMap<Slot, BTreeMap<Hash, V>>,
     /// FinalVotes received for the second round of the slow path.
-    final_vote_pool: BTreeMap<Slot, BTreeSet<ActorId>>,
+    final_vote_pool: BTreeMap<Slot, V>,
+    /// SkipVotes received; enough of them form a skip certificate.
+    skip_vote_pool: BTreeMap<Slot, V>,
+    /// Every slot up to this one is finalized and pruned; genesis, slot 0, holds nothing to prune.
+    pruned_through: Slot,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
@@ -86,12 +129,52 @@ pub enum Message {
     SkipVote { slot: Slot, voter: ActorId },
 }
 
+/// Blocks are identified by a deterministic hash of their slot and proposer
+pub(crate) fn block_hash(slot: Slot, proposer: ActorId) -> Hash {
+    slot * 1000 + proposer as u64
+}
+
+impl Message {
+    /// The slot this message belongs to
+    pub fn slot(&self) -> Slot {
+        match self {
+            Message::Block { slot, .. } | Message::NotarVote { slot, .. } | Message::FinalVote { slot, .. } | Message::SkipVote { slot, .. } => *slot,
+        }
+    }
+
+    /// The validator that sent this message
+    pub fn sender(&self) -> ActorId {
+        match self {
+            Message::Block { hash, .. } => (hash % 1000) as ActorId,
+            Message::NotarVote { voter, .. } | Message::FinalVote { voter, .. } | Message::SkipVote { voter, .. } => *voter,
+        }
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Message::Block { slot, hash, parent_hash } => write!(f, "Block slot {} hash {} parent {}", slot, hash, parent_hash),
+            Message::NotarVote { slot, hash, .. } => write!(f, "NotarVote slot {} hash {}", slot, hash),
+            Message::FinalVote { slot, .. } => write!(f, "FinalVote slot {}", slot),
+            Message::SkipVote { slot, .. } => write!(f, "SkipVote slot {}", slot),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct MessageInTransit {
     dst: ActorId,
     msg: Message,
 }
 
+impl MessageInTransit {
+    /// A message on its way to `dst`
+    pub fn new(dst: ActorId, msg: Message) -> Self {
+        Self { dst, msg }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Action {
     /// A node proposes a new block for a given slot.
@@ -103,6 +186,12 @@ pub enum Action {
     Deliver { msg: MessageInTransit },
     /// A node's local timer for a slot expires.
     Timeout { slot: Slot, node_id: ActorId },
+    /// The network adversary drops, duplicates or delays a message.
+    NetworkFault { fault: NetworkFault<MessageInTransit> },
+    /// A node discards its state for a finalized slot, the one after the last it pruned.
+    Prune { slot: Slot, node_id: ActorId },
+    /// A gossiped finalization certificate reaches a node that has not learned of it.
+    LearnFinalization { slot: Slot, node_id: ActorId },
 }
 
 #[derive(Clone)]
@@ -111,6 +200,8 @@ pub struct VotorModel {
     pub honest_validators: usize,
     /// Maximum number of slots to explore.
     pub max_slot: Slot,
+    /// Messages the network adversary may drop, duplicate or delay.
+    pub network_faults: usize,
 }
 
 impl VotorState {
@@ -118,15 +209,323 @@ impl VotorState {
         let mut genesis_finalized = BTreeMap::new();
         genesis_finalized.insert(0, 0); // Genesis block: slot 0, hash 0
 
-        Self {
+        let mut state = Self {
             network: BTreeSet::new(),
+            network_faults: NetworkFaults::new(0),
             finalized_blocks: genesis_finalized,
-            node_states: (0..validator_count).map(|_| NodeState {
-                slot_states: BTreeMap::new(),
-                vote_pool: BTreeMap::new(),
-                final_vote_pool: BTreeMap::new(),
-            }).collect(),
+            fast_finalized: BTreeSet::new(),
+            certificates: BTreeSet::new(),
+            skip_certificates: BTreeSet::new(),
+            node_states: (0..validator_count).map(|_| NodeState::default()).collect(),
             current_slot: 0,
+            proposals: BTreeSet::new(),
+            pruned_notarized: BTreeSet::new(),
+            pruned_notar_votes: BTreeMap::new(),
+            finalized_history: FinalizedHistory::new(),
+            finality_known: BTreeMap::new(),
+            certificate_gossip: BTreeSet::new(),
+            finalization_age: BTreeMap::new(),
+        };
+        state.record_finalized();
+        state
+    }
+
+    /// Record what a node produced: its broadcasts enter the network, its certificates, skip
+    /// certificates and finalizations the global record
+    fn apply(&mut self, output: NodeOutput, validator_count: usize) {
+        for msg in output.broadcast {
+            for i in 0..validator_count {
+                self.network.insert(MessageInTransit { dst: i, msg: msg.clone() });
+            }
+        }
+        self.certificates.extend(output.certificates);
+        self.skip_certificates.extend(output.skipped);
+        if let Some((slot, hash, fast)) = output.finalized {
+            self.finalized_blocks.insert(slot, hash);
+            if fast {
+                self.fast_finalized.insert((slot, hash));
+            }
+        }
+    }
+
+    /// Record that `node` knows `slot` is finalized; it gossips the certificate to every node
+    /// that has not learned of it yet
+    fn learn_finalization(&mut self, node: ActorId, slot: Slot, validator_count: usize) {
+        let known = self.finality_known.entry(slot).or_default();
+        if !known.insert(node) {
+            return;
+        }
+        self.certificate_gossip.remove(&(slot, node));
+        self.certificate_gossip.extend((0..validator_count).filter(|n| !known.contains(n)).map(|n| (slot, n)));
+    }
+
+    /// Age every finalization some node has not learned of by one delivery
+    fn age_finalizations(&mut self) {
+        let lagging: BTreeSet<Slot> = self.certificate_gossip.iter().map(|(slot, _)| *slot).collect();
+        self.finalization_age.retain(|slot, _| lagging.contains(slot));
+        for slot in lagging {
+            *self.finalization_age.entry(slot).or_default() += 1;
+        }
+    }
+
+    /// Whether pruning lost nothing: nodes pruned only finalized slots and hold no state for
+    /// them, and every certificate formed is still recorded as a notarization
+    fn check_no_certificate_regression(&self) -> bool {
+        let notarized = self.notarized_blocks();
+        self.node_states.iter().all(|node| {
+            (1..=node.pruned_through).all(|slot| self.finalized_blocks.contains_key(&slot))
+                && node.slot_states.keys()
+                    .chain(node.vote_pool.keys())
+                    .chain(node.final_vote_pool.keys())
+                    .chain(node.skip_vote_pool.keys())
+                    .all(|slot| *slot > node.pruned_through)
+        }) && self.certificates.iter().all(|(slot, hash, _)| notarized.contains(&(*slot, *hash)))
+    }
+}
+
+/// What a node's handling of a message or timeout produces for the rest of the system
+#[derive(Debug, Default)]
+pub(crate) struct NodeOutput {
+    /// Votes the node broadcasts to every validator
+    pub(crate) broadcast: Vec<Message>,
+    /// Certificates the node formed, with the stake they were formed with
+    pub(crate) certificates: Vec<(Slot, Hash, Stake)>,
+    /// Block the node finalized, and whether through the fast path
+    pub(crate) finalized: Option<(Slot, Hash, bool)>,
+    /// Slot the node formed a skip certificate for
+    pub(crate) skipped: Option<Slot>,
+}
+
+/// Stake of `voters` validators, in percent, when each of `validators` validators holds an
+/// equal share
+pub(crate) fn equal_stake(voters: usize, validators: usize) -> Stake {
+    voters as u64 * 100 / validators.max(1) as u64
+}
+
+impl<V: Voters> NodeState<V> {
+    /// Handle `msg` delivered to validator `me`, whose parent checks use the blocks in `finalized`
+    /// and whose vote counts weigh `voters` validators as `stake(voters)` percent of the stake
+    pub(crate) fn on_message(&mut self, me: ActorId, msg: &Message, finalized: &BTreeMap<Slot, Hash>,
+                             stake: impl Fn(usize) -> Stake) -> NodeOutput {
+        let mut output = NodeOutput::default();
+        if msg.slot() <= self.pruned_through {
+            return output; // The slot is finalized and its state discarded
+        }
+        match *msg {
+            Message::Block { slot, hash, parent_hash } => {
+                // TRYNOTAR logic (Algorithm 2)
+                let slot_state = self.slot_states.entry(slot).or_default();
+                let parent_slot = slot - 1;
+
+                // Precondition 1: Voted flag is not set
+                // Precondition 2: Parent is ready (we check against finalized blocks for simplicity)
+                if !slot_state.voted && finalized.get(&parent_slot) == Some(&parent_hash) {
+                    slot_state.voted = true;
+                    slot_state.voted_notar = Some(hash);
+                    output.broadcast.push(Message::NotarVote { slot, hash, voter: me });
+                }
+            }
+            Message::NotarVote { slot, hash, voter } => {
+                // Add vote to the node's local pool
+                let slot_votes = self.vote_pool.entry(slot).or_default();
+                let block_voters = slot_votes.entry(hash).or_default();
+                block_voters.insert(voter);
+
+                let total_stake = stake(block_voters.len());
+
+                // Check for FAST-FINALIZATION (>= 80% stake)
+                if total_stake >= FAST_FINALIZE_THRESHOLD {
+                    output.finalized = Some((slot, hash, true));
+                    output.certificates.push((slot, hash, total_stake));
+                }
+
+                // Check for NOTARIZATION (>= 60% stake)
+                if total_stake >= NOTARIZE_THRESHOLD {
+                    let slot_state = self.slot_states.entry(slot).or_default();
+                    if slot_state.block_notarized.is_none() {
+                        slot_state.block_notarized = Some(hash);
+                        output.certificates.push((slot, hash, total_stake));
+
+                        // TRYFINAL logic (Algorithm 2)
+                        // Precondition 1: BlockNotarized is set (just happened)
+                        // Precondition 2: Node personally voted for this block
+                        // Precondition 3: BadWindow is not set
+                        if slot_state.voted_notar == Some(hash) && !slot_state.bad_window {
+                            slot_state.its_over = true;
+                            output.broadcast.push(Message::FinalVote { slot, voter: me });
+                        }
+                    }
+                }
+            }
+            Message::FinalVote { slot, voter } => {
+                // Aggregate FinalVotes
+                let slot_final_voters = self.final_vote_pool.entry(slot).or_default();
+                slot_final_voters.insert(voter);
+
+                let total_stake = stake(slot_final_voters.len());
+
+                // Check for SLOW-FINALIZATION (>= 60% stake)
+                if total_stake >= SLOW_FINALIZE_THRESHOLD {
+                    if let Some(notarized_hash) = self.slot_states.get(&slot).and_then(|ss| ss.block_notarized) {
+                        output.finalized = Some((slot, notarized_hash, false));
+                    }
+                }
+            }
+            Message::SkipVote { slot, voter } => {
+                // Skip votes indicate timeout and trigger BadWindow flag
+                let slot_state = self.slot_states.entry(slot).or_default();
+                slot_state.bad_window = true;
+
+                // Check for a SKIP CERTIFICATE (>= 60% stake)
+                let skip_voters = self.skip_vote_pool.entry(slot).or_default();
+                skip_voters.insert(voter);
+                if stake(skip_voters.len()) >= SKIP_THRESHOLD {
+                    output.skipped = Some(slot);
+                }
+            }
+        }
+        output
+    }
+
+    /// Whether a timeout for `slot` would still make this node skip it: it has not voted in
+    /// the slot, nor pruned it
+    pub(crate) fn can_time_out(&self, slot: Slot) -> bool {
+        slot > self.pruned_through && !self.slot_states.get(&slot).is_some_and(|slot_state| slot_state.voted)
+    }
+
+    /// Handle the expiry of validator `me`'s timer for `slot`
+    pub(crate) fn on_timeout(&mut self, me: ActorId, slot: Slot) -> NodeOutput {
+        let mut output = NodeOutput::default();
+        if slot <= self.pruned_through {
+            return output;
+        }
+        let slot_state = self.slot_states.entry(slot).or_default();
+
+        // TRYSKIP_WINDOW logic
+        if !slot_state.voted {
+            slot_state.voted = true;
+            slot_state.bad_window = true;
+            output.broadcast.push(Message::SkipVote { slot, voter: me });
+        }
+        output
+    }
+
+    /// Discard the vote pools and flags of `slot`, the finalized slot after the last one pruned,
+    /// returning its flags; messages for it are ignored from now on
+    pub(crate) fn prune(&mut self, slot: Slot) -> SlotState {
+        self.pruned_through = slot;
+        self.vote_pool.remove(&slot);
+        self.final_vote_pool.remove(&slot);
+        self.skip_vote_pool.remove(&slot);
+        self.slot_states.remove(&slot).unwrap_or_default()
+    }
+}
+
+impl FinalizedRecord for VotorState {
+    fn finalized_entries(&self) -> FinalizedHistory {
+        self.finalized_blocks.iter().map(|(slot, hash)| (0, *slot, *hash)).collect()
+    }
+
+    fn finalized_history(&self) -> &FinalizedHistory {
+        &self.finalized_history
+    }
+
+    fn finalized_history_mut(&mut self) -> &mut FinalizedHistory {
+        &mut self.finalized_history
+    }
+}
+
+impl ConsensusRecord for VotorState {
+    fn finalized_blocks(&self) -> BTreeSet<(Slot, Hash)> {
+        self.finalized_blocks.iter()
+            .filter(|(slot, _)| **slot > 0)
+            .map(|(slot, hash)| (*slot, *hash))
+            .collect()
+    }
+
+    fn notarized_blocks(&self) -> BTreeSet<(Slot, Hash)> {
+        self.node_states.iter()
+            .flat_map(|node| node.slot_states.iter())
+            .filter_map(|(slot, slot_state)| slot_state.block_notarized.map(|hash| (*slot, hash)))
+            .chain(self.pruned_notarized.iter().copied())
+            .collect()
+    }
+
+    fn fast_finalized_blocks(&self) -> BTreeSet<(Slot, Hash)> {
+        self.fast_finalized.clone()
+    }
+
+    fn certificates(&self) -> Vec<(Slot, Hash, Stake)> {
+        self.certificates.iter().copied().collect()
+    }
+
+    fn vote_stake(&self, slot: Slot, hash: Hash) -> Stake {
+        let pruned = self.pruned_notar_votes.get(&(slot, hash));
+        let voters = self.node_states.iter().enumerate()
+            .filter(|(id, node)| {
+                node.slot_states.get(&slot).and_then(|ss| ss.voted_notar) == Some(hash)
+                    || pruned.is_some_and(|voters| voters.contains(id))
+            })
+            .count();
+        equal_stake(voters, self.node_states.len())
+    }
+}
+
+impl Bounded for VotorState {
+    fn current_slot(&self) -> Slot {
+        self.current_slot
+    }
+
+    fn in_flight(&self) -> usize {
+        self.network.len()
+    }
+}
+
+impl TraceSummary for VotorState {
+    fn summary(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("current_slot", self.current_slot.to_string()),
+            ("in_flight", format!("{:?}", self.network.iter().map(|m| (m.dst, &m.msg)).collect::<Vec<_>>())),
+            ("certificates", format!("{:?}", self.certificates)),
+            ("skip_certificates", format!("{:?}", self.skip_certificates)),
+            ("finalized", format!("{:?}", self.finalized_blocks)),
+            ("fast_finalized", format!("{:?}", self.fast_finalized)),
+        ]
+    }
+}
+
+impl SequenceTrace for VotorModel {
+    fn participants(&self) -> usize {
+        self.honest_validators
+    }
+
+    fn sequence_events(&self, _state: &VotorState, action: &Action) -> Vec<SequenceEvent> {
+        match action {
+            Action::Propose { slot, proposer } => vec![SequenceEvent::Note {
+                actor: *proposer,
+                text: format!("propose slot {}", slot),
+            }],
+            Action::Deliver { msg } => vec![SequenceEvent::Message {
+                from: msg.msg.sender(),
+                to: msg.dst,
+                label: msg.msg.to_string(),
+            }],
+            Action::Timeout { slot, node_id } => vec![SequenceEvent::Note {
+                actor: *node_id,
+                text: format!("timeout slot {}", slot),
+            }],
+            Action::NetworkFault { fault } => vec![SequenceEvent::Note {
+                actor: fault.message().dst,
+                text: format!("network {}s {} from V{}", fault.label(), fault.message().msg, fault.message().msg.sender()),
+            }],
+            Action::Prune { slot, node_id } => vec![SequenceEvent::Note {
+                actor: *node_id,
+                text: format!("prune slot {}", slot),
+            }],
+            Action::LearnFinalization { slot, node_id } => vec![SequenceEvent::Note {
+                actor: *node_id,
+                text: format!("learn slot {} finalized", slot),
+            }],
         }
     }
 }
@@ -136,20 +535,42 @@ impl Model for VotorModel {
     type Action = Action;
 
     fn init_states(&self) -> Vec<Self::State> {
-        vec![VotorState::new(self.honest_validators)]
+        let mut state = VotorState::new(self.honest_validators);
+        state.network_faults = NetworkFaults::new(self.network_faults);
+        vec![state]
     }
 
     fn actions(&self, state: &Self::State, actions: &mut Vec<Self::Action>) {
-        // 1. Deliver any message in the network
+        // 0. Finalization certificates that lagged for FINALIZATION_GOSSIP_DELTA deliveries
+        //    reach the nodes still missing them before anything else happens
+        let overdue: Vec<&(Slot, ActorId)> = state.certificate_gossip.iter()
+            .filter(|(slot, _)| state.finalization_age.get(slot).is_some_and(|age| *age >= FINALIZATION_GOSSIP_DELTA))
+            .collect();
+        if !overdue.is_empty() {
+            for (slot, node_id) in overdue {
+                actions.push(Action::LearnFinalization { slot: *slot, node_id: *node_id });
+            }
+            return;
+        }
+
+        // 1. Deliver any message in the network, and any gossiped finalization certificate
         for msg in &state.network {
             actions.push(Action::Deliver { msg: msg.clone() });
         }
+        for (slot, node_id) in &state.certificate_gossip {
+            actions.push(Action::LearnFinalization { slot: *slot, node_id: *node_id });
+        }
+
+        // The network adversary drops, duplicates or delays a bounded number of messages
+        for fault in state.network_faults.faults(&state.network) {
+            actions.push(Action::NetworkFault { fault });
+        }
 
         // 2. Any node can propose a block for a future slot
         for proposer_id in 0..self.honest_validators {
             let last_finalized_slot = *state.finalized_blocks.keys().max().unwrap_or(&0);
             let next_slot = last_finalized_slot + 1;
-            if next_slot <= self.max_slot {
+            if next_slot <= self.max_slot && !state.proposals.contains(&(next_slot, proposer_id)) {
                 actions.push(Action::Propose {
                     slot: next_slot,
                     proposer: proposer_id,
@@ -157,14 +578,22 @@ impl Model for VotorModel {
             }
         }
         
-        // 3. Timeouts can occur for any non-finalized slot at any node
+        // 3. Timeouts can occur for any non-finalized slot at any node that has not voted in it
         for node_id in 0..self.honest_validators {
             for s in 1..=self.max_slot {
-                if !state.finalized_blocks.contains_key(&s) {
+                if !state.finalized_blocks.contains_key(&s) && state.node_states[node_id].can_time_out(s) {
                      actions.push(Action::Timeout { slot: s, node_id });
                 }
             }
         }
+
+        // 4. A node can prune the next slot it still holds state for once it knows it is finalized
+        for node_id in 0..self.honest_validators {
+            let slot = state.node_states[node_id].pruned_through + 1;
+            if state.finality_known.get(&slot).is_some_and(|known| known.contains(&node_id)) {
+                actions.push(Action::Prune { slot, node_id });
+            }
+        }
     }
 
     fn next_state(&self, last_state: &Self::State, action: Self::Action) -> Option<Self::State> {
@@ -173,146 +602,81 @@ impl Model for VotorModel {
 
         match action {
             Action::Propose { slot, proposer } => {
-                // Find a valid parent for the new block.
-                let parent_slot = slot - 1;
+                // Find a valid parent for the new block; slot 0 is genesis and has none.
+                let parent_slot = slot.checked_sub(1)?;
                 if let Some(parent_hash) = next_state.finalized_blocks.get(&parent_slot) {
-                    let block_hash = slot * 1000 + proposer as u64; // Deterministic hash based on slot and proposer
+                    if !next_state.proposals.insert((slot, proposer)) { return None; }
+                    let block_hash = block_hash(slot, proposer);
                     let block_msg = Message::Block {
                         slot,
                         hash: block_hash,
                         parent_hash: *parent_hash,
                     };
 
-                    // Broadcast block to all other nodes
+                    // Broadcast block to all nodes, the proposer included so it votes for its own block
                     for i in 0..self.honest_validators {
-                        if i != proposer {
-                            next_state.network.insert(MessageInTransit {
-                                dst: i,
-                                msg: block_msg.clone(),
-                            });
-                        }
+                        next_state.network.insert(MessageInTransit {
+                            dst: i,
+                            msg: block_msg.clone(),
+                        });
                     }
                 }
             }
+            Action::NetworkFault { fault } => {
+                if !next_state.network_faults.apply(&mut next_state.network, fault) { return None; }
+            }
             Action::Deliver { msg } => {
-                let recipient_id = msg.dst;
-                let mut node_state = node_states[recipient_id].clone();
-                
                 // Remove message from network
                 if !next_state.network.remove(&msg) { return None; }
-
-                match msg.msg {
-                    Message::Block { slot, hash, parent_hash } => {
-                        // TRYNOTAR logic (Algorithm 2)
-                        let slot_state = node_state.slot_states.entry(slot).or_default();
-                        let parent_slot = slot - 1;
-
-                        // Precondition 1: Voted flag is not set
-                        // Precondition 2: Parent is ready (we check against global finalized state for simplicity)
-                        if !slot_state.voted && next_state.finalized_blocks.get(&parent_slot) == Some(&parent_hash) {
-                            slot_state.voted = true;
-                            slot_state.voted_notar = Some(hash);
-
-                            // Broadcast NotarVote to all nodes
-                            for i in 0..self.honest_validators {
-                                next_state.network.insert(MessageInTransit {
-                                    dst: i,
-                                    msg: Message::NotarVote { slot, hash, voter: recipient_id },
-                                });
-                            }
-                        }
-                        node_states[recipient_id] = node_state;
-                    }
-                    Message::NotarVote { slot, hash, voter } => {
-                        // Add vote to the node's local pool
-                        let slot_votes = node_state.vote_pool.entry(slot).or_default();
-                        let block_voters = slot_votes.entry(hash).or_default();
-                        block_voters.insert(voter);
-
-                        let total_stake: Stake = block_voters.len() as u64 * STAKE_PER_VALIDATOR;
-
-                        // Check for FAST-FINALIZATION (>= 80% stake)
-                        if total_stake >= FAST_FINALIZE_THRESHOLD {
-                             next_state.finalized_blocks.insert(slot, hash);
-                        }
-
-                        // Check for NOTARIZATION (>= 60% stake)
-                        if total_stake >= NOTARIZE_THRESHOLD {
-                             let slot_state = node_state.slot_states.entry(slot).or_default();
-                             if slot_state.block_notarized.is_none() {
-                                slot_state.block_notarized = Some(hash);
-
-                                // TRYFINAL logic (Algorithm 2)
-                                // Precondition 1: BlockNotarized is set (just happened)
-                                // Precondition 2: Node personally voted for this block
-                                // Precondition 3: BadWindow is not set
-                                if slot_state.voted_notar == Some(hash) && !slot_state.bad_window {
-                                    slot_state.its_over = true;
-                                    // Broadcast FinalVote
-                                    for i in 0..self.honest_validators {
-                                        next_state.network.insert(MessageInTransit {
-                                            dst: i,
-                                            msg: Message::FinalVote { slot, voter: recipient_id }
-                                        });
-                                    }
-                                }
-                             }
-                        }
-                        node_states[recipient_id] = node_state;
-                    }
-                    Message::FinalVote { slot, voter } => {
-                        // Aggregate FinalVotes
-                        let slot_final_voters = node_state.final_vote_pool.entry(slot).or_default();
-                        slot_final_voters.insert(voter);
-                        
-                        let total_stake: Stake = slot_final_voters.len() as u64 * STAKE_PER_VALIDATOR;
-                        
-                        // Check for SLOW-FINALIZATION (>= 60% stake)
-                        if total_stake >= SLOW_FINALIZE_THRESHOLD {
-                            if let Some(notarized_hash) = node_state.slot_states.get(&slot).and_then(|ss| ss.block_notarized) {
-                                next_state.finalized_blocks.insert(slot, notarized_hash);
-                            }
-                        }
-                        node_states[recipient_id] = node_state;
-                    }
-                    Message::SkipVote { slot, voter: _ } => {
-                        // Handle skip votes - implement proper skip certificate logic
-                        // Skip votes indicate timeout and trigger BadWindow flag
-                         let slot_state = node_state.slot_states.entry(slot).or_default();
-                         slot_state.bad_window = true;
-                         node_states[recipient_id] = node_state;
-                    }
+                next_state.network_faults.record_delivery(&msg);
+
+                let stake = |voters| equal_stake(voters, self.honest_validators);
+                let output = node_states[msg.dst].on_message(msg.dst, &msg.msg, &next_state.finalized_blocks, stake);
+                let finalized = output.finalized;
+                next_state.apply(output, self.honest_validators);
+                if let Some((slot, _, _)) = finalized {
+                    next_state.learn_finalization(msg.dst, slot, self.honest_validators);
                 }
+                next_state.age_finalizations();
+            }
+            Action::LearnFinalization { slot, node_id } => {
+                if !next_state.certificate_gossip.contains(&(slot, node_id)) { return None; }
+                next_state.learn_finalization(node_id, slot, self.honest_validators);
+                next_state.age_finalizations();
             }
             Action::Timeout { slot, node_id } => {
-                let mut node_state = node_states[node_id].clone();
-                let slot_state = node_state.slot_states.entry(slot).or_default();
-
-                // TRYSKIP_WINDOW logic
-                if !slot_state.voted {
-                    slot_state.voted = true;
-                    slot_state.bad_window = true;
-
-                    // Broadcast SkipVote
-                    for i in 0..self.honest_validators {
-                        next_state.network.insert(MessageInTransit {
-                            dst: i,
-                            msg: Message::SkipVote { slot, voter: node_id },
-                        });
-                    }
+                let output = node_states.get_mut(node_id)?.on_timeout(node_id, slot);
+                next_state.apply(output, self.honest_validators);
+            }
+            Action::Prune { slot, node_id } => {
+                let node = node_states.get_mut(node_id)?;
+                if slot != node.pruned_through + 1
+                    || !next_state.finality_known.get(&slot).is_some_and(|known| known.contains(&node_id))
+                {
+                    return None;
+                }
+                let pruned = node.prune(slot);
+                if let Some(hash) = pruned.block_notarized {
+                    next_state.pruned_notarized.insert((slot, hash));
+                }
+                if let Some(hash) = pruned.voted_notar {
+                    next_state.pruned_notar_votes.entry((slot, hash)).or_default().insert(node_id);
                 }
-                node_states[node_id] = node_state;
             }
         }
         
         next_state.node_states = node_states;
+        next_state.record_finalized();
         Some(next_state)
     }
 
-    /// Defines the property we want to check: No two different blocks are ever
-    /// finalized for the same slot.
+    /// Defines the properties we want to check: No two different blocks are ever
+    /// finalized for the same slot, no slot is both notarized and skip-certified, no finalization
+    /// is ever rolled back, pruning never loses a certificate, every node learns of a
+    /// finalization within a bounded number of deliveries, plus the derived invariants
+    /// relating votes, certificates and finalizations.
     fn properties(&self) -> Vec<Property<Self>> {
-        vec![Property::<Self>::always("safety", |_, state| {
+        let mut properties = vec![Property::<Self>::always("safety", |_, state| {
             let mut observed_slots = BTreeMap::new();
             for (slot, hash) in &state.finalized_blocks {
                 if let Some(existing_hash) = observed_slots.get(slot) {
@@ -324,6 +688,144 @@ impl Model for VotorModel {
                 }
             }
             true
-        })]
+        }), Property::<Self>::always("notar_skip_exclusion", |_, state| {
+            // A node that timed out skips the slot, yet NotarVotes for its block may still be
+            // in flight: they must never complete a certificate the skip votes excluded
+            state.skip_certificates.iter().all(|slot| {
+                !state.finalized_blocks.contains_key(slot) && !state.certificates.iter().any(|(s, _, _)| s == slot)
+            })
+        }), Property::<Self>::always("no_certificate_regression", |_, state| {
+            state.check_no_certificate_regression()
+        }), Property::<Self>::always("finality_knowledge_propagation", |model, state| {
+            // Pruning and leader windows act on finalized slots, so every node must learn of a
+            // finalization soon after the first one does. Each forced delivery closes one gap,
+            // and gaps of every slot may queue up.
+            let bound = FINALIZATION_GOSSIP_DELTA + model.honest_validators.saturating_sub(1) as u64 * model.max_slot;
+            state.finalization_age.values().all(|age| *age <= bound)
+        }), invariants::no_finalized_rollback_property()];
+        properties.extend(invariants::derived_invariants());
+        properties
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pruning_keeps_certificates() {
+        let model = VotorModel { honest_validators: 3, max_slot: 1, network_faults: 0 };
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, Action::Propose { slot: 1, proposer: 0 }).unwrap();
+        while !state.finalized_blocks.contains_key(&1) {
+            let msg = state.network.iter().next().cloned().unwrap();
+            state = model.next_state(&state, Action::Deliver { msg }).unwrap();
+        }
+        while let Some((slot, node_id)) = state.certificate_gossip.iter().next().copied() {
+            state = model.next_state(&state, Action::LearnFinalization { slot, node_id }).unwrap();
+        }
+
+        let prune = Action::Prune { slot: 1, node_id: 1 };
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert!(actions.contains(&prune));
+        state = model.next_state(&state, prune.clone()).unwrap();
+        assert!(model.next_state(&state, prune).is_none());
+        assert!(state.node_states[1].slot_states.is_empty() && state.node_states[1].vote_pool.is_empty());
+
+        // Votes still in flight for the pruned slot leave no trace at the node that pruned it
+        while let Some(msg) = state.network.iter().next().cloned() {
+            state = model.next_state(&state, Action::Deliver { msg }).unwrap();
+        }
+        assert!(state.node_states[1].vote_pool.is_empty() && state.node_states[1].final_vote_pool.is_empty());
+        assert!(state.check_no_certificate_regression());
+        assert!(invariants::finalized_implies_notarized(&state));
+        assert!(invariants::certificate_stake_backed(&state));
+        assert!(invariants::no_finalized_rollback(&state));
+    }
+
+    #[test]
+    fn test_overwritten_finalization_is_a_rollback() {
+        let model = VotorModel { honest_validators: 3, max_slot: 1, network_faults: 0 };
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, Action::Propose { slot: 1, proposer: 0 }).unwrap();
+        while !state.finalized_blocks.contains_key(&1) {
+            let msg = state.network.iter().next().cloned().unwrap();
+            state = model.next_state(&state, Action::Deliver { msg }).unwrap();
+        }
+        assert!(invariants::no_finalized_rollback(&state));
+
+        // A later finalization of another block overwrites the slot's entry: the state alone
+        // shows a single finalized block, but its history still holds the first one
+        state.finalized_blocks.insert(1, block_hash(1, 1));
+        state.record_finalized();
+        assert!(!invariants::no_finalized_rollback(&state));
+    }
+
+    #[test]
+    fn test_finalization_gossip_forced_once_overdue() {
+        let model = VotorModel { honest_validators: 3, max_slot: 1, network_faults: 0 };
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, Action::Propose { slot: 1, proposer: 0 }).unwrap();
+        while state.certificate_gossip.is_empty() {
+            let msg = state.network.iter().next().cloned().unwrap();
+            state = model.next_state(&state, Action::Deliver { msg }).unwrap();
+        }
+        // Only the node that finalized knows, and may prune; the others are sent the certificate
+        let known = state.finality_known[&1].clone();
+        assert_eq!(known.len(), 1);
+        assert!(state.certificate_gossip.iter().all(|(slot, node)| *slot == 1 && !known.contains(node)));
+        let ignorant = state.certificate_gossip.iter().next().unwrap().1;
+        assert!(model.next_state(&state, Action::Prune { slot: 1, node_id: ignorant }).is_none());
+
+        // Once the certificate is overdue, delivering it is all that may happen
+        state.finalization_age.insert(1, FINALIZATION_GOSSIP_DELTA);
+        let mut actions = Vec::new();
+        model.actions(&state, &mut actions);
+        assert_eq!(actions.len(), state.certificate_gossip.len());
+        assert!(actions.iter().all(|action| matches!(action, Action::LearnFinalization { .. })));
+        for action in actions {
+            state = model.next_state(&state, action).unwrap();
+        }
+        assert!(state.certificate_gossip.is_empty() && state.finalization_age.is_empty());
+        assert!((model.property("finality_knowledge_propagation").condition)(&model, &state));
+    }
+
+    #[test]
+    fn test_fast_finalization_agrees_with_slow_path() {
+        // The proposer does not vote for its own block, so four of five voters take the fast path
+        let model = VotorModel { honest_validators: 5, max_slot: 1, network_faults: 0 };
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, Action::Propose { slot: 1, proposer: 0 }).unwrap();
+        while let Some(msg) = state.network.iter().next().cloned() {
+            state = model.next_state(&state, Action::Deliver { msg }).unwrap();
+        }
+        assert!(state.fast_finalized.contains(&(1, block_hash(1, 0))));
+        assert!(invariants::fast_finalized_implies_notarized(&state));
+        assert!(invariants::fast_finalization_uncontested(&state));
+
+        // A second notarization in the slot would let FinalVotes finalize a conflicting block
+        state.pruned_notarized.insert((1, block_hash(1, 1)));
+        assert!(!invariants::fast_finalization_uncontested(&state));
+    }
+
+    #[test]
+    fn test_skip_certificate_excludes_late_notarization() {
+        let model = VotorModel { honest_validators: 3, max_slot: 1, network_faults: 0 };
+        let property = model.property("notar_skip_exclusion");
+        let mut state = model.init_states().remove(0);
+        state = model.next_state(&state, Action::Propose { slot: 1, proposer: 0 }).unwrap();
+        for node_id in [0, 1] {
+            state = model.next_state(&state, Action::Timeout { slot: 1, node_id }).unwrap();
+        }
+        // The block reaches node 2 alone, whose NotarVote cannot outweigh the two skips
+        while let Some(msg) = state.network.iter().next().cloned() {
+            state = model.next_state(&state, Action::Deliver { msg }).unwrap();
+        }
+        assert!(state.skip_certificates.contains(&1));
+        assert!(state.certificates.is_empty() && !state.finalized_blocks.contains_key(&1));
+        assert!((property.condition)(&model, &state));
+
+        state.certificates.insert((1, block_hash(1, 0), equal_stake(2, 3)));
+        assert!(!(property.condition)(&model, &state));
+    }
+}
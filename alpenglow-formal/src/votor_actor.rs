@@ -0,0 +1,360 @@
+//! Votor as a `stateright::actor`, runnable both in the checker and on UDP sockets.
+//! Each validator is an actor reacting to messages and skip timers with the same
+//! [`NodeState`] handlers [`VotorModel`](crate::votor::VotorModel) steps its nodes with, so the
+//! protocol logic that is model-checked is the logic that runs. [`actor_model`] checks a group
+//! of actors, [`spawn_localhost`] runs one validator per localhost UDP port for end-to-end
+//! smoke runs.
+//!
+//! Unlike the model, where any validator may propose the next slot, slots rotate through the
+//! validators, and every validator tracks its own finalized blocks rather than a global record.
+//! A validator moves on to the next slot once it sees a slot finalized or skip-certified; after a
+//! skipped slot, the next block builds on the block the skipped slot would have.
+
+use crate::bounds::Bounded;
+use crate::votor::{block_hash, equal_stake, Message, NodeOutput, NodeState};
+use stateright::actor::{spawn, Actor, ActorModel, ActorModelState, Id, Network, Out};
+use stateright::Expectation;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::{Ipv4Addr, SocketAddrV4};
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Time a spawned validator waits for a slot to be finalized before voting to skip it
+pub const SPAWN_SKIP_TIMEOUT: Range<Duration> = Duration::from_millis(800)..Duration::from_millis(1200);
+
+/// Time a spawned validator waits before starting the first slot. Each validator binds its port
+/// on its own thread, so a block sent straight away could reach ports nobody listens on yet.
+pub const SPAWN_START_DELAY: Range<Duration> = Duration::from_millis(200)..Duration::from_millis(200);
+
+/// Timers of a validator
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VotorTimer {
+    /// The first slot starts
+    Start,
+    /// Skip timer of a slot
+    Skip(u64),
+}
+
+/// One validator
+#[derive(Clone, Debug)]
+pub struct VotorActor {
+    /// Every validator, this one included, in validator index order
+    pub peers: Vec<Id>,
+    /// Last slot proposed
+    pub max_slot: u64,
+    /// Delay before a slot's skip timer fires
+    pub timeout: Range<Duration>,
+    /// Delay before the first slot starts; `None` starts it as soon as the validator does
+    pub start_delay: Option<Range<Duration>>,
+    /// Highest slot any validator finalized, shared with whoever spawned the validators
+    pub progress: Option<Arc<AtomicU64>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VotorActorState {
+    node: NodeState,
+    /// Blocks this validator saw finalized, including genesis. Map<Slot, Hash>.
+    finalized: BTreeMap<u64, u64>,
+    /// Block a proposal for the slot after each finalized or skipped slot builds on: the
+    /// finalized block, or for a skipped slot the block it would have built on. Map<Slot, Hash>.
+    parents: BTreeMap<u64, u64>,
+    /// Slots this validator saw skip-certified
+    skipped: BTreeSet<u64>,
+    fast_finalized: BTreeSet<(u64, u64)>,
+    /// Certificates formed, with the stake they were formed with
+    certificates: BTreeSet<(u64, u64, u64)>,
+}
+
+impl VotorActor {
+    /// Validator index of the actor `id`
+    fn index(&self, id: Id) -> usize {
+        self.peers.iter().position(|peer| *peer == id).expect("every actor is one of its peers")
+    }
+
+    fn leader(&self, slot: u64) -> usize {
+        slot as usize % self.peers.len()
+    }
+
+    /// Start `slot` once its parent is ready: propose it when leading it, and arm its skip timer
+    fn start_slot(&self, id: Id, state: &VotorActorState, slot: u64, o: &mut Out<Self>) {
+        if slot > self.max_slot {
+            return;
+        }
+        let me = self.index(id);
+        if self.leader(slot) == me {
+            let block = Message::Block { slot, hash: block_hash(slot, me), parent_hash: state.parents[&(slot - 1)] };
+            // The leader votes for its own block like every other validator, so it sends itself a copy
+            o.broadcast(&self.peers, &block);
+        }
+        o.set_timer(VotorTimer::Skip(slot), self.timeout.clone());
+    }
+
+    /// Leave `slot`, finalized or skipped, with `parent` as the block the next slot builds on
+    fn end_slot(&self, id: Id, state: &mut VotorActorState, slot: u64, parent: u64, o: &mut Out<Self>) {
+        if state.parents.insert(slot, parent).is_some() {
+            return;
+        }
+        o.cancel_timer(VotorTimer::Skip(slot));
+        if state.skipped.contains(&(slot + 1)) {
+            // The next slot was skip-certified before this one ended
+            self.end_slot(id, state, slot + 1, parent, o);
+        } else {
+            self.start_slot(id, state, slot + 1, o);
+        }
+    }
+
+    /// Send the votes of `output` and record its certificates, finalization and skip
+    fn apply(&self, id: Id, state: &mut VotorActorState, output: NodeOutput, o: &mut Out<Self>) {
+        for msg in &output.broadcast {
+            o.broadcast(&self.peers, msg);
+        }
+        state.certificates.extend(output.certificates);
+        if let Some((slot, hash, fast)) = output.finalized {
+            let first = state.finalized.insert(slot, hash).is_none();
+            if fast {
+                state.fast_finalized.insert((slot, hash));
+            }
+            if first {
+                tracing::info!("validator {} finalized slot {} with block {}{}",
+                               self.index(id), slot, hash, if fast { " on the fast path" } else { "" });
+                if let Some(progress) = &self.progress {
+                    progress.fetch_max(slot, Ordering::Relaxed);
+                }
+                self.end_slot(id, state, slot, hash, o);
+            }
+        }
+        if let Some(slot) = output.skipped {
+            if state.skipped.insert(slot) {
+                tracing::info!("validator {} skipped slot {}", self.index(id), slot);
+            }
+            // Until the previous slot ends, the block a proposal after the skipped slot builds on is unknown
+            if let Some(&parent) = state.parents.get(&(slot - 1)) {
+                self.end_slot(id, state, slot, parent, o);
+            }
+        }
+    }
+}
+
+impl Actor for VotorActor {
+    type Msg = Message;
+    type Timer = VotorTimer;
+    type Random = ();
+    type State = VotorActorState;
+    type Storage = ();
+
+    fn on_start(&self, id: Id, _storage: &Option<Self::Storage>, o: &mut Out<Self>) -> Self::State {
+        let state = VotorActorState {
+            node: NodeState::default(),
+            finalized: BTreeMap::from([(0, 0)]), // Genesis block: slot 0, hash 0
+            parents: BTreeMap::from([(0, 0)]),
+            skipped: BTreeSet::new(),
+            fast_finalized: BTreeSet::new(),
+            certificates: BTreeSet::new(),
+        };
+        match &self.start_delay {
+            Some(delay) => o.set_timer(VotorTimer::Start, delay.clone()),
+            None => self.start_slot(id, &state, 1, o),
+        }
+        state
+    }
+
+    fn on_msg(&self, id: Id, state: &mut Cow<Self::State>, _src: Id, msg: Self::Msg, o: &mut Out<Self>) {
+        let state = state.to_mut();
+        let validators = self.peers.len();
+        let output = state.node.on_message(self.index(id), &msg, &state.parents, |voters| equal_stake(voters, validators));
+        self.apply(id, state, output, o);
+    }
+
+    fn on_timeout(&self, id: Id, state: &mut Cow<Self::State>, timer: &Self::Timer, o: &mut Out<Self>) {
+        let slot = match *timer {
+            VotorTimer::Start => return self.start_slot(id, state, 1, o),
+            VotorTimer::Skip(slot) => slot,
+        };
+        if state.parents.contains_key(&slot) {
+            return;
+        }
+        let state = state.to_mut();
+        let output = state.node.on_timeout(self.index(id), slot);
+        self.apply(id, state, output, o);
+    }
+}
+
+impl Bounded for ActorModelState<VotorActor> {
+    fn current_slot(&self) -> u64 {
+        self.actor_states.iter().filter_map(|state| state.finalized.keys().max()).max().copied().unwrap_or(0)
+    }
+
+    fn in_flight(&self) -> usize {
+        self.network.len()
+    }
+}
+
+/// No two validators finalize different blocks in the same slot
+fn no_conflicting_finalization(state: &ActorModelState<VotorActor>) -> bool {
+    let mut observed = BTreeMap::new();
+    state.actor_states.iter()
+        .flat_map(|state| &state.finalized)
+        .all(|(slot, hash)| *observed.entry(*slot).or_insert(*hash) == *hash)
+}
+
+/// `validators` Votor actors exchanging messages over an unordered network
+pub fn actor_model(validators: usize, max_slot: u64) -> ActorModel<VotorActor> {
+    let peers: Vec<Id> = (0..validators).map(Id::from).collect();
+    // The checker fires timers in any order, whatever their delay
+    let actor = VotorActor { peers, max_slot, timeout: Duration::ZERO..Duration::ZERO, start_delay: None, progress: None };
+    ActorModel::new((), ())
+        .actors((0..validators).map(|_| actor.clone()))
+        .init_network(Network::new_unordered_nonduplicating([]))
+        .property(Expectation::Always, "no_conflicting_finalization", |_, state| no_conflicting_finalization(state))
+        .property(Expectation::Sometimes, "block_finalized", |_, state| {
+            state.actor_states.iter().any(|state| state.finalized.len() > 1)
+        })
+}
+
+/// Encode a message for the wire as its kind followed by its fields
+pub fn encode(msg: &Message) -> Result<Vec<u8>, String> {
+    let text = match msg {
+        Message::Block { slot, hash, parent_hash } => format!("block {} {} {}", slot, hash, parent_hash),
+        Message::NotarVote { slot, hash, voter } => format!("notar {} {} {}", slot, hash, voter),
+        Message::FinalVote { slot, voter } => format!("final {} {}", slot, voter),
+        Message::SkipVote { slot, voter } => format!("skip {} {}", slot, voter),
+    };
+    Ok(text.into_bytes())
+}
+
+/// Decode a message encoded by [`encode`]
+pub fn decode(bytes: &[u8]) -> Result<Message, String> {
+    let text = std::str::from_utf8(bytes).map_err(|e| e.to_string())?;
+    let mut words = text.split_whitespace();
+    let kind = words.next().ok_or("empty message")?;
+    let fields = words
+        .map(|word| word.parse::<u64>().map_err(|_| format!("invalid number '{}'", word)))
+        .collect::<Result<Vec<_>, _>>()?;
+    match (kind, fields.as_slice()) {
+        ("block", &[slot, hash, parent_hash]) => Ok(Message::Block { slot, hash, parent_hash }),
+        ("notar", &[slot, hash, voter]) => Ok(Message::NotarVote { slot, hash, voter: voter as usize }),
+        ("final", &[slot, voter]) => Ok(Message::FinalVote { slot, voter: voter as usize }),
+        ("skip", &[slot, voter]) => Ok(Message::SkipVote { slot, voter: voter as usize }),
+        _ => Err(format!("malformed message '{}'", text)),
+    }
+}
+
+/// Validators spawned by [`spawn_localhost`]
+pub struct LocalValidators {
+    /// Finishes early, with the reason, only if a validator failed, as when its port is taken
+    pub handle: JoinHandle<Result<(), String>>,
+    progress: Arc<AtomicU64>,
+}
+
+impl LocalValidators {
+    /// Highest slot any validator finalized so far; 0, genesis, until one finalizes a block
+    pub fn highest_finalized(&self) -> u64 {
+        self.progress.load(Ordering::Relaxed)
+    }
+}
+
+/// Spawn `validators` actors on consecutive localhost UDP ports from `base_port`, in the
+/// background. The actors run until the process exits.
+pub fn spawn_localhost(validators: usize, max_slot: u64, base_port: u16, timeout: Range<Duration>) -> LocalValidators {
+    let peers: Vec<Id> = (0..validators)
+        .map(|i| Id::from(SocketAddrV4::new(Ipv4Addr::LOCALHOST, base_port + i as u16)))
+        .collect();
+    let progress = Arc::new(AtomicU64::new(0));
+    let actor = VotorActor {
+        peers: peers.clone(),
+        max_slot,
+        timeout,
+        start_delay: Some(SPAWN_START_DELAY),
+        progress: Some(progress.clone()),
+    };
+    let handle = thread::spawn(move || {
+        // Blocks for as long as the actors run, which is until one of them panics
+        spawn(encode, decode, |_| Ok(Vec::new()), |_| Ok(()),
+              peers.into_iter().map(|id| (id, actor.clone())).collect())
+            .map_err(|panic| match (panic.downcast_ref::<String>(), panic.downcast_ref::<&str>()) {
+                (Some(message), _) => message.clone(),
+                (None, Some(message)) => message.to_string(),
+                (None, None) => "a validator panicked".to_string(),
+            })
+    });
+    LocalValidators { handle, progress }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stateright::actor::Command;
+    use stateright::{Checker, Model};
+
+    /// Messages `out` sends to `dst`
+    fn sent_to(out: Out<VotorActor>, dst: Id) -> Vec<Message> {
+        out.into_iter()
+            .filter_map(|command| match command {
+                Command::Send(to, msg) if to == dst => Some(msg),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_actor_model_properties() {
+        let checker = actor_model(3, 1).checker().spawn_bfs().join();
+        checker.assert_properties();
+        let finalized = checker.discovery("block_finalized").unwrap().last_state().clone();
+        assert_eq!(finalized.current_slot(), 1);
+    }
+
+    #[test]
+    fn test_skip_starts_next_slot() {
+        let peers: Vec<Id> = (0..3).map(Id::from).collect();
+        let actor = VotorActor { peers: peers.clone(), max_slot: 2, timeout: Duration::ZERO..Duration::ZERO, start_delay: None, progress: None };
+        // Validator 2 leads slot 2 and sees slot 1 skip-certified
+        let me = peers[2];
+        let mut state = Cow::Owned(actor.on_start(me, &None, &mut Out::new()));
+        actor.on_timeout(me, &mut state, &VotorTimer::Skip(1), &mut Out::new());
+        let mut out = Out::new();
+        for voter in [0, 2] {
+            actor.on_msg(me, &mut state, peers[voter], Message::SkipVote { slot: 1, voter }, &mut out);
+        }
+        // It proposes slot 2 on genesis, sending itself the block too, and votes for it
+        let block = Message::Block { slot: 2, hash: block_hash(2, 2), parent_hash: 0 };
+        assert_eq!(sent_to(out, me), vec![block.clone()]);
+        let mut out = Out::new();
+        actor.on_msg(me, &mut state, me, block, &mut out);
+        assert_eq!(sent_to(out, peers[0]), vec![Message::NotarVote { slot: 2, hash: block_hash(2, 2), voter: 2 }]);
+    }
+
+    #[test]
+    fn test_start_delay() {
+        let peers: Vec<Id> = (0..3).map(Id::from).collect();
+        let actor = VotorActor { peers: peers.clone(), max_slot: 1, timeout: SPAWN_SKIP_TIMEOUT, start_delay: Some(SPAWN_START_DELAY), progress: None };
+        // Validator 1 leads slot 1, but proposes only once the start timer fires
+        let mut out = Out::new();
+        let mut state = Cow::Owned(actor.on_start(peers[1], &None, &mut out));
+        let commands: Vec<_> = out.into_iter().collect();
+        assert!(matches!(commands.as_slice(), [Command::SetTimer(VotorTimer::Start, delay)] if *delay == SPAWN_START_DELAY));
+        let mut out = Out::new();
+        actor.on_timeout(peers[1], &mut state, &VotorTimer::Start, &mut out);
+        assert_eq!(sent_to(out, peers[0]), vec![Message::Block { slot: 1, hash: block_hash(1, 1), parent_hash: 0 }]);
+    }
+
+    #[test]
+    fn test_wire_encoding() {
+        let messages = [
+            Message::Block { slot: 2, hash: 2001, parent_hash: 1000 },
+            Message::NotarVote { slot: 2, hash: 2001, voter: 0 },
+            Message::FinalVote { slot: 2, voter: 1 },
+            Message::SkipVote { slot: 3, voter: 2 },
+        ];
+        for msg in messages {
+            assert_eq!(decode(&encode(&msg).unwrap()), Ok(msg));
+        }
+        assert!(decode(b"final 2").is_err());
+        assert!(decode(b"vote 1 2").is_err());
+        assert!(decode(b"skip 1 x").is_err());
+    }
+}